@@ -10,7 +10,7 @@ use std::io::Cursor;
 use claude_snatch::export::{
     ExportOptions, Exporter, JsonExporter, MarkdownExporter, TextExporter,
 };
-use claude_snatch::parser::JsonlParser;
+use claude_snatch::parser::{JsonlParser, ParseFilter};
 use claude_snatch::reconstruction::Conversation;
 
 /// Sample JSONL data for benchmarking.
@@ -74,6 +74,15 @@ fn bench_parser(c: &mut Criterion) {
             });
         });
 
+        group.bench_with_input(BenchmarkId::new("parse_user", size), &data, |b, data| {
+            b.iter(|| {
+                let filter = ParseFilter::new().with_types(["user"]);
+                let mut parser = JsonlParser::new().with_filter(filter);
+                let entries = parser.parse_str(data);
+                black_box(entries)
+            });
+        });
+
         group.bench_with_input(BenchmarkId::new("parse_lenient", size), &data, |b, data| {
             b.iter(|| {
                 let mut parser = JsonlParser::new().with_lenient(true);
@@ -86,6 +95,33 @@ fn bench_parser(c: &mut Criterion) {
     group.finish();
 }
 
+/// File parsing through `BufReader` versus the SIMD fast path. Without the
+/// `simd` feature both take the same path; run with
+/// `cargo bench --features simd -- parse_file` to compare.
+fn bench_parse_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_file");
+
+    for size in [1000, 10000, 100_000].iter() {
+        let data = generate_sample_jsonl(*size);
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &data).unwrap();
+
+        group.throughput(Throughput::Bytes(data.len() as u64));
+
+        for (name, simd) in [("serde_json", false), ("simd", true)] {
+            group.bench_with_input(BenchmarkId::new(name, size), file.path(), |b, path| {
+                b.iter(|| {
+                    let mut parser = JsonlParser::new().with_simd(simd);
+                    let entries = parser.parse_file(path);
+                    black_box(entries)
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
 fn bench_reconstruction(c: &mut Criterion) {
     let mut group = c.benchmark_group("reconstruction");
 
@@ -152,5 +188,11 @@ fn bench_export(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_parser, bench_reconstruction, bench_export);
+criterion_group!(
+    benches,
+    bench_parser,
+    bench_parse_file,
+    bench_reconstruction,
+    bench_export
+);
 criterion_main!(benches);
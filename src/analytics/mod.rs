@@ -7,6 +7,7 @@
 //! - Cost estimation
 //! - Session duration analysis
 //! - Historical cost tracking and persistence
+//! - Per-session snapshots for incremental global stats
 //!
 //! # Example
 //!
@@ -44,10 +45,13 @@
 //! ```
 
 pub mod history;
+pub mod prometheus;
+pub mod snapshot;
 
-use chrono::{DateTime, Datelike, Duration, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike, Utc, Weekday};
 use indexmap::IndexMap;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 
 use crate::model::{usage::AggregatedUsage, AssistantMessage, ContentBlock, LogEntry};
 use crate::reconstruction::Conversation;
@@ -86,6 +90,12 @@ pub struct SessionAnalytics {
     pub file_stats: FileModificationStats,
     /// Subagent (Task) usage, mined from Task tool results.
     pub subagent_stats: SubagentStats,
+    /// Usage recorded on sidechain entries.
+    pub sidechain: SidechainStats,
+    /// Work left on abandoned edit/retry branches.
+    pub abandoned: AbandonedBranchStats,
+    /// Mid-conversation model switches.
+    pub model_switches: ModelSwitchStats,
 }
 
 impl SessionAnalytics {
@@ -99,6 +109,8 @@ impl SessionAnalytics {
     /// Process a conversation to extract analytics.
     pub fn process_conversation(&mut self, conversation: &Conversation) {
         self.branch_count = conversation.branch_points().len();
+        self.abandoned = AbandonedBranchStats::from_conversation(conversation);
+        self.model_switches = ModelSwitchStats::from_conversation(conversation);
 
         for node in conversation.nodes().values() {
             self.process_entry(&node.entry);
@@ -117,6 +129,7 @@ impl SessionAnalytics {
 
         // Calculate cost
         self.usage.calculate_cost();
+        self.sidechain.usage.calculate_cost();
     }
 
     /// Aggregate token usage with one entry per distinct assistant `message.id`.
@@ -129,6 +142,10 @@ impl SessionAnalytics {
         // (model, message.id) -> (deduped usage, first timestamp) for that turn.
         let mut deduped: IndexMap<(String, String), (crate::model::usage::Usage, DateTime<Utc>)> =
             IndexMap::new();
+        // `merge_max` leaves open-ended payload fields behind, so the reported
+        // thinking split is deduplicated alongside it.
+        let mut thinking: IndexMap<(String, String), u64> = IndexMap::new();
+        let mut sidechain_turns: HashSet<(String, String)> = HashSet::new();
 
         for node in conversation.nodes().values() {
             if let LogEntry::Assistant(assistant) = &node.entry {
@@ -144,11 +161,35 @@ impl SessionAnalytics {
                 });
                 deduped_usage.merge_max(usage);
                 *first_timestamp = (*first_timestamp).min(assistant.timestamp);
+                if assistant.is_sidechain {
+                    sidechain_turns.insert((
+                        assistant.message.model.clone(),
+                        assistant.message.id.clone(),
+                    ));
+                }
+                if let Some(tokens) = usage.thinking_tokens() {
+                    let slot = thinking
+                        .entry((
+                            assistant.message.model.clone(),
+                            assistant.message.id.clone(),
+                        ))
+                        .or_insert(0);
+                    *slot = (*slot).max(tokens);
+                }
             }
         }
 
-        for ((model, _id), (usage, observed_at)) in &deduped {
-            self.usage.add_usage_at(model, usage, *observed_at);
+        for (key, (usage, observed_at)) in &deduped {
+            self.usage.add_usage_at(&key.0, usage, *observed_at);
+            if sidechain_turns.contains(key) {
+                self.sidechain
+                    .usage
+                    .add_usage_at(&key.0, usage, *observed_at);
+            }
+            if let Some(&tokens) = thinking.get(key) {
+                self.thinking_stats
+                    .record_turn(&key.0, tokens, usage.output_tokens, *observed_at);
+            }
         }
     }
 
@@ -291,11 +332,25 @@ impl SessionAnalytics {
 
         let timestamp = Some(assistant.timestamp);
 
+        if assistant.is_sidechain {
+            self.sidechain.assistant_entries += 1;
+            if let Some(agent_id) = &assistant.agent_id {
+                *self
+                    .sidechain
+                    .agent_ids
+                    .entry(agent_id.clone())
+                    .or_insert(0) += 1;
+            }
+        }
+
         // Process content blocks
         for content in &assistant.message.content {
             match content {
                 ContentBlock::ToolUse(tool_use) => {
                     self.message_counts.tool_uses += 1;
+                    if assistant.is_sidechain {
+                        self.sidechain.tool_uses += 1;
+                    }
                     *self.tool_counts.entry(tool_use.name.clone()).or_insert(0) += 1;
                     self.usage.record_tool(&tool_use.name);
 
@@ -474,6 +529,7 @@ impl SessionAnalytics {
             cache_hit_rate: self.cache_efficiency(),
             estimated_cost: self.usage.estimated_cost,
             unpriced_models: self.usage.unpriced_models.clone(),
+            cache_savings: self.usage.cache_savings().map(|(savings, _)| savings),
             branch_count: self.branch_count,
             primary_model: self.primary_model().map(String::from),
             subagent_count: self.subagent_stats.count,
@@ -496,7 +552,7 @@ impl SessionAnalytics {
 }
 
 /// Message counts by type.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MessageCounts {
     /// User messages.
     pub user: usize,
@@ -565,13 +621,202 @@ pub struct SubagentStats {
     pub total_duration_ms: u64,
 }
 
-/// Thinking block statistics.
+/// Usage recorded on sidechain entries (`isSidechain: true`).
+///
+/// Subagent transcripts mark every entry as sidechain, and older Claude Code
+/// versions also wrote subagent turns inline in the parent transcript. These
+/// totals are a subset of the session's own usage and counts.
+#[derive(Debug, Clone, Default)]
+pub struct SidechainStats {
+    /// Usage of sidechain assistant turns, once per `message.id`.
+    pub usage: AggregatedUsage,
+    /// Sidechain assistant entries.
+    pub assistant_entries: usize,
+    /// Tool invocations made on sidechain entries.
+    pub tool_uses: usize,
+    /// Sidechain assistant entries by `agentId`, when the log records it.
+    pub agent_ids: IndexMap<String, usize>,
+}
+
+/// Tokens, cost, and tool calls attributed to one agent bucket.
 #[derive(Debug, Clone, Default)]
+pub struct AgentUsage {
+    /// Transcripts contributing to this bucket.
+    pub sessions: usize,
+    /// Work tokens (input + cache creation + output).
+    pub tokens: u64,
+    /// Estimated cost over priced models.
+    pub estimated_cost: Option<f64>,
+    /// Tool invocations.
+    pub tool_calls: usize,
+}
+
+impl AgentUsage {
+    fn add(&mut self, tokens: u64, cost: Option<f64>, tool_calls: usize) {
+        self.tokens += tokens;
+        self.tool_calls += tool_calls;
+        if let Some(cost) = cost {
+            *self.estimated_cost.get_or_insert(0.0) += cost;
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.sessions += other.sessions;
+        self.add(other.tokens, other.estimated_cost, other.tool_calls);
+    }
+}
+
+/// Parent-session vs subagent usage, with subagents split by agent type.
+///
+/// Subagent transcripts are attributed whole to their agent type (from the
+/// `agent-*.meta.json` sidecar). Inside parent transcripts, sidechain turns
+/// written inline by older Claude Code versions go to
+/// [`Self::INLINE_SIDECHAIN`]; everything else is the parent's own usage.
+#[derive(Debug, Clone, Default)]
+pub struct AgentBreakdown {
+    /// The parent (main-thread) sessions' own usage.
+    pub parent: AgentUsage,
+    /// Subagent usage keyed by agent type.
+    pub by_agent_type: IndexMap<String, AgentUsage>,
+}
+
+impl AgentBreakdown {
+    /// Bucket for sidechain turns logged inside a parent transcript.
+    pub const INLINE_SIDECHAIN: &'static str = "sidechain";
+    /// Bucket for subagent transcripts without a recorded agent type.
+    pub const UNKNOWN_AGENT: &'static str = "unknown";
+
+    /// Attribute one transcript's analytics.
+    ///
+    /// `agent_type` is `None` for a parent session and the subagent's type
+    /// (or [`Self::UNKNOWN_AGENT`]) for a subagent transcript.
+    pub fn add_session(&mut self, agent_type: Option<&str>, analytics: &SessionAnalytics) {
+        let usage = &analytics.usage;
+        if let Some(agent_type) = agent_type {
+            let entry = self
+                .by_agent_type
+                .entry(agent_type.to_string())
+                .or_default();
+            entry.sessions += 1;
+            entry.add(
+                usage.usage.work_tokens(),
+                usage.estimated_cost,
+                analytics.message_counts.tool_uses,
+            );
+            return;
+        }
+
+        let sidechain = &analytics.sidechain;
+        let sidechain_tokens = sidechain.usage.usage.work_tokens();
+        let sidechain_cost = sidechain.usage.estimated_cost;
+        self.parent.sessions += 1;
+        self.parent.add(
+            usage.usage.work_tokens().saturating_sub(sidechain_tokens),
+            usage
+                .estimated_cost
+                .map(|cost| (cost - sidechain_cost.unwrap_or(0.0)).max(0.0)),
+            analytics
+                .message_counts
+                .tool_uses
+                .saturating_sub(sidechain.tool_uses),
+        );
+        if sidechain.assistant_entries > 0 {
+            let entry = self
+                .by_agent_type
+                .entry(Self::INLINE_SIDECHAIN.to_string())
+                .or_default();
+            entry.add(sidechain_tokens, sidechain_cost, sidechain.tool_uses);
+        }
+    }
+
+    /// Merge another breakdown into this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.parent.merge(&other.parent);
+        for (agent_type, usage) in &other.by_agent_type {
+            self.by_agent_type
+                .entry(agent_type.clone())
+                .or_default()
+                .merge(usage);
+        }
+    }
+
+    /// Combined usage across every subagent bucket.
+    #[must_use]
+    pub fn subagent_total(&self) -> AgentUsage {
+        let mut total = AgentUsage::default();
+        for usage in self.by_agent_type.values() {
+            total.merge(usage);
+        }
+        total
+    }
+
+    /// Subagent share of all work tokens, as a percentage.
+    #[must_use]
+    pub fn subagent_share(&self) -> f64 {
+        let subagent = self.subagent_total().tokens;
+        let total = subagent + self.parent.tokens;
+        if total == 0 {
+            return 0.0;
+        }
+        subagent as f64 / total as f64 * 100.0
+    }
+
+    /// Agent types ordered by tokens, highest first.
+    #[must_use]
+    pub fn ranked(&self) -> Vec<(&str, &AgentUsage)> {
+        let mut ranked: Vec<_> = self
+            .by_agent_type
+            .iter()
+            .map(|(agent_type, usage)| (agent_type.as_str(), usage))
+            .collect();
+        ranked.sort_by(|a, b| b.1.tokens.cmp(&a.1.tokens).then_with(|| a.0.cmp(b.0)));
+        ranked
+    }
+}
+
+/// Thinking block statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ThinkingStats {
     /// Number of thinking blocks.
     pub block_count: usize,
     /// Total characters in thinking.
     pub total_chars: usize,
+    /// Thinking tokens reported in usage payloads, once per API turn.
+    pub reported_tokens: u64,
+    /// Output tokens of the turns that reported thinking tokens.
+    pub reported_output_tokens: u64,
+    /// Reported thinking usage by model.
+    pub by_model: IndexMap<String, ModelThinkingUsage>,
+}
+
+/// Reported thinking usage for a single model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelThinkingUsage {
+    /// Thinking tokens reported for this model.
+    pub thinking_tokens: u64,
+    /// Output tokens (thinking included) of the same turns.
+    pub output_tokens: u64,
+    /// Output-rate cost of the thinking tokens, if the model is priced.
+    pub estimated_cost: Option<f64>,
+}
+
+impl ModelThinkingUsage {
+    /// Thinking tokens as a percentage of output tokens.
+    #[must_use]
+    pub fn ratio(&self) -> f64 {
+        if self.output_tokens == 0 {
+            return 0.0;
+        }
+        self.thinking_tokens as f64 / self.output_tokens as f64 * 100.0
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.thinking_tokens += other.thinking_tokens;
+        self.output_tokens += other.output_tokens;
+        if let Some(cost) = other.estimated_cost {
+            *self.estimated_cost.get_or_insert(0.0) += cost;
+        }
+    }
 }
 
 impl ThinkingStats {
@@ -580,6 +825,58 @@ impl ThinkingStats {
     pub fn average_length(&self) -> usize {
         self.total_chars.checked_div(self.block_count).unwrap_or(0)
     }
+
+    /// Record one deduplicated API turn that reported thinking tokens.
+    ///
+    /// Thinking is billed at the model's output rate, so the attributable
+    /// cost uses the output price in effect at `observed_at`.
+    pub fn record_turn(
+        &mut self,
+        model: &str,
+        thinking_tokens: u64,
+        output_tokens: u64,
+        observed_at: DateTime<Utc>,
+    ) {
+        self.reported_tokens += thinking_tokens;
+        self.reported_output_tokens += output_tokens;
+        let entry = self.by_model.entry(model.to_string()).or_default();
+        entry.thinking_tokens += thinking_tokens;
+        entry.output_tokens += output_tokens;
+        if let Some(pricing) = crate::model::ModelPricing::for_model_at(model, observed_at) {
+            *entry.estimated_cost.get_or_insert(0.0) +=
+                thinking_tokens as f64 * pricing.output_per_million / 1_000_000.0;
+        }
+    }
+
+    /// Reported thinking tokens as a percentage of output tokens, if any
+    /// turn reported them.
+    #[must_use]
+    pub fn token_ratio(&self) -> Option<f64> {
+        if self.reported_output_tokens == 0 {
+            return None;
+        }
+        Some(self.reported_tokens as f64 / self.reported_output_tokens as f64 * 100.0)
+    }
+
+    /// Cost attributable to extended thinking across priced models.
+    #[must_use]
+    pub fn estimated_cost(&self) -> Option<f64> {
+        self.by_model
+            .values()
+            .filter_map(|usage| usage.estimated_cost)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Merge another session's thinking statistics into this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.block_count += other.block_count;
+        self.total_chars += other.total_chars;
+        self.reported_tokens += other.reported_tokens;
+        self.reported_output_tokens += other.reported_output_tokens;
+        for (model, usage) in &other.by_model {
+            self.by_model.entry(model.clone()).or_default().merge(usage);
+        }
+    }
 }
 
 /// File modification statistics.
@@ -599,6 +896,117 @@ pub struct FileModificationStats {
     pub files_created: usize,
     /// Files edited (Edit tool).
     pub files_edited: usize,
+    /// Modifications and line changes by programming language.
+    pub languages: IndexMap<String, LanguageStats>,
+}
+
+/// Modifications and line changes for one programming language.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageStats {
+    /// Number of modifications (Edit and Write calls).
+    pub modifications: usize,
+    /// Lines added.
+    pub lines_added: usize,
+    /// Lines removed.
+    pub lines_removed: usize,
+}
+
+impl LanguageStats {
+    /// Lines added plus lines removed.
+    #[must_use]
+    pub fn lines_changed(&self) -> usize {
+        self.lines_added + self.lines_removed
+    }
+
+    /// Net line change (added - removed).
+    #[must_use]
+    pub fn net_lines(&self) -> i64 {
+        self.lines_added as i64 - self.lines_removed as i64
+    }
+
+    /// Accumulate another language tally.
+    pub fn merge(&mut self, other: &Self) {
+        self.modifications += other.modifications;
+        self.lines_added += other.lines_added;
+        self.lines_removed += other.lines_removed;
+    }
+}
+
+/// Order a language map by lines changed, then modifications, then name.
+#[must_use]
+pub fn rank_languages(languages: &IndexMap<String, LanguageStats>) -> Vec<(&str, &LanguageStats)> {
+    let mut ranked: Vec<_> = languages
+        .iter()
+        .map(|(language, stats)| (language.as_str(), stats))
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.1.lines_changed()
+            .cmp(&a.1.lines_changed())
+            .then_with(|| b.1.modifications.cmp(&a.1.modifications))
+            .then_with(|| a.0.cmp(b.0))
+    });
+    ranked
+}
+
+/// Language bucket for files whose extension is not recognized.
+pub const OTHER_LANGUAGE: &str = "Other";
+
+/// Map a file path to a programming language by extension or well-known
+/// file name. Returns [`OTHER_LANGUAGE`] when unrecognized.
+#[must_use]
+pub fn language_for_path(file_path: &str) -> &'static str {
+    let path = std::path::Path::new(file_path);
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    match file_name {
+        "Dockerfile" | "Containerfile" => return "Dockerfile",
+        "Makefile" | "GNUmakefile" => return "Makefile",
+        "CMakeLists.txt" => return "CMake",
+        _ => {}
+    }
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return OTHER_LANGUAGE;
+    };
+    match ext.to_ascii_lowercase().as_str() {
+        "rs" => "Rust",
+        "py" | "pyi" => "Python",
+        "js" | "mjs" | "cjs" | "jsx" => "JavaScript",
+        "ts" | "mts" | "cts" | "tsx" => "TypeScript",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "scala" | "sc" => "Scala",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "ps1" | "psm1" => "PowerShell",
+        "lua" => "Lua",
+        "r" => "R",
+        "dart" => "Dart",
+        "ex" | "exs" => "Elixir",
+        "erl" | "hrl" => "Erlang",
+        "hs" => "Haskell",
+        "ml" | "mli" => "OCaml",
+        "clj" | "cljs" | "cljc" => "Clojure",
+        "zig" => "Zig",
+        "nix" => "Nix",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "md" | "markdown" | "mdx" => "Markdown",
+        "json" | "jsonc" | "jsonl" => "JSON",
+        "yaml" | "yml" => "YAML",
+        "toml" => "TOML",
+        "xml" => "XML",
+        "proto" => "Protobuf",
+        "tf" | "hcl" => "HCL",
+        _ => OTHER_LANGUAGE,
+    }
 }
 
 /// Entry for a single file's modification history.
@@ -631,15 +1039,15 @@ impl FileModificationStats {
         // Calculate line changes
         let old_lines = old_string.lines().count();
         let new_lines = new_string.lines().count();
-        if new_lines > old_lines {
-            let added = new_lines - old_lines;
-            entry.lines_added += added;
-            self.total_lines_added += added;
+        let (added, removed) = if new_lines > old_lines {
+            (new_lines - old_lines, 0)
         } else {
-            let removed = old_lines - new_lines;
-            entry.lines_removed += removed;
-            self.total_lines_removed += removed;
-        }
+            (0, old_lines - new_lines)
+        };
+        entry.lines_added += added;
+        entry.lines_removed += removed;
+        self.total_lines_added += added;
+        self.total_lines_removed += removed;
 
         // Update timestamps
         if let Some(ts) = timestamp {
@@ -659,6 +1067,7 @@ impl FileModificationStats {
             *self.extensions.entry(ext.to_string()).or_insert(0) += 1;
         }
 
+        self.record_language(file_path, added, removed);
         self.total_modifications += 1;
         self.files_edited += 1;
     }
@@ -694,10 +1103,21 @@ impl FileModificationStats {
             *self.extensions.entry(ext.to_string()).or_insert(0) += 1;
         }
 
+        self.record_language(file_path, lines, 0);
         self.total_modifications += 1;
         self.files_created += 1;
     }
 
+    fn record_language(&mut self, file_path: &str, added: usize, removed: usize) {
+        let stats = self
+            .languages
+            .entry(language_for_path(file_path).to_string())
+            .or_default();
+        stats.modifications += 1;
+        stats.lines_added += added;
+        stats.lines_removed += removed;
+    }
+
     /// Get top N most modified files.
     #[must_use]
     pub fn top_files(&self, n: usize) -> Vec<(&str, &FileModificationEntry)> {
@@ -721,6 +1141,12 @@ impl FileModificationStats {
             .collect()
     }
 
+    /// Languages ordered by lines changed, highest first.
+    #[must_use]
+    pub fn top_languages(&self) -> Vec<(&str, &LanguageStats)> {
+        rank_languages(&self.languages)
+    }
+
     /// Get total unique files modified.
     #[must_use]
     pub fn unique_files(&self) -> usize {
@@ -778,6 +1204,9 @@ pub struct AnalyticsSummary {
     /// Models with no known rate, excluded from `estimated_cost`. Non-empty
     /// means the cost estimate covers only the priced models.
     pub unpriced_models: Vec<String>,
+    /// Estimated USD saved by cache reads versus the full input rate, over
+    /// the priced models.
+    pub cache_savings: Option<f64>,
     /// Branch count.
     pub branch_count: usize,
     /// Primary model used.
@@ -828,6 +1257,7 @@ impl AnalyticsSummary {
             cache_hit_rate: 0.0,
             estimated_cost: None,
             unpriced_models: Vec::new(),
+            cache_savings: None,
             branch_count: 0,
             primary_model: None,
             subagent_count: 0,
@@ -854,6 +1284,9 @@ impl AnalyticsSummary {
             agg.subagent_tokens += s.subagent_tokens;
             agg.subagent_tool_invocations += s.subagent_tool_invocations;
             cost += s.estimated_cost.unwrap_or(0.0);
+            if let Some(saved) = s.cache_savings {
+                *agg.cache_savings.get_or_insert(0.0) += saved;
+            }
             unpriced.extend(s.unpriced_models.iter().cloned());
         }
         if cost > 0.0 {
@@ -1016,8 +1449,25 @@ pub mod limits {
     pub const CLAUDE_ENTERPRISE_MONTHLY: u64 = 1_000_000_000; // 1B tokens (estimated)
 }
 
+/// Serde for a [`Duration`] as whole milliseconds.
+mod duration_millis {
+    use chrono::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(duration.num_milliseconds())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        i64::deserialize(deserializer).map(Duration::milliseconds)
+    }
+}
+
 /// Aggregate analytics across multiple sessions.
-#[derive(Debug, Default)]
+///
+/// Serialized form holds raw counts only; call [`Self::calculate_cost`]
+/// after deserializing.
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ProjectAnalytics {
     /// Session count.
     pub session_count: usize,
@@ -1028,9 +1478,86 @@ pub struct ProjectAnalytics {
     /// Combined tool counts.
     pub tool_counts: IndexMap<String, usize>,
     /// Total duration across all sessions.
+    #[serde(with = "duration_millis")]
     pub total_duration: Duration,
     /// Model usage breakdown.
     pub model_usage: IndexMap<String, u64>,
+    /// Sessions started per local calendar day.
+    pub session_days: BTreeMap<NaiveDate, usize>,
+    /// File modifications (Edit/Write) across sessions.
+    pub file_modifications: usize,
+    /// Combined thinking statistics.
+    pub thinking_stats: ThinkingStats,
+    /// Modifications and line changes by programming language.
+    pub languages: IndexMap<String, LanguageStats>,
+    /// Mid-conversation model switches.
+    pub model_switches: ModelSwitchStats,
+}
+
+/// Day-over-day activity streaks across sessions.
+///
+/// A day counts as active when at least one session started on it (local
+/// calendar date). The current streak stays alive through `today` until the
+/// day has passed without activity, so it does not reset each morning.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityStreaks {
+    /// Consecutive active days ending today or yesterday.
+    pub current_streak: u32,
+    /// Longest run of consecutive active days.
+    pub longest_streak: u32,
+    /// First day of the longest streak.
+    pub longest_start: Option<NaiveDate>,
+    /// Last day of the longest streak.
+    pub longest_end: Option<NaiveDate>,
+    /// Number of distinct active days.
+    pub active_days: usize,
+    /// Average sessions started per active day.
+    pub avg_daily_sessions: f64,
+}
+
+impl ActivityStreaks {
+    /// Compute streaks from per-day session counts, relative to `today`.
+    pub fn from_days(days: &BTreeMap<NaiveDate, usize>, today: NaiveDate) -> Self {
+        let mut streaks = Self {
+            active_days: days.len(),
+            ..Self::default()
+        };
+        if days.is_empty() {
+            return streaks;
+        }
+
+        let total_sessions: usize = days.values().sum();
+        streaks.avg_daily_sessions = total_sessions as f64 / days.len() as f64;
+
+        let mut run = 0_u32;
+        let mut run_start: Option<NaiveDate> = None;
+        let mut previous: Option<NaiveDate> = None;
+        for &day in days.keys() {
+            if previous.and_then(|p| p.succ_opt()) == Some(day) {
+                run += 1;
+            } else {
+                run = 1;
+                run_start = Some(day);
+            }
+            if run > streaks.longest_streak {
+                streaks.longest_streak = run;
+                streaks.longest_start = run_start;
+                streaks.longest_end = Some(day);
+            }
+            previous = Some(day);
+        }
+
+        // `run` now ends at the latest active day; it is current only if that
+        // day is today or yesterday.
+        if let Some(last) = previous {
+            let yesterday = today.pred_opt();
+            if last == today || Some(last) == yesterday {
+                streaks.current_streak = run;
+            }
+        }
+
+        streaks
+    }
 }
 
 /// Time bucket granularity for usage trends.
@@ -1224,92 +1751,1663 @@ impl UsageTrends {
     }
 }
 
-/// Response time statistics.
-#[derive(Debug, Clone, Default)]
-pub struct ResponseTimeStats {
-    /// Average time between user message and assistant response (seconds).
-    pub avg_response_time_secs: f64,
-    /// Minimum response time.
-    pub min_response_time_secs: f64,
-    /// Maximum response time.
-    pub max_response_time_secs: f64,
-    /// Median response time.
-    pub median_response_time_secs: f64,
-    /// 95th percentile response time.
-    pub p95_response_time_secs: f64,
-    /// Number of response pairs analyzed.
-    pub sample_count: usize,
+/// API errors and retries on one local calendar day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DailyErrors {
+    /// `api_error` system entries.
+    pub errors: usize,
+    /// Assistant turns (distinct `message.id`) that completed.
+    pub turns: usize,
 }
 
-impl ResponseTimeStats {
-    /// Calculate response time statistics from a conversation.
-    pub fn from_conversation(conversation: &Conversation) -> Self {
-        let mut response_times: Vec<f64> = Vec::new();
-
-        // Get entries sorted by timestamp
-        let mut entries: Vec<_> = conversation.nodes().values().map(|n| &n.entry).collect();
+impl DailyErrors {
+    /// Errors per attempted API call (errors plus completed turns).
+    #[must_use]
+    pub fn error_rate(&self) -> f64 {
+        let attempts = self.errors + self.turns;
+        if attempts == 0 {
+            0.0
+        } else {
+            self.errors as f64 / attempts as f64
+        }
+    }
+}
 
-        entries.sort_by_key(|e| e.timestamp());
+/// API error and retry analytics.
+///
+/// Claude Code records a failed request as a system entry with the
+/// `api_error` subtype, carrying the HTTP status, the API error type (e.g.
+/// `overloaded_error`), and retry markers (`retryAttempt`, `maxRetries`).
+/// A turn is *retried* when the same prompt or tool result is answered by
+/// more than one assistant message: every answer but the last was paid for
+/// and discarded, and its tokens are counted as wasted.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorAnalytics {
+    /// `api_error` system entries.
+    pub api_errors: usize,
+    /// Errors by API error type, falling back to `http_<status>` or
+    /// `unknown`.
+    pub by_type: IndexMap<String, usize>,
+    /// Errors by HTTP status code, when recorded.
+    pub by_status: IndexMap<u16, usize>,
+    /// Errors that carried a retry attempt number.
+    pub retry_attempts: usize,
+    /// Highest retry attempt seen.
+    pub max_retry_attempt: u32,
+    /// Errors on the final allowed attempt (`retryAttempt >= maxRetries`).
+    pub retries_exhausted: usize,
+    /// Synthetic assistant messages that surfaced an API error to the user.
+    pub error_messages: usize,
+    /// Assistant turns superseded by a later answer to the same request.
+    pub retried_turns: usize,
+    /// Usage of the superseded turns, once per `message.id`.
+    pub wasted_usage: AggregatedUsage,
+    /// Errors and completed turns per local calendar day.
+    pub by_day: BTreeMap<NaiveDate, DailyErrors>,
+}
 
-        // Find pairs of user -> assistant messages
-        let mut prev_user_time: Option<DateTime<Utc>> = None;
+impl ErrorAnalytics {
+    /// Analyze the API errors and retried turns of a conversation.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let mut stats = Self::default();
+        // Request anchor (nearest user ancestor) -> answers as
+        // (message.id, model, deduped usage, first timestamp).
+        let mut answers: IndexMap<
+            &str,
+            IndexMap<&str, (&str, crate::model::usage::Usage, DateTime<Utc>)>,
+        > = IndexMap::new();
 
-        for entry in &entries {
-            match entry {
-                LogEntry::User(user) => {
-                    prev_user_time = Some(user.timestamp);
+        for node in conversation.nodes().values() {
+            match &node.entry {
+                LogEntry::System(system)
+                    if system.subtype == Some(crate::model::SystemSubtype::ApiError) =>
+                {
+                    stats.record_api_error(system);
                 }
                 LogEntry::Assistant(assistant) => {
-                    if let Some(user_time) = prev_user_time {
-                        let response_time =
-                            (assistant.timestamp - user_time).num_milliseconds() as f64 / 1000.0;
-                        if response_time > 0.0 && response_time < 3600.0 {
-                            // Only count reasonable response times (< 1 hour)
-                            response_times.push(response_time);
-                        }
+                    if assistant.is_api_error_message == Some(true) {
+                        stats.error_messages += 1;
+                        continue;
                     }
-                    prev_user_time = None;
+                    let Some(anchor) = request_anchor(conversation, &node.uuid) else {
+                        continue;
+                    };
+                    let (_, usage, first_seen) = answers
+                        .entry(anchor)
+                        .or_default()
+                        .entry(assistant.message.id.as_str())
+                        .or_insert_with(|| {
+                            (
+                                assistant.message.model.as_str(),
+                                crate::model::usage::Usage::default(),
+                                assistant.timestamp,
+                            )
+                        });
+                    if let Some(turn_usage) = &assistant.message.usage {
+                        usage.merge_max(turn_usage);
+                    }
+                    *first_seen = (*first_seen).min(assistant.timestamp);
                 }
                 _ => {}
             }
         }
 
-        if response_times.is_empty() {
-            return Self::default();
+        for turns in answers.values() {
+            let last = turns.values().map(|(_, _, at)| *at).max();
+            for (_, _, observed_at) in turns.values() {
+                stats
+                    .by_day
+                    .entry(observed_at.with_timezone(&Local).date_naive())
+                    .or_default()
+                    .turns += 1;
+            }
+            if turns.len() < 2 {
+                continue;
+            }
+            let mut kept = false;
+            for (model, usage, observed_at) in turns.values() {
+                if !kept && Some(*observed_at) == last {
+                    kept = true;
+                    continue;
+                }
+                stats.retried_turns += 1;
+                stats.wasted_usage.add_usage_at(model, usage, *observed_at);
+            }
         }
+        stats.wasted_usage.calculate_cost();
+        stats
+    }
 
-        response_times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    fn record_api_error(&mut self, system: &crate::model::SystemMessage) {
+        self.api_errors += 1;
+        self.by_day
+            .entry(system.timestamp.with_timezone(&Local).date_naive())
+            .or_default()
+            .errors += 1;
 
-        let sum: f64 = response_times.iter().sum();
-        let count = response_times.len();
+        let details = system.error.as_ref().and_then(|error| {
+            serde_json::from_value::<crate::model::ApiErrorDetails>(error.clone()).ok()
+        });
+        let status = details.as_ref().and_then(|details| details.status);
+        let error_type = details
+            .as_ref()
+            .and_then(|details| details.error.as_ref())
+            .and_then(|inner| inner.error.as_ref())
+            .map(|kind| kind.error_type.clone())
+            .or_else(|| status.map(|status| format!("http_{status}")))
+            .unwrap_or_else(|| "unknown".to_string());
+        *self.by_type.entry(error_type).or_insert(0) += 1;
+        if let Some(status) = status {
+            *self.by_status.entry(status).or_insert(0) += 1;
+        }
 
-        let median_idx = count / 2;
-        let p95_idx = (count as f64 * 0.95) as usize;
+        if let Some(attempt) = system.retry_attempt {
+            self.retry_attempts += 1;
+            self.max_retry_attempt = self.max_retry_attempt.max(attempt);
+            if system.max_retries.is_some_and(|max| attempt >= max) {
+                self.retries_exhausted += 1;
+            }
+        }
+    }
 
-        Self {
-            avg_response_time_secs: sum / count as f64,
-            min_response_time_secs: *response_times.first().unwrap_or(&0.0),
-            max_response_time_secs: *response_times.last().unwrap_or(&0.0),
-            median_response_time_secs: response_times.get(median_idx).copied().unwrap_or(0.0),
-            p95_response_time_secs: response_times.get(p95_idx).copied().unwrap_or(0.0),
-            sample_count: count,
+    /// Errors per attempted API call over the whole range.
+    #[must_use]
+    pub fn error_rate(&self) -> f64 {
+        let total = self
+            .by_day
+            .values()
+            .fold(DailyErrors::default(), |acc, day| DailyErrors {
+                errors: acc.errors + day.errors,
+                turns: acc.turns + day.turns,
+            });
+        total.error_rate()
+    }
+
+    /// Error types ranked by count.
+    #[must_use]
+    pub fn ranked_types(&self) -> Vec<(&str, usize)> {
+        let mut types: Vec<_> = self
+            .by_type
+            .iter()
+            .map(|(kind, count)| (kind.as_str(), *count))
+            .collect();
+        types.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        types
+    }
+
+    /// Merge another session's error analytics into this one.
+    ///
+    /// Re-price with `wasted_usage.calculate_cost()` after merging.
+    pub fn merge(&mut self, other: &Self) {
+        self.api_errors += other.api_errors;
+        for (kind, count) in &other.by_type {
+            *self.by_type.entry(kind.clone()).or_insert(0) += count;
+        }
+        for (status, count) in &other.by_status {
+            *self.by_status.entry(*status).or_insert(0) += count;
+        }
+        self.retry_attempts += other.retry_attempts;
+        self.max_retry_attempt = self.max_retry_attempt.max(other.max_retry_attempt);
+        self.retries_exhausted += other.retries_exhausted;
+        self.error_messages += other.error_messages;
+        self.retried_turns += other.retried_turns;
+        self.wasted_usage.merge(&other.wasted_usage);
+        for (day, counts) in &other.by_day {
+            let entry = self.by_day.entry(*day).or_default();
+            entry.errors += counts.errors;
+            entry.turns += counts.turns;
+        }
+    }
+}
+
+/// UUID of the nearest user entry above `uuid`: the prompt or tool result
+/// an assistant message answers.
+fn request_anchor<'a>(conversation: &'a Conversation, uuid: &str) -> Option<&'a str> {
+    let mut current = uuid;
+    // Bounded walk; parent cycles in malformed logs must not hang.
+    for _ in 0..conversation.len() {
+        let parent = conversation.parent_of(current)?;
+        if matches!(parent.entry, LogEntry::User(_)) {
+            return Some(parent.uuid.as_str());
+        }
+        current = parent.uuid.as_str();
+    }
+    None
+}
+
+/// Mid-conversation model switches.
+///
+/// Main-conversation assistant turns are ordered by time and each change of
+/// model between consecutive turns counts as a switch. Sidechain turns are
+/// excluded (subagents choose their own model), as are `<synthetic>`
+/// placeholders. In sessions that switch, usage is split at the first
+/// switch into before and after.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelSwitchStats {
+    /// Model changes between consecutive turns.
+    pub switches: usize,
+    /// Sessions with at least one switch.
+    pub sessions_switched: usize,
+    /// Switch counts by `(from, to)` model.
+    #[serde(with = "crate::util::serde_pairs")]
+    pub transitions: IndexMap<(String, String), usize>,
+    /// Usage before the first switch, in sessions that switched.
+    pub before_switch: AggregatedUsage,
+    /// Usage from the first switch on, in sessions that switched.
+    pub after_switch: AggregatedUsage,
+}
+
+impl ModelSwitchStats {
+    /// Find the model switches in a conversation.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let mut turns: IndexMap<&str, (&str, crate::model::usage::Usage, DateTime<Utc>)> =
+            IndexMap::new();
+        for node in conversation.nodes().values() {
+            let LogEntry::Assistant(assistant) = &node.entry else {
+                continue;
+            };
+            if assistant.is_sidechain || assistant.message.model == "<synthetic>" {
+                continue;
+            }
+            let (_, usage, first_seen) =
+                turns
+                    .entry(assistant.message.id.as_str())
+                    .or_insert_with(|| {
+                        (
+                            assistant.message.model.as_str(),
+                            crate::model::usage::Usage::default(),
+                            assistant.timestamp,
+                        )
+                    });
+            if let Some(turn_usage) = &assistant.message.usage {
+                usage.merge_max(turn_usage);
+            }
+            *first_seen = (*first_seen).min(assistant.timestamp);
+        }
+        let mut turns: Vec<_> = turns.into_values().collect();
+        turns.sort_by_key(|(_, _, at)| *at);
+
+        let mut stats = Self::default();
+        let mut previous: Option<&str> = None;
+        for (model, usage, observed_at) in &turns {
+            if let Some(from) = previous.filter(|from| from != model) {
+                stats.switches += 1;
+                *stats
+                    .transitions
+                    .entry((from.to_string(), (*model).to_string()))
+                    .or_insert(0) += 1;
+            }
+            previous = Some(*model);
+            if stats.switches == 0 {
+                stats.before_switch.add_usage_at(model, usage, *observed_at);
+            } else {
+                stats.after_switch.add_usage_at(model, usage, *observed_at);
+            }
+        }
+
+        if stats.switches == 0 {
+            // Only sessions that switched contribute to the split.
+            return Self::default();
+        }
+        stats.sessions_switched = 1;
+        stats.calculate_cost();
+        stats
+    }
+
+    /// Switches ranked by count.
+    #[must_use]
+    pub fn ranked_transitions(&self) -> Vec<(&str, &str, usize)> {
+        let mut transitions: Vec<_> = self
+            .transitions
+            .iter()
+            .map(|((from, to), count)| (from.as_str(), to.as_str(), *count))
+            .collect();
+        transitions.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| (a.0, a.1).cmp(&(b.0, b.1))));
+        transitions
+    }
+
+    /// Merge another session's switches into this one.
+    ///
+    /// Re-price with [`Self::calculate_cost`] after merging.
+    pub fn merge(&mut self, other: &Self) {
+        self.switches += other.switches;
+        self.sessions_switched += other.sessions_switched;
+        for (pair, count) in &other.transitions {
+            *self.transitions.entry(pair.clone()).or_insert(0) += count;
+        }
+        self.before_switch.merge(&other.before_switch);
+        self.after_switch.merge(&other.after_switch);
+    }
+
+    /// Price the before/after usage.
+    pub fn calculate_cost(&mut self) {
+        self.before_switch.calculate_cost();
+        self.after_switch.calculate_cost();
+    }
+}
+
+/// Token usage of one API turn.
+#[derive(Debug, Clone)]
+pub struct TurnTokens {
+    /// 1-based turn number.
+    pub turn: usize,
+    /// When the turn's first entry was written.
+    pub timestamp: DateTime<Utc>,
+    /// Model that produced the turn.
+    pub model: String,
+    /// Reported usage, once per `message.id`.
+    pub usage: crate::model::usage::Usage,
+}
+
+impl TurnTokens {
+    /// Prompt size sent with the turn: fresh input plus cache reads and
+    /// writes. Plotted over turns, this is how the context grows.
+    #[must_use]
+    pub fn context_tokens(&self) -> u64 {
+        self.usage.total_input_tokens()
+    }
+}
+
+/// Per-turn token usage along a conversation's main thread.
+///
+/// Streaming chunks of one API response share a `message.id` and repeat its
+/// usage block, so each response is one turn, ordered by first appearance.
+#[derive(Debug, Clone, Default)]
+pub struct TokenTimeline {
+    /// Turns in conversation order.
+    pub turns: Vec<TurnTokens>,
+}
+
+impl TokenTimeline {
+    /// Build from the assistant turns on a conversation's main thread.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let mut turns: IndexMap<&str, TurnTokens> = IndexMap::new();
+        for entry in conversation.main_thread_entries() {
+            let LogEntry::Assistant(assistant) = entry else {
+                continue;
+            };
+            if assistant.message.model == "<synthetic>" {
+                continue;
+            }
+            let turn = turns
+                .entry(assistant.message.id.as_str())
+                .or_insert_with(|| TurnTokens {
+                    turn: 0,
+                    timestamp: assistant.timestamp,
+                    model: assistant.message.model.clone(),
+                    usage: crate::model::usage::Usage::default(),
+                });
+            if let Some(usage) = &assistant.message.usage {
+                turn.usage.merge_max(usage);
+            }
+        }
+        let turns = turns
+            .into_values()
+            .enumerate()
+            .map(|(i, turn)| TurnTokens {
+                turn: i + 1,
+                ..turn
+            })
+            .collect();
+        Self { turns }
+    }
+
+    /// Largest context sent in any turn.
+    #[must_use]
+    pub fn peak_context(&self) -> u64 {
+        self.turns
+            .iter()
+            .map(TurnTokens::context_tokens)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Output tokens generated through each turn, inclusive.
+    #[must_use]
+    pub fn cumulative_output(&self) -> Vec<u64> {
+        self.turns
+            .iter()
+            .scan(0, |total, turn| {
+                *total += turn.usage.output_tokens;
+                Some(*total)
+            })
+            .collect()
+    }
+}
+
+/// Usage within one trailing time window.
+#[derive(Debug, Clone)]
+pub struct WindowUsage {
+    /// Window label as given, e.g. `24h`.
+    pub label: String,
+    /// Inclusive window start.
+    pub start: DateTime<Utc>,
+    /// Assistant turn usage observed in the window, once per `message.id`.
+    pub usage: AggregatedUsage,
+    /// User and assistant messages in the window (assistant turns, not
+    /// streaming chunks).
+    pub messages: usize,
+    /// Sessions with at least one message in the window.
+    pub sessions: usize,
+}
+
+/// Rolling usage aggregates over trailing windows ending at one instant.
+///
+/// Unlike per-session or all-time totals, entries are bucketed by their own
+/// timestamps, so a long session contributes only the part that falls inside
+/// each window.
+#[derive(Debug, Clone)]
+pub struct RollingUsage {
+    /// Exclusive end of every window.
+    pub end: DateTime<Utc>,
+    /// One aggregate per window, in the order given.
+    pub windows: Vec<WindowUsage>,
+}
+
+impl RollingUsage {
+    /// Default window labels.
+    pub const DEFAULT_WINDOWS: [&'static str; 3] = ["24h", "7d", "30d"];
+
+    /// Create empty windows of the given `(label, length)` ending at `end`.
+    #[must_use]
+    pub fn new(end: DateTime<Utc>, windows: &[(String, Duration)]) -> Self {
+        Self {
+            end,
+            windows: windows
+                .iter()
+                .map(|(label, length)| WindowUsage {
+                    label: label.clone(),
+                    start: end - *length,
+                    usage: AggregatedUsage::default(),
+                    messages: 0,
+                    sessions: 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Earliest window start; nothing before it is counted.
+    #[must_use]
+    pub fn earliest_start(&self) -> Option<DateTime<Utc>> {
+        self.windows.iter().map(|window| window.start).min()
+    }
+
+    /// Add one session's entries to every window they fall in.
+    pub fn add_conversation(&mut self, conversation: &Conversation) {
+        let mut turns: IndexMap<(&str, &str), (crate::model::usage::Usage, DateTime<Utc>)> =
+            IndexMap::new();
+        let mut user_times = Vec::new();
+        for node in conversation.nodes().values() {
+            match &node.entry {
+                LogEntry::User(user) => user_times.push(user.timestamp),
+                LogEntry::Assistant(assistant) => {
+                    let (usage, first_seen) = turns
+                        .entry((
+                            assistant.message.model.as_str(),
+                            assistant.message.id.as_str(),
+                        ))
+                        .or_insert_with(|| {
+                            (crate::model::usage::Usage::default(), assistant.timestamp)
+                        });
+                    if let Some(turn_usage) = &assistant.message.usage {
+                        usage.merge_max(turn_usage);
+                    }
+                    *first_seen = (*first_seen).min(assistant.timestamp);
+                }
+                _ => {}
+            }
+        }
+
+        let end = self.end;
+        for window in &mut self.windows {
+            let in_window = |at: &DateTime<Utc>| *at >= window.start && *at < end;
+            let mut messages = user_times.iter().filter(|at| in_window(at)).count();
+            for ((model, _), (usage, observed_at)) in &turns {
+                if in_window(observed_at) {
+                    messages += 1;
+                    if usage.total_tokens() > 0 {
+                        window.usage.add_usage_at(model, usage, *observed_at);
+                    }
+                }
+            }
+            if messages > 0 {
+                window.messages += messages;
+                window.sessions += 1;
+            }
+        }
+    }
+
+    /// Merge windows computed for other sessions over the same spans.
+    pub fn merge(&mut self, other: &Self) {
+        for (window, theirs) in self.windows.iter_mut().zip(&other.windows) {
+            window.usage.merge(&theirs.usage);
+            window.messages += theirs.messages;
+            window.sessions += theirs.sessions;
+        }
+    }
+
+    /// Price every window's usage.
+    pub fn calculate_cost(&mut self) {
+        for window in &mut self.windows {
+            window.usage.calculate_cost();
+        }
+    }
+}
+
+/// Usage attributed to one git branch.
+#[derive(Debug, Clone, Default)]
+pub struct BranchUsage {
+    /// Assistant turn usage on the branch, once per `message.id`.
+    pub usage: AggregatedUsage,
+    /// User and assistant messages (assistant turns, not streaming chunks).
+    pub messages: usize,
+    /// Sessions with at least one message on the branch.
+    pub sessions: usize,
+    /// Active time: gaps between consecutive entries of a session, credited
+    /// to the later entry's branch, skipping gaps longer than
+    /// [`GitBranchStats::ACTIVE_GAP_MINUTES`].
+    pub active: Duration,
+    /// Timestamp of the first message on the branch.
+    pub first_seen: Option<DateTime<Utc>>,
+    /// Timestamp of the last message on the branch.
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+/// Usage grouped by the git branch recorded on each entry (`gitBranch`).
+///
+/// Entries are attributed individually, so a session that switched branches
+/// counts toward each of them. Entries without a branch (outside a git
+/// repository, or from older logs) are grouped under
+/// [`GitBranchStats::NO_BRANCH`].
+#[derive(Debug, Clone, Default)]
+pub struct GitBranchStats {
+    /// Usage per branch name, in first-seen order.
+    pub branches: IndexMap<String, BranchUsage>,
+}
+
+impl GitBranchStats {
+    /// Group name for entries without a branch.
+    pub const NO_BRANCH: &'static str = "(no branch)";
+
+    /// Longest gap between entries still counted as active time.
+    pub const ACTIVE_GAP_MINUTES: i64 = 30;
+
+    /// Add one session's entries to the branches they were recorded on.
+    pub fn add_conversation(&mut self, conversation: &Conversation) {
+        let mut entries: Vec<(DateTime<Utc>, &LogEntry)> = conversation
+            .nodes()
+            .values()
+            .filter_map(|node| Some((node.entry.timestamp()?, &node.entry)))
+            .collect();
+        entries.sort_by_key(|(at, _)| *at);
+
+        let mut turns: IndexMap<(&str, &str), (crate::model::usage::Usage, DateTime<Utc>, &str)> =
+            IndexMap::new();
+        let mut session_branches: HashSet<&str> = HashSet::new();
+        let mut previous: Option<DateTime<Utc>> = None;
+        for (at, entry) in entries {
+            let branch = entry
+                .git_branch()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or(Self::NO_BRANCH);
+            let gap = previous.map(|before| at - before);
+            previous = Some(at);
+            let counted = match entry {
+                LogEntry::User(_) => true,
+                LogEntry::Assistant(assistant) => {
+                    let key = (
+                        assistant.message.model.as_str(),
+                        assistant.message.id.as_str(),
+                    );
+                    let is_new = !turns.contains_key(&key);
+                    let (usage, _, _) = turns
+                        .entry(key)
+                        .or_insert_with(|| (crate::model::usage::Usage::default(), at, branch));
+                    if let Some(turn_usage) = &assistant.message.usage {
+                        usage.merge_max(turn_usage);
+                    }
+                    is_new
+                }
+                _ => false,
+            };
+            let active = gap.filter(|gap| {
+                *gap > Duration::zero() && *gap <= Duration::minutes(Self::ACTIVE_GAP_MINUTES)
+            });
+            if !counted && active.is_none() {
+                continue;
+            }
+
+            let usage = self.branches.entry(branch.to_string()).or_default();
+            if let Some(gap) = active {
+                usage.active = usage.active + gap;
+            }
+            if counted {
+                usage.messages += 1;
+                usage.first_seen = Some(usage.first_seen.map_or(at, |first| first.min(at)));
+                usage.last_seen = Some(usage.last_seen.map_or(at, |last| last.max(at)));
+                if session_branches.insert(branch) {
+                    usage.sessions += 1;
+                }
+            }
+        }
+
+        // A turn's streaming chunks share one usage; it counts once, on the
+        // branch of its first chunk.
+        for ((model, _), (usage, observed_at, branch)) in turns {
+            if usage.total_tokens() > 0 {
+                self.branches
+                    .entry(branch.to_string())
+                    .or_default()
+                    .usage
+                    .add_usage_at(model, &usage, observed_at);
+            }
+        }
+    }
+
+    /// Merge branches computed for other sessions.
+    pub fn merge(&mut self, other: &Self) {
+        for (branch, theirs) in &other.branches {
+            let usage = self.branches.entry(branch.clone()).or_default();
+            usage.usage.merge(&theirs.usage);
+            usage.messages += theirs.messages;
+            usage.sessions += theirs.sessions;
+            usage.active = usage.active + theirs.active;
+            usage.first_seen = match (usage.first_seen, theirs.first_seen) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            usage.last_seen = match (usage.last_seen, theirs.last_seen) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            };
+        }
+    }
+
+    /// Price every branch's usage.
+    pub fn calculate_cost(&mut self) {
+        for usage in self.branches.values_mut() {
+            usage.usage.calculate_cost();
+        }
+    }
+
+    /// Branches by work tokens, then active time, descending.
+    #[must_use]
+    pub fn ranked(&self) -> Vec<(&str, &BranchUsage)> {
+        let mut ranked: Vec<_> = self
+            .branches
+            .iter()
+            .map(|(branch, usage)| (branch.as_str(), usage))
+            .collect();
+        ranked.sort_by(|(a_name, a), (b_name, b)| {
+            b.usage
+                .usage
+                .work_tokens()
+                .cmp(&a.usage.usage.work_tokens())
+                .then(b.active.cmp(&a.active))
+                .then(a_name.cmp(b_name))
+        });
+        ranked
+    }
+}
+
+/// Work left behind on abandoned conversation branches.
+///
+/// Editing a prompt or retrying a response forks the conversation; the old
+/// attempt stays in the log as a branch off the main thread. Each
+/// non-main-thread leaf that forks from the main thread counts as one
+/// abandoned branch, and its depth is the number of entries between the fork
+/// and the leaf. Progress dead-ends and sidechain entries are not retries and
+/// are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct AbandonedBranchStats {
+    /// Abandoned branches (non-main-thread leaves).
+    pub branches: usize,
+    /// Distinct entries on abandoned branches.
+    pub nodes: usize,
+    /// Sum of branch depths, for averaging.
+    pub total_depth: usize,
+    /// Deepest abandoned branch.
+    pub max_depth: usize,
+    /// Work tokens of assistant turns only found on abandoned branches.
+    pub tokens: u64,
+    /// Estimated cost of those turns over priced models.
+    pub estimated_cost: Option<f64>,
+}
+
+impl AbandonedBranchStats {
+    /// Measure the abandoned branches of a conversation.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let mut stats = Self::default();
+        let mut abandoned: HashSet<&str> = HashSet::new();
+
+        for leaf in conversation.leaves() {
+            if leaf.is_main_thread
+                || leaf.entry.is_sidechain()
+                || matches!(leaf.entry, LogEntry::Progress(_))
+            {
+                continue;
+            }
+            // Walk up to the main-thread fork. The step bound guards against
+            // parent cycles in malformed logs.
+            let mut path = vec![leaf.uuid.as_str()];
+            let mut fork = None;
+            let mut current = leaf;
+            for _ in 0..conversation.len() {
+                let Some(parent) = conversation.parent_of(&current.uuid) else {
+                    break;
+                };
+                if parent.is_main_thread {
+                    fork = Some(parent);
+                    break;
+                }
+                path.push(parent.uuid.as_str());
+                current = parent;
+            }
+            let Some(fork) = fork else {
+                continue;
+            };
+
+            let depth = leaf.depth.saturating_sub(fork.depth);
+            stats.branches += 1;
+            stats.total_depth += depth;
+            stats.max_depth = stats.max_depth.max(depth);
+            abandoned.extend(path);
+        }
+        stats.nodes = abandoned.len();
+
+        // A turn whose streaming chunks also reached the main thread was
+        // kept, not wasted.
+        let kept: HashSet<&str> = conversation
+            .main_thread_entries()
+            .into_iter()
+            .filter_map(|entry| match entry {
+                LogEntry::Assistant(assistant) => Some(assistant.message.id.as_str()),
+                _ => None,
+            })
+            .collect();
+        let mut deduped: IndexMap<(&str, &str), (crate::model::usage::Usage, DateTime<Utc>)> =
+            IndexMap::new();
+        for node in conversation
+            .nodes()
+            .values()
+            .filter(|node| abandoned.contains(node.uuid.as_str()))
+        {
+            let LogEntry::Assistant(assistant) = &node.entry else {
+                continue;
+            };
+            let Some(usage) = &assistant.message.usage else {
+                continue;
+            };
+            if kept.contains(assistant.message.id.as_str()) {
+                continue;
+            }
+            let (turn, first_seen) = deduped
+                .entry((
+                    assistant.message.model.as_str(),
+                    assistant.message.id.as_str(),
+                ))
+                .or_insert_with(|| (crate::model::usage::Usage::default(), assistant.timestamp));
+            turn.merge_max(usage);
+            *first_seen = (*first_seen).min(assistant.timestamp);
+        }
+
+        if !deduped.is_empty() {
+            let mut usage = AggregatedUsage::default();
+            for ((model, _), (turn, observed_at)) in &deduped {
+                usage.add_usage_at(model, turn, *observed_at);
+            }
+            usage.calculate_cost();
+            stats.tokens = usage.usage.work_tokens();
+            stats.estimated_cost = usage.estimated_cost;
+        }
+        stats
+    }
+
+    /// Mean abandoned-branch depth.
+    #[must_use]
+    pub fn average_depth(&self) -> f64 {
+        if self.branches == 0 {
+            0.0
+        } else {
+            self.total_depth as f64 / self.branches as f64
+        }
+    }
+
+    /// Merge another session's abandoned branches into this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.branches += other.branches;
+        self.nodes += other.nodes;
+        self.total_depth += other.total_depth;
+        self.max_depth = self.max_depth.max(other.max_depth);
+        self.tokens += other.tokens;
+        if let Some(cost) = other.estimated_cost {
+            *self.estimated_cost.get_or_insert(0.0) += cost;
+        }
+    }
+}
+
+/// Tool call sequences as a first-order Markov chain.
+///
+/// Tool calls are read in main-thread order. Transitions count each
+/// consecutive pair; chains count every run of [`Self::CHAIN_LENGTH`]
+/// consecutive calls, surfacing common workflows such as Read → Edit → Bash.
+#[derive(Debug, Clone, Default)]
+pub struct ToolChainStats {
+    /// Sessions with at least one tool call.
+    pub sessions: usize,
+    /// Consecutive `(from, to)` tool pairs with counts.
+    pub transitions: IndexMap<(String, String), usize>,
+    /// Transitions leaving each tool, the denominator for probabilities.
+    pub outgoing: IndexMap<String, usize>,
+    /// Runs of [`Self::CHAIN_LENGTH`] consecutive tools with counts.
+    pub chains: IndexMap<Vec<String>, usize>,
+}
+
+impl ToolChainStats {
+    /// Number of consecutive calls in a workflow chain.
+    pub const CHAIN_LENGTH: usize = 3;
+
+    /// Build from the ordered tool calls on a conversation's main thread.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let tools: Vec<&str> = conversation
+            .main_thread_entries()
+            .into_iter()
+            .filter_map(|entry| match entry {
+                LogEntry::Assistant(assistant) => Some(&assistant.message.content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse(tool_use) => Some(tool_use.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        Self::from_sequence(&tools)
+    }
+
+    /// Build from one session's ordered tool names.
+    #[must_use]
+    pub fn from_sequence(tools: &[&str]) -> Self {
+        let mut stats = Self::default();
+        if tools.is_empty() {
+            return stats;
+        }
+        stats.sessions = 1;
+        for pair in tools.windows(2) {
+            *stats
+                .transitions
+                .entry((pair[0].to_string(), pair[1].to_string()))
+                .or_insert(0) += 1;
+            *stats.outgoing.entry(pair[0].to_string()).or_insert(0) += 1;
+        }
+        for run in tools.windows(Self::CHAIN_LENGTH) {
+            let chain = run.iter().map(ToString::to_string).collect();
+            *stats.chains.entry(chain).or_insert(0) += 1;
+        }
+        stats
+    }
+
+    /// Merge another session's counts into this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.sessions += other.sessions;
+        for (pair, count) in &other.transitions {
+            *self.transitions.entry(pair.clone()).or_insert(0) += count;
+        }
+        for (tool, count) in &other.outgoing {
+            *self.outgoing.entry(tool.clone()).or_insert(0) += count;
+        }
+        for (chain, count) in &other.chains {
+            *self.chains.entry(chain.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Probability that `to` directly follows `from`.
+    #[must_use]
+    pub fn probability(&self, from: &str, to: &str) -> f64 {
+        let total = self.outgoing.get(from).copied().unwrap_or(0);
+        if total == 0 {
+            return 0.0;
+        }
+        let count = self
+            .transitions
+            .get(&(from.to_string(), to.to_string()))
+            .copied()
+            .unwrap_or(0);
+        count as f64 / total as f64
+    }
+
+    /// The `n` most frequent transitions as `(from, to, count)`.
+    #[must_use]
+    pub fn top_transitions(&self, n: usize) -> Vec<(&str, &str, usize)> {
+        let mut transitions: Vec<_> = self
+            .transitions
+            .iter()
+            .map(|((from, to), count)| (from.as_str(), to.as_str(), *count))
+            .collect();
+        transitions.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| (a.0, a.1).cmp(&(b.0, b.1))));
+        transitions.truncate(n);
+        transitions
+    }
+
+    /// The `n` most frequent workflow chains.
+    #[must_use]
+    pub fn top_chains(&self, n: usize) -> Vec<(&[String], usize)> {
+        let mut chains: Vec<_> = self
+            .chains
+            .iter()
+            .map(|(chain, count)| (chain.as_slice(), *count))
+            .collect();
+        chains.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        chains.truncate(n);
+        chains
+    }
+}
+
+/// Edit churn on one file within a session.
+#[derive(Debug, Clone, Default)]
+pub struct FileChurn {
+    /// Edit calls on the file.
+    pub edits: usize,
+    /// Edits that replaced text written by an earlier edit.
+    pub rewrites: usize,
+    /// Replacement text of earlier edits, in order.
+    written: Vec<String>,
+}
+
+impl FileChurn {
+    /// Rewrites as a percentage of edits.
+    #[must_use]
+    pub fn rewrite_rate(&self) -> f64 {
+        if self.edits == 0 {
+            return 0.0;
+        }
+        self.rewrites as f64 / self.edits as f64 * 100.0
+    }
+
+    /// Whether `old_string` overlaps text an earlier edit wrote.
+    fn overlaps(&self, old_string: &str) -> bool {
+        let old_lines: HashSet<&str> = significant_lines(old_string).collect();
+        if old_lines.is_empty() {
+            return false;
+        }
+        self.written.iter().any(|new_string| {
+            new_string.contains(old_string)
+                || (significant_lines(new_string).next().is_some()
+                    && old_string.contains(new_string.as_str()))
+                || significant_lines(new_string).any(|line| old_lines.contains(line))
+        })
+    }
+}
+
+/// Trimmed lines that carry code: lines with no letter or digit (braces,
+/// blank lines) recur everywhere and would make unrelated edits overlap.
+fn significant_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| line.chars().any(char::is_alphanumeric))
+}
+
+/// Repeated edits of the same file regions within one session.
+///
+/// An Edit call is a rewrite when the text it replaces overlaps text that an
+/// earlier Edit in the session wrote to the same file: one contains the
+/// other, or they share a line of code. Files collecting several rewrites
+/// are hotspots where the assistant kept reworking its own changes.
+#[derive(Debug, Clone, Default)]
+pub struct EditChurn {
+    /// Edit calls across all files.
+    pub edits: usize,
+    /// Edits that replaced text written by an earlier edit.
+    pub rewrites: usize,
+    /// Churn by file path, in first-edit order.
+    pub files: IndexMap<String, FileChurn>,
+}
+
+impl EditChurn {
+    /// Rewrites on one file at which it counts as a hotspot.
+    pub const HOTSPOT_REWRITES: usize = 2;
+
+    /// Build from the ordered Edit calls on a conversation's main thread.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let mut churn = Self::default();
+        for entry in conversation.main_thread_entries() {
+            let LogEntry::Assistant(assistant) = entry else {
+                continue;
+            };
+            for block in &assistant.message.content {
+                let ContentBlock::ToolUse(tool_use) = block else {
+                    continue;
+                };
+                if tool_use.name != "Edit" {
+                    continue;
+                }
+                if let (Some(file_path), Some(old_string), Some(new_string)) = (
+                    tool_use.input.get("file_path").and_then(|v| v.as_str()),
+                    tool_use.input.get("old_string").and_then(|v| v.as_str()),
+                    tool_use.input.get("new_string").and_then(|v| v.as_str()),
+                ) {
+                    churn.record_edit(file_path, old_string, new_string);
+                }
+            }
+        }
+        churn
+    }
+
+    /// Record one Edit call, in session order.
+    pub fn record_edit(&mut self, file_path: &str, old_string: &str, new_string: &str) {
+        let file = self.files.entry(file_path.to_string()).or_default();
+        file.edits += 1;
+        self.edits += 1;
+        if file.overlaps(old_string) {
+            file.rewrites += 1;
+            self.rewrites += 1;
+        }
+        file.written.push(new_string.to_string());
+    }
+
+    /// Rewrites as a percentage of edits.
+    #[must_use]
+    pub fn rewrite_rate(&self) -> f64 {
+        if self.edits == 0 {
+            return 0.0;
+        }
+        self.rewrites as f64 / self.edits as f64 * 100.0
+    }
+
+    /// Files with at least [`Self::HOTSPOT_REWRITES`] rewrites, most
+    /// rewritten first.
+    #[must_use]
+    pub fn hotspots(&self) -> Vec<(&str, &FileChurn)> {
+        let mut hotspots: Vec<_> = self
+            .files
+            .iter()
+            .filter(|(_, file)| file.rewrites >= Self::HOTSPOT_REWRITES)
+            .map(|(path, file)| (path.as_str(), file))
+            .collect();
+        hotspots.sort_by(|a, b| {
+            b.1.rewrites
+                .cmp(&a.1.rewrites)
+                .then_with(|| b.1.edits.cmp(&a.1.edits))
+        });
+        hotspots
+    }
+}
+
+/// Files modified together across sessions.
+///
+/// Nodes are files, counted by the sessions that modified them (Edit or
+/// Write). An edge joins two files modified in the same session and is
+/// weighted by how many sessions did so, revealing coupling that keeps
+/// getting touched together.
+#[derive(Debug, Clone, Default)]
+pub struct CoModificationGraph {
+    /// Sessions that modified at least one file.
+    pub sessions: usize,
+    /// Sessions modifying each file.
+    pub nodes: IndexMap<String, usize>,
+    /// Sessions modifying both files, keyed by the pair in sorted order.
+    pub edges: IndexMap<(String, String), usize>,
+}
+
+impl CoModificationGraph {
+    /// Add the set of files one session modified.
+    pub fn add_session<'a>(&mut self, files: impl IntoIterator<Item = &'a str>) {
+        let mut files: Vec<&str> = files.into_iter().collect();
+        files.sort_unstable();
+        files.dedup();
+        if files.is_empty() {
+            return;
+        }
+        self.sessions += 1;
+        for (i, file) in files.iter().enumerate() {
+            *self.nodes.entry((*file).to_string()).or_insert(0) += 1;
+            for other in &files[i + 1..] {
+                *self
+                    .edges
+                    .entry(((*file).to_string(), (*other).to_string()))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Merge another graph into this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.sessions += other.sessions;
+        for (file, count) in &other.nodes {
+            *self.nodes.entry(file.clone()).or_insert(0) += count;
+        }
+        for (pair, weight) in &other.edges {
+            *self.edges.entry(pair.clone()).or_insert(0) += weight;
+        }
+    }
+
+    /// Edges as `(source, target, weight)`, heaviest first.
+    #[must_use]
+    pub fn ranked_edges(&self) -> Vec<(&str, &str, usize)> {
+        let mut edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|((source, target), weight)| (source.as_str(), target.as_str(), *weight))
+            .collect();
+        edges.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| (a.0, a.1).cmp(&(b.0, b.1))));
+        edges
+    }
+
+    /// Render as an undirected Graphviz DOT graph. Nodes are labeled with
+    /// the file name; edges carry their weight as `weight` and `label`.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        use std::fmt::Write;
+
+        fn quote(id: &str) -> String {
+            format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+
+        let mut dot = String::from("graph co_modification {\n");
+        for (file, sessions) in &self.nodes {
+            let name = std::path::Path::new(file)
+                .file_name()
+                .map_or_else(|| file.clone(), |name| name.to_string_lossy().into_owned());
+            let _ = writeln!(
+                dot,
+                "  {} [label={}, tooltip={}];",
+                quote(file),
+                quote(&name),
+                quote(&format!("{file} ({sessions} sessions)"))
+            );
+        }
+        for (source, target, weight) in self.ranked_edges() {
+            let _ = writeln!(
+                dot,
+                "  {} -- {} [weight={weight}, label=\"{weight}\"];",
+                quote(source),
+                quote(target)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// One human prompt paired with the assistant turn that answered it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptResponseSample {
+    /// Prompt length in characters.
+    pub prompt_chars: usize,
+    /// Output tokens across the answering turn, once per `message.id`.
+    pub response_tokens: u64,
+    /// Seconds from the prompt to the last assistant entry of the turn.
+    /// `None` when out of range (non-positive or over an hour).
+    pub latency_secs: Option<f64>,
+}
+
+/// Least-squares line `y = slope * x + intercept` with Pearson's `r`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    /// Change in `y` per unit of `x`.
+    pub slope: f64,
+    /// Value of `y` at `x = 0`.
+    pub intercept: f64,
+    /// Pearson correlation coefficient in `[-1, 1]`.
+    pub r: f64,
+    /// Number of points fitted.
+    pub n: usize,
+}
+
+impl LinearFit {
+    /// Fit a line through `points`. `None` with fewer than two points or
+    /// when every `x` is identical.
+    #[must_use]
+    pub fn from_points(points: &[(f64, f64)]) -> Option<Self> {
+        let n = points.len();
+        if n < 2 {
+            return None;
+        }
+        let count = n as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+        let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+        for (x, y) in points {
+            let (dx, dy) = (x - mean_x, y - mean_y);
+            sxx += dx * dx;
+            syy += dy * dy;
+            sxy += dx * dy;
+        }
+        if sxx == 0.0 {
+            return None;
+        }
+        let slope = sxy / sxx;
+        let r = if syy == 0.0 {
+            0.0
+        } else {
+            sxy / (sxx * syy).sqrt()
+        };
+        Some(Self {
+            slope,
+            intercept: mean_y - slope * mean_x,
+            r,
+            n,
+        })
+    }
+}
+
+/// Averages for prompts within one length range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptLengthBucket {
+    /// Human-readable range, e.g. `100-499`.
+    pub label: &'static str,
+    /// Prompts in this range.
+    pub prompts: usize,
+    /// Mean prompt length in characters.
+    pub avg_prompt_chars: f64,
+    /// Mean response output tokens.
+    pub avg_response_tokens: f64,
+    /// Mean latency over samples with a usable latency.
+    pub avg_latency_secs: Option<f64>,
+}
+
+/// Prompt length vs response size and latency across sessions.
+#[derive(Debug, Clone, Default)]
+pub struct PromptCorrelation {
+    /// One sample per answered human prompt.
+    pub samples: Vec<PromptResponseSample>,
+}
+
+impl PromptCorrelation {
+    /// Prompt length ranges used by [`Self::buckets`], as `[lower, upper)`.
+    pub const BUCKETS: [(&'static str, usize, usize); 4] = [
+        ("<100", 0, 100),
+        ("100-499", 100, 500),
+        ("500-1999", 500, 2000),
+        ("2000+", 2000, usize::MAX),
+    ];
+
+    /// Pair each human prompt on the main thread with its answering turn.
+    ///
+    /// Tool-result user entries continue the current turn, so agentic
+    /// back-and-forth counts toward the prompt that started it.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        struct Turn {
+            prompt_chars: usize,
+            started: DateTime<Utc>,
+            last_reply: Option<DateTime<Utc>>,
+            output_by_message: IndexMap<String, u64>,
+        }
+
+        let mut samples = Vec::new();
+        let mut flush = |turn: Option<Turn>| {
+            let Some(turn) = turn else { return };
+            let Some(last_reply) = turn.last_reply else {
+                return;
+            };
+            let latency = (last_reply - turn.started).num_milliseconds() as f64 / 1000.0;
+            samples.push(PromptResponseSample {
+                prompt_chars: turn.prompt_chars,
+                response_tokens: turn.output_by_message.values().sum(),
+                latency_secs: (latency > 0.0 && latency < 3600.0).then_some(latency),
+            });
+        };
+
+        let mut current: Option<Turn> = None;
+        for entry in conversation.main_thread_entries() {
+            match entry {
+                LogEntry::User(user) => {
+                    if let Some(text) = crate::analysis::extraction::extract_user_prompt_text(entry)
+                    {
+                        flush(current.take());
+                        current = Some(Turn {
+                            prompt_chars: text.chars().count(),
+                            started: user.timestamp,
+                            last_reply: None,
+                            output_by_message: IndexMap::new(),
+                        });
+                    }
+                }
+                LogEntry::Assistant(assistant) => {
+                    if let Some(turn) = current.as_mut() {
+                        turn.last_reply = Some(assistant.timestamp);
+                        if let Some(usage) = &assistant.message.usage {
+                            let slot = turn
+                                .output_by_message
+                                .entry(assistant.message.id.clone())
+                                .or_insert(0);
+                            *slot = (*slot).max(usage.output_tokens);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        flush(current);
+
+        Self { samples }
+    }
+
+    /// Add another session's samples.
+    pub fn merge(&mut self, other: Self) {
+        self.samples.extend(other.samples);
+    }
+
+    /// Regression of response output tokens on prompt characters.
+    #[must_use]
+    pub fn tokens_fit(&self) -> Option<LinearFit> {
+        let points: Vec<(f64, f64)> = self
+            .samples
+            .iter()
+            .map(|s| (s.prompt_chars as f64, s.response_tokens as f64))
+            .collect();
+        LinearFit::from_points(&points)
+    }
+
+    /// Regression of latency seconds on prompt characters.
+    #[must_use]
+    pub fn latency_fit(&self) -> Option<LinearFit> {
+        let points: Vec<(f64, f64)> = self
+            .samples
+            .iter()
+            .filter_map(|s| Some((s.prompt_chars as f64, s.latency_secs?)))
+            .collect();
+        LinearFit::from_points(&points)
+    }
+
+    /// Per-range averages, one entry per [`Self::BUCKETS`] range.
+    #[must_use]
+    pub fn buckets(&self) -> Vec<PromptLengthBucket> {
+        Self::BUCKETS
+            .iter()
+            .map(|&(label, lower, upper)| {
+                let in_range: Vec<&PromptResponseSample> = self
+                    .samples
+                    .iter()
+                    .filter(|s| s.prompt_chars >= lower && s.prompt_chars < upper)
+                    .collect();
+                let prompts = in_range.len();
+                let mean = |sum: f64, count: usize| {
+                    if count == 0 {
+                        0.0
+                    } else {
+                        sum / count as f64
+                    }
+                };
+                let latencies: Vec<f64> = in_range.iter().filter_map(|s| s.latency_secs).collect();
+                PromptLengthBucket {
+                    label,
+                    prompts,
+                    avg_prompt_chars: mean(
+                        in_range.iter().map(|s| s.prompt_chars as f64).sum(),
+                        prompts,
+                    ),
+                    avg_response_tokens: mean(
+                        in_range.iter().map(|s| s.response_tokens as f64).sum(),
+                        prompts,
+                    ),
+                    avg_latency_secs: (!latencies.is_empty())
+                        .then(|| mean(latencies.iter().sum(), latencies.len())),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Response time statistics.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseTimeStats {
+    /// Average time between user message and assistant response (seconds).
+    pub avg_response_time_secs: f64,
+    /// Minimum response time.
+    pub min_response_time_secs: f64,
+    /// Maximum response time.
+    pub max_response_time_secs: f64,
+    /// Median response time.
+    pub median_response_time_secs: f64,
+    /// 95th percentile response time.
+    pub p95_response_time_secs: f64,
+    /// Number of response pairs analyzed.
+    pub sample_count: usize,
+}
+
+impl ResponseTimeStats {
+    /// Calculate response time statistics from a conversation.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let mut response_times: Vec<f64> = Vec::new();
+
+        // Get entries sorted by timestamp
+        let mut entries: Vec<_> = conversation.nodes().values().map(|n| &n.entry).collect();
+
+        entries.sort_by_key(|e| e.timestamp());
+
+        // Find pairs of user -> assistant messages
+        let mut prev_user_time: Option<DateTime<Utc>> = None;
+
+        for entry in &entries {
+            match entry {
+                LogEntry::User(user) => {
+                    prev_user_time = Some(user.timestamp);
+                }
+                LogEntry::Assistant(assistant) => {
+                    if let Some(user_time) = prev_user_time {
+                        let response_time =
+                            (assistant.timestamp - user_time).num_milliseconds() as f64 / 1000.0;
+                        if response_time > 0.0 && response_time < 3600.0 {
+                            // Only count reasonable response times (< 1 hour)
+                            response_times.push(response_time);
+                        }
+                    }
+                    prev_user_time = None;
+                }
+                _ => {}
+            }
+        }
+
+        if response_times.is_empty() {
+            return Self::default();
+        }
+
+        response_times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let sum: f64 = response_times.iter().sum();
+        let count = response_times.len();
+
+        let median_idx = count / 2;
+        let p95_idx = (count as f64 * 0.95) as usize;
+
+        Self {
+            avg_response_time_secs: sum / count as f64,
+            min_response_time_secs: *response_times.first().unwrap_or(&0.0),
+            max_response_time_secs: *response_times.last().unwrap_or(&0.0),
+            median_response_time_secs: response_times.get(median_idx).copied().unwrap_or(0.0),
+            p95_response_time_secs: response_times.get(p95_idx).copied().unwrap_or(0.0),
+            sample_count: count,
+        }
+    }
+
+    /// Format as a readable string.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if self.sample_count == 0 {
+            return "No response time data available".to_string();
+        }
+
+        format!(
+            "Response times: avg={:.1}s, median={:.1}s, p95={:.1}s (n={})",
+            self.avg_response_time_secs,
+            self.median_response_time_secs,
+            self.p95_response_time_secs,
+            self.sample_count
+        )
+    }
+}
+
+/// Activity bucketed by hour of day × day of week, in local time.
+///
+/// Cells are indexed `[weekday][hour]` with Monday as weekday 0. Assistant
+/// tokens are counted once per distinct `message.id`, matching how
+/// [`SessionAnalytics`] dedupes streaming chunks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivityHeatmap {
+    /// User and assistant messages per cell.
+    pub messages: [[u64; 24]; 7],
+    /// Work tokens (input + cache creation + output) per cell.
+    pub tokens: [[u64; 24]; 7],
+}
+
+/// Which heatmap counter to read or render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapMetric {
+    /// Message counts.
+    Messages,
+    /// Work token counts.
+    Tokens,
+}
+
+impl ActivityHeatmap {
+    /// Short weekday labels in row order.
+    pub const WEEKDAYS: [&'static str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+    /// Intensity ramp used by [`Self::render`], from empty to peak.
+    const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+    /// Build a heatmap from a single conversation.
+    pub fn from_conversation(conversation: &Conversation) -> Self {
+        let mut heatmap = Self::default();
+        heatmap.add_conversation(conversation);
+        heatmap
+    }
+
+    /// Add a conversation's messages and tokens to the heatmap.
+    pub fn add_conversation(&mut self, conversation: &Conversation) {
+        // message.id -> (deduped usage, first timestamp), as in accumulate_usage.
+        let mut turns: IndexMap<String, (crate::model::usage::Usage, DateTime<Utc>)> =
+            IndexMap::new();
+
+        for node in conversation.nodes().values() {
+            match &node.entry {
+                LogEntry::User(user) => self.record(user.timestamp, 1, 0),
+                LogEntry::Assistant(assistant) => {
+                    let (usage, first_timestamp) = turns
+                        .entry(assistant.message.id.clone())
+                        .or_insert_with(|| {
+                            (crate::model::usage::Usage::default(), assistant.timestamp)
+                        });
+                    if let Some(chunk_usage) = &assistant.message.usage {
+                        usage.merge_max(chunk_usage);
+                    }
+                    *first_timestamp = (*first_timestamp).min(assistant.timestamp);
+                }
+                _ => {}
+            }
+        }
+
+        for (usage, timestamp) in turns.values() {
+            self.record(*timestamp, 1, usage.work_tokens());
+        }
+    }
+
+    /// Record activity at a UTC timestamp, bucketed in local time.
+    pub fn record(&mut self, timestamp: DateTime<Utc>, messages: u64, tokens: u64) {
+        let local = timestamp.with_timezone(&Local);
+        self.record_slot(local.weekday(), local.hour(), messages, tokens);
+    }
+
+    /// Record activity directly into a weekday/hour cell.
+    pub fn record_slot(&mut self, weekday: Weekday, hour: u32, messages: u64, tokens: u64) {
+        let day = weekday.num_days_from_monday() as usize;
+        let hour = (hour as usize).min(23);
+        self.messages[day][hour] += messages;
+        self.tokens[day][hour] += tokens;
+    }
+
+    /// Fold another heatmap into this one.
+    pub fn merge(&mut self, other: &Self) {
+        let cells = self
+            .messages
+            .iter_mut()
+            .flatten()
+            .zip(other.messages.iter().flatten());
+        for (mine, theirs) in cells {
+            *mine += theirs;
+        }
+        let cells = self
+            .tokens
+            .iter_mut()
+            .flatten()
+            .zip(other.tokens.iter().flatten());
+        for (mine, theirs) in cells {
+            *mine += theirs;
+        }
+    }
+
+    /// The grid for a metric.
+    #[must_use]
+    pub const fn grid(&self, metric: HeatmapMetric) -> &[[u64; 24]; 7] {
+        match metric {
+            HeatmapMetric::Messages => &self.messages,
+            HeatmapMetric::Tokens => &self.tokens,
+        }
+    }
+
+    /// Sum of a metric across all cells.
+    #[must_use]
+    pub fn total(&self, metric: HeatmapMetric) -> u64 {
+        self.grid(metric).iter().flatten().sum()
+    }
+
+    /// Whether no activity has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.total(HeatmapMetric::Messages) == 0 && self.total(HeatmapMetric::Tokens) == 0
+    }
+
+    /// The busiest cell for a metric as `(weekday index, hour, value)`.
+    ///
+    /// Ties resolve to the earliest cell. Returns `None` when the grid is empty.
+    #[must_use]
+    pub fn peak(&self, metric: HeatmapMetric) -> Option<(usize, usize, u64)> {
+        let mut peak: Option<(usize, usize, u64)> = None;
+        for (day, row) in self.grid(metric).iter().enumerate() {
+            for (hour, &value) in row.iter().enumerate() {
+                if value > 0 && peak.is_none_or(|(_, _, best)| value > best) {
+                    peak = Some((day, hour, value));
+                }
+            }
         }
+        peak
     }
 
-    /// Format as a readable string.
+    /// Render a metric as a Unicode grid, two columns per hour.
+    ///
+    /// Cells are shaded relative to the busiest cell; empty cells show `·`.
     #[must_use]
-    pub fn summary(&self) -> String {
-        if self.sample_count == 0 {
-            return "No response time data available".to_string();
+    pub fn render(&self, metric: HeatmapMetric) -> String {
+        let grid = self.grid(metric);
+        let max = grid.iter().flatten().copied().max().unwrap_or(0);
+
+        let mut out = String::from("     ");
+        for hour in (0..24).step_by(3) {
+            out.push_str(&format!("{hour:<6}"));
+        }
+        out.push('\n');
+
+        for (day, row) in grid.iter().enumerate() {
+            out.push_str(&format!("{:<5}", Self::WEEKDAYS[day]));
+            for &value in row {
+                let shade = Self::shade(value, max);
+                out.push(shade);
+                out.push(shade);
+            }
+            out.push('\n');
         }
+        out
+    }
 
-        format!(
-            "Response times: avg={:.1}s, median={:.1}s, p95={:.1}s (n={})",
-            self.avg_response_time_secs,
-            self.median_response_time_secs,
-            self.p95_response_time_secs,
-            self.sample_count
-        )
+    /// Map a value onto the shade ramp relative to `max`.
+    fn shade(value: u64, max: u64) -> char {
+        if value == 0 || max == 0 {
+            return Self::SHADES[0];
+        }
+        let steps = (Self::SHADES.len() - 1) as f64;
+        let level = ((value as f64 / max as f64) * steps).ceil() as usize;
+        Self::SHADES[level.clamp(1, Self::SHADES.len() - 1)]
     }
 }
 
@@ -1324,8 +3422,14 @@ pub struct EfficiencyMetrics {
     pub overall_cache_hit_rate: f64,
     /// Average session duration.
     pub avg_session_duration_mins: f64,
-    /// Thinking tokens ratio (thinking / total output).
+    /// Thinking share of output, as a percentage.
+    ///
+    /// Uses reported thinking tokens over output tokens when usage payloads
+    /// carry the split, and the thinking share of assistant text/thinking
+    /// blocks otherwise.
     pub thinking_ratio: f64,
+    /// Output-rate cost attributable to extended thinking, when reported.
+    pub thinking_cost: Option<f64>,
     /// Tool efficiency (successful / total tool uses).
     pub tool_success_rate: f64,
 }
@@ -1365,14 +3469,18 @@ impl EfficiencyMetrics {
             0.0
         };
 
-        // Thinking ratio: percentage of assistant output blocks that are thinking
+        // Thinking ratio: reported thinking tokens over output tokens, falling
+        // back to the share of assistant output blocks that are thinking.
         let thinking_blocks = analytics.message_counts.thinking_blocks as f64;
         let text_blocks = analytics.message_counts.text_blocks as f64;
-        let thinking_ratio = if thinking_blocks + text_blocks > 0.0 {
-            thinking_blocks / (thinking_blocks + text_blocks) * 100.0
-        } else {
-            0.0
-        };
+        let thinking_ratio = analytics.thinking_stats.token_ratio().unwrap_or(
+            if thinking_blocks + text_blocks > 0.0 {
+                thinking_blocks / (thinking_blocks + text_blocks) * 100.0
+            } else {
+                0.0
+            },
+        );
+        let thinking_cost = analytics.thinking_stats.estimated_cost();
 
         // Tool success rate: percentage of tool results that succeeded
         let tool_results = analytics.message_counts.tool_results as f64;
@@ -1389,6 +3497,7 @@ impl EfficiencyMetrics {
             overall_cache_hit_rate,
             avg_session_duration_mins,
             thinking_ratio,
+            thinking_cost,
             tool_success_rate,
         }
     }
@@ -1403,13 +3512,16 @@ impl EfficiencyMetrics {
              - Cache hit rate:         {:.1}%\n\
              - Avg session duration:   {:.0} minutes\n\
              - Thinking ratio:         {:.1}%\n\
-             - Tool success rate:      {:.1}%",
+             - Tool success rate:      {:.1}%{}",
             self.tokens_per_message,
             self.tool_uses_per_session,
             self.overall_cache_hit_rate,
             self.avg_session_duration_mins,
             self.thinking_ratio,
-            self.tool_success_rate
+            self.tool_success_rate,
+            self.thinking_cost
+                .map(|cost| format!("\n - Thinking cost:          ${cost:.4}"))
+                .unwrap_or_default()
         )
     }
 }
@@ -1801,6 +3913,13 @@ impl ProjectAnalytics {
         self.message_counts.tool_errors += session.message_counts.tool_errors;
         self.message_counts.thinking_blocks += session.message_counts.thinking_blocks;
         self.message_counts.text_blocks += session.message_counts.text_blocks;
+        self.thinking_stats.merge(&session.thinking_stats);
+        for (language, stats) in &session.file_stats.languages {
+            self.languages
+                .entry(language.clone())
+                .or_default()
+                .merge(stats);
+        }
 
         // Merge tool counts
         for (tool, count) in &session.tool_counts {
@@ -1816,11 +3935,65 @@ impl ProjectAnalytics {
         for (model, count) in &session.models_used {
             *self.model_usage.entry(model.clone()).or_insert(0) += *count as u64;
         }
+
+        self.file_modifications += session.file_stats.total_modifications;
+        self.model_switches.merge(&session.model_switches);
+
+        // Record the session's start day for streak tracking
+        if let Some(start) = session.start_time {
+            let day = start.with_timezone(&Local).date_naive();
+            *self.session_days.entry(day).or_insert(0) += 1;
+        }
+    }
+
+    /// Merge another aggregate into this one.
+    ///
+    /// Equivalent to having added the other aggregate's sessions here.
+    /// Re-price with [`Self::calculate_cost`] after merging.
+    pub fn merge(&mut self, other: &Self) {
+        self.session_count += other.session_count;
+        self.total_usage.merge(&other.total_usage);
+
+        self.message_counts.user += other.message_counts.user;
+        self.message_counts.assistant += other.message_counts.assistant;
+        self.message_counts.system += other.message_counts.system;
+        self.message_counts.tool_uses += other.message_counts.tool_uses;
+        self.message_counts.tool_results += other.message_counts.tool_results;
+        self.message_counts.tool_errors += other.message_counts.tool_errors;
+        self.message_counts.thinking_blocks += other.message_counts.thinking_blocks;
+        self.message_counts.text_blocks += other.message_counts.text_blocks;
+        self.thinking_stats.merge(&other.thinking_stats);
+        for (language, stats) in &other.languages {
+            self.languages
+                .entry(language.clone())
+                .or_default()
+                .merge(stats);
+        }
+
+        for (tool, count) in &other.tool_counts {
+            *self.tool_counts.entry(tool.clone()).or_insert(0) += count;
+        }
+        self.total_duration += other.total_duration;
+        for (model, count) in &other.model_usage {
+            *self.model_usage.entry(model.clone()).or_insert(0) += count;
+        }
+
+        self.file_modifications += other.file_modifications;
+        self.model_switches.merge(&other.model_switches);
+        for (day, count) in &other.session_days {
+            *self.session_days.entry(*day).or_insert(0) += count;
+        }
+    }
+
+    /// Activity streaks over the recorded session days, as of today.
+    pub fn streaks(&self) -> ActivityStreaks {
+        ActivityStreaks::from_days(&self.session_days, Local::now().date_naive())
     }
 
     /// Calculate estimated total cost.
     pub fn calculate_cost(&mut self) {
         self.total_usage.calculate_cost();
+        self.model_switches.calculate_cost();
     }
 }
 
@@ -2380,9 +4553,36 @@ mod tests {
         let stats = ThinkingStats {
             block_count: 5,
             total_chars: 500,
+            ..Default::default()
         };
 
         assert_eq!(stats.average_length(), 100);
+        assert_eq!(stats.token_ratio(), None);
+        assert_eq!(stats.estimated_cost(), None);
+    }
+
+    #[test]
+    fn test_thinking_stats_reported_tokens() {
+        let at = Utc::now();
+        let mut stats = ThinkingStats::default();
+        stats.record_turn("claude-sonnet-4-20250514", 300, 400, at);
+        stats.record_turn("unpriced-model", 100, 600, at);
+
+        assert_eq!(stats.reported_tokens, 400);
+        assert!((stats.token_ratio().unwrap() - 40.0).abs() < 0.01);
+        assert!((stats.by_model["claude-sonnet-4-20250514"].ratio() - 75.0).abs() < 0.01);
+        assert!(stats.by_model["unpriced-model"].estimated_cost.is_none());
+
+        let pricing =
+            crate::model::ModelPricing::for_model_at("claude-sonnet-4-20250514", at).unwrap();
+        let expected = 300.0 * pricing.output_per_million / 1_000_000.0;
+        assert!((stats.estimated_cost().unwrap() - expected).abs() < 1e-12);
+
+        let mut combined = ThinkingStats::default();
+        combined.merge(&stats);
+        combined.merge(&stats);
+        assert_eq!(combined.reported_tokens, 800);
+        assert_eq!(combined.by_model["unpriced-model"].output_tokens, 1200);
     }
 
     #[test]
@@ -2406,6 +4606,7 @@ mod tests {
             cache_hit_rate: 0.0,
             estimated_cost: Some(0.0042),
             unpriced_models: Vec::new(),
+            cache_savings: None,
             branch_count: 0,
             primary_model: None,
             subagent_count: 0,
@@ -2750,6 +4951,614 @@ mod tests {
         assert!(summary.contains("n=100"));
     }
 
+    #[test]
+    fn test_activity_heatmap_slots_and_peak() {
+        let mut heatmap = ActivityHeatmap::default();
+        assert!(heatmap.is_empty());
+        assert_eq!(heatmap.peak(HeatmapMetric::Messages), None);
+
+        heatmap.record_slot(Weekday::Mon, 9, 2, 100);
+        heatmap.record_slot(Weekday::Fri, 17, 5, 40);
+        heatmap.record_slot(Weekday::Sun, 99, 1, 0);
+
+        assert_eq!(heatmap.messages[0][9], 2);
+        assert_eq!(heatmap.messages[6][23], 1);
+        assert_eq!(heatmap.total(HeatmapMetric::Messages), 8);
+        assert_eq!(heatmap.total(HeatmapMetric::Tokens), 140);
+        assert_eq!(heatmap.peak(HeatmapMetric::Messages), Some((4, 17, 5)));
+        assert_eq!(heatmap.peak(HeatmapMetric::Tokens), Some((0, 9, 100)));
+
+        let mut merged = heatmap.clone();
+        merged.merge(&heatmap);
+        assert_eq!(merged.messages[4][17], 10);
+        assert_eq!(merged.tokens[0][9], 200);
+    }
+
+    #[test]
+    fn test_activity_heatmap_render() {
+        let mut heatmap = ActivityHeatmap::default();
+        heatmap.record_slot(Weekday::Tue, 0, 4, 0);
+        heatmap.record_slot(Weekday::Tue, 1, 1, 0);
+
+        let rendered = heatmap.render(HeatmapMetric::Messages);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].trim_start().starts_with("0     3"));
+        assert!(lines[2].starts_with("Tue  ██░░··"));
+        assert_eq!(lines[1], format!("Mon  {}", "·".repeat(48)));
+    }
+
+    #[test]
+    fn test_activity_heatmap_dedups_streaming_chunks() {
+        let assistant = |uuid: &str, parent: &str, output: u64| {
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "parentUuid": parent,
+                "timestamp": "2026-01-05T12:00:00Z",
+                "sessionId": "s",
+                "version": "2.1.0",
+                "message": {
+                    "id": "msg_A",
+                    "type": "message",
+                    "role": "assistant",
+                    "model": "claude-sonnet-5",
+                    "content": [{"type": "text", "text": "x"}],
+                    "usage": {"input_tokens": 10, "output_tokens": output}
+                }
+            }))
+            .unwrap()
+        };
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            assistant("a1", "root", 5),
+            assistant("a2", "a1", 30),
+        ])
+        .unwrap();
+        let heatmap = ActivityHeatmap::from_conversation(&conversation);
+
+        assert_eq!(heatmap.total(HeatmapMetric::Messages), 1);
+        assert_eq!(heatmap.total(HeatmapMetric::Tokens), 40);
+    }
+
+    #[test]
+    fn test_activity_streaks_current_and_longest() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let days: BTreeMap<NaiveDate, usize> = [
+            (day(1), 2),
+            (day(2), 1),
+            (day(3), 3),
+            (day(7), 1),
+            (day(8), 1),
+        ]
+        .into_iter()
+        .collect();
+
+        let streaks = ActivityStreaks::from_days(&days, day(9));
+        assert_eq!(streaks.longest_streak, 3);
+        assert_eq!(streaks.longest_start, Some(day(1)));
+        assert_eq!(streaks.longest_end, Some(day(3)));
+        // Yesterday was active, so the 7th-8th run is still current.
+        assert_eq!(streaks.current_streak, 2);
+        assert_eq!(streaks.active_days, 5);
+        assert!((streaks.avg_daily_sessions - 1.6).abs() < f64::EPSILON);
+
+        // A full idle day breaks the current streak.
+        assert_eq!(ActivityStreaks::from_days(&days, day(10)).current_streak, 0);
+    }
+
+    #[test]
+    fn test_activity_streaks_empty() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let streaks = ActivityStreaks::from_days(&BTreeMap::new(), today);
+        assert_eq!(streaks, ActivityStreaks::default());
+    }
+
+    #[test]
+    fn test_agent_breakdown_splits_inline_sidechain_and_subagents() {
+        let assistant = |uuid: &str, parent: &str, id: &str, sidechain: bool, tool: bool| {
+            let mut content = vec![serde_json::json!({"type": "text", "text": "x"})];
+            if tool {
+                content.push(serde_json::json!({
+                    "type": "tool_use", "id": format!("t_{uuid}"), "name": "Read", "input": {}
+                }));
+            }
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "parentUuid": parent,
+                "timestamp": "2026-01-05T12:00:00Z",
+                "sessionId": "s",
+                "version": "2.1.0",
+                "isSidechain": sidechain,
+                "agentId": if sidechain { Some("a1b2") } else { None },
+                "message": {
+                    "id": id,
+                    "type": "message",
+                    "role": "assistant",
+                    "model": "claude-sonnet-4-20250514",
+                    "content": content,
+                    "usage": {"input_tokens": 10, "output_tokens": 30}
+                }
+            }))
+            .unwrap()
+        };
+        let parent = SessionAnalytics::from_conversation(
+            &crate::reconstruction::Conversation::from_entries(vec![
+                assistant("p1", "root", "msg_1", false, true),
+                assistant("p2", "p1", "msg_2", true, true),
+            ])
+            .unwrap(),
+        );
+        assert_eq!(parent.sidechain.tool_uses, 1);
+        assert_eq!(parent.sidechain.agent_ids["a1b2"], 1);
+        assert_eq!(parent.sidechain.usage.usage.work_tokens(), 40);
+
+        let subagent = SessionAnalytics::from_conversation(
+            &crate::reconstruction::Conversation::from_entries(vec![assistant(
+                "s1", "root", "msg_3", true, false,
+            )])
+            .unwrap(),
+        );
+
+        let mut breakdown = AgentBreakdown::default();
+        breakdown.add_session(None, &parent);
+        breakdown.add_session(Some("Explore"), &subagent);
+
+        assert_eq!(breakdown.parent.sessions, 1);
+        assert_eq!(breakdown.parent.tokens, 40);
+        assert_eq!(breakdown.parent.tool_calls, 1);
+        let inline = &breakdown.by_agent_type[AgentBreakdown::INLINE_SIDECHAIN];
+        assert_eq!(
+            (inline.sessions, inline.tokens, inline.tool_calls),
+            (0, 40, 1)
+        );
+        assert_eq!(breakdown.by_agent_type["Explore"].sessions, 1);
+        assert!((breakdown.subagent_share() - 200.0 / 3.0).abs() < 0.01);
+
+        let parent_cost = breakdown.parent.estimated_cost.unwrap();
+        let inline_cost = inline.estimated_cost.unwrap();
+        assert!((parent_cost - inline_cost).abs() < 1e-12);
+        assert!((parent_cost + inline_cost - parent.usage.estimated_cost.unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tool_chain_transitions_and_chains() {
+        let mut stats = ToolChainStats::from_sequence(&["Read", "Edit", "Bash", "Read", "Edit"]);
+        assert_eq!(stats.sessions, 1);
+        assert_eq!(
+            stats.transitions[&("Read".to_string(), "Edit".to_string())],
+            2
+        );
+        assert!((stats.probability("Read", "Edit") - 1.0).abs() < f64::EPSILON);
+        assert!((stats.probability("Edit", "Bash") - 1.0).abs() < f64::EPSILON);
+        assert!(stats.probability("Bash", "Edit").abs() < f64::EPSILON);
+
+        let top = stats.top_chains(1);
+        assert_eq!(top[0].0, ["Read", "Edit", "Bash"]);
+        assert_eq!(top[0].1, 1);
+
+        stats.merge(&ToolChainStats::from_sequence(&["Read", "Grep"]));
+        stats.merge(&ToolChainStats::from_sequence(&[]));
+        assert_eq!(stats.sessions, 2);
+        assert!((stats.probability("Read", "Edit") - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.top_transitions(1), vec![("Read", "Edit", 2)]);
+    }
+
+    #[test]
+    fn test_linear_fit() {
+        let fit = LinearFit::from_points(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)]).unwrap();
+        assert!((fit.slope - 2.0).abs() < 1e-9);
+        assert!((fit.intercept - 1.0).abs() < 1e-9);
+        assert!((fit.r - 1.0).abs() < 1e-9);
+        assert_eq!(fit.n, 3);
+
+        assert!(LinearFit::from_points(&[(1.0, 2.0)]).is_none());
+        assert!(LinearFit::from_points(&[(1.0, 2.0), (1.0, 4.0)]).is_none());
+        let flat = LinearFit::from_points(&[(1.0, 2.0), (3.0, 2.0)]).unwrap();
+        assert!(flat.r.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_prompt_correlation_pairs_prompts_with_turns() {
+        let user = |uuid: &str, parent: Option<&str>, ts: &str, content: serde_json::Value| {
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "user", "uuid": uuid, "parentUuid": parent, "timestamp": ts,
+                "sessionId": "s", "version": "2.1.0",
+                "message": {"role": "user", "content": content}
+            }))
+            .unwrap()
+        };
+        let assistant = |uuid: &str, parent: &str, ts: &str, id: &str, output: u64| {
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "assistant", "uuid": uuid, "parentUuid": parent, "timestamp": ts,
+                "sessionId": "s", "version": "2.1.0",
+                "message": {"id": id, "type": "message", "role": "assistant",
+                    "model": "claude-sonnet-5", "content": [{"type": "text", "text": "x"}],
+                    "usage": {"input_tokens": 1, "output_tokens": output}}
+            }))
+            .unwrap()
+        };
+        let tool_result = serde_json::json!([
+            {"type": "tool_result", "tool_use_id": "t1", "content": "ok"}
+        ]);
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            user("u1", None, "2026-01-05T12:00:00Z", serde_json::json!("hi")),
+            assistant("a1", "u1", "2026-01-05T12:00:02Z", "m1", 5),
+            assistant("a2", "a1", "2026-01-05T12:00:03Z", "m1", 20),
+            user("u2", Some("a2"), "2026-01-05T12:00:04Z", tool_result),
+            assistant("a3", "u2", "2026-01-05T12:00:10Z", "m2", 30),
+            user(
+                "u3",
+                Some("a3"),
+                "2026-01-05T12:01:00Z",
+                serde_json::json!("x".repeat(150)),
+            ),
+            assistant("a4", "u3", "2026-01-05T12:01:05Z", "m3", 100),
+        ])
+        .unwrap();
+
+        let correlation = PromptCorrelation::from_conversation(&conversation);
+        assert_eq!(
+            correlation.samples,
+            vec![
+                PromptResponseSample {
+                    prompt_chars: 2,
+                    response_tokens: 50,
+                    latency_secs: Some(10.0),
+                },
+                PromptResponseSample {
+                    prompt_chars: 150,
+                    response_tokens: 100,
+                    latency_secs: Some(5.0),
+                },
+            ]
+        );
+
+        let buckets = correlation.buckets();
+        assert_eq!(buckets.len(), PromptCorrelation::BUCKETS.len());
+        assert_eq!((buckets[0].prompts, buckets[1].prompts), (1, 1));
+        assert_eq!(buckets[2].avg_latency_secs, None);
+        assert!(correlation.tokens_fit().unwrap().slope > 0.0);
+    }
+
+    #[test]
+    fn test_error_analytics_counts_retries_and_waste() {
+        let entry = |value: serde_json::Value| {
+            serde_json::from_value::<crate::model::LogEntry>(value).unwrap()
+        };
+        let assistant = |uuid: &str, parent: &str, ts: &str, id: &str, output: u64| {
+            entry(serde_json::json!({
+                "type": "assistant", "uuid": uuid, "parentUuid": parent, "timestamp": ts,
+                "sessionId": "s", "version": "2.1.0",
+                "message": {"id": id, "type": "message", "role": "assistant",
+                    "model": "claude-sonnet-5", "content": [{"type": "text", "text": "x"}],
+                    "usage": {"input_tokens": 1, "output_tokens": output}}
+            }))
+        };
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            entry(serde_json::json!({
+                "type": "user", "uuid": "u1", "parentUuid": null,
+                "timestamp": "2026-01-05T12:00:00Z", "sessionId": "s", "version": "2.1.0",
+                "message": {"role": "user", "content": "hi"}
+            })),
+            // A partial answer, then an overloaded error and a retried answer.
+            assistant("a1", "u1", "2026-01-05T12:00:01Z", "m1", 10),
+            entry(serde_json::json!({
+                "type": "system", "uuid": "s1", "parentUuid": "a1",
+                "timestamp": "2026-01-05T12:00:02Z", "subtype": "api_error", "level": "error",
+                "error": {"status": 529, "error": {"type": "error",
+                    "error": {"type": "overloaded_error", "message": "Overloaded"}}},
+                "retryAttempt": 10, "maxRetries": 10, "retryInMs": 500.0
+            })),
+            assistant("a2", "s1", "2026-01-05T12:00:05Z", "m2", 20),
+        ])
+        .unwrap();
+
+        let mut errors = ErrorAnalytics::from_conversation(&conversation);
+        assert_eq!(errors.api_errors, 1);
+        assert_eq!(errors.ranked_types(), vec![("overloaded_error", 1)]);
+        assert_eq!(errors.by_status[&529], 1);
+        assert_eq!((errors.retry_attempts, errors.retries_exhausted), (1, 1));
+        assert_eq!(errors.retried_turns, 1);
+        assert_eq!(errors.wasted_usage.usage.work_tokens(), 11);
+        assert!((errors.error_rate() - 1.0 / 3.0).abs() < 1e-9);
+
+        errors.merge(&ErrorAnalytics::default());
+        assert_eq!(errors.max_retry_attempt, 10);
+        assert_eq!(
+            errors.by_day.values().map(|day| day.errors).sum::<usize>(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_model_switches_split_usage_at_first_switch() {
+        let assistant = |uuid: &str, parent: Option<&str>, ts: &str, model: &str, output: u64| {
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "assistant", "uuid": uuid, "parentUuid": parent, "timestamp": ts,
+                "sessionId": "s", "version": "2.1.0",
+                "message": {"id": format!("m-{uuid}"), "type": "message", "role": "assistant",
+                    "model": model, "content": [{"type": "text", "text": "x"}],
+                    "usage": {"input_tokens": 0, "output_tokens": output}}
+            }))
+            .unwrap()
+        };
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            assistant("a1", None, "2026-01-05T12:00:00Z", "claude-sonnet-5", 10),
+            assistant(
+                "a2",
+                Some("a1"),
+                "2026-01-05T12:01:00Z",
+                "claude-opus-4-8",
+                20,
+            ),
+            assistant(
+                "a3",
+                Some("a2"),
+                "2026-01-05T12:02:00Z",
+                "claude-opus-4-8",
+                30,
+            ),
+            assistant(
+                "a4",
+                Some("a3"),
+                "2026-01-05T12:03:00Z",
+                "claude-sonnet-5",
+                40,
+            ),
+        ])
+        .unwrap();
+
+        let mut switches = ModelSwitchStats::from_conversation(&conversation);
+        assert_eq!((switches.switches, switches.sessions_switched), (2, 1));
+        assert_eq!(
+            switches.ranked_transitions(),
+            vec![
+                ("claude-opus-4-8", "claude-sonnet-5", 1),
+                ("claude-sonnet-5", "claude-opus-4-8", 1),
+            ]
+        );
+        assert_eq!(switches.before_switch.usage.output_tokens, 10);
+        assert_eq!(switches.after_switch.usage.output_tokens, 90);
+
+        let steady = crate::reconstruction::Conversation::from_entries(vec![assistant(
+            "b1",
+            None,
+            "2026-01-05T12:00:00Z",
+            "claude-sonnet-5",
+            5,
+        )])
+        .unwrap();
+        switches.merge(&ModelSwitchStats::from_conversation(&steady));
+        assert_eq!(switches.sessions_switched, 1);
+        assert_eq!(switches.before_switch.usage.output_tokens, 10);
+    }
+
+    #[test]
+    fn test_rolling_usage_buckets_entries_by_timestamp() {
+        let entry = |kind: &str, uuid: &str, parent: Option<&str>, ts: &str| {
+            let message = if kind == "user" {
+                serde_json::json!({"role": "user", "content": "hi"})
+            } else {
+                serde_json::json!({"id": format!("m-{uuid}"), "type": "message",
+                    "role": "assistant", "model": "claude-sonnet-5",
+                    "content": [{"type": "text", "text": "x"}],
+                    "usage": {"input_tokens": 10, "output_tokens": 5}})
+            };
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": kind, "uuid": uuid, "parentUuid": parent, "timestamp": ts,
+                "sessionId": "s", "version": "2.1.0", "message": message
+            }))
+            .unwrap()
+        };
+        // One exchange ten days ago, one an hour ago.
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            entry("user", "u1", None, "2026-01-20T12:00:00Z"),
+            entry("assistant", "a1", Some("u1"), "2026-01-20T12:00:01Z"),
+            entry("user", "u2", Some("a1"), "2026-01-30T11:00:00Z"),
+            entry("assistant", "a2", Some("u2"), "2026-01-30T11:00:01Z"),
+        ])
+        .unwrap();
+
+        let end = "2026-01-30T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let windows = [
+            ("24h".to_string(), Duration::hours(24)),
+            ("30d".to_string(), Duration::days(30)),
+        ];
+        let mut rolling = RollingUsage::new(end, &windows);
+        rolling.add_conversation(&conversation);
+        let mut other = RollingUsage::new(end, &windows);
+        other.merge(&rolling);
+        rolling.merge(&other);
+
+        let day = &rolling.windows[0];
+        assert_eq!((day.messages, day.sessions), (4, 2));
+        assert_eq!(day.usage.usage.work_tokens(), 30);
+        let month = &rolling.windows[1];
+        assert_eq!((month.messages, month.sessions), (8, 2));
+        assert_eq!(month.usage.usage.work_tokens(), 60);
+        assert_eq!(rolling.earliest_start(), Some(end - Duration::days(30)));
+    }
+
+    #[test]
+    fn test_git_branch_stats_attribute_entries_by_branch() {
+        let entry = |uuid: &str, parent: Option<&str>, time: &str, branch: &str| {
+            let kind = if uuid.starts_with('u') {
+                "user"
+            } else {
+                "assistant"
+            };
+            let message = if kind == "user" {
+                serde_json::json!({"role": "user", "content": "hi"})
+            } else {
+                serde_json::json!({"id": format!("m-{uuid}"), "type": "message",
+                    "role": "assistant", "model": "claude-sonnet-5",
+                    "content": [{"type": "text", "text": "x"}],
+                    "usage": {"input_tokens": 10, "output_tokens": 5}})
+            };
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": kind, "uuid": uuid, "parentUuid": parent,
+                "timestamp": format!("2026-01-20T{time}:00Z"),
+                "sessionId": "s", "version": "2.1.0", "gitBranch": branch,
+                "message": message
+            }))
+            .unwrap()
+        };
+        // Two minutes on main, a switch to feature/x, and an exchange after
+        // a two-hour break that adds no active time.
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            entry("u1", None, "12:00", "main"),
+            entry("a1", Some("u1"), "12:02", "main"),
+            entry("u2", Some("a1"), "12:03", "feature/x"),
+            entry("a2", Some("u2"), "12:04", "feature/x"),
+            entry("u3", Some("a2"), "14:04", ""),
+        ])
+        .unwrap();
+
+        let mut stats = GitBranchStats::default();
+        stats.add_conversation(&conversation);
+        stats.merge(&GitBranchStats::default());
+
+        let main = &stats.branches["main"];
+        assert_eq!((main.messages, main.sessions), (2, 1));
+        assert_eq!(main.active, Duration::minutes(2));
+        assert_eq!(main.usage.usage.work_tokens(), 15);
+        let feature = &stats.branches["feature/x"];
+        assert_eq!(feature.active, Duration::minutes(2));
+        let none = &stats.branches[GitBranchStats::NO_BRANCH];
+        assert_eq!((none.messages, none.active), (1, Duration::zero()));
+        let ranked: Vec<&str> = stats.ranked().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(ranked, ["feature/x", "main", GitBranchStats::NO_BRANCH]);
+    }
+
+    #[test]
+    fn test_abandoned_branches_measure_retried_work() {
+        let user = |uuid: &str, parent: Option<&str>, ts: &str| {
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "user", "uuid": uuid, "parentUuid": parent, "timestamp": ts,
+                "sessionId": "s", "version": "2.1.0",
+                "message": {"role": "user", "content": "prompt"}
+            }))
+            .unwrap()
+        };
+        let assistant = |uuid: &str, parent: &str, ts: &str, id: &str, output: u64| {
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "assistant", "uuid": uuid, "parentUuid": parent, "timestamp": ts,
+                "sessionId": "s", "version": "2.1.0",
+                "message": {"id": id, "type": "message", "role": "assistant",
+                    "model": "claude-sonnet-5", "content": [{"type": "text", "text": "x"}],
+                    "usage": {"input_tokens": 1, "output_tokens": output}}
+            }))
+            .unwrap()
+        };
+        // The second prompt was edited: the older attempt (u2, a2) is abandoned.
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            user("u1", None, "2026-01-05T12:00:00Z"),
+            assistant("a1", "u1", "2026-01-05T12:00:01Z", "m1", 10),
+            user("u2", Some("a1"), "2026-01-05T12:00:10Z"),
+            assistant("a2", "u2", "2026-01-05T12:00:11Z", "m2", 40),
+            user("u3", Some("a1"), "2026-01-05T12:01:00Z"),
+            assistant("a3", "u3", "2026-01-05T12:01:01Z", "m3", 20),
+        ])
+        .unwrap();
+
+        let stats = AbandonedBranchStats::from_conversation(&conversation);
+        assert_eq!(stats.branches, 1);
+        assert_eq!(stats.nodes, 2);
+        assert_eq!(stats.max_depth, 2);
+        assert!((stats.average_depth() - 2.0).abs() < f64::EPSILON);
+        assert_eq!(stats.tokens, 41);
+
+        let mut total = stats.clone();
+        total.merge(&AbandonedBranchStats::default());
+        assert_eq!(total.branches, 1);
+        assert_eq!(total.tokens, 41);
+    }
+
+    #[test]
+    fn test_edit_churn_counts_rewrites_of_earlier_edits() {
+        let mut churn = EditChurn::default();
+        churn.record_edit("src/lib.rs", "fn a() {}", "fn a() -> u32 {\n    1\n}");
+        // Replaces the line the previous edit wrote.
+        churn.record_edit("src/lib.rs", "fn a() -> u32 {", "fn a() -> u64 {");
+        // Unrelated region; a shared closing brace is not an overlap.
+        churn.record_edit("src/lib.rs", "fn b() {\n}", "fn b() {\n    todo!()\n}");
+        churn.record_edit("src/lib.rs", "fn a() -> u64 {", "pub fn a() -> u64 {");
+        churn.record_edit("src/main.rs", "fn a() -> u32 {", "fn main() {}");
+
+        assert_eq!(churn.edits, 5);
+        assert_eq!(churn.rewrites, 2);
+        assert_eq!(churn.files["src/lib.rs"].rewrites, 2);
+        assert_eq!(churn.files["src/main.rs"].rewrites, 0);
+        let hotspots = churn.hotspots();
+        assert_eq!(hotspots.len(), 1);
+        assert_eq!(hotspots[0].0, "src/lib.rs");
+        assert!((hotspots[0].1.rewrite_rate() - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_co_modification_graph_weights_shared_sessions() {
+        let mut graph = CoModificationGraph::default();
+        graph.add_session(["src/a.rs", "src/b.rs", "src/a.rs"]);
+        graph.add_session(["src/b.rs", "src/a.rs", "src/c.rs"]);
+        graph.add_session(std::iter::empty());
+
+        assert_eq!(graph.sessions, 2);
+        assert_eq!(graph.nodes["src/a.rs"], 2);
+        assert_eq!(graph.nodes["src/c.rs"], 1);
+        let edges = graph.ranked_edges();
+        assert_eq!(edges[0], ("src/a.rs", "src/b.rs", 2));
+        assert_eq!(edges.len(), 3);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph co_modification {\n"));
+        assert!(
+            dot.contains("  \"src/a.rs\" [label=\"a.rs\", tooltip=\"src/a.rs (2 sessions)\"];\n")
+        );
+        assert!(dot.contains("  \"src/a.rs\" -- \"src/b.rs\" [weight=2, label=\"2\"];\n"));
+    }
+
+    #[test]
+    fn test_token_timeline_dedupes_streamed_turns() {
+        let entry = |uuid: &str, parent: Option<&str>, id: &str, input: u64, output: u64| {
+            serde_json::from_value::<crate::model::LogEntry>(serde_json::json!({
+                "type": "assistant",
+                "uuid": uuid,
+                "parentUuid": parent,
+                "timestamp": "2025-01-15T10:00:00Z",
+                "sessionId": "s",
+                "version": "2.0.0",
+                "message": {
+                    "id": id,
+                    "type": "message",
+                    "role": "assistant",
+                    "model": "claude-sonnet-4-5",
+                    "content": [{"type": "text", "text": "x"}],
+                    "usage": {
+                        "input_tokens": input,
+                        "output_tokens": output,
+                        "cache_read_input_tokens": 100
+                    }
+                }
+            }))
+            .unwrap()
+        };
+        let conversation = crate::reconstruction::Conversation::from_entries(vec![
+            entry("a1", None, "msg_1", 10, 5),
+            // A second streaming chunk of the same response.
+            entry("a2", Some("a1"), "msg_1", 10, 5),
+            entry("a3", Some("a2"), "msg_2", 30, 7),
+        ])
+        .unwrap();
+
+        let timeline = TokenTimeline::from_conversation(&conversation);
+        assert_eq!(timeline.turns.len(), 2);
+        assert_eq!(timeline.turns[1].turn, 2);
+        assert_eq!(timeline.turns[0].context_tokens(), 110);
+        assert_eq!(timeline.peak_context(), 130);
+        assert_eq!(timeline.cumulative_output(), vec![5, 12]);
+    }
+
     #[test]
     fn test_efficiency_metrics_default() {
         let metrics = EfficiencyMetrics::default();
@@ -2785,6 +5594,7 @@ mod tests {
             overall_cache_hit_rate: 75.0,
             avg_session_duration_mins: 30.0,
             thinking_ratio: 0.0,
+            thinking_cost: None,
             tool_success_rate: 100.0,
         };
 
@@ -2812,6 +5622,15 @@ mod tests {
 
         // thinking_ratio = 20 / (20 + 80) * 100 = 20%
         assert!((metrics.thinking_ratio - 20.0).abs() < 0.01);
+        assert!(metrics.thinking_cost.is_none());
+
+        // Reported thinking tokens take precedence over the block share.
+        analytics
+            .thinking_stats
+            .record_turn("claude-sonnet-4-20250514", 50, 200, Utc::now());
+        let metrics = EfficiencyMetrics::from_project(&analytics);
+        assert!((metrics.thinking_ratio - 25.0).abs() < 0.01);
+        assert!(metrics.thinking_cost.is_some());
 
         // tool_success_rate = (50 - 5) / 50 * 100 = 90%
         assert!((metrics.tool_success_rate - 90.0).abs() < 0.01);
@@ -2906,6 +5725,32 @@ mod tests {
         assert_eq!(top[0].1.modification_count, 2);
     }
 
+    #[test]
+    fn test_language_for_path() {
+        assert_eq!(language_for_path("/src/main.rs"), "Rust");
+        assert_eq!(language_for_path("app/View.TSX"), "TypeScript");
+        assert_eq!(language_for_path("/repo/Dockerfile"), "Dockerfile");
+        assert_eq!(language_for_path("notes.xyz"), OTHER_LANGUAGE);
+        assert_eq!(language_for_path("LICENSE"), OTHER_LANGUAGE);
+    }
+
+    #[test]
+    fn test_file_modification_stats_languages() {
+        let mut stats = FileModificationStats::default();
+        stats.record_write("/src/lib.rs", "1\n2\n3", None);
+        stats.record_edit("/src/lib.rs", "1\n2\n3", "1", None);
+        stats.record_write("/app.py", "x", None);
+
+        let rust = &stats.languages["Rust"];
+        assert_eq!(rust.modifications, 2);
+        assert_eq!((rust.lines_added, rust.lines_removed), (3, 2));
+        assert_eq!(rust.net_lines(), 1);
+
+        let ranked = stats.top_languages();
+        assert_eq!(ranked[0].0, "Rust");
+        assert_eq!(ranked[1].0, "Python");
+    }
+
     #[test]
     fn test_file_modification_stats_net_lines() {
         let mut stats = FileModificationStats::default();
@@ -0,0 +1,290 @@
+//! Prometheus text exposition of usage analytics.
+//!
+//! Renders [`ProjectAnalytics`] as counters and gauges in the Prometheus text
+//! format (version 0.0.4) so usage can be scraped into Grafana. Values are
+//! totals over the transcripts on disk: token and cost counters only grow as
+//! sessions are added, and reset when transcripts are pruned, which
+//! Prometheus treats like a process restart.
+
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use super::ProjectAnalytics;
+use crate::model::usage::Usage;
+
+/// `Content-Type` for a scrape response carrying this format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Prometheus metric type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    /// Monotonic total.
+    Counter,
+    /// Point-in-time value.
+    Gauge,
+}
+
+impl MetricType {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+        }
+    }
+}
+
+/// Builder for a Prometheus text exposition document.
+#[derive(Debug, Default)]
+pub struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    /// Create an empty document.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a metric family with its `# HELP` and `# TYPE` lines.
+    pub fn family(&mut self, name: &str, help: &str, kind: MetricType) -> &mut Self {
+        let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {name} {}", kind.as_str());
+        self
+    }
+
+    /// Add one sample to the current family.
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) -> &mut Self {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                .collect();
+            let _ = write!(self.out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.out, " {}", format_value(value));
+        self
+    }
+
+    /// The finished document.
+    #[must_use]
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Escape a label value: backslash, double quote, and line feed.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Format a sample value, spelling infinities and NaN the Prometheus way.
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Token counts by type, for the `type` label.
+fn token_types(usage: &Usage) -> [(&'static str, u64); 4] {
+    [
+        ("input", usage.input_tokens),
+        ("output", usage.output_tokens),
+        ("cache_read", usage.cache_read_input_tokens.unwrap_or(0)),
+        (
+            "cache_creation",
+            usage.cache_creation_input_tokens.unwrap_or(0),
+        ),
+    ]
+}
+
+/// Render usage metrics for `combined` and each project in `by_project`.
+///
+/// Per-project families carry a `project` label; model families are summed
+/// across projects and carry a `model` label.
+#[must_use]
+pub fn render(
+    combined: &ProjectAnalytics,
+    by_project: &IndexMap<String, ProjectAnalytics>,
+) -> String {
+    let mut doc = Exposition::new();
+
+    doc.family("snatch_sessions", "Sessions on disk.", MetricType::Gauge);
+    for (project, analytics) in by_project {
+        doc.sample(
+            "snatch_sessions",
+            &[("project", project)],
+            analytics.session_count as f64,
+        );
+    }
+
+    doc.family(
+        "snatch_messages_total",
+        "User and assistant messages.",
+        MetricType::Counter,
+    );
+    for (project, analytics) in by_project {
+        doc.sample(
+            "snatch_messages_total",
+            &[("project", project)],
+            analytics.message_counts.conversation() as f64,
+        );
+    }
+
+    doc.family(
+        "snatch_tokens_total",
+        "Tokens by type.",
+        MetricType::Counter,
+    );
+    for (project, analytics) in by_project {
+        for (kind, tokens) in token_types(&analytics.total_usage.usage) {
+            doc.sample(
+                "snatch_tokens_total",
+                &[("project", project), ("type", kind)],
+                tokens as f64,
+            );
+        }
+    }
+
+    doc.family(
+        "snatch_estimated_cost_usd_total",
+        "Estimated API list cost in USD over priced models.",
+        MetricType::Counter,
+    );
+    for (project, analytics) in by_project {
+        if let Some(cost) = analytics.total_usage.estimated_cost {
+            doc.sample(
+                "snatch_estimated_cost_usd_total",
+                &[("project", project)],
+                cost,
+            );
+        }
+    }
+
+    doc.family(
+        "snatch_tool_invocations_total",
+        "Tool invocations by tool.",
+        MetricType::Counter,
+    );
+    for (project, analytics) in by_project {
+        for (tool, count) in &analytics.tool_counts {
+            doc.sample(
+                "snatch_tool_invocations_total",
+                &[("project", project), ("tool", tool)],
+                *count as f64,
+            );
+        }
+    }
+
+    doc.family(
+        "snatch_file_modifications_total",
+        "Edit and Write operations.",
+        MetricType::Counter,
+    );
+    for (project, analytics) in by_project {
+        doc.sample(
+            "snatch_file_modifications_total",
+            &[("project", project)],
+            analytics.file_modifications as f64,
+        );
+    }
+
+    doc.family(
+        "snatch_model_tokens_total",
+        "Tokens by model and type.",
+        MetricType::Counter,
+    );
+    for (model, usage) in &combined.total_usage.by_model {
+        for (kind, tokens) in token_types(usage) {
+            doc.sample(
+                "snatch_model_tokens_total",
+                &[("model", model), ("type", kind)],
+                tokens as f64,
+            );
+        }
+    }
+
+    doc.family(
+        "snatch_model_estimated_cost_usd_total",
+        "Estimated API list cost in USD by model.",
+        MetricType::Counter,
+    );
+    for (model, cost) in &combined.total_usage.cost_by_model {
+        doc.sample(
+            "snatch_model_estimated_cost_usd_total",
+            &[("model", model)],
+            cost.total_cost,
+        );
+    }
+
+    let streaks = combined.streaks();
+    doc.family(
+        "snatch_active_days",
+        "Distinct days with at least one session.",
+        MetricType::Gauge,
+    );
+    doc.sample("snatch_active_days", &[], streaks.active_days as f64);
+    doc.family(
+        "snatch_current_streak_days",
+        "Consecutive active days ending today or yesterday.",
+        MetricType::Gauge,
+    );
+    doc.sample(
+        "snatch_current_streak_days",
+        &[],
+        f64::from(streaks.current_streak),
+    );
+
+    doc.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposition_escapes_labels() {
+        let mut doc = Exposition::new();
+        doc.family("snatch_x", "Example.", MetricType::Counter)
+            .sample("snatch_x", &[("project", "a\"b\\c\nd")], 3.0)
+            .sample("snatch_x", &[], f64::INFINITY);
+        assert_eq!(
+            doc.finish(),
+            "# HELP snatch_x Example.\n# TYPE snatch_x counter\n\
+             snatch_x{project=\"a\\\"b\\\\c\\nd\"} 3\nsnatch_x +Inf\n"
+        );
+    }
+
+    #[test]
+    fn test_render_labels_projects() {
+        let project = || {
+            let mut project = ProjectAnalytics::default();
+            project.session_count = 2;
+            project.total_usage.usage.input_tokens = 10;
+            project.tool_counts.insert("Bash".to_string(), 4);
+            project
+        };
+        let mut by_project = IndexMap::new();
+        by_project.insert("/home/user/app".to_string(), project());
+
+        let text = render(&project(), &by_project);
+        assert!(text.contains("# TYPE snatch_tokens_total counter\n"));
+        assert!(text.contains("snatch_sessions{project=\"/home/user/app\"} 2\n"));
+        assert!(
+            text.contains("snatch_tokens_total{project=\"/home/user/app\",type=\"input\"} 10\n")
+        );
+        assert!(text.contains(
+            "snatch_tool_invocations_total{project=\"/home/user/app\",tool=\"Bash\"} 4\n"
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+//! Persisted per-session analytics snapshots.
+//!
+//! Global stats parse every transcript on disk, which gets slow once there
+//! are thousands of them. The snapshot store keeps each session's aggregate
+//! keyed by path, modification time, and size, so a later run only parses
+//! the sessions that changed since the last one.
+//!
+//! Data is stored in `analytics_snapshots.json` in the cache directory
+//! (`~/.cache/claude-snatch` by default). Snapshots hold raw counts only; costs are recomputed from them
+//! with the current rate cards on every run.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::ProjectAnalytics;
+use crate::config::CacheConfig;
+use crate::discovery::Session;
+use crate::error::{Result, SnatchError};
+
+/// Format version of the snapshot file. Bump when the serialized shape of
+/// [`ProjectAnalytics`] changes.
+const CURRENT_VERSION: u32 = 1;
+
+/// File name of the store within the cache directory.
+pub const FILENAME: &str = "analytics_snapshots.json";
+
+/// One session's aggregate and the file state it was computed from.
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotEntry {
+    /// Transcript path.
+    path: PathBuf,
+    /// Modification time in nanoseconds since the Unix epoch.
+    mtime_nanos: u64,
+    /// File size in bytes.
+    size: u64,
+    /// Single-session aggregate.
+    analytics: ProjectAnalytics,
+}
+
+/// On-disk layout of the store.
+#[derive(Debug, Deserialize)]
+struct PersistedSnapshots {
+    version: u32,
+    /// Version of snatch that computed the snapshots. Analytics logic changes
+    /// between releases, so snapshots from another version are discarded.
+    snatch_version: String,
+    entries: Vec<SnapshotEntry>,
+}
+
+/// Per-session analytics snapshots keyed by transcript path.
+#[derive(Debug, Default)]
+pub struct AnalyticsStore {
+    entries: HashMap<PathBuf, SnapshotEntry>,
+    /// Snapshots reused by [`Self::get`] since loading.
+    hits: usize,
+}
+
+/// Modification time of a session in nanoseconds since the Unix epoch.
+fn mtime_nanos(session: &Session) -> u64 {
+    session
+        .modified_time()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX))
+}
+
+impl AnalyticsStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store path for a cache configuration, in the directory given by
+    /// [`resolve_cache_dir`](crate::config::resolve_cache_dir).
+    pub fn path_for(cache: &CacheConfig) -> Result<PathBuf> {
+        Ok(crate::config::resolve_cache_dir(cache)?.join(FILENAME))
+    }
+
+    /// Load snapshots from `path`.
+    ///
+    /// A missing file, or one written by another format or snatch version,
+    /// yields an empty store.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let file = fs::File::open(path).map_err(|e| {
+            SnatchError::io(
+                format!("Failed to open analytics snapshots: {}", path.display()),
+                e,
+            )
+        })?;
+        let persisted: PersistedSnapshots =
+            serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+                SnatchError::SerializationError {
+                    context: format!("Failed to parse analytics snapshots: {}", path.display()),
+                    source: e,
+                }
+            })?;
+
+        if persisted.version != CURRENT_VERSION || persisted.snatch_version != crate::VERSION {
+            return Ok(Self::new());
+        }
+
+        Ok(Self {
+            entries: persisted
+                .entries
+                .into_iter()
+                .map(|entry| (entry.path.clone(), entry))
+                .collect(),
+            hits: 0,
+        })
+    }
+
+    /// Save snapshots to `path`, creating its directory if needed.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        #[derive(Serialize)]
+        struct Persisted<'a> {
+            version: u32,
+            snatch_version: &'a str,
+            entries: Vec<&'a SnapshotEntry>,
+        }
+
+        let mut entries: Vec<&SnapshotEntry> = self.entries.values().collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let json = serde_json::to_vec(&Persisted {
+            version: CURRENT_VERSION,
+            snatch_version: crate::VERSION,
+            entries,
+        })
+        .map_err(|e| SnatchError::SerializationError {
+            context: "Failed to serialize analytics snapshots".to_string(),
+            source: e,
+        })?;
+        crate::util::atomic_write(path, &json)
+    }
+
+    /// The snapshot for `session`, if the transcript is unchanged since it
+    /// was taken.
+    pub fn get(&mut self, session: &Session) -> Option<&ProjectAnalytics> {
+        let entry = self.entries.get(session.path())?;
+        if entry.mtime_nanos != mtime_nanos(session) || entry.size != session.file_size() {
+            return None;
+        }
+        self.hits += 1;
+        Some(&entry.analytics)
+    }
+
+    /// Record the aggregate computed for `session`.
+    pub fn insert(&mut self, session: &Session, analytics: ProjectAnalytics) {
+        self.entries.insert(
+            session.path().to_path_buf(),
+            SnapshotEntry {
+                path: session.path().to_path_buf(),
+                mtime_nanos: mtime_nanos(session),
+                size: session.file_size(),
+                analytics,
+            },
+        );
+    }
+
+    /// Drop snapshots of transcripts not in `sessions`, so deleted sessions
+    /// do not accumulate in the file.
+    pub fn retain_sessions(&mut self, sessions: &[Session]) {
+        let live: HashSet<&Path> = sessions.iter().map(Session::path).collect();
+        self.entries.retain(|path, _| live.contains(path.as_path()));
+    }
+
+    /// Drop the snapshot of the transcript at `path`, returning whether
+    /// there was one.
+    pub fn remove(&mut self, path: &Path) -> bool {
+        self.entries.remove(path).is_some()
+    }
+
+    /// Number of stored snapshots.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the store has no snapshots.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Snapshots reused since loading.
+    #[must_use]
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FIRST: &str = "11111111-1111-1111-1111-111111111111";
+    const SECOND: &str = "22222222-2222-2222-2222-222222222222";
+
+    fn session(dir: &Path, id: &str, content: &str) -> Session {
+        let path = dir.join(format!("{id}.jsonl"));
+        fs::write(&path, content).unwrap();
+        Session::from_path(&path, "/home/user/app").unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_and_invalidation() {
+        let dir = TempDir::new().unwrap();
+        let first = session(dir.path(), FIRST, "{}\n");
+        let second = session(dir.path(), SECOND, "{}\n");
+
+        let mut analytics = ProjectAnalytics::default();
+        analytics.session_count = 1;
+        analytics.total_usage.usage.output_tokens = 42;
+        analytics.model_switches.transitions.insert(
+            (
+                "claude-sonnet-4-5".to_string(),
+                "claude-opus-4-5".to_string(),
+            ),
+            1,
+        );
+
+        let mut store = AnalyticsStore::new();
+        store.insert(&first, analytics);
+        store.insert(&second, ProjectAnalytics::default());
+        let path = dir.path().join("cache").join("snapshots.json");
+        store.save_to(&path).unwrap();
+
+        let mut loaded = AnalyticsStore::load_from(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        let restored = loaded.get(&first).unwrap();
+        assert_eq!(restored.total_usage.usage.output_tokens, 42);
+        assert_eq!(restored.model_switches.transitions.len(), 1);
+        assert_eq!(loaded.hits(), 1);
+
+        // A transcript that grew since the snapshot must be recomputed.
+        let grown = session(dir.path(), FIRST, "{}\n{}\n");
+        assert!(loaded.get(&grown).is_none());
+
+        loaded.retain_sessions(std::slice::from_ref(&grown));
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded.remove(grown.path()));
+        assert!(!loaded.remove(grown.path()));
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_from_other_version_is_discarded() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("snapshots.json");
+        fs::write(
+            &path,
+            r#"{"version":1,"snatch_version":"0.0.0-old","entries":[]}"#,
+        )
+        .unwrap();
+        assert!(AnalyticsStore::load_from(&path).unwrap().is_empty());
+    }
+}
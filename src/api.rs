@@ -89,7 +89,7 @@ pub enum ExportFormat {
 }
 
 /// Simplified project information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ProjectInfo {
     /// Decoded project path.
     pub path: String,
@@ -98,7 +98,7 @@ pub struct ProjectInfo {
 }
 
 /// Simplified session information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct SessionInfo {
     /// Session ID.
     pub id: String,
@@ -111,7 +111,7 @@ pub struct SessionInfo {
 }
 
 /// Session analytics summary.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct AnalyticsSummary {
     /// Total messages.
     pub total_messages: usize,
@@ -135,6 +135,23 @@ pub struct AnalyticsSummary {
     pub primary_model: Option<String>,
 }
 
+/// A regex match found by [`SnatchClient::search`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchMatch {
+    /// Session containing the match.
+    pub session_id: String,
+    /// Project path the session belongs to.
+    pub project_path: String,
+    /// UUID of the matching entry.
+    pub uuid: Option<String>,
+    /// Timestamp of the matching entry.
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// The matched text.
+    pub matched: String,
+    /// The match with surrounding context.
+    pub context: String,
+}
+
 impl SnatchClient {
     /// Create a new client with auto-discovery of Claude Code data.
     ///
@@ -208,6 +225,15 @@ impl SnatchClient {
         Ok(sessions.iter().map(|s| self.session_to_info(s)).collect())
     }
 
+    /// List recent sessions whose project path contains `project`.
+    pub fn recent_project_sessions(&self, project: &str, limit: usize) -> Result<Vec<SessionInfo>> {
+        let mut sessions = self.claude_dir.all_sessions()?;
+        sessions.retain(|s| s.display_project_path().contains(project));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.modified_time()));
+        sessions.truncate(limit);
+        Ok(sessions.iter().map(|s| self.session_to_info(s)).collect())
+    }
+
     /// List sessions matching a filter.
     pub fn filtered_sessions(&self, filter: &SessionFilter) -> Result<Vec<SessionInfo>> {
         let sessions = self.claude_dir.all_sessions()?;
@@ -390,6 +416,47 @@ impl SnatchClient {
         std::fs::write(path, content).map_err(|e| SnatchError::io("Failed to write export file", e))
     }
 
+    /// Search user and assistant text of every session, newest first, for a
+    /// case-insensitive regex, returning at most `limit` matches.
+    ///
+    /// Sessions that fail to parse are skipped.
+    pub fn search(&self, pattern: &str, limit: usize) -> Result<Vec<SearchMatch>> {
+        let regex = regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| SnatchError::InvalidArgument {
+                name: "pattern".to_string(),
+                reason: e.to_string(),
+            })?;
+        let mut sessions = self.claude_dir.all_sessions()?;
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.modified_time()));
+
+        let mut matches = Vec::new();
+        for session in &sessions {
+            let Ok(entries) = session.parse() else {
+                continue;
+            };
+            for entry in &entries {
+                for (matched, context) in
+                    crate::analysis::search::search_entry_text(entry, &regex, "text", 80)
+                {
+                    if matches.len() == limit {
+                        return Ok(matches);
+                    }
+                    matches.push(SearchMatch {
+                        session_id: session.session_id().to_string(),
+                        project_path: session.display_project_path(),
+                        uuid: entry.uuid().map(String::from),
+                        timestamp: entry.timestamp(),
+                        matched,
+                        context,
+                    });
+                }
+            }
+        }
+        Ok(matches)
+    }
+
     /// Parse a JSONL file directly.
     pub fn parse_jsonl_file(&self, path: impl AsRef<Path>) -> Result<Vec<LogEntry>> {
         let mut parser = JsonlParser::new();
@@ -410,6 +477,7 @@ impl SnatchClient {
         crate::provider::registry::ProviderRegistry::with_config(
             &crate::provider::registry::RegistryConfig {
                 claude_root: Some(self.claude_dir.root().to_path_buf()),
+                claude_peers: self.claude_dir.peers().to_vec(),
                 codex_root: self.codex_dir.clone(),
                 max_file_size: None,
             },
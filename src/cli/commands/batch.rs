@@ -0,0 +1,699 @@
+//! Batch command implementation.
+//!
+//! `snatch batch run jobs.toml` executes the export jobs declared in a TOML
+//! manifest. Each `[[job]]` table selects sessions (project, date range, tags,
+//! explicit ids), picks a format and export options, and names its output
+//! files through a template; `[defaults]` supplies values for every job.
+//!
+//! ```toml
+//! [defaults]
+//! output = "exports/{job}/{date}-{short_id}.{ext}"
+//! thinking = false
+//!
+//! [[job]]
+//! name = "weekly-html"
+//! project = "myapp"
+//! since = "7d"
+//! format = "html"
+//! dark = true
+//! ```
+//!
+//! Every job's options are checked before anything is written, and the
+//! exports then run in parallel on the global thread pool (`--threads`).
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use clap::Parser;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{
+    BatchArgs, BatchRunArgs, BatchSubcommand, Cli, ExportArgs, ExportFormatArg, OutputFormat,
+};
+use crate::discovery::{Session, SessionFilter};
+use crate::error::{Result, SnatchError};
+use crate::tags::TagStore;
+use crate::util::truncate_path;
+
+use super::{get_claude_dir, parse_date_filter};
+
+/// Placeholders accepted in a job's output template.
+const PLACEHOLDERS: &[&str] = &["job", "session_id", "short_id", "project", "date", "ext"];
+
+/// A batch manifest.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    /// Values used by every job that does not set them itself.
+    #[serde(default)]
+    defaults: JobSpec,
+    /// The export jobs, in manifest order.
+    #[serde(default, rename = "job")]
+    jobs: Vec<JobSpec>,
+}
+
+/// One `[[job]]` (or `[defaults]`) table. Unset fields fall back to the
+/// defaults, then to `snatch export`'s own defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct JobSpec {
+    name: Option<String>,
+    // Selection.
+    sessions: Option<Vec<String>>,
+    project: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    tags: Option<Vec<String>>,
+    subagents: Option<bool>,
+    limit: Option<usize>,
+    // Output.
+    format: Option<String>,
+    output: Option<String>,
+    overwrite: Option<bool>,
+    // Export options.
+    thinking: Option<bool>,
+    tool_use: Option<bool>,
+    tool_results: Option<bool>,
+    images: Option<bool>,
+    timestamps: Option<bool>,
+    usage: Option<bool>,
+    system: Option<bool>,
+    metadata: Option<bool>,
+    annotations: Option<bool>,
+    main_thread: Option<bool>,
+    chain: Option<bool>,
+    full: Option<bool>,
+    pretty: Option<bool>,
+    resolve_tool_results: Option<bool>,
+    toc: Option<bool>,
+    dark: Option<bool>,
+    redact: Option<String>,
+    only: Option<Vec<String>>,
+}
+
+impl JobSpec {
+    /// Fill unset fields from `defaults`.
+    fn or(self, defaults: &JobSpec) -> JobSpec {
+        let d = defaults.clone();
+        JobSpec {
+            name: self.name,
+            sessions: self.sessions.or(d.sessions),
+            project: self.project.or(d.project),
+            since: self.since.or(d.since),
+            until: self.until.or(d.until),
+            tags: self.tags.or(d.tags),
+            subagents: self.subagents.or(d.subagents),
+            limit: self.limit.or(d.limit),
+            format: self.format.or(d.format),
+            output: self.output.or(d.output),
+            overwrite: self.overwrite.or(d.overwrite),
+            thinking: self.thinking.or(d.thinking),
+            tool_use: self.tool_use.or(d.tool_use),
+            tool_results: self.tool_results.or(d.tool_results),
+            images: self.images.or(d.images),
+            timestamps: self.timestamps.or(d.timestamps),
+            usage: self.usage.or(d.usage),
+            system: self.system.or(d.system),
+            metadata: self.metadata.or(d.metadata),
+            annotations: self.annotations.or(d.annotations),
+            main_thread: self.main_thread.or(d.main_thread),
+            chain: self.chain.or(d.chain),
+            full: self.full.or(d.full),
+            pretty: self.pretty.or(d.pretty),
+            resolve_tool_results: self.resolve_tool_results.or(d.resolve_tool_results),
+            toc: self.toc.or(d.toc),
+            dark: self.dark.or(d.dark),
+            redact: self.redact.or(d.redact),
+            only: self.only.or(d.only),
+        }
+    }
+
+    /// The `snatch export` arguments equivalent to this job's options.
+    fn export_argv(&self) -> Vec<String> {
+        let mut argv: Vec<String> = vec!["export".into()];
+        if let Some(format) = &self.format {
+            argv.extend(["--format".into(), format.clone()]);
+        }
+        // Options `snatch export` enables by default are disabled with --no-*.
+        for (value, flag) in [
+            (self.thinking, "thinking"),
+            (self.tool_use, "tool-use"),
+            (self.tool_results, "tool-results"),
+            (self.images, "images"),
+            (self.timestamps, "timestamps"),
+            (self.usage, "usage"),
+        ] {
+            if value == Some(false) {
+                argv.push(format!("--no-{flag}"));
+            }
+        }
+        for (value, flag) in [
+            (self.system, "system"),
+            (self.metadata, "metadata"),
+            (self.annotations, "annotations"),
+            (self.main_thread, "main-thread"),
+            (self.full, "full"),
+            (self.pretty, "pretty"),
+            (self.resolve_tool_results, "resolve-tool-results"),
+            (self.toc, "toc"),
+            (self.dark, "dark"),
+        ] {
+            if value == Some(true) {
+                argv.push(format!("--{flag}"));
+            }
+        }
+        if let Some(level) = &self.redact {
+            argv.extend(["--redact".into(), level.clone()]);
+        }
+        if let Some(only) = self.only.as_ref().filter(|only| !only.is_empty()) {
+            argv.extend(["--only".into(), only.join(",")]);
+        }
+        argv
+    }
+}
+
+/// A validated job, ready to select sessions and export.
+struct Job {
+    name: String,
+    spec: JobSpec,
+    args: ExportArgs,
+    output: String,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+}
+
+/// One file to write.
+struct Task {
+    job: usize,
+    session: Session,
+    path: PathBuf,
+}
+
+/// What happened to one task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TaskStatus {
+    Exported,
+    Skipped,
+    Empty,
+    Failed,
+}
+
+/// Per-task result for the summary.
+#[derive(Debug, Serialize)]
+struct TaskOutcome {
+    job: String,
+    session_id: String,
+    path: PathBuf,
+    status: TaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Per-job counts for the summary.
+#[derive(Debug, Default, Serialize)]
+struct JobSummary {
+    sessions: usize,
+    exported: usize,
+    skipped: usize,
+    empty: usize,
+    failed: usize,
+}
+
+/// The batch run report (`-o json`).
+#[derive(Debug, Serialize)]
+struct BatchReport {
+    manifest: PathBuf,
+    preview: bool,
+    jobs: BTreeMap<String, JobSummary>,
+    tasks: Vec<TaskOutcome>,
+}
+
+/// Run the batch command.
+pub fn run(cli: &Cli, args: &BatchArgs) -> Result<()> {
+    match &args.command {
+        BatchSubcommand::Run(run_args) => run_manifest(cli, run_args),
+    }
+}
+
+/// Execute the jobs of a manifest.
+fn run_manifest(cli: &Cli, args: &BatchRunArgs) -> Result<()> {
+    let text = fs::read_to_string(&args.manifest).map_err(|e| {
+        SnatchError::io(
+            format!("Failed to read manifest {}", args.manifest.display()),
+            e,
+        )
+    })?;
+    let manifest: Manifest = toml::from_str(&text).map_err(|e| SnatchError::ConfigError {
+        message: format!("Invalid manifest {}: {e}", args.manifest.display()),
+    })?;
+    let jobs = validate(manifest, &args.jobs)?;
+
+    // Relative output paths are resolved against the manifest's directory so
+    // a manifest behaves the same wherever it is run from.
+    let base = args
+        .manifest
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let tasks = plan(cli, &jobs, &base)?;
+
+    let outcomes: Vec<TaskOutcome> = if args.preview {
+        tasks
+            .iter()
+            .map(|task| {
+                let job = &jobs[task.job];
+                let status = if task.path.exists() && !overwrite(job, args) {
+                    TaskStatus::Skipped
+                } else {
+                    TaskStatus::Exported
+                };
+                outcome(job, task, status, None)
+            })
+            .collect()
+    } else {
+        tasks
+            .par_iter()
+            .map(|task| execute(cli, &jobs[task.job], task, overwrite(&jobs[task.job], args)))
+            .collect()
+    };
+
+    let mut summaries: BTreeMap<String, JobSummary> = jobs
+        .iter()
+        .map(|job| (job.name.clone(), JobSummary::default()))
+        .collect();
+    for task in &outcomes {
+        let summary = summaries.entry(task.job.clone()).or_default();
+        summary.sessions += 1;
+        match task.status {
+            TaskStatus::Exported => summary.exported += 1,
+            TaskStatus::Skipped => summary.skipped += 1,
+            TaskStatus::Empty => summary.empty += 1,
+            TaskStatus::Failed => summary.failed += 1,
+        }
+    }
+    let failed = outcomes
+        .iter()
+        .filter(|task| task.status == TaskStatus::Failed)
+        .count();
+    let report = BatchReport {
+        manifest: args.manifest.clone(),
+        preview: args.preview,
+        jobs: summaries,
+        tasks: outcomes,
+    };
+
+    match cli.effective_output() {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => print_report(cli, &jobs, &report),
+    }
+
+    if failed > 0 {
+        return Err(SnatchError::export(format!(
+            "{failed} batch export{} failed",
+            if failed == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
+
+/// Whether existing output files are replaced for a job.
+fn overwrite(job: &Job, args: &BatchRunArgs) -> bool {
+    args.overwrite || job.spec.overwrite.unwrap_or(false)
+}
+
+/// Merge defaults into each job and check every option up front, so a typo in
+/// the last job is reported before the first one writes anything.
+fn validate(manifest: Manifest, only: &[String]) -> Result<Vec<Job>> {
+    let invalid = |reason: String| SnatchError::ConfigError { message: reason };
+    if manifest.jobs.is_empty() {
+        return Err(invalid("manifest defines no [[job]] tables".to_string()));
+    }
+    if manifest.defaults.name.is_some() {
+        return Err(invalid("[defaults] cannot set a job name".to_string()));
+    }
+
+    let mut jobs: Vec<Job> = Vec::new();
+    for (position, spec) in manifest.jobs.into_iter().enumerate() {
+        let name = spec
+            .name
+            .clone()
+            .ok_or_else(|| invalid(format!("job #{} has no name", position + 1)))?;
+        if jobs.iter().any(|job| job.name == name) {
+            return Err(invalid(format!("duplicate job name '{name}'")));
+        }
+        let spec = spec.or(&manifest.defaults);
+
+        let args = ExportArgs::try_parse_from(spec.export_argv())
+            .map_err(|e| invalid(format!("job '{name}': {}", first_line(&e.to_string()))))?;
+        if matches!(
+            args.format(),
+            ExportFormatArg::Native | ExportFormatArg::Archive
+        ) {
+            return Err(invalid(format!(
+                "job '{name}': native and archive exports are provider-routed; use `snatch export`"
+            )));
+        }
+
+        let output = spec
+            .output
+            .clone()
+            .ok_or_else(|| invalid(format!("job '{name}' has no output template")))?;
+        check_template(&output).map_err(|reason| invalid(format!("job '{name}': {reason}")))?;
+
+        let date = |value: Option<&String>| value.map(|v| parse_date_filter(v)).transpose();
+        let since = date(spec.since.as_ref())?;
+        let until = date(spec.until.as_ref())?;
+
+        jobs.push(Job {
+            name,
+            spec,
+            args,
+            output,
+            since,
+            until,
+        });
+    }
+
+    if !only.is_empty() {
+        if let Some(unknown) = only
+            .iter()
+            .find(|n| !jobs.iter().any(|job| &job.name == *n))
+        {
+            return Err(SnatchError::InvalidArgument {
+                name: "job".to_string(),
+                reason: format!("no job named '{unknown}' in the manifest"),
+            });
+        }
+        jobs.retain(|job| only.contains(&job.name));
+    }
+    Ok(jobs)
+}
+
+/// The first line of a clap error, without its usage footer.
+fn first_line(message: &str) -> &str {
+    message
+        .lines()
+        .next()
+        .unwrap_or(message)
+        .trim_start_matches("error: ")
+}
+
+/// Check that a template only uses known placeholders and closes its braces.
+fn check_template(template: &str) -> std::result::Result<(), String> {
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            return Err(format!("unclosed '{{' in output template '{template}'"));
+        };
+        let name = &rest[open + 1..open + close];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "unknown placeholder {{{name}}} in output template (expected one of: {})",
+                PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{p}}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &rest[open + close + 1..];
+    }
+    Ok(())
+}
+
+/// Substitute placeholder values into an output template.
+fn render(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = template.to_string();
+    for (name, value) in values {
+        out = out.replace(&format!("{{{name}}}"), value);
+    }
+    out
+}
+
+/// A project path as a single path component.
+fn project_slug(project_path: &str) -> String {
+    let slug = project_path
+        .trim_matches(['/', '\\'])
+        .replace(['/', '\\', ':'], "_");
+    if slug.is_empty() {
+        "root".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Select each job's sessions and render their output paths.
+fn plan(cli: &Cli, jobs: &[Job], base: &Path) -> Result<Vec<Task>> {
+    let claude_dir = get_claude_dir(cli)?;
+    let all_sessions = claude_dir.all_sessions()?;
+    let tag_store = if jobs.iter().any(|job| job.spec.tags.is_some()) {
+        TagStore::load()?
+    } else {
+        TagStore::default()
+    };
+
+    let mut tasks = Vec::new();
+    let mut claimed: HashMap<PathBuf, &str> = HashMap::new();
+    for (index, job) in jobs.iter().enumerate() {
+        let mut filter = SessionFilter::new();
+        if !job.spec.subagents.unwrap_or(false) {
+            filter = filter.main_only();
+        }
+        filter.modified_after = job.since;
+        filter.modified_before = job.until;
+        let tags: Vec<&str> = job.spec.tags.iter().flatten().map(String::as_str).collect();
+
+        let selected: Vec<Session> = match &job.spec.sessions {
+            Some(ids) => ids
+                .iter()
+                .map(|id| {
+                    claude_dir
+                        .find_session(id)?
+                        .ok_or_else(|| SnatchError::SessionNotFound {
+                            session_id: id.clone(),
+                        })
+                })
+                .collect::<Result<_>>()?,
+            None => all_sessions
+                .iter()
+                .filter(|s| {
+                    job.spec
+                        .project
+                        .as_ref()
+                        .is_none_or(|project| s.project_path().contains(project.as_str()))
+                        && (tags.is_empty()
+                            || super::helpers::session_has_any_tag(
+                                &tag_store,
+                                s.session_id(),
+                                &tags,
+                            ))
+                        && filter.matches(s).unwrap_or_default()
+                })
+                .take(job.spec.limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect(),
+        };
+
+        for session in selected {
+            let date: chrono::DateTime<chrono::Local> = session.modified_time().into();
+            let values = HashMap::from([
+                ("job", job.name.clone()),
+                ("session_id", session.session_id().to_string()),
+                ("short_id", session.short_id().to_string()),
+                ("project", project_slug(session.project_path())),
+                ("date", date.format("%Y-%m-%d").to_string()),
+                (
+                    "ext",
+                    super::export::get_format_extension(job.args.format()).to_string(),
+                ),
+            ]);
+            let path = base.join(render(&job.output, &values));
+            if let Some(other) = claimed.insert(path.clone(), &job.name) {
+                return Err(SnatchError::ConfigError {
+                    message: format!(
+                        "jobs '{other}' and '{}' both write {}; add {{session_id}} or {{job}} to the output template",
+                        job.name,
+                        path.display()
+                    ),
+                });
+            }
+            tasks.push(Task {
+                job: index,
+                session,
+                path,
+            });
+        }
+    }
+    Ok(tasks)
+}
+
+/// Export one session to its output file.
+fn execute(cli: &Cli, job: &Job, task: &Task, overwrite: bool) -> TaskOutcome {
+    if task.path.exists() && !overwrite {
+        return outcome(job, task, TaskStatus::Skipped, None);
+    }
+    let result = task
+        .path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map_or(Ok(()), |dir| {
+            fs::create_dir_all(dir)
+                .map_err(|e| SnatchError::io(format!("Failed to create {}", dir.display()), e))
+        })
+        .and_then(|()| {
+            super::export::export_session(
+                cli,
+                &job.args,
+                &task.session,
+                Some(&task.path),
+                job.spec.chain.unwrap_or(false),
+            )
+        });
+    match result {
+        Ok(true) => outcome(job, task, TaskStatus::Exported, None),
+        Ok(false) => outcome(job, task, TaskStatus::Empty, None),
+        Err(e) => outcome(job, task, TaskStatus::Failed, Some(e.to_string())),
+    }
+}
+
+/// The summary entry for a task.
+fn outcome(job: &Job, task: &Task, status: TaskStatus, error: Option<String>) -> TaskOutcome {
+    TaskOutcome {
+        job: job.name.clone(),
+        session_id: task.session.session_id().to_string(),
+        path: task.path.clone(),
+        status,
+        error,
+    }
+}
+
+/// Print the human-readable summary.
+fn print_report(cli: &Cli, jobs: &[Job], report: &BatchReport) {
+    if report.preview {
+        for task in &report.tasks {
+            let note = if task.status == TaskStatus::Skipped {
+                " (exists, skipped)"
+            } else {
+                ""
+            };
+            println!(
+                "{:<16} {}  →  {}{note}",
+                task.job,
+                &task.session_id[..task.session_id.len().min(8)],
+                truncate_path(&task.path.display().to_string(), 70)
+            );
+        }
+        if !report.tasks.is_empty() {
+            println!();
+        }
+    } else {
+        for task in &report.tasks {
+            if let Some(error) = &task.error {
+                eprintln!("✗ {} {}: {error}", task.job, task.session_id);
+            } else if cli.verbose && task.status == TaskStatus::Skipped {
+                eprintln!("Skipped (exists): {}", task.path.display());
+            }
+        }
+    }
+
+    println!(
+        "{:<20} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        "Job", "Sessions", "Exported", "Skipped", "Empty", "Failed"
+    );
+    for job in jobs {
+        let summary = &report.jobs[&job.name];
+        println!(
+            "{:<20} {:>8} {:>8} {:>8} {:>8} {:>8}",
+            job.name,
+            summary.sessions,
+            summary.exported,
+            summary.skipped,
+            summary.empty,
+            summary.failed
+        );
+    }
+    if report.preview && !cli.quiet {
+        eprintln!();
+        eprintln!("Preview only; run without --preview to export.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(text: &str) -> Manifest {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_validate_merges_defaults_into_jobs() {
+        let jobs = validate(
+            manifest(
+                r#"
+                [defaults]
+                output = "out/{job}/{short_id}.{ext}"
+                thinking = false
+
+                [[job]]
+                name = "html"
+                format = "html"
+                dark = true
+
+                [[job]]
+                name = "md"
+                thinking = true
+                only = ["user", "assistant"]
+                "#,
+            ),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].args.format(), ExportFormatArg::Html);
+        assert!(jobs[0].args.no_thinking && jobs[0].args.dark);
+        assert_eq!(jobs[1].args.format(), ExportFormatArg::Markdown);
+        assert!(!jobs[1].args.no_thinking);
+        assert_eq!(jobs[1].args.only.len(), 2);
+        assert_eq!(jobs[1].output, "out/{job}/{short_id}.{ext}");
+
+        let only_md = validate(
+            manifest(
+                "[[job]]\nname = \"a\"\noutput = \"a\"\n[[job]]\nname = \"b\"\noutput = \"b\"",
+            ),
+            &["b".to_string()],
+        )
+        .unwrap();
+        assert_eq!(only_md.len(), 1);
+        assert_eq!(only_md[0].name, "b");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_jobs() {
+        let err = |text: &str| validate(manifest(text), &[]).err().unwrap().to_string();
+        assert!(err("[[job]]\noutput = \"x\"").contains("no name"));
+        assert!(err("[[job]]\nname = \"a\"").contains("no output template"));
+        assert!(err("[[job]]\nname = \"a\"\noutput = \"{id}.md\"").contains("{id}"));
+        assert!(err("[[job]]\nname = \"a\"\noutput = \"x\"\nformat = \"pdf\"").contains("'a'"));
+        assert!(toml::from_str::<Manifest>("[[job]]\nname = \"a\"\nformt = \"md\"").is_err());
+    }
+
+    #[test]
+    fn test_render_substitutes_placeholders() {
+        let values = HashMap::from([
+            ("job", "weekly".to_string()),
+            ("short_id", "abcd1234".to_string()),
+            ("ext", "md".to_string()),
+        ]);
+        assert_eq!(
+            render("{job}/{short_id}.{ext}", &values),
+            "weekly/abcd1234.md"
+        );
+        assert_eq!(project_slug("/home/me/app"), "home_me_app");
+        assert_eq!(project_slug("/"), "root");
+    }
+}
@@ -0,0 +1,331 @@
+//! Bench command implementation.
+//!
+//! Times each parse strategy over the user's own session files and prints a
+//! comparison, so the choice of build features (`mmap`, `simd`) rests on
+//! real data rather than on synthetic benchmarks. Every strategy parses the
+//! same plain JSONL files; compressed files are left out because only the
+//! buffered reader can decode them. Each strategy runs several times and
+//! the fastest run counts, so the first run also warms the page cache.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::cli::{BenchAction, BenchArgs, Cli, OutputFormat};
+use crate::discovery::format_size;
+use crate::error::{Result, SnatchError};
+use crate::parser::{is_compressed_path, JsonlParser};
+
+use super::get_claude_dir;
+
+/// A way of parsing session files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Strategy {
+    /// Line-by-line through a buffered reader, one file at a time.
+    Buffered,
+    /// Memory-mapped lines, one file at a time.
+    Mmap,
+    /// SIMD JSON over memory-mapped files, one file at a time.
+    Simd,
+    /// Buffered, with files spread across threads.
+    Parallel,
+}
+
+impl Strategy {
+    const ALL: [Self; 4] = [Self::Buffered, Self::Mmap, Self::Simd, Self::Parallel];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Buffered => "buffered",
+            Self::Mmap => "mmap",
+            Self::Simd => "simd",
+            Self::Parallel => "parallel",
+        }
+    }
+
+    /// The cargo feature this strategy needs but the build lacks.
+    const fn missing_feature(self) -> Option<&'static str> {
+        match self {
+            Self::Mmap if !cfg!(feature = "mmap") => Some("mmap"),
+            Self::Simd if !cfg!(feature = "simd") => Some("simd"),
+            _ => None,
+        }
+    }
+
+    /// Parse every file once, returning the number of entries parsed.
+    fn run(self, files: &[PathBuf]) -> Result<usize> {
+        match self {
+            Self::Buffered => files.iter().map(|path| parse_buffered(path)).sum(),
+            Self::Mmap => files.iter().map(|path| parse_mmap(path)).sum(),
+            Self::Simd => files.iter().map(|path| parse_simd(path)).sum(),
+            Self::Parallel => Ok(files
+                .par_iter()
+                .map(|path| parse_buffered(path))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .sum()),
+        }
+    }
+}
+
+fn parse_buffered(path: &Path) -> Result<usize> {
+    Ok(JsonlParser::new().parse_file(path)?.len())
+}
+
+fn parse_simd(path: &Path) -> Result<usize> {
+    Ok(JsonlParser::new().with_simd(true).parse_file(path)?.len())
+}
+
+#[cfg(feature = "mmap")]
+fn parse_mmap(path: &Path) -> Result<usize> {
+    Ok(crate::parser::MmapParser::from_path(path)?
+        .lenient(true)
+        .parse_all()?
+        .len())
+}
+
+#[cfg(not(feature = "mmap"))]
+fn parse_mmap(_path: &Path) -> Result<usize> {
+    Err(SnatchError::Unsupported {
+        feature: "memory-mapped parsing (build with the `mmap` feature)".to_string(),
+    })
+}
+
+/// Timing of one strategy.
+#[derive(Debug, Serialize)]
+struct StrategyResult {
+    strategy: Strategy,
+    /// Feature the build lacks, for strategies that could not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    missing_feature: Option<&'static str>,
+    /// Fastest run, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seconds: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mb_per_sec: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entries_per_sec: Option<f64>,
+    /// Throughput relative to the buffered strategy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speedup: Option<f64>,
+}
+
+/// Result of `snatch bench parse`.
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    files: usize,
+    /// Compressed files left out.
+    skipped_compressed: usize,
+    bytes: u64,
+    entries: usize,
+    iterations: usize,
+    threads: usize,
+    results: Vec<StrategyResult>,
+}
+
+/// Run the bench command.
+pub fn run(cli: &Cli, args: &BenchArgs) -> Result<()> {
+    match &args.action {
+        BenchAction::Parse {
+            path,
+            limit,
+            iterations,
+        } => bench_parse(cli, path, *limit, *iterations),
+    }
+}
+
+fn bench_parse(cli: &Cli, paths: &[PathBuf], limit: usize, iterations: usize) -> Result<()> {
+    let (files, skipped_compressed) = collect_files(cli, paths, limit)?;
+    if files.is_empty() {
+        return Err(SnatchError::validation(
+            "no plain session files to benchmark",
+        ));
+    }
+    let bytes: u64 = files
+        .iter()
+        .filter_map(|path| path.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    let iterations = iterations.max(1);
+    if !cli.quiet {
+        eprintln!(
+            "Benchmarking {} file(s), {}, {iterations} run(s) per strategy...",
+            files.len(),
+            format_size(bytes)
+        );
+    }
+
+    let mut entries = 0;
+    let mut results = Vec::new();
+    for strategy in Strategy::ALL {
+        if let Some(feature) = strategy.missing_feature() {
+            results.push(StrategyResult {
+                strategy,
+                missing_feature: Some(feature),
+                seconds: None,
+                mb_per_sec: None,
+                entries_per_sec: None,
+                speedup: None,
+            });
+            continue;
+        }
+        let mut best = Duration::MAX;
+        let mut parsed = 0;
+        for _ in 0..iterations {
+            let start = Instant::now();
+            parsed = strategy.run(&files)?;
+            best = best.min(start.elapsed());
+        }
+        if strategy == Strategy::Buffered {
+            entries = parsed;
+        }
+        let seconds = best.as_secs_f64().max(f64::EPSILON);
+        results.push(StrategyResult {
+            strategy,
+            missing_feature: None,
+            seconds: Some(seconds),
+            mb_per_sec: Some(bytes as f64 / (1024.0 * 1024.0) / seconds),
+            entries_per_sec: Some(parsed as f64 / seconds),
+            speedup: None,
+        });
+    }
+    let baseline = results[0].seconds;
+    for result in &mut results {
+        result.speedup = baseline.zip(result.seconds).map(|(base, s)| base / s);
+    }
+
+    let report = BenchReport {
+        files: files.len(),
+        skipped_compressed,
+        bytes,
+        entries,
+        iterations,
+        threads: rayon::current_num_threads(),
+        results,
+    };
+    match cli.effective_output() {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            println!("strategy\tseconds\tmb_per_sec\tentries_per_sec\tspeedup");
+            for result in &report.results {
+                let field =
+                    |value: Option<f64>| value.map_or_else(String::new, |v| format!("{v:.3}"));
+                println!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    result.strategy.name(),
+                    field(result.seconds),
+                    field(result.mb_per_sec),
+                    field(result.entries_per_sec),
+                    field(result.speedup)
+                );
+            }
+        }
+        OutputFormat::Text | OutputFormat::Compact => print_report(&report),
+    }
+    Ok(())
+}
+
+fn print_report(report: &BenchReport) {
+    println!(
+        "{} file(s), {}, {} entries; fastest of {} run(s), {} thread(s)",
+        report.files,
+        format_size(report.bytes),
+        report.entries,
+        report.iterations,
+        report.threads
+    );
+    if report.skipped_compressed > 0 {
+        println!(
+            "({} compressed file(s) left out)",
+            report.skipped_compressed
+        );
+    }
+    println!();
+    println!(
+        "{:<10} {:>9} {:>10} {:>12} {:>12}",
+        "STRATEGY", "TIME", "MB/S", "ENTRIES/S", "VS BUFFERED"
+    );
+    for result in &report.results {
+        let name = result.strategy.name();
+        match (result.seconds, result.missing_feature) {
+            (Some(seconds), _) => println!(
+                "{name:<10} {:>8.3}s {:>10.1} {:>12.0} {:>11.2}x",
+                seconds,
+                result.mb_per_sec.unwrap_or_default(),
+                result.entries_per_sec.unwrap_or_default(),
+                result.speedup.unwrap_or(1.0)
+            ),
+            (None, Some(feature)) => {
+                println!("{name:<10} not built (enable the `{feature}` feature)");
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Plain session files to parse: the given files and the `.jsonl` files
+/// under the given directories, or else the largest sessions of the Claude
+/// directory, at most `limit` of them. Also returns how many compressed
+/// files were left out.
+fn collect_files(cli: &Cli, paths: &[PathBuf], limit: usize) -> Result<(Vec<PathBuf>, usize)> {
+    let mut files: Vec<(u64, PathBuf)> = Vec::new();
+    if paths.is_empty() {
+        for session in get_claude_dir(cli)?.all_sessions()? {
+            files.push((session.file_size(), session.path().to_path_buf()));
+        }
+    } else {
+        for path in paths {
+            if !path.exists() {
+                return Err(SnatchError::FileNotFound { path: path.clone() });
+            }
+            for entry in walkdir::WalkDir::new(path)
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+            {
+                let is_session =
+                    entry.depth() == 0 || entry.file_name().to_string_lossy().contains(".jsonl");
+                if entry.file_type().is_file() && is_session {
+                    let size = entry.metadata().map_or(0, |metadata| metadata.len());
+                    files.push((size, entry.into_path()));
+                }
+            }
+        }
+    }
+    let total = files.len();
+    files.retain(|(_, path)| !is_compressed_path(path));
+    let skipped_compressed = total - files.len();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    if limit > 0 {
+        files.truncate(limit);
+    }
+    Ok((
+        files.into_iter().map(|(_, path)| path).collect(),
+        skipped_compressed,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategies_agree_on_entry_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let line = r#"{"type":"summary","summary":"s","leafUuid":"u1"}"#;
+        let files: Vec<PathBuf> = (1..=3)
+            .map(|n| {
+                let path = dir.path().join(format!("{n}.jsonl"));
+                std::fs::write(&path, format!("{line}\n").repeat(n)).unwrap();
+                path
+            })
+            .collect();
+        for strategy in Strategy::ALL {
+            if strategy.missing_feature().is_none() {
+                assert_eq!(strategy.run(&files).unwrap(), 6, "{}", strategy.name());
+            }
+        }
+    }
+}
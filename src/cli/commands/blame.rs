@@ -0,0 +1,533 @@
+//! Blame command implementation.
+//!
+//! Answers "which session last changed this file?" from two sources: the
+//! Write, Edit, and `MultiEdit` tool calls that name the file, and
+//! `file-history-snapshot` backups for sessions whose tool calls were not
+//! recorded. With `-L` it also attributes lines of the file as it is on disk
+//! now: the last Write covers every line, and each later edit claims the lines
+//! where its replacement text still appears exactly once.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, Utc};
+use serde_json::Value;
+
+use crate::cli::{BlameArgs, Cli, OutputFormat};
+use crate::error::{Result, SnatchError};
+use crate::model::{ContentBlock, LogEntry};
+
+use super::get_claude_dir;
+
+/// Where a change was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ChangeSource {
+    Write,
+    Edit,
+    MultiEdit,
+    FileHistory,
+}
+
+impl ChangeSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Write => "Write",
+            Self::Edit => "Edit",
+            Self::MultiEdit => "MultiEdit",
+            Self::FileHistory => "snapshot",
+        }
+    }
+}
+
+/// One recorded change to the file.
+#[derive(Debug, serde::Serialize)]
+struct Change {
+    timestamp: DateTime<Utc>,
+    session_id: String,
+    project: String,
+    /// The assistant message with the tool call, or for a snapshot the user
+    /// message it was taken for.
+    message_uuid: String,
+    source: ChangeSource,
+    /// Text the change put in the file, with whether every occurrence was
+    /// replaced. Empty for snapshots.
+    #[serde(skip)]
+    inserted: Vec<(String, bool)>,
+}
+
+/// One line of the requested range with the change it is attributed to.
+#[derive(serde::Serialize)]
+struct BlameLine<'a> {
+    line: usize,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    change: Option<&'a Change>,
+}
+
+#[derive(serde::Serialize)]
+struct BlameOutput<'a> {
+    file: String,
+    total_changes: usize,
+    sessions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_change: Option<&'a Change>,
+    changes: Vec<&'a Change>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lines: Option<Vec<BlameLine<'a>>>,
+}
+
+/// Run the blame command.
+pub fn run(cli: &Cli, args: &BlameArgs) -> Result<()> {
+    let absolute = if args.path.is_absolute() {
+        args.path.clone()
+    } else {
+        std::env::current_dir()
+            .map_err(|e| SnatchError::io("Failed to read the current directory", e))?
+            .join(&args.path)
+    };
+    let mut targets = vec![absolute.clone()];
+    if let Ok(canonical) = absolute.canonicalize() {
+        if canonical != absolute {
+            targets.push(canonical);
+        }
+    }
+
+    let range = args.lines.as_deref().map(parse_range).transpose()?;
+    // Lines are attributed against the file as it is now, so it must exist.
+    let content = if range.is_some() {
+        let content = std::fs::read_to_string(&absolute)
+            .map_err(|e| SnatchError::io(format!("Failed to read {}", absolute.display()), e))?;
+        Some(content)
+    } else {
+        None
+    };
+
+    let claude_dir = get_claude_dir(cli)?;
+    let mut changes = Vec::new();
+    for session in claude_dir.all_sessions()? {
+        if args
+            .project
+            .as_ref()
+            .is_some_and(|project| !session.project_path().contains(project.as_str()))
+        {
+            continue;
+        }
+        let Ok(entries) = session.parse_with_options(cli.max_file_size) else {
+            continue;
+        };
+        changes.extend(file_changes(
+            session.session_id(),
+            session.project_path(),
+            &entries,
+            &targets,
+        ));
+    }
+    changes.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    let lines = match (range, content.as_deref()) {
+        (Some((start, end)), Some(content)) => {
+            let attribution = attribute_lines(content, &changes);
+            let total = attribution.len();
+            if start > total {
+                return Err(SnatchError::InvalidArgument {
+                    name: "lines".to_string(),
+                    reason: format!("{} has only {total} lines", args.path.display()),
+                });
+            }
+            Some(
+                content
+                    .lines()
+                    .zip(attribution)
+                    .enumerate()
+                    .skip(start - 1)
+                    .take(end.min(total) + 1 - start)
+                    .map(|(index, (text, change))| BlameLine {
+                        line: index + 1,
+                        text,
+                        change: change.map(|i| &changes[i]),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        }
+        _ => None,
+    };
+
+    // Latest first; with a range, only the changes that still own a line in it.
+    let listed: Vec<&Change> = match &lines {
+        Some(lines) => {
+            let mut seen = HashSet::new();
+            let mut owners: Vec<&Change> = lines
+                .iter()
+                .filter_map(|line| line.change)
+                .filter(|change| seen.insert(std::ptr::from_ref(*change)))
+                .collect();
+            owners.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            owners
+        }
+        None => changes.iter().rev().collect(),
+    };
+    let sessions = changes
+        .iter()
+        .map(|change| change.session_id.as_str())
+        .collect::<HashSet<_>>()
+        .len();
+
+    if matches!(cli.effective_output(), OutputFormat::Json) {
+        let output = BlameOutput {
+            file: absolute.display().to_string(),
+            total_changes: changes.len(),
+            sessions,
+            last_change: listed.first().copied(),
+            changes: listed.iter().take(args.limit).copied().collect(),
+            lines,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let Some(last) = listed.first() else {
+        if lines.is_some() {
+            println!(
+                "No recorded session changes to these lines of {}.",
+                absolute.display()
+            );
+        } else {
+            println!("No recorded session changes to {}.", absolute.display());
+        }
+        return Ok(());
+    };
+
+    if let Some(lines) = &lines {
+        for line in lines {
+            let owner = line.change.map_or_else(
+                || format!("{:8}  {:16}  {:9}", "-", "", ""),
+                |change| {
+                    format!(
+                        "{:8}  {}  {:9}",
+                        short_id(&change.session_id),
+                        change
+                            .timestamp
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M"),
+                        change.source.label()
+                    )
+                },
+            );
+            println!("{owner}  {:>5} | {}", line.line, line.text);
+        }
+        println!();
+    }
+
+    println!(
+        "{}: {} change{} in {} session{}",
+        absolute.display(),
+        changes.len(),
+        if changes.len() == 1 { "" } else { "s" },
+        sessions,
+        if sessions == 1 { "" } else { "s" }
+    );
+    println!(
+        "Last changed {} by session {} ({}), message {}",
+        last.timestamp
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M"),
+        short_id(&last.session_id),
+        last.source.label(),
+        last.message_uuid
+    );
+    println!();
+    for change in listed.iter().take(args.limit) {
+        println!(
+            "  {}  {:9}  {:8}  {}",
+            change
+                .timestamp
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M"),
+            change.source.label(),
+            short_id(&change.session_id),
+            change.message_uuid
+        );
+    }
+    if listed.len() > args.limit {
+        println!("  ... {} more (raise --limit)", listed.len() - args.limit);
+    }
+    println!(
+        "\nJump to a change: snatch context {} -m {}",
+        last.session_id, last.message_uuid
+    );
+    Ok(())
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Parse `-L` as `START,END` or a single line, 1-based and inclusive.
+fn parse_range(spec: &str) -> Result<(usize, usize)> {
+    let invalid = || SnatchError::InvalidArgument {
+        name: "lines".to_string(),
+        reason: format!("expected START,END or a line number, got '{spec}'"),
+    };
+    let (start, end) = spec.split_once(',').unwrap_or((spec, spec));
+    let start: usize = start.trim().parse().map_err(|_| invalid())?;
+    let end: usize = end.trim().parse().map_err(|_| invalid())?;
+    if start == 0 || end < start {
+        return Err(invalid());
+    }
+    Ok((start, end))
+}
+
+/// Whether a path recorded in a session names the target file.
+fn names_target(recorded: &str, cwd: &str, targets: &[PathBuf]) -> bool {
+    let recorded = Path::new(recorded);
+    let recorded = if recorded.is_absolute() {
+        recorded.to_path_buf()
+    } else {
+        Path::new(cwd).join(recorded)
+    };
+    targets.contains(&recorded)
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// Changes one session made to the target file.
+///
+/// Snapshot backups are used only when the session has no matching tool
+/// call, since each Edit or Write is also backed up.
+fn file_changes(
+    session_id: &str,
+    project: &str,
+    entries: &[LogEntry],
+    targets: &[PathBuf],
+) -> Vec<Change> {
+    let mut cwd = project;
+    let mut changes = Vec::new();
+    let mut snapshots: HashMap<u32, Change> = HashMap::new();
+    let change = |timestamp, message_uuid: &str, source, inserted| Change {
+        timestamp,
+        session_id: session_id.to_string(),
+        project: project.to_string(),
+        message_uuid: message_uuid.to_string(),
+        source,
+        inserted,
+    };
+
+    for entry in entries {
+        if let Some(entry_cwd) = entry.cwd() {
+            cwd = entry_cwd;
+        }
+        match entry {
+            LogEntry::Assistant(assistant) => {
+                for block in &assistant.message.content {
+                    let ContentBlock::ToolUse(tool_use) = block else {
+                        continue;
+                    };
+                    let input = &tool_use.input;
+                    if !str_field(input, "file_path").is_some_and(|p| names_target(p, cwd, targets))
+                    {
+                        continue;
+                    }
+                    let replacement = |edit: &Value| {
+                        let all = edit
+                            .get("replace_all")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false);
+                        str_field(edit, "new_string").map(|text| (text.to_string(), all))
+                    };
+                    let (source, inserted) = match tool_use.name.as_str() {
+                        "Write" => (
+                            ChangeSource::Write,
+                            str_field(input, "content")
+                                .map(|text| vec![(text.to_string(), false)])
+                                .unwrap_or_default(),
+                        ),
+                        "Edit" => (ChangeSource::Edit, replacement(input).into_iter().collect()),
+                        "MultiEdit" => (
+                            ChangeSource::MultiEdit,
+                            input
+                                .get("edits")
+                                .and_then(Value::as_array)
+                                .map(|edits| edits.iter().filter_map(replacement).collect())
+                                .unwrap_or_default(),
+                        ),
+                        _ => continue,
+                    };
+                    changes.push(change(
+                        assistant.timestamp,
+                        &assistant.uuid,
+                        source,
+                        inserted,
+                    ));
+                }
+            }
+            LogEntry::FileHistorySnapshot(snapshot) => {
+                for (path, backup) in &snapshot.snapshot.tracked_file_backups {
+                    if !names_target(path, cwd, targets) {
+                        continue;
+                    }
+                    // Snapshots repeat every tracked file; keep the first
+                    // record of each version.
+                    snapshots.entry(backup.version).or_insert_with(|| {
+                        change(
+                            backup.backup_time,
+                            &snapshot.message_id,
+                            ChangeSource::FileHistory,
+                            Vec::new(),
+                        )
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    if changes.is_empty() {
+        changes.extend(snapshots.into_values());
+        changes.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    }
+    changes
+}
+
+/// Attribute each line of `content` to the index of the chronologically
+/// sorted change that last put it there, if any.
+fn attribute_lines(content: &str, changes: &[Change]) -> Vec<Option<usize>> {
+    // Byte offset where each line starts.
+    let starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .filter(|&start| start < content.len())
+        .collect();
+    let line_of = |offset: usize| starts.partition_point(|&start| start <= offset) - 1;
+    let mut owners = vec![None; starts.len()];
+
+    for (index, change) in changes.iter().enumerate() {
+        if change.source == ChangeSource::Write {
+            owners.fill(Some(index));
+            continue;
+        }
+        for (text, all) in &change.inserted {
+            if text.is_empty() {
+                continue;
+            }
+            let found: Vec<usize> = content
+                .match_indices(text.as_str())
+                .map(|(i, _)| i)
+                .collect();
+            // Ambiguous text (a lone brace, say) cannot be placed.
+            if found.is_empty() || (found.len() > 1 && !all) {
+                continue;
+            }
+            for offset in found {
+                let last = line_of(offset + text.trim_end_matches('\n').len().max(1) - 1);
+                for owner in &mut owners[line_of(offset)..=last] {
+                    *owner = Some(index);
+                }
+            }
+        }
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_call(uuid: &str, minute: u32, name: &str, input: Value) -> LogEntry {
+        serde_json::from_value(json!({
+            "type": "assistant",
+            "uuid": uuid,
+            "parentUuid": null,
+            "timestamp": format!("2026-01-01T10:{minute:02}:00Z"),
+            "sessionId": "s",
+            "version": "2.0.0",
+            "cwd": "/work/app",
+            "message": {
+                "id": uuid,
+                "type": "message",
+                "role": "assistant",
+                "model": "claude-opus-4-5",
+                "content": [{ "type": "tool_use", "id": uuid, "name": name, "input": input }],
+                "stop_reason": null,
+                "stop_sequence": null,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_file_changes_match_relative_and_absolute_paths() {
+        let targets = [PathBuf::from("/work/app/src/lib.rs")];
+        let entries = vec![
+            tool_call(
+                "a1",
+                0,
+                "Write",
+                json!({ "file_path": "/work/app/src/lib.rs", "content": "fn a() {}\n" }),
+            ),
+            tool_call(
+                "a2",
+                1,
+                "Edit",
+                json!({ "file_path": "src/lib.rs", "old_string": "a", "new_string": "b" }),
+            ),
+            tool_call(
+                "a3",
+                2,
+                "Edit",
+                json!({ "file_path": "/work/app/src/main.rs", "old_string": "a", "new_string": "b" }),
+            ),
+            tool_call(
+                "a4",
+                3,
+                "Read",
+                json!({ "file_path": "/work/app/src/lib.rs" }),
+            ),
+        ];
+
+        let changes = file_changes("s", "/work/app", &entries, &targets);
+        let found: Vec<_> = changes
+            .iter()
+            .map(|c| (c.message_uuid.as_str(), c.source))
+            .collect();
+        assert_eq!(
+            found,
+            [("a1", ChangeSource::Write), ("a2", ChangeSource::Edit)]
+        );
+    }
+
+    #[test]
+    fn test_attribute_lines_prefers_later_unique_edits() {
+        let change = |source, inserted: &[&str]| Change {
+            timestamp: DateTime::<Utc>::UNIX_EPOCH,
+            session_id: "s".to_string(),
+            project: "/work/app".to_string(),
+            message_uuid: "m".to_string(),
+            source,
+            inserted: inserted.iter().map(|t| ((*t).to_string(), false)).collect(),
+        };
+        let content = "fn main() {\n    run();\n    log();\n}\n";
+        let changes = [
+            change(ChangeSource::Write, &[]),
+            change(ChangeSource::Edit, &["    run();\n    log();\n"]),
+            change(ChangeSource::Edit, &["log();"]),
+            // Appears twice, so it is not placed.
+            change(ChangeSource::Edit, &["    "]),
+        ];
+
+        assert_eq!(
+            attribute_lines(content, &changes),
+            [Some(0), Some(1), Some(2), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("10,20").unwrap(), (10, 20));
+        assert_eq!(parse_range("7").unwrap(), (7, 7));
+        assert!(parse_range("0,3").is_err());
+        assert!(parse_range("5,2").is_err());
+        assert!(parse_range("a,b").is_err());
+    }
+}
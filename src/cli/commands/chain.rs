@@ -37,7 +37,7 @@ pub fn run(cli: &Cli, args: &ChainArgs) -> Result<()> {
 }
 
 fn run_classic(cli: &Cli, args: &ChainArgs) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let mut writer = PagerWriter::new(false);
 
     let projects = claude_dir.projects()?;
@@ -290,7 +290,7 @@ fn run_provider(cli: &Cli, args: &ChainArgs) -> Result<()> {
         OutputFormat::Json => {
             writeln!(writer, "{}", serde_json::to_string_pretty(&output)?)?;
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             writeln!(
                 writer,
                 "provider\tkind\tfrom\tto\tfrom_project\tto_project\tdangling_from\tdangling_to\ttool_use_id\tagent_type\tdescription"
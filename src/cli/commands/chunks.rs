@@ -125,7 +125,7 @@ pub fn run(cli: &Cli, args: &ChunksArgs) -> Result<()> {
             Conversation::from_parsed_session(parsed)?,
         )
     } else {
-        let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+        let claude_dir = get_claude_dir(cli)?;
         let session = claude_dir.find_session(&args.session_id)?.ok_or_else(|| {
             SnatchError::SessionNotFound {
                 session_id: args.session_id.clone(),
@@ -1,28 +1,61 @@
 //! Cleanup command implementation.
 //!
 //! Provides safe session cleanup with dry-run support and confirmation prompts.
+//! Retention rules from the `[retention]` config section fill in flags not
+//! given on the command line; `retention.older_than` only applies when no
+//! criterion flag is given. Removed sessions are deleted or moved to an
+//! archive directory, and their analytics snapshots, offset sidecars and
+//! search-index entries are purged afterwards.
 
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
+use crate::analytics::snapshot::AnalyticsStore;
 use crate::cli::{CleanupArgs, Cli, OutputFormat};
-use crate::discovery::{Session, SessionFilter, SessionState};
+use crate::config::Config;
+use crate::discovery::{ClaudeDirectory, Session, SessionFilter, SessionState};
 use crate::error::{Result, SnatchError};
+use crate::index::build::{update_provider_index, ProviderIndexBuildOptions};
+use crate::index::provider::ProviderSearchIndex;
+use crate::parser::OffsetIndex;
+use crate::tags::TagStore;
+use crate::IMPORTED_DIR_NAME;
 
 use super::{get_claude_dir, parse_date_filter};
 
 /// Run the cleanup command.
 pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
+    let config = super::index::load_config(cli);
+    // The criteria are OR'ed, so the configured age only stands in for a
+    // missing criterion; `--empty` alone must not also remove old sessions.
+    let older_than = if args.empty || args.older_than.is_some() {
+        args.older_than.as_ref()
+    } else {
+        config.retention.older_than.as_ref()
+    };
+    let keep_tagged = args.keep_tagged || config.retention.keep_tagged;
+    let archive = args
+        .archive
+        .clone()
+        .or_else(|| config.retention.archive_dir.clone());
+
     // Require at least one filter criterion
-    if !args.empty && args.older_than.is_none() {
+    if !args.empty && older_than.is_none() {
         return Err(SnatchError::InvalidArgument {
             name: "filter".to_string(),
             reason: "At least one filter is required. Use --empty to delete empty sessions \
-                     or --older-than to delete old sessions."
+                     or --older-than (or retention.older_than in the config) to delete old \
+                     sessions."
                 .to_string(),
         });
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
+    let tags = if keep_tagged {
+        Some(TagStore::load()?)
+    } else {
+        None
+    };
 
     // Get all sessions
     let mut sessions: Vec<Session> = if let Some(project_filter) = &args.project {
@@ -44,7 +77,7 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
     }
 
     // Apply criteria filters
-    let older_than_time = if let Some(ref date_str) = args.older_than {
+    let older_than_time = if let Some(date_str) = older_than {
         Some(parse_date_filter(date_str)?)
     } else {
         None
@@ -52,6 +85,7 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
 
     let mut to_delete: Vec<(Session, String)> = Vec::new();
     let mut skipped_active = 0;
+    let mut skipped_tagged = 0;
 
     for session in sessions {
         let mut reasons = Vec::new();
@@ -81,11 +115,29 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
             }
         }
 
+        if tags
+            .as_ref()
+            .and_then(|tags| tags.get(session.session_id()))
+            .is_some_and(|meta| !meta.is_empty())
+        {
+            skipped_tagged += 1;
+            continue;
+        }
+
         to_delete.push((session, reasons.join(", ")));
     }
 
+    let json = matches!(cli.effective_output(), OutputFormat::Json);
+
     // Report results
     if to_delete.is_empty() {
+        if json {
+            println!("[]");
+            return Ok(());
+        }
+        if cli.quiet {
+            return Ok(());
+        }
         println!("No sessions match the cleanup criteria.");
         if skipped_active > 0 {
             println!(
@@ -94,11 +146,23 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
                 if skipped_active == 1 { "" } else { "s" }
             );
         }
+        if skipped_tagged > 0 {
+            println!(
+                "({} tagged session{} kept)",
+                skipped_tagged,
+                if skipped_tagged == 1 { "" } else { "s" }
+            );
+        }
         return Ok(());
     }
 
     // Calculate total size to be freed
     let total_size: u64 = to_delete.iter().map(|(s, _)| s.file_size()).sum();
+    let verb = if archive.is_some() {
+        "archive"
+    } else {
+        "delete"
+    };
 
     // Output based on format
     match cli.effective_output() {
@@ -117,7 +181,7 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
                 .collect();
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("session_id\tproject\tsize\treason");
             for (session, reason) in &to_delete {
                 println!(
@@ -137,16 +201,18 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
         OutputFormat::Text => {
             if args.preview {
                 println!(
-                    "Would delete {} session{} ({} total):",
+                    "Would {} {} session{} ({} total):",
+                    verb,
                     to_delete.len(),
                     if to_delete.len() == 1 { "" } else { "s" },
                     crate::discovery::format_size(total_size)
                 );
             } else {
                 println!(
-                    "Found {} session{} to delete ({} total):",
+                    "Found {} session{} to {} ({} total):",
                     to_delete.len(),
                     if to_delete.len() == 1 { "" } else { "s" },
+                    verb,
                     crate::discovery::format_size(total_size)
                 );
             }
@@ -185,6 +251,13 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
                     if skipped_active == 1 { "" } else { "s" }
                 );
             }
+            if skipped_tagged > 0 {
+                println!(
+                    "Note: {} tagged session{} kept.",
+                    skipped_tagged,
+                    if skipped_tagged == 1 { "" } else { "s" }
+                );
+            }
         }
     }
 
@@ -195,62 +268,197 @@ pub fn run(cli: &Cli, args: &CleanupArgs) -> Result<()> {
 
     // Confirmation prompt
     if !args.yes {
-        println!();
-        print!(
-            "Delete {} session{}? This cannot be undone. [y/N] ",
-            to_delete.len(),
-            if to_delete.len() == 1 { "" } else { "s" }
-        );
-        io::stdout().flush()?;
+        eprintln!();
+        let plural = if to_delete.len() == 1 { "" } else { "s" };
+        match &archive {
+            Some(dir) => eprint!(
+                "Move {} session{} to {}? [y/N] ",
+                to_delete.len(),
+                plural,
+                dir.display()
+            ),
+            None => eprint!(
+                "Delete {} session{}? This cannot be undone. [y/N] ",
+                to_delete.len(),
+                plural
+            ),
+        }
+        io::stderr().flush()?;
 
         let mut input = String::new();
         io::stdin().read_line(&mut input)?;
 
         let answer = input.trim().to_lowercase();
         if answer != "y" && answer != "yes" {
-            println!("Cleanup cancelled.");
+            eprintln!("Cleanup cancelled.");
             return Ok(());
         }
     }
 
     // Perform deletion
-    let mut deleted = 0;
+    let done = if archive.is_some() {
+        "Archived"
+    } else {
+        "Deleted"
+    };
+    let mut removed = Vec::new();
     let mut failed = 0;
     let mut freed_bytes: u64 = 0;
 
     for (session, _) in &to_delete {
-        match std::fs::remove_file(session.path()) {
+        let result = match &archive {
+            Some(dir) => archive_destination(dir, &claude_dir, session.path())
+                .and_then(|to| archive_session(session.path(), &to)),
+            None => std::fs::remove_file(session.path()),
+        };
+        match result {
             Ok(()) => {
-                deleted += 1;
+                removed.push(session.path().to_path_buf());
                 freed_bytes += session.file_size();
                 if args.verbose {
-                    println!("Deleted: {}", session.session_id());
+                    println!("{done}: {}", session.session_id());
                 }
             }
             Err(e) => {
                 failed += 1;
-                eprintln!("Failed to delete {}: {}", session.session_id(), e);
+                eprintln!("Failed to {verb} {}: {}", session.session_id(), e);
             }
         }
     }
 
     // Summary
-    println!();
-    println!(
-        "Deleted {} session{} ({})",
-        deleted,
-        if deleted == 1 { "" } else { "s" },
-        crate::discovery::format_size(freed_bytes)
-    );
+    if !json && !cli.quiet {
+        println!();
+        println!(
+            "{} {} session{} ({})",
+            done,
+            removed.len(),
+            if removed.len() == 1 { "" } else { "s" },
+            crate::discovery::format_size(freed_bytes)
+        );
+    }
 
     if failed > 0 {
         eprintln!(
-            "Failed to delete {} session{}",
+            "Failed to {} {} session{}",
+            verb,
             failed,
             if failed == 1 { "" } else { "s" }
         );
     }
 
+    if !removed.is_empty() {
+        purge_derived_data(cli, &config, &removed)?;
+    }
+
+    Ok(())
+}
+
+/// Where an archived session goes: its path below the directory holding
+/// its project, recreated under `archive`. Sessions of the projects
+/// directory keep their path; imported and peer sessions go below a
+/// directory naming their source, so their names cannot collide.
+pub(super) fn archive_destination(
+    archive: &Path,
+    claude_dir: &ClaudeDirectory,
+    session_path: &Path,
+) -> io::Result<PathBuf> {
+    let mut roots = vec![
+        (PathBuf::new(), claude_dir.projects_dir().to_path_buf()),
+        (
+            PathBuf::from(IMPORTED_DIR_NAME),
+            claude_dir.imported_dir().to_path_buf(),
+        ),
+    ];
+    for peer in claude_dir.peers() {
+        let name: String = peer
+            .to_string_lossy()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        roots.push((
+            Path::new("peers").join(name.trim_matches('_')),
+            peer.join(crate::PROJECTS_DIR_NAME),
+        ));
+    }
+    roots
+        .iter()
+        .find_map(|(prefix, root)| {
+            let relative = session_path.strip_prefix(root).ok()?;
+            Some(archive.join(prefix).join(relative))
+        })
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} is outside the Claude directory's projects",
+                    session_path.display()
+                ),
+            )
+        })
+}
+
+/// Move a session file, copying across filesystems when a rename fails.
+pub(super) fn archive_session(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// Drop the analytics snapshots, offset sidecars and search-index entries of
+/// removed or rewritten sessions.
+///
+/// Reports what it dropped in text output unless `--quiet` is given.
+pub(super) fn purge_derived_data(cli: &Cli, config: &Config, removed: &[PathBuf]) -> Result<()> {
+    let store_path = AnalyticsStore::path_for(&config.cache)?;
+    let mut snapshots = 0;
+    if store_path.exists() {
+        let mut store = AnalyticsStore::load_from(&store_path)?;
+        snapshots = removed.iter().filter(|path| store.remove(path)).count();
+        if snapshots > 0 {
+            store.save_to(&store_path)?;
+        }
+    }
+
+    let cache_dir = crate::config::resolve_cache_dir(&config.cache)?;
+    for path in removed {
+        OffsetIndex::remove_sidecar(&cache_dir, path).map_err(|e| {
+            SnatchError::io(
+                format!("Failed to remove the offset index of {}", path.display()),
+                e,
+            )
+        })?;
+    }
+
+    let index_path = super::index::index_path(cli);
+    let mut unindexed = 0;
+    if index_path.exists() {
+        let index = ProviderSearchIndex::open(&index_path)?;
+        let selection = super::index::provider_selection(&[])?;
+        let (registry, sources) = super::index::source_registry(cli)?;
+        let options = ProviderIndexBuildOptions::new(&selection, None).with_sources(&sources);
+        unindexed = update_provider_index(&index, &registry, &options)?.sessions_removed;
+    }
+
+    if !cli.quiet && matches!(cli.effective_output(), OutputFormat::Text) {
+        if snapshots > 0 {
+            println!("Purged {snapshots} analytics snapshot(s)");
+        }
+        if unindexed > 0 {
+            println!("Removed {unindexed} session(s) from the search index");
+        }
+    }
     Ok(())
 }
 
@@ -267,6 +475,32 @@ fn short_id(id: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_archive_destination_keeps_project_layout() {
+        let tmp = tempfile::tempdir().unwrap();
+        let peer = tmp.path().join("peer");
+        std::fs::create_dir_all(&peer).unwrap();
+        let claude_dir = ClaudeDirectory::from_path(tmp.path())
+            .unwrap()
+            .with_peers([peer.clone()]);
+        let archive = Path::new("/archive");
+        let destination = |session: &Path| archive_destination(archive, &claude_dir, session);
+
+        assert_eq!(
+            destination(&claude_dir.projects_dir().join("-home-u-app/abc.jsonl")).unwrap(),
+            archive.join("-home-u-app/abc.jsonl")
+        );
+        assert_eq!(
+            destination(&claude_dir.imported_dir().join("chatgpt/abc.jsonl")).unwrap(),
+            archive.join(IMPORTED_DIR_NAME).join("chatgpt/abc.jsonl")
+        );
+        let from_peer = destination(&peer.join("projects/-home-u-app/abc.jsonl")).unwrap();
+        assert!(from_peer.starts_with(archive.join("peers")));
+        assert!(from_peer.ends_with("-home-u-app/abc.jsonl"));
+        assert_ne!(from_peer, archive.join("-home-u-app/abc.jsonl"));
+        assert!(destination(Path::new("/elsewhere/abc.jsonl")).is_err());
+    }
+
     #[test]
     fn test_short_id() {
         assert_eq!(short_id("40afc8a7-3fcb-4d29-b1ee-100b81b8c6c0"), "40afc8a7");
@@ -87,7 +87,7 @@ pub fn run(cli: &Cli, args: &CodeArgs) -> Result<()> {
         };
         (Conversation::from_parsed_session(parsed)?, source)
     } else {
-        let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+        let claude_dir = get_claude_dir(cli)?;
         let sessions = claude_dir.all_sessions()?;
         let session = sessions
             .iter()
@@ -136,7 +136,7 @@ pub fn run(cli: &Cli, args: &CodeArgs) -> Result<()> {
             };
             println!("{json}");
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("index\tlanguage\tsource\ttimestamp\tcode_preview");
             for e in &extracted {
                 let lang = e.language.as_deref().unwrap_or("-");
@@ -75,7 +75,7 @@ pub fn run(cli: &Cli, args: &DynamicCompletionsArgs) -> Result<()> {
 
 /// Complete session IDs.
 fn complete_sessions(cli: &Cli, prefix: Option<&str>, limit: Option<usize>) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let sessions = claude_dir.all_sessions()?;
 
     let limit = limit.unwrap_or(50);
@@ -111,7 +111,7 @@ fn complete_sessions(cli: &Cli, prefix: Option<&str>, limit: Option<usize>) -> R
 
 /// Complete project names/paths.
 fn complete_projects(cli: &Cli, prefix: Option<&str>, limit: Option<usize>) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let projects = claude_dir.projects()?;
 
     let limit = limit.unwrap_or(50);
@@ -146,7 +146,7 @@ fn complete_projects(cli: &Cli, prefix: Option<&str>, limit: Option<usize>) -> R
 
 /// Complete tool names.
 fn complete_tools(cli: &Cli, prefix: Option<&str>, limit: Option<usize>) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let sessions = claude_dir.all_sessions()?;
 
     // Collect unique tool names
@@ -200,6 +200,7 @@ fn complete_formats(prefix: Option<&str>) -> Result<()> {
         ("text", "Plain text format"),
         ("compact", "Compact format"),
         ("tsv", "Tab-separated values"),
+        ("csv", "Comma-separated values"),
     ];
 
     for (format, desc) in formats {
@@ -4,8 +4,8 @@
 
 use std::path::PathBuf;
 
-use crate::cli::{Cli, ConfigAction, ConfigArgs, OutputFormat};
-use crate::config::{default_config_path, Config};
+use crate::cli::{Cli, ConfigAction, ConfigArgs, OutputFormat, SavedSearchAction};
+use crate::config::{default_config_path, Config, SavedSearch};
 use crate::discovery::format_size;
 use crate::error::{Result, SnatchError};
 
@@ -18,6 +18,7 @@ pub fn run(cli: &Cli, args: &ConfigArgs) -> Result<()> {
         ConfigAction::Path => show_config_path(),
         ConfigAction::Init => init_config(),
         ConfigAction::Reset => reset_config(),
+        ConfigAction::Searches { action } => run_saved_searches(cli, action),
     }
 }
 
@@ -81,6 +82,24 @@ fn show_config(cli: &Cli) -> Result<()> {
                 config.budget.warning_threshold * 100.0
             );
             println!("  show_in_stats = {}", config.budget.show_in_stats);
+            println!();
+
+            println!("[retention]");
+            if let Some(older_than) = &config.retention.older_than {
+                println!("  older_than = \"{older_than}\"");
+            } else {
+                println!("  older_than = # not set");
+            }
+            println!("  keep_tagged = {}", config.retention.keep_tagged);
+            if let Some(dir) = &config.retention.archive_dir {
+                println!("  archive_dir = \"{}\"", dir.display());
+            } else {
+                println!("  archive_dir = # not set (delete)");
+            }
+            println!();
+
+            println!("[discovery]");
+            println!("  wsl = {}", config.discovery.wsl);
         }
     }
 
@@ -124,6 +143,19 @@ fn get_config_value(cli: &Cli, key: &str) -> Result<()> {
         "budget.warning_threshold" => format!("{:.0}", config.budget.warning_threshold * 100.0),
         "budget.show_in_stats" => config.budget.show_in_stats.to_string(),
 
+        "retention.older_than" => config
+            .retention
+            .older_than
+            .unwrap_or_else(|| "(not set)".to_string()),
+        "retention.keep_tagged" => config.retention.keep_tagged.to_string(),
+        "retention.archive_dir" => config
+            .retention
+            .archive_dir
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "(not set)".to_string()),
+
+        "discovery.wsl" => config.discovery.wsl.to_string(),
+
         _ => {
             return Err(SnatchError::ConfigError {
                 message: format!("Unknown configuration key: {key}"),
@@ -192,6 +224,29 @@ fn set_config_value(_cli: &Cli, key: &str, value: &str) -> Result<()> {
             config.budget.show_in_stats = parse_bool(value)?;
         }
 
+        "retention.older_than" => {
+            config.retention.older_than = match value.to_lowercase().as_str() {
+                "none" | "unset" | "clear" | "" => None,
+                _ => {
+                    super::parse_date_filter(value)?;
+                    Some(value.to_string())
+                }
+            };
+        }
+        "retention.keep_tagged" => {
+            config.retention.keep_tagged = parse_bool(value)?;
+        }
+        "retention.archive_dir" => {
+            config.retention.archive_dir = match value.to_lowercase().as_str() {
+                "none" | "unset" | "clear" | "" => None,
+                _ => Some(PathBuf::from(value)),
+            };
+        }
+
+        "discovery.wsl" => {
+            config.discovery.wsl = parse_bool(value)?;
+        }
+
         _ => {
             return Err(SnatchError::ConfigError {
                 message: format!("Unknown configuration key: {key}"),
@@ -245,6 +300,97 @@ fn reset_config() -> Result<()> {
     Ok(())
 }
 
+/// List, add, or remove saved searches.
+fn run_saved_searches(cli: &Cli, action: &SavedSearchAction) -> Result<()> {
+    let mut config = Config::load().unwrap_or_default();
+
+    match action {
+        SavedSearchAction::List => match cli.effective_output() {
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&config.searches)?);
+            }
+            _ if config.searches.is_empty() => {
+                println!("No saved searches. Add one with 'snatch config searches add'.");
+            }
+            _ => {
+                for (name, search) in &config.searches {
+                    let mut filters = Vec::new();
+                    if let Some(kind) = &search.message_type {
+                        filters.push(format!("type={kind}"));
+                    }
+                    if let Some(project) = &search.project {
+                        filters.push(format!("project={project}"));
+                    }
+                    if let Some(model) = &search.model {
+                        filters.push(format!("model={model}"));
+                    }
+                    if let Some(tool) = &search.tool_name {
+                        filters.push(format!("tool={tool}"));
+                    }
+                    for (flag, set) in [
+                        ("ignore-case", search.ignore_case),
+                        ("thinking", search.thinking),
+                        ("tools", search.tools),
+                    ] {
+                        if set {
+                            filters.push(flag.to_string());
+                        }
+                    }
+                    if filters.is_empty() {
+                        println!("{name}: {}", search.query);
+                    } else {
+                        println!("{name}: {} ({})", search.query, filters.join(", "));
+                    }
+                }
+            }
+        },
+        SavedSearchAction::Add {
+            name,
+            query,
+            message_type,
+            project,
+            model,
+            tool_name,
+            ignore_case,
+            thinking,
+            tools,
+        } => {
+            if name.trim().is_empty() || query.trim().is_empty() {
+                return Err(SnatchError::InvalidArgument {
+                    name: "searches add".to_string(),
+                    reason: "name and query must be non-empty".to_string(),
+                });
+            }
+            config.searches.insert(
+                name.clone(),
+                SavedSearch {
+                    query: query.clone(),
+                    message_type: message_type.clone(),
+                    project: project.clone(),
+                    model: model.clone(),
+                    tool_name: tool_name.clone(),
+                    ignore_case: *ignore_case,
+                    thinking: *thinking,
+                    tools: *tools,
+                },
+            );
+            config.save()?;
+            println!("Saved search '{name}'");
+        }
+        SavedSearchAction::Remove { name } => {
+            if config.searches.remove(name).is_none() {
+                return Err(SnatchError::ConfigError {
+                    message: format!("No saved search named '{name}'"),
+                });
+            }
+            config.save()?;
+            println!("Removed saved search '{name}'");
+        }
+    }
+
+    Ok(())
+}
+
 /// Parse boolean value.
 fn parse_bool(s: &str) -> Result<bool> {
     match s.to_lowercase().as_str() {
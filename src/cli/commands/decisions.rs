@@ -477,7 +477,7 @@ pub fn run(cli: &Cli, args: &DecisionsArgs) -> Result<()> {
                 return Ok(());
             }
 
-            let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+            let claude_dir = get_claude_dir(cli)?;
             let mut scored_count = 0u32;
             let mut skipped_no_id = 0u32;
             let mut skipped_not_found = 0u32;
@@ -0,0 +1,37 @@
+//! Decrypt command implementation.
+//!
+//! Reads back an export written with `snatch export --encrypt`, through the
+//! `age` CLI.
+
+use crate::cli::{Cli, DecryptArgs};
+use crate::error::{Result, SnatchError};
+use crate::util::age;
+
+/// Run the decrypt command.
+pub fn run(cli: &Cli, args: &DecryptArgs) -> Result<()> {
+    if !args.input.is_file() {
+        return Err(SnatchError::InvalidArgument {
+            name: "file".to_string(),
+            reason: format!("{} is not a file", args.input.display()),
+        });
+    }
+    if let Some(output) = &args.output_file {
+        if output.exists() && !args.overwrite {
+            return Err(SnatchError::InvalidArgument {
+                name: "--out".to_string(),
+                reason: format!("{} exists (use --overwrite to replace)", output.display()),
+            });
+        }
+    }
+    if !age::is_available() {
+        return Err(SnatchError::export(
+            "decrypt needs the age CLI; install it from https://age-encryption.org",
+        ));
+    }
+
+    age::decrypt_file(&args.input, args.output_file.as_deref(), &args.identity)?;
+    if let (false, Some(output)) = (cli.quiet, &args.output_file) {
+        eprintln!("Decrypted {} to {}", args.input.display(), output.display());
+    }
+    Ok(())
+}
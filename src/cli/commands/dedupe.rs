@@ -0,0 +1,643 @@
+//! Dedupe command implementation.
+//!
+//! Finds duplicated session data left behind by sync conflicts and copied
+//! project directories:
+//!
+//! - byte-identical session files;
+//! - copies whose messages are all contained in another file of the same
+//!   session (same message UUIDs, same recorded session ids);
+//! - lines repeated verbatim within one file.
+//!
+//! Sessions that merely share messages are reported as overlapping but never
+//! removed: a resumed session repeats the history it continues under a new
+//! session id. `--remove` deletes (or archives) the redundant copies and
+//! rewrites files without their repeated lines, skipping active sessions.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cli::{Cli, DedupeArgs, OutputFormat};
+use crate::discovery::{format_size, Session, SessionFilter, SessionState};
+use crate::error::{Result, SnatchError};
+use crate::parser::is_compressed_path;
+use crate::util::{atomic_write, truncate_path};
+
+use super::cleanup::{archive_destination, archive_session, purge_derived_data};
+use super::get_claude_dir;
+
+/// What deduplication needs to know about one session file's contents.
+#[derive(Debug, Default)]
+struct Contents {
+    /// Hash of the raw bytes.
+    hash: u64,
+    /// Message UUIDs in the file.
+    uuids: HashSet<String>,
+    /// Session ids recorded on the file's entries.
+    session_ids: BTreeSet<String>,
+    /// Lines carrying a UUID that repeat an earlier line verbatim.
+    repeated_lines: usize,
+}
+
+impl Contents {
+    fn scan(bytes: &[u8]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        let mut contents = Self {
+            hash: hasher.finish(),
+            ..Self::default()
+        };
+        let text = String::from_utf8_lossy(bytes);
+        let mut seen: HashSet<&str> = HashSet::new();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let Ok(value) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            if let Some(id) = value.get("sessionId").and_then(Value::as_str) {
+                contents.session_ids.insert(id.to_string());
+            }
+            if let Some(uuid) = value.get("uuid").and_then(Value::as_str) {
+                contents.uuids.insert(uuid.to_string());
+                if !seen.insert(line) {
+                    contents.repeated_lines += 1;
+                }
+            }
+        }
+        contents
+    }
+}
+
+/// A session file in the report.
+#[derive(Debug, Clone, Serialize)]
+struct FileRef {
+    session_id: String,
+    project: String,
+    path: PathBuf,
+    size: u64,
+}
+
+impl FileRef {
+    fn from_session(session: &Session) -> Self {
+        Self {
+            session_id: session.session_id().to_string(),
+            project: session.project_path().to_string(),
+            path: session.path().to_path_buf(),
+            size: session.file_size(),
+        }
+    }
+}
+
+/// A scanned session file.
+#[derive(Debug)]
+struct Scanned {
+    file: FileRef,
+    modified: SystemTime,
+    contents: Contents,
+}
+
+impl Scanned {
+    /// Which file of a duplicate set to keep: one named after a session id
+    /// it records, then the oldest, then by path.
+    fn keep_rank(&self) -> (bool, SystemTime, &PathBuf) {
+        (
+            !self.contents.session_ids.contains(&self.file.session_id),
+            self.modified,
+            &self.file.path,
+        )
+    }
+}
+
+/// Byte-identical files; all but `keep` are redundant.
+#[derive(Debug, Serialize)]
+struct IdenticalGroup {
+    keep: FileRef,
+    duplicates: Vec<FileRef>,
+}
+
+/// A file whose messages all appear in another file of the same session.
+#[derive(Debug, Serialize)]
+struct ContainedCopy {
+    copy: FileRef,
+    within: FileRef,
+    messages: usize,
+}
+
+/// Two sessions sharing messages, reported only.
+#[derive(Debug, Serialize)]
+struct Overlap {
+    first: FileRef,
+    second: FileRef,
+    shared: usize,
+    /// Shared messages as a fraction of the smaller session's.
+    ratio: f64,
+}
+
+/// A file with verbatim repeated lines.
+#[derive(Debug, Serialize)]
+struct RepeatedLines {
+    file: FileRef,
+    lines: usize,
+}
+
+/// Everything dedupe found.
+#[derive(Debug, Default, Serialize)]
+struct DedupeReport {
+    sessions_scanned: usize,
+    identical: Vec<IdenticalGroup>,
+    contained: Vec<ContainedCopy>,
+    overlapping: Vec<Overlap>,
+    repeated_lines: Vec<RepeatedLines>,
+    /// Bytes freed by removing the redundant copies.
+    reclaimable_bytes: u64,
+}
+
+impl DedupeReport {
+    /// Session files `--remove` would take away.
+    fn redundant(&self) -> Vec<&FileRef> {
+        self.identical
+            .iter()
+            .flat_map(|group| &group.duplicates)
+            .chain(self.contained.iter().map(|copy| &copy.copy))
+            .collect()
+    }
+
+    fn is_clean(&self) -> bool {
+        self.identical.is_empty()
+            && self.contained.is_empty()
+            && self.overlapping.is_empty()
+            && self.repeated_lines.is_empty()
+    }
+}
+
+/// Run the dedupe command.
+pub fn run(cli: &Cli, args: &DedupeArgs) -> Result<()> {
+    if !(0.0..=1.0).contains(&args.min_overlap) {
+        return Err(SnatchError::InvalidArgument {
+            name: "min-overlap".to_string(),
+            reason: "must be between 0 and 1".to_string(),
+        });
+    }
+    let claude_dir = get_claude_dir(cli)?;
+    let mut sessions: Vec<Session> = if let Some(project_filter) = &args.project {
+        let projects = claude_dir.projects()?;
+        let mut sess = Vec::new();
+        for project in super::helpers::filter_projects(projects, project_filter) {
+            sess.extend(project.sessions()?);
+        }
+        sess
+    } else {
+        claude_dir.all_sessions()?
+    };
+    if !args.subagents {
+        let filter = SessionFilter::new().main_only();
+        sessions.retain(|s| filter.matches(s).unwrap_or(false));
+    }
+
+    let scanned: Vec<Scanned> = sessions
+        .iter()
+        .filter_map(|session| {
+            // Archives are left alone: removing or rewriting lines would
+            // replace them with plain JSONL.
+            if is_compressed_path(session.path()) {
+                return None;
+            }
+            let bytes = fs::read(session.path()).ok()?;
+            Some(Scanned {
+                file: FileRef::from_session(session),
+                modified: session.modified_time(),
+                contents: Contents::scan(&bytes),
+            })
+        })
+        .collect();
+    let report = find_duplicates(&scanned, args.min_overlap);
+
+    match cli.effective_output() {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => print_report(&report, args.remove),
+    }
+
+    if args.remove && !(report.redundant().is_empty() && report.repeated_lines.is_empty()) {
+        remove_duplicates(cli, args, &sessions, &report)?;
+    }
+    Ok(())
+}
+
+/// Classify the scanned files into duplicate sets.
+fn find_duplicates(scanned: &[Scanned], min_overlap: f64) -> DedupeReport {
+    let mut report = DedupeReport {
+        sessions_scanned: scanned.len(),
+        ..DedupeReport::default()
+    };
+
+    // Byte-identical files, confirmed byte for byte after the hash match.
+    let mut by_hash: HashMap<(u64, u64), Vec<&Scanned>> = HashMap::new();
+    for file in scanned {
+        by_hash
+            .entry((file.file.size, file.contents.hash))
+            .or_default()
+            .push(file);
+    }
+    let mut redundant: HashSet<&PathBuf> = HashSet::new();
+    for mut group in by_hash.into_values().filter(|group| group.len() > 1) {
+        group.sort_by(|a, b| a.keep_rank().cmp(&b.keep_rank()));
+        let keep = group[0];
+        let Ok(kept_bytes) = fs::read(&keep.file.path) else {
+            continue;
+        };
+        let duplicates: Vec<&Scanned> = group[1..]
+            .iter()
+            .copied()
+            .filter(|file| fs::read(&file.file.path).is_ok_and(|bytes| bytes == kept_bytes))
+            .collect();
+        if !duplicates.is_empty() {
+            redundant.extend(duplicates.iter().map(|file| &file.file.path));
+            report.identical.push(IdenticalGroup {
+                keep: keep.file.clone(),
+                duplicates: duplicates.iter().map(|file| file.file.clone()).collect(),
+            });
+        }
+    }
+    report
+        .identical
+        .sort_by(|a, b| a.keep.path.cmp(&b.keep.path));
+
+    // Shared message counts per pair of remaining files.
+    let remaining: Vec<&Scanned> = scanned
+        .iter()
+        .filter(|file| !redundant.contains(&file.file.path))
+        .collect();
+    let mut holders: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, file) in remaining.iter().enumerate() {
+        for uuid in &file.contents.uuids {
+            holders.entry(uuid.as_str()).or_default().push(index);
+        }
+    }
+    let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+    for files in holders.values() {
+        for (i, &a) in files.iter().enumerate() {
+            for &b in &files[i + 1..] {
+                *shared.entry((a, b)).or_default() += 1;
+            }
+        }
+    }
+    let mut pairs: Vec<_> = shared.into_iter().collect();
+    pairs.sort_unstable();
+
+    let mut contained: HashSet<usize> = HashSet::new();
+    for ((a, b), count) in pairs {
+        let (first, second) = (remaining[a], remaining[b]);
+        // The copy is the smaller file, or the lower-ranked of equal sets.
+        let (copy, within, copy_index) =
+            match first.contents.uuids.len().cmp(&second.contents.uuids.len()) {
+                std::cmp::Ordering::Less => (first, second, a),
+                std::cmp::Ordering::Greater => (second, first, b),
+                std::cmp::Ordering::Equal if first.keep_rank() > second.keep_rank() => {
+                    (first, second, a)
+                }
+                std::cmp::Ordering::Equal => (second, first, b),
+            };
+        let smaller = copy.contents.uuids.len();
+        if count == smaller && copy.contents.session_ids == within.contents.session_ids {
+            if contained.insert(copy_index) {
+                report.contained.push(ContainedCopy {
+                    copy: copy.file.clone(),
+                    within: within.file.clone(),
+                    messages: count,
+                });
+            }
+            continue;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = count as f64 / smaller as f64;
+        if ratio >= min_overlap {
+            report.overlapping.push(Overlap {
+                first: first.file.clone(),
+                second: second.file.clone(),
+                shared: count,
+                ratio,
+            });
+        }
+    }
+    report.overlapping.sort_by(|a, b| b.shared.cmp(&a.shared));
+
+    report.repeated_lines = remaining
+        .iter()
+        .enumerate()
+        .filter(|(index, file)| file.contents.repeated_lines > 0 && !contained.contains(index))
+        .map(|(_, file)| RepeatedLines {
+            file: file.file.clone(),
+            lines: file.contents.repeated_lines,
+        })
+        .collect();
+    report.reclaimable_bytes = report.redundant().iter().map(|file| file.size).sum();
+    report
+}
+
+/// Drop UUID-carrying lines that repeat an earlier line verbatim, returning
+/// the new text and the number of lines dropped.
+fn without_repeated_lines(text: &str) -> (String, usize) {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut out = String::with_capacity(text.len());
+    let mut dropped = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let has_uuid = serde_json::from_str::<Value>(trimmed)
+            .is_ok_and(|value| value.get("uuid").is_some_and(Value::is_string));
+        if has_uuid && !seen.insert(trimmed) {
+            dropped += 1;
+            continue;
+        }
+        out.push_str(line);
+    }
+    (out, dropped)
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+fn describe(file: &FileRef) -> String {
+    format!(
+        "{}  {}  {}",
+        file.session_id.get(..8).unwrap_or(&file.session_id),
+        truncate_path(&file.project, 30),
+        file.path.display()
+    )
+}
+
+fn print_report(report: &DedupeReport, removing: bool) {
+    println!(
+        "Scanned {} session{}.",
+        report.sessions_scanned,
+        plural(report.sessions_scanned)
+    );
+    if report.is_clean() {
+        println!("No duplicates found.");
+        return;
+    }
+
+    if !report.identical.is_empty() {
+        let copies: usize = report.identical.iter().map(|g| g.duplicates.len()).sum();
+        println!();
+        println!(
+            "Identical files ({} group{}, {copies} redundant cop{}):",
+            report.identical.len(),
+            plural(report.identical.len()),
+            if copies == 1 { "y" } else { "ies" }
+        );
+        for group in &report.identical {
+            println!("  keep  {}", describe(&group.keep));
+            for duplicate in &group.duplicates {
+                println!("  dup   {}", describe(duplicate));
+            }
+        }
+    }
+
+    if !report.contained.is_empty() {
+        println!();
+        println!(
+            "Copies contained in another file of the same session ({}):",
+            report.contained.len()
+        );
+        for copy in &report.contained {
+            println!(
+                "  {} ({} message{})",
+                describe(&copy.copy),
+                copy.messages,
+                plural(copy.messages)
+            );
+            println!("    within {}", copy.within.path.display());
+        }
+    }
+
+    if !report.overlapping.is_empty() {
+        println!();
+        println!("Overlapping sessions (reported only; a resumed session repeats the history it continues):");
+        for overlap in &report.overlapping {
+            println!(
+                "  {} ↔ {}  {} shared message{} ({:.0}% of the smaller)",
+                overlap
+                    .first
+                    .session_id
+                    .get(..8)
+                    .unwrap_or(&overlap.first.session_id),
+                overlap
+                    .second
+                    .session_id
+                    .get(..8)
+                    .unwrap_or(&overlap.second.session_id),
+                overlap.shared,
+                plural(overlap.shared),
+                overlap.ratio * 100.0
+            );
+        }
+    }
+
+    if !report.repeated_lines.is_empty() {
+        let lines: usize = report.repeated_lines.iter().map(|r| r.lines).sum();
+        println!();
+        println!(
+            "Repeated lines ({lines} line{} in {} file{}):",
+            plural(lines),
+            report.repeated_lines.len(),
+            plural(report.repeated_lines.len())
+        );
+        for repeated in &report.repeated_lines {
+            println!(
+                "  {}  ({} line{})",
+                describe(&repeated.file),
+                repeated.lines,
+                plural(repeated.lines)
+            );
+        }
+    }
+
+    let redundant = report.redundant().len();
+    if redundant > 0 || !report.repeated_lines.is_empty() {
+        println!();
+        println!(
+            "Reclaimable: {} in {redundant} redundant file{}.",
+            format_size(report.reclaimable_bytes),
+            plural(redundant)
+        );
+        if !removing {
+            println!("Run with --remove to remove redundant copies and repeated lines.");
+        }
+    }
+}
+
+/// Remove the redundant copies and rewrite files with repeated lines,
+/// leaving active sessions alone.
+fn remove_duplicates(
+    cli: &Cli,
+    args: &DedupeArgs,
+    sessions: &[Session],
+    report: &DedupeReport,
+) -> Result<()> {
+    let is_active = |path: &PathBuf| {
+        sessions
+            .iter()
+            .find(|s| s.path() == path.as_path())
+            .is_some_and(|s| s.state().is_ok_and(|state| state != SessionState::Inactive))
+    };
+    let (redundant, active_copies): (Vec<&FileRef>, Vec<&FileRef>) = report
+        .redundant()
+        .into_iter()
+        .partition(|file| !is_active(&file.path));
+    let (rewrites, active_rewrites): (Vec<&RepeatedLines>, Vec<&RepeatedLines>) = report
+        .repeated_lines
+        .iter()
+        .partition(|repeated| !is_active(&repeated.file.path));
+    let skipped_active = active_copies.len() + active_rewrites.len();
+    if skipped_active > 0 {
+        eprintln!(
+            "Note: {skipped_active} active session{} skipped for safety.",
+            plural(skipped_active)
+        );
+    }
+    if redundant.is_empty() && rewrites.is_empty() {
+        return Ok(());
+    }
+
+    if !args.yes {
+        let verb = if args.archive.is_some() {
+            "Archive"
+        } else {
+            "Delete"
+        };
+        eprint!(
+            "{verb} {} redundant file{} and rewrite {} file{} without repeated lines? [y/N] ",
+            redundant.len(),
+            plural(redundant.len()),
+            rewrites.len(),
+            plural(rewrites.len())
+        );
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let answer = input.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            eprintln!("Dedupe cancelled.");
+            return Ok(());
+        }
+    }
+
+    let claude_dir = get_claude_dir(cli)?;
+    let mut removed = Vec::new();
+    for file in redundant {
+        let result = match &args.archive {
+            Some(dir) => archive_destination(dir, &claude_dir, &file.path)
+                .and_then(|to| archive_session(&file.path, &to)),
+            None => fs::remove_file(&file.path),
+        };
+        match result {
+            Ok(()) => removed.push(file.path.clone()),
+            Err(e) => eprintln!("Failed to remove {}: {e}", file.path.display()),
+        }
+    }
+
+    let mut dropped_lines = 0;
+    for repeated in &rewrites {
+        let path = &repeated.file.path;
+        let text =
+            fs::read_to_string(path).map_err(|e| SnatchError::io(path.display().to_string(), e))?;
+        let (deduped, dropped) = without_repeated_lines(&text);
+        atomic_write(path, deduped.as_bytes())?;
+        dropped_lines += dropped;
+    }
+
+    if !cli.quiet {
+        eprintln!(
+            "{} {} redundant file{}; dropped {dropped_lines} repeated line{} from {} file{}.",
+            if args.archive.is_some() {
+                "Archived"
+            } else {
+                "Deleted"
+            },
+            removed.len(),
+            plural(removed.len()),
+            plural(dropped_lines),
+            rewrites.len(),
+            plural(rewrites.len())
+        );
+    }
+    if !removed.is_empty() {
+        let config = super::index::load_config(cli);
+        purge_derived_data(cli, &config, &removed)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn line(uuid: &str, session: &str) -> String {
+        format!("{{\"type\":\"user\",\"uuid\":\"{uuid}\",\"sessionId\":\"{session}\"}}\n")
+    }
+
+    fn scanned(id: &str, age: u64, text: &str) -> Scanned {
+        Scanned {
+            file: FileRef {
+                session_id: id.to_string(),
+                project: "/p".to_string(),
+                path: PathBuf::from(format!("/nonexistent/{id}.jsonl")),
+                size: text.len() as u64,
+            },
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(age),
+            contents: Contents::scan(text.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_contained_copies_and_resumed_overlap() {
+        let original = [line("a", "s1"), line("b", "s1")].concat();
+        let grown = [line("a", "s1"), line("b", "s1"), line("c", "s1")].concat();
+        let resumed = [line("a", "s1"), line("b", "s1"), line("d", "s2")].concat();
+        let files = vec![
+            scanned("s1", 10, &grown),
+            scanned("s1-copy", 5, &original),
+            scanned("s2", 20, &resumed),
+        ];
+        let report = find_duplicates(&files, 0.5);
+
+        assert_eq!(report.contained.len(), 1);
+        assert_eq!(report.contained[0].copy.session_id, "s1-copy");
+        assert_eq!(report.contained[0].within.session_id, "s1");
+        // The resumed session records another session id, so it only overlaps.
+        assert_eq!(report.overlapping.len(), 1);
+        assert_eq!(report.overlapping[0].shared, 2);
+        assert_eq!(report.redundant().len(), 1);
+    }
+
+    #[test]
+    fn test_repeated_lines_are_counted_and_dropped() {
+        let text = [
+            line("a", "s"),
+            line("b", "s"),
+            line("a", "s"),
+            "{}\n{}\n".to_string(),
+        ]
+        .concat();
+        assert_eq!(Contents::scan(text.as_bytes()).repeated_lines, 1);
+
+        let (deduped, dropped) = without_repeated_lines(&text);
+        assert_eq!(dropped, 1);
+        assert_eq!(
+            deduped,
+            [line("a", "s"), line("b", "s"), "{}\n{}\n".to_string()].concat()
+        );
+    }
+}
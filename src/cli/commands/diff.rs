@@ -90,7 +90,7 @@ pub fn run(cli: &Cli, args: &DiffArgs) -> Result<()> {
     let claude_dir = if first_provider && second_provider {
         None
     } else {
-        Some(get_claude_dir(cli.claude_dir.as_ref())?)
+        Some(get_claude_dir(cli)?)
     };
 
     // Default to semantic diff (more useful for JSONL sessions)
@@ -247,7 +247,7 @@ fn load_line_target(
             });
         }
     }
-    let content = fs::read_to_string(&path)
+    let content = crate::parser::read_session_to_string(&path)
         .map_err(|e| SnatchError::io(format!("Failed to read file: {}", path.display()), e))?;
     Ok(LineTarget {
         source: DiffSource::classic(&path),
@@ -1,18 +1,97 @@
-//! Doctor command: schema-drift diagnostics across sessions.
+//! Doctor command: environment and schema-drift diagnostics across sessions.
 //!
 //! Claude Code's on-disk schema drifts under snatch (new entry types,
 //! attachment kinds, subtypes; fields emptying out), and the tolerant parser
 //! absorbs it silently. `snatch doctor` reports everything unmodeled — with
 //! counts and last-seen dates so fossils are distinguishable from live
 //! features — plus the known degradation signals.
+//!
+//! It first checks the environment: the Claude directory layout, unreadable
+//! session files, malformed JSONL lines, Claude Code versions, subagent
+//! transcripts whose parent session is gone, and the search index and cache
+//! directory. Each problem carries a suggested fix; `--fix` applies the safe
+//! ones (restoring owner access, removing an abandoned index rebuild lock).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use walkdir::WalkDir;
 
 use crate::analysis::doctor::{Diagnoser, DoctorReport, DriftSighting};
 use crate::cli::{Cli, DoctorArgs, OutputFormat};
+use crate::discovery::{ClaudeDirectory, Session};
 use crate::error::Result;
-use crate::parser::JsonlParser;
+use crate::index::provider::{rebuild_lock_path, ProviderSearchIndex};
+use crate::model::SchemaVersion;
+use crate::parser::{strip_session_suffix, JsonlParser};
 
+use super::get_claude_dir;
 use super::helpers::{self, SessionCollectParams};
 
+/// A search-index rebuild lock older than this is presumed abandoned.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Outcome of one environment check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Ok,
+    Warn,
+    Error,
+}
+
+/// One environment check, with a suggested fix when it did not pass.
+#[derive(Debug, Serialize)]
+struct EnvironmentCheck {
+    name: &'static str,
+    status: CheckStatus,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<String>,
+    /// What `--fix` did about it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fixed: Option<String>,
+}
+
+impl EnvironmentCheck {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            suggestion: None,
+            fixed: None,
+        }
+    }
+
+    fn problem(
+        name: &'static str,
+        status: CheckStatus,
+        detail: impl Into<String>,
+        suggestion: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            suggestion: Some(suggestion.into()),
+            fixed: None,
+        }
+    }
+}
+
+/// JSON output: the drift report with the environment checks alongside.
+#[derive(Serialize)]
+struct DoctorOutput<'a> {
+    #[serde(flatten)]
+    report: &'a DoctorReport,
+    environment: &'a [EnvironmentCheck],
+}
+
 /// Run the doctor command.
 pub fn run(cli: &Cli, args: &DoctorArgs) -> Result<()> {
     if !args.provider.is_empty() {
@@ -28,6 +107,14 @@ pub fn run(cli: &Cli, args: &DoctorArgs) -> Result<()> {
         (None, false) => Some("30d"),
     };
 
+    // Permissions come first so that --fix can make sessions readable
+    // before they are collected.
+    let claude_dir = get_claude_dir(cli)?;
+    let mut environment = vec![
+        check_layout(&claude_dir),
+        check_permissions(&claude_dir, args.fix),
+    ];
+
     let sessions = helpers::collect_sessions(
         cli,
         &SessionCollectParams {
@@ -42,25 +129,416 @@ pub fn run(cli: &Cli, args: &DoctorArgs) -> Result<()> {
 
     let mut diagnoser = Diagnoser::new();
     let mut failed = 0usize;
+    let mut malformed: Vec<(&str, usize)> = Vec::new();
+    let mut versions: BTreeMap<String, usize> = BTreeMap::new();
+    let mut unknown_versions: BTreeSet<String> = BTreeSet::new();
     for session in &sessions {
         let mut parser = JsonlParser::new().with_lenient(true);
         if let Some(max) = cli.max_file_size {
             parser = parser.with_max_file_size(max);
         }
         match parser.parse_file(session.path()) {
-            Ok(entries) => diagnoser.diagnose(session.session_id(), &entries, parser.stats()),
+            Ok(entries) => {
+                let stats = parser.stats();
+                diagnoser.diagnose(session.session_id(), &entries, stats);
+                if stats.lines_skipped > 0 {
+                    malformed.push((session.session_id(), stats.lines_skipped));
+                }
+                if let Some(version) = &stats.schema_version {
+                    *versions.entry(version.to_string()).or_default() += 1;
+                    if let SchemaVersion::Unknown(raw) = version {
+                        unknown_versions.insert(raw.clone());
+                    }
+                }
+            }
             Err(_) => failed += 1,
         }
     }
     let report = diagnoser.finish();
 
+    let all_sessions = claude_dir.all_sessions().unwrap_or_default();
+    environment.push(check_malformed_lines(&mut malformed, failed));
+    environment.push(check_schema_versions(&versions, &unknown_versions));
+    environment.push(check_orphaned_subagents(&all_sessions));
+    environment.extend(check_index(cli, &all_sessions, args.fix));
+    environment.push(check_cache(cli));
+
     match cli.effective_output() {
-        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
-        _ => print_human(&report, failed, since),
+        OutputFormat::Json => {
+            let output = DoctorOutput {
+                report: &report,
+                environment: &environment,
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        _ => print_human(&report, &environment, failed, since),
     }
     Ok(())
 }
 
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// The Claude directory has a `projects/` tree and readable settings.
+fn check_layout(claude_dir: &ClaudeDirectory) -> EnvironmentCheck {
+    const NAME: &str = "layout";
+    let root = claude_dir.root().display();
+    if !claude_dir.has_projects() {
+        return EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Error,
+            format!("{root} has no projects/ directory"),
+            "Point --claude-dir or SNATCH_CLAUDE_DIR at the Claude data root (normally ~/.claude)",
+        );
+    }
+    let settings = claude_dir.settings_path();
+    if settings.exists() {
+        let parsed = fs::read_to_string(&settings)
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                serde_json::from_str::<serde_json::Value>(&text).map_err(|e| e.to_string())
+            });
+        if let Err(error) = parsed {
+            return EnvironmentCheck::problem(
+                NAME,
+                CheckStatus::Warn,
+                format!("{} cannot be read as JSON: {error}", settings.display()),
+                "Fix the file; Claude Code settings in it are not being applied",
+            );
+        }
+    }
+    let projects = fs::read_dir(claude_dir.projects_dir())
+        .map(|entries| entries.flatten().filter(|e| e.path().is_dir()).count())
+        .unwrap_or(0);
+    EnvironmentCheck::ok(
+        NAME,
+        format!(
+            "{root} ({projects} project{}{})",
+            plural(projects),
+            if claude_dir.has_file_history() {
+                ", file history"
+            } else {
+                ""
+            }
+        ),
+    )
+}
+
+/// Every session file and directory under `projects/` can be read.
+fn check_permissions(claude_dir: &ClaudeDirectory, fix: bool) -> EnvironmentCheck {
+    const NAME: &str = "permissions";
+    let denied_error = |error: &io::Error| error.kind() == io::ErrorKind::PermissionDenied;
+    let mut denied: Vec<PathBuf> = Vec::new();
+    // projects/<project>/<session>/subagents/<agent>.jsonl is the deepest
+    // file; compressed sessions count too.
+    for entry in WalkDir::new(claude_dir.projects_dir()).max_depth(4) {
+        match entry {
+            Ok(entry) => {
+                let path = entry.path();
+                let is_session = entry.file_type().is_file()
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(strip_session_suffix)
+                        .is_some();
+                if is_session && fs::File::open(path).is_err_and(|e| denied_error(&e)) {
+                    denied.push(path.to_path_buf());
+                }
+            }
+            Err(error) => {
+                if error.io_error().is_some_and(denied_error) {
+                    denied.extend(error.path().map(Path::to_path_buf));
+                }
+            }
+        }
+    }
+    if denied.is_empty() {
+        return EnvironmentCheck::ok(NAME, "session files and directories are readable");
+    }
+
+    let mut check = EnvironmentCheck::problem(
+        NAME,
+        CheckStatus::Error,
+        format!(
+            "{} unreadable path{} (first: {})",
+            denied.len(),
+            plural(denied.len()),
+            denied[0].display()
+        ),
+        "Restore owner access with `snatch doctor --fix` (or chmod u+rw)",
+    );
+    if fix {
+        let restored = denied
+            .iter()
+            .filter(|path| restore_owner_access(path).is_ok())
+            .count();
+        check.fixed = Some(format!(
+            "restored owner access on {restored} of {}",
+            denied.len()
+        ));
+    }
+    check
+}
+
+/// Add owner read/write (and search, for directories) permission.
+#[cfg(unix)]
+fn restore_owner_access(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = fs::symlink_metadata(path)?;
+    let owner_bits = if metadata.is_dir() { 0o700 } else { 0o600 };
+    let mut permissions = metadata.permissions();
+    permissions.set_mode(permissions.mode() | owner_bits);
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn restore_owner_access(_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "permission repair is only supported on Unix",
+    ))
+}
+
+/// Lines the lenient parser had to skip, worst session first.
+fn check_malformed_lines(malformed: &mut [(&str, usize)], unreadable: usize) -> EnvironmentCheck {
+    const NAME: &str = "jsonl";
+    malformed.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let lines: usize = malformed.iter().map(|(_, n)| n).sum();
+    let mut detail = Vec::new();
+    if lines > 0 {
+        detail.push(format!(
+            "{lines} malformed line{} in {} session{}",
+            plural(lines),
+            malformed.len(),
+            plural(malformed.len())
+        ));
+    }
+    if unreadable > 0 {
+        detail.push(format!(
+            "{unreadable} session{} could not be parsed",
+            plural(unreadable)
+        ));
+    }
+    match malformed.first() {
+        _ if detail.is_empty() => EnvironmentCheck::ok(NAME, "no malformed lines in scope"),
+        Some((worst, _)) => EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            detail.join(", "),
+            format!("Inspect the worst with `snatch validate {worst}`; skipped lines are left out of every view"),
+        ),
+        None => EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            detail.join(", "),
+            "Check file sizes against --max-file-size, then run `snatch validate --all`",
+        ),
+    }
+}
+
+/// Claude Code versions in scope, flagging any snatch does not recognize.
+fn check_schema_versions(
+    versions: &BTreeMap<String, usize>,
+    unknown: &BTreeSet<String>,
+) -> EnvironmentCheck {
+    const NAME: &str = "schema";
+    let mut counts: Vec<_> = versions.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    let summary = counts
+        .iter()
+        .map(|(version, n)| format!("{version} ({n})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if !unknown.is_empty() {
+        return EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "unrecognized Claude Code version{} {}",
+                plural(unknown.len()),
+                unknown.iter().cloned().collect::<Vec<_>>().join(", ")
+            ),
+            "Update snatch; the drift sections below show what it does not model yet",
+        );
+    }
+    if summary.is_empty() {
+        EnvironmentCheck::ok(NAME, "no versioned sessions in scope")
+    } else {
+        EnvironmentCheck::ok(NAME, summary)
+    }
+}
+
+/// Subagent transcripts whose parent session file no longer exists.
+fn check_orphaned_subagents(sessions: &[Session]) -> EnvironmentCheck {
+    const NAME: &str = "subagents";
+    // Subagents live at <project>/<parent>/subagents/<agent>.jsonl, next to
+    // the parent's <project>/<parent>.jsonl (possibly compressed).
+    let orphans: Vec<&Session> = sessions
+        .iter()
+        .filter(|session| session.parent_session_id().is_some())
+        .filter(|session| {
+            session
+                .path()
+                .parent()
+                .and_then(Path::parent)
+                .is_some_and(|dir| {
+                    ["jsonl", "jsonl.gz", "jsonl.zst"]
+                        .iter()
+                        .all(|ext| !dir.with_extension(ext).exists())
+                })
+        })
+        .collect();
+    match orphans.first() {
+        None => EnvironmentCheck::ok(NAME, "every subagent transcript has its parent session"),
+        Some(first) => EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "{} subagent transcript{} without a parent session (first: {})",
+                orphans.len(),
+                plural(orphans.len()),
+                first.path().display()
+            ),
+            "They stay readable with `snatch info <ID>`; delete the <parent>/subagents/ \
+             directory if they are not wanted",
+        ),
+    }
+}
+
+/// The search index opens, covers every provider it was built for, and is
+/// newer than the sessions; an abandoned rebuild lock is reported first,
+/// since it keeps the index from opening.
+fn check_index(cli: &Cli, sessions: &[Session], fix: bool) -> Vec<EnvironmentCheck> {
+    const NAME: &str = "index";
+    let path = super::index::index_path(cli);
+    let mut checks = Vec::new();
+
+    if let Ok(lock) = rebuild_lock_path(&path) {
+        checks.extend(check_rebuild_lock(&lock, fix));
+    }
+
+    if !path.exists() {
+        checks.push(EnvironmentCheck::ok(
+            NAME,
+            "not built (optional; `snatch index build` enables indexed search)",
+        ));
+        return checks;
+    }
+    let check = match ProviderSearchIndex::open_read_only(&path).and_then(|index| index.stats()) {
+        Err(error) => EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Error,
+            format!("{} cannot be opened: {error}", path.display()),
+            "Rebuild it with `snatch index rebuild`",
+        ),
+        Ok(stats) => match stats.build {
+            None => EnvironmentCheck::problem(
+                NAME,
+                CheckStatus::Warn,
+                format!("{} is empty", path.display()),
+                "Build it with `snatch index build`",
+            ),
+            Some(build) if !build.removal_coverage_complete || !build.skipped.is_empty() => {
+                EnvironmentCheck::problem(
+                    NAME,
+                    CheckStatus::Warn,
+                    format!(
+                        "coverage incomplete ({} session{} skipped)",
+                        build.skipped.len(),
+                        plural(build.skipped.len())
+                    ),
+                    "Rebuild it with `snatch index rebuild`",
+                )
+            }
+            Some(build) => {
+                let changed = sessions
+                    .iter()
+                    .filter(|session| session.modified_datetime() > build.built_at)
+                    .count();
+                if changed > 0 {
+                    EnvironmentCheck::problem(
+                        NAME,
+                        CheckStatus::Warn,
+                        format!(
+                            "{changed} session{} changed since the index was built on {}",
+                            plural(changed),
+                            build.built_at.format("%Y-%m-%d %H:%M UTC")
+                        ),
+                        "Update it with `snatch index build`",
+                    )
+                } else {
+                    EnvironmentCheck::ok(
+                        NAME,
+                        format!(
+                            "{} entries from {} sessions, up to date",
+                            stats.entry_count, stats.session_count
+                        ),
+                    )
+                }
+            }
+        },
+    };
+    checks.push(check);
+    checks
+}
+
+/// A rebuild lock older than [`STALE_LOCK_AGE`], removed under `--fix`.
+fn check_rebuild_lock(lock: &Path, fix: bool) -> Option<EnvironmentCheck> {
+    let age = fs::metadata(lock)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .filter(|age| *age > STALE_LOCK_AGE)?;
+    let mut check = EnvironmentCheck::problem(
+        "index lock",
+        CheckStatus::Warn,
+        format!(
+            "rebuild lock {} is {}h old",
+            lock.display(),
+            age.as_secs() / 3600
+        ),
+        "If no `snatch index rebuild` is running, remove it with `snatch doctor --fix`",
+    );
+    if fix {
+        check.fixed = Some(match fs::remove_file(lock) {
+            Ok(()) => "removed the stale lock".to_string(),
+            Err(error) => format!("could not remove the lock: {error}"),
+        });
+    }
+    Some(check)
+}
+
+/// The on-disk cache directory, when it exists, is writable. It is the one
+/// the configuration names, as for every other command.
+fn check_cache(cli: &Cli) -> EnvironmentCheck {
+    const NAME: &str = "cache";
+    let config = super::index::load_config(cli);
+    let Ok(dir) = crate::config::resolve_cache_dir(&config.cache) else {
+        return EnvironmentCheck::ok(
+            NAME,
+            "no platform cache directory; nothing is cached on disk",
+        );
+    };
+    if !dir.exists() {
+        return EnvironmentCheck::ok(NAME, format!("{} (not created yet)", dir.display()));
+    }
+    match tempfile::NamedTempFile::new_in(&dir) {
+        Ok(_) => EnvironmentCheck::ok(NAME, dir.display().to_string()),
+        Err(error) => EnvironmentCheck::problem(
+            NAME,
+            CheckStatus::Error,
+            format!("{} is not writable: {error}", dir.display()),
+            "Fix its permissions (chmod u+rwx) or remove it; snatch recreates it",
+        ),
+    }
+}
+
 fn fmt_sighting(name: &str, s: &DriftSighting) -> String {
     let last_seen = s
         .last_seen
@@ -72,7 +550,12 @@ fn fmt_sighting(name: &str, s: &DriftSighting) -> String {
     )
 }
 
-fn print_human(report: &DoctorReport, failed: usize, since: Option<&str>) {
+fn print_human(
+    report: &DoctorReport,
+    environment: &[EnvironmentCheck],
+    failed: usize,
+    since: Option<&str>,
+) {
     let scope = since.map_or_else(|| "entire corpus".to_string(), |s| format!("since {s}"));
     println!("Doctor Report ({scope})");
     println!("========================================");
@@ -85,6 +568,22 @@ fn print_human(report: &DoctorReport, failed: usize, since: Option<&str>) {
     }
     println!();
 
+    println!("Environment:");
+    for check in environment {
+        let status = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Error => "error",
+        };
+        println!("  {status:<5}  {:<11}  {}", check.name, check.detail);
+        if let Some(fixed) = &check.fixed {
+            println!("  {:<5}  {:<11}  fixed: {fixed}", "", "");
+        } else if let Some(suggestion) = &check.suggestion {
+            println!("  {:<5}  {:<11}  fix: {suggestion}", "", "");
+        }
+    }
+    println!();
+
     println!("Parsing:");
     println!(
         "  Unparsed lines: {} (in {} session(s))",
@@ -187,6 +686,7 @@ fn provider_diagnostics(cli: &Cli, args: &DoctorArgs) -> Result<()> {
         all: _,
         subagents,
         provider: _,
+        fix,
     } = args;
     helpers::refuse_unsupported_flags(
         "doctor --provider (full-corpus provider diagnostics)",
@@ -194,6 +694,7 @@ fn provider_diagnostics(cli: &Cli, args: &DoctorArgs) -> Result<()> {
             ("project", project.is_some()),
             ("--since", since.is_some()),
             ("--subagents", *subagents),
+            ("--fix", *fix),
         ],
     )?;
 
@@ -310,3 +811,132 @@ fn provider_diagnostics(cli: &Cli, args: &DoctorArgs) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARENT: &str = "11111111-2222-3333-4444-555555555555";
+    const LINE: &str =
+        r#"{"type":"user","uuid":"u1","sessionId":"s","message":{"role":"user","content":"hi"}}"#;
+
+    fn claude_dir() -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("projects").join("-home-me-app");
+        fs::create_dir_all(&project).unwrap();
+        (tmp, project)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fix_restores_unreadable_compressed_sessions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (tmp, project) = claude_dir();
+        let session = project.join(format!("{PARENT}.jsonl.zst"));
+        fs::write(&session, b"").unwrap();
+        fs::set_permissions(&session, fs::Permissions::from_mode(0o000)).unwrap();
+        if fs::File::open(&session).is_ok() {
+            // Running as root: nothing is unreadable.
+            return;
+        }
+        let dir = ClaudeDirectory::from_path(tmp.path()).unwrap();
+
+        let check = check_permissions(&dir, false);
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(
+            check.detail.starts_with("1 unreadable path"),
+            "{}",
+            check.detail
+        );
+        assert!(check.fixed.is_none());
+
+        let check = check_permissions(&dir, true);
+        assert_eq!(
+            check.fixed.as_deref(),
+            Some("restored owner access on 1 of 1")
+        );
+        assert!(fs::File::open(&session).is_ok());
+        assert_eq!(check_permissions(&dir, false).status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_fix_removes_only_stale_locks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let fresh = tmp.path().join("fresh.lock");
+        let stale = tmp.path().join("stale.lock");
+        fs::write(&fresh, b"").unwrap();
+        fs::File::create(&stale)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - 2 * STALE_LOCK_AGE)
+            .unwrap();
+
+        assert!(check_rebuild_lock(&fresh, true).is_none());
+        assert!(fresh.exists());
+
+        let check = check_rebuild_lock(&stale, false).unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.detail.ends_with("is 2h old"), "{}", check.detail);
+        assert!(stale.exists());
+
+        let check = check_rebuild_lock(&stale, true).unwrap();
+        assert_eq!(check.fixed.as_deref(), Some("removed the stale lock"));
+        assert!(!stale.exists());
+        assert!(check_rebuild_lock(&tmp.path().join("missing.lock"), true).is_none());
+    }
+
+    #[test]
+    fn test_orphaned_subagents_are_those_without_a_parent_file() {
+        let (tmp, project) = claude_dir();
+        let orphan_parent = "99999999-8888-7777-6666-555555555555";
+        for parent in [PARENT, orphan_parent] {
+            let subagents = project.join(parent).join("subagents");
+            fs::create_dir_all(&subagents).unwrap();
+            fs::write(
+                subagents.join(format!("agent-{}.jsonl", &parent[..8])),
+                LINE,
+            )
+            .unwrap();
+        }
+        // The parent may be compressed.
+        fs::write(project.join(format!("{PARENT}.jsonl.gz")), b"x").unwrap();
+        let sessions = ClaudeDirectory::from_path(tmp.path())
+            .unwrap()
+            .all_sessions()
+            .unwrap();
+
+        let check = check_orphaned_subagents(&sessions);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(
+            check.detail.starts_with("1 subagent transcript without"),
+            "{}",
+            check.detail
+        );
+        assert!(check.detail.contains(orphan_parent), "{}", check.detail);
+
+        fs::write(project.join(format!("{orphan_parent}.jsonl")), LINE).unwrap();
+        let sessions = ClaudeDirectory::from_path(tmp.path())
+            .unwrap()
+            .all_sessions()
+            .unwrap();
+        assert_eq!(check_orphaned_subagents(&sessions).status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_malformed_lines_name_the_worst_session() {
+        let check = check_malformed_lines(&mut [], 0);
+        assert_eq!(check.status, CheckStatus::Ok);
+
+        let check = check_malformed_lines(&mut [("a", 1), ("b", 3)], 0);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(check.detail, "4 malformed lines in 2 sessions");
+        assert!(check
+            .suggestion
+            .unwrap()
+            .starts_with("Inspect the worst with `snatch validate b`"));
+
+        let check = check_malformed_lines(&mut [], 1);
+        assert_eq!(check.detail, "1 session could not be parsed");
+        assert!(check.suggestion.unwrap().contains("--max-file-size"));
+    }
+}
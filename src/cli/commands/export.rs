@@ -7,9 +7,10 @@ use std::io::{self, Write};
 use std::path::PathBuf;
 
 use chrono::Utc;
+use clap::ValueEnum;
 use indicatif::{ProgressBar, ProgressStyle};
 
-use crate::cli::{Cli, ContentFilter, ExportArgs, ExportFormatArg};
+use crate::cli::{Cli, ContentFilter, ExportArgs, ExportFormatArg, RedactionLevel};
 use crate::config::{
     create_sample_template, default_templates_dir, list_templates, load_template, ExportTemplate,
 };
@@ -21,6 +22,8 @@ use crate::export::{
 };
 use crate::model::{ContentBlock, LogEntry};
 use crate::reconstruction::Conversation;
+use crate::tags::{SessionNote, TagStore};
+use crate::util::json_filter::JsonFilter;
 use crate::util::{detect_sensitive, AtomicFile, RedactionConfig, SensitiveDataType};
 
 use super::{get_claude_dir, parse_date_filter};
@@ -90,7 +93,7 @@ fn subagent_transcript_stats(
 }
 
 fn validate_raw_jsonl_compat(args: &ExportArgs) -> Result<()> {
-    if !matches!(args.format, ExportFormatArg::RawJsonl) {
+    if !matches!(args.format(), ExportFormatArg::RawJsonl) {
         return Ok(());
     }
     let mut bad: Vec<&str> = Vec::new();
@@ -127,6 +130,9 @@ fn validate_raw_jsonl_compat(args: &ExportArgs) -> Result<()> {
     if args.warn_pii {
         bad.push("--warn-pii");
     }
+    if args.annotations {
+        bad.push("--annotations");
+    }
     if args.main_thread {
         bad.push("--main-thread");
     }
@@ -179,8 +185,133 @@ fn validate_raw_jsonl_compat(args: &ExportArgs) -> Result<()> {
     })
 }
 
+/// Fill `args` from the export preset `name`, keeping flags set on the
+/// command line. Switches left off and unset options count as unset.
+fn apply_preset(cli: &Cli, args: &ExportArgs, name: &str) -> Result<ExportArgs> {
+    let config = super::index::load_config(cli);
+    let preset = config
+        .presets
+        .get(name)
+        .ok_or_else(|| SnatchError::ConfigError {
+            message: if config.presets.is_empty() {
+                format!("No export preset named '{name}'; none are configured")
+            } else {
+                format!(
+                    "No export preset named '{name}' (available: {})",
+                    config
+                        .presets
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            },
+        })?;
+    let invalid = |field: &str, reason: String| SnatchError::ConfigError {
+        message: format!("Export preset '{name}' has an invalid {field}: {reason}"),
+    };
+
+    let mut resolved = args.clone();
+    if let Some(format) = &preset.format {
+        if args.format.is_none() {
+            resolved.format = Some(
+                ExportFormatArg::from_str(format, true)
+                    .map_err(|reason| invalid("format", reason))?,
+            );
+        }
+    }
+    if let Some(redact) = &preset.redact {
+        if args.redact.is_none() {
+            resolved.redact = Some(
+                RedactionLevel::from_str(redact, true)
+                    .map_err(|reason| invalid("redact", reason))?,
+            );
+        }
+    }
+    if args.only.is_empty() {
+        resolved.only = preset
+            .only
+            .iter()
+            .map(|only| ContentFilter::from_str(only, true))
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|reason| invalid("only", reason))?;
+    }
+    resolved.template = args.template.clone().or_else(|| preset.template.clone());
+    resolved.no_thinking |= preset.thinking == Some(false);
+    resolved.no_tool_use |= preset.tool_use == Some(false);
+    resolved.no_tool_results |= preset.tool_results == Some(false);
+    resolved.no_images |= preset.images == Some(false);
+    resolved.no_timestamps |= preset.timestamps == Some(false);
+    resolved.no_usage |= preset.usage == Some(false);
+    resolved.main_thread |= preset.main_thread;
+    resolved.subagents |= preset.subagents;
+    resolved.system |= preset.system;
+    resolved.metadata |= preset.metadata;
+    resolved.warn_pii |= preset.warn_pii;
+    resolved.toc |= preset.toc;
+    resolved.dark |= preset.dark;
+    resolved.pretty |= preset.pretty;
+    resolved.preset = None;
+    Ok(resolved)
+}
+
 /// Run the export command.
 pub fn run(cli: &Cli, args: &ExportArgs) -> Result<()> {
+    let resolved;
+    let args = match &args.preset {
+        Some(name) => {
+            resolved = apply_preset(cli, args, name)?;
+            &resolved
+        }
+        None => args,
+    };
+    if args.filter.is_some() {
+        super::helpers::refuse_unsupported_flags(
+            "--filter",
+            &[
+                ("--list-templates", args.list_templates),
+                ("--template", args.template.is_some()),
+                ("--combine-agents", args.combine_agents),
+                ("--gist", args.gist),
+                ("--clipboard", args.clipboard),
+            ],
+        )?;
+        if !matches!(
+            args.format(),
+            ExportFormatArg::Json | ExportFormatArg::JsonPretty
+        ) {
+            return Err(SnatchError::InvalidArgument {
+                name: "--filter".to_string(),
+                reason: "filters JSON documents; use --format json or json-pretty".to_string(),
+            });
+        }
+    }
+    if !args.encrypt.is_empty() {
+        super::helpers::refuse_unsupported_flags(
+            "--encrypt",
+            &[
+                ("--all", args.all),
+                ("--list-templates", args.list_templates),
+                ("--template", args.template.is_some()),
+                ("--combine-agents", args.combine_agents),
+                ("--gist", args.gist),
+                ("--clipboard", args.clipboard),
+            ],
+        )?;
+        crate::util::age::check_targets(&args.encrypt)?;
+        if args.output_file.is_none() {
+            return Err(SnatchError::InvalidArgument {
+                name: "--encrypt".to_string(),
+                reason: "writes an encrypted file; name it with --out".to_string(),
+            });
+        }
+        if !crate::util::age::is_available() {
+            return Err(SnatchError::export(
+                "--encrypt needs the age CLI; install it from https://age-encryption.org",
+            ));
+        }
+    }
+
     // Handle --list-templates. It is an independent action; combining it
     // with a provider selection would silently ignore the selection, so the
     // combination is rejected (round-19).
@@ -208,7 +339,7 @@ pub fn run(cli: &Cli, args: &ExportArgs) -> Result<()> {
     // (native/archive), or a session reference qualified with a registered
     // provider's name. The flagless Claude path below stays byte-identical.
     let provider_tier = matches!(
-        args.format,
+        args.format(),
         ExportFormatArg::Native | ExportFormatArg::Archive
     );
     if !args.provider.is_empty()
@@ -245,7 +376,7 @@ pub fn run(cli: &Cli, args: &ExportArgs) -> Result<()> {
         }
 
         // Gist doesn't support SQLite format
-        if matches!(args.format, ExportFormatArg::Sqlite) {
+        if matches!(args.format(), ExportFormatArg::Sqlite) {
             return Err(SnatchError::ConfigError {
                 message: "--gist is not compatible with SQLite format".to_string(),
             });
@@ -278,7 +409,7 @@ pub fn run(cli: &Cli, args: &ExportArgs) -> Result<()> {
         }
 
         // Clipboard doesn't support SQLite format
-        if matches!(args.format, ExportFormatArg::Sqlite) {
+        if matches!(args.format(), ExportFormatArg::Sqlite) {
             return Err(SnatchError::ConfigError {
                 message: "--clipboard is not compatible with SQLite format".to_string(),
             });
@@ -310,6 +441,16 @@ pub fn run(cli: &Cli, args: &ExportArgs) -> Result<()> {
                 "Note: --since/--until filters are only applied with --all; single session export ignores them"
             );
         }
+        if (args.tag.is_some() || args.tags.is_some()) && !cli.quiet {
+            eprintln!(
+                "Note: --tag/--tags filters are only applied with --all; single session export ignores them"
+            );
+        }
+        if args.annotations && !supports_annotations(args.format()) && !cli.quiet {
+            eprintln!(
+                "Note: --annotations only adds a section to markdown and text exports; ignored for this format"
+            );
+        }
         // --subagents only affects --all batch discovery; on a single session it
         // is inert (issue 0007). Point the user at the working path.
         if args.subagents && !args.combine_agents && !cli.quiet {
@@ -328,7 +469,7 @@ pub fn run(cli: &Cli, args: &ExportArgs) -> Result<()> {
 
 /// Export a single session.
 fn export_single_session(cli: &Cli, args: &ExportArgs, session_id: &str) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Find the session
     let session =
@@ -381,7 +522,7 @@ fn export_single_session(cli: &Cli, args: &ExportArgs, session_id: &str) -> Resu
     // (parent + subagent transcripts, verbatim); all other formats get the
     // parsed, time-interleaved combine.
     if args.combine_agents {
-        if matches!(args.format, ExportFormatArg::RawJsonl) {
+        if matches!(args.format(), ExportFormatArg::RawJsonl) {
             let output = args.output_file.as_ref();
             export_raw_combined_agents(cli, &session, output, !args.no_chain)?;
             if let (false, Some(out)) = (cli.quiet, output) {
@@ -406,21 +547,42 @@ fn export_single_session(cli: &Cli, args: &ExportArgs, session_id: &str) -> Resu
         return export_session_with_template(cli, args, &session, template_name);
     }
 
+    // With --encrypt the plaintext goes to a private temporary directory
+    // (removed on drop) and only the encrypted file lands at --out.
+    let plaintext = if args.encrypt.is_empty() {
+        None
+    } else {
+        let dir = tempfile::tempdir()
+            .map_err(|e| SnatchError::io("Failed to create a temporary directory", e))?;
+        let path = dir
+            .path()
+            .join(format!("export.{}", get_format_extension(args.format())));
+        Some((dir, path))
+    };
+    let target = plaintext
+        .as_ref()
+        .map(|(_, path)| path)
+        .or(args.output_file.as_ref());
+
     // Export the session (chain-aware by default for single-session export).
-    let exported = export_session(
-        cli,
-        args,
-        &session,
-        args.output_file.as_ref(),
-        !args.no_chain,
-    )?;
+    let exported = export_session(cli, args, &session, target, !args.no_chain)?;
+    if let (true, Some((_, path)), Some(output_file)) =
+        (exported, &plaintext, args.output_file.as_ref())
+    {
+        crate::util::age::encrypt_file(path, output_file, &args.encrypt)?;
+    }
 
     // Print success message to stderr if writing to file
     if let (true, Some(output_file)) = (exported && !cli.quiet, args.output_file.as_ref()) {
         eprintln!(
-            "Exported session {} to {}",
+            "Exported session {} to {}{}",
             session_id,
-            output_file.display()
+            output_file.display(),
+            if plaintext.is_some() {
+                " (encrypted)"
+            } else {
+                ""
+            }
         );
     }
 
@@ -524,7 +686,7 @@ fn export_session_to_gist(cli: &Cli, args: &ExportArgs, session: &Session) -> Re
     // chain envelope metadata is attached.
     let content = export_to_string(
         &conversation,
-        args.format,
+        args.format(),
         &options,
         args.pretty,
         args.main_thread,
@@ -534,7 +696,7 @@ fn export_session_to_gist(cli: &Cli, args: &ExportArgs, session: &Session) -> Re
     )?;
 
     // Generate filename
-    let ext = get_format_extension(args.format);
+    let ext = get_format_extension(args.format());
     let short_id = &session.session_id()[..8.min(session.session_id().len())];
     let filename = format!("session-{short_id}.{ext}");
 
@@ -575,7 +737,7 @@ fn export_combined_agents(cli: &Cli, args: &ExportArgs, session: &Session) -> Re
     use crate::discovery::{collect_hierarchy_entries, HierarchyBuilder};
 
     // Get the project for this session
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let projects = claude_dir.projects()?;
 
     // Find the project containing this session
@@ -662,7 +824,7 @@ fn export_combined_agents(cli: &Cli, args: &ExportArgs, session: &Session) -> Re
     let conversation = crate::export::apply_export_transform(&conversation, &options).into_owned();
 
     // Handle SQLite separately as it manages its own file
-    if matches!(args.format, ExportFormatArg::Sqlite) {
+    if matches!(args.format(), ExportFormatArg::Sqlite) {
         if let Some(output_path) = &args.output_file {
             let exporter = SqliteExporter::new();
             exporter.export_to_file(&conversation, output_path, &options)?;
@@ -688,7 +850,7 @@ fn export_combined_agents(cli: &Cli, args: &ExportArgs, session: &Session) -> Re
         let mut atomic = AtomicFile::create(output_path)?;
         let mut output = std::io::BufWriter::new(atomic.writer());
 
-        match args.format {
+        match args.format() {
             ExportFormatArg::Native | ExportFormatArg::Archive => {
                 return Err(SnatchError::ConfigError {
                     message: "native/archive are provider-routed formats; handled before the \
@@ -748,7 +910,7 @@ fn export_combined_agents(cli: &Cli, args: &ExportArgs, session: &Session) -> Re
         // Write to stdout (no atomic write needed)
         let mut output: Box<dyn Write> = Box::new(io::stdout());
 
-        match args.format {
+        match args.format() {
             ExportFormatArg::Native | ExportFormatArg::Archive => {
                 return Err(SnatchError::ConfigError {
                     message: "native/archive are provider-routed formats; handled before the \
@@ -833,11 +995,11 @@ fn find_node_in_children<'a>(
 /// Export all sessions matching filters.
 fn export_all_sessions(cli: &Cli, args: &ExportArgs) -> Result<()> {
     // Special handling for SQLite: export all sessions to a single database
-    if matches!(args.format, ExportFormatArg::Sqlite) {
+    if matches!(args.format(), ExportFormatArg::Sqlite) {
         return export_all_sessions_sqlite(cli, args);
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     let chain_aware = !args.no_chain;
 
@@ -868,9 +1030,15 @@ fn export_all_sessions(cli: &Cli, args: &ExportArgs) -> Result<()> {
 
     // Get all sessions
     let all_sessions = claude_dir.all_sessions()?;
+    let tag_filters = super::helpers::tag_filters(args.tag.as_deref(), args.tags.as_deref());
+    let tag_store = if tag_filters.is_empty() {
+        TagStore::default()
+    } else {
+        TagStore::load()?
+    };
 
-    // Filter sessions (project + subagent; date is applied per logical chain
-    // below when chain-aware).
+    // Filter sessions (project + subagent + tags; date is applied per logical
+    // chain below when chain-aware).
     let mut sessions: Vec<&Session> = all_sessions
         .iter()
         .filter(|s| {
@@ -881,6 +1049,13 @@ fn export_all_sessions(cli: &Cli, args: &ExportArgs) -> Result<()> {
                 }
             }
 
+            // Apply tag filters
+            if !tag_filters.is_empty()
+                && !super::helpers::session_has_any_tag(&tag_store, s.session_id(), &tag_filters)
+            {
+                return false;
+            }
+
             // Apply session filter
             filter.matches(s).unwrap_or_default()
         })
@@ -1005,7 +1180,7 @@ fn export_all_sessions(cli: &Cli, args: &ExportArgs) -> Result<()> {
         })?;
     }
 
-    let extension = get_format_extension(args.format);
+    let extension = get_format_extension(args.format());
     let mut exported_count = 0;
     let mut error_count = 0;
     let mut skipped_count = 0;
@@ -1100,7 +1275,7 @@ fn export_all_sessions(cli: &Cli, args: &ExportArgs) -> Result<()> {
 fn export_all_sessions_sqlite(cli: &Cli, args: &ExportArgs) -> Result<()> {
     use rusqlite::Connection;
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // SQLite export requires an output file
     let output_path = args.output_file.as_ref().ok_or_else(|| {
@@ -1135,9 +1310,15 @@ fn export_all_sessions_sqlite(cli: &Cli, args: &ExportArgs) -> Result<()> {
 
     // Get all sessions
     let all_sessions = claude_dir.all_sessions()?;
+    let tag_filters = super::helpers::tag_filters(args.tag.as_deref(), args.tags.as_deref());
+    let tag_store = if tag_filters.is_empty() {
+        TagStore::default()
+    } else {
+        TagStore::load()?
+    };
 
-    // Filter sessions (project + subagent; date applied per logical chain below
-    // when chain-aware).
+    // Filter sessions (project + subagent + tags; date applied per logical
+    // chain below when chain-aware).
     let mut sessions: Vec<&Session> = all_sessions
         .iter()
         .filter(|s| {
@@ -1146,6 +1327,11 @@ fn export_all_sessions_sqlite(cli: &Cli, args: &ExportArgs) -> Result<()> {
                     return false;
                 }
             }
+            if !tag_filters.is_empty()
+                && !super::helpers::session_has_any_tag(&tag_store, s.session_id(), &tag_filters)
+            {
+                return false;
+            }
             filter.matches(s).unwrap_or_default()
         })
         .collect();
@@ -1465,6 +1651,9 @@ fn stream_raw_paths(paths: &[PathBuf], output_path: Option<&PathBuf>) -> Result<
         let mut atomic = AtomicFile::create(path)?;
         let mut writer = std::io::BufWriter::new(atomic.writer());
         copy_all(&mut writer)?;
+        if let Some(ref section) = annotations {
+            writer.write_all(section.as_bytes())?;
+        }
         writer.flush()?;
         drop(writer);
         atomic.finish()?;
@@ -1505,7 +1694,7 @@ fn export_raw_combined_agents(
     }
 
     // Subagent source files: every descendant in the agent hierarchy, pre-order.
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let projects = claude_dir.projects()?;
     let project = projects
         .iter()
@@ -1548,7 +1737,7 @@ fn raw_chain_source_paths(
     chain_aware: bool,
 ) -> Result<RawChainSources> {
     if chain_aware {
-        let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+        let claude_dir = get_claude_dir(cli)?;
         if let Some(project) = claude_dir.projects()?.into_iter().find(|p| {
             p.best_path() == session.project_path() || p.decoded_path() == session.project_path()
         }) {
@@ -1583,7 +1772,7 @@ fn raw_chain_source_paths(
     Ok((vec![session.path().to_path_buf()], None))
 }
 
-fn export_session(
+pub(super) fn export_session(
     cli: &Cli,
     args: &ExportArgs,
     session: &Session,
@@ -1593,12 +1782,12 @@ fn export_session(
     // raw-jsonl bypasses parsing/reconstruction entirely and streams the
     // original source file(s) verbatim (byte-for-byte). Chain-order when
     // chain-aware and part of a multi-file chain.
-    if matches!(args.format, ExportFormatArg::RawJsonl) {
+    if matches!(args.format(), ExportFormatArg::RawJsonl) {
         return export_raw_jsonl(cli, session, output_path, chain_aware);
     }
 
     // Parse the session, reconstructing the full resume chain when chain-aware.
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let (mut entries, unparsed, chain) = super::helpers::resolve_chain_entries(
         &claude_dir,
         session,
@@ -1628,6 +1817,17 @@ fn export_session(
         return Ok(false);
     }
 
+    let annotations = if args.annotations && supports_annotations(args.format()) {
+        let own_id = session.session_id();
+        let mut ids = vec![own_id.to_string()];
+        if let Some(ref chain) = chain {
+            ids.extend(chain.members.iter().filter(|id| *id != own_id).cloned());
+        }
+        annotations_section(args.format(), &session_notes(&ids)?)
+    } else {
+        None
+    };
+
     // Chain metadata for the JSON export envelope (carried like messages JSON).
     let chain_export = chain.as_ref().map(|c| ChainExportMeta {
         root_id: c.root_id.clone(),
@@ -1691,7 +1891,7 @@ fn export_session(
     let conversation = crate::export::apply_export_transform(&conversation, &options).into_owned();
 
     // Handle SQLite separately as it manages its own file
-    if matches!(args.format, ExportFormatArg::Sqlite) {
+    if matches!(args.format(), ExportFormatArg::Sqlite) {
         if args.clipboard {
             return Err(SnatchError::ConfigError {
                 message: "--clipboard is not compatible with SQLite format".to_string(),
@@ -1709,7 +1909,8 @@ fn export_session(
                 git_commit: None,
             };
             exporter.export_to_file_with_meta(&conversation, path, &options, Some(&meta))?;
-            if args.session.is_some() && !cli.quiet {
+            // With --encrypt, `path` is the temporary plaintext.
+            if args.session.is_some() && args.encrypt.is_empty() && !cli.quiet {
                 eprintln!(
                     "Exported {} entries to {}",
                     conversation.len(),
@@ -1728,7 +1929,7 @@ fn export_session(
     if args.clipboard {
         let content = export_to_string(
             &conversation,
-            args.format,
+            args.format(),
             &options,
             args.pretty,
             args.main_thread,
@@ -1736,25 +1937,22 @@ fn export_session(
             args.dark,
             chain_export.clone(),
         )?;
+        let content = match annotations {
+            Some(section) => content + &section,
+            None => content,
+        };
 
-        match arboard::Clipboard::new() {
-            Ok(mut clipboard) => {
-                if let Err(e) = clipboard.set_text(&content) {
-                    return Err(SnatchError::ExportError {
-                        message: format!("Failed to copy to clipboard: {e}"),
-                        source: None,
-                    });
-                }
-                if !cli.quiet {
-                    eprintln!("Copied {} entries to clipboard.", conversation.len());
-                }
-            }
-            Err(e) => {
-                return Err(SnatchError::ExportError {
-                    message: format!("Failed to access clipboard: {e}"),
-                    source: None,
-                });
-            }
+        let method =
+            crate::util::clipboard::copy(&content).map_err(|e| SnatchError::ExportError {
+                message: format!("Failed to copy to clipboard: {e}"),
+                source: None,
+            })?;
+        if !cli.quiet {
+            eprintln!(
+                "Copied {} entries to {}.",
+                conversation.len(),
+                method.describe()
+            );
         }
         return Ok(true);
     }
@@ -1764,7 +1962,7 @@ fn export_session(
         let mut atomic = AtomicFile::create(path)?;
         let mut writer = std::io::BufWriter::new(atomic.writer());
 
-        match args.format {
+        match args.format() {
             ExportFormatArg::Native | ExportFormatArg::Archive => {
                 return Err(SnatchError::ConfigError {
                     message: "native/archive are provider-routed formats; handled before the \
@@ -1780,13 +1978,25 @@ fn export_session(
                 let exporter = JsonExporter::new()
                     .pretty(args.pretty)
                     .with_chain(chain_export.clone());
-                exporter.export_conversation(&conversation, &mut writer, &options)?;
+                export_json(
+                    &exporter,
+                    &conversation,
+                    &mut writer,
+                    &options,
+                    args.filter.as_ref(),
+                )?;
             }
             ExportFormatArg::JsonPretty => {
                 let exporter = JsonExporter::new()
                     .pretty(true)
                     .with_chain(chain_export.clone());
-                exporter.export_conversation(&conversation, &mut writer, &options)?;
+                export_json(
+                    &exporter,
+                    &conversation,
+                    &mut writer,
+                    &options,
+                    args.filter.as_ref(),
+                )?;
             }
             ExportFormatArg::Text => {
                 let exporter = TextExporter::new();
@@ -1815,7 +2025,8 @@ fn export_session(
         drop(writer);
         atomic.finish()?;
 
-        if args.session.is_some() && !cli.quiet {
+        // With --encrypt, `path` is the temporary plaintext.
+        if args.session.is_some() && args.encrypt.is_empty() && !cli.quiet {
             eprintln!(
                 "Exported {} entries to {}",
                 conversation.len(),
@@ -1826,7 +2037,7 @@ fn export_session(
         // Write to stdout (no atomic write needed)
         let mut writer: Box<dyn Write> = Box::new(io::stdout().lock());
 
-        match args.format {
+        match args.format() {
             ExportFormatArg::Native | ExportFormatArg::Archive => {
                 return Err(SnatchError::ConfigError {
                     message: "native/archive are provider-routed formats; handled before the \
@@ -1842,13 +2053,25 @@ fn export_session(
                 let exporter = JsonExporter::new()
                     .pretty(args.pretty)
                     .with_chain(chain_export.clone());
-                exporter.export_conversation(&conversation, &mut writer, &options)?;
+                export_json(
+                    &exporter,
+                    &conversation,
+                    &mut writer,
+                    &options,
+                    args.filter.as_ref(),
+                )?;
             }
             ExportFormatArg::JsonPretty => {
                 let exporter = JsonExporter::new()
                     .pretty(true)
                     .with_chain(chain_export.clone());
-                exporter.export_conversation(&conversation, &mut writer, &options)?;
+                export_json(
+                    &exporter,
+                    &conversation,
+                    &mut writer,
+                    &options,
+                    args.filter.as_ref(),
+                )?;
             }
             ExportFormatArg::Text => {
                 let exporter = TextExporter::new();
@@ -1875,14 +2098,75 @@ fn export_session(
             }
         }
 
+        if let Some(ref section) = annotations {
+            writer.write_all(section.as_bytes())?;
+        }
         writer.flush()?;
     }
 
     Ok(true)
 }
 
+/// Whether `--annotations` adds a section to this format.
+fn supports_annotations(format: ExportFormatArg) -> bool {
+    matches!(
+        format,
+        ExportFormatArg::Markdown | ExportFormatArg::Md | ExportFormatArg::Text
+    )
+}
+
+/// Notes stored for the given session files, in file then creation order.
+fn session_notes(session_ids: &[String]) -> Result<Vec<SessionNote>> {
+    let store = TagStore::load()?;
+    Ok(session_ids
+        .iter()
+        .filter_map(|id| store.get_notes(id))
+        .flatten()
+        .cloned()
+        .collect())
+}
+
+/// Render notes as an Annotations section appended after the conversation.
+///
+/// Returns `None` when there are no notes or the format has no section.
+fn annotations_section(format: ExportFormatArg, notes: &[SessionNote]) -> Option<String> {
+    if notes.is_empty() || !supports_annotations(format) {
+        return None;
+    }
+    let markdown = !matches!(format, ExportFormatArg::Text);
+    let mut out = if markdown {
+        String::from("\n## Annotations\n\n")
+    } else {
+        String::from("\nANNOTATIONS:\n")
+    };
+    for note in notes {
+        let target = match (&note.message, markdown) {
+            (Some(uuid), true) => format!("Message `{uuid}`"),
+            (Some(uuid), false) => format!("Message {uuid}"),
+            (None, _) => "Session".to_string(),
+        };
+        let label = note
+            .label
+            .as_ref()
+            .map(|label| format!(" [{label}]"))
+            .unwrap_or_default();
+        let when = note.created_at.format("%Y-%m-%d %H:%M");
+        let mut lines = note.text.lines();
+        let first = lines.next().unwrap_or("");
+        if markdown {
+            out.push_str(&format!("- **{target}**{label} ({when}): {first}\n"));
+        } else {
+            out.push_str(&format!("  {target}{label} ({when}): {first}\n"));
+        }
+        for line in lines {
+            out.push_str(&format!("    {line}\n"));
+        }
+    }
+    Some(out)
+}
+
 /// Get the file extension for a format.
-fn get_format_extension(format: ExportFormatArg) -> &'static str {
+pub(super) fn get_format_extension(format: ExportFormatArg) -> &'static str {
     match format {
         ExportFormatArg::Markdown | ExportFormatArg::Md => "md",
         ExportFormatArg::Json | ExportFormatArg::JsonPretty => "json",
@@ -2077,10 +2361,10 @@ fn upload_to_gist(
     Ok(url)
 }
 
-/// Export a conversation to a string for gist upload.
+/// Export a conversation to a string for gist upload and `show` excerpts.
 #[allow(clippy::fn_params_excessive_bools)]
 #[allow(clippy::too_many_arguments)]
-fn export_to_string(
+pub(super) fn export_to_string(
     conversation: &Conversation,
     format: ExportFormatArg,
     options: &ExportOptions,
@@ -2194,28 +2478,18 @@ fn export_session_with_template(
             source: None,
         })?;
 
-        match arboard::Clipboard::new() {
-            Ok(mut clipboard) => {
-                if let Err(e) = clipboard.set_text(&content) {
-                    return Err(SnatchError::ExportError {
-                        message: format!("Failed to copy to clipboard: {e}"),
-                        source: None,
-                    });
-                }
-                if !cli.quiet {
-                    eprintln!(
-                        "Copied {} entries to clipboard using template '{}'.",
-                        conversation.len(),
-                        template_name
-                    );
-                }
-            }
-            Err(e) => {
-                return Err(SnatchError::ExportError {
-                    message: format!("Failed to access clipboard: {e}"),
-                    source: None,
-                });
-            }
+        let method =
+            crate::util::clipboard::copy(&content).map_err(|e| SnatchError::ExportError {
+                message: format!("Failed to copy to clipboard: {e}"),
+                source: None,
+            })?;
+        if !cli.quiet {
+            eprintln!(
+                "Copied {} entries to {} using template '{}'.",
+                conversation.len(),
+                method.describe(),
+                template_name
+            );
         }
         return Ok(());
     }
@@ -2460,8 +2734,9 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
     // COMPLETE argument classification: the struct is destructured WITHOUT
     // `..`, so a new ExportArgs field must be classified here to compile
     // (round-19 exhaustiveness). Universal: session, --provider, -O/--out,
-    // -f/--format, --overwrite (--list-templates is rejected against
-    // --provider in run()). Fidelity tiers stream source data unmodified,
+    // -f/--format, --overwrite, --filter (--list-templates is rejected
+    // against --provider in run(), which also limits --filter to the JSON
+    // formats). Fidelity tiers stream source data unmodified,
     // so every content-shaping, filtering, presentation, security-
     // transform, and delivery flag is refused when it deviates from its
     // default — never silently ignored (--warn-pii/--redact-preview/
@@ -2476,6 +2751,8 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
         project,
         since,
         until,
+        tag,
+        tags,
         subagents,
         combine_agents,
         resolve_tool_results,
@@ -2494,6 +2771,7 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
         usage,
         no_usage,
         metadata,
+        annotations,
         main_thread,
         no_chain,
         pretty,
@@ -2511,9 +2789,12 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
         clipboard,
         template,
         list_templates: _,
+        filter: _,
+        encrypt,
+        preset: _,
     } = args;
     let fidelity_tier = matches!(
-        args.format,
+        args.format(),
         ExportFormatArg::RawJsonl | ExportFormatArg::Native | ExportFormatArg::Archive
     );
     if fidelity_tier {
@@ -2524,6 +2805,8 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
                 ("--project", project.is_some()),
                 ("--since", since.is_some()),
                 ("--until", until.is_some()),
+                ("--tag", tag.is_some()),
+                ("--tags", tags.is_some()),
                 ("--subagents", *subagents),
                 ("--combine-agents", *combine_agents),
                 ("--resolve-tool-results", *resolve_tool_results),
@@ -2542,6 +2825,7 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
                 ("--usage=false", !*usage),
                 ("--no-usage", *no_usage),
                 ("--metadata", *metadata),
+                ("--annotations", *annotations),
                 ("--main-thread", *main_thread),
                 ("--no-chain", *no_chain),
                 ("--pretty", *pretty),
@@ -2557,6 +2841,7 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
                 ("--dark", *dark),
                 ("--clipboard", *clipboard),
                 ("--template", template.is_some()),
+                ("--encrypt", !encrypt.is_empty()),
             ],
         )?;
     } else {
@@ -2571,9 +2856,12 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
                 ("--project", project.is_some()),
                 ("--since", since.is_some()),
                 ("--until", until.is_some()),
+                ("--tag", tag.is_some()),
+                ("--tags", tags.is_some()),
                 ("--subagents", *subagents),
                 ("--combine-agents", *combine_agents),
                 ("--resolve-tool-results", *resolve_tool_results),
+                ("--annotations", *annotations),
                 ("--no-chain", *no_chain),
                 ("--progress", *progress),
                 ("--gist", *gist),
@@ -2581,6 +2869,7 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
                 ("--gist-description", gist_description.is_some()),
                 ("--clipboard", *clipboard),
                 ("--template", template.is_some()),
+                ("--encrypt", !encrypt.is_empty()),
             ],
         )?;
     }
@@ -2610,7 +2899,7 @@ fn export_via_provider(cli: &Cli, args: &ExportArgs) -> Result<()> {
         Archive,
         Native(crate::provider::ArtifactId),
     }
-    let tier = match args.format {
+    let tier = match args.format() {
         ExportFormatArg::RawJsonl => {
             if !capabilities.raw_jsonl {
                 return Err(SnatchError::ConfigError {
@@ -2735,25 +3024,58 @@ fn normalized_provider_options(args: &ExportArgs) -> ExportOptions {
     }
 }
 
+/// Run the JSON exporter, passing its document through `--filter` when one
+/// is given.
+fn export_json<W: Write>(
+    exporter: &JsonExporter,
+    conversation: &Conversation,
+    writer: &mut W,
+    options: &ExportOptions,
+    filter: Option<&JsonFilter>,
+) -> Result<()> {
+    let Some(filter) = filter else {
+        return exporter.export_conversation(conversation, writer, options);
+    };
+    let mut document = Vec::new();
+    exporter.export_conversation(conversation, &mut document, options)?;
+    let document: serde_json::Value = serde_json::from_slice(&document)?;
+    writeln!(
+        writer,
+        "{}",
+        super::helpers::json_output(&document, Some(filter))?
+    )?;
+    Ok(())
+}
+
 fn write_normalized_provider_export<W: Write>(
     conversation: &Conversation,
     args: &ExportArgs,
     options: &ExportOptions,
     writer: &mut W,
 ) -> Result<()> {
-    match args.format {
+    match args.format() {
         ExportFormatArg::Markdown | ExportFormatArg::Md => {
             MarkdownExporter::new().export_conversation(conversation, writer, options)?;
         }
         ExportFormatArg::Json => {
-            JsonExporter::new()
-                .pretty(args.pretty)
-                .export_conversation(conversation, writer, options)?;
+            let exporter = JsonExporter::new().pretty(args.pretty);
+            export_json(
+                &exporter,
+                conversation,
+                writer,
+                options,
+                args.filter.as_ref(),
+            )?;
         }
         ExportFormatArg::JsonPretty => {
-            JsonExporter::new()
-                .pretty(true)
-                .export_conversation(conversation, writer, options)?;
+            let exporter = JsonExporter::new().pretty(true);
+            export_json(
+                &exporter,
+                conversation,
+                writer,
+                options,
+                args.filter.as_ref(),
+            )?;
         }
         ExportFormatArg::Text => {
             TextExporter::new().export_conversation(conversation, writer, options)?;
@@ -2804,7 +3126,7 @@ fn export_normalized_via_provider(
     let options = normalized_provider_options(args);
     let conversation = crate::export::apply_export_transform(&conversation, &options).into_owned();
 
-    if matches!(args.format, ExportFormatArg::Sqlite) && args.output_file.is_none() {
+    if matches!(args.format(), ExportFormatArg::Sqlite) && args.output_file.is_none() {
         return Err(SnatchError::export(
             "SQLite export requires an output file (--output <path.db>)",
         ));
@@ -2820,7 +3142,7 @@ fn export_normalized_via_provider(
         }
     }
 
-    if matches!(args.format, ExportFormatArg::Sqlite) {
+    if matches!(args.format(), ExportFormatArg::Sqlite) {
         let path = args.output_file.as_ref().expect("preflight requires path");
         let parent = path.parent().ok_or_else(|| SnatchError::IoError {
             context: format!("Cannot determine parent directory for: {}", path.display()),
@@ -2884,6 +3206,26 @@ mod tests {
         assert!(parse_date_filter("abc123").is_err());
     }
 
+    #[test]
+    fn test_preset_format_yields_to_explicit_flag() {
+        use clap::Parser;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        std::fs::write(&config, "[presets.share]\nformat = \"html\"\n").unwrap();
+        let cli =
+            Cli::try_parse_from(["snatch", "--config", config.to_str().unwrap(), "list"]).unwrap();
+
+        let args = ExportArgs::try_parse_from(["export", "abc"]).unwrap();
+        let resolved = apply_preset(&cli, &args, "share").unwrap();
+        assert_eq!(resolved.format(), ExportFormatArg::Html);
+
+        // The default format given explicitly still wins over the preset.
+        let args = ExportArgs::try_parse_from(["export", "abc", "-f", "markdown"]).unwrap();
+        let resolved = apply_preset(&cli, &args, "share").unwrap();
+        assert_eq!(resolved.format(), ExportFormatArg::Markdown);
+    }
+
     #[test]
     fn test_get_format_extension() {
         assert_eq!(get_format_extension(ExportFormatArg::Markdown), "md");
@@ -2893,6 +3235,26 @@ mod tests {
         assert_eq!(get_format_extension(ExportFormatArg::RawJsonl), "jsonl");
     }
 
+    #[test]
+    fn test_annotations_section_renders_notes() {
+        let notes = vec![
+            SessionNote::with_label("Wrong fix", "bug").for_message("abc-123"),
+            SessionNote::new("Worth revisiting\nafter the refactor"),
+        ];
+
+        let markdown = annotations_section(ExportFormatArg::Markdown, &notes).unwrap();
+        assert!(markdown.starts_with("\n## Annotations\n\n"));
+        assert!(markdown.contains("- **Message `abc-123`** [bug] ("));
+        assert!(markdown.contains("): Wrong fix\n"));
+        assert!(markdown.contains("- **Session** ("));
+        assert!(markdown.ends_with("    after the refactor\n"));
+
+        let text = annotations_section(ExportFormatArg::Text, &notes).unwrap();
+        assert!(text.contains("  Message abc-123 [bug] ("));
+        assert!(annotations_section(ExportFormatArg::Json, &notes).is_none());
+        assert!(annotations_section(ExportFormatArg::Markdown, &[]).is_none());
+    }
+
     #[test]
     fn test_raw_jsonl_rejects_warn_pii() {
         use clap::Parser;
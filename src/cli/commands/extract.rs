@@ -14,7 +14,7 @@ use super::get_claude_dir;
 
 /// Run the extract command.
 pub fn run(cli: &Cli, args: &ExtractArgs) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Extract data based on scope
     let data = if let Some(ref project_path) = args.project {
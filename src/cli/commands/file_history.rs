@@ -37,7 +37,7 @@ pub fn run(cli: &Cli, args: &FileHistoryArgs) -> Result<()> {
     if !args.provider.is_empty() {
         return run_provider(cli, args);
     }
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let mut writer = PagerWriter::new(false);
 
     // Collect sessions to index
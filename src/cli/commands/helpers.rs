@@ -9,10 +9,11 @@ use std::time::SystemTime;
 use chrono::{DateTime, Utc};
 use regex::Regex;
 
-use crate::cli::Cli;
+use crate::cli::{Cli, OutputFormat};
 use crate::discovery::{ClaudeDirectory, Project, Session};
 use crate::error::{Result, SnatchError};
 use crate::model::{ContentBlock, LogEntry};
+use crate::util::json_filter::JsonFilter;
 
 use super::get_claude_dir;
 
@@ -644,7 +645,7 @@ pub fn filter_projects(projects: Vec<Project>, filter: &str) -> Vec<Project> {
 /// Uses `filter_projects` for smart matching, then requires exactly one result.
 /// Returns an error if zero or multiple projects match.
 pub fn resolve_single_project(cli: &Cli, filter: &str) -> Result<crate::discovery::Project> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let projects = claude_dir.projects()?;
     let mut matches = filter_projects(projects, filter);
 
@@ -687,7 +688,7 @@ pub struct SessionCollectParams<'a> {
 
 /// Collect sessions matching common filter parameters.
 pub fn collect_sessions(cli: &Cli, params: &SessionCollectParams) -> Result<Vec<Session>> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     let mut sessions = if let Some(session_id) = params.session {
         let session =
@@ -777,9 +778,27 @@ pub fn main_thread_entries(entries: &[LogEntry]) -> Vec<&LogEntry> {
 /// construction path for CLI surfaces, so global parsing limits can never
 /// be silently dropped (round-18 blocker 4).
 pub fn provider_registry(cli: &crate::cli::Cli) -> crate::provider::registry::ProviderRegistry {
+    provider_registry_with_peers(cli, Vec::new())
+}
+
+/// [`provider_registry`] with more Claude directories merged into the
+/// Claude root, such as the configured `[[index.sources]]`.
+pub fn provider_registry_with_peers(
+    cli: &crate::cli::Cli,
+    peers: Vec<std::path::PathBuf>,
+) -> crate::provider::registry::ProviderRegistry {
+    let mut claude_peers = super::peer_claude_dirs(cli);
+    claude_peers.extend(peers);
     crate::provider::registry::ProviderRegistry::with_config(
         &crate::provider::registry::RegistryConfig {
-            claude_root: cli.claude_dir.clone(),
+            claude_root: match super::local_claude_dir(cli) {
+                Ok(root) => root,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Could not mirror the remote Claude directory");
+                    cli.claude_dir.clone()
+                }
+            },
+            claude_peers,
             codex_root: None,
             max_file_size: cli.max_file_size,
         },
@@ -827,10 +846,211 @@ pub fn refuse_unsupported_flags(
     Ok(())
 }
 
+/// Refuse `--filter` unless the command is producing JSON output.
+pub fn require_json_for_filter(cli: &Cli, filter: Option<&JsonFilter>) -> Result<()> {
+    if filter.is_some() && cli.effective_output() != OutputFormat::Json {
+        return Err(SnatchError::InvalidArgument {
+            name: "--filter".to_string(),
+            reason: "filters JSON output; add --json (or -o json)".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Pretty-printed JSON for `value`, passed through `--filter` when one is
+/// given. Each filter output is its own document, as with jq.
+pub fn json_output<T: serde::Serialize + ?Sized>(
+    value: &T,
+    filter: Option<&JsonFilter>,
+) -> Result<String> {
+    let Some(filter) = filter else {
+        return Ok(serde_json::to_string_pretty(value)?);
+    };
+    let outputs = filter.apply(&serde_json::to_value(value)?)?;
+    let documents = outputs
+        .iter()
+        .map(serde_json::to_string_pretty)
+        .collect::<serde_json::Result<Vec<_>>>()?;
+    Ok(documents.join("\n"))
+}
+
+/// Tags selected by a command's `--tag` and comma-separated `--tags` options.
+pub fn tag_filters<'a>(tag: Option<&'a str>, tags: Option<&'a str>) -> Vec<&'a str> {
+    tag.into_iter()
+        .chain(
+            tags.into_iter()
+                .flat_map(|list| list.split(',').map(str::trim)),
+        )
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Whether a session has a tag containing any of `filters`.
+pub fn session_has_any_tag(
+    store: &crate::tags::TagStore,
+    session_id: &str,
+    filters: &[&str],
+) -> bool {
+    store.get(session_id).is_some_and(|meta| {
+        filters
+            .iter()
+            .any(|filter| meta.tags.iter().any(|tag| tag.contains(filter)))
+    })
+}
+
+/// The offset index of a session, kept in the cache directory when caching
+/// is enabled. `None` when the session cannot be indexed (compressed, or
+/// unreadable).
+pub fn offset_index(cli: &Cli, path: &std::path::Path) -> Option<crate::parser::OffsetIndex> {
+    let config = super::index::load_config(cli);
+    let cache_dir = config
+        .cache
+        .enabled
+        .then(|| crate::config::resolve_cache_dir(&config.cache).ok())
+        .flatten();
+    crate::parser::OffsetIndex::open(path, cache_dir.as_deref()).ok()
+}
+
+/// Read a session file as raw JSON values, one per non-empty line, keeping
+/// every field for commands that rewrite session files.
+pub fn read_raw_jsonl(path: &std::path::Path) -> Result<Vec<serde_json::Value>> {
+    let content = crate::parser::read_session_to_string(path)
+        .map_err(|e| SnatchError::io(format!("Failed to read {}", path.display()), e))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                SnatchError::parse_with_source(i + 1, format!("in {}", path.display()), e)
+            })
+        })
+        .collect()
+}
+
+/// Write raw JSON values as JSONL to `path`, refusing to replace an existing
+/// file unless `overwrite` is set.
+pub fn write_raw_jsonl(
+    path: &std::path::Path,
+    entries: &[serde_json::Value],
+    overwrite: bool,
+) -> Result<()> {
+    if !overwrite && path.exists() {
+        return Err(SnatchError::InvalidArgument {
+            name: "out".to_string(),
+            reason: format!(
+                "{} already exists; pass --overwrite to replace it",
+                path.display()
+            ),
+        });
+    }
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+    crate::util::atomic_write(path, content.as_bytes())
+}
+
+/// Save search results to `path` as JSON or CSV, chosen by its extension.
+///
+/// CSV columns are the union of the result fields in first-seen order;
+/// nested values are written as compact JSON.
+pub fn write_results_file<T: serde::Serialize>(
+    cli: &Cli,
+    path: &std::path::Path,
+    results: &[T],
+) -> Result<()> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let content = match extension.as_deref() {
+        Some("json") => serde_json::to_string_pretty(results)? + "\n",
+        Some("csv") => results_csv(results)?,
+        _ => {
+            return Err(SnatchError::InvalidArgument {
+                name: "out".to_string(),
+                reason: format!(
+                    "cannot infer a format from '{}'; use a .json or .csv file name",
+                    path.display()
+                ),
+            })
+        }
+    };
+    crate::util::atomic_write(path, content.as_bytes())?;
+    if !cli.quiet {
+        eprintln!("Wrote {} results to {}", results.len(), path.display());
+    }
+    Ok(())
+}
+
+fn results_csv<T: serde::Serialize>(results: &[T]) -> Result<String> {
+    let rows = results
+        .iter()
+        .map(|result| match serde_json::to_value(result)? {
+            serde_json::Value::Object(fields) => Ok(fields),
+            other => Ok(std::iter::once(("value".to_string(), other)).collect()),
+        })
+        .collect::<Result<Vec<serde_json::Map<String, serde_json::Value>>>>()?;
+    let mut columns: Vec<&str> = Vec::new();
+    for row in &rows {
+        for key in row.keys() {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key.as_str());
+            }
+        }
+    }
+
+    let mut out = String::new();
+    push_csv_row(&mut out, columns.iter().map(|column| (*column).to_string()));
+    for row in &rows {
+        push_csv_row(
+            &mut out,
+            columns.iter().map(|column| match row.get(*column) {
+                None | Some(serde_json::Value::Null) => String::new(),
+                Some(serde_json::Value::String(text)) => text.clone(),
+                Some(other) => other.to_string(),
+            }),
+        );
+    }
+    Ok(out)
+}
+
+fn push_csv_row(out: &mut String, cells: impl Iterator<Item = String>) {
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if cell.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&cell.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&cell);
+        }
+    }
+    out.push('\n');
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ─── results_csv ────────────────────────────────────────────────
+
+    #[test]
+    fn test_results_csv_unions_columns_and_escapes() {
+        let results = vec![
+            serde_json::json!({"session": "a", "line": "x, \"y\""}),
+            serde_json::json!({"session": "b", "score": 7, "tags": ["t"]}),
+        ];
+        assert_eq!(
+            results_csv(&results).unwrap(),
+            "session,line,score,tags\na,\"x, \"\"y\"\"\",,\nb,,7,\"[\"\"t\"\"]\"\n"
+        );
+    }
+
     // ─── is_interrogative ───────────────────────────────────────────
 
     #[test]
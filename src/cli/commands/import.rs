@@ -0,0 +1,525 @@
+//! Import command implementation.
+//!
+//! Converts conversation logs from other tools into Claude Code's JSONL
+//! shape and stores them under `<claude-dir>/snatch-imported/`, which
+//! discovery reads alongside `projects/`. Imported conversations then list,
+//! search, index, and export like native sessions.
+//!
+//! Supported sources:
+//!
+//! - `chatgpt`: the `conversations.json` file of a ChatGPT data export. Each
+//!   conversation's current branch is imported.
+//! - `generic-jsonl`: one message per line, `{"role": "user", "content":
+//!   "..."}`, with optional `timestamp`, `model`, and `conversation_id` (or
+//!   `session_id`) fields. Lines without a conversation id form a single
+//!   conversation named after the file.
+//!
+//! Session and message ids are derived from the source ids, so importing the
+//! same export again updates the earlier copies instead of duplicating them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::cli::{Cli, ImportArgs, ImportFormat, OutputFormat};
+use crate::discovery::encode_project_path;
+use crate::error::{Result, SnatchError};
+use crate::model::LogEntry;
+use crate::util::{atomic_write, truncate_text};
+
+use super::get_claude_dir;
+
+/// `version` recorded on imported entries.
+const IMPORT_VERSION: &str = "snatch-import";
+
+/// Message author, for the roles that are imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    User,
+    Assistant,
+}
+
+/// One message of a source conversation.
+#[derive(Debug)]
+struct SourceMessage {
+    id: String,
+    role: Role,
+    text: String,
+    timestamp: Option<DateTime<Utc>>,
+    model: Option<String>,
+}
+
+/// One conversation of a source file.
+#[derive(Debug)]
+struct SourceConversation {
+    id: String,
+    title: Option<String>,
+    created: Option<DateTime<Utc>>,
+    messages: Vec<SourceMessage>,
+}
+
+/// What happened to one imported conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ImportStatus {
+    New,
+    Updated,
+    Unchanged,
+}
+
+/// Per-conversation result (`-o json`).
+#[derive(Debug, Serialize)]
+struct ImportedSession {
+    session_id: String,
+    source_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    messages: usize,
+    path: PathBuf,
+    status: ImportStatus,
+}
+
+/// Run the import command.
+pub fn run(cli: &Cli, args: &ImportArgs) -> Result<()> {
+    let text = fs::read_to_string(&args.file)
+        .map_err(|e| SnatchError::io(format!("Failed to read {}", args.file.display()), e))?;
+    let conversations = match args.format {
+        ImportFormat::Chatgpt => parse_chatgpt(&text)?,
+        ImportFormat::GenericJsonl => {
+            let stem = args.file.file_stem().map_or_else(
+                || "imported".to_string(),
+                |s| s.to_string_lossy().into_owned(),
+            );
+            parse_generic_jsonl(&text, &stem)?
+        }
+    };
+    let skipped = conversations
+        .iter()
+        .filter(|c| c.messages.is_empty())
+        .count();
+
+    let source = match args.format {
+        ImportFormat::Chatgpt => "chatgpt",
+        ImportFormat::GenericJsonl => "generic-jsonl",
+    };
+    let project = format!("/imported/{}", args.project.as_deref().unwrap_or(source));
+    let claude_dir = get_claude_dir(cli)?;
+    let dir = claude_dir
+        .imported_dir()
+        .join(encode_project_path(&project));
+
+    let mut results = Vec::new();
+    for conversation in conversations.iter().filter(|c| !c.messages.is_empty()) {
+        let session_id = stable_uuid(&[source, &conversation.id]);
+        let content = to_jsonl(conversation, &session_id, &project)?;
+        let path = dir.join(format!("{session_id}.jsonl"));
+        let status = match fs::read_to_string(&path) {
+            Ok(existing) if existing == content => ImportStatus::Unchanged,
+            Ok(_) => ImportStatus::Updated,
+            Err(_) => ImportStatus::New,
+        };
+        if !args.preview && status != ImportStatus::Unchanged {
+            fs::create_dir_all(&dir)
+                .map_err(|e| SnatchError::io(format!("Failed to create {}", dir.display()), e))?;
+            atomic_write(&path, content.as_bytes())?;
+        }
+        results.push(ImportedSession {
+            session_id,
+            source_id: conversation.id.clone(),
+            title: conversation.title.clone(),
+            messages: conversation.messages.len(),
+            path,
+            status,
+        });
+    }
+
+    if matches!(cli.effective_output(), OutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+    for result in &results {
+        println!(
+            "{:<9}  {}  {:>5} msgs  {}",
+            match result.status {
+                ImportStatus::New => "new",
+                ImportStatus::Updated => "updated",
+                ImportStatus::Unchanged => "unchanged",
+            },
+            &result.session_id[..8],
+            result.messages,
+            truncate_text(result.title.as_deref().unwrap_or(&result.source_id), 60)
+        );
+    }
+    if !cli.quiet {
+        let count = |status| results.iter().filter(|r| r.status == status).count();
+        eprintln!();
+        eprintln!(
+            "{} {} new, {} updated, {} unchanged conversation(s) into project {project}{}",
+            if args.preview {
+                "Would import"
+            } else {
+                "Imported"
+            },
+            count(ImportStatus::New),
+            count(ImportStatus::Updated),
+            count(ImportStatus::Unchanged),
+            if skipped > 0 {
+                format!(" ({skipped} without messages skipped)")
+            } else {
+                String::new()
+            }
+        );
+        if args.preview {
+            eprintln!("Preview only; run without --preview to import.");
+        }
+    }
+    Ok(())
+}
+
+/// Parse a ChatGPT `conversations.json` export.
+fn parse_chatgpt(text: &str) -> Result<Vec<SourceConversation>> {
+    let invalid = |reason: &str| SnatchError::InvalidArgument {
+        name: "file".to_string(),
+        reason: format!("not a ChatGPT conversations.json export: {reason}"),
+    };
+    let root: Value = serde_json::from_str(text).map_err(|e| invalid(&e.to_string()))?;
+    let items = root
+        .as_array()
+        .ok_or_else(|| invalid("expected a JSON array of conversations"))?;
+
+    let mut conversations = Vec::new();
+    for (position, item) in items.iter().enumerate() {
+        let mapping = item
+            .get("mapping")
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid(&format!("conversation #{} has no mapping", position + 1)))?;
+        let id = ["conversation_id", "id"]
+            .iter()
+            .find_map(|key| item.get(*key).and_then(Value::as_str))
+            .map_or_else(|| format!("conversation-{}", position + 1), str::to_string);
+
+        // The current branch: walk up from the current node (or the last
+        // leaf) to the root.
+        let leaf = item
+            .get("current_node")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                mapping
+                    .iter()
+                    .filter(|(_, node)| {
+                        node.get("children")
+                            .and_then(Value::as_array)
+                            .is_none_or(Vec::is_empty)
+                    })
+                    .max_by(|(_, a), (_, b)| {
+                        let time = |node: &Value| {
+                            node.pointer("/message/create_time")
+                                .and_then(Value::as_f64)
+                                .unwrap_or_default()
+                        };
+                        time(a).total_cmp(&time(b))
+                    })
+                    .map(|(id, _)| id.clone())
+            });
+        let mut path = Vec::new();
+        let mut current = leaf;
+        while let Some(node_id) = current {
+            let Some(node) = mapping.get(&node_id) else {
+                break;
+            };
+            if path.len() > mapping.len() {
+                break; // Cyclic parent links.
+            }
+            path.push(node);
+            current = node
+                .get("parent")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+        path.reverse();
+
+        let messages = path
+            .into_iter()
+            .filter_map(|node| chatgpt_message(node.get("message")?))
+            .collect();
+        conversations.push(SourceConversation {
+            id,
+            title: item
+                .get("title")
+                .and_then(Value::as_str)
+                .filter(|t| !t.trim().is_empty())
+                .map(str::to_string),
+            created: item
+                .get("create_time")
+                .and_then(Value::as_f64)
+                .and_then(epoch_seconds),
+            messages,
+        });
+    }
+    Ok(conversations)
+}
+
+/// A visible user or assistant text message of a ChatGPT mapping node.
+fn chatgpt_message(message: &Value) -> Option<SourceMessage> {
+    let role = match message.pointer("/author/role").and_then(Value::as_str)? {
+        "user" => Role::User,
+        "assistant" => Role::Assistant,
+        _ => return None,
+    };
+    if message
+        .pointer("/metadata/is_visually_hidden_from_conversation")
+        .and_then(Value::as_bool)
+        == Some(true)
+    {
+        return None;
+    }
+    let text = message
+        .pointer("/content/parts")
+        .and_then(Value::as_array)?
+        .iter()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(SourceMessage {
+        id: message.get("id").and_then(Value::as_str)?.to_string(),
+        role,
+        text,
+        timestamp: message
+            .get("create_time")
+            .and_then(Value::as_f64)
+            .and_then(epoch_seconds),
+        model: message
+            .pointer("/metadata/model_slug")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// Parse generic JSONL messages, grouped by conversation id in order of
+/// first appearance.
+fn parse_generic_jsonl(text: &str, default_id: &str) -> Result<Vec<SourceConversation>> {
+    let mut conversations: Vec<SourceConversation> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = |reason: String| SnatchError::InvalidArgument {
+            name: "file".to_string(),
+            reason: format!("line {}: {reason}", number + 1),
+        };
+        let record: Value = serde_json::from_str(line).map_err(|e| invalid(e.to_string()))?;
+        let role = match record.get("role").and_then(Value::as_str) {
+            Some("user" | "human") => Role::User,
+            Some("assistant" | "ai" | "model" | "bot") => Role::Assistant,
+            Some(_) => continue,
+            None => return Err(invalid("missing \"role\"".to_string())),
+        };
+        let text = match record.get("content") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Array(parts)) => parts
+                .iter()
+                .filter_map(|part| {
+                    part.as_str()
+                        .or_else(|| part.get("text").and_then(Value::as_str))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => return Err(invalid("missing \"content\"".to_string())),
+        };
+        if text.trim().is_empty() {
+            continue;
+        }
+        let timestamp = match record.get("timestamp") {
+            Some(Value::String(s)) => DateTime::parse_from_rfc3339(s)
+                .map(|t| t.with_timezone(&Utc))
+                .ok(),
+            Some(Value::Number(n)) => n.as_f64().and_then(epoch_seconds),
+            _ => None,
+        };
+        let conversation_id = ["conversation_id", "session_id", "conversation"]
+            .iter()
+            .find_map(|key| record.get(*key).and_then(Value::as_str))
+            .unwrap_or(default_id)
+            .to_string();
+
+        let index = *positions.entry(conversation_id.clone()).or_insert_with(|| {
+            conversations.push(SourceConversation {
+                id: conversation_id.clone(),
+                title: None,
+                created: None,
+                messages: Vec::new(),
+            });
+            conversations.len() - 1
+        });
+        let conversation = &mut conversations[index];
+        conversation.messages.push(SourceMessage {
+            id: record
+                .get("id")
+                .and_then(Value::as_str)
+                .map_or_else(|| format!("line-{}", number + 1), str::to_string),
+            role,
+            text,
+            timestamp,
+            model: record
+                .get("model")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        });
+    }
+    Ok(conversations)
+}
+
+/// Render a conversation as Claude Code JSONL: one linear thread of user
+/// and assistant entries, plus a summary line carrying the title.
+fn to_jsonl(conversation: &SourceConversation, session_id: &str, cwd: &str) -> Result<String> {
+    let mut out = String::new();
+    let mut parent: Option<String> = None;
+    // Messages without a timestamp take the previous one (or the
+    // conversation's creation time), keeping the thread in order.
+    let mut last_time = conversation.created.unwrap_or(DateTime::UNIX_EPOCH);
+    for message in &conversation.messages {
+        let uuid = stable_uuid(&[session_id, &message.id]);
+        let timestamp = message.timestamp.unwrap_or(last_time).max(last_time);
+        last_time = timestamp;
+        let mut entry = json!({
+            "type": match message.role {
+                Role::User => "user",
+                Role::Assistant => "assistant",
+            },
+            "uuid": uuid,
+            "parentUuid": parent,
+            "timestamp": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "sessionId": session_id,
+            "version": IMPORT_VERSION,
+            "cwd": cwd,
+            "userType": "external",
+            "isSidechain": false,
+        });
+        entry["message"] = match message.role {
+            Role::User => json!({ "role": "user", "content": message.text }),
+            Role::Assistant => json!({
+                "id": format!("msg_{}", uuid.replace('-', "")),
+                "type": "message",
+                "role": "assistant",
+                "model": message.model.as_deref().unwrap_or("unknown"),
+                "content": [{ "type": "text", "text": message.text }],
+                "stop_reason": "end_turn",
+                "stop_sequence": null,
+            }),
+        };
+        // Every line must read back as a LogEntry.
+        serde_json::from_value::<LogEntry>(entry.clone())?;
+        out.push_str(&entry.to_string());
+        out.push('\n');
+        parent = Some(uuid);
+    }
+    if let Some(title) = &conversation.title {
+        let summary = json!({ "type": "summary", "summary": title, "leafUuid": parent });
+        serde_json::from_value::<LogEntry>(summary.clone())?;
+        out.push_str(&summary.to_string());
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Seconds since the epoch (fractional) as a UTC time.
+fn epoch_seconds(seconds: f64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_millis((seconds * 1000.0) as i64)
+}
+
+/// Namespace of the name-based (v5) ids of imported sessions and messages.
+const IMPORT_NAMESPACE: Uuid = Uuid::from_u128(0x6f1c_2f4e_8a7b_4c3d_9e05_b1a2_c3d4_e5f6);
+
+/// A UUID derived from `parts`, the same on every run and toolchain, so a
+/// re-import updates the sessions it created before.
+fn stable_uuid(parts: &[&str]) -> String {
+    // Join with a separator no id contains, so ("ab", "c") and ("a", "bc")
+    // stay distinct.
+    Uuid::new_v5(&IMPORT_NAMESPACE, parts.join("\u{0}").as_bytes()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reconstruction::Conversation;
+
+    #[test]
+    fn test_parse_chatgpt_follows_current_branch() {
+        let export = json!([{
+            "title": "Rust lifetimes",
+            "create_time": 1_700_000_000.0,
+            "conversation_id": "conv-1",
+            "current_node": "a2",
+            "mapping": {
+                "root": { "id": "root", "message": null, "parent": null, "children": ["sys"] },
+                "sys": { "id": "sys", "parent": "root", "children": ["u1"], "message": {
+                    "id": "sys", "author": { "role": "system" },
+                    "content": { "content_type": "text", "parts": [""] } } },
+                "u1": { "id": "u1", "parent": "sys", "children": ["a1", "a2"], "message": {
+                    "id": "u1", "author": { "role": "user" }, "create_time": 1_700_000_001.0,
+                    "content": { "content_type": "text", "parts": ["What is 'a?"] } } },
+                "a1": { "id": "a1", "parent": "u1", "children": [], "message": {
+                    "id": "a1", "author": { "role": "assistant" },
+                    "content": { "content_type": "text", "parts": ["Old answer"] } } },
+                "a2": { "id": "a2", "parent": "u1", "children": [], "message": {
+                    "id": "a2", "author": { "role": "assistant" }, "create_time": 1_700_000_005.0,
+                    "metadata": { "model_slug": "gpt-4o" },
+                    "content": { "content_type": "text", "parts": ["A lifetime."] } } }
+            }
+        }]);
+        let conversations = parse_chatgpt(&export.to_string()).unwrap();
+        assert_eq!(conversations.len(), 1);
+        let conversation = &conversations[0];
+        assert_eq!(conversation.title.as_deref(), Some("Rust lifetimes"));
+        let texts: Vec<_> = conversation
+            .messages
+            .iter()
+            .map(|m| m.text.as_str())
+            .collect();
+        assert_eq!(texts, ["What is 'a?", "A lifetime."]);
+        assert_eq!(conversation.messages[1].model.as_deref(), Some("gpt-4o"));
+
+        let session_id = stable_uuid(&["chatgpt", &conversation.id]);
+        assert_eq!(session_id, stable_uuid(&["chatgpt", "conv-1"]));
+        // Fixed forever: a different value would duplicate re-imported sessions.
+        assert_eq!(session_id, "709028ff-c619-518f-a607-6d63bfb8c1eb");
+        let jsonl = to_jsonl(conversation, &session_id, "/imported/chatgpt").unwrap();
+        let entries: Vec<LogEntry> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 3);
+        let thread = Conversation::from_entries(entries).unwrap();
+        assert_eq!(thread.main_thread_entries().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_generic_jsonl_groups_conversations() {
+        let text = concat!(
+            "{\"role\":\"user\",\"content\":\"hi\",\"conversation_id\":\"x\"}\n",
+            "{\"role\":\"system\",\"content\":\"ignored\"}\n",
+            "{\"role\":\"assistant\",\"content\":[{\"text\":\"hello\"}],\"conversation_id\":\"x\",\"timestamp\":\"2026-01-01T10:00:00Z\"}\n",
+            "\n",
+            "{\"role\":\"human\",\"content\":\"loose\"}\n",
+        );
+        let conversations = parse_generic_jsonl(text, "notes").unwrap();
+        let ids: Vec<_> = conversations.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["x", "notes"]);
+        assert_eq!(conversations[0].messages.len(), 2);
+        assert_eq!(conversations[0].messages[1].role, Role::Assistant);
+        assert!(conversations[0].messages[1].timestamp.is_some());
+
+        assert!(parse_generic_jsonl("{\"content\":\"x\"}", "n").is_err());
+        assert!(parse_generic_jsonl("not json", "n").is_err());
+    }
+}
@@ -1,25 +1,33 @@
 //! Provider-neutral persistent search-index commands.
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::analysis::search::{ExactSearchMatcher, SearchScope};
-use crate::cli::{Cli, IndexArgs, IndexSubcommand, OutputFormat};
-use crate::config::Config;
+use crate::cli::{Cli, IndexArgs, IndexSearchSort, IndexSubcommand, OutputFormat, TableArgs};
+use crate::config::{ClaudeSource, Config};
 use crate::discovery::{format_count, format_number, format_size};
 use crate::error::{Result, SnatchError};
 use crate::index::build::{
     rebuild_provider_index, update_provider_index, ProviderIndexBuildOptions,
 };
+use crate::index::dsl::QueryExpr;
 use crate::index::provider::{
     ProviderIndexStats, ProviderSearchIndex, PROVIDER_INDEX_SCHEMA_VERSION,
 };
 use crate::index::query::{
-    IndexedProviderSelection, IndexedSearchFilters, IndexedSearchOrder, IndexedSearchRequest,
+    IndexedProviderSelection, IndexedQueryExpr, IndexedSearchFilters, IndexedSearchOrder,
+    IndexedSearchRequest,
 };
-use crate::provider::registry::ProviderSelection;
+use crate::index::LOCAL_SOURCE;
+use crate::provider::registry::{ProviderRegistry, ProviderSelection};
 use crate::provider::ProviderId;
 
+use super::table::{Table, INDEX_SEARCH_COLUMNS};
+
 /// Run the index command.
 pub fn run(cli: &Cli, args: &IndexArgs) -> Result<()> {
     match &args.command {
@@ -28,15 +36,38 @@ pub fn run(cli: &Cli, args: &IndexArgs) -> Result<()> {
         IndexSubcommand::Status => run_status(cli),
         IndexSubcommand::Clear => run_clear(cli),
         IndexSubcommand::Search(search_args) => run_search(cli, search_args),
+        IndexSubcommand::Watch(watch_args) => run_watch(cli, watch_args),
     }
 }
 
-pub(super) fn index_path(cli: &Cli) -> PathBuf {
-    let config = match &cli.config {
+pub(super) fn load_config(cli: &Cli) -> Config {
+    match &cli.config {
         Some(path) => Config::load_from(path).unwrap_or_default(),
         None => Config::load().unwrap_or_default(),
-    };
-    config
+    }
+}
+
+/// The configured `[[index.sources]]`, checked for unique names, and a
+/// provider registry that reads them alongside the local Claude directory.
+pub(super) fn source_registry(cli: &Cli) -> Result<(ProviderRegistry, Vec<ClaudeSource>)> {
+    let sources = load_config(cli).index.sources;
+    let mut names = BTreeSet::from([LOCAL_SOURCE]);
+    for source in &sources {
+        if !names.insert(source.name.as_str()) {
+            return Err(SnatchError::ConfigError {
+                message: format!("duplicate index source name '{}'", source.name),
+            });
+        }
+    }
+    let peers = sources.iter().map(|source| source.path.clone()).collect();
+    Ok((
+        super::helpers::provider_registry_with_peers(cli, peers),
+        sources,
+    ))
+}
+
+pub(super) fn index_path(cli: &Cli) -> PathBuf {
+    load_config(cli)
         .index
         .directory
         .unwrap_or_else(ProviderSearchIndex::default_index_dir)
@@ -78,9 +109,10 @@ pub(super) fn resolve_indexed_session(
 
 fn run_build(cli: &Cli, args: &crate::cli::IndexBuildArgs) -> Result<()> {
     let selection = provider_selection(&args.provider)?;
-    let registry = super::helpers::provider_registry(cli);
+    let (registry, sources) = source_registry(cli)?;
     let index = ProviderSearchIndex::open(index_path(cli))?;
-    let options = ProviderIndexBuildOptions::new(&selection, args.project.as_deref());
+    let options =
+        ProviderIndexBuildOptions::new(&selection, args.project.as_deref()).with_sources(&sources);
     let report = update_provider_index(&index, &registry, &options)?;
 
     match cli.effective_output() {
@@ -111,6 +143,83 @@ fn run_build(cli: &Cli, args: &crate::cli::IndexBuildArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_watch(cli: &Cli, args: &crate::cli::IndexWatchArgs) -> Result<()> {
+    let selection = provider_selection(&args.provider)?;
+    let (registry, sources) = source_registry(cli)?;
+    let index = ProviderSearchIndex::open(index_path(cli))?;
+    let interval = Duration::from_millis(args.interval.max(100));
+
+    if !cli.quiet && cli.effective_output() != OutputFormat::Json {
+        eprintln!("Watching session logs for changes... (Ctrl+C to stop)");
+    }
+    let mut last_seen = None;
+    loop {
+        let fingerprint = session_log_fingerprint(&registry, &selection, &sources);
+        if last_seen != Some(fingerprint) {
+            let options = ProviderIndexBuildOptions::new(&selection, args.project.as_deref())
+                .with_sources(&sources);
+            let report = update_provider_index(&index, &registry, &options)?;
+            // The first pass always reports, so the index state is visible.
+            let changed = report.sessions_replaced > 0 || report.sessions_removed > 0;
+            if changed || last_seen.is_none() {
+                match cli.effective_output() {
+                    OutputFormat::Json => println!("{}", serde_json::to_string(&report)?),
+                    _ => println!(
+                        "[{}] Indexed {} entries from {} changed sessions ({} removed)",
+                        chrono::Local::now().format("%H:%M:%S"),
+                        format_count(report.entries_replaced),
+                        format_count(report.sessions_replaced),
+                        format_count(report.sessions_removed)
+                    ),
+                }
+            }
+            last_seen = Some(fingerprint);
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Path, size, and modification time of every session log under the
+/// selected providers' roots and, with Claude Code, the configured sources,
+/// so unchanged polls skip the index update.
+fn session_log_fingerprint(
+    registry: &ProviderRegistry,
+    selection: &ProviderSelection,
+    sources: &[ClaudeSource],
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for entry in registry.entries() {
+        if let ProviderSelection::Explicit(ids) = selection {
+            if !ids.contains(&entry.id) {
+                continue;
+            }
+        }
+        let Some(root) = &entry.root else {
+            continue;
+        };
+        let source_roots = sources
+            .iter()
+            .filter(|_| entry.id == ProviderId::claude_code())
+            .map(|source| &source.path);
+        for file in std::iter::once(root)
+            .chain(source_roots)
+            .flat_map(|dir| walkdir::WalkDir::new(dir).sort_by_file_name())
+            .filter_map(std::result::Result::ok)
+        {
+            if !file.file_type().is_file() || !file.file_name().to_string_lossy().contains(".jsonl")
+            {
+                continue;
+            }
+            if let Ok(metadata) = file.metadata() {
+                file.path().hash(&mut hasher);
+                metadata.len().hash(&mut hasher);
+                metadata.modified().ok().hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
 fn run_rebuild(cli: &Cli, args: &crate::cli::IndexRebuildArgs) -> Result<()> {
     if args.project.is_some() {
         return Err(SnatchError::InvalidArgument {
@@ -119,8 +228,8 @@ fn run_rebuild(cli: &Cli, args: &crate::cli::IndexRebuildArgs) -> Result<()> {
         });
     }
     let selection = provider_selection(&args.provider)?;
-    let registry = super::helpers::provider_registry(cli);
-    let options = ProviderIndexBuildOptions::new(&selection, None);
+    let (registry, sources) = source_registry(cli)?;
+    let options = ProviderIndexBuildOptions::new(&selection, None).with_sources(&sources);
     let report = rebuild_provider_index(index_path(cli), &registry, &options)?;
 
     match cli.effective_output() {
@@ -184,6 +293,14 @@ fn run_status(cli: &Cli) -> Result<()> {
                 if !build.removal_coverage_complete || !build.skipped.is_empty() {
                     println!("Coverage: incomplete");
                 }
+                for skip in &build.skipped {
+                    let target = skip
+                        .session_key
+                        .as_deref()
+                        .or(skip.provider.as_deref())
+                        .unwrap_or("unknown");
+                    println!("  Skipped {target}: {}", skip.reason);
+                }
             } else {
                 println!();
                 println!("Index is empty. Run 'snatch index build' to create it.");
@@ -210,21 +327,57 @@ fn run_search(cli: &Cli, args: &crate::cli::IndexSearchArgs) -> Result<()> {
             reason: "search query cannot be empty or whitespace-only".to_string(),
         });
     }
+    let limit = args.limit.unwrap_or(100);
+    let offset = match args.page {
+        Some(0) => {
+            return Err(SnatchError::InvalidArgument {
+                name: "page".to_string(),
+                reason: "pages are numbered from 1".to_string(),
+            });
+        }
+        Some(page) => (page - 1).saturating_mul(limit),
+        None => args.offset,
+    };
     let selection = provider_selection(&args.provider)?;
     let index = ProviderSearchIndex::open_read_only(index_path(cli))?;
     let session_keys = match &args.session {
         Some(reference) => vec![resolve_indexed_session(&index, &selection, reference)?],
         None => Vec::new(),
     };
+    let mut scope = match (args.thinking, args.tools) {
+        (true, true) => SearchScope::All,
+        (true, false) => SearchScope::Thinking,
+        (false, true) => SearchScope::Tools,
+        (false, false) => SearchScope::Default,
+    };
+    let query_error = |error: regex::Error| SnatchError::InvalidArgument {
+        name: "query".to_string(),
+        reason: error.to_string(),
+    };
+    let mut expression = None;
     let matcher = if args.fuzzy {
         ExactSearchMatcher::fuzzy(&args.query, args.ignore_case, args.fuzzy_threshold)
+    } else if args.regex {
+        ExactSearchMatcher::regex(&args.query, args.ignore_case).map_err(query_error)?
     } else {
-        ExactSearchMatcher::regex(&args.query, args.ignore_case).map_err(|error| {
-            SnatchError::InvalidArgument {
-                name: "query".to_string(),
-                reason: error.to_string(),
+        match crate::index::dsl::parse(&args.query)? {
+            // A lone word or phrase keeps its regex meaning.
+            QueryExpr::Match { field: None, text } => {
+                ExactSearchMatcher::regex(&text, args.ignore_case).map_err(query_error)?
+            }
+            parsed => {
+                let compiled = IndexedQueryExpr::compile(&parsed, scope, args.ignore_case)?;
+                let Some((matcher, terms_scope)) = compiled.highlighter(args.ignore_case)? else {
+                    return Err(SnatchError::InvalidArgument {
+                        name: "query".to_string(),
+                        reason: "the query needs at least one search term outside NOT".to_string(),
+                    });
+                };
+                scope = terms_scope;
+                expression = Some(compiled);
+                matcher
             }
-        })?
+        }
     };
     let exclude = args
         .exclude
@@ -239,34 +392,43 @@ fn run_search(cli: &Cli, args: &crate::cli::IndexSearchArgs) -> Result<()> {
         selection: indexed_selection(&selection),
         matcher,
         exclude,
-        scope: if args.thinking {
-            SearchScope::Thinking
-        } else {
-            SearchScope::Default
-        },
+        scope,
         filters: IndexedSearchFilters {
             session_keys,
             message_types: args.message_type.iter().cloned().collect(),
             model_contains: args.model.clone(),
             tool_name_contains: args.tool_name.clone(),
+            sources: args.source.clone(),
             include_spawned: true,
+            expression,
             ..Default::default()
         },
         context_lines: args.context,
-        order: if args.sort {
-            IndexedSearchOrder::Relevance
-        } else {
-            IndexedSearchOrder::Source
+        order: match args.sort {
+            None | Some(IndexSearchSort::Source) => IndexedSearchOrder::Source,
+            Some(IndexSearchSort::Relevance) => IndexedSearchOrder::Relevance,
+            Some(IndexSearchSort::Time) => IndexedSearchOrder::Time,
         },
-        offset: args.offset,
-        limit: args.limit.unwrap_or(100),
+        boosts: load_config(cli).index.boosts,
+        offset,
+        limit,
     };
     let response = index.query(&request)?;
-    output_search_response(cli, &response)
+    if let Some(path) = &args.output_file {
+        return super::helpers::write_results_file(cli, path, &response.matches);
+    }
+    output_search_response(cli, &args.table, &response)?;
+    if let Some(next) = response.next_offset() {
+        if cli.effective_output() == OutputFormat::Text && !cli.quiet {
+            eprintln!("More matches available; continue with --offset {next}");
+        }
+    }
+    Ok(())
 }
 
 pub(super) fn output_search_response(
     cli: &Cli,
+    table_args: &TableArgs,
     response: &crate::index::query::IndexedSearchResponse,
 ) -> Result<()> {
     match cli.effective_output() {
@@ -279,24 +441,29 @@ pub(super) fn output_search_response(
                 "returned": response.returned,
                 "offset": response.offset,
                 "limit": response.limit,
+                "next_offset": response.next_offset(),
                 "matches": &response.matches,
                 "coverage": &response.coverage,
             }))?
         ),
-        OutputFormat::Tsv => {
-            println!("provider\tsession\tproject\tentry\ttype\tlocation\tscore\tline");
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, INDEX_SEARCH_COLUMNS, table_args)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
             for hit in &response.matches {
-                println!(
-                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
-                    hit.provider,
-                    hit.session_key,
-                    hit.project_path.replace('\t', " "),
-                    hit.entry_id,
-                    hit.message_type,
-                    hit.location,
-                    hit.score,
-                    hit.line.replace(['\t', '\n'], " ")
-                );
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        hit.provider.clone(),
+                        hit.session_key.clone(),
+                        hit.project_path.clone(),
+                        hit.entry_id.clone(),
+                        hit.message_type.clone(),
+                        hit.location.clone(),
+                        hit.score.to_string(),
+                        hit.line.clone(),
+                    ],
+                )?;
             }
         }
         OutputFormat::Compact => {
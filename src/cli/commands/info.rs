@@ -31,7 +31,7 @@ pub fn run(cli: &Cli, args: &InfoArgs) -> Result<()> {
         });
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     if let Some(target) = &args.target {
         // Try to find as session first
@@ -184,7 +184,7 @@ fn show_session_info(
     let session_meta = tag_store.get(&summary.session_id);
 
     // Look up chain info
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let chain_info = claude_dir.projects().ok().and_then(|projects| {
         let project = projects.into_iter().find(|p| {
             p.best_path() == summary.project_path || p.decoded_path() == summary.project_path
@@ -320,7 +320,7 @@ fn show_session_info(
                 })?
             );
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("field\tvalue");
             println!("session_id\t{}", summary.session_id);
             println!("project\t{}", summary.project_path);
@@ -840,7 +840,7 @@ fn show_project_info(
                 })?
             );
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("field\tvalue");
             println!("path\t{}", project.decoded_path());
             println!("sessions\t{}", sessions.len());
@@ -911,6 +911,11 @@ fn show_directory_info(
                 "{}",
                 serde_json::to_string_pretty(&DirectoryInfoOutput {
                     root_path: claude_dir.root().to_string_lossy().to_string(),
+                    peer_paths: claude_dir
+                        .peers()
+                        .iter()
+                        .map(|peer| peer.to_string_lossy().to_string())
+                        .collect(),
                     project_count: stats.project_count,
                     session_count: stats.session_count,
                     subagent_count: stats.subagent_count,
@@ -921,7 +926,7 @@ fn show_directory_info(
                 })?
             );
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("field\tvalue");
             println!("root\t{}", claude_dir.root().display());
             println!("projects\t{}", stats.project_count);
@@ -941,6 +946,9 @@ fn show_directory_info(
             println!("=====================");
             println!();
             println!("Root:           {}", claude_dir.root().display());
+            for peer in claude_dir.peers() {
+                println!("Merged:         {}", peer.display());
+            }
             println!();
             println!("Projects:       {}", stats.project_count);
             println!("Sessions:       {}", stats.session_count);
@@ -1134,6 +1142,9 @@ struct ProjectInfoOutput {
 #[derive(Debug, serde::Serialize)]
 struct DirectoryInfoOutput {
     root_path: String,
+    /// Claude directories across a WSL boundary whose projects are merged in.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    peer_paths: Vec<String>,
     project_count: usize,
     session_count: usize,
     subagent_count: usize,
@@ -144,7 +144,7 @@ pub fn run(cli: &Cli, args: &LessonsArgs) -> Result<()> {
                 semantic_annotations,
             )
         } else {
-            let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+            let claude_dir = get_claude_dir(cli)?;
             let session = claude_dir.find_session(session_id)?.ok_or_else(|| {
                 SnatchError::SessionNotFound {
                     session_id: session_id.clone(),
@@ -527,7 +527,7 @@ fn continue_or_record_failure(
 
 /// Run classic Claude-only cross-session lessons extraction.
 fn run_cross_session(cli: &Cli, args: &LessonsArgs) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     let sessions = if let Some(ref project_filter) = args.project {
         let projects = claude_dir.projects()?;
@@ -12,15 +12,17 @@ use crate::parser::JsonlParser;
 use crate::tags::{SessionMeta, TagStore};
 use crate::util::pager::PagerWriter;
 
+use super::table::{Table, LIST_CHAIN_COLUMNS, LIST_PROJECT_COLUMNS, LIST_SESSION_COLUMNS};
 use super::{get_claude_dir, parse_size};
 
 /// Run the list command.
 pub fn run(cli: &Cli, args: &ListArgs) -> Result<()> {
+    super::helpers::require_json_for_filter(cli, args.filter.as_ref())?;
     if !args.provider.is_empty() {
         return list_provider_sessions(cli, args);
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Create a writer that optionally uses a pager
     let mut writer = PagerWriter::new(args.pager);
@@ -99,19 +101,25 @@ fn list_projects<W: Write>(
     match cli.effective_output() {
         OutputFormat::Json => {
             let output: Vec<_> = projects.iter().map(|p| ProjectInfo::from(p)).collect();
-            writeln!(writer, "{}", serde_json::to_string_pretty(&output)?)?;
+            writeln!(
+                writer,
+                "{}",
+                super::helpers::json_output(&output, args.filter.as_ref())?
+            )?;
         }
-        OutputFormat::Tsv => {
-            writeln!(writer, "path\tencoded\tsession_count")?;
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, LIST_PROJECT_COLUMNS, &args.table)?;
+            table.write_header(writer)?;
             for project in &projects {
                 let session_count = project.sessions().map(|s| s.len()).unwrap_or(0);
                 // Use best_path() which prefers the authoritative cwd from JSONL
-                writeln!(
+                table.write_row(
                     writer,
-                    "{}\t{}\t{}",
-                    project.best_path(),
-                    project.encoded_name(),
-                    session_count
+                    &[
+                        project.best_path(),
+                        project.encoded_name().to_string(),
+                        session_count.to_string(),
+                    ],
                 )?;
             }
         }
@@ -273,26 +281,10 @@ fn list_sessions_flat<W: Write>(
     let tag_store = TagStore::load()?;
 
     // Apply tag filters
-    let tag_filters: Vec<&str> = {
-        let mut tags = Vec::new();
-        if let Some(ref tag) = args.tag {
-            tags.push(tag.as_str());
-        }
-        if let Some(ref tag_list) = args.tags {
-            tags.extend(tag_list.split(',').map(str::trim));
-        }
-        tags
-    };
-
+    let tag_filters = super::helpers::tag_filters(args.tag.as_deref(), args.tags.as_deref());
     if !tag_filters.is_empty() {
         sessions.retain(|s| {
-            if let Some(meta) = tag_store.get(s.session_id()) {
-                tag_filters
-                    .iter()
-                    .any(|t| meta.tags.iter().any(|mt| mt.contains(t)))
-            } else {
-                false
-            }
+            super::helpers::session_has_any_tag(&tag_store, s.session_id(), &tag_filters)
         });
     }
 
@@ -388,20 +380,20 @@ fn list_sessions_flat<W: Write>(
                     SessionInfo::from_session(s, &tag_store, args.context, args.context_length)
                 })
                 .collect();
-            writeln!(writer, "{}", serde_json::to_string_pretty(&output)?)?;
+            writeln!(
+                writer,
+                "{}",
+                super::helpers::json_output(&output, args.filter.as_ref())?
+            )?;
         }
-        OutputFormat::Tsv => {
-            if args.context {
-                writeln!(
-                    writer,
-                    "session_id\tproject\tsize\tmodified\tsubagent\tname\tcontext"
-                )?;
-            } else {
-                writeln!(
-                    writer,
-                    "session_id\tproject\tsize\tmodified\tsubagent\tname"
-                )?;
-            }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::new(
+                format,
+                LIST_SESSION_COLUMNS,
+                &list_default_columns(LIST_SESSION_COLUMNS, args.context),
+                &args.table,
+            )?;
+            table.write_header(writer)?;
             for session in &sessions {
                 let id = if args.full_ids {
                     session.session_id().to_string()
@@ -410,34 +402,26 @@ fn list_sessions_flat<W: Write>(
                 };
                 let meta = tag_store.get(session.session_id());
                 let name = meta.and_then(|m| m.name.as_deref()).unwrap_or("");
-                if args.context {
-                    let context =
-                        get_session_context(session, args.context_length).unwrap_or_default();
-                    // Escape tabs and newlines in context for TSV
-                    let context_escaped = context.replace(['\t', '\n'], " ");
-                    writeln!(
-                        writer,
-                        "{}\t{}\t{}\t{}\t{}\t{}\t{}",
-                        id,
-                        session.display_project_path(),
-                        session.file_size(),
-                        session.modified_datetime().format("%Y-%m-%d %H:%M:%S UTC"),
-                        session.is_subagent(),
-                        name,
-                        context_escaped
-                    )?;
+                let context = if table.includes("context") {
+                    get_session_context(session, args.context_length).unwrap_or_default()
                 } else {
-                    writeln!(
-                        writer,
-                        "{}\t{}\t{}\t{}\t{}\t{}",
+                    String::new()
+                };
+                table.write_row(
+                    writer,
+                    &[
                         id,
                         session.display_project_path(),
-                        session.file_size(),
-                        session.modified_datetime().format("%Y-%m-%d %H:%M:%S UTC"),
-                        session.is_subagent(),
-                        name
-                    )?;
-                }
+                        session.file_size().to_string(),
+                        session
+                            .modified_datetime()
+                            .format("%Y-%m-%d %H:%M:%S UTC")
+                            .to_string(),
+                        session.is_subagent().to_string(),
+                        name.to_string(),
+                        context,
+                    ],
+                )?;
             }
         }
         OutputFormat::Compact => {
@@ -696,27 +680,11 @@ fn list_sessions_collapsed<W: Write>(
     }
 
     // Tag filters (any member matches).
-    let tag_filters: Vec<&str> = {
-        let mut tags = Vec::new();
-        if let Some(ref tag) = args.tag {
-            tags.push(tag.as_str());
-        }
-        if let Some(ref tag_list) = args.tags {
-            tags.extend(tag_list.split(',').map(str::trim));
-        }
-        tags
-    };
+    let tag_filters = super::helpers::tag_filters(args.tag.as_deref(), args.tags.as_deref());
     if !tag_filters.is_empty() {
         rows.retain(|r| {
             r.any_member(|s| {
-                tag_store
-                    .get(s.session_id())
-                    .map(|meta| {
-                        tag_filters
-                            .iter()
-                            .any(|t| meta.tags.iter().any(|mt| mt.contains(t)))
-                    })
-                    .unwrap_or(false)
+                super::helpers::session_has_any_tag(&tag_store, s.session_id(), &tag_filters)
             })
         });
     }
@@ -869,20 +837,20 @@ fn list_sessions_collapsed<W: Write>(
                     )
                 })
                 .collect();
-            writeln!(writer, "{}", serde_json::to_string_pretty(&output)?)?;
+            writeln!(
+                writer,
+                "{}",
+                super::helpers::json_output(&output, args.filter.as_ref())?
+            )?;
         }
-        OutputFormat::Tsv => {
-            if args.context {
-                writeln!(
-                    writer,
-                    "session_id\tproject\tsize\tmodified\tsubagent\tname\tmember_count\tlatest_session_id\tcontext"
-                )?;
-            } else {
-                writeln!(
-                    writer,
-                    "session_id\tproject\tsize\tmodified\tsubagent\tname\tmember_count\tlatest_session_id"
-                )?;
-            }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::new(
+                format,
+                LIST_CHAIN_COLUMNS,
+                &list_default_columns(LIST_CHAIN_COLUMNS, args.context),
+                &args.table,
+            )?;
+            table.write_header(writer)?;
             for row in &rows {
                 let root = row.root();
                 let id = if args.full_ids {
@@ -897,37 +865,25 @@ fn list_sessions_collapsed<W: Write>(
                 };
                 let meta = tag_store.get(&row.root_id);
                 let name = meta.and_then(|m| m.name.as_deref()).unwrap_or("");
-                if args.context {
-                    let context =
-                        get_session_context(root, args.context_length).unwrap_or_default();
-                    let context_escaped = context.replace(['\t', '\n'], " ");
-                    writeln!(
-                        writer,
-                        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
-                        id,
-                        root.display_project_path(),
-                        row.total_size(),
-                        format_systemtime(row.latest_modified()),
-                        root.is_subagent(),
-                        name,
-                        row.member_count(),
-                        latest,
-                        context_escaped
-                    )?;
+                let context = if table.includes("context") {
+                    get_session_context(root, args.context_length).unwrap_or_default()
                 } else {
-                    writeln!(
-                        writer,
-                        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    String::new()
+                };
+                table.write_row(
+                    writer,
+                    &[
                         id,
                         root.display_project_path(),
-                        row.total_size(),
+                        row.total_size().to_string(),
                         format_systemtime(row.latest_modified()),
-                        root.is_subagent(),
-                        name,
-                        row.member_count(),
-                        latest
-                    )?;
-                }
+                        root.is_subagent().to_string(),
+                        name.to_string(),
+                        row.member_count().to_string(),
+                        latest,
+                        context,
+                    ],
+                )?;
             }
         }
         OutputFormat::Compact => {
@@ -1090,6 +1046,16 @@ fn list_sessions_collapsed<W: Write>(
     Ok(())
 }
 
+/// Default TSV/CSV columns for a session listing: everything except the
+/// context preview, which is only extracted when `--context` asks for it.
+fn list_default_columns(columns: &'static [&'static str], context: bool) -> Vec<&'static str> {
+    columns
+        .iter()
+        .copied()
+        .filter(|column| context || *column != "context")
+        .collect()
+}
+
 /// Format a `SystemTime` as the listing's UTC timestamp string.
 fn format_systemtime(t: std::time::SystemTime) -> String {
     chrono::DateTime::<chrono::Utc>::from(t)
@@ -1366,7 +1332,8 @@ fn list_provider_sessions(cli: &Cli, args: &ListArgs) -> Result<()> {
     // COMPLETE argument classification for this route: the struct is
     // destructured WITHOUT `..`, so adding a ListArgs field without
     // classifying it here is a compile error (round-19 exhaustiveness).
-    // Universal: target, --provider, --project, --sort, -n/--limit, --sizes.
+    // Universal: target, --provider, --project, --sort, -n/--limit, --sizes,
+    // --filter.
     // --context-length is refused alongside --context (inert without it, but
     // a set value must not be silently accepted).
     let crate::cli::ListArgs {
@@ -1395,6 +1362,8 @@ fn list_provider_sessions(cli: &Cli, args: &ListArgs) -> Result<()> {
         context_length,
         hide_empty,
         no_chain,
+        table,
+        filter,
     } = args;
     super::helpers::refuse_unsupported_flags(
         "list --provider",
@@ -1413,6 +1382,8 @@ fn list_provider_sessions(cli: &Cli, args: &ListArgs) -> Result<()> {
             ("--context-length", *context_length != 100),
             ("--hide-empty", *hide_empty),
             ("--no-chain", *no_chain),
+            ("--columns", table.columns.is_some()),
+            ("--no-header", table.no_header),
         ],
     )?;
 
@@ -1609,7 +1580,7 @@ fn list_provider_sessions(cli: &Cli, args: &ListArgs) -> Result<()> {
                 "project_warnings": warning_json,
             }),
         };
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        println!("{}", super::helpers::json_output(&out, filter.as_ref())?);
         return Ok(());
     }
 
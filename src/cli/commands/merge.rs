@@ -0,0 +1,241 @@
+//! Merge command implementation.
+//!
+//! Interleaves the raw entries of two sessions by timestamp into one JSONL
+//! file, for a task that spanned a crash or restart. Every field is kept as
+//! written except `sessionId`, which becomes the first session's, and
+//! `parentUuid`: each message's parent becomes the message before it in the
+//! merged order, so the result reads as one linear thread whose latest leaf
+//! reaches every message. Sidechain entries keep their parents. Entries
+//! present in both files (same `uuid`) are written once.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::cli::{Cli, MergeArgs, OutputFormat};
+use crate::error::{Result, SnatchError};
+
+use super::get_claude_dir;
+use super::helpers::{read_raw_jsonl, refuse_qualified_provider_reference, write_raw_jsonl};
+
+/// Counts reported after a merge.
+#[derive(Debug, Default, serde::Serialize, PartialEq, Eq)]
+struct MergeReport {
+    /// Entries written.
+    entries: usize,
+    /// Entries of the second session skipped as duplicates of the first.
+    duplicates: usize,
+    /// Parent links rewritten to chain the messages in merged order.
+    relinked: usize,
+}
+
+/// Run the merge command.
+pub fn run(cli: &Cli, args: &MergeArgs) -> Result<()> {
+    for reference in [&args.first, &args.second] {
+        refuse_qualified_provider_reference(
+            cli,
+            reference,
+            "merge",
+            "merge rewrites Claude Code session files; provider-qualified sessions are not supported",
+        )?;
+    }
+    let claude_dir = get_claude_dir(cli)?;
+    let find = |reference: &String| {
+        claude_dir
+            .find_session(reference)?
+            .ok_or_else(|| SnatchError::SessionNotFound {
+                session_id: reference.clone(),
+            })
+    };
+    let first = find(&args.first)?;
+    let second = find(&args.second)?;
+    if first.path() == second.path() {
+        return Err(SnatchError::InvalidArgument {
+            name: "second".to_string(),
+            reason: "cannot merge a session with itself".to_string(),
+        });
+    }
+
+    let (merged, report) = merge_entries(
+        read_raw_jsonl(first.path())?,
+        read_raw_jsonl(second.path())?,
+    );
+    write_raw_jsonl(&args.output, &merged, args.overwrite)?;
+
+    match cli.effective_output() {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "output": args.output,
+                "session_id": first.session_id(),
+                "report": report,
+            }))?
+        ),
+        _ if cli.quiet => {}
+        _ => println!(
+            "Merged {} entries into {} ({} duplicate{} skipped, {} parent link{} rewritten)",
+            report.entries,
+            args.output.display(),
+            report.duplicates,
+            if report.duplicates == 1 { "" } else { "s" },
+            report.relinked,
+            if report.relinked == 1 { "" } else { "s" }
+        ),
+    }
+    Ok(())
+}
+
+fn timestamp(entry: &Value) -> Option<DateTime<Utc>> {
+    entry
+        .get("timestamp")?
+        .as_str()
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| ts.with_timezone(&Utc))
+}
+
+fn uuid(entry: &Value) -> Option<&str> {
+    entry.get("uuid")?.as_str()
+}
+
+/// Interleave two sessions' entries; see the [module docs](self).
+///
+/// Entries without a timestamp, such as summaries, sort with the entry
+/// before them in their own file. Ties keep the first session first.
+fn merge_entries(first: Vec<Value>, second: Vec<Value>) -> (Vec<Value>, MergeReport) {
+    let mut report = MergeReport::default();
+    let first_uuids: HashSet<String> = first.iter().filter_map(uuid).map(String::from).collect();
+    let session_id = first
+        .iter()
+        .find_map(|entry| entry.get("sessionId").cloned());
+
+    let mut keyed = Vec::with_capacity(first.len() + second.len());
+    for (source, entries) in [first, second].into_iter().enumerate() {
+        let mut last_time = None;
+        for (index, entry) in entries.into_iter().enumerate() {
+            if source == 1 && uuid(&entry).is_some_and(|id| first_uuids.contains(id)) {
+                report.duplicates += 1;
+                continue;
+            }
+            last_time = timestamp(&entry).or(last_time);
+            keyed.push((last_time, source, index, entry));
+        }
+    }
+    keyed.sort_by_key(|(time, source, index, _)| (*time, *source, *index));
+
+    let mut previous: Option<String> = None;
+    let mut merged = Vec::with_capacity(keyed.len());
+    for (_, _, _, mut entry) in keyed {
+        if let (Some(id), Some(fields)) = (session_id.clone(), entry.as_object_mut()) {
+            if fields.contains_key("sessionId") {
+                fields.insert("sessionId".to_string(), id);
+            }
+        }
+        let sidechain = entry.get("isSidechain").and_then(Value::as_bool) == Some(true);
+        if let (Some(id), false) = (uuid(&entry).map(String::from), sidechain) {
+            let parent = previous.map_or(Value::Null, Value::String);
+            if entry.get("parentUuid").unwrap_or(&Value::Null) != &parent {
+                entry["parentUuid"] = parent;
+                report.relinked += 1;
+            }
+            previous = Some(id);
+        }
+        merged.push(entry);
+    }
+    report.entries = merged.len();
+    (merged, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(uuid: &str, parent: Option<&str>, session: &str, minute: u32) -> Value {
+        json!({
+            "type": "user",
+            "uuid": uuid,
+            "parentUuid": parent,
+            "sessionId": session,
+            "timestamp": format!("2026-01-01T10:{minute:02}:00Z"),
+            "extra": { "kept": true },
+        })
+    }
+
+    /// The uuids on the `parentUuid` chain from the last message to its root.
+    fn leaf_chain(merged: &[Value]) -> Vec<String> {
+        let parents: std::collections::HashMap<&str, Option<&str>> = merged
+            .iter()
+            .filter_map(|e| Some((uuid(e)?, e["parentUuid"].as_str())))
+            .collect();
+        let mut next = merged.iter().rev().find_map(uuid);
+        let mut chain = Vec::new();
+        while let Some(id) = next {
+            chain.push(id.to_string());
+            next = parents[id];
+        }
+        chain.reverse();
+        chain
+    }
+
+    #[test]
+    fn test_merge_interleaves_into_one_thread() {
+        let first = vec![
+            entry("a1", None, "A", 0),
+            entry("a2", Some("a1"), "A", 2),
+            json!({ "type": "summary", "summary": "s", "leafUuid": "a2" }),
+        ];
+        let second = vec![
+            entry("a1", None, "B", 0),
+            entry("b1", None, "B", 1),
+            entry("b2", Some("b1"), "B", 3),
+        ];
+
+        let (merged, report) = merge_entries(first, second);
+        let order: Vec<_> = merged
+            .iter()
+            .map(|e| e.get("uuid").and_then(Value::as_str).unwrap_or("summary"))
+            .collect();
+        // The summary sorts with the entry before it in its own file.
+        assert_eq!(order, ["a1", "b1", "a2", "summary", "b2"]);
+        assert_eq!(
+            report,
+            MergeReport {
+                entries: 5,
+                duplicates: 1,
+                relinked: 3,
+            }
+        );
+        // The latest leaf reaches every message, so nothing is on a branch.
+        assert_eq!(leaf_chain(&merged), ["a1", "b1", "a2", "b2"]);
+        assert!(merged
+            .iter()
+            .filter(|e| e.get("sessionId").is_some())
+            .all(|e| e["sessionId"] == "A"));
+        assert_eq!(merged[4]["extra"]["kept"], true);
+    }
+
+    #[test]
+    fn test_merge_links_restarted_session_to_the_first() {
+        let first = vec![entry("a1", None, "A", 0), entry("a2", Some("a1"), "A", 1)];
+        let second = vec![entry("b1", None, "B", 9), entry("b2", Some("b1"), "B", 10)];
+
+        let (merged, report) = merge_entries(first, second);
+        assert_eq!(report.relinked, 1);
+        assert_eq!(merged[2]["uuid"], "b1");
+        assert_eq!(merged[2]["parentUuid"], "a2");
+        assert_eq!(leaf_chain(&merged), ["a1", "a2", "b1", "b2"]);
+    }
+
+    #[test]
+    fn test_merge_leaves_sidechains_alone() {
+        let mut side = entry("s1", Some("a1"), "A", 1);
+        side["isSidechain"] = json!(true);
+        let first = vec![entry("a1", None, "A", 0), side];
+        let second = vec![entry("b1", None, "B", 2)];
+
+        let (merged, _) = merge_entries(first, second);
+        assert_eq!(merged[1]["parentUuid"], "a1");
+        assert_eq!(merged[2]["parentUuid"], "a1");
+    }
+}
@@ -174,7 +174,7 @@ pub fn run(cli: &Cli, args: &MessagesArgs) -> Result<()> {
         return run_provider(cli, args);
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     let session =
         claude_dir
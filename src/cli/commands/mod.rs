@@ -3,6 +3,9 @@
 //! Each command is implemented in its own module with a `run` function
 //! that handles the command logic.
 
+pub mod batch;
+pub mod bench;
+pub mod blame;
 pub mod cache;
 pub mod chain;
 pub mod chunks;
@@ -12,6 +15,8 @@ pub mod completions;
 pub mod config;
 pub mod context;
 pub mod decisions;
+pub mod decrypt;
+pub mod dedupe;
 pub mod diff;
 pub mod digest;
 pub mod doctor;
@@ -23,10 +28,12 @@ pub mod goals;
 pub mod grab;
 pub mod health;
 pub mod helpers;
+pub mod import;
 pub mod index;
 pub mod info;
 pub mod lessons;
 pub mod list;
+pub mod merge;
 pub mod messages;
 pub mod notes;
 pub mod pick;
@@ -36,32 +43,95 @@ pub mod providers;
 pub mod quickstart;
 pub mod recent;
 pub mod recover;
+pub mod report;
+pub mod scrub;
 pub mod search;
+pub mod serve;
+pub mod shell;
+pub mod show;
+pub mod split;
 pub mod standup;
 pub mod stats;
 pub mod summary;
+pub mod sync;
+pub mod table;
 pub mod tag;
 pub mod thread;
 pub mod timeline;
+pub mod top;
 pub mod validate;
+pub mod verify;
 pub mod watch;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
 use std::time::SystemTime;
 
 use chrono::{Duration, NaiveDate, Utc};
 
-use crate::discovery::ClaudeDirectory;
+use crate::cli::Cli;
+use crate::discovery::{ClaudeDirectory, RemoteClaudeDir};
 use crate::error::{Result, SnatchError};
 
 /// Get the Claude directory from CLI args or discover automatically.
-pub fn get_claude_dir(custom_path: Option<&PathBuf>) -> Result<ClaudeDirectory> {
-    match custom_path {
+pub fn get_claude_dir(cli: &Cli) -> Result<ClaudeDirectory> {
+    match local_claude_dir(cli)? {
         Some(path) => ClaudeDirectory::from_path(path),
-        None => ClaudeDirectory::discover(),
+        None => Ok(ClaudeDirectory::discover()?.with_peers(peer_claude_dirs(cli))),
     }
 }
 
+/// Claude directories to merge into the discovered one: those across a WSL
+/// boundary when `discovery.wsl` is set, unless the directory is given by
+/// `--claude-dir` or `CLAUDE_CODE_DIR`.
+pub fn peer_claude_dirs(cli: &Cli) -> Vec<PathBuf> {
+    if cli.claude_dir.is_some()
+        || std::env::var_os("CLAUDE_CODE_DIR").is_some()
+        || !index::load_config(cli).discovery.wsl
+    {
+        return Vec::new();
+    }
+    crate::discovery::wsl_peer_directories()
+}
+
+/// The local path of a `--claude-dir` value. An `ssh://` location is
+/// mirrored into the cache directory, once per process, and the mirror
+/// returned.
+pub fn local_claude_dir(cli: &Cli) -> Result<Option<PathBuf>> {
+    static MIRRORS: OnceLock<Mutex<HashMap<PathBuf, PathBuf>>> = OnceLock::new();
+
+    let Some(path) = cli.claude_dir.as_ref() else {
+        return Ok(None);
+    };
+    let Some(remote) = RemoteClaudeDir::from_path(path) else {
+        return Ok(Some(path.clone()));
+    };
+    let mut mirrors = MIRRORS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(mirror) = mirrors.get(path) {
+        return Ok(Some(mirror.clone()));
+    }
+    let config = index::load_config(cli);
+    let cache_dir = crate::config::resolve_cache_dir(&config.cache)?;
+    let stats = remote.mirror(&cache_dir)?;
+    if !cli.quiet && (stats.fetched > 0 || stats.removed > 0) {
+        eprintln!(
+            "Mirrored {}:{}: {} of {} session file(s) updated ({}), {} removed",
+            remote.destination(),
+            remote.path(),
+            stats.fetched,
+            stats.files,
+            crate::discovery::format_size(stats.bytes),
+            stats.removed
+        );
+    }
+    mirrors.insert(path.clone(), stats.root.clone());
+    Ok(Some(stats.root))
+}
+
 /// Parse a date filter string.
 ///
 /// Supports:
@@ -183,7 +183,7 @@ pub fn run(cli: &Cli, args: &PickArgs) -> Result<()> {
     if !args.provider.is_empty() {
         return run_provider(cli, args);
     }
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Build session filter
     let mut filter = SessionFilter::new();
@@ -289,6 +289,8 @@ pub fn run(cli: &Cli, args: &PickArgs) -> Result<()> {
             let stats_args = crate::cli::StatsArgs {
                 project: None,
                 session: Some(session_id.to_string()),
+                tag: None,
+                tags: None,
                 provider: Vec::new(),
                 global: false,
                 tools: true,
@@ -309,6 +311,22 @@ pub fn run(cli: &Cli, args: &PickArgs) -> Result<()> {
                 granularity: "daily".to_string(),
                 graph: false,
                 graph_width: 60,
+                rank_by: crate::cli::LeaderboardMetric::default(),
+                heatmap: false,
+                agents: false,
+                languages: false,
+                correlation: false,
+                tool_chains: false,
+                branches: false,
+                rolling: false,
+                by_branch: false,
+                errors: false,
+                churn: false,
+                co_modification: false,
+                windows: None,
+                format: None,
+                rebuild_snapshots: false,
+                table: crate::cli::TableArgs::default(),
             };
             crate::cli::commands::stats::run(cli, &stats_args)?;
         }
@@ -319,11 +337,61 @@ pub fn run(cli: &Cli, args: &PickArgs) -> Result<()> {
                 eprintln!("Session file: {}", selected_session.path().display());
             }
         }
+        PickAction::Resume => resume_in_claude(cli, selected_session)?,
     }
 
     Ok(())
 }
 
+/// Run `claude --resume <session_id>` in the session's project directory.
+fn resume_in_claude(cli: &Cli, session: &crate::discovery::Session) -> Result<()> {
+    if session.is_subagent() {
+        return Err(SnatchError::InvalidArgument {
+            name: "--action resume".to_string(),
+            reason: "subagent sessions cannot be resumed; pick the parent session".to_string(),
+        });
+    }
+    let project_dir = std::path::Path::new(session.project_path());
+    if !project_dir.is_dir() {
+        return Err(SnatchError::DirectoryNotFound {
+            path: project_dir.to_path_buf(),
+        });
+    }
+
+    if !cli.quiet {
+        eprintln!(
+            "Resuming {} in {}",
+            session.session_id(),
+            project_dir.display()
+        );
+    }
+    let status = resume_command(session).status().map_err(|e| {
+        let context = if e.kind() == std::io::ErrorKind::NotFound {
+            "`claude` is not on PATH (is Claude Code installed?)".to_string()
+        } else {
+            "Failed to launch `claude`".to_string()
+        };
+        SnatchError::io(context, e)
+    })?;
+    if !status.success() {
+        return Err(SnatchError::io(
+            "`claude --resume` failed",
+            std::io::Error::other(format!("exited with {status}")),
+        ));
+    }
+    Ok(())
+}
+
+/// `claude --resume <session_id>`, run from the session's project directory.
+fn resume_command(session: &crate::discovery::Session) -> std::process::Command {
+    let mut command = std::process::Command::new("claude");
+    command
+        .arg("--resume")
+        .arg(session.session_id())
+        .current_dir(session.project_path());
+    command
+}
+
 fn run_provider(cli: &Cli, args: &PickArgs) -> Result<()> {
     use crate::provider::registry::ProviderSelection;
 
@@ -335,6 +403,14 @@ fn run_provider(cli: &Cli, args: &PickArgs) -> Result<()> {
     })?;
     let registry = super::helpers::provider_registry(cli);
     registry.select(&selection)?;
+    if args.action == PickAction::Resume {
+        return Err(SnatchError::InvalidArgument {
+            name: "--action resume".to_string(),
+            reason:
+                "resuming launches Claude Code; omit --provider for the classic filesystem picker"
+                    .to_string(),
+        });
+    }
     if args.action == PickAction::Open {
         return Err(SnatchError::InvalidArgument {
             name: "--action open".to_string(),
@@ -422,6 +498,8 @@ fn run_provider(cli: &Cli, args: &PickArgs) -> Result<()> {
                 &crate::cli::StatsArgs {
                     project: None,
                     session: Some(qualified_id),
+                    tag: None,
+                    tags: None,
                     provider: provider_flag,
                     global: false,
                     tools: true,
@@ -442,10 +520,27 @@ fn run_provider(cli: &Cli, args: &PickArgs) -> Result<()> {
                     granularity: "daily".to_string(),
                     graph: false,
                     graph_width: 60,
+                    rank_by: crate::cli::LeaderboardMetric::default(),
+                    heatmap: false,
+                    agents: false,
+                    languages: false,
+                    correlation: false,
+                    tool_chains: false,
+                    branches: false,
+                    rolling: false,
+                    by_branch: false,
+                    errors: false,
+                    churn: false,
+                    co_modification: false,
+                    windows: None,
+                    format: None,
+                    rebuild_snapshots: false,
+                    table: crate::cli::TableArgs::default(),
                 },
             )?;
         }
         PickAction::Open => unreachable!("provider open is rejected before discovery"),
+        PickAction::Resume => unreachable!("provider resume is rejected before discovery"),
     }
     Ok(())
 }
@@ -454,6 +549,26 @@ fn run_provider(cli: &Cli, args: &PickArgs) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resume_command_targets_session_in_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let path = dir.path().join(format!("{session_id}.jsonl"));
+        std::fs::write(&path, "").unwrap();
+        let session = crate::discovery::Session::from_path(&path, "/home/user/proj").unwrap();
+
+        let command = resume_command(&session);
+        assert_eq!(command.get_program(), "claude");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["--resume", session_id]
+        );
+        assert_eq!(
+            command.get_current_dir(),
+            Some(std::path::Path::new("/home/user/proj"))
+        );
+    }
+
     #[test]
     fn test_format_session_item_short() {
         // Basic test - just ensure formatting doesn't panic
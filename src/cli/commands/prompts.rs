@@ -16,6 +16,7 @@ use crate::cli::{Cli, OutputFormat, PromptsArgs};
 use crate::discovery::{Session, SessionFilter};
 use crate::error::{Result, SnatchError};
 use crate::model::LogEntry;
+use crate::parser::ParseFilter;
 
 use super::{get_claude_dir, parse_date_filter};
 
@@ -698,7 +699,7 @@ fn extract_single_session(cli: &Cli, args: &PromptsArgs, session_id: &str) -> Re
             }),
         )
     } else {
-        let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+        let claude_dir = get_claude_dir(cli)?;
         let session =
             claude_dir
                 .find_session(session_id)?
@@ -995,7 +996,7 @@ fn extract_provider_multiple_sessions(cli: &Cli, args: &PromptsArgs) -> Result<(
 
 /// Extract prompts from multiple sessions.
 fn extract_multiple_sessions(cli: &Cli, args: &PromptsArgs) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Build grep regex if pattern specified (do this early to fail fast on invalid regex)
     let grep_regex = build_grep_regex(args)?;
@@ -1323,7 +1324,9 @@ fn extract_prompts_from_session(
     args: &PromptsArgs,
     max_file_size: Option<u64>,
 ) -> Result<Vec<Prompt>> {
-    let entries = session.parse_with_options(max_file_size)?;
+    // Prompts are user entries; skip everything else before deserializing.
+    let filter = ParseFilter::new().with_types(["user"]);
+    let entries = session.parse_filtered(filter, max_file_size)?;
     Ok(entries
         .iter()
         .filter(|entry| is_human_prompt(entry))
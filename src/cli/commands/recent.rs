@@ -174,7 +174,7 @@ fn run_provider(cli: &Cli, args: &RecentArgs) -> Result<()> {
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!(
                 "qualified_id\tproject\tmodified\tsize\tcontinuation_members\tlatest_qualified_id\tname\ttags\tbookmarked\toutcome"
             );
@@ -437,7 +437,7 @@ pub fn run(cli: &Cli, args: &RecentArgs) -> Result<()> {
         return run_provider(cli, args);
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let tag_store = TagStore::load()?;
 
     // Get all sessions
@@ -481,7 +481,7 @@ pub fn run(cli: &Cli, args: &RecentArgs) -> Result<()> {
                 .collect();
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("id\tproject\tmodified\tsize\tname");
             for session in &sessions {
                 let id = session.session_id();
@@ -602,7 +602,7 @@ fn run_collapsed(
                 .collect();
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("id\tproject\tmodified\tsize\tname\tmember_count\tlatest_session_id");
             for row in &rows {
                 let id = &row.root_id;
@@ -96,7 +96,7 @@ pub fn run(cli: &Cli, args: &RecoverArgs) -> Result<()> {
         "recover",
         "provider-qualified sessions require provider-owned full-content reconstruction evidence; use `snatch file-history` or `snatch file-evolution` to inspect observed changes",
     )?;
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Find the session
     let sessions = claude_dir.all_sessions()?;
@@ -177,7 +177,7 @@ pub fn run(cli: &Cli, args: &RecoverArgs) -> Result<()> {
             };
             println!("{json}");
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!(
                 "path\tsize\tlines\twrites\tedits_applied\tedits_skipped\tcomplete\tintegrity"
             );
@@ -0,0 +1,538 @@
+//! Report command implementation.
+//!
+//! Composes a shareable usage report for a period — activity totals, cost by
+//! project and model, the costliest sessions, and the most used tools — as
+//! Markdown or plain text. Plain text carries no color or Markdown markup, so
+//! a cron job can mail it as-is.
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::analysis::filters::period_cutoff;
+use crate::analytics::{ProjectAnalytics, SessionAnalytics};
+use crate::cli::{Cli, OutputFormat, ReportArgs, ReportFormat};
+use crate::discovery::{format_count, format_number};
+use crate::error::{Result, SnatchError};
+use crate::reconstruction::Conversation;
+use crate::util::{atomic_write, truncate_path};
+
+use super::get_claude_dir;
+
+/// A session's analytics with the identity shown in the report.
+struct AnalyzedSession {
+    session_id: String,
+    project: String,
+    analytics: SessionAnalytics,
+}
+
+/// Everything a report shows, in render order.
+#[derive(Debug, Serialize)]
+struct Report {
+    period: String,
+    since: Option<DateTime<Utc>>,
+    until: DateTime<Utc>,
+    sessions: usize,
+    projects: usize,
+    messages: usize,
+    tool_invocations: usize,
+    total_tokens: u64,
+    estimated_cost: Option<f64>,
+    unpriced_models: Vec<String>,
+    top_projects: Vec<ProjectLine>,
+    models: Vec<ModelLine>,
+    top_sessions: Vec<SessionLine>,
+    top_tools: Vec<ToolLine>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectLine {
+    path: String,
+    sessions: usize,
+    tokens: u64,
+    estimated_cost: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct ModelLine {
+    model: String,
+    tokens: u64,
+    estimated_cost: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct SessionLine {
+    session_id: String,
+    project: String,
+    started: Option<DateTime<Utc>>,
+    messages: usize,
+    tokens: u64,
+    estimated_cost: Option<f64>,
+    primary_model: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolLine {
+    tool: String,
+    calls: usize,
+}
+
+/// Run the report command.
+pub fn run(cli: &Cli, args: &ReportArgs) -> Result<()> {
+    let (label, period) = if args.daily {
+        ("daily".to_string(), "1d")
+    } else if args.monthly {
+        ("monthly".to_string(), "30d")
+    } else if let Some(ref period) = args.period {
+        (format!("last {period}"), period.as_str())
+    } else {
+        ("weekly".to_string(), "7d")
+    };
+    let since = period_cutoff(period).map_err(|reason| SnatchError::InvalidArgument {
+        name: "period".to_string(),
+        reason,
+    })?;
+
+    let claude_dir = get_claude_dir(cli)?;
+    let sessions: Vec<_> = claude_dir
+        .all_sessions()?
+        .into_iter()
+        .filter(|s| since.is_none_or(|since| s.modified_datetime() > since))
+        .filter(|s| {
+            args.project
+                .as_ref()
+                .is_none_or(|project| s.project_path().contains(project.as_str()))
+        })
+        .collect();
+
+    let analyzed: Vec<AnalyzedSession> = sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(cli.max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            Some(AnalyzedSession {
+                session_id: session.session_id().to_string(),
+                project: session.project_path().to_string(),
+                analytics: SessionAnalytics::from_conversation(&conversation),
+            })
+        })
+        .collect();
+
+    let report = build_report(label, since, Utc::now(), analyzed, args.top);
+    let rendered = match (cli.effective_output(), args.format) {
+        (OutputFormat::Json, _) => serde_json::to_string_pretty(&report)? + "\n",
+        (_, ReportFormat::Markdown) => render_markdown(&report),
+        (_, ReportFormat::Text) => render_text(&report),
+    };
+
+    match args.output_file {
+        Some(ref path) => {
+            atomic_write(path, rendered.as_bytes())?;
+            if !cli.quiet {
+                eprintln!("Wrote {} report to {}", report.period, path.display());
+            }
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// Aggregate analyzed sessions into a report, keeping `top` rows per list.
+fn build_report(
+    period: String,
+    since: Option<DateTime<Utc>>,
+    until: DateTime<Utc>,
+    mut sessions: Vec<AnalyzedSession>,
+    top: usize,
+) -> Report {
+    let mut combined = ProjectAnalytics::default();
+    let mut by_project: IndexMap<String, ProjectAnalytics> = IndexMap::new();
+    for session in &sessions {
+        combined.add_session(&session.analytics);
+        by_project
+            .entry(session.project.clone())
+            .or_default()
+            .add_session(&session.analytics);
+    }
+    combined.calculate_cost();
+
+    let mut top_projects: Vec<ProjectLine> = by_project
+        .into_iter()
+        .map(|(path, mut project)| {
+            project.calculate_cost();
+            ProjectLine {
+                path,
+                sessions: project.session_count,
+                tokens: project.total_usage.usage.work_tokens(),
+                estimated_cost: project.total_usage.estimated_cost,
+            }
+        })
+        .collect();
+    top_projects.sort_by(|a, b| {
+        cost_key(b.estimated_cost)
+            .total_cmp(&cost_key(a.estimated_cost))
+            .then(b.tokens.cmp(&a.tokens))
+    });
+    top_projects.truncate(top);
+
+    let mut models: Vec<ModelLine> = combined
+        .total_usage
+        .by_model
+        .iter()
+        .map(|(model, usage)| ModelLine {
+            model: model.clone(),
+            tokens: usage.work_tokens(),
+            estimated_cost: combined
+                .total_usage
+                .cost_by_model
+                .get(model)
+                .map(|cost| cost.total_cost),
+        })
+        .collect();
+    models.sort_by(|a, b| {
+        cost_key(b.estimated_cost)
+            .total_cmp(&cost_key(a.estimated_cost))
+            .then(b.tokens.cmp(&a.tokens))
+    });
+
+    sessions.sort_by(|a, b| {
+        let (a, b) = (&a.analytics.usage, &b.analytics.usage);
+        cost_key(b.estimated_cost)
+            .total_cmp(&cost_key(a.estimated_cost))
+            .then(b.usage.work_tokens().cmp(&a.usage.work_tokens()))
+    });
+    let top_sessions = sessions
+        .iter()
+        .take(top)
+        .map(|session| {
+            let summary = session.analytics.summary_report();
+            SessionLine {
+                session_id: session.session_id.clone(),
+                project: session.project.clone(),
+                started: session.analytics.start_time,
+                messages: summary.total_messages,
+                tokens: summary.total_tokens,
+                estimated_cost: summary.estimated_cost,
+                primary_model: summary.primary_model,
+            }
+        })
+        .collect();
+
+    let mut top_tools: Vec<ToolLine> = combined
+        .tool_counts
+        .iter()
+        .map(|(tool, calls)| ToolLine {
+            tool: tool.clone(),
+            calls: *calls,
+        })
+        .collect();
+    top_tools.sort_by(|a, b| b.calls.cmp(&a.calls).then(a.tool.cmp(&b.tool)));
+    top_tools.truncate(top);
+
+    Report {
+        period,
+        since,
+        until,
+        sessions: sessions.len(),
+        projects: project_count(&sessions),
+        messages: combined.message_counts.conversation(),
+        tool_invocations: combined.message_counts.tool_uses,
+        total_tokens: combined.total_usage.usage.work_tokens(),
+        estimated_cost: combined.total_usage.estimated_cost,
+        unpriced_models: combined.total_usage.unpriced_models.clone(),
+        top_projects,
+        models,
+        top_sessions,
+        top_tools,
+    }
+}
+
+/// Count distinct projects across all sessions.
+fn project_count(sessions: &[AnalyzedSession]) -> usize {
+    let mut projects: Vec<&str> = sessions.iter().map(|s| s.project.as_str()).collect();
+    projects.sort_unstable();
+    projects.dedup();
+    projects.len()
+}
+
+/// Sort key ranking unpriced rows last.
+fn cost_key(cost: Option<f64>) -> f64 {
+    cost.unwrap_or(-1.0)
+}
+
+fn format_cost(cost: Option<f64>) -> String {
+    cost.map_or_else(|| "n/a".to_string(), |cost| format!("${cost:.2}"))
+}
+
+fn format_range(report: &Report) -> String {
+    let until = report.until.format("%Y-%m-%d");
+    match report.since {
+        Some(since) => format!("{} to {until}", since.format("%Y-%m-%d")),
+        None => format!("all time to {until}"),
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Render the report as Markdown.
+fn render_markdown(report: &Report) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Claude Code Report ({})", report.period);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", format_range(report));
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Summary");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Metric | Value |");
+    let _ = writeln!(out, "|--------|-------|");
+    let _ = writeln!(out, "| Sessions | {} |", format_count(report.sessions));
+    let _ = writeln!(out, "| Projects | {} |", format_count(report.projects));
+    let _ = writeln!(out, "| Messages | {} |", format_count(report.messages));
+    let _ = writeln!(out, "| Tokens | {} |", format_number(report.total_tokens));
+    let _ = writeln!(
+        out,
+        "| Tool calls | {} |",
+        format_count(report.tool_invocations)
+    );
+    let _ = writeln!(
+        out,
+        "| Estimated cost | {} |",
+        format_cost(report.estimated_cost)
+    );
+    if !report.unpriced_models.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "Cost excludes unpriced models: {}.",
+            report.unpriced_models.join(", ")
+        );
+    }
+
+    if report.sessions == 0 {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "No sessions in this period.");
+        return out;
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Cost by Project");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Project | Sessions | Tokens | Cost |");
+    let _ = writeln!(out, "|---------|---------:|-------:|-----:|");
+    for project in &report.top_projects {
+        let _ = writeln!(
+            out,
+            "| `{}` | {} | {} | {} |",
+            project.path,
+            project.sessions,
+            format_number(project.tokens),
+            format_cost(project.estimated_cost)
+        );
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Cost by Model");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Model | Tokens | Cost |");
+    let _ = writeln!(out, "|-------|-------:|-----:|");
+    for model in &report.models {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} |",
+            model.model,
+            format_number(model.tokens),
+            format_cost(model.estimated_cost)
+        );
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Top Sessions");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "| Session | Project | Started | Messages | Tokens | Cost |"
+    );
+    let _ = writeln!(
+        out,
+        "|---------|---------|---------|---------:|-------:|-----:|"
+    );
+    for session in &report.top_sessions {
+        let started = session
+            .started
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "| `{}` | `{}` | {} | {} | {} | {} |",
+            short_id(&session.session_id),
+            session.project,
+            started,
+            session.messages,
+            format_number(session.tokens),
+            format_cost(session.estimated_cost)
+        );
+    }
+
+    if !report.top_tools.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Top Tools");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "| Tool | Calls |");
+        let _ = writeln!(out, "|------|------:|");
+        for tool in &report.top_tools {
+            let _ = writeln!(out, "| {} | {} |", tool.tool, format_count(tool.calls));
+        }
+    }
+    out
+}
+
+/// Render the report as plain text for mail and terminals.
+fn render_text(report: &Report) -> String {
+    fn heading(out: &mut String, title: &str) {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{title}");
+        let _ = writeln!(out, "{}", "-".repeat(title.len()));
+    }
+
+    let mut out = String::new();
+    let title = format!("Claude Code Report ({})", report.period);
+    let _ = writeln!(out, "{title}");
+    let _ = writeln!(out, "{}", format_range(report));
+    let _ = writeln!(out, "{}", "=".repeat(title.len()));
+
+    heading(&mut out, "Summary");
+    let _ = writeln!(out, "  Sessions:    {}", format_count(report.sessions));
+    let _ = writeln!(out, "  Projects:    {}", format_count(report.projects));
+    let _ = writeln!(out, "  Messages:    {}", format_count(report.messages));
+    let _ = writeln!(out, "  Tokens:      {}", format_number(report.total_tokens));
+    let _ = writeln!(
+        out,
+        "  Tool calls:  {}",
+        format_count(report.tool_invocations)
+    );
+    let _ = writeln!(out, "  Cost:        {}", format_cost(report.estimated_cost));
+    if !report.unpriced_models.is_empty() {
+        let _ = writeln!(
+            out,
+            "  (cost excludes unpriced models: {})",
+            report.unpriced_models.join(", ")
+        );
+    }
+
+    if report.sessions == 0 {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "No sessions in this period.");
+        return out;
+    }
+
+    heading(&mut out, "Cost by Project");
+    for project in &report.top_projects {
+        let _ = writeln!(
+            out,
+            "  {:40} {:>5} sessions {:>14} tokens {:>10}",
+            truncate_path(&project.path, 40),
+            project.sessions,
+            format_number(project.tokens),
+            format_cost(project.estimated_cost)
+        );
+    }
+
+    heading(&mut out, "Cost by Model");
+    for model in &report.models {
+        let _ = writeln!(
+            out,
+            "  {:40} {:>14} tokens {:>10}",
+            model.model,
+            format_number(model.tokens),
+            format_cost(model.estimated_cost)
+        );
+    }
+
+    heading(&mut out, "Top Sessions");
+    for session in &report.top_sessions {
+        let started = session
+            .started
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "  {:8}  {:16}  {:30} {:>5} msgs {:>14} tokens {:>10}",
+            short_id(&session.session_id),
+            started,
+            truncate_path(&session.project, 30),
+            session.messages,
+            format_number(session.tokens),
+            format_cost(session.estimated_cost)
+        );
+    }
+
+    if !report.top_tools.is_empty() {
+        heading(&mut out, "Top Tools");
+        for tool in &report.top_tools {
+            let _ = writeln!(out, "  {:30} {:>8}", tool.tool, format_count(tool.calls));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, project: &str, tool_calls: &[(&str, usize)]) -> AnalyzedSession {
+        let mut analytics = SessionAnalytics::default();
+        analytics.message_counts.user = 2;
+        analytics.message_counts.assistant = 2;
+        for (tool, calls) in tool_calls {
+            analytics.tool_counts.insert((*tool).to_string(), *calls);
+            analytics.message_counts.tool_uses += calls;
+        }
+        AnalyzedSession {
+            session_id: id.to_string(),
+            project: project.to_string(),
+            analytics,
+        }
+    }
+
+    #[test]
+    fn test_build_report_aggregates_and_ranks() {
+        let sessions = vec![
+            session("aaaaaaaa-1", "/work/api", &[("Bash", 3)]),
+            session("bbbbbbbb-2", "/work/api", &[("Read", 5), ("Bash", 1)]),
+            session("cccccccc-3", "/work/web", &[]),
+        ];
+        let report = build_report("weekly".to_string(), None, Utc::now(), sessions, 1);
+
+        assert_eq!(report.sessions, 3);
+        assert_eq!(report.projects, 2);
+        assert_eq!(report.messages, 12);
+        assert_eq!(report.tool_invocations, 9);
+        assert_eq!(report.top_projects.len(), 1);
+        assert_eq!(report.top_sessions.len(), 1);
+        assert_eq!(report.top_tools.len(), 1);
+        assert_eq!(report.top_tools[0].tool, "Read");
+    }
+
+    #[test]
+    fn test_renderers_emit_sections() {
+        let sessions = vec![session("aaaaaaaa-1", "/work/api", &[("Bash", 3)])];
+        let report = build_report("weekly".to_string(), None, Utc::now(), sessions, 5);
+
+        let markdown = render_markdown(&report);
+        assert!(markdown.starts_with("# Claude Code Report (weekly)\n"));
+        assert!(markdown.contains("## Top Sessions"));
+        assert!(markdown.contains("| `aaaaaaaa` | `/work/api` |"));
+
+        let text = render_text(&report);
+        assert!(text.contains("Top Sessions\n------------\n"));
+        assert!(!text.contains('|') && !text.contains('#'));
+
+        let empty = build_report("daily".to_string(), None, Utc::now(), Vec::new(), 5);
+        assert!(render_text(&empty).ends_with("No sessions in this period.\n"));
+    }
+}
@@ -0,0 +1,395 @@
+//! Scrub command implementation.
+//!
+//! Rewrites a session's original JSONL with sensitive values redacted, for
+//! when secrets landed in the logs. Only string values are redacted, and
+//! identifiers, timestamps, and signatures are left alone so the session
+//! still parses and threads the same way. Lines without a match keep their
+//! exact bytes.
+//!
+//! The rewrite is checked before anything is written (same line count, every
+//! record still parses with its `uuid` and `type`), optionally backed up, and
+//! written atomically. The search index is refreshed afterwards so it no
+//! longer holds the redacted text.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cli::{Cli, OutputFormat, ScrubArgs};
+use crate::discovery::SessionState;
+use crate::error::{Result, SnatchError};
+use crate::parser::is_compressed_path;
+use crate::util::{atomic_write, detect_sensitive, redact_sensitive, RedactionConfig};
+
+use super::cleanup::purge_derived_data;
+use super::get_claude_dir;
+
+/// The scrub result for one file.
+#[derive(Debug, Serialize)]
+struct ScrubbedFile {
+    path: PathBuf,
+    /// Lines rewritten.
+    changed_lines: usize,
+    /// Redacted values by kind of sensitive data.
+    findings: BTreeMap<&'static str, usize>,
+    /// Backup copy of the original, when one was made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backup: Option<PathBuf>,
+    #[serde(skip)]
+    content: String,
+}
+
+/// The scrub report (`-o json`).
+#[derive(Debug, Serialize)]
+struct ScrubReport {
+    session_id: String,
+    preview: bool,
+    written: bool,
+    files: Vec<ScrubbedFile>,
+}
+
+/// Run the scrub command.
+pub fn run(cli: &Cli, args: &ScrubArgs) -> Result<()> {
+    let claude_dir = get_claude_dir(cli)?;
+    let session =
+        claude_dir
+            .find_session(&args.session)?
+            .ok_or_else(|| SnatchError::SessionNotFound {
+                session_id: args.session.clone(),
+            })?;
+    if !args.force
+        && session
+            .state()
+            .is_ok_and(|state| state != SessionState::Inactive)
+    {
+        return Err(SnatchError::InvalidArgument {
+            name: "session".to_string(),
+            reason: format!(
+                "session {} looks active; wait for it to finish or pass --force",
+                session.short_id()
+            ),
+        });
+    }
+
+    let mut paths = vec![session.path().to_path_buf()];
+    if args.subagents {
+        paths.extend(session.subagent_links().into_iter().map(|link| link.path));
+    }
+    if let Some(path) = paths.iter().find(|path| is_compressed_path(path)) {
+        return Err(SnatchError::InvalidArgument {
+            name: "session".to_string(),
+            reason: format!(
+                "{} is compressed; decompress it before scrubbing",
+                path.display()
+            ),
+        });
+    }
+
+    let config: RedactionConfig = args.redact.into();
+    let mut files = Vec::new();
+    for path in paths {
+        let text = fs::read_to_string(&path)
+            .map_err(|e| SnatchError::io(path.display().to_string(), e))?;
+        let (content, changed_lines, findings) = scrub_text(&text, &config);
+        if changed_lines == 0 {
+            continue;
+        }
+        validate(&text, &content).map_err(|reason| {
+            SnatchError::export(format!(
+                "scrubbed {} failed validation ({reason}); nothing was written",
+                path.display()
+            ))
+        })?;
+        files.push(ScrubbedFile {
+            path,
+            changed_lines,
+            findings,
+            backup: None,
+            content,
+        });
+    }
+
+    let mut report = ScrubReport {
+        session_id: session.session_id().to_string(),
+        preview: args.preview,
+        written: false,
+        files,
+    };
+    let json = matches!(cli.effective_output(), OutputFormat::Json);
+    if !json {
+        print_findings(&report);
+    }
+    if report.files.is_empty() || args.preview {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        return Ok(());
+    }
+
+    if !args.yes {
+        let lines: usize = report.files.iter().map(|f| f.changed_lines).sum();
+        eprint!(
+            "Rewrite {lines} line{} in {} file{}{}? [y/N] ",
+            plural(lines),
+            report.files.len(),
+            plural(report.files.len()),
+            if args.backup {
+                " (originals backed up)"
+            } else {
+                " without a backup"
+            }
+        );
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let answer = input.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            eprintln!("Scrub cancelled.");
+            return Ok(());
+        }
+    }
+
+    for file in &mut report.files {
+        if args.backup {
+            let backup = backup_path(&file.path);
+            fs::copy(&file.path, &backup).map_err(|e| {
+                SnatchError::io(format!("Failed to back up {}", file.path.display()), e)
+            })?;
+            file.backup = Some(backup);
+        }
+        atomic_write(&file.path, file.content.as_bytes())?;
+    }
+    report.written = true;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if !cli.quiet {
+        for file in &report.files {
+            if let Some(backup) = &file.backup {
+                eprintln!("Backed up {} to {}", file.path.display(), backup.display());
+            }
+        }
+        eprintln!(
+            "Scrubbed {} file{}.",
+            report.files.len(),
+            plural(report.files.len())
+        );
+    }
+    let config = super::index::load_config(cli);
+    let written: Vec<PathBuf> = report.files.iter().map(|f| f.path.clone()).collect();
+    purge_derived_data(cli, &config, &written)
+}
+
+/// Print what was (or would be) redacted.
+fn print_findings(report: &ScrubReport) {
+    if report.files.is_empty() {
+        println!(
+            "No sensitive values found in session {}.",
+            report.session_id
+        );
+        return;
+    }
+    for file in &report.files {
+        println!(
+            "{}: {} line{}",
+            file.path.display(),
+            file.changed_lines,
+            plural(file.changed_lines)
+        );
+        for (kind, count) in &file.findings {
+            println!("  {count:>5}  {kind}");
+        }
+    }
+    if report.preview {
+        println!();
+        println!("Preview only; run without --preview to rewrite.");
+    }
+}
+
+/// A backup path next to the original that does not overwrite an earlier one.
+pub(super) fn backup_path(path: &Path) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut backup = path.with_file_name(format!("{name}.{stamp}.bak"));
+    let mut n = 1;
+    while backup.exists() {
+        backup = path.with_file_name(format!("{name}.{stamp}-{n}.bak"));
+        n += 1;
+    }
+    backup
+}
+
+/// Redact a JSONL text line by line. Returns the new text, the number of
+/// rewritten lines, and redacted value counts by kind. Lines that are not
+/// valid JSON are redacted as plain text.
+fn scrub_text(
+    text: &str,
+    config: &RedactionConfig,
+) -> (String, usize, BTreeMap<&'static str, usize>) {
+    let mut out = String::with_capacity(text.len());
+    let mut changed_lines = 0;
+    let mut findings = BTreeMap::new();
+    for line in text.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let ending = &line[body.len()..];
+        let rewritten = match serde_json::from_str::<Value>(body) {
+            Ok(mut value) => {
+                redact_value(&mut value, None, config, &mut findings).then(|| value.to_string())
+            }
+            Err(_) => match redact_sensitive(body, config) {
+                Cow::Owned(redacted) if redacted != body => {
+                    count_findings(body, config, &mut findings);
+                    Some(redacted)
+                }
+                _ => None,
+            },
+        };
+        match rewritten {
+            Some(body) => {
+                changed_lines += 1;
+                out.push_str(&body);
+                out.push_str(ending);
+            }
+            None => out.push_str(line),
+        }
+    }
+    (out, changed_lines, findings)
+}
+
+/// Redact string values in place, returning whether anything changed.
+fn redact_value(
+    value: &mut Value,
+    key: Option<&str>,
+    config: &RedactionConfig,
+    findings: &mut BTreeMap<&'static str, usize>,
+) -> bool {
+    match value {
+        Value::String(s) if !key.is_some_and(is_structural) => match redact_sensitive(s, config) {
+            Cow::Owned(redacted) if redacted != *s => {
+                count_findings(s, config, findings);
+                *s = redacted;
+                true
+            }
+            _ => false,
+        },
+        Value::Array(items) => items.iter_mut().fold(false, |changed, item| {
+            redact_value(item, None, config, findings) || changed
+        }),
+        Value::Object(map) => map.iter_mut().fold(false, |changed, (k, v)| {
+            redact_value(v, Some(k.as_str()), config, findings) || changed
+        }),
+        _ => false,
+    }
+}
+
+fn count_findings(
+    text: &str,
+    config: &RedactionConfig,
+    findings: &mut BTreeMap<&'static str, usize>,
+) {
+    for kind in detect_sensitive(text, config) {
+        *findings.entry(kind.description()).or_default() += 1;
+    }
+}
+
+/// Keys whose string values are identifiers or encodings, never redacted:
+/// changing them would break threading, signatures, or embedded images.
+fn is_structural(key: &str) -> bool {
+    matches!(
+        key,
+        "type"
+            | "uuid"
+            | "id"
+            | "timestamp"
+            | "version"
+            | "signature"
+            | "data"
+            | "media_type"
+            | "model"
+            | "role"
+            | "userType"
+            | "stop_reason"
+    ) || key.ends_with("Uuid")
+        || key.ends_with("UUID")
+        || key.ends_with("Id")
+        || key.ends_with("_id")
+}
+
+/// Check a rewrite against the original: same line count, and every JSON
+/// record still parses with the same `uuid` and `type`.
+fn validate(original: &str, scrubbed: &str) -> std::result::Result<(), String> {
+    let before: Vec<&str> = original.lines().collect();
+    let after: Vec<&str> = scrubbed.lines().collect();
+    if before.len() != after.len() {
+        return Err(format!(
+            "line count changed from {} to {}",
+            before.len(),
+            after.len()
+        ));
+    }
+    for (number, (old, new)) in before.iter().zip(&after).enumerate() {
+        let Ok(old) = serde_json::from_str::<Value>(old) else {
+            continue;
+        };
+        let new = serde_json::from_str::<Value>(new)
+            .map_err(|e| format!("line {} no longer parses: {e}", number + 1))?;
+        for field in ["uuid", "type"] {
+            if old.get(field) != new.get(field) {
+                return Err(format!("line {} changed its {field}", number + 1));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_text_redacts_content_only() {
+        let text = concat!(
+            r#"{"type":"user","uuid":"u1","sessionId":"s","message":{"role":"user","content":"deploy with api_key=abcdef0123456789abcdef"}}"#,
+            "\n",
+            r#"{"type":"assistant","uuid":"u2", "message":{"content":[{"type":"text","text":"done"}]}}"#,
+            "\r\n",
+            "not json password=hunter22\n",
+        );
+        let (scrubbed, changed, findings) = scrub_text(text, &RedactionConfig::security());
+        assert_eq!(changed, 2);
+        assert!(!scrubbed.contains("abcdef0123456789abcdef"));
+        assert!(!scrubbed.contains("hunter22"));
+        assert!(scrubbed.contains(r#""uuid":"u1""#));
+        // Untouched lines keep their exact bytes, including spacing and CRLF.
+        assert!(scrubbed.contains(
+            "{\"type\":\"assistant\",\"uuid\":\"u2\", \"message\":{\"content\":[{\"type\":\"text\",\"text\":\"done\"}]}}\r\n"
+        ));
+        assert_eq!(findings.values().sum::<usize>(), 2);
+        assert!(validate(text, &scrubbed).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_structural_changes() {
+        let original = "{\"type\":\"user\",\"uuid\":\"a\"}\n";
+        assert!(validate(original, "{\"type\":\"user\",\"uuid\":\"b\"}\n").is_err());
+        assert!(validate(original, "{\"type\":\"user\"\n").is_err());
+        assert!(validate(original, "").is_err());
+        assert!(!is_structural("content") && is_structural("parentUuid"));
+    }
+}
@@ -17,7 +17,7 @@ use crate::analysis::search::{
     ExactSearchMatcher, ProjectedSearchMatch, SearchScope,
 };
 use crate::cli::{Cli, OutputFormat, SearchArgs};
-use crate::discovery::Session;
+use crate::discovery::{ClaudeDirectory, Session};
 use crate::error::{Result, SnatchError};
 use crate::index::provider::{IndexedSessionManifest, ProviderSearchIndex};
 use crate::index::query::{
@@ -29,6 +29,9 @@ use crate::model::{ContentBlock, LogEntry};
 type SessionMatchCount = (String, usize, Option<SystemTime>);
 
 use super::get_claude_dir;
+use super::table::{
+    Table, SEARCH_PATTERN_COLUMNS, SEARCH_PATTERN_TSV_COLUMNS, SEARCH_RESULT_COLUMNS,
+};
 
 /// Check if an entry matches the search filters.
 fn matches_filters(entry: &LogEntry, args: &SearchArgs) -> bool {
@@ -329,8 +332,11 @@ fn parse_patterns_tsv(path: &std::path::Path) -> Result<Vec<BatchPattern>> {
 
 /// Collect and filter sessions based on search args (shared by single-pattern and batch paths).
 fn collect_sessions(cli: &Cli, args: &SearchArgs) -> Result<Vec<Session>> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    collect_sessions_in(&get_claude_dir(cli)?, args)
+}
 
+/// Collect and filter sessions of one Claude directory based on search args.
+fn collect_sessions_in(claude_dir: &ClaudeDirectory, args: &SearchArgs) -> Result<Vec<Session>> {
     let mut sessions = if let Some(session_id) = &args.session {
         let session =
             claude_dir
@@ -491,9 +497,9 @@ fn run_batch(cli: &Cli, args: &SearchArgs, patterns: Vec<BatchPattern>) -> Resul
     let is_tsv_mode = args.patterns_tsv.is_some();
 
     if args.breakdown {
-        output_batch_breakdown(cli, &patterns, &counts, &per_session, is_tsv_mode)?;
+        output_batch_breakdown(cli, args, &patterns, &counts, &per_session, is_tsv_mode)?;
     } else {
-        output_batch_aggregate(cli, &patterns, &counts, is_tsv_mode)?;
+        output_batch_aggregate(cli, args, &patterns, &counts, is_tsv_mode)?;
     }
 
     Ok(())
@@ -502,6 +508,7 @@ fn run_batch(cli: &Cli, args: &SearchArgs, patterns: Vec<BatchPattern>) -> Resul
 /// Output batch results as aggregate counts (original behavior).
 fn output_batch_aggregate(
     cli: &Cli,
+    args: &SearchArgs,
     patterns: &[BatchPattern],
     counts: &[usize],
     is_tsv_mode: bool,
@@ -522,7 +529,10 @@ fn output_batch_aggregate(
                         })
                     })
                     .collect();
-                println!("{}", serde_json::to_string_pretty(&entries)?);
+                println!(
+                    "{}",
+                    super::helpers::json_output(&entries, args.filter.as_ref())?
+                );
             } else {
                 let map: Vec<serde_json::Value> = patterns
                     .iter()
@@ -534,20 +544,30 @@ fn output_batch_aggregate(
                         })
                     })
                     .collect();
-                println!("{}", serde_json::to_string_pretty(&map)?);
+                println!(
+                    "{}",
+                    super::helpers::json_output(&map, args.filter.as_ref())?
+                );
             }
         }
-        OutputFormat::Tsv => {
-            if is_tsv_mode {
-                println!("category\tsubcategory\tlabel\tcount");
-                for (i, p) in patterns.iter().enumerate() {
-                    println!("{}\t{}", p.label, counts[i]);
-                }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let columns = if is_tsv_mode {
+                SEARCH_PATTERN_TSV_COLUMNS
             } else {
-                println!("pattern\tcount");
-                for (i, p) in patterns.iter().enumerate() {
-                    println!("{}\t{}", p.label, counts[i]);
-                }
+                SEARCH_PATTERN_COLUMNS
+            };
+            let table = Table::with_all(format, columns, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (p, count) in patterns.iter().zip(counts) {
+                let mut row: Vec<String> = if is_tsv_mode {
+                    let mut parts = p.label.splitn(3, '\t').map(String::from);
+                    (0..3).map(|_| parts.next().unwrap_or_default()).collect()
+                } else {
+                    vec![p.label.clone()]
+                };
+                row.push(count.to_string());
+                table.write_row(&mut stdout, &row)?;
             }
         }
         _ => {
@@ -579,6 +599,7 @@ fn output_batch_aggregate(
 /// Output batch results with per-session breakdown (--breakdown).
 fn output_batch_breakdown(
     cli: &Cli,
+    args: &SearchArgs,
     patterns: &[BatchPattern],
     counts: &[usize],
     per_session: &[std::collections::HashMap<String, (usize, Option<SystemTime>)>],
@@ -624,7 +645,10 @@ fn output_batch_breakdown(
                     }
                 })
                 .collect();
-            println!("{}", serde_json::to_string_pretty(&entries)?);
+            println!(
+                "{}",
+                super::helpers::json_output(&entries, args.filter.as_ref())?
+            );
         }
         _ => {
             let mut prev_cat = String::new();
@@ -706,6 +730,11 @@ fn validate_indexed_args(args: &SearchArgs) -> Result<()> {
         breakdown,
         phase,
         show_uuid,
+        semantic,
+        output_file: _,
+        saved: _,
+        filter: _,
+        table,
     } = args;
     super::helpers::refuse_unsupported_flags(
         "provider-index search",
@@ -717,6 +746,9 @@ fn validate_indexed_args(args: &SearchArgs) -> Result<()> {
             ("--breakdown", *breakdown),
             ("--phase", phase.is_some()),
             ("--show-uuid", *show_uuid),
+            ("--semantic", *semantic),
+            ("--columns", table.columns.is_some()),
+            ("--no-header", table.no_header),
             (
                 "--with-date",
                 *with_date && !args.count && !*aggregate_by_session,
@@ -893,12 +925,15 @@ fn output_indexed_summary(
         OutputFormat::Json => {
             println!(
                 "{}",
-                serde_json::to_string_pretty(&serde_json::json!({
-                    "total": total,
-                    "count_basis": if use_occurrences { "occurrences" } else { "matching_lines" },
-                    "by_session": summaries,
-                    "coverage": response.coverage,
-                }))?
+                super::helpers::json_output(
+                    &serde_json::json!({
+                        "total": total,
+                        "count_basis": if use_occurrences { "occurrences" } else { "matching_lines" },
+                        "by_session": summaries,
+                        "coverage": response.coverage,
+                    }),
+                    args.filter.as_ref(),
+                )?
             );
         }
         _ if cli.quiet && args.count => println!("{total}"),
@@ -952,11 +987,14 @@ fn output_indexed_files(
     match cli.effective_output() {
         OutputFormat::Json => println!(
             "{}",
-            serde_json::to_string_pretty(&serde_json::json!({
-                "total_sessions": total,
-                "sessions": summaries,
-                "coverage": response.coverage,
-            }))?
+            super::helpers::json_output(
+                &serde_json::json!({
+                    "total_sessions": total,
+                    "sessions": summaries,
+                    "coverage": response.coverage,
+                }),
+                args.filter.as_ref(),
+            )?
         ),
         _ => {
             for summary in &summaries {
@@ -975,15 +1013,22 @@ fn output_indexed_files(
     Ok(())
 }
 
-fn output_indexed_match_only(cli: &Cli, response: &IndexedSearchResponse) -> Result<()> {
+fn output_indexed_match_only(
+    cli: &Cli,
+    args: &SearchArgs,
+    response: &IndexedSearchResponse,
+) -> Result<()> {
     match cli.effective_output() {
         OutputFormat::Json => println!(
             "{}",
-            serde_json::to_string_pretty(&serde_json::json!({
-                "total_matches": response.total_matches,
-                "matches": response.matches.iter().map(|hit| &hit.matched_text).collect::<Vec<_>>(),
-                "coverage": response.coverage,
-            }))?
+            super::helpers::json_output(
+                &serde_json::json!({
+                    "total_matches": response.total_matches,
+                    "matches": response.matches.iter().map(|hit| &hit.matched_text).collect::<Vec<_>>(),
+                    "coverage": response.coverage,
+                }),
+                args.filter.as_ref(),
+            )?
         ),
         _ => {
             for hit in &response.matches {
@@ -1055,22 +1100,304 @@ fn run_indexed(cli: &Cli, args: &SearchArgs) -> Result<()> {
         } else {
             IndexedSearchOrder::Source
         },
+        boosts: super::index::load_config(cli).index.boosts,
         offset: 0,
         limit: if summary_only { 0 } else { args.limit },
     })?;
-    if args.files_only {
+    if let Some(path) = &args.output_file {
+        super::helpers::write_results_file(cli, path, &response.matches)
+    } else if args.files_only {
         output_indexed_files(cli, args, &response)
     } else if args.aggregate_by_session || args.count {
         output_indexed_summary(cli, args, &response)
     } else if args.match_only {
-        output_indexed_match_only(cli, &response)
+        output_indexed_match_only(cli, args, &response)
     } else {
-        super::index::output_search_response(cli, &response)
+        super::index::output_search_response(cli, &args.table, &response)
     }
 }
 
 /// Run the search command.
+/// Run `search --semantic`: embed new or changed sessions, then rank
+/// messages by fused embedding similarity and keyword relevance.
+#[cfg(feature = "semantic")]
+fn run_semantic(cli: &Cli, args: &SearchArgs) -> Result<()> {
+    use super::table::SEARCH_SEMANTIC_COLUMNS;
+    use crate::index::semantic::{embedder_from_config, SemanticIndex};
+    use crate::index::LOCAL_SOURCE;
+
+    super::helpers::refuse_unsupported_flags(
+        "semantic search",
+        &[
+            (
+                "multiple patterns (quote the query)",
+                args.pattern.len() > 1,
+            ),
+            ("--patterns-tsv", args.patterns_tsv.is_some()),
+            ("--thinking", args.thinking),
+            ("--thinking-only", args.thinking_only),
+            ("--tools", args.tools),
+            ("--all", args.all),
+            ("--files-only", args.files_only),
+            ("--count", args.count),
+            ("--model", args.model.is_some()),
+            ("--tool-name", args.tool_name.is_some()),
+            ("--errors", args.errors),
+            ("--fuzzy", args.fuzzy),
+            ("--min-tokens", args.min_tokens.is_some()),
+            ("--max-tokens", args.max_tokens.is_some()),
+            ("--branch", args.git_branch.is_some()),
+            ("--sort", args.sort),
+            ("--match-only", args.match_only),
+            ("--exclude", args.exclude.is_some()),
+            ("--aggregate-by-session", args.aggregate_by_session),
+            ("--breakdown", args.breakdown),
+            ("--phase", args.phase.is_some()),
+        ],
+    )?;
+    let query = args.pattern.first().map_or("", String::as_str);
+    if query.trim().is_empty() {
+        return Err(SnatchError::InvalidArgument {
+            name: "pattern".to_string(),
+            reason: "--semantic needs a query".to_string(),
+        });
+    }
+
+    let config = super::index::load_config(cli);
+    let embedder = embedder_from_config(&config.index)?;
+    let mut index = SemanticIndex::open(&SemanticIndex::dir_for(&super::index::index_path(cli)))?;
+
+    // The local Claude directory plus any configured sources. The index
+    // always covers every session so deletions are noticed; the scope flags
+    // narrow the results instead.
+    let mut sources = vec![(LOCAL_SOURCE.to_string(), get_claude_dir(cli)?)];
+    for source in &config.index.sources {
+        if sources.iter().any(|(name, _)| *name == source.name) {
+            return Err(SnatchError::ConfigError {
+                message: format!("duplicate index source name '{}'", source.name),
+            });
+        }
+        sources.push((
+            source.name.clone(),
+            ClaudeDirectory::from_path(&source.path)?,
+        ));
+    }
+    let all_sessions = sources
+        .iter()
+        .map(|(name, claude_dir)| Ok((name.as_str(), claude_dir.all_sessions()?)))
+        .collect::<Result<Vec<_>>>()?;
+    let by_source: Vec<(&str, &[Session])> = all_sessions
+        .iter()
+        .map(|(name, sessions)| (*name, sessions.as_slice()))
+        .collect();
+    let update = index.update(&by_source, embedder.as_ref())?;
+    if !cli.quiet {
+        for (session_id, error) in &update.errors {
+            eprintln!("Warning: {session_id}: {error}");
+        }
+    }
+
+    let scoped = args.project.is_some()
+        || args.session.is_some()
+        || args.since.is_some()
+        || args.until.is_some()
+        || args.recent.is_some()
+        || args.no_subagents
+        || args.message_type.is_some();
+    let mut in_scope: HashSet<(String, String)> = HashSet::new();
+    for (name, claude_dir) in &sources {
+        // A session filter naturally matches in only one source.
+        let sessions = match collect_sessions_in(claude_dir, args) {
+            Err(SnatchError::SessionNotFound { .. }) if args.session.is_some() => continue,
+            result => result?,
+        };
+        in_scope.extend(
+            sessions
+                .iter()
+                .map(|session| (name.clone(), session.session_id().to_string())),
+        );
+    }
+    if in_scope.is_empty() {
+        if let Some(session_id) = &args.session {
+            return Err(SnatchError::SessionNotFound {
+                session_id: session_id.clone(),
+            });
+        }
+    }
+    let limit = if args.no_limit {
+        usize::MAX
+    } else {
+        args.limit
+    };
+    // Scope filters apply after ranking, so draw a deeper candidate list.
+    let candidates = if scoped {
+        limit.saturating_mul(10)
+    } else {
+        limit
+    };
+    let hits: Vec<_> = index
+        .search(query, embedder.as_ref(), candidates)?
+        .into_iter()
+        .filter(|hit| in_scope.contains(&(hit.source.clone(), hit.session_id.clone())))
+        .filter(|hit| {
+            args.message_type
+                .as_deref()
+                .is_none_or(|kind| hit.message_type.eq_ignore_ascii_case(kind))
+        })
+        .take(limit)
+        .collect();
+
+    if let Some(path) = &args.output_file {
+        return super::helpers::write_results_file(cli, path, &hits);
+    }
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                super::helpers::json_output(&hits, args.filter.as_ref())?
+            );
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, SEARCH_SEMANTIC_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for hit in &hits {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        hit.source.clone(),
+                        hit.session_id.clone(),
+                        hit.project.clone(),
+                        hit.uuid.clone(),
+                        hit.message_type.clone(),
+                        hit.timestamp.clone(),
+                        format!("{:.4}", hit.score),
+                        hit.snippet.clone(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            for hit in &hits {
+                println!(
+                    "{} ({}): {}",
+                    &hit.session_id[..8.min(hit.session_id.len())],
+                    hit.project,
+                    hit.snippet.replace('\n', " ")
+                );
+            }
+        }
+        OutputFormat::Text => {
+            if hits.is_empty() {
+                println!("No matches found.");
+                return Ok(());
+            }
+            println!("Top {} semantic matches:", hits.len());
+            for hit in &hits {
+                println!();
+                let uuid_suffix = if args.show_uuid && !hit.uuid.is_empty() {
+                    format!(" [{}]", &hit.uuid[..hit.uuid.len().min(12)])
+                } else {
+                    String::new()
+                };
+                let source_prefix = if hit.source == LOCAL_SOURCE {
+                    String::new()
+                } else {
+                    format!("{}: ", hit.source)
+                };
+                println!(
+                    "Session: {}{} ({}) [{}]{}",
+                    source_prefix,
+                    &hit.session_id[..8.min(hit.session_id.len())],
+                    hit.project,
+                    hit.message_type,
+                    uuid_suffix
+                );
+                println!("  > {}", hit.snippet.replace('\n', " "));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "semantic"))]
+fn run_semantic(_cli: &Cli, _args: &SearchArgs) -> Result<()> {
+    Err(SnatchError::InvalidArgument {
+        name: "--semantic".to_string(),
+        reason:
+            "this build of snatch lacks the `semantic` feature (cargo install --features semantic)"
+                .to_string(),
+    })
+}
+
+/// Fill `args` from the saved search `name`, keeping flags set on the
+/// command line.
+fn apply_saved_search(cli: &Cli, args: &SearchArgs, name: &str) -> Result<SearchArgs> {
+    let config = super::index::load_config(cli);
+    let saved = config
+        .searches
+        .get(name)
+        .ok_or_else(|| SnatchError::ConfigError {
+            message: if config.searches.is_empty() {
+                format!("No saved search named '{name}'; none are configured")
+            } else {
+                format!(
+                    "No saved search named '{name}' (available: {})",
+                    config
+                        .searches
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            },
+        })?;
+    if !args.pattern.is_empty() {
+        return Err(SnatchError::InvalidArgument {
+            name: "saved".to_string(),
+            reason: format!("saved search '{name}' supplies the pattern; omit the positional one"),
+        });
+    }
+
+    let mut resolved = args.clone();
+    resolved.pattern = vec![saved.query.clone()];
+    resolved.message_type = args
+        .message_type
+        .clone()
+        .or_else(|| saved.message_type.clone());
+    resolved.project = args.project.clone().or_else(|| saved.project.clone());
+    resolved.model = args.model.clone().or_else(|| saved.model.clone());
+    resolved.tool_name = args.tool_name.clone().or_else(|| saved.tool_name.clone());
+    resolved.ignore_case |= saved.ignore_case;
+    resolved.thinking |= saved.thinking;
+    resolved.tools |= saved.tools;
+    Ok(resolved)
+}
+
 pub fn run(cli: &Cli, args: &SearchArgs) -> Result<()> {
+    let resolved;
+    let args = match &args.saved {
+        Some(name) => {
+            resolved = apply_saved_search(cli, args, name)?;
+            &resolved
+        }
+        None => args,
+    };
+    if args.output_file.is_some() {
+        super::helpers::refuse_unsupported_flags(
+            "--out, which saves individual matches",
+            &[
+                ("multiple patterns", args.pattern.len() > 1),
+                ("--patterns-tsv", args.patterns_tsv.is_some()),
+                ("--files-only", args.files_only),
+                ("--count", args.count),
+                ("--aggregate-by-session", args.aggregate_by_session),
+                ("--filter", args.filter.is_some()),
+            ],
+        )?;
+    }
+    super::helpers::require_json_for_filter(cli, args.filter.as_ref())?;
     let qualified_session = args
         .session
         .as_deref()
@@ -1078,6 +1405,9 @@ pub fn run(cli: &Cli, args: &SearchArgs) -> Result<()> {
     if !args.provider.is_empty() || qualified_session {
         return run_indexed(cli, args);
     }
+    if args.semantic {
+        return run_semantic(cli, args);
+    }
     // ── TSV batch mode ──────────────────────────────────────────────────
     // Design note: --patterns-tsv changes search's semantics from "find and display results"
     // to "batch count across heterogeneous queries." This is coherent for counting but may not
@@ -1358,12 +1688,14 @@ pub fn run(cli: &Cli, args: &SearchArgs) -> Result<()> {
     }
 
     // Output results based on mode
-    if args.files_only {
+    if let Some(path) = &args.output_file {
+        super::helpers::write_results_file(cli, path, &all_results)?;
+    } else if args.files_only {
         let files_only_truncated = !args.no_limit && sessions_with_matches.len() > args.limit;
         if files_only_truncated {
             sessions_with_matches.truncate(args.limit);
         }
-        output_files_only(cli, &sessions_with_matches)?;
+        output_files_only(cli, args, &sessions_with_matches)?;
         if files_only_truncated && !cli.quiet {
             eprintln!(
                 "Showing {} matching sessions (limit: {}, use --no-limit for all)",
@@ -1372,11 +1704,11 @@ pub fn run(cli: &Cli, args: &SearchArgs) -> Result<()> {
             );
         }
     } else if args.aggregate_by_session {
-        output_aggregate(cli, &match_counts, total_matches)?;
+        output_aggregate(cli, args, &match_counts, total_matches)?;
     } else if args.count {
         output_count(cli, args, &match_counts, total_matches)?;
     } else if args.match_only {
-        output_match_only(cli, &all_results)?;
+        output_match_only(cli, args, &all_results)?;
     } else {
         output_full_results(cli, args, &all_results, total_matches)?;
     }
@@ -1397,10 +1729,13 @@ fn matches_message_type(entry: &LogEntry, type_filter: &str) -> bool {
 }
 
 /// Output only session IDs with matches.
-fn output_files_only(cli: &Cli, sessions: &[String]) -> Result<()> {
+fn output_files_only(cli: &Cli, args: &SearchArgs, sessions: &[String]) -> Result<()> {
     match cli.effective_output() {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(sessions)?);
+            println!(
+                "{}",
+                super::helpers::json_output(sessions, args.filter.as_ref())?
+            );
         }
         _ => {
             for session_id in sessions {
@@ -1459,7 +1794,10 @@ fn output_count(
                 "total": total,
                 "by_session": by_session,
             });
-            println!("{}", serde_json::to_string_pretty(&output)?);
+            println!(
+                "{}",
+                super::helpers::json_output(&output, args.filter.as_ref())?
+            );
         }
         _ => {
             if match_counts.len() == 1 {
@@ -1493,6 +1831,7 @@ fn output_count(
 /// Output one line per session with match count (--aggregate-by-session).
 fn output_aggregate(
     cli: &Cli,
+    args: &SearchArgs,
     match_counts: &std::collections::HashMap<String, SessionMatchCount>,
     total: usize,
 ) -> Result<()> {
@@ -1527,7 +1866,10 @@ fn output_aggregate(
                 "total": total,
                 "sessions": entries,
             });
-            println!("{}", serde_json::to_string_pretty(&output)?);
+            println!(
+                "{}",
+                super::helpers::json_output(&output, args.filter.as_ref())?
+            );
         }
         _ => {
             let mut counts: Vec<(&String, &SessionMatchCount)> = match_counts.iter().collect();
@@ -1552,11 +1894,14 @@ fn output_aggregate(
 }
 
 /// Output only matched text (--match-only, like grep -o).
-fn output_match_only(cli: &Cli, results: &[SearchResult]) -> Result<()> {
+fn output_match_only(cli: &Cli, args: &SearchArgs, results: &[SearchResult]) -> Result<()> {
     match cli.effective_output() {
         OutputFormat::Json => {
             let matches: Vec<&str> = results.iter().map(|r| r.matched_text.as_str()).collect();
-            println!("{}", serde_json::to_string_pretty(&matches)?);
+            println!(
+                "{}",
+                super::helpers::json_output(&matches, args.filter.as_ref())?
+            );
         }
         _ => {
             for result in results {
@@ -1576,20 +1921,27 @@ fn output_full_results(
 ) -> Result<()> {
     match cli.effective_output() {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&all_results)?);
+            println!(
+                "{}",
+                super::helpers::json_output(&all_results, args.filter.as_ref())?
+            );
         }
-        OutputFormat::Tsv => {
-            println!("session\tproject\tuuid\ttype\tlocation\tline");
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, SEARCH_RESULT_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
             for result in all_results {
-                println!(
-                    "{}\t{}\t{}\t{}\t{}\t{}",
-                    &result.session_id[..8.min(result.session_id.len())],
-                    result.project,
-                    &result.uuid[..8.min(result.uuid.len())],
-                    result.entry_type,
-                    result.location,
-                    result.line.replace('\t', " ")
-                );
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        result.session_id[..8.min(result.session_id.len())].to_string(),
+                        result.project.clone(),
+                        result.uuid[..8.min(result.uuid.len())].to_string(),
+                        result.entry_type.clone(),
+                        result.location.clone(),
+                        result.line.clone(),
+                    ],
+                )?;
             }
         }
         OutputFormat::Compact => {
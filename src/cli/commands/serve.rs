@@ -0,0 +1,454 @@
+//! Serve command implementation.
+//!
+//! Exposes read-only session data as JSON over HTTP, backed by
+//! [`SnatchClient`], so web frontends and scripts can query it without
+//! shelling out. A small pool of threads handles connections, each with read
+//! and write timeouts, so a stalled client holds up only its own thread. The
+//! server binds to loopback unless told otherwise and sends no CORS headers, since session
+//! logs are private. Requests must name the server in `Host` (its address or
+//! `localhost`), so a web page that rebinds its own domain name to the
+//! loopback address cannot read them either.
+//!
+//! Endpoints (all `GET`):
+//!
+//! - `/projects`
+//! - `/sessions?project=<substring>&limit=<n>`
+//! - `/sessions/<id>`
+//! - `/sessions/<id>/conversation`
+//! - `/sessions/<id>/stats`
+//! - `/search?q=<regex>&limit=<n>`
+//! - `/metrics`, usage in the Prometheus text format for scraping
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::analytics::prometheus;
+use crate::api::{ExportFormat, SnatchClient};
+use crate::cli::{Cli, ServeArgs};
+use crate::discovery::ClaudeDirectory;
+use crate::error::{Result, SnatchError};
+
+use super::get_claude_dir;
+use super::stats::compute_stats_by_project;
+
+/// Default number of sessions or search matches returned.
+const DEFAULT_LIMIT: usize = 50;
+
+/// Largest request head (request line plus headers) accepted, in bytes.
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// Connections served at once.
+const WORKERS: usize = 4;
+
+/// How long a client may take to send its request or receive the response.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What requests are served from.
+struct Server {
+    client: SnatchClient,
+    claude_dir: ClaudeDirectory,
+    max_file_size: Option<u64>,
+    /// The bound address, which `Host` must name.
+    addr: SocketAddr,
+}
+
+/// Run the serve command.
+pub fn run(cli: &Cli, args: &ServeArgs) -> Result<()> {
+    let claude_dir = get_claude_dir(cli)?;
+    let client = SnatchClient::with_path(claude_dir.root())?;
+
+    let addr = SocketAddr::new(args.bind, args.port);
+    let listener = TcpListener::bind(addr)
+        .map_err(|e| SnatchError::io(format!("Failed to bind {addr}"), e))?;
+    if !cli.quiet {
+        eprintln!("Serving session data on http://{addr} (Ctrl-C to stop)");
+    }
+
+    let server = Server {
+        client,
+        claude_dir,
+        max_file_size: cli.max_file_size,
+        addr,
+    };
+    // Accepted connections queue for the workers; when every worker is busy
+    // and the queue is full, accepting waits.
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(WORKERS);
+    let receiver = Mutex::new(receiver);
+    std::thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            scope.spawn(|| loop {
+                // The lock is held only while waiting for the next connection.
+                let next = receiver.lock().ok().and_then(|r| r.recv().ok());
+                let Some(stream) = next else {
+                    return;
+                };
+                if let Err(e) = handle_connection(&server, stream) {
+                    tracing::debug!("HTTP connection failed: {e}");
+                }
+            });
+        }
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            if sender.send(stream).is_err() {
+                break;
+            }
+        }
+        drop(sender);
+    });
+    Ok(())
+}
+
+/// A parsed request line.
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    /// The `Host` header, if any.
+    host: Option<String>,
+}
+
+impl Request {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn limit(&self) -> std::result::Result<usize, Response> {
+        match self.param("limit") {
+            None => Ok(DEFAULT_LIMIT),
+            Some(raw) => raw
+                .parse()
+                .map_err(|_| Response::error(400, &format!("invalid limit '{raw}'"))),
+        }
+    }
+}
+
+/// `Content-Type` of JSON responses.
+const JSON: &str = "application/json";
+
+/// A response ready to write.
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self::ok(JSON, body),
+            Err(e) => Self::error(500, &e.to_string()),
+        }
+    }
+
+    const fn ok(content_type: &'static str, body: String) -> Self {
+        Self {
+            status: 200,
+            content_type,
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            content_type: JSON,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+
+    fn from_error(error: &SnatchError) -> Self {
+        let status = match error {
+            SnatchError::SessionNotFound { .. } => 404,
+            SnatchError::InvalidArgument { .. } => 400,
+            _ => 500,
+        };
+        Self::error(status, &error.to_string())
+    }
+}
+
+fn handle_connection(server: &Server, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    // Reading stops one byte past the limit, however long a line is.
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST_HEAD as u64 + 1));
+    let mut head = String::new();
+    loop {
+        let read = reader.read_line(&mut head)?;
+        if head.len() > MAX_REQUEST_HEAD {
+            return write_response(stream, &Response::error(431, "request head too large"));
+        }
+        if read == 0 || head.ends_with("\r\n\r\n") || head.ends_with("\n\n") {
+            break;
+        }
+    }
+
+    let response = match parse_request(&head) {
+        Some(request)
+            if !request
+                .host
+                .as_deref()
+                .is_some_and(|host| host_allowed(host, server.addr)) =>
+        {
+            Response::error(421, "Host does not name this server")
+        }
+        Some(request) => route(server, &request),
+        None => Response::error(400, "malformed request"),
+    };
+    write_response(stream, &response)
+}
+
+/// Whether a `Host` header names the server bound at `addr`: `localhost` or
+/// the bound address (any address when bound to all of them), with the
+/// bound port. Any other name may be a rebound domain.
+fn host_allowed(host: &str, addr: SocketAddr) -> bool {
+    let (name, port) = match host.strip_prefix('[') {
+        Some(bracketed) => match bracketed.split_once(']') {
+            Some((name, rest)) => (name, rest.strip_prefix(':')),
+            None => return false,
+        },
+        None => match host.rsplit_once(':') {
+            Some((name, port)) => (name, Some(port)),
+            None => (host, None),
+        },
+    };
+    let port_matches = match port {
+        Some(port) => port.parse::<u16>().ok() == Some(addr.port()),
+        None => addr.port() == 80,
+    };
+    let name_matches = name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| {
+            ip == addr.ip()
+                || addr.ip().is_unspecified()
+                || (ip.is_loopback() && addr.ip().is_loopback())
+        });
+    port_matches && name_matches
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> std::io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        421 => "Misdirected Request",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Parse the request line and `Host` header of an HTTP request head.
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.lines();
+    let mut parts = lines.next()?.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    if !parts.next()?.starts_with("HTTP/") {
+        return None;
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect();
+    let host = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.trim().to_string());
+    Some(Request {
+        method,
+        path: percent_decode(path),
+        query,
+        host,
+    })
+}
+
+/// Decode `%XX` escapes and `+` (as a space) in a URL component.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = |offset: usize| {
+                    bytes
+                        .get(i + offset)
+                        .and_then(|&b| char::from(b).to_digit(16))
+                        .and_then(|digit| u8::try_from(digit).ok())
+                };
+                match (hex(1), hex(2)) {
+                    (Some(high), Some(low)) => {
+                        decoded.push(high * 16 + low);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn route(server: &Server, request: &Request) -> Response {
+    let client = &server.client;
+    if request.method != "GET" {
+        return Response::error(405, "only GET is supported");
+    }
+    let segments: Vec<&str> = request
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    let result = match segments.as_slice() {
+        ["projects"] => client.projects().map(|projects| Response::json(&projects)),
+        ["sessions"] => {
+            let limit = match request.limit() {
+                Ok(limit) => limit,
+                Err(response) => return response,
+            };
+            let sessions = match request.param("project") {
+                Some(project) => client.recent_project_sessions(project, limit),
+                None => client.recent_sessions(limit),
+            };
+            sessions.map(|sessions| Response::json(&sessions))
+        }
+        ["sessions", id] => client.get_session(id).map(|session| match session {
+            Some(session) => Response::json(&session),
+            None => Response::from_error(&SnatchError::SessionNotFound {
+                session_id: (*id).to_string(),
+            }),
+        }),
+        ["sessions", id, "conversation"] => client
+            .export_session(id, ExportFormat::Json)
+            .map(|body| Response::ok(JSON, body)),
+        ["sessions", id, "stats"] => client
+            .session_analytics(id)
+            .map(|summary| Response::json(&summary)),
+        ["search"] => {
+            let Some(pattern) = request.param("q") else {
+                return Response::error(400, "missing query parameter 'q'");
+            };
+            let limit = match request.limit() {
+                Ok(limit) => limit,
+                Err(response) => return response,
+            };
+            client
+                .search(pattern, limit)
+                .map(|matches| Response::json(&matches))
+        }
+        ["metrics"] => server.claude_dir.all_sessions().map(|sessions| {
+            let (combined, by_project) = compute_stats_by_project(&sessions, server.max_file_size);
+            Response::ok(
+                prometheus::CONTENT_TYPE,
+                prometheus::render(&combined, &by_project),
+            )
+        }),
+        _ => return Response::error(404, &format!("no endpoint at {}", request.path)),
+    };
+    result.unwrap_or_else(|e| Response::from_error(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_decodes_path_and_query() {
+        let request = parse_request(
+            "GET /search?q=race+condition&limit=5&x=%2Fa%zz HTTP/1.1\r\nHost: x\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/search");
+        assert_eq!(request.param("q"), Some("race condition"));
+        assert_eq!(request.limit().unwrap(), 5);
+        assert_eq!(request.param("x"), Some("/a%zz"));
+
+        assert_eq!(request.host.as_deref(), Some("x"));
+
+        assert!(parse_request("GET /projects\r\n\r\n").is_none());
+        assert!(parse_request("").is_none());
+    }
+
+    #[test]
+    fn test_host_must_name_the_server() {
+        let loopback: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+        for host in [
+            "localhost:8080",
+            "LOCALHOST:8080",
+            "127.0.0.1:8080",
+            "[::1]:8080",
+        ] {
+            assert!(host_allowed(host, loopback), "{host}");
+        }
+        for host in [
+            "evil.example:8080",
+            "127.0.0.1:9090",
+            "localhost",
+            "[::1",
+            "",
+        ] {
+            assert!(!host_allowed(host, loopback), "{host}");
+        }
+        let all: SocketAddr = "0.0.0.0:80".parse().unwrap();
+        assert!(host_allowed("192.168.1.5", all));
+        assert!(!host_allowed("rebound.example", all));
+    }
+
+    #[test]
+    fn test_route_rejects_unknown_paths_and_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("projects")).unwrap();
+        let server = Server {
+            client: SnatchClient::with_path(dir.path()).unwrap(),
+            claude_dir: ClaudeDirectory::from_path(dir.path()).unwrap(),
+            max_file_size: None,
+            addr: "127.0.0.1:8080".parse().unwrap(),
+        };
+        let request = |method: &str, target: &str| {
+            route(
+                &server,
+                &parse_request(&format!("{method} {target} HTTP/1.1\r\n\r\n")).unwrap(),
+            )
+        };
+
+        assert_eq!(request("GET", "/projects").body, "[]");
+        assert_eq!(request("GET", "/nope").status, 404);
+        assert_eq!(request("POST", "/projects").status, 405);
+        assert_eq!(request("GET", "/sessions?limit=x").status, 400);
+        assert_eq!(request("GET", "/search").status, 400);
+        assert_eq!(request("GET", "/search?q=(").status, 400);
+        assert_eq!(request("GET", "/sessions/missing").status, 404);
+        let metrics = request("GET", "/metrics");
+        assert_eq!(metrics.content_type, prometheus::CONTENT_TYPE);
+        assert!(metrics.body.contains("# TYPE snatch_sessions gauge"));
+    }
+}
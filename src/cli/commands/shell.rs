@@ -0,0 +1,561 @@
+//! Shell command implementation.
+//!
+//! An interactive query shell over an in-memory session catalog. Sessions are
+//! discovered once at startup and parsed on first use; parsed conversations
+//! stay cached for the rest of the shell, so repeated `grep`/`stats`/`export`
+//! commands over the same sessions skip re-parsing.
+//!
+//! Commands are read line by line from stdin, so a script can be piped in:
+//! `printf 'use 1\nstats\n' | snatch shell`.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use regex::RegexBuilder;
+
+use crate::analytics::{AnalyticsSummary, SessionAnalytics};
+use crate::cli::{Cli, ExportFormatArg, ShellArgs};
+use crate::discovery::Session;
+use crate::error::{Result, SnatchError};
+use crate::export::ExportOptions;
+use crate::model::LogEntry;
+use crate::reconstruction::Conversation;
+use crate::util::{truncate_path, truncate_text};
+
+use super::helpers::{collect_sessions, extract_text, SessionCollectParams};
+
+/// Matches printed by one `grep` before the rest are only counted.
+const MAX_GREP_MATCHES: usize = 100;
+
+const HELP: &str = "\
+Commands:
+  list [N] [TEXT]         List catalog sessions (default 20), optionally matching TEXT
+  use <#N|SESSION>        Select a session by list number or id prefix
+  info                    Show the selected session
+  grep [-i] <REGEX>       Search messages in the selected session (all sessions if none)
+  stats                   Usage summary for the selected session (all sessions if none)
+  export <FORMAT> [FILE]  Export the selected session to stdout or FILE
+  reload                  Rediscover sessions and drop parsed data
+  help                    Show this help
+  quit                    Leave the shell";
+
+/// A parsed shell command line.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    List {
+        limit: usize,
+        text: Option<String>,
+    },
+    Use(String),
+    Info,
+    Grep {
+        pattern: String,
+        ignore_case: bool,
+    },
+    Stats,
+    Export {
+        format: ExportFormatArg,
+        file: Option<PathBuf>,
+    },
+    Reload,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// Parse one input line. Blank lines and `#` comments yield `None`.
+    fn parse(line: &str) -> std::result::Result<Option<Self>, String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        let (name, rest) = line
+            .split_once(char::is_whitespace)
+            .map_or((line, ""), |(name, rest)| (name, rest.trim()));
+        let command = match name {
+            "list" | "ls" => {
+                let (limit, text) = match rest.split_once(char::is_whitespace) {
+                    Some((first, text)) if first.parse::<usize>().is_ok() => {
+                        (first.parse().ok(), Some(text.trim()))
+                    }
+                    _ => match rest.parse::<usize>() {
+                        Ok(limit) => (Some(limit), None),
+                        Err(_) => (None, Some(rest)),
+                    },
+                };
+                Command::List {
+                    limit: limit.unwrap_or(20),
+                    text: text.filter(|t| !t.is_empty()).map(str::to_string),
+                }
+            }
+            "use" | "cd" if !rest.is_empty() => Command::Use(rest.to_string()),
+            "use" | "cd" => return Err("usage: use <#N|SESSION>".to_string()),
+            "info" => Command::Info,
+            "grep" | "search" => {
+                let (ignore_case, pattern) = match rest.strip_prefix("-i") {
+                    Some(pattern) if pattern.is_empty() || pattern.starts_with(' ') => {
+                        (true, pattern.trim())
+                    }
+                    _ => (false, rest),
+                };
+                if pattern.is_empty() {
+                    return Err("usage: grep [-i] <REGEX>".to_string());
+                }
+                Command::Grep {
+                    pattern: pattern.to_string(),
+                    ignore_case,
+                }
+            }
+            "stats" => Command::Stats,
+            "export" => {
+                let mut words = rest.split_whitespace();
+                let format = words
+                    .next()
+                    .ok_or_else(|| "usage: export <FORMAT> [FILE]".to_string())?;
+                Command::Export {
+                    format: ExportFormatArg::from_str(format, true)
+                        .map_err(|_| format!("unknown export format '{format}'"))?,
+                    file: words.next().map(PathBuf::from),
+                }
+            }
+            "reload" => Command::Reload,
+            "help" | "?" => Command::Help,
+            "quit" | "exit" | "q" => Command::Quit,
+            other => return Err(format!("unknown command '{other}' (try `help`)")),
+        };
+        Ok(Some(command))
+    }
+}
+
+/// Shell state: the session catalog, parsed conversations, and the selection.
+struct Shell<'a> {
+    cli: &'a Cli,
+    args: &'a ShellArgs,
+    /// Sessions, newest first; list numbers are 1-based positions here.
+    catalog: Vec<Session>,
+    /// Parsed conversations by session id.
+    parsed: HashMap<String, Conversation>,
+    /// Index of the selected session in `catalog`.
+    current: Option<usize>,
+}
+
+/// Run the shell command.
+pub fn run(cli: &Cli, args: &ShellArgs) -> Result<()> {
+    let mut shell = Shell {
+        cli,
+        args,
+        catalog: Vec::new(),
+        parsed: HashMap::new(),
+        current: None,
+    };
+    shell.reload()?;
+
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    if interactive && !cli.quiet {
+        eprintln!(
+            "snatch shell: {} session{} loaded. Type `help` for commands, `quit` to leave.",
+            shell.catalog.len(),
+            if shell.catalog.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    let mut input = stdin.lock();
+    let mut line = String::new();
+    loop {
+        if interactive {
+            let prompt = shell
+                .current
+                .map(|i| format!("snatch:{}> ", shell.catalog[i].short_id()))
+                .unwrap_or_else(|| "snatch> ".to_string());
+            eprint!("{prompt}");
+            io::stderr().flush()?;
+        }
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        let command = match Command::parse(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(message) => {
+                eprintln!("{message}");
+                continue;
+            }
+        };
+        if command == Command::Quit {
+            break;
+        }
+        // A failed command is reported and the shell carries on.
+        if let Err(e) = shell.execute(command) {
+            eprintln!("Error: {e}");
+        }
+    }
+    Ok(())
+}
+
+impl Shell<'_> {
+    fn execute(&mut self, command: Command) -> Result<()> {
+        match command {
+            Command::List { limit, text } => self.list(limit, text.as_deref()),
+            Command::Use(reference) => self.select(&reference),
+            Command::Info => self.info(),
+            Command::Grep {
+                pattern,
+                ignore_case,
+            } => self.grep(&pattern, ignore_case),
+            Command::Stats => self.stats(),
+            Command::Export { format, file } => self.export(format, file),
+            Command::Reload => {
+                self.reload()?;
+                println!("{} sessions loaded.", self.catalog.len());
+                Ok(())
+            }
+            Command::Help => {
+                println!("{HELP}");
+                Ok(())
+            }
+            Command::Quit => Ok(()),
+        }
+    }
+
+    /// Rediscover the catalog and drop cached conversations.
+    fn reload(&mut self) -> Result<()> {
+        let mut catalog = collect_sessions(
+            self.cli,
+            &SessionCollectParams {
+                session: None,
+                project: self.args.project.as_deref(),
+                since: self.args.since.as_deref(),
+                until: self.args.until.as_deref(),
+                recent: None,
+                no_subagents: !self.args.subagents,
+            },
+        )?;
+        catalog.sort_by_key(|s| std::cmp::Reverse(s.modified_time()));
+        self.catalog = catalog;
+        self.parsed.clear();
+        self.current = None;
+        Ok(())
+    }
+
+    fn list(&self, limit: usize, text: Option<&str>) -> Result<()> {
+        let matches: Vec<(usize, &Session)> = self
+            .catalog
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| {
+                text.is_none_or(|text| {
+                    s.session_id().starts_with(text) || s.project_path().contains(text)
+                })
+            })
+            .collect();
+        for (i, session) in matches.iter().take(limit) {
+            let marker = if self.current == Some(*i) { '*' } else { ' ' };
+            println!(
+                "{marker}{:>4}  {}  {}  {:>9}  {}",
+                i + 1,
+                session.short_id(),
+                session
+                    .modified_datetime()
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M"),
+                session.file_size_human(),
+                truncate_path(session.project_path(), 50)
+            );
+        }
+        if matches.len() > limit {
+            println!("  … {} more (list N to show more)", matches.len() - limit);
+        }
+        Ok(())
+    }
+
+    /// Select a session by 1-based list number (`#3` or `3`) or id prefix.
+    fn select(&mut self, reference: &str) -> Result<()> {
+        let number = reference.strip_prefix('#').unwrap_or(reference);
+        let index = match number.parse::<usize>() {
+            Ok(n) if (1..=self.catalog.len()).contains(&n) => n - 1,
+            Ok(_) => {
+                return Err(SnatchError::InvalidArgument {
+                    name: "session".to_string(),
+                    reason: format!("no session #{number} (catalog has {})", self.catalog.len()),
+                })
+            }
+            Err(_) => {
+                let found: Vec<usize> = self
+                    .catalog
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, s)| s.session_id().starts_with(reference))
+                    .map(|(i, _)| i)
+                    .collect();
+                match found.as_slice() {
+                    [index] => *index,
+                    [] => {
+                        return Err(SnatchError::SessionNotFound {
+                            session_id: reference.to_string(),
+                        })
+                    }
+                    _ => {
+                        return Err(SnatchError::InvalidArgument {
+                            name: "session".to_string(),
+                            reason: format!(
+                                "'{reference}' matches {} sessions; give more characters",
+                                found.len()
+                            ),
+                        })
+                    }
+                }
+            }
+        };
+        let entries = self.conversation(index)?.len();
+        let session = &self.catalog[index];
+        println!(
+            "Using {} ({}): {entries} entries",
+            session.session_id(),
+            session.project_path()
+        );
+        self.current = Some(index);
+        Ok(())
+    }
+
+    fn info(&mut self) -> Result<()> {
+        let index = self.selected()?;
+        let entries = self.conversation(index)?.len();
+        let session = &self.catalog[index];
+        println!("Session:  {}", session.session_id());
+        println!("Project:  {}", session.project_path());
+        println!("File:     {}", session.path().display());
+        println!(
+            "Size:     {} ({entries} entries)",
+            session.file_size_human()
+        );
+        println!(
+            "Modified: {}",
+            session
+                .modified_datetime()
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        );
+        println!("Cached:   {} parsed session(s)", self.parsed.len());
+        Ok(())
+    }
+
+    fn grep(&mut self, pattern: &str, ignore_case: bool) -> Result<()> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|e| SnatchError::InvalidArgument {
+                name: "pattern".to_string(),
+                reason: e.to_string(),
+            })?;
+        let scope: Vec<usize> = match self.current {
+            Some(index) => vec![index],
+            None => (0..self.catalog.len()).collect(),
+        };
+
+        let mut total = 0;
+        for index in scope {
+            let short_id = self.catalog[index].short_id().to_string();
+            let conversation = self.conversation(index)?;
+            for entry in conversation.chronological_entries() {
+                let Some(text) = extract_text(entry) else {
+                    continue;
+                };
+                let Some(found) = regex.find(&text) else {
+                    continue;
+                };
+                total += 1;
+                if total > MAX_GREP_MATCHES {
+                    continue;
+                }
+                let role = match entry {
+                    LogEntry::User(_) => "user",
+                    _ => "assistant",
+                };
+                let time = entry
+                    .timestamp()
+                    .map(|t| {
+                        t.with_timezone(&chrono::Local)
+                            .format("%m-%d %H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                let start = text[..found.start()]
+                    .char_indices()
+                    .rev()
+                    .nth(40)
+                    .map_or(0, |(i, _)| i);
+                let snippet = text[start..].replace('\n', " ");
+                println!(
+                    "{short_id}  {time:<11}  {role:<9}  {}",
+                    truncate_text(&snippet, 100)
+                );
+            }
+        }
+        if total > MAX_GREP_MATCHES {
+            println!("  … {} more matches", total - MAX_GREP_MATCHES);
+        }
+        println!(
+            "{total} matching message{}",
+            if total == 1 { "" } else { "s" }
+        );
+        Ok(())
+    }
+
+    fn stats(&mut self) -> Result<()> {
+        let scope: Vec<usize> = match self.current {
+            Some(index) => vec![index],
+            None => (0..self.catalog.len()).collect(),
+        };
+        let mut summaries = Vec::with_capacity(scope.len());
+        for &index in &scope {
+            summaries.push(
+                SessionAnalytics::from_conversation(self.conversation(index)?).summary_report(),
+            );
+        }
+        let summary = match summaries.as_slice() {
+            [single] => single.clone(),
+            _ => AnalyticsSummary::aggregate(&summaries),
+        };
+
+        println!("Sessions:       {}", scope.len());
+        println!(
+            "Messages:       {} ({} user, {} assistant)",
+            summary.total_messages, summary.user_messages, summary.assistant_messages
+        );
+        println!(
+            "Tokens:         {} ({} in, {} out, {} cache read)",
+            summary.total_tokens,
+            summary.input_tokens,
+            summary.output_tokens,
+            summary.cache_read_tokens
+        );
+        println!(
+            "Tool calls:     {} ({} tools)",
+            summary.tool_invocations, summary.unique_tools
+        );
+        println!("Errors:         {}", summary.error_count);
+        if let Some(model) = &summary.primary_model {
+            println!("Primary model:  {model}");
+        }
+        if let Some(duration) = summary.duration {
+            println!("Duration:       {}m", duration.num_minutes());
+        }
+        println!("Estimated cost: {}", summary.cost_string());
+        Ok(())
+    }
+
+    fn export(&mut self, format: ExportFormatArg, file: Option<PathBuf>) -> Result<()> {
+        let index = self.selected()?;
+        let conversation = self.conversation(index)?;
+        let content = super::export::export_to_string(
+            conversation,
+            format,
+            &ExportOptions::default(),
+            false,
+            false,
+            false,
+            false,
+            None,
+        )?;
+        match file {
+            Some(path) => {
+                crate::util::atomic_write(&path, content.as_bytes())?;
+                println!("Wrote {}", path.display());
+            }
+            None => print!("{content}"),
+        }
+        Ok(())
+    }
+
+    /// The selected session, or an error pointing at `use`.
+    fn selected(&self) -> Result<usize> {
+        self.current.ok_or_else(|| SnatchError::InvalidArgument {
+            name: "session".to_string(),
+            reason: "no session selected; `use <#N|SESSION>` first".to_string(),
+        })
+    }
+
+    /// The parsed conversation for a catalog session, parsing it on first use.
+    fn conversation(&mut self, index: usize) -> Result<&Conversation> {
+        let session = &self.catalog[index];
+        match self.parsed.entry(session.session_id().to_string()) {
+            Entry::Occupied(cached) => Ok(cached.into_mut()),
+            Entry::Vacant(slot) => {
+                let entries = session.parse_with_options(self.cli.max_file_size)?;
+                Ok(slot.insert(Conversation::from_entries(entries)?))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Command {
+        Command::parse(line).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(Command::parse("  ").unwrap(), None);
+        assert_eq!(Command::parse("# note").unwrap(), None);
+        assert_eq!(
+            parse("list"),
+            Command::List {
+                limit: 20,
+                text: None
+            }
+        );
+        assert_eq!(
+            parse("ls 5 my app"),
+            Command::List {
+                limit: 5,
+                text: Some("my app".to_string())
+            }
+        );
+        assert_eq!(
+            parse("list webapp"),
+            Command::List {
+                limit: 20,
+                text: Some("webapp".to_string())
+            }
+        );
+        assert_eq!(parse("use #3"), Command::Use("#3".to_string()));
+        assert_eq!(
+            parse("grep -i fn main\\("),
+            Command::Grep {
+                pattern: "fn main\\(".to_string(),
+                ignore_case: true
+            }
+        );
+        assert_eq!(
+            parse("grep -io"),
+            Command::Grep {
+                pattern: "-io".to_string(),
+                ignore_case: false
+            }
+        );
+        assert_eq!(
+            parse("export md out.md"),
+            Command::Export {
+                format: ExportFormatArg::Md,
+                file: Some(PathBuf::from("out.md"))
+            }
+        );
+        assert_eq!(parse("exit"), Command::Quit);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(Command::parse("use").is_err());
+        assert!(Command::parse("grep -i").is_err());
+        assert!(Command::parse("export pdf").is_err());
+        assert!(Command::parse("frobnicate").is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//! Show command implementation.
+//!
+//! Finds a message by UUID (or unique prefix) across sessions and prints it
+//! with its thread context: up to N ancestors along the parent chain and N
+//! levels of replies below it, rendered through the export formats.
+//!
+//! The search index stores provider entry ids rather than native message
+//! UUIDs, so the lookup goes through each session's offset index (kept in
+//! the cache directory), which also walks the thread and reads only the
+//! excerpt's lines. Sessions that cannot be indexed fall back to a
+//! substring scan of the raw file and a full parse. A resumed session
+//! repeats the messages it inherited, so the earliest file holding the UUID
+//! is used.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::cli::{Cli, ExportFormatArg, OutputFormat, ShowArgs};
+use crate::discovery::Session;
+use crate::error::{Result, SnatchError};
+use crate::export::{ExportOptions, SqliteExporter};
+use crate::model::LogEntry;
+use crate::parser::OffsetIndex;
+use crate::reconstruction::Conversation;
+
+use super::get_claude_dir;
+use super::helpers::{offset_index, read_raw_jsonl};
+
+/// A message with its surrounding thread.
+struct Excerpt {
+    entries: Vec<LogEntry>,
+    ancestors: usize,
+    descendants: usize,
+}
+
+/// Run the show command.
+pub fn run(cli: &Cli, args: &ShowArgs) -> Result<()> {
+    let (session, uuid, copies) = locate(cli, args)?;
+    let excerpt = match offset_index(cli, session.path()) {
+        Some(index) => indexed_excerpt(&index, &uuid, args.context)?,
+        None => {
+            let entries = session.parse_with_options(cli.max_file_size)?;
+            excerpt(&Conversation::from_entries(entries)?, &uuid, args.context)
+        }
+    };
+    if !cli.quiet {
+        eprintln!(
+            "Message {uuid} in session {} ({}): {} ancestor{}, {} descendant{}",
+            session.session_id(),
+            session.project_path(),
+            excerpt.ancestors,
+            if excerpt.ancestors == 1 { "" } else { "s" },
+            excerpt.descendants,
+            if excerpt.descendants == 1 { "" } else { "s" }
+        );
+        if copies > 0 {
+            eprintln!(
+                "ℹ Also present in {copies} later session file{} (resumed copies).",
+                if copies == 1 { "" } else { "s" }
+            );
+        }
+    }
+
+    let format = args.format.unwrap_or(match cli.effective_output() {
+        OutputFormat::Json => ExportFormatArg::JsonPretty,
+        _ => ExportFormatArg::Markdown,
+    });
+    let content = match format {
+        ExportFormatArg::Native | ExportFormatArg::Archive => {
+            return Err(SnatchError::InvalidArgument {
+                name: "format".to_string(),
+                reason: "native and archive cover a whole session; use `snatch export` for them"
+                    .to_string(),
+            });
+        }
+        ExportFormatArg::RawJsonl => {
+            // The excerpt's original records, in file order.
+            let keep: HashSet<&str> = excerpt.entries.iter().filter_map(LogEntry::uuid).collect();
+            let mut out = String::new();
+            for record in read_raw_jsonl(session.path())? {
+                if record
+                    .get("uuid")
+                    .and_then(serde_json::Value::as_str)
+                    .is_some_and(|id| keep.contains(id))
+                {
+                    out.push_str(&serde_json::to_string(&record)?);
+                    out.push('\n');
+                }
+            }
+            out
+        }
+        ExportFormatArg::Sqlite => {
+            let path = args.output_file.as_ref().ok_or_else(|| {
+                SnatchError::export("SQLite export requires an output file (--out <path.db>)")
+            })?;
+            let conversation = Conversation::from_entries(excerpt.entries)?;
+            SqliteExporter::new().export_to_file(&conversation, path, &options())?;
+            if !cli.quiet {
+                eprintln!("Wrote {} entries to {}", conversation.len(), path.display());
+            }
+            return Ok(());
+        }
+        _ => {
+            let conversation = Conversation::from_entries(excerpt.entries)?;
+            super::export::export_to_string(
+                &conversation,
+                format,
+                &options(),
+                false,
+                false,
+                false,
+                false,
+                None,
+            )?
+        }
+    };
+
+    match &args.output_file {
+        Some(path) => {
+            crate::util::atomic_write(path, content.as_bytes())?;
+            if !cli.quiet {
+                eprintln!("Wrote excerpt to {}", path.display());
+            }
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+/// Export options for an excerpt. Every entry is kept, since a reply
+/// branch need not be on the session's main thread.
+fn options() -> ExportOptions {
+    ExportOptions {
+        main_thread_only: false,
+        ..ExportOptions::default()
+    }
+}
+
+/// Find the session holding the message, returning it with the full UUID
+/// and the number of later files that repeat it.
+fn locate(cli: &Cli, args: &ShowArgs) -> Result<(Session, String, usize)> {
+    let needle = args.uuid.trim();
+    if needle.len() < 4 {
+        return Err(SnatchError::InvalidArgument {
+            name: "uuid".to_string(),
+            reason: "give at least four characters of the message UUID".to_string(),
+        });
+    }
+    let claude_dir = get_claude_dir(cli)?;
+    let mut sessions = match &args.session {
+        Some(reference) => vec![claude_dir.find_session(reference)?.ok_or_else(|| {
+            SnatchError::SessionNotFound {
+                session_id: reference.clone(),
+            }
+        })?],
+        None => claude_dir
+            .all_sessions()?
+            .into_iter()
+            .filter(|s| {
+                args.project
+                    .as_ref()
+                    .is_none_or(|project| s.project_path().contains(project.as_str()))
+            })
+            .collect(),
+    };
+    sessions.sort_by_key(Session::modified_time);
+
+    // Full UUID -> sessions holding it, in file-age order.
+    let mut found: Vec<(String, Vec<Session>)> = Vec::new();
+    for session in sessions {
+        let ids: HashSet<String> = match offset_index(cli, session.path()) {
+            Some(index) => index.matching_prefix(needle).map(str::to_string).collect(),
+            None => {
+                let Ok(bytes) = crate::parser::read_session(session.path()) else {
+                    continue;
+                };
+                if !String::from_utf8_lossy(&bytes).contains(needle) {
+                    continue;
+                }
+                let Ok(entries) = session.parse_with_options(cli.max_file_size) else {
+                    continue;
+                };
+                entries
+                    .iter()
+                    .filter_map(LogEntry::uuid)
+                    .filter(|id| id.starts_with(needle))
+                    .map(str::to_string)
+                    .collect()
+            }
+        };
+        for id in ids {
+            match found.iter_mut().find(|(uuid, _)| *uuid == id) {
+                Some((_, holders)) => holders.push(session.clone()),
+                None => found.push((id, vec![session.clone()])),
+            }
+        }
+    }
+    if found.iter().any(|(uuid, _)| uuid == needle) {
+        found.retain(|(uuid, _)| uuid == needle);
+    }
+
+    match found.len() {
+        0 => Err(SnatchError::InvalidArgument {
+            name: "uuid".to_string(),
+            reason: format!("no message with UUID '{needle}' found"),
+        }),
+        1 => {
+            let (uuid, mut holders) = found.remove(0);
+            let copies = holders.len() - 1;
+            Ok((holders.remove(0), uuid, copies))
+        }
+        n => Err(SnatchError::InvalidArgument {
+            name: "uuid".to_string(),
+            reason: format!(
+                "'{needle}' matches {n} messages ({}); give more characters",
+                found
+                    .iter()
+                    .take(5)
+                    .map(|(uuid, _)| uuid.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }),
+    }
+}
+
+/// The message, up to `context` ancestors, and replies up to `context`
+/// levels deep, in chronological order.
+fn excerpt(conversation: &Conversation, uuid: &str, context: usize) -> Excerpt {
+    let (keep, ancestors, descendants) = thread(
+        uuid,
+        context,
+        |id| {
+            conversation
+                .parent_of(id)
+                .map(|parent| parent.uuid.as_str())
+        },
+        |id| {
+            conversation
+                .children_of(id)
+                .into_iter()
+                .map(|child| child.uuid.as_str())
+                .collect()
+        },
+    );
+    let entries = conversation
+        .chronological_entries()
+        .into_iter()
+        .filter(|entry| entry.uuid().is_some_and(|id| keep.contains(id)))
+        .cloned()
+        .collect();
+    Excerpt {
+        entries,
+        ancestors,
+        descendants,
+    }
+}
+
+/// [`excerpt`] read through an offset index, without parsing the rest of
+/// the session.
+fn indexed_excerpt(index: &OffsetIndex, uuid: &str, context: usize) -> Result<Excerpt> {
+    let (keep, ancestors, descendants) = thread(
+        uuid,
+        context,
+        |id| {
+            index
+                .parent(id)
+                .filter(|parent| index.offset(parent).is_some())
+        },
+        |id| index.children(id),
+    );
+    let conversation = Conversation::from_entries(index.read_entries(keep)?)?;
+    Ok(Excerpt {
+        entries: conversation
+            .chronological_entries()
+            .into_iter()
+            .cloned()
+            .collect(),
+        ancestors,
+        descendants,
+    })
+}
+
+/// The UUIDs of a message's thread, with the number of ancestors and
+/// descendants among them.
+fn thread<'a>(
+    uuid: &'a str,
+    context: usize,
+    parent: impl Fn(&str) -> Option<&'a str>,
+    children: impl Fn(&str) -> Vec<&'a str>,
+) -> (HashSet<&'a str>, usize, usize) {
+    let mut keep = HashSet::from([uuid]);
+
+    let mut ancestors = 0;
+    let mut current = uuid;
+    while ancestors < context {
+        let Some(id) = parent(current) else {
+            break;
+        };
+        keep.insert(id);
+        current = id;
+        ancestors += 1;
+    }
+
+    let mut descendants = 0;
+    let mut queue = VecDeque::from([(uuid, 0)]);
+    while let Some((node, depth)) = queue.pop_front() {
+        if depth == context {
+            continue;
+        }
+        for child in children(node) {
+            if keep.insert(child) {
+                descendants += 1;
+                queue.push_back((child, depth + 1));
+            }
+        }
+    }
+    (keep, ancestors, descendants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(uuid: &str, parent: Option<&str>, minute: u32) -> LogEntry {
+        serde_json::from_value(json!({
+            "type": "user",
+            "uuid": uuid,
+            "parentUuid": parent,
+            "timestamp": format!("2026-01-01T10:{minute:02}:00Z"),
+            "sessionId": "s",
+            "version": "2.0.0",
+            "message": { "role": "user", "content": format!("message {uuid}") },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_excerpt_takes_ancestors_and_reply_levels() {
+        // a -> b -> c -> d -> e, with a second reply c -> x.
+        let conversation = Conversation::from_entries(vec![
+            message("a", None, 0),
+            message("b", Some("a"), 1),
+            message("c", Some("b"), 2),
+            message("d", Some("c"), 3),
+            message("x", Some("c"), 4),
+            message("e", Some("d"), 5),
+        ])
+        .unwrap();
+
+        let shown = excerpt(&conversation, "c", 1);
+        let ids: Vec<_> = shown.entries.iter().filter_map(LogEntry::uuid).collect();
+        assert_eq!(ids, ["b", "c", "d", "x"]);
+        assert_eq!((shown.ancestors, shown.descendants), (1, 2));
+
+        let shown = excerpt(&conversation, "b", 5);
+        assert_eq!(shown.entries.len(), 6);
+        assert_eq!((shown.ancestors, shown.descendants), (1, 4));
+    }
+}
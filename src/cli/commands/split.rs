@@ -0,0 +1,260 @@
+//! Split command implementation.
+//!
+//! Breaks one session's JSONL into two files at a message UUID or timestamp,
+//! so a long multi-task session can be reviewed in pieces. The first part
+//! keeps the session ID; the second gets a new one, and its entries whose
+//! parent is in the first part become roots, so both files are valid
+//! sessions on their own.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::cli::{Cli, OutputFormat, SplitArgs};
+use crate::error::{Result, SnatchError};
+
+use super::get_claude_dir;
+use super::helpers::{read_raw_jsonl, refuse_qualified_provider_reference, write_raw_jsonl};
+
+/// Where to split: the first entry of the second part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SplitPoint {
+    /// The entry with this UUID, or the only one starting with it.
+    Uuid(String),
+    /// The first entry at or after this time.
+    Time(DateTime<Utc>),
+}
+
+impl SplitPoint {
+    fn parse(text: &str) -> Self {
+        DateTime::parse_from_rfc3339(text).map_or_else(
+            |_| Self::Uuid(text.to_string()),
+            |time| Self::Time(time.with_timezone(&Utc)),
+        )
+    }
+
+    /// Index of the split entry in `entries`.
+    fn locate(&self, entries: &[Value]) -> Result<usize> {
+        let found = match self {
+            Self::Uuid(prefix) => {
+                let uuid = |entry: &Value| entry.get("uuid").and_then(Value::as_str);
+                match entries
+                    .iter()
+                    .position(|e| uuid(e) == Some(prefix.as_str()))
+                {
+                    Some(index) => Some(index),
+                    None => {
+                        let mut matches = entries
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, e)| uuid(e).is_some_and(|id| id.starts_with(prefix)));
+                        let first = matches.next().map(|(index, _)| index);
+                        if matches.next().is_some() {
+                            return Err(SnatchError::InvalidArgument {
+                                name: "at".to_string(),
+                                reason: format!("'{prefix}' matches more than one message"),
+                            });
+                        }
+                        first
+                    }
+                }
+            }
+            Self::Time(at) => entries.iter().position(|entry| {
+                entry
+                    .get("timestamp")
+                    .and_then(Value::as_str)
+                    .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                    .is_some_and(|ts| ts >= *at)
+            }),
+        };
+        found.ok_or_else(|| SnatchError::InvalidArgument {
+            name: "at".to_string(),
+            reason: "no message matches the split point".to_string(),
+        })
+    }
+}
+
+/// The two halves of a split session.
+#[derive(Debug)]
+struct SplitParts {
+    first: Vec<Value>,
+    second: Vec<Value>,
+    /// Entries of the second part whose parent was cut off.
+    new_roots: usize,
+    /// Whether the second part opens with a tool result, separated from its
+    /// tool call.
+    starts_with_tool_result: bool,
+}
+
+/// Split `entries` before the entry at `index`, giving the second part
+/// `new_session_id`.
+fn split_entries(
+    mut entries: Vec<Value>,
+    index: usize,
+    new_session_id: &str,
+) -> Result<SplitParts> {
+    if index == 0 || index >= entries.len() {
+        return Err(SnatchError::InvalidArgument {
+            name: "at".to_string(),
+            reason: "the split point must leave entries on both sides".to_string(),
+        });
+    }
+    let mut second = entries.split_off(index);
+    let first_uuids: HashSet<&str> = entries
+        .iter()
+        .filter_map(|entry| entry.get("uuid").and_then(Value::as_str))
+        .collect();
+
+    let starts_with_tool_result = second[0]
+        .pointer("/message/content")
+        .and_then(Value::as_array)
+        .is_some_and(|blocks| blocks.iter().any(|b| b["type"] == "tool_result"));
+    let mut new_roots = 0;
+    for entry in &mut second {
+        let Some(fields) = entry.as_object_mut() else {
+            continue;
+        };
+        if fields.contains_key("sessionId") {
+            fields.insert(
+                "sessionId".to_string(),
+                Value::String(new_session_id.to_string()),
+            );
+        }
+        let cut_off = fields
+            .get("parentUuid")
+            .and_then(Value::as_str)
+            .is_some_and(|parent| first_uuids.contains(parent));
+        if cut_off {
+            fields.insert("parentUuid".to_string(), Value::Null);
+            new_roots += 1;
+        }
+    }
+
+    Ok(SplitParts {
+        first: entries,
+        second,
+        new_roots,
+        starts_with_tool_result,
+    })
+}
+
+/// Run the split command.
+pub fn run(cli: &Cli, args: &SplitArgs) -> Result<()> {
+    refuse_qualified_provider_reference(
+        cli,
+        &args.session,
+        "split",
+        "split rewrites Claude Code session files; provider-qualified sessions are not supported",
+    )?;
+    let claude_dir = get_claude_dir(cli)?;
+    let session =
+        claude_dir
+            .find_session(&args.session)?
+            .ok_or_else(|| SnatchError::SessionNotFound {
+                session_id: args.session.clone(),
+            })?;
+
+    let entries = read_raw_jsonl(session.path())?;
+    let index = SplitPoint::parse(&args.at).locate(&entries)?;
+    let new_session_id = uuid::Uuid::new_v4().to_string();
+    let parts = split_entries(entries, index, &new_session_id)?;
+
+    let first_path = args
+        .output_dir
+        .join(format!("{}.jsonl", session.session_id()));
+    let second_path = args.output_dir.join(format!("{new_session_id}.jsonl"));
+    write_raw_jsonl(&first_path, &parts.first, args.overwrite)?;
+    write_raw_jsonl(&second_path, &parts.second, args.overwrite)?;
+
+    if parts.starts_with_tool_result && !cli.quiet {
+        eprintln!(
+            "Warning: the second part starts with a tool result whose call is in the first part"
+        );
+    }
+    match cli.effective_output() {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "first": {
+                    "path": first_path,
+                    "session_id": session.session_id(),
+                    "entries": parts.first.len(),
+                },
+                "second": {
+                    "path": second_path,
+                    "session_id": new_session_id,
+                    "entries": parts.second.len(),
+                },
+                "new_roots": parts.new_roots,
+                "starts_with_tool_result": parts.starts_with_tool_result,
+            }))?
+        ),
+        _ if cli.quiet => {}
+        _ => {
+            println!("{} ({} entries)", first_path.display(), parts.first.len());
+            println!(
+                "{} ({} entries, {} new root{})",
+                second_path.display(),
+                parts.second.len(),
+                parts.new_roots,
+                if parts.new_roots == 1 { "" } else { "s" }
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(uuid: &str, parent: Option<&str>, time: &str) -> Value {
+        json!({
+            "uuid": uuid,
+            "parentUuid": parent,
+            "sessionId": "S",
+            "timestamp": format!("2026-01-01T{time}:00Z"),
+        })
+    }
+
+    fn entries() -> Vec<Value> {
+        let mut next_task = entry("b1", Some("a2"), "11:00");
+        next_task["message"] = json!({ "content": [{ "type": "text", "text": "next task" }] });
+        vec![
+            entry("a1", None, "10:00"),
+            entry("a2", Some("a1"), "10:01"),
+            next_task,
+            entry("b2", Some("b1"), "11:01"),
+        ]
+    }
+
+    #[test]
+    fn test_split_point_locates_uuid_prefix_or_time() {
+        let entries = entries();
+        assert_eq!(SplitPoint::parse("b1").locate(&entries).unwrap(), 2);
+        assert_eq!(
+            SplitPoint::parse("2026-01-01T10:30:00Z")
+                .locate(&entries)
+                .unwrap(),
+            2
+        );
+        assert!(SplitPoint::parse("a").locate(&entries).is_err());
+        assert!(SplitPoint::parse("zz").locate(&entries).is_err());
+    }
+
+    #[test]
+    fn test_split_reroots_the_second_part() {
+        let parts = split_entries(entries(), 2, "T").unwrap();
+        assert_eq!(parts.first.len(), 2);
+        assert_eq!(parts.second[0]["parentUuid"], Value::Null);
+        assert_eq!(parts.second[1]["parentUuid"], "b1");
+        assert!(parts.second.iter().all(|e| e["sessionId"] == "T"));
+        assert_eq!(parts.first[1]["sessionId"], "S");
+        assert_eq!(parts.new_roots, 1);
+        assert!(!parts.starts_with_tool_result);
+
+        assert!(split_entries(entries(), 0, "T").is_err());
+    }
+}
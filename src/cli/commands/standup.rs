@@ -583,7 +583,7 @@ pub fn run(cli: &Cli, args: &StandupArgs) -> Result<()> {
     if !args.provider.is_empty() {
         return run_provider(cli, args);
     }
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Parse period
     let duration = parse_period(&args.period)?;
@@ -793,16 +793,14 @@ pub fn run(cli: &Cli, args: &StandupArgs) -> Result<()> {
 
     // Handle clipboard
     if args.clipboard {
-        match arboard::Clipboard::new() {
-            Ok(mut clipboard) => {
-                if let Err(e) = clipboard.set_text(&output) {
-                    eprintln!("Warning: Failed to copy to clipboard: {e}");
-                } else if !cli.quiet {
-                    eprintln!("Copied to clipboard.");
+        match crate::util::clipboard::copy(&output) {
+            Ok(method) => {
+                if !cli.quiet {
+                    eprintln!("Copied to {}.", method.describe());
                 }
             }
             Err(e) => {
-                eprintln!("Warning: Failed to access clipboard: {e}");
+                eprintln!("Warning: Failed to copy to clipboard: {e}");
             }
         }
     }
@@ -1050,15 +1048,13 @@ fn run_provider(cli: &Cli, args: &StandupArgs) -> Result<()> {
     };
     let output = format_provider_report(&report, effective_format)?;
     if args.clipboard {
-        match arboard::Clipboard::new() {
-            Ok(mut clipboard) => {
-                if let Err(error) = clipboard.set_text(&output) {
-                    eprintln!("Warning: Failed to copy to clipboard: {error}");
-                } else if !cli.quiet {
-                    eprintln!("Copied to clipboard.");
+        match crate::util::clipboard::copy(&output) {
+            Ok(method) => {
+                if !cli.quiet {
+                    eprintln!("Copied to {}.", method.describe());
                 }
             }
-            Err(error) => eprintln!("Warning: Failed to access clipboard: {error}"),
+            Err(error) => eprintln!("Warning: Failed to copy to clipboard: {error}"),
         }
     }
     println!("{output}");
@@ -4,21 +4,37 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike, Utc};
+use indexmap::IndexMap;
 use rayon::prelude::*;
 
 use crate::analytics::history::{CostDataPoint, CostHistory};
-use crate::analytics::{ProjectAnalytics, SessionAnalytics};
-use crate::cli::{Cli, OutputFormat, StatsArgs};
-use crate::config::Config;
-use crate::discovery::{format_count, format_number, Session};
+use crate::analytics::prometheus;
+use crate::analytics::snapshot::AnalyticsStore;
+use crate::analytics::{
+    rank_languages, AbandonedBranchStats, ActivityHeatmap, ActivityStreaks, AgentBreakdown,
+    AgentUsage, CoModificationGraph, EditChurn, ErrorAnalytics, FileChurn, GitBranchStats,
+    HeatmapMetric, LanguageStats, LinearFit, ModelSwitchStats, ProjectAnalytics, PromptCorrelation,
+    RollingUsage, SessionAnalytics, ThinkingStats, TokenTimeline, ToolChainStats, TurnTokens,
+};
+use crate::cli::{Cli, LeaderboardMetric, OutputFormat, StatsArgs, StatsFormat, TableArgs};
+use crate::config::CacheConfig;
+use crate::discovery::{format_count, format_number, ClaudeDirectory, Session};
 use crate::error::{Result, SnatchError};
 use crate::model::usage::{AggregatedUsage, Usage};
 use crate::model::{ContentBlock, LogEntry};
 use crate::reconstruction::Conversation;
-use crate::util::{sparkline_u64, sparkline_with_range};
+use crate::tags::TagStore;
+use crate::util::{sparkline_u64, sparkline_with_range, truncate_path};
 
 use super::get_claude_dir;
+use super::table::{
+    Table, STATS_AGENT_COLUMNS, STATS_BLOCK_COLUMNS, STATS_BRANCH_COLUMNS, STATS_BUDGET_COLUMNS,
+    STATS_CHURN_COLUMNS, STATS_CORRELATION_COLUMNS, STATS_ERROR_COLUMNS, STATS_GIT_BRANCH_COLUMNS,
+    STATS_GRAPH_COLUMNS, STATS_HEATMAP_COLUMNS, STATS_HISTORY_COLUMNS, STATS_LANGUAGE_COLUMNS,
+    STATS_MONTHLY_COLUMNS, STATS_OVERVIEW_COLUMNS, STATS_ROLLING_COLUMNS, STATS_SUMMARY_COLUMNS,
+    STATS_TIMELINE_COLUMNS, STATS_TOOL_CHAIN_COLUMNS, STATS_TURN_COLUMNS, STATS_WEEKLY_COLUMNS,
+};
 
 /// The duration of a billing window (5 hours).
 const BILLING_WINDOW_HOURS: i64 = 5;
@@ -244,10 +260,12 @@ fn format_model_name(model: &str) -> String {
     }
 }
 
-/// Compute statistics in parallel across multiple sessions.
-fn compute_stats_parallel(sessions: &[Session], max_file_size: Option<u64>) -> ProjectAnalytics {
-    // Process sessions in parallel and collect individual analytics
-    let session_analytics: Vec<SessionAnalytics> = sessions
+/// Parse and analyze sessions in parallel, skipping any that fail to parse.
+fn analyze_sessions_parallel<'a>(
+    sessions: &'a [Session],
+    max_file_size: Option<u64>,
+) -> Vec<(&'a Session, SessionAnalytics)> {
+    sessions
         .par_iter()
         .filter_map(|session| {
             session
@@ -256,20 +274,217 @@ fn compute_stats_parallel(sessions: &[Session], max_file_size: Option<u64>) -> P
                 .and_then(|entries| {
                     Conversation::from_entries(entries)
                         .ok()
-                        .map(|conv| SessionAnalytics::from_conversation(&conv))
+                        .map(|conv| (session, SessionAnalytics::from_conversation(&conv)))
                 })
         })
-        .collect();
+        .collect()
+}
 
+/// Compute statistics in parallel across multiple sessions.
+fn compute_stats_parallel(sessions: &[Session], max_file_size: Option<u64>) -> ProjectAnalytics {
     // Merge all analytics into a single ProjectAnalytics
     let mut combined = ProjectAnalytics::default();
-    for analytics in session_analytics {
+    for (_, analytics) in analyze_sessions_parallel(sessions, max_file_size) {
         combined.add_session(&analytics);
     }
     combined.calculate_cost();
     combined
 }
 
+/// Compute combined statistics plus a per-project breakdown in one pass.
+pub(super) fn compute_stats_by_project(
+    sessions: &[Session],
+    max_file_size: Option<u64>,
+) -> (ProjectAnalytics, IndexMap<String, ProjectAnalytics>) {
+    let mut combined = ProjectAnalytics::default();
+    let mut by_project: IndexMap<String, ProjectAnalytics> = IndexMap::new();
+    for (session, analytics) in analyze_sessions_parallel(sessions, max_file_size) {
+        combined.add_session(&analytics);
+        by_project
+            .entry(session.project_path().to_string())
+            .or_default()
+            .add_session(&analytics);
+    }
+    combined.calculate_cost();
+    for project in by_project.values_mut() {
+        project.calculate_cost();
+    }
+    (combined, by_project)
+}
+
+/// Global statistics plus a per-project breakdown, reusing persisted
+/// per-session snapshots for transcripts unchanged since the last run.
+///
+/// The snapshot store is a cache: it is skipped when `cache.enabled` is off,
+/// rewritten only when a snapshot was added or dropped, and failing to read
+/// or write it falls back to a full recompute rather than failing the command.
+fn compute_stats_incremental(
+    sessions: &[Session],
+    cache: &CacheConfig,
+    max_file_size: Option<u64>,
+    rebuild: bool,
+) -> (ProjectAnalytics, IndexMap<String, ProjectAnalytics>) {
+    let path = if cache.enabled {
+        AnalyticsStore::path_for(cache).ok()
+    } else {
+        None
+    };
+    let mut store = match &path {
+        Some(path) if !rebuild => AnalyticsStore::load_from(path).unwrap_or_else(|e| {
+            tracing::debug!("Discarding analytics snapshots: {e}");
+            AnalyticsStore::new()
+        }),
+        _ => AnalyticsStore::new(),
+    };
+    let loaded = store.len();
+
+    let mut combined = ProjectAnalytics::default();
+    let mut by_project: IndexMap<String, ProjectAnalytics> = IndexMap::new();
+    let mut changed = Vec::new();
+    for session in sessions {
+        match store.get(session) {
+            Some(snapshot) => {
+                combined.merge(snapshot);
+                by_project
+                    .entry(session.project_path().to_string())
+                    .or_default()
+                    .merge(snapshot);
+            }
+            None => changed.push(session.clone()),
+        }
+    }
+
+    for (session, analytics) in analyze_sessions_parallel(&changed, max_file_size) {
+        let mut snapshot = ProjectAnalytics::default();
+        snapshot.add_session(&analytics);
+        combined.merge(&snapshot);
+        by_project
+            .entry(session.project_path().to_string())
+            .or_default()
+            .merge(&snapshot);
+        store.insert(session, snapshot);
+    }
+    tracing::debug!(
+        reused = store.hits(),
+        recomputed = changed.len(),
+        "Global stats snapshots"
+    );
+
+    if let Some(path) = path {
+        store.retain_sessions(sessions);
+        // Rewrite only if a snapshot was added or dropped.
+        if !changed.is_empty() || store.len() != loaded {
+            if let Err(e) = store.save_to(&path) {
+                tracing::debug!("Failed to save analytics snapshots: {e}");
+            }
+        }
+    }
+
+    combined.calculate_cost();
+    for project in by_project.values_mut() {
+        project.calculate_cost();
+    }
+    (combined, by_project)
+}
+
+/// One ranked row of the global stats project leaderboard.
+#[derive(Debug, serde::Serialize)]
+struct LeaderboardEntry {
+    rank: usize,
+    project: String,
+    sessions: usize,
+    total_tokens: u64,
+    estimated_cost: Option<f64>,
+    file_modifications: usize,
+}
+
+/// Rank projects by `metric`, highest first; ties fall back to tokens, then name.
+fn project_leaderboard(
+    by_project: &IndexMap<String, ProjectAnalytics>,
+    metric: LeaderboardMetric,
+) -> Vec<LeaderboardEntry> {
+    let mut entries: Vec<LeaderboardEntry> = by_project
+        .iter()
+        .map(|(project, analytics)| LeaderboardEntry {
+            rank: 0,
+            project: project.clone(),
+            sessions: analytics.session_count,
+            total_tokens: analytics.total_usage.usage.work_tokens(),
+            estimated_cost: analytics.total_usage.estimated_cost,
+            file_modifications: analytics.file_modifications,
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        let primary = match metric {
+            LeaderboardMetric::Tokens => b.total_tokens.cmp(&a.total_tokens),
+            LeaderboardMetric::Cost => b
+                .estimated_cost
+                .unwrap_or(0.0)
+                .total_cmp(&a.estimated_cost.unwrap_or(0.0)),
+            LeaderboardMetric::Sessions => b.sessions.cmp(&a.sessions),
+            LeaderboardMetric::Files => b.file_modifications.cmp(&a.file_modifications),
+        };
+        primary
+            .then_with(|| b.total_tokens.cmp(&a.total_tokens))
+            .then_with(|| a.project.cmp(&b.project))
+    });
+    for (index, entry) in entries.iter_mut().enumerate() {
+        entry.rank = index + 1;
+    }
+    entries
+}
+
+/// Resolve the sessions selected by `--session`, `--project`, or neither (all),
+/// narrowed to tagged sessions by `--tag`/`--tags`.
+fn scoped_sessions(claude_dir: &ClaudeDirectory, args: &StatsArgs) -> Result<Vec<Session>> {
+    let sessions = if let Some(session_id) = &args.session {
+        // Specific session
+        let session =
+            claude_dir
+                .find_session(session_id)?
+                .ok_or_else(|| SnatchError::SessionNotFound {
+                    session_id: session_id.clone(),
+                })?;
+        vec![session]
+    } else if let Some(project_filters) = &args.project {
+        // Specific project(s)
+        let projects = claude_dir.projects()?;
+        let mut matching_projects = Vec::new();
+        for filter in project_filters {
+            matching_projects.extend(super::helpers::filter_projects(projects.clone(), filter));
+        }
+
+        if matching_projects.is_empty() {
+            return Err(SnatchError::ProjectNotFound {
+                project_path: project_filters.join(", "),
+            });
+        }
+
+        let mut all_sessions = Vec::new();
+        for project in matching_projects {
+            all_sessions.extend(project.sessions()?);
+        }
+        all_sessions
+    } else {
+        // All sessions
+        claude_dir.all_sessions()?
+    };
+    retain_tagged(args, sessions)
+}
+
+/// Keep only sessions matching `--tag`/`--tags`, if given.
+fn retain_tagged(args: &StatsArgs, mut sessions: Vec<Session>) -> Result<Vec<Session>> {
+    let tag_filters = super::helpers::tag_filters(args.tag.as_deref(), args.tags.as_deref());
+    if args.session.is_none() && !tag_filters.is_empty() {
+        let tag_store = TagStore::load()?;
+        sessions.retain(|s| {
+            super::helpers::session_has_any_tag(&tag_store, s.session_id(), &tag_filters)
+        });
+    }
+    Ok(sessions)
+}
+
 /// Run the stats command.
 pub fn run(cli: &Cli, args: &StatsArgs) -> Result<()> {
     let provider_route = !args.provider.is_empty()
@@ -280,7 +495,7 @@ pub fn run(cli: &Cli, args: &StatsArgs) -> Result<()> {
     if provider_route {
         return run_provider_session(cli, args);
     }
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Handle cost history clear
     if args.clear_history {
@@ -345,6 +560,20 @@ pub fn run(cli: &Cli, args: &StatsArgs) -> Result<()> {
         return Ok(());
     }
 
+    // Handle Prometheus exposition
+    if args.format == Some(StatsFormat::Prometheus) {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        let (combined, by_project) = compute_stats_by_project(&sessions, cli.max_file_size);
+        print!("{}", prometheus::render(&combined, &by_project));
+        return Ok(());
+    }
+    if args.format == Some(StatsFormat::Dot) && !args.co_modification {
+        return Err(SnatchError::InvalidArgument {
+            name: "--format".to_string(),
+            reason: "dot output requires --co-modification".to_string(),
+        });
+    }
+
     // Handle cost history display
     if args.history || args.weekly || args.monthly {
         return output_cost_history(cli, args);
@@ -352,6 +581,10 @@ pub fn run(cli: &Cli, args: &StatsArgs) -> Result<()> {
 
     // Handle timeline visualization
     if args.timeline {
+        if args.session.is_some() {
+            let sessions = scoped_sessions(&claude_dir, args)?;
+            return output_turn_timeline(cli, args, &sessions[0]);
+        }
         let sessions = claude_dir.all_sessions()?;
         return output_timeline(cli, args, &sessions);
     }
@@ -362,40 +595,86 @@ pub fn run(cli: &Cli, args: &StatsArgs) -> Result<()> {
         return output_token_graph(cli, args, &sessions);
     }
 
-    // Handle billing blocks mode
-    if args.blocks {
-        let sessions: Vec<Session> = if let Some(session_id) = &args.session {
-            // Blocks for specific session
-            let session = claude_dir.find_session(session_id)?.ok_or_else(|| {
-                SnatchError::SessionNotFound {
-                    session_id: session_id.clone(),
-                }
-            })?;
-            vec![session]
-        } else if let Some(project_filters) = &args.project {
-            // Blocks for specific project(s)
-            let projects = claude_dir.projects()?;
-            let mut matching_projects = Vec::new();
-            for filter in project_filters {
-                matching_projects.extend(super::helpers::filter_projects(projects.clone(), filter));
-            }
+    // Handle activity heatmap
+    if args.heatmap {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_heatmap(cli, args, &sessions);
+    }
 
-            if matching_projects.is_empty() {
-                return Err(SnatchError::ProjectNotFound {
-                    project_path: project_filters.join(", "),
-                });
-            }
+    // Handle parent vs subagent attribution
+    if args.agents {
+        let mut sessions = scoped_sessions(&claude_dir, args)?;
+        if args.session.is_some() {
+            // A session scope covers the subagents it spawned.
+            let links: Vec<_> = sessions.iter().flat_map(Session::subagent_links).collect();
+            let project_path = sessions[0].project_path().to_string();
+            sessions.extend(
+                links
+                    .iter()
+                    .filter_map(|link| Session::from_path(&link.path, &project_path).ok()),
+            );
+        }
+        return output_agent_stats(cli, args, &sessions);
+    }
 
-            let mut all_sessions = Vec::new();
-            for project in matching_projects {
-                all_sessions.extend(project.sessions()?);
-            }
-            all_sessions
-        } else {
-            // Global blocks across all sessions
-            claude_dir.all_sessions()?
-        };
+    // Handle per-language line changes
+    if args.languages {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        let (combined, by_project) = compute_stats_by_project(&sessions, cli.max_file_size);
+        return output_language_stats(cli, args, &combined, &by_project);
+    }
+
+    // Handle prompt length vs response correlation
+    if args.correlation {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_prompt_correlation(cli, args, &sessions);
+    }
+
+    // Handle tool call sequence analysis
+    if args.tool_chains {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_tool_chains(cli, args, &sessions);
+    }
 
+    // Handle rolling-window usage
+    if args.rolling {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_rolling_usage(cli, args, &sessions);
+    }
+
+    // Handle per-git-branch usage
+    if args.by_branch {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_git_branch_stats(cli, args, &sessions);
+    }
+
+    // Handle API error and retry analysis
+    if args.errors {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_error_stats(cli, args, &sessions);
+    }
+
+    // Handle edit churn analysis
+    if args.churn {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_churn_stats(cli, args, &sessions);
+    }
+
+    // Handle file co-modification graph
+    if args.co_modification {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_co_modification(cli, args, &sessions);
+    }
+
+    // Handle abandoned branch analysis
+    if args.branches {
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        return output_branch_stats(cli, args, &sessions);
+    }
+
+    // Handle billing blocks mode
+    if args.blocks {
+        let sessions = scoped_sessions(&claude_dir, args)?;
         return output_blocks_stats(cli, args, &sessions);
     }
 
@@ -413,6 +692,17 @@ pub fn run(cli: &Cli, args: &StatsArgs) -> Result<()> {
         let analytics = SessionAnalytics::from_conversation(&conversation);
 
         output_session_stats(cli, args, &analytics, None)?;
+    } else if args.tag.is_some() || args.tags.is_some() {
+        // Stats for tagged sessions, within --project if given
+        let sessions = scoped_sessions(&claude_dir, args)?;
+        let tag_filters = super::helpers::tag_filters(args.tag.as_deref(), args.tags.as_deref());
+        let analytics = compute_stats_parallel(&sessions, cli.max_file_size);
+        output_project_stats(
+            cli,
+            args,
+            &analytics,
+            &format!("tag:{}", tag_filters.join(",")),
+        )?;
     } else if let Some(project_filters) = &args.project {
         // Stats for specific project(s)
         let projects = claude_dir.projects()?;
@@ -451,18 +741,24 @@ pub fn run(cli: &Cli, args: &StatsArgs) -> Result<()> {
         // Also show global stats when --models, --costs, or --all is specified without a scope,
         // since these flags require computing full statistics to be useful.
         let all_sessions = claude_dir.all_sessions()?;
-        let global_analytics = compute_stats_parallel(&all_sessions, cli.max_file_size);
+        let (global_analytics, by_project) = compute_stats_incremental(
+            &all_sessions,
+            &super::index::load_config(cli).cache,
+            cli.max_file_size,
+            args.rebuild_snapshots,
+        );
+        let leaderboard = project_leaderboard(&by_project, args.rank_by);
 
-        output_global_stats(cli, args, &global_analytics)?;
+        output_global_stats(cli, args, &global_analytics, &leaderboard)?;
 
         // Show budget status if configured
-        output_budget_status(cli)?;
+        output_budget_status(cli, args)?;
     } else {
         // Default: show summary of all projects
         output_overview(cli, args, &claude_dir)?;
 
         // Show budget status if configured
-        output_budget_status(cli)?;
+        output_budget_status(cli, args)?;
     }
 
     Ok(())
@@ -481,6 +777,8 @@ fn run_provider_session(cli: &Cli, args: &StatsArgs) -> Result<()> {
         session,
         provider: _,
         project,
+        tag,
+        tags,
         global,
         tools: _,
         models: _,
@@ -500,11 +798,29 @@ fn run_provider_session(cli: &Cli, args: &StatsArgs) -> Result<()> {
         granularity: _,
         graph,
         graph_width: _,
+        rank_by: _,
+        heatmap,
+        agents,
+        languages,
+        correlation,
+        tool_chains,
+        branches,
+        rolling,
+        by_branch,
+        windows: _,
+        errors,
+        churn,
+        co_modification,
+        format,
+        rebuild_snapshots,
+        table: _,
     } = args;
     super::helpers::refuse_unsupported_flags(
         "provider-routed session stats",
         &[
             ("--project", project.is_some()),
+            ("--tag", tag.is_some()),
+            ("--tags", tags.is_some()),
             ("--global", *global),
             ("--costs", *costs),
             ("--blocks", *blocks),
@@ -518,6 +834,19 @@ fn run_provider_session(cli: &Cli, args: &StatsArgs) -> Result<()> {
             ("--clear-history", *clear_history),
             ("--timeline", *timeline),
             ("--graph", *graph),
+            ("--heatmap", *heatmap),
+            ("--agents", *agents),
+            ("--languages", *languages),
+            ("--correlation", *correlation),
+            ("--tool-chains", *tool_chains),
+            ("--branches", *branches),
+            ("--rolling", *rolling),
+            ("--by-branch", *by_branch),
+            ("--errors", *errors),
+            ("--churn", *churn),
+            ("--co-modification", *co_modification),
+            ("--format", format.is_some()),
+            ("--rebuild-snapshots", *rebuild_snapshots),
         ],
     )?;
     let reference = session.as_deref().ok_or_else(|| SnatchError::InvalidArgument {
@@ -544,6 +873,89 @@ fn run_provider_session(cli: &Cli, args: &StatsArgs) -> Result<()> {
     output_session_stats(cli, args, &analytics, Some(&context))
 }
 
+/// Values for one [`STATS_SUMMARY_COLUMNS`] row.
+struct SummaryRow<'a> {
+    scope: String,
+    projects: Option<usize>,
+    sessions: usize,
+    messages: usize,
+    usage: &'a Usage,
+    tool_invocations: usize,
+    estimated_cost: Option<f64>,
+    provider: Option<&'a ProviderSessionStats>,
+    streaks: Option<&'a ActivityStreaks>,
+}
+
+impl<'a> SummaryRow<'a> {
+    fn from_project(
+        scope: String,
+        projects: Option<usize>,
+        analytics: &'a ProjectAnalytics,
+    ) -> Self {
+        Self {
+            scope,
+            projects,
+            sessions: analytics.session_count,
+            messages: analytics.message_counts.conversation(),
+            usage: &analytics.total_usage.usage,
+            tool_invocations: analytics.message_counts.tool_uses,
+            estimated_cost: analytics.total_usage.estimated_cost,
+            provider: None,
+            streaks: None,
+        }
+    }
+
+    fn print(&self, format: OutputFormat, args: &TableArgs) -> Result<()> {
+        let table = Table::with_all(format, STATS_SUMMARY_COLUMNS, args)?;
+        let mut stdout = std::io::stdout().lock();
+        table.write_header(&mut stdout)?;
+        table.write_row(
+            &mut stdout,
+            &[
+                self.scope.clone(),
+                self.projects.map(|n| n.to_string()).unwrap_or_default(),
+                self.sessions.to_string(),
+                self.messages.to_string(),
+                self.usage.input_tokens.to_string(),
+                self.usage.output_tokens.to_string(),
+                self.usage.cache_read_input_tokens.unwrap_or(0).to_string(),
+                self.usage
+                    .cache_creation_input_tokens
+                    .unwrap_or(0)
+                    .to_string(),
+                self.usage.work_tokens().to_string(),
+                self.usage.total_tokens().to_string(),
+                self.tool_invocations.to_string(),
+                format!("{:.2}", self.usage.cache_hit_rate()),
+                self.estimated_cost
+                    .map(|cost| format!("{cost:.4}"))
+                    .unwrap_or_default(),
+                self.provider
+                    .map(|context| context.provider.clone())
+                    .unwrap_or_default(),
+                self.provider
+                    .map(|context| context.qualified_id.clone())
+                    .unwrap_or_default(),
+                self.provider
+                    .map(|context| context.usage.pricing.policy.to_string())
+                    .unwrap_or_default(),
+                self.streaks
+                    .map(|streaks| streaks.current_streak.to_string())
+                    .unwrap_or_default(),
+                self.streaks
+                    .map(|streaks| streaks.longest_streak.to_string())
+                    .unwrap_or_default(),
+                self.streaks
+                    .map(|streaks| streaks.active_days.to_string())
+                    .unwrap_or_default(),
+                self.streaks
+                    .map(|streaks| format!("{:.2}", streaks.avg_daily_sessions))
+                    .unwrap_or_default(),
+            ],
+        )
+    }
+}
+
 /// Output session statistics.
 fn output_session_stats(
     cli: &Cli,
@@ -563,25 +975,19 @@ fn output_session_stats(
                 serde_json::to_string_pretty(&StatsOutput::from_session(analytics, provider))?
             );
         }
-        OutputFormat::Tsv => {
-            println!("metric\tvalue");
-            println!("total_tokens\t{}", summary.total_tokens);
-            println!("input_tokens\t{}", summary.input_tokens);
-            println!("output_tokens\t{}", summary.output_tokens);
-            println!("cache_read_tokens\t{}", summary.cache_read_tokens);
-            println!("cache_creation_tokens\t{}", summary.cache_creation_tokens);
-            println!("total_processed_tokens\t{}", summary.total_processed_tokens);
-            println!("messages\t{}", summary.total_messages);
-            println!("tool_invocations\t{}", summary.tool_invocations);
-            println!("cache_hit_rate\t{:.2}", summary.cache_hit_rate);
-            if let Some(cost) = estimated_cost {
-                println!("estimated_cost\t{cost:.4}");
-            }
-            if let Some(context) = provider {
-                println!("provider\t{}", context.provider);
-                println!("qualified_id\t{}", context.qualified_id);
-                println!("pricing_policy\t{}", context.usage.pricing.policy);
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            SummaryRow {
+                scope: "session".to_string(),
+                projects: Some(1),
+                sessions: 1,
+                messages: summary.total_messages,
+                usage: &analytics.usage.usage,
+                tool_invocations: summary.tool_invocations,
+                estimated_cost,
+                provider,
+                streaks: None,
             }
+            .print(format, &args.table)?;
         }
         OutputFormat::Compact => {
             println!(
@@ -619,6 +1025,7 @@ fn output_session_stats(
                     let display_name = format_model_name(model);
                     println!("  {display_name}: {} uses", format_number(*count as u64));
                 }
+                print_model_switches(&analytics.model_switches);
             }
             println!();
 
@@ -689,13 +1096,33 @@ fn output_session_stats(
             }
 
             // Thinking
-            if summary.thinking_blocks > 0 {
+            let thinking = &analytics.thinking_stats;
+            if summary.thinking_blocks > 0 || thinking.reported_tokens > 0 {
                 println!("Thinking:");
                 println!("  Blocks: {}", format_count(summary.thinking_blocks));
                 println!(
                     "  Avg Block Length: {} chars",
-                    format_count(analytics.thinking_stats.average_length())
+                    format_count(thinking.average_length())
                 );
+                if let Some(ratio) = thinking.token_ratio() {
+                    println!(
+                        "  Tokens: {} ({ratio:.1}% of output)",
+                        format_number(thinking.reported_tokens)
+                    );
+                    if let Some(cost) = thinking.estimated_cost() {
+                        println!("  Thinking Cost: ${cost:.4}");
+                    }
+                    if args.models || args.all {
+                        for (model, usage) in &thinking.by_model {
+                            println!(
+                                "    {}: {} tokens ({:.1}% of output)",
+                                format_model_name(model),
+                                format_number(usage.thinking_tokens),
+                                usage.ratio()
+                            );
+                        }
+                    }
+                }
                 println!();
             }
 
@@ -704,6 +1131,9 @@ fn output_session_stats(
                 .map(|cost| format!("${cost:.4}"))
                 .unwrap_or_else(|| "N/A".into());
             println!("Estimated Cost: {cost}");
+            if provider.is_none() {
+                print_cache_savings(&analytics.usage);
+            }
             if let Some(context) = provider {
                 println!("Provider: {}", context.provider);
                 println!("Qualified ID: {}", context.qualified_id);
@@ -739,6 +1169,65 @@ fn output_session_stats(
     Ok(())
 }
 
+/// Print what prompt caching saved, when any priced model read from cache.
+fn print_cache_savings(usage: &AggregatedUsage) {
+    if let Some((savings, net)) = usage.cache_savings() {
+        if savings > 0.0 {
+            println!(
+                "Cache Savings: ${savings:.2} on cache reads (${net:.2} net of cache-write premium)"
+            );
+        }
+    }
+}
+
+/// Print mid-conversation model switches under the model breakdown.
+fn print_model_switches(stats: &ModelSwitchStats) {
+    if stats.switches == 0 {
+        return;
+    }
+    println!(
+        "  Model Switches: {} in {} session(s)",
+        format_count(stats.switches),
+        format_count(stats.sessions_switched)
+    );
+    for (from, to, count) in stats.ranked_transitions() {
+        println!(
+            "    {} -> {}: {}",
+            format_model_name(from),
+            format_model_name(to),
+            format_count(count)
+        );
+    }
+    for (label, usage) in [
+        ("Before switch", &stats.before_switch),
+        ("After switch", &stats.after_switch),
+    ] {
+        let cost = usage
+            .estimated_cost
+            .map(|cost| format!(" (${cost:.4})"))
+            .unwrap_or_default();
+        println!(
+            "    {label}: {} tokens{cost}",
+            format_number(usage.usage.work_tokens())
+        );
+    }
+}
+
+/// Print the reported extended-thinking share, when usage payloads carry it.
+fn print_thinking_usage(stats: &ThinkingStats) {
+    let Some(ratio) = stats.token_ratio() else {
+        return;
+    };
+    let cost = stats
+        .estimated_cost()
+        .map(|cost| format!(", ${cost:.2} at output rates"))
+        .unwrap_or_default();
+    println!(
+        "Extended Thinking: {} tokens ({ratio:.1}% of output{cost})",
+        format_number(stats.reported_tokens)
+    );
+}
+
 /// Output project statistics.
 fn print_pricing_context(usage: &AggregatedUsage) {
     if !usage.pricing_rate_cards.is_empty() {
@@ -777,14 +1266,9 @@ fn output_project_stats(
                 serde_json::to_string_pretty(&StatsOutput::from_project(analytics, project_path))?
             );
         }
-        OutputFormat::Tsv => {
-            println!("metric\tvalue");
-            println!("sessions\t{}", analytics.session_count);
-            println!(
-                "total_tokens\t{}",
-                analytics.total_usage.usage.work_tokens()
-            );
-            println!("tool_invocations\t{}", analytics.message_counts.tool_uses);
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            SummaryRow::from_project(project_path.to_string(), Some(1), analytics)
+                .print(format, &args.table)?;
         }
         OutputFormat::Compact => {
             println!(
@@ -850,6 +1334,7 @@ fn output_project_stats(
                     let display_name = format_model_name(model);
                     println!("  {display_name}: {} uses", format_number(*count));
                 }
+                print_model_switches(&analytics.model_switches);
                 println!();
             }
 
@@ -867,6 +1352,9 @@ fn output_project_stats(
                         println!("    Cache Write: ${:.4}", cost.cache_write_cost);
                         println!("    Cache Read:  ${:.4}", cost.cache_read_cost);
                         println!("    Server Tools: ${:.4}", cost.server_tool_cost);
+                        if cost.cache_read_savings > 0.0 {
+                            println!("    Cache Saved: ${:.4}", cost.cache_read_savings);
+                        }
                         println!("    Subtotal:    ${:.4}", cost.total_cost);
                     }
                 }
@@ -876,7 +1364,9 @@ fn output_project_stats(
                 // Just show total cost without breakdown
                 println!("Estimated API List Cost: ${cost:.2}");
             }
+            print_cache_savings(&analytics.total_usage);
             print_pricing_context(&analytics.total_usage);
+            print_thinking_usage(&analytics.thinking_stats);
         }
     }
 
@@ -902,18 +1392,13 @@ fn output_multi_project_stats(
             }
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!("metric\tvalue");
-            println!("project_count\t{}", project_names.len());
-            println!("sessions\t{}", analytics.session_count);
-            println!(
-                "total_tokens\t{}",
-                analytics.total_usage.usage.work_tokens()
-            );
-            println!("tool_invocations\t{}", analytics.message_counts.tool_uses);
-            if let Some(cost) = analytics.total_usage.estimated_cost {
-                println!("estimated_cost\t{cost:.4}");
-            }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            SummaryRow::from_project(
+                project_names.join(";"),
+                Some(project_names.len()),
+                analytics,
+            )
+            .print(format, &args.table)?;
         }
         OutputFormat::Compact => {
             let cost = analytics
@@ -999,6 +1484,7 @@ fn output_multi_project_stats(
                     let display_name = format_model_name(model);
                     println!("  {display_name}: {} uses", format_number(*count));
                 }
+                print_model_switches(&analytics.model_switches);
                 println!();
             }
 
@@ -1016,6 +1502,9 @@ fn output_multi_project_stats(
                         println!("    Cache Write: ${:.4}", cost.cache_write_cost);
                         println!("    Cache Read:  ${:.4}", cost.cache_read_cost);
                         println!("    Server Tools: ${:.4}", cost.server_tool_cost);
+                        if cost.cache_read_savings > 0.0 {
+                            println!("    Cache Saved: ${:.4}", cost.cache_read_savings);
+                        }
                         println!("    Subtotal:    ${:.4}", cost.total_cost);
                     }
                 }
@@ -1025,7 +1514,9 @@ fn output_multi_project_stats(
                 // Just show total cost without breakdown
                 println!("Estimated API List Cost: ${cost:.2}");
             }
+            print_cache_savings(&analytics.total_usage);
             print_pricing_context(&analytics.total_usage);
+            print_thinking_usage(&analytics.thinking_stats);
         }
     }
 
@@ -1033,24 +1524,25 @@ fn output_multi_project_stats(
 }
 
 /// Output global statistics.
-fn output_global_stats(cli: &Cli, args: &StatsArgs, analytics: &ProjectAnalytics) -> Result<()> {
+fn output_global_stats(
+    cli: &Cli,
+    args: &StatsArgs,
+    analytics: &ProjectAnalytics,
+    leaderboard: &[LeaderboardEntry],
+) -> Result<()> {
+    let streaks = analytics.streaks();
+
     match cli.effective_output() {
         OutputFormat::Json => {
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&StatsOutput::from_project(analytics, "global"))?
-            );
+            let mut output = StatsOutput::from_project(analytics, "global");
+            output.streaks = Some(StreaksOutput::from(&streaks));
+            output.projects = Some(leaderboard);
+            println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!("metric\tvalue");
-            println!("sessions\t{}", analytics.session_count);
-            println!(
-                "total_tokens\t{}",
-                analytics.total_usage.usage.work_tokens()
-            );
-            if let Some(cost) = analytics.total_usage.estimated_cost {
-                println!("estimated_cost\t{cost:.4}");
-            }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let mut row = SummaryRow::from_project("global".to_string(), None, analytics);
+            row.streaks = Some(&streaks);
+            row.print(format, &args.table)?;
         }
         OutputFormat::Compact => {
             let cost = analytics
@@ -1059,10 +1551,11 @@ fn output_global_stats(cli: &Cli, args: &StatsArgs, analytics: &ProjectAnalytics
                 .map(|c| format!("${c:.2}"))
                 .unwrap_or_else(|| "N/A".to_string());
             println!(
-                "sessions:{} tokens:{} cost:{}",
+                "sessions:{} tokens:{} cost:{} streak:{}d",
                 analytics.session_count,
                 analytics.total_usage.usage.work_tokens(),
-                cost
+                cost,
+                streaks.current_streak
             );
         }
         OutputFormat::Text => {
@@ -1111,6 +1604,62 @@ fn output_global_stats(cli: &Cli, args: &StatsArgs, analytics: &ProjectAnalytics
             );
             println!();
 
+            // Project leaderboard
+            if !leaderboard.is_empty() {
+                let metric_label = match args.rank_by {
+                    LeaderboardMetric::Tokens => "tokens",
+                    LeaderboardMetric::Cost => "cost",
+                    LeaderboardMetric::Sessions => "sessions",
+                    LeaderboardMetric::Files => "file modifications",
+                };
+                let shown = if args.all { leaderboard.len() } else { 10 };
+                println!("Project Leaderboard (by {metric_label}):");
+                println!(
+                    "  {:>3}  {:<40} {:>8} {:>14} {:>10} {:>8}",
+                    "#", "Project", "Sessions", "Tokens", "Cost", "Files"
+                );
+                for entry in leaderboard.iter().take(shown) {
+                    println!(
+                        "  {:>3}  {:<40} {:>8} {:>14} {:>10} {:>8}",
+                        entry.rank,
+                        truncate_path(&entry.project, 40),
+                        format_count(entry.sessions),
+                        format_number(entry.total_tokens),
+                        entry
+                            .estimated_cost
+                            .map(|cost| format!("${cost:.2}"))
+                            .unwrap_or_else(|| "N/A".to_string()),
+                        format_count(entry.file_modifications)
+                    );
+                }
+                if leaderboard.len() > shown {
+                    println!(
+                        "  ... and {} more projects (use --all to show every project)",
+                        leaderboard.len() - shown
+                    );
+                }
+                println!();
+            }
+
+            // Streaks
+            if streaks.active_days > 0 {
+                println!("Activity Streaks:");
+                println!("  Current: {} day(s)", streaks.current_streak);
+                match (streaks.longest_start, streaks.longest_end) {
+                    (Some(start), Some(end)) => println!(
+                        "  Longest: {} day(s) ({} to {})",
+                        streaks.longest_streak, start, end
+                    ),
+                    _ => println!("  Longest: {} day(s)", streaks.longest_streak),
+                }
+                println!("  Active Days: {}", format_count(streaks.active_days));
+                println!(
+                    "  Avg Sessions/Active Day: {:.1}",
+                    streaks.avg_daily_sessions
+                );
+                println!();
+            }
+
             // Top tools
             if args.tools || args.all {
                 println!("Top Tools:");
@@ -1129,6 +1678,7 @@ fn output_global_stats(cli: &Cli, args: &StatsArgs, analytics: &ProjectAnalytics
                     let display_name = format_model_name(model);
                     println!("  {display_name}: {} uses", format_number(*count));
                 }
+                print_model_switches(&analytics.model_switches);
                 println!();
             }
 
@@ -1146,6 +1696,9 @@ fn output_global_stats(cli: &Cli, args: &StatsArgs, analytics: &ProjectAnalytics
                         println!("    Cache Write: ${:.4}", cost.cache_write_cost);
                         println!("    Cache Read:  ${:.4}", cost.cache_read_cost);
                         println!("    Server Tools: ${:.4}", cost.server_tool_cost);
+                        if cost.cache_read_savings > 0.0 {
+                            println!("    Cache Saved: ${:.4}", cost.cache_read_savings);
+                        }
                         println!("    Subtotal:    ${:.4}", cost.total_cost);
                     }
                 }
@@ -1155,7 +1708,9 @@ fn output_global_stats(cli: &Cli, args: &StatsArgs, analytics: &ProjectAnalytics
                 // Just show total cost without breakdown
                 println!("Estimated API List Cost: ${cost:.2}");
             }
+            print_cache_savings(&analytics.total_usage);
             print_pricing_context(&analytics.total_usage);
+            print_thinking_usage(&analytics.thinking_stats);
         }
     }
 
@@ -1165,7 +1720,7 @@ fn output_global_stats(cli: &Cli, args: &StatsArgs, analytics: &ProjectAnalytics
 /// Output overview of all projects.
 fn output_overview(
     cli: &Cli,
-    _args: &StatsArgs,
+    args: &StatsArgs,
     claude_dir: &crate::discovery::ClaudeDirectory,
 ) -> Result<()> {
     let stats = claude_dir.statistics()?;
@@ -1183,12 +1738,19 @@ fn output_overview(
                 })?
             );
         }
-        OutputFormat::Tsv => {
-            println!("metric\tvalue");
-            println!("projects\t{}", stats.project_count);
-            println!("sessions\t{}", stats.session_count);
-            println!("subagents\t{}", stats.subagent_count);
-            println!("total_size\t{}", stats.total_size_bytes);
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_OVERVIEW_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            table.write_row(
+                &mut stdout,
+                &[
+                    stats.project_count.to_string(),
+                    stats.session_count.to_string(),
+                    stats.subagent_count.to_string(),
+                    stats.total_size_bytes.to_string(),
+                ],
+            )?;
         }
         OutputFormat::Compact => {
             println!(
@@ -1298,26 +1860,28 @@ fn output_blocks_stats(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Res
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!(
-                "block_start\tblock_end\tstatus\ttokens\tinput\toutput\tmessages\ttools\tcost"
-            );
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_BLOCK_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
             for block in &blocks {
-                println!(
-                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
-                    block.start.format("%Y-%m-%d %H:%M"),
-                    block.end.format("%Y-%m-%d %H:%M"),
-                    block.status,
-                    block.total_tokens,
-                    block.input_tokens,
-                    block.output_tokens,
-                    block.message_count,
-                    block.tool_invocations,
-                    block
-                        .estimated_cost
-                        .map(|c| format!("{c:.4}"))
-                        .unwrap_or_default()
-                );
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        block.start.format("%Y-%m-%d %H:%M").to_string(),
+                        block.end.format("%Y-%m-%d %H:%M").to_string(),
+                        block.status.clone(),
+                        block.total_tokens.to_string(),
+                        block.input_tokens.to_string(),
+                        block.output_tokens.to_string(),
+                        block.message_count.to_string(),
+                        block.tool_invocations.to_string(),
+                        block
+                            .estimated_cost
+                            .map(|c| format!("{c:.4}"))
+                            .unwrap_or_default(),
+                    ],
+                )?;
             }
         }
         OutputFormat::Compact => {
@@ -1505,7 +2069,7 @@ fn output_blocks_stats(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Res
 
 /// Stats output for JSON serialization.
 #[derive(Debug, serde::Serialize)]
-struct StatsOutput {
+struct StatsOutput<'a> {
     scope: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     provider: Option<String>,
@@ -1526,43 +2090,199 @@ struct StatsOutput {
     pricing_policy: Option<&'static str>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     unpriced_models: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_savings: Option<CacheSavingsOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<ThinkingOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model_switches: Option<ModelSwitchOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    streaks: Option<StreaksOutput>,
+    /// Project leaderboard (global scope only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    projects: Option<&'a [LeaderboardEntry]>,
 }
 
-impl StatsOutput {
-    fn from_session(analytics: &SessionAnalytics, provider: Option<&ProviderSessionStats>) -> Self {
-        let summary = analytics.summary_report();
-        Self {
-            scope: "session".to_string(),
-            provider: provider.map(|context| context.provider.clone()),
-            qualified_id: provider.map(|context| context.qualified_id.clone()),
-            sessions: Some(1),
-            total_tokens: summary.total_tokens,
-            input_tokens: summary.input_tokens,
-            output_tokens: summary.output_tokens,
-            cache_read_tokens: summary.cache_read_tokens,
-            cache_creation_tokens: summary.cache_creation_tokens,
-            total_processed_tokens: summary.total_processed_tokens,
-            messages: summary.total_messages,
-            tool_invocations: summary.tool_invocations,
-            cache_hit_rate: Some(summary.cache_hit_rate),
-            estimated_cost: provider
-                .map(|context| context.usage.pricing.estimated_cost)
-                .unwrap_or(summary.estimated_cost),
-            pricing_policy: provider.map(|context| context.usage.pricing.policy),
-            unpriced_models: provider
-                .map(|context| context.usage.pricing.unpriced_models.clone())
-                .unwrap_or_default(),
-        }
+/// Prompt-caching savings for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct CacheSavingsOutput {
+    /// Saved by cache reads versus the full input rate.
+    cache_read_savings: f64,
+    /// Cache-read savings minus the cache-write premium.
+    net_savings: f64,
+}
+
+impl CacheSavingsOutput {
+    fn from_usage(usage: &AggregatedUsage) -> Option<Self> {
+        usage
+            .cache_savings()
+            .map(|(cache_read_savings, net_savings)| Self {
+                cache_read_savings,
+                net_savings,
+            })
     }
+}
 
-    fn from_project(analytics: &ProjectAnalytics, scope: &str) -> Self {
-        Self {
-            scope: scope.to_string(),
-            provider: None,
-            qualified_id: None,
-            sessions: Some(analytics.session_count),
-            total_tokens: analytics.total_usage.usage.work_tokens(),
-            input_tokens: analytics.total_usage.usage.input_tokens,
+/// Reported thinking-token usage for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ThinkingOutput {
+    blocks: usize,
+    thinking_tokens: u64,
+    output_ratio: Option<f64>,
+    estimated_cost: Option<f64>,
+    by_model: Vec<ModelThinkingOutput>,
+}
+
+/// Per-model thinking usage for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ModelThinkingOutput {
+    model: String,
+    thinking_tokens: u64,
+    output_tokens: u64,
+    output_ratio: f64,
+    estimated_cost: Option<f64>,
+}
+
+impl ThinkingOutput {
+    /// Only emitted when usage payloads reported a thinking-token split.
+    fn from_stats(stats: &ThinkingStats) -> Option<Self> {
+        if stats.reported_tokens == 0 {
+            return None;
+        }
+        Some(Self {
+            blocks: stats.block_count,
+            thinking_tokens: stats.reported_tokens,
+            output_ratio: stats.token_ratio(),
+            estimated_cost: stats.estimated_cost(),
+            by_model: stats
+                .by_model
+                .iter()
+                .map(|(model, usage)| ModelThinkingOutput {
+                    model: model.clone(),
+                    thinking_tokens: usage.thinking_tokens,
+                    output_tokens: usage.output_tokens,
+                    output_ratio: usage.ratio(),
+                    estimated_cost: usage.estimated_cost,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// One model-to-model switch for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ModelTransitionOutput {
+    from: String,
+    to: String,
+    count: usize,
+}
+
+/// Model switches for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ModelSwitchOutput {
+    switches: usize,
+    sessions_switched: usize,
+    transitions: Vec<ModelTransitionOutput>,
+    tokens_before_switch: u64,
+    cost_before_switch: Option<f64>,
+    tokens_after_switch: u64,
+    cost_after_switch: Option<f64>,
+}
+
+impl ModelSwitchOutput {
+    /// Only emitted when a session switched models.
+    fn from_stats(stats: &ModelSwitchStats) -> Option<Self> {
+        if stats.switches == 0 {
+            return None;
+        }
+        Some(Self {
+            switches: stats.switches,
+            sessions_switched: stats.sessions_switched,
+            transitions: stats
+                .ranked_transitions()
+                .into_iter()
+                .map(|(from, to, count)| ModelTransitionOutput {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    count,
+                })
+                .collect(),
+            tokens_before_switch: stats.before_switch.usage.work_tokens(),
+            cost_before_switch: stats.before_switch.estimated_cost,
+            tokens_after_switch: stats.after_switch.usage.work_tokens(),
+            cost_after_switch: stats.after_switch.estimated_cost,
+        })
+    }
+}
+
+/// Activity streaks for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct StreaksOutput {
+    current_streak: u32,
+    longest_streak: u32,
+    longest_start: Option<chrono::NaiveDate>,
+    longest_end: Option<chrono::NaiveDate>,
+    active_days: usize,
+    avg_daily_sessions: f64,
+}
+
+impl From<&ActivityStreaks> for StreaksOutput {
+    fn from(streaks: &ActivityStreaks) -> Self {
+        Self {
+            current_streak: streaks.current_streak,
+            longest_streak: streaks.longest_streak,
+            longest_start: streaks.longest_start,
+            longest_end: streaks.longest_end,
+            active_days: streaks.active_days,
+            avg_daily_sessions: streaks.avg_daily_sessions,
+        }
+    }
+}
+
+impl StatsOutput<'_> {
+    fn from_session(analytics: &SessionAnalytics, provider: Option<&ProviderSessionStats>) -> Self {
+        let summary = analytics.summary_report();
+        Self {
+            scope: "session".to_string(),
+            provider: provider.map(|context| context.provider.clone()),
+            qualified_id: provider.map(|context| context.qualified_id.clone()),
+            sessions: Some(1),
+            total_tokens: summary.total_tokens,
+            input_tokens: summary.input_tokens,
+            output_tokens: summary.output_tokens,
+            cache_read_tokens: summary.cache_read_tokens,
+            cache_creation_tokens: summary.cache_creation_tokens,
+            total_processed_tokens: summary.total_processed_tokens,
+            messages: summary.total_messages,
+            tool_invocations: summary.tool_invocations,
+            cache_hit_rate: Some(summary.cache_hit_rate),
+            estimated_cost: provider
+                .map(|context| context.usage.pricing.estimated_cost)
+                .unwrap_or(summary.estimated_cost),
+            pricing_policy: provider.map(|context| context.usage.pricing.policy),
+            unpriced_models: provider
+                .map(|context| context.usage.pricing.unpriced_models.clone())
+                .unwrap_or_default(),
+            cache_savings: if provider.is_some() {
+                None
+            } else {
+                CacheSavingsOutput::from_usage(&analytics.usage)
+            },
+            thinking: ThinkingOutput::from_stats(&analytics.thinking_stats),
+            model_switches: ModelSwitchOutput::from_stats(&analytics.model_switches),
+            streaks: None,
+            projects: None,
+        }
+    }
+
+    fn from_project(analytics: &ProjectAnalytics, scope: &str) -> Self {
+        Self {
+            scope: scope.to_string(),
+            provider: None,
+            qualified_id: None,
+            sessions: Some(analytics.session_count),
+            total_tokens: analytics.total_usage.usage.work_tokens(),
+            input_tokens: analytics.total_usage.usage.input_tokens,
             output_tokens: analytics.total_usage.usage.output_tokens,
             cache_read_tokens: analytics
                 .total_usage
@@ -1581,6 +2301,11 @@ impl StatsOutput {
             estimated_cost: analytics.total_usage.estimated_cost,
             pricing_policy: None,
             unpriced_models: Vec::new(),
+            cache_savings: CacheSavingsOutput::from_usage(&analytics.total_usage),
+            thinking: ThinkingOutput::from_stats(&analytics.thinking_stats),
+            model_switches: ModelSwitchOutput::from_stats(&analytics.model_switches),
+            streaks: None,
+            projects: None,
         }
     }
 }
@@ -1669,27 +2394,29 @@ fn output_cost_history(cli: &Cli, args: &StatsArgs) -> Result<()> {
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!(
-                "date\ttokens\tinput_tokens\toutput_tokens\tcache_read\tcost\tsessions\tmessages"
-            );
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_HISTORY_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
             let end = Utc::now().date_naive();
             let start = end - Duration::days(args.days - 1);
 
             let mut current = start;
             while current <= end {
                 if let Some(point) = history.get(current) {
-                    println!(
-                        "{}\t{}\t{}\t{}\t{}\t{:.6}\t{}\t{}",
-                        current,
-                        point.tokens,
-                        point.input_tokens,
-                        point.output_tokens,
-                        point.cache_read_tokens,
-                        point.cost,
-                        point.session_count,
-                        point.message_count,
-                    );
+                    table.write_row(
+                        &mut stdout,
+                        &[
+                            current.to_string(),
+                            point.tokens.to_string(),
+                            point.input_tokens.to_string(),
+                            point.output_tokens.to_string(),
+                            point.cache_read_tokens.to_string(),
+                            format!("{:.6}", point.cost),
+                            point.session_count.to_string(),
+                            point.message_count.to_string(),
+                        ],
+                    )?;
                 }
                 current += Duration::days(1);
             }
@@ -1800,10 +2527,12 @@ fn output_weekly_costs(cli: &Cli, args: &StatsArgs, history: &CostHistory) -> Re
             });
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!("period\tcost");
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_WEEKLY_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
             for (period, cost) in &weekly_data {
-                println!("{}\t{:.4}", period, cost);
+                table.write_row(&mut stdout, &[period.to_string(), format!("{:.4}", cost)])?;
             }
         }
         OutputFormat::Compact => {
@@ -1884,10 +2613,12 @@ fn output_monthly_costs(cli: &Cli, args: &StatsArgs, history: &CostHistory) -> R
             });
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!("month\tcost");
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_MONTHLY_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
             for (month, cost) in &monthly_data {
-                println!("{}\t{:.4}", month, cost);
+                table.write_row(&mut stdout, &[month.to_string(), format!("{:.4}", cost)])?;
             }
         }
         OutputFormat::Compact => {
@@ -2019,6 +2750,133 @@ fn collect_timeline_entries(
     buckets.into_values().collect()
 }
 
+/// One turn of `stats <session> --timeline`, for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct TurnTokensOutput<'a> {
+    turn: usize,
+    timestamp: DateTime<Utc>,
+    model: &'a str,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+    context_tokens: u64,
+    cumulative_output_tokens: u64,
+}
+
+/// `stats <session> --timeline` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct TokenTimelineOutput<'a> {
+    session_id: &'a str,
+    peak_context_tokens: u64,
+    turns: Vec<TurnTokensOutput<'a>>,
+}
+
+/// Output per-turn token usage for one session.
+fn output_turn_timeline(cli: &Cli, args: &StatsArgs, session: &Session) -> Result<()> {
+    let entries = session.parse_with_options(cli.max_file_size)?;
+    let conversation = Conversation::from_entries(entries)?;
+    let timeline = TokenTimeline::from_conversation(&conversation);
+    let cumulative = timeline.cumulative_output();
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = TokenTimelineOutput {
+                session_id: session.session_id(),
+                peak_context_tokens: timeline.peak_context(),
+                turns: timeline
+                    .turns
+                    .iter()
+                    .zip(&cumulative)
+                    .map(|(turn, cumulative)| TurnTokensOutput {
+                        turn: turn.turn,
+                        timestamp: turn.timestamp,
+                        model: &turn.model,
+                        input_tokens: turn.usage.input_tokens,
+                        output_tokens: turn.usage.output_tokens,
+                        cache_read_tokens: turn.usage.cache_read_input_tokens.unwrap_or(0),
+                        cache_creation_tokens: turn.usage.cache_creation_input_tokens.unwrap_or(0),
+                        context_tokens: turn.context_tokens(),
+                        cumulative_output_tokens: *cumulative,
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_TURN_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (turn, cumulative) in timeline.turns.iter().zip(&cumulative) {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        turn.turn.to_string(),
+                        turn.timestamp.to_rfc3339(),
+                        turn.model.clone(),
+                        turn.usage.input_tokens.to_string(),
+                        turn.usage.output_tokens.to_string(),
+                        turn.usage.cache_read_input_tokens.unwrap_or(0).to_string(),
+                        turn.usage
+                            .cache_creation_input_tokens
+                            .unwrap_or(0)
+                            .to_string(),
+                        turn.context_tokens().to_string(),
+                        cumulative.to_string(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            println!(
+                "turns:{} peak_context:{} output:{}",
+                timeline.turns.len(),
+                timeline.peak_context(),
+                cumulative.last().copied().unwrap_or(0)
+            );
+        }
+        OutputFormat::Text => {
+            println!("Token Timeline: {}", session.session_id());
+            println!("{}", "=".repeat(60));
+            println!();
+            if timeline.turns.is_empty() {
+                println!("  No assistant turns in this session.");
+                return Ok(());
+            }
+            let context: Vec<u64> = timeline
+                .turns
+                .iter()
+                .map(TurnTokens::context_tokens)
+                .collect();
+            println!("  Context: {}", sparkline_u64(&context));
+            println!(
+                "  Peak:    {} tokens over {} turns",
+                format_number(timeline.peak_context()),
+                format_count(timeline.turns.len())
+            );
+            println!();
+            println!(
+                "  {:>5}  {:<8}  {:>10} {:>10} {:>12} {:>12} {:>12}",
+                "Turn", "Time", "Input", "Output", "Cache Read", "Cache Write", "Context"
+            );
+            for turn in &timeline.turns {
+                println!(
+                    "  {:>5}  {:<8}  {:>10} {:>10} {:>12} {:>12} {:>12}",
+                    turn.turn,
+                    turn.timestamp.with_timezone(&Local).format("%H:%M:%S"),
+                    format_number(turn.usage.input_tokens),
+                    format_number(turn.usage.output_tokens),
+                    format_number(turn.usage.cache_read_input_tokens.unwrap_or(0)),
+                    format_number(turn.usage.cache_creation_input_tokens.unwrap_or(0)),
+                    format_number(turn.context_tokens())
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Output activity timeline visualization.
 fn output_timeline(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
     let entries = collect_timeline_entries(sessions, &args.granularity, cli.max_file_size);
@@ -2051,20 +2909,24 @@ fn output_timeline(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!("period\tsessions\ttokens\tmessages\tcost");
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_TIMELINE_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
             for entry in &entries {
-                println!(
-                    "{}\t{}\t{}\t{}\t{}",
-                    entry.period,
-                    entry.session_count,
-                    entry.total_tokens,
-                    entry.message_count,
-                    entry
-                        .estimated_cost
-                        .map(|c| format!("{:.4}", c))
-                        .unwrap_or_default()
-                );
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        entry.period.clone(),
+                        entry.session_count.to_string(),
+                        entry.total_tokens.to_string(),
+                        entry.message_count.to_string(),
+                        entry
+                            .estimated_cost
+                            .map(|c| format!("{:.4}", c))
+                            .unwrap_or_default(),
+                    ],
+                )?;
             }
         }
         OutputFormat::Compact => {
@@ -2159,156 +3021,1882 @@ fn output_timeline(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<
     Ok(())
 }
 
-/// Token breakdown entry for graph visualization.
-#[derive(Debug, Clone, serde::Serialize)]
-struct TokenBreakdown {
-    period: String,
-    input_tokens: u64,
-    output_tokens: u64,
-    cache_read_tokens: u64,
-    cache_write_tokens: u64,
-    total_tokens: u64,
-}
-
-/// Token graph output for JSON serialization.
+/// Busiest heatmap cell for JSON serialization.
 #[derive(Debug, serde::Serialize)]
-struct TokenGraphOutput {
-    granularity: String,
-    data: Vec<TokenBreakdown>,
-    totals: TokenBreakdown,
+struct HeatmapPeak {
+    weekday: &'static str,
+    hour: usize,
+    value: u64,
 }
 
-/// Collect token breakdown data from sessions.
-fn collect_token_breakdown(
-    sessions: &[Session],
-    granularity: &str,
-    max_file_size: Option<u64>,
-) -> Vec<TokenBreakdown> {
-    use std::collections::BTreeMap;
-
-    let mut buckets: BTreeMap<String, TokenBreakdown> = BTreeMap::new();
-
-    for session in sessions {
-        let entries = match session.parse_with_options(max_file_size) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        // Get session start time
-        let start_time = entries.iter().find_map(|e| e.timestamp());
-        let start_time = match start_time {
-            Some(t) => t,
-            None => continue,
-        };
-
-        // Determine bucket key based on granularity
-        let bucket_key = match granularity {
-            "hourly" => start_time.format("%Y-%m-%d %H:00").to_string(),
-            "weekly" => {
-                let week = start_time.iso_week();
-                format!("{}-W{:02}", week.year(), week.week())
-            }
-            _ => start_time.format("%Y-%m-%d").to_string(), // daily default
-        };
-
-        // Calculate session metrics
-        let conv = match Conversation::from_entries(entries) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
-        let analytics = SessionAnalytics::from_conversation(&conv);
-        let usage = &analytics.usage.usage;
-
-        let entry = buckets
-            .entry(bucket_key.clone())
-            .or_insert_with(|| TokenBreakdown {
-                period: bucket_key,
-                input_tokens: 0,
-                output_tokens: 0,
-                cache_read_tokens: 0,
-                cache_write_tokens: 0,
-                total_tokens: 0,
-            });
-
-        entry.input_tokens += usage.input_tokens;
-        entry.output_tokens += usage.output_tokens;
-        entry.cache_read_tokens += usage.cache_read_input_tokens.unwrap_or(0);
-        entry.cache_write_tokens += usage.cache_creation_input_tokens.unwrap_or(0);
-        entry.total_tokens += usage.total_tokens();
+impl HeatmapPeak {
+    fn from_cell(cell: Option<(usize, usize, u64)>) -> Option<Self> {
+        cell.map(|(day, hour, value)| Self {
+            weekday: ActivityHeatmap::WEEKDAYS[day],
+            hour,
+            value,
+        })
     }
+}
 
-    buckets.into_values().collect()
+/// Activity heatmap for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct HeatmapOutput {
+    /// UTC offset the hours are expressed in.
+    utc_offset: String,
+    weekdays: [&'static str; 7],
+    /// Messages indexed `[weekday][hour]`.
+    messages: [[u64; 24]; 7],
+    /// Work tokens indexed `[weekday][hour]`.
+    tokens: [[u64; 24]; 7],
+    total_messages: u64,
+    total_tokens: u64,
+    peak_messages: Option<HeatmapPeak>,
+    peak_tokens: Option<HeatmapPeak>,
 }
 
-/// Output token usage graph visualization.
-fn output_token_graph(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
-    let data = collect_token_breakdown(sessions, &args.granularity, cli.max_file_size);
+/// Build an hour × weekday heatmap across sessions in parallel.
+fn collect_heatmap(sessions: &[Session], max_file_size: Option<u64>) -> ActivityHeatmap {
+    sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            Some(ActivityHeatmap::from_conversation(&conversation))
+        })
+        .reduce(ActivityHeatmap::default, |mut acc, heatmap| {
+            acc.merge(&heatmap);
+            acc
+        })
+}
 
-    if data.is_empty() {
+/// Output the hour × weekday activity heatmap.
+fn output_heatmap(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let heatmap = collect_heatmap(sessions, cli.max_file_size);
+
+    // Machine-readable formats still emit an all-zero heatmap.
+    if heatmap.is_empty() && cli.effective_output() == OutputFormat::Text {
         if !cli.quiet {
-            println!("No token data found.");
+            println!("No session activity found.");
         }
         return Ok(());
     }
 
-    // Calculate totals
-    let totals = TokenBreakdown {
-        period: "Total".to_string(),
-        input_tokens: data.iter().map(|d| d.input_tokens).sum(),
-        output_tokens: data.iter().map(|d| d.output_tokens).sum(),
-        cache_read_tokens: data.iter().map(|d| d.cache_read_tokens).sum(),
-        cache_write_tokens: data.iter().map(|d| d.cache_write_tokens).sum(),
-        total_tokens: data.iter().map(|d| d.total_tokens).sum(),
-    };
+    let utc_offset = Local::now().offset().to_string();
+    let peak_messages = heatmap.peak(HeatmapMetric::Messages);
+    let peak_tokens = heatmap.peak(HeatmapMetric::Tokens);
 
     match cli.effective_output() {
         OutputFormat::Json => {
-            let output = TokenGraphOutput {
-                granularity: args.granularity.clone(),
-                data,
-                totals,
+            let output = HeatmapOutput {
+                utc_offset,
+                weekdays: ActivityHeatmap::WEEKDAYS,
+                messages: heatmap.messages,
+                tokens: heatmap.tokens,
+                total_messages: heatmap.total(HeatmapMetric::Messages),
+                total_tokens: heatmap.total(HeatmapMetric::Tokens),
+                peak_messages: HeatmapPeak::from_cell(peak_messages),
+                peak_tokens: HeatmapPeak::from_cell(peak_tokens),
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
-            println!("period\tinput\toutput\tcache_read\tcache_write\ttotal");
-            for entry in &data {
-                println!(
-                    "{}\t{}\t{}\t{}\t{}\t{}",
-                    entry.period,
-                    entry.input_tokens,
-                    entry.output_tokens,
-                    entry.cache_read_tokens,
-                    entry.cache_write_tokens,
-                    entry.total_tokens
-                );
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_HEATMAP_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (day, label) in ActivityHeatmap::WEEKDAYS.iter().enumerate() {
+                let cells = heatmap.messages[day].iter().zip(&heatmap.tokens[day]);
+                for (hour, (messages, tokens)) in cells.enumerate() {
+                    table.write_row(
+                        &mut stdout,
+                        &[
+                            (*label).to_string(),
+                            hour.to_string(),
+                            messages.to_string(),
+                            tokens.to_string(),
+                        ],
+                    )?;
+                }
             }
         }
         OutputFormat::Compact => {
-            // Sparklines for each token type
-            let input_vals: Vec<u64> = data.iter().map(|d| d.input_tokens).collect();
-            let output_vals: Vec<u64> = data.iter().map(|d| d.output_tokens).collect();
+            let peak = peak_messages.map_or_else(String::new, |(day, hour, _)| {
+                format!(" peak:{} {:02}:00", ActivityHeatmap::WEEKDAYS[day], hour)
+            });
             println!(
-                "periods:{} in:{} out:{} input:{} output:{}",
-                data.len(),
-                format_number(totals.input_tokens),
-                format_number(totals.output_tokens),
-                sparkline_u64(&input_vals),
-                sparkline_u64(&output_vals)
+                "messages:{} tokens:{}{}",
+                heatmap.total(HeatmapMetric::Messages),
+                format_number(heatmap.total(HeatmapMetric::Tokens)),
+                peak
             );
         }
         OutputFormat::Text => {
-            let granularity_label = match args.granularity.as_str() {
-                "hourly" => "Hourly",
-                "weekly" => "Weekly",
-                _ => "Daily",
-            };
+            println!("Activity Heatmap (local time, UTC{})", utc_offset);
+            println!("{}", "=".repeat(40));
 
-            println!("{} Token Usage Graph", granularity_label);
-            println!("{}", "=".repeat(35));
-            println!();
+            for (title, metric, peak) in [
+                ("Messages", HeatmapMetric::Messages, peak_messages),
+                ("Tokens", HeatmapMetric::Tokens, peak_tokens),
+            ] {
+                println!();
+                println!("{} ({} total)", title, format_number(heatmap.total(metric)));
+                println!("{}", "-".repeat(title.len()));
+                print!("{}", heatmap.render(metric));
+                if let Some((day, hour, value)) = peak {
+                    println!(
+                        "Peak: {} {:02}:00 ({})",
+                        ActivityHeatmap::WEEKDAYS[day],
+                        hour,
+                        format_number(value)
+                    );
+                }
+            }
 
-            // Summary
+            println!();
+            println!("Legend: · none  ░ low  ▒ medium  ▓ high  █ peak");
+        }
+    }
+
+    Ok(())
+}
+
+/// A least-squares fit for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct LinearFitOutput {
+    slope: f64,
+    intercept: f64,
+    r: f64,
+    samples: usize,
+}
+
+impl From<LinearFit> for LinearFitOutput {
+    fn from(fit: LinearFit) -> Self {
+        Self {
+            slope: fit.slope,
+            intercept: fit.intercept,
+            r: fit.r,
+            samples: fit.n,
+        }
+    }
+}
+
+/// Averages for one prompt length range, for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct PromptBucketOutput {
+    prompt_length: &'static str,
+    prompts: usize,
+    avg_prompt_chars: f64,
+    avg_response_tokens: f64,
+    avg_latency_secs: Option<f64>,
+}
+
+/// `stats --correlation` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct PromptCorrelationOutput {
+    prompts: usize,
+    /// Response output tokens regressed on prompt characters.
+    response_tokens_fit: Option<LinearFitOutput>,
+    /// Latency seconds regressed on prompt characters.
+    latency_fit: Option<LinearFitOutput>,
+    buckets: Vec<PromptBucketOutput>,
+}
+
+/// Collect prompt/response samples across sessions in parallel.
+fn collect_prompt_correlation(
+    sessions: &[Session],
+    max_file_size: Option<u64>,
+) -> PromptCorrelation {
+    sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            Some(PromptCorrelation::from_conversation(&conversation))
+        })
+        .reduce(PromptCorrelation::default, |mut acc, correlation| {
+            acc.merge(correlation);
+            acc
+        })
+}
+
+/// Describe how strongly `r` correlates, for the text report.
+fn correlation_strength(r: f64) -> &'static str {
+    match r.abs() {
+        r if r >= 0.7 => "strong",
+        r if r >= 0.4 => "moderate",
+        r if r >= 0.2 => "weak",
+        _ => "negligible",
+    }
+}
+
+/// Output the prompt length vs response correlation.
+fn output_prompt_correlation(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let correlation = collect_prompt_correlation(sessions, cli.max_file_size);
+    let tokens_fit = correlation.tokens_fit();
+    let latency_fit = correlation.latency_fit();
+    let buckets = correlation.buckets();
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = PromptCorrelationOutput {
+                prompts: correlation.samples.len(),
+                response_tokens_fit: tokens_fit.map(LinearFitOutput::from),
+                latency_fit: latency_fit.map(LinearFitOutput::from),
+                buckets: buckets
+                    .into_iter()
+                    .map(|bucket| PromptBucketOutput {
+                        prompt_length: bucket.label,
+                        prompts: bucket.prompts,
+                        avg_prompt_chars: bucket.avg_prompt_chars,
+                        avg_response_tokens: bucket.avg_response_tokens,
+                        avg_latency_secs: bucket.avg_latency_secs,
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_CORRELATION_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for bucket in &buckets {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        bucket.label.to_string(),
+                        bucket.prompts.to_string(),
+                        format!("{:.1}", bucket.avg_prompt_chars),
+                        format!("{:.1}", bucket.avg_response_tokens),
+                        bucket
+                            .avg_latency_secs
+                            .map(|secs| format!("{secs:.1}"))
+                            .unwrap_or_default(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            let r = |fit: Option<LinearFit>| {
+                fit.map_or_else(|| "-".to_string(), |f| format!("{:.2}", f.r))
+            };
+            println!(
+                "prompts:{} r_tokens:{} r_latency:{}",
+                correlation.samples.len(),
+                r(tokens_fit),
+                r(latency_fit)
+            );
+        }
+        OutputFormat::Text => {
+            if correlation.samples.is_empty() {
+                if !cli.quiet {
+                    println!("No answered prompts found.");
+                }
+                return Ok(());
+            }
+
+            println!(
+                "Prompt Length vs Response ({} prompts)",
+                format_count(correlation.samples.len())
+            );
+            println!("{}", "=".repeat(40));
+            println!();
+            for (label, unit, fit) in [
+                ("Response tokens", "tokens", tokens_fit),
+                ("Latency", "s", latency_fit),
+            ] {
+                match fit {
+                    Some(fit) => println!(
+                        "  {label:<16} {:+.2} {unit} per 100 prompt chars, base {:.1} {unit} (r = {:.2}, {})",
+                        fit.slope * 100.0,
+                        fit.intercept,
+                        fit.r,
+                        correlation_strength(fit.r)
+                    ),
+                    None => println!("  {label:<16} not enough varied prompts to fit"),
+                }
+            }
+            println!();
+            println!(
+                "  {:<10} {:>8} {:>12} {:>14} {:>12}",
+                "Length", "Prompts", "Avg chars", "Avg tokens", "Avg latency"
+            );
+            for bucket in buckets.iter().filter(|bucket| bucket.prompts > 0) {
+                println!(
+                    "  {:<10} {:>8} {:>12.0} {:>14.0} {:>12}",
+                    bucket.label,
+                    format_count(bucket.prompts),
+                    bucket.avg_prompt_chars,
+                    bucket.avg_response_tokens,
+                    bucket
+                        .avg_latency_secs
+                        .map_or_else(|| "-".to_string(), |secs| format!("{secs:.1}s"))
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Days shown in the text error timeline unless `--all` is given.
+const ERROR_DAY_LIMIT: usize = 14;
+
+/// One API error type for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ErrorTypeOutput<'a> {
+    #[serde(rename = "type")]
+    error_type: &'a str,
+    count: usize,
+}
+
+/// One HTTP status for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ErrorStatusOutput {
+    status: u16,
+    count: usize,
+}
+
+/// One day of the error timeline for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ErrorDayOutput {
+    date: chrono::NaiveDate,
+    errors: usize,
+    turns: usize,
+    error_rate: f64,
+}
+
+/// `stats --errors` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ErrorStatsOutput<'a> {
+    api_errors: usize,
+    /// Errors per attempted API call (errors plus completed turns).
+    error_rate: f64,
+    error_messages: usize,
+    retry_attempts: usize,
+    max_retry_attempt: u32,
+    retries_exhausted: usize,
+    retried_turns: usize,
+    wasted_tokens: u64,
+    wasted_cost: Option<f64>,
+    by_type: Vec<ErrorTypeOutput<'a>>,
+    by_status: Vec<ErrorStatusOutput>,
+    by_day: Vec<ErrorDayOutput>,
+}
+
+/// Collect API error analytics across sessions in parallel.
+fn collect_error_analytics(sessions: &[Session], max_file_size: Option<u64>) -> ErrorAnalytics {
+    let mut errors = sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            Some(ErrorAnalytics::from_conversation(&conversation))
+        })
+        .reduce(ErrorAnalytics::default, |mut acc, errors| {
+            acc.merge(&errors);
+            acc
+        });
+    errors.wasted_usage.calculate_cost();
+    errors
+}
+
+/// Output API error rates, types, and retry waste.
+fn output_error_stats(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let errors = collect_error_analytics(sessions, cli.max_file_size);
+    let wasted_tokens = errors.wasted_usage.usage.work_tokens();
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let mut by_status: Vec<_> = errors
+                .by_status
+                .iter()
+                .map(|(status, count)| ErrorStatusOutput {
+                    status: *status,
+                    count: *count,
+                })
+                .collect();
+            by_status.sort_by_key(|status| status.status);
+            let output = ErrorStatsOutput {
+                api_errors: errors.api_errors,
+                error_rate: errors.error_rate(),
+                error_messages: errors.error_messages,
+                retry_attempts: errors.retry_attempts,
+                max_retry_attempt: errors.max_retry_attempt,
+                retries_exhausted: errors.retries_exhausted,
+                retried_turns: errors.retried_turns,
+                wasted_tokens,
+                wasted_cost: errors.wasted_usage.estimated_cost,
+                by_type: errors
+                    .ranked_types()
+                    .into_iter()
+                    .map(|(error_type, count)| ErrorTypeOutput { error_type, count })
+                    .collect(),
+                by_status,
+                by_day: errors
+                    .by_day
+                    .iter()
+                    .map(|(date, day)| ErrorDayOutput {
+                        date: *date,
+                        errors: day.errors,
+                        turns: day.turns,
+                        error_rate: day.error_rate(),
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_ERROR_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (date, day) in &errors.by_day {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        date.to_string(),
+                        day.errors.to_string(),
+                        day.turns.to_string(),
+                        format!("{:.4}", day.error_rate()),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            println!(
+                "api_errors:{} rate:{:.2}% retried_turns:{} wasted_tokens:{wasted_tokens}",
+                errors.api_errors,
+                errors.error_rate() * 100.0,
+                errors.retried_turns
+            );
+        }
+        OutputFormat::Text => {
+            println!("API Errors and Retries");
+            println!("{}", "=".repeat(40));
+            println!();
+            println!(
+                "  API Errors:    {} ({:.2}% of API calls)",
+                format_count(errors.api_errors),
+                errors.error_rate() * 100.0
+            );
+            if errors.retry_attempts > 0 {
+                println!(
+                    "  Retries:       {} (max attempt {}, {} exhausted)",
+                    format_count(errors.retry_attempts),
+                    errors.max_retry_attempt,
+                    format_count(errors.retries_exhausted)
+                );
+            }
+            if errors.error_messages > 0 {
+                println!("  Shown to user: {}", format_count(errors.error_messages));
+            }
+            println!(
+                "  Retried turns: {} ({} tokens wasted{})",
+                format_count(errors.retried_turns),
+                format_number(wasted_tokens),
+                errors
+                    .wasted_usage
+                    .estimated_cost
+                    .map(|cost| format!(", ${cost:.4}"))
+                    .unwrap_or_default()
+            );
+
+            let types = errors.ranked_types();
+            if !types.is_empty() {
+                println!();
+                println!("Error Types:");
+                for (error_type, count) in types {
+                    println!("  {error_type:<24} {:>8}", format_count(count));
+                }
+            }
+
+            let days: Vec<_> = errors
+                .by_day
+                .iter()
+                .filter(|(_, day)| day.errors > 0)
+                .collect();
+            if !days.is_empty() {
+                println!();
+                println!("Error Rate by Day:");
+                let skip = if args.all {
+                    0
+                } else {
+                    days.len().saturating_sub(ERROR_DAY_LIMIT)
+                };
+                for (date, day) in days.iter().skip(skip) {
+                    println!(
+                        "  {date}  {:>6} errors  {:>6} turns  {:>6.2}%",
+                        format_count(day.errors),
+                        format_count(day.turns),
+                        day.error_rate() * 100.0
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One rolling window for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct WindowUsageOutput<'a> {
+    window: &'a str,
+    start: DateTime<Utc>,
+    sessions: usize,
+    messages: usize,
+    tokens: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+    estimated_cost: Option<f64>,
+}
+
+/// `stats --rolling` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct RollingUsageOutput<'a> {
+    end: DateTime<Utc>,
+    windows: Vec<WindowUsageOutput<'a>>,
+}
+
+/// Parse `--windows` (or the defaults) into labeled durations.
+fn rolling_windows(args: &StatsArgs) -> Result<Vec<(String, Duration)>> {
+    let labels: Vec<String> = match &args.windows {
+        Some(windows) => windows.iter().map(|w| w.trim().to_string()).collect(),
+        None => RollingUsage::DEFAULT_WINDOWS
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    };
+    labels
+        .into_iter()
+        .map(|label| {
+            match crate::analysis::filters::parse_period(&label) {
+                Ok(Some(length)) if length > Duration::zero() => Ok((label, length)),
+                Ok(_) => Err(format!("window must be a positive period, got '{label}'")),
+                Err(reason) => Err(reason),
+            }
+            .map_err(|reason| SnatchError::InvalidArgument {
+                name: "--windows".to_string(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Output usage aggregated over trailing windows ending now.
+fn output_rolling_usage(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let windows = rolling_windows(args)?;
+    let empty = RollingUsage::new(Utc::now(), &windows);
+    // A transcript last written before every window opened has nothing in
+    // any of them.
+    let earliest = empty.earliest_start();
+    let mut rolling = sessions
+        .par_iter()
+        .filter(|session| earliest.is_none_or(|start| session.modified_datetime() >= start))
+        .filter_map(|session| {
+            let entries = session.parse_with_options(cli.max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            let mut rolling = empty.clone();
+            rolling.add_conversation(&conversation);
+            Some(rolling)
+        })
+        .reduce(
+            || empty.clone(),
+            |mut acc, rolling| {
+                acc.merge(&rolling);
+                acc
+            },
+        );
+    rolling.calculate_cost();
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = RollingUsageOutput {
+                end: rolling.end,
+                windows: rolling
+                    .windows
+                    .iter()
+                    .map(|window| {
+                        let usage = &window.usage.usage;
+                        WindowUsageOutput {
+                            window: &window.label,
+                            start: window.start,
+                            sessions: window.sessions,
+                            messages: window.messages,
+                            tokens: usage.work_tokens(),
+                            input_tokens: usage.input_tokens,
+                            output_tokens: usage.output_tokens,
+                            cache_read_tokens: usage.cache_read_input_tokens.unwrap_or(0),
+                            cache_creation_tokens: usage.cache_creation_input_tokens.unwrap_or(0),
+                            estimated_cost: window.usage.estimated_cost,
+                        }
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_ROLLING_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for window in &rolling.windows {
+                let usage = &window.usage.usage;
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        window.label.clone(),
+                        window.start.to_rfc3339(),
+                        window.sessions.to_string(),
+                        window.messages.to_string(),
+                        usage.work_tokens().to_string(),
+                        usage.input_tokens.to_string(),
+                        usage.output_tokens.to_string(),
+                        usage.cache_read_input_tokens.unwrap_or(0).to_string(),
+                        window
+                            .usage
+                            .estimated_cost
+                            .map(|cost| format!("{cost:.4}"))
+                            .unwrap_or_default(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            let parts: Vec<String> = rolling
+                .windows
+                .iter()
+                .map(|window| {
+                    format!(
+                        "{}:{}/${:.2}",
+                        window.label,
+                        window.usage.usage.work_tokens(),
+                        window.usage.estimated_cost.unwrap_or(0.0)
+                    )
+                })
+                .collect();
+            println!("{}", parts.join(" "));
+        }
+        OutputFormat::Text => {
+            println!("Rolling Usage");
+            println!("=============");
+            println!();
+            println!(
+                "  {:<8} {:>9} {:>10} {:>14} {:>10}",
+                "Window", "Sessions", "Messages", "Tokens", "Cost"
+            );
+            for window in &rolling.windows {
+                println!(
+                    "  {:<8} {:>9} {:>10} {:>14} {:>10}",
+                    window.label,
+                    format_count(window.sessions),
+                    format_count(window.messages),
+                    format_number(window.usage.usage.work_tokens()),
+                    window
+                        .usage
+                        .estimated_cost
+                        .map_or_else(|| "N/A".to_string(), |cost| format!("${cost:.2}"))
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One git branch for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct GitBranchOutput<'a> {
+    branch: &'a str,
+    sessions: usize,
+    messages: usize,
+    tokens: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    estimated_cost: Option<f64>,
+    active_minutes: i64,
+    first_seen: Option<DateTime<Utc>>,
+    last_seen: Option<DateTime<Utc>>,
+}
+
+/// Output usage grouped by the git branch recorded on each entry.
+fn output_git_branch_stats(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let mut stats = sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(cli.max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            let mut stats = GitBranchStats::default();
+            stats.add_conversation(&conversation);
+            Some(stats)
+        })
+        .reduce(GitBranchStats::default, |mut acc, stats| {
+            acc.merge(&stats);
+            acc
+        });
+    stats.calculate_cost();
+    let ranked = stats.ranked();
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output: Vec<GitBranchOutput> = ranked
+                .iter()
+                .map(|(branch, usage)| GitBranchOutput {
+                    branch,
+                    sessions: usage.sessions,
+                    messages: usage.messages,
+                    tokens: usage.usage.usage.work_tokens(),
+                    input_tokens: usage.usage.usage.input_tokens,
+                    output_tokens: usage.usage.usage.output_tokens,
+                    estimated_cost: usage.usage.estimated_cost,
+                    active_minutes: usage.active.num_minutes(),
+                    first_seen: usage.first_seen,
+                    last_seen: usage.last_seen,
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_GIT_BRANCH_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (branch, usage) in &ranked {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        (*branch).to_string(),
+                        usage.sessions.to_string(),
+                        usage.messages.to_string(),
+                        usage.usage.usage.work_tokens().to_string(),
+                        usage
+                            .usage
+                            .estimated_cost
+                            .map(|cost| format!("{cost:.4}"))
+                            .unwrap_or_default(),
+                        usage.active.num_minutes().to_string(),
+                        usage.first_seen.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                        usage.last_seen.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            let parts: Vec<String> = ranked
+                .iter()
+                .map(|(branch, usage)| {
+                    format!(
+                        "{branch}:{}/${:.2}/{}m",
+                        usage.usage.usage.work_tokens(),
+                        usage.usage.estimated_cost.unwrap_or(0.0),
+                        usage.active.num_minutes()
+                    )
+                })
+                .collect();
+            println!("{}", parts.join(" "));
+        }
+        OutputFormat::Text => {
+            println!("Usage by Git Branch");
+            println!("===================");
+            println!();
+            if ranked.is_empty() {
+                println!("No messages found.");
+                return Ok(());
+            }
+            println!(
+                "  {:<32} {:>9} {:>10} {:>14} {:>10} {:>9}",
+                "Branch", "Sessions", "Messages", "Tokens", "Cost", "Active"
+            );
+            for (branch, usage) in &ranked {
+                let minutes = usage.active.num_minutes();
+                println!(
+                    "  {:<32} {:>9} {:>10} {:>14} {:>10} {:>9}",
+                    truncate_path(branch, 32),
+                    format_count(usage.sessions),
+                    format_count(usage.messages),
+                    format_number(usage.usage.usage.work_tokens()),
+                    usage
+                        .usage
+                        .estimated_cost
+                        .map_or_else(|| "N/A".to_string(), |cost| format!("${cost:.2}")),
+                    format!("{}h{:02}m", minutes / 60, minutes % 60)
+                );
+            }
+            if !cli.quiet {
+                println!();
+                println!(
+                    "Active time sums gaps of up to {} minutes between entries.",
+                    GitBranchStats::ACTIVE_GAP_MINUTES
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Abandoned branches of one session, for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct SessionBranchOutput<'a> {
+    session_id: &'a str,
+    project: &'a str,
+    branches: usize,
+    tokens: u64,
+    estimated_cost: Option<f64>,
+    avg_depth: f64,
+    max_depth: usize,
+}
+
+/// `stats --branches` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct BranchStatsOutput<'a> {
+    sessions: usize,
+    sessions_with_abandoned: usize,
+    abandoned_branches: usize,
+    abandoned_entries: usize,
+    tokens: u64,
+    estimated_cost: Option<f64>,
+    /// Abandoned tokens as a percentage of all work tokens in scope.
+    token_share: f64,
+    avg_depth: f64,
+    max_depth: usize,
+    by_session: Vec<SessionBranchOutput<'a>>,
+}
+
+/// Output abandoned-branch totals and the sessions that wasted the most.
+fn output_branch_stats(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let analyzed = analyze_sessions_parallel(sessions, cli.max_file_size);
+    let mut total = AbandonedBranchStats::default();
+    let mut work_tokens = 0u64;
+    for (_, analytics) in &analyzed {
+        total.merge(&analytics.abandoned);
+        work_tokens += analytics.usage.usage.work_tokens();
+    }
+    let mut wasteful: Vec<_> = analyzed
+        .iter()
+        .filter(|(_, analytics)| analytics.abandoned.branches > 0)
+        .map(|(session, analytics)| (*session, &analytics.abandoned))
+        .collect();
+    wasteful.sort_by(|a, b| {
+        b.1.tokens
+            .cmp(&a.1.tokens)
+            .then_with(|| b.1.branches.cmp(&a.1.branches))
+    });
+    let token_share = if work_tokens == 0 {
+        0.0
+    } else {
+        total.tokens as f64 / work_tokens as f64 * 100.0
+    };
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = BranchStatsOutput {
+                sessions: analyzed.len(),
+                sessions_with_abandoned: wasteful.len(),
+                abandoned_branches: total.branches,
+                abandoned_entries: total.nodes,
+                tokens: total.tokens,
+                estimated_cost: total.estimated_cost,
+                token_share,
+                avg_depth: total.average_depth(),
+                max_depth: total.max_depth,
+                by_session: wasteful
+                    .iter()
+                    .map(|(session, stats)| SessionBranchOutput {
+                        session_id: session.session_id(),
+                        project: session.project_path(),
+                        branches: stats.branches,
+                        tokens: stats.tokens,
+                        estimated_cost: stats.estimated_cost,
+                        avg_depth: stats.average_depth(),
+                        max_depth: stats.max_depth,
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_BRANCH_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (session, stats) in &wasteful {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        session.session_id().to_string(),
+                        session.project_path().to_string(),
+                        stats.branches.to_string(),
+                        stats.tokens.to_string(),
+                        stats
+                            .estimated_cost
+                            .map(|cost| format!("{cost:.4}"))
+                            .unwrap_or_default(),
+                        format!("{:.1}", stats.average_depth()),
+                        stats.max_depth.to_string(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            println!(
+                "abandoned:{} tokens:{} share:{token_share:.1}% avg_depth:{:.1}",
+                total.branches,
+                total.tokens,
+                total.average_depth()
+            );
+        }
+        OutputFormat::Text => {
+            println!("Abandoned Branches");
+            println!("{}", "=".repeat(40));
+            println!();
+            if total.branches == 0 {
+                println!(
+                    "  No abandoned branches in {} sessions.",
+                    format_count(analyzed.len())
+                );
+                return Ok(());
+            }
+            println!(
+                "  Branches:      {} in {} of {} sessions",
+                format_count(total.branches),
+                format_count(wasteful.len()),
+                format_count(analyzed.len())
+            );
+            println!(
+                "  Tokens:        {} ({token_share:.1}% of work tokens)",
+                format_number(total.tokens)
+            );
+            if let Some(cost) = total.estimated_cost {
+                println!("  Est. Cost:     ${cost:.4}");
+            }
+            println!(
+                "  Depth:         {:.1} avg, {} max",
+                total.average_depth(),
+                total.max_depth
+            );
+
+            println!();
+            println!("Most Wasteful Sessions:");
+            let limit = if args.all { usize::MAX } else { 10 };
+            for (session, stats) in wasteful.iter().take(limit) {
+                println!(
+                    "  {}  {:>3} branches  {:>10} tokens  {}",
+                    &session.session_id()[..8.min(session.session_id().len())],
+                    stats.branches,
+                    format_number(stats.tokens),
+                    truncate_path(session.project_path(), 40)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One churn hotspot, for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ChurnHotspotOutput<'a> {
+    session_id: &'a str,
+    project: &'a str,
+    file: &'a str,
+    edits: usize,
+    rewrites: usize,
+    rewrite_rate: f64,
+}
+
+/// `stats --churn` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ChurnStatsOutput<'a> {
+    sessions: usize,
+    sessions_with_edits: usize,
+    edits: usize,
+    rewrites: usize,
+    rewrite_rate: f64,
+    hotspots: Vec<ChurnHotspotOutput<'a>>,
+}
+
+/// Edit churn of each session in scope that made at least one edit.
+fn collect_edit_churn(
+    sessions: &[Session],
+    max_file_size: Option<u64>,
+) -> Vec<(&Session, EditChurn)> {
+    sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            let churn = EditChurn::from_conversation(&conversation);
+            (churn.edits > 0).then_some((session, churn))
+        })
+        .collect()
+}
+
+/// Output edit churn totals and the files reworked most within a session.
+fn output_churn_stats(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let churned = collect_edit_churn(sessions, cli.max_file_size);
+    let mut total = EditChurn::default();
+    for (_, churn) in &churned {
+        total.edits += churn.edits;
+        total.rewrites += churn.rewrites;
+    }
+    let mut hotspots: Vec<(&Session, &str, &FileChurn)> = churned
+        .iter()
+        .flat_map(|(session, churn)| {
+            churn
+                .hotspots()
+                .into_iter()
+                .map(move |(file, stats)| (*session, file, stats))
+        })
+        .collect();
+    hotspots.sort_by(|a, b| {
+        b.2.rewrites
+            .cmp(&a.2.rewrites)
+            .then_with(|| b.2.edits.cmp(&a.2.edits))
+    });
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = ChurnStatsOutput {
+                sessions: sessions.len(),
+                sessions_with_edits: churned.len(),
+                edits: total.edits,
+                rewrites: total.rewrites,
+                rewrite_rate: total.rewrite_rate(),
+                hotspots: hotspots
+                    .iter()
+                    .map(|(session, file, stats)| ChurnHotspotOutput {
+                        session_id: session.session_id(),
+                        project: session.project_path(),
+                        file,
+                        edits: stats.edits,
+                        rewrites: stats.rewrites,
+                        rewrite_rate: stats.rewrite_rate(),
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_CHURN_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (session, file, stats) in &hotspots {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        session.session_id().to_string(),
+                        session.project_path().to_string(),
+                        (*file).to_string(),
+                        stats.edits.to_string(),
+                        stats.rewrites.to_string(),
+                        format!("{:.1}", stats.rewrite_rate()),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            println!(
+                "edits:{} rewrites:{} rate:{:.1}% hotspots:{}",
+                total.edits,
+                total.rewrites,
+                total.rewrite_rate(),
+                hotspots.len()
+            );
+        }
+        OutputFormat::Text => {
+            println!("Edit Churn");
+            println!("{}", "=".repeat(40));
+            println!();
+            if total.edits == 0 {
+                println!("  No edits in {} sessions.", format_count(sessions.len()));
+                return Ok(());
+            }
+            println!(
+                "  Edits:         {} in {} of {} sessions",
+                format_count(total.edits),
+                format_count(churned.len()),
+                format_count(sessions.len())
+            );
+            println!(
+                "  Rewrites:      {} ({:.1}% of edits)",
+                format_count(total.rewrites),
+                total.rewrite_rate()
+            );
+
+            println!();
+            if hotspots.is_empty() {
+                println!(
+                    "No hotspots (files with {}+ rewrites in a session).",
+                    EditChurn::HOTSPOT_REWRITES
+                );
+                return Ok(());
+            }
+            println!("Hotspots:");
+            let limit = if args.all { usize::MAX } else { 10 };
+            for (session, file, stats) in hotspots.iter().take(limit) {
+                println!(
+                    "  {}  {:>3} rewrites / {:>3} edits  {}",
+                    &session.session_id()[..8.min(session.session_id().len())],
+                    stats.rewrites,
+                    stats.edits,
+                    truncate_path(file, 50)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One file node of the co-modification graph, for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct CoModificationNodeOutput<'a> {
+    file: &'a str,
+    sessions: usize,
+}
+
+/// One weighted edge of the co-modification graph, for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct CoModificationEdgeOutput<'a> {
+    source: &'a str,
+    target: &'a str,
+    weight: usize,
+}
+
+/// `stats --co-modification` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct CoModificationOutput<'a> {
+    sessions: usize,
+    nodes: Vec<CoModificationNodeOutput<'a>>,
+    edges: Vec<CoModificationEdgeOutput<'a>>,
+}
+
+/// File pairs shown in the text report unless `--all` is given.
+const CO_MODIFICATION_EDGE_LIMIT: usize = 15;
+
+/// Output the graph of files modified together across sessions.
+fn output_co_modification(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let mut graph = CoModificationGraph::default();
+    for (_, analytics) in analyze_sessions_parallel(sessions, cli.max_file_size) {
+        graph.add_session(analytics.file_stats.files.keys().map(String::as_str));
+    }
+    let edges = graph.ranked_edges();
+
+    if args.format == Some(StatsFormat::Dot) {
+        print!("{}", graph.to_dot());
+        return Ok(());
+    }
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = CoModificationOutput {
+                sessions: graph.sessions,
+                nodes: graph
+                    .nodes
+                    .iter()
+                    .map(|(file, sessions)| CoModificationNodeOutput {
+                        file,
+                        sessions: *sessions,
+                    })
+                    .collect(),
+                edges: edges
+                    .iter()
+                    .map(|(source, target, weight)| CoModificationEdgeOutput {
+                        source,
+                        target,
+                        weight: *weight,
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_CO_MODIFICATION_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for (source, target, weight) in &edges {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        (*source).to_string(),
+                        (*target).to_string(),
+                        weight.to_string(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            println!(
+                "files:{} pairs:{} sessions:{}",
+                graph.nodes.len(),
+                edges.len(),
+                graph.sessions
+            );
+        }
+        OutputFormat::Text => {
+            println!("File Co-Modification");
+            println!("{}", "=".repeat(40));
+            println!();
+            if edges.is_empty() {
+                println!(
+                    "  No files modified together in {} sessions.",
+                    format_count(sessions.len())
+                );
+                return Ok(());
+            }
+            println!(
+                "  {} files, {} pairs across {} sessions with modifications",
+                format_count(graph.nodes.len()),
+                format_count(edges.len()),
+                format_count(graph.sessions)
+            );
+            println!();
+            println!("Modified Together Most Often:");
+            let limit = if args.all {
+                usize::MAX
+            } else {
+                CO_MODIFICATION_EDGE_LIMIT
+            };
+            for (source, target, weight) in edges.iter().take(limit) {
+                println!(
+                    "  {weight:>4} sessions  {}  <->  {}",
+                    truncate_path(source, 40),
+                    truncate_path(target, 40)
+                );
+            }
+            println!();
+            println!("Export with --format dot for Graphviz, or -o json.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Transitions shown in the text report unless `--all` is given.
+const TOOL_TRANSITION_LIMIT: usize = 15;
+/// Workflow chains shown in the text report unless `--all` is given.
+const TOOL_CHAIN_LIMIT: usize = 10;
+
+/// One tool-to-tool transition for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ToolTransitionOutput<'a> {
+    from: &'a str,
+    to: &'a str,
+    count: usize,
+    /// Share of `from`'s outgoing transitions that go to `to`.
+    probability: f64,
+}
+
+/// One multi-step workflow for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ToolChainOutput<'a> {
+    tools: &'a [String],
+    count: usize,
+}
+
+/// `stats --tool-chains` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ToolChainsOutput<'a> {
+    sessions: usize,
+    transitions: Vec<ToolTransitionOutput<'a>>,
+    chains: Vec<ToolChainOutput<'a>>,
+}
+
+/// Collect tool call sequences across sessions in parallel.
+fn collect_tool_chains(sessions: &[Session], max_file_size: Option<u64>) -> ToolChainStats {
+    sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            Some(ToolChainStats::from_conversation(&conversation))
+        })
+        .reduce(ToolChainStats::default, |mut acc, chains| {
+            acc.merge(&chains);
+            acc
+        })
+}
+
+/// Output tool call transitions and common workflows.
+fn output_tool_chains(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let stats = collect_tool_chains(sessions, cli.max_file_size);
+    let transitions = stats.top_transitions(usize::MAX);
+    let chains = stats.top_chains(usize::MAX);
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = ToolChainsOutput {
+                sessions: stats.sessions,
+                transitions: transitions
+                    .iter()
+                    .map(|&(from, to, count)| ToolTransitionOutput {
+                        from,
+                        to,
+                        count,
+                        probability: stats.probability(from, to),
+                    })
+                    .collect(),
+                chains: chains
+                    .iter()
+                    .map(|&(tools, count)| ToolChainOutput { tools, count })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_TOOL_CHAIN_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for &(from, to, count) in &transitions {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        from.to_string(),
+                        to.to_string(),
+                        count.to_string(),
+                        format!("{:.4}", stats.probability(from, to)),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            let top = chains
+                .first()
+                .map_or_else(|| "-".to_string(), |(tools, _)| tools.join(">"));
+            println!(
+                "sessions:{} transitions:{} top_chain:{top}",
+                stats.sessions,
+                transitions.len()
+            );
+        }
+        OutputFormat::Text => {
+            if transitions.is_empty() {
+                if !cli.quiet {
+                    println!("No tool call sequences found.");
+                }
+                return Ok(());
+            }
+
+            println!(
+                "Tool Call Transitions ({} sessions)",
+                format_count(stats.sessions)
+            );
+            println!("{}", "=".repeat(40));
+            println!();
+            println!(
+                "  {:<20} {:<20} {:>8} {:>8}",
+                "From", "To", "Count", "P(next)"
+            );
+            let limit = if args.all {
+                usize::MAX
+            } else {
+                TOOL_TRANSITION_LIMIT
+            };
+            for &(from, to, count) in transitions.iter().take(limit) {
+                println!(
+                    "  {:<20} {:<20} {:>8} {:>7.0}%",
+                    truncate_path(from, 20),
+                    truncate_path(to, 20),
+                    format_count(count),
+                    stats.probability(from, to) * 100.0
+                );
+            }
+
+            if !chains.is_empty() {
+                println!();
+                println!("Common Workflows:");
+                let limit = if args.all {
+                    usize::MAX
+                } else {
+                    TOOL_CHAIN_LIMIT
+                };
+                for &(tools, count) in chains.iter().take(limit) {
+                    println!("  {:>6}  {}", format_count(count), tools.join(" → "));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Line changes for one language, for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct LanguageOutput {
+    language: String,
+    modifications: usize,
+    lines_added: usize,
+    lines_removed: usize,
+    net_lines: i64,
+}
+
+/// Per-project language breakdown for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct ProjectLanguagesOutput {
+    project: String,
+    languages: Vec<LanguageOutput>,
+}
+
+/// `stats --languages` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct LanguageStatsOutput {
+    languages: Vec<LanguageOutput>,
+    projects: Vec<ProjectLanguagesOutput>,
+}
+
+fn language_outputs(languages: &IndexMap<String, LanguageStats>) -> Vec<LanguageOutput> {
+    rank_languages(languages)
+        .into_iter()
+        .map(|(language, stats)| LanguageOutput {
+            language: language.to_string(),
+            modifications: stats.modifications,
+            lines_added: stats.lines_added,
+            lines_removed: stats.lines_removed,
+            net_lines: stats.net_lines(),
+        })
+        .collect()
+}
+
+/// Output lines changed per programming language.
+fn output_language_stats(
+    cli: &Cli,
+    args: &StatsArgs,
+    combined: &ProjectAnalytics,
+    by_project: &IndexMap<String, ProjectAnalytics>,
+) -> Result<()> {
+    let projects: Vec<(&String, &ProjectAnalytics)> = by_project
+        .iter()
+        .filter(|(_, analytics)| !analytics.languages.is_empty())
+        .collect();
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = LanguageStatsOutput {
+                languages: language_outputs(&combined.languages),
+                projects: projects
+                    .iter()
+                    .map(|(project, analytics)| ProjectLanguagesOutput {
+                        project: (*project).clone(),
+                        languages: language_outputs(&analytics.languages),
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_LANGUAGE_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            let scopes = std::iter::once(("all", &combined.languages)).chain(
+                projects
+                    .iter()
+                    .map(|(project, analytics)| (project.as_str(), &analytics.languages)),
+            );
+            for (scope, languages) in scopes {
+                for (language, stats) in rank_languages(languages) {
+                    table.write_row(
+                        &mut stdout,
+                        &[
+                            scope.to_string(),
+                            language.to_string(),
+                            stats.modifications.to_string(),
+                            stats.lines_added.to_string(),
+                            stats.lines_removed.to_string(),
+                            stats.net_lines().to_string(),
+                        ],
+                    )?;
+                }
+            }
+        }
+        OutputFormat::Compact => {
+            let top: Vec<String> = rank_languages(&combined.languages)
+                .into_iter()
+                .take(5)
+                .map(|(language, stats)| format!("{language}:{}", stats.lines_changed()))
+                .collect();
+            println!("languages:{} {}", combined.languages.len(), top.join(" "));
+        }
+        OutputFormat::Text => {
+            if combined.languages.is_empty() {
+                if !cli.quiet {
+                    println!("No file modifications found.");
+                }
+                return Ok(());
+            }
+
+            println!("Languages (by lines changed)");
+            println!("{}", "=".repeat(40));
+            println!();
+            println!(
+                "  {:<14} {:>8} {:>10} {:>10} {:>10}",
+                "Language", "Edits", "Added", "Removed", "Net"
+            );
+            for (language, stats) in rank_languages(&combined.languages) {
+                println!(
+                    "  {:<14} {:>8} {:>10} {:>10} {:>+10}",
+                    language,
+                    format_count(stats.modifications),
+                    format_count(stats.lines_added),
+                    format_count(stats.lines_removed),
+                    stats.net_lines()
+                );
+            }
+
+            if projects.len() > 1 {
+                let shown = if args.all { projects.len() } else { 10 };
+                println!();
+                println!("By Project:");
+                for (project, analytics) in projects.iter().take(shown) {
+                    let top: Vec<String> = rank_languages(&analytics.languages)
+                        .into_iter()
+                        .take(3)
+                        .map(|(language, stats)| {
+                            format!(
+                                "{language} +{}/-{}",
+                                format_count(stats.lines_added),
+                                format_count(stats.lines_removed)
+                            )
+                        })
+                        .collect();
+                    println!("  {}: {}", truncate_path(project, 40), top.join(", "));
+                }
+                if projects.len() > shown {
+                    println!(
+                        "  ... and {} more projects (use --all to show every project)",
+                        projects.len() - shown
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One agent bucket of `stats --agents` for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct AgentUsageOutput {
+    agent: String,
+    sessions: usize,
+    tokens: u64,
+    estimated_cost: Option<f64>,
+    tool_calls: usize,
+    /// Share of all work tokens, as a percentage.
+    token_share: f64,
+}
+
+/// Parent vs subagent attribution for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct AgentStatsOutput {
+    parent: AgentUsageOutput,
+    subagents: AgentUsageOutput,
+    by_agent_type: Vec<AgentUsageOutput>,
+}
+
+impl AgentUsageOutput {
+    fn new(agent: &str, usage: &AgentUsage, total_tokens: u64) -> Self {
+        Self {
+            agent: agent.to_string(),
+            sessions: usage.sessions,
+            tokens: usage.tokens,
+            estimated_cost: usage.estimated_cost,
+            tool_calls: usage.tool_calls,
+            token_share: if total_tokens > 0 {
+                usage.tokens as f64 / total_tokens as f64 * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Attribute usage to parent sessions and subagent types in parallel.
+fn collect_agent_breakdown(sessions: &[Session], max_file_size: Option<u64>) -> AgentBreakdown {
+    sessions
+        .par_iter()
+        .filter_map(|session| {
+            let entries = session.parse_with_options(max_file_size).ok()?;
+            let conversation = Conversation::from_entries(entries).ok()?;
+            let analytics = SessionAnalytics::from_conversation(&conversation);
+            let agent_type = session.effective_is_subagent().then(|| {
+                session
+                    .subagent_type()
+                    .unwrap_or_else(|| AgentBreakdown::UNKNOWN_AGENT.to_string())
+            });
+            let mut breakdown = AgentBreakdown::default();
+            breakdown.add_session(agent_type.as_deref(), &analytics);
+            Some(breakdown)
+        })
+        .reduce(AgentBreakdown::default, |mut acc, breakdown| {
+            acc.merge(&breakdown);
+            acc
+        })
+}
+
+/// Output the parent vs subagent usage attribution.
+fn output_agent_stats(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let breakdown = collect_agent_breakdown(sessions, cli.max_file_size);
+    let subagents = breakdown.subagent_total();
+    let total_tokens = breakdown.parent.tokens + subagents.tokens;
+    let ranked = breakdown.ranked();
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = AgentStatsOutput {
+                parent: AgentUsageOutput::new("parent", &breakdown.parent, total_tokens),
+                subagents: AgentUsageOutput::new("subagents", &subagents, total_tokens),
+                by_agent_type: ranked
+                    .iter()
+                    .map(|(agent, usage)| AgentUsageOutput::new(agent, usage, total_tokens))
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_AGENT_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            let rows = std::iter::once(("parent", &breakdown.parent)).chain(ranked.iter().copied());
+            for (agent, usage) in rows {
+                let row = AgentUsageOutput::new(agent, usage, total_tokens);
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        row.agent,
+                        row.sessions.to_string(),
+                        row.tokens.to_string(),
+                        row.estimated_cost
+                            .map(|cost| format!("{cost:.4}"))
+                            .unwrap_or_default(),
+                        row.tool_calls.to_string(),
+                        format!("{:.1}", row.token_share),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            println!(
+                "parent:{} subagents:{} ({:.1}%) agent_types:{}",
+                format_number(breakdown.parent.tokens),
+                format_number(subagents.tokens),
+                breakdown.subagent_share(),
+                ranked.len()
+            );
+        }
+        OutputFormat::Text => {
+            if breakdown.parent.sessions == 0 && ranked.is_empty() {
+                if !cli.quiet {
+                    println!("No session activity found.");
+                }
+                return Ok(());
+            }
+
+            let cost = |usage: &AgentUsage| {
+                usage
+                    .estimated_cost
+                    .map(|cost| format!("${cost:.2}"))
+                    .unwrap_or_else(|| "N/A".to_string())
+            };
+            let print_row = |agent: &str, usage: &AgentUsage| {
+                let share = AgentUsageOutput::new(agent, usage, total_tokens).token_share;
+                println!(
+                    "  {:<24} {:>8} {:>14} {:>10} {:>8} {:>6.1}%",
+                    truncate_path(agent, 24),
+                    format_count(usage.sessions),
+                    format_number(usage.tokens),
+                    cost(usage),
+                    format_count(usage.tool_calls),
+                    share
+                );
+            };
+
+            println!("Agent Usage");
+            println!("{}", "=".repeat(40));
+            println!();
+            println!(
+                "  {:<24} {:>8} {:>14} {:>10} {:>8} {:>7}",
+                "Agent", "Sessions", "Tokens", "Cost", "Tools", "Share"
+            );
+            print_row("parent", &breakdown.parent);
+            for (agent, usage) in &ranked {
+                print_row(agent, usage);
+            }
+            println!();
+            println!(
+                "Subagents: {} tokens ({:.1}% of work tokens), {} cost, {} tool calls",
+                format_number(subagents.tokens),
+                breakdown.subagent_share(),
+                cost(&subagents),
+                format_count(subagents.tool_calls)
+            );
+            if breakdown
+                .by_agent_type
+                .contains_key(AgentBreakdown::INLINE_SIDECHAIN)
+            {
+                println!(
+                    "Note: '{}' is subagent work logged inline in parent transcripts.",
+                    AgentBreakdown::INLINE_SIDECHAIN
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Token breakdown entry for graph visualization.
+#[derive(Debug, Clone, serde::Serialize)]
+struct TokenBreakdown {
+    period: String,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_write_tokens: u64,
+    total_tokens: u64,
+}
+
+/// Token graph output for JSON serialization.
+#[derive(Debug, serde::Serialize)]
+struct TokenGraphOutput {
+    granularity: String,
+    data: Vec<TokenBreakdown>,
+    totals: TokenBreakdown,
+}
+
+/// Collect token breakdown data from sessions.
+fn collect_token_breakdown(
+    sessions: &[Session],
+    granularity: &str,
+    max_file_size: Option<u64>,
+) -> Vec<TokenBreakdown> {
+    use std::collections::BTreeMap;
+
+    let mut buckets: BTreeMap<String, TokenBreakdown> = BTreeMap::new();
+
+    for session in sessions {
+        let entries = match session.parse_with_options(max_file_size) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        // Get session start time
+        let start_time = entries.iter().find_map(|e| e.timestamp());
+        let start_time = match start_time {
+            Some(t) => t,
+            None => continue,
+        };
+
+        // Determine bucket key based on granularity
+        let bucket_key = match granularity {
+            "hourly" => start_time.format("%Y-%m-%d %H:00").to_string(),
+            "weekly" => {
+                let week = start_time.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            _ => start_time.format("%Y-%m-%d").to_string(), // daily default
+        };
+
+        // Calculate session metrics
+        let conv = match Conversation::from_entries(entries) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let analytics = SessionAnalytics::from_conversation(&conv);
+        let usage = &analytics.usage.usage;
+
+        let entry = buckets
+            .entry(bucket_key.clone())
+            .or_insert_with(|| TokenBreakdown {
+                period: bucket_key,
+                input_tokens: 0,
+                output_tokens: 0,
+                cache_read_tokens: 0,
+                cache_write_tokens: 0,
+                total_tokens: 0,
+            });
+
+        entry.input_tokens += usage.input_tokens;
+        entry.output_tokens += usage.output_tokens;
+        entry.cache_read_tokens += usage.cache_read_input_tokens.unwrap_or(0);
+        entry.cache_write_tokens += usage.cache_creation_input_tokens.unwrap_or(0);
+        entry.total_tokens += usage.total_tokens();
+    }
+
+    buckets.into_values().collect()
+}
+
+/// Output token usage graph visualization.
+fn output_token_graph(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Result<()> {
+    let data = collect_token_breakdown(sessions, &args.granularity, cli.max_file_size);
+
+    if data.is_empty() {
+        if !cli.quiet {
+            println!("No token data found.");
+        }
+        return Ok(());
+    }
+
+    // Calculate totals
+    let totals = TokenBreakdown {
+        period: "Total".to_string(),
+        input_tokens: data.iter().map(|d| d.input_tokens).sum(),
+        output_tokens: data.iter().map(|d| d.output_tokens).sum(),
+        cache_read_tokens: data.iter().map(|d| d.cache_read_tokens).sum(),
+        cache_write_tokens: data.iter().map(|d| d.cache_write_tokens).sum(),
+        total_tokens: data.iter().map(|d| d.total_tokens).sum(),
+    };
+
+    match cli.effective_output() {
+        OutputFormat::Json => {
+            let output = TokenGraphOutput {
+                granularity: args.granularity.clone(),
+                data,
+                totals,
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
+            let table = Table::with_all(format, STATS_GRAPH_COLUMNS, &args.table)?;
+            let mut stdout = std::io::stdout().lock();
+            table.write_header(&mut stdout)?;
+            for entry in &data {
+                table.write_row(
+                    &mut stdout,
+                    &[
+                        entry.period.clone(),
+                        entry.input_tokens.to_string(),
+                        entry.output_tokens.to_string(),
+                        entry.cache_read_tokens.to_string(),
+                        entry.cache_write_tokens.to_string(),
+                        entry.total_tokens.to_string(),
+                    ],
+                )?;
+            }
+        }
+        OutputFormat::Compact => {
+            // Sparklines for each token type
+            let input_vals: Vec<u64> = data.iter().map(|d| d.input_tokens).collect();
+            let output_vals: Vec<u64> = data.iter().map(|d| d.output_tokens).collect();
+            println!(
+                "periods:{} in:{} out:{} input:{} output:{}",
+                data.len(),
+                format_number(totals.input_tokens),
+                format_number(totals.output_tokens),
+                sparkline_u64(&input_vals),
+                sparkline_u64(&output_vals)
+            );
+        }
+        OutputFormat::Text => {
+            let granularity_label = match args.granularity.as_str() {
+                "hourly" => "Hourly",
+                "weekly" => "Weekly",
+                _ => "Daily",
+            };
+
+            println!("{} Token Usage Graph", granularity_label);
+            println!("{}", "=".repeat(35));
+            println!();
+
+            // Summary
             println!("Token Breakdown Summary");
             println!("-----------------------");
             println!(
@@ -2414,8 +5002,8 @@ fn output_token_graph(cli: &Cli, args: &StatsArgs, sessions: &[Session]) -> Resu
 }
 
 /// Display budget status if configured.
-fn output_budget_status(cli: &Cli) -> Result<()> {
-    let config = Config::load().unwrap_or_default();
+fn output_budget_status(cli: &Cli, args: &StatsArgs) -> Result<()> {
+    let config = super::index::load_config(cli);
 
     if !config.budget.has_limits() || !config.budget.show_in_stats {
         return Ok(());
@@ -2467,18 +5055,28 @@ fn output_budget_status(cli: &Cli) -> Result<()> {
                 );
             }
         }
-        OutputFormat::Tsv => {
+        format @ (OutputFormat::Tsv | OutputFormat::Csv) => {
             if has_alerts {
-                println!("period\tspent\tlimit\tpercent_used\tstatus");
+                // A trailing table of its own: `--columns` names the primary
+                // stats columns, so only `--no-header` applies here.
+                let table_args = TableArgs {
+                    columns: None,
+                    no_header: args.table.no_header,
+                };
+                let table = Table::with_all(format, STATS_BUDGET_COLUMNS, &table_args)?;
+                let mut stdout = std::io::stdout().lock();
+                table.write_header(&mut stdout)?;
                 for alert in status.alerts() {
-                    println!(
-                        "{}\t{:.2}\t{:.2}\t{:.1}\t{}",
-                        alert.period,
-                        alert.spent,
-                        alert.limit,
-                        alert.percent_used * 100.0,
-                        alert.status_indicator()
-                    );
+                    table.write_row(
+                        &mut stdout,
+                        &[
+                            alert.period.to_string(),
+                            format!("{:.2}", alert.spent),
+                            format!("{:.2}", alert.limit),
+                            format!("{:.1}", alert.percent_used * 100.0),
+                            alert.status_indicator().to_string(),
+                        ],
+                    )?;
                 }
             }
         }
@@ -2644,6 +5242,11 @@ mod tests {
             estimated_cost: Some(0.05),
             pricing_policy: None,
             unpriced_models: Vec::new(),
+            cache_savings: None,
+            thinking: None,
+            model_switches: None,
+            streaks: None,
+            projects: None,
         };
 
         let json = serde_json::to_string(&output).unwrap();
@@ -187,7 +187,7 @@ pub fn run(cli: &Cli, args: &SummaryArgs) -> Result<()> {
         return run_provider(cli, args);
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let duration = parse_period(&args.period)?;
     let cutoff = Utc::now() - duration;
 
@@ -274,7 +274,7 @@ pub fn run(cli: &Cli, args: &SummaryArgs) -> Result<()> {
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("metric\tvalue");
             println!("period\t{}", args.period);
             println!("projects\t{num_projects}");
@@ -555,7 +555,7 @@ fn run_provider(cli: &Cli, args: &SummaryArgs) -> Result<()> {
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("metric\tvalue");
             println!("period\t{}", args.period);
             println!("period_basis\tlogical_sessions_native_or_source_activity_whole_artifacts");
@@ -682,7 +682,7 @@ fn run_provider(cli: &Cli, args: &SummaryArgs) -> Result<()> {
 /// This provides a brief overview without requiring arguments, suitable for
 /// piping or scripting contexts.
 pub fn run_quick_summary(cli: &Cli) -> Result<()> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     // Use 24h default period
     let duration = Duration::hours(24);
@@ -711,7 +711,7 @@ pub fn run_quick_summary(cli: &Cli) -> Result<()> {
             });
             println!("{}", serde_json::to_string(&output)?);
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("sessions_24h\tprojects_24h\ttotal_sessions");
             println!(
                 "{}\t{}\t{}",
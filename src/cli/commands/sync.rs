@@ -0,0 +1,638 @@
+//! Sync command implementation.
+//!
+//! Incremental backup of the Claude directory's session data: `projects/`,
+//! `file-history/`, and imported conversations. Each run copies files that
+//! are new or changed since the last run and records every copied file's
+//! size, modification time, and content hash in `snatch-sync.json` at the
+//! backup root. Unchanged size and mtime skip the file without reading it;
+//! otherwise the content hash decides.
+//!
+//! Files removed locally stay in the backup. `--verify` re-hashes the backup
+//! against the manifest, and `--restore` copies files back, leaving local
+//! files that differ from the backup alone unless `--overwrite` is given.
+//!
+//! The hashes are a checksum against accidental corruption, not a tamper
+//! check: anyone who can edit the backup can update the manifest to match.
+//! Manifest keys that are not plain relative paths are rejected, so a
+//! damaged or hostile manifest cannot make `--restore` write outside the
+//! Claude directory.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{Cli, OutputFormat, SyncArgs};
+use crate::discovery::{format_size, ClaudeDirectory};
+use crate::error::{Result, SnatchError};
+use crate::util::{atomic_write, atomic_write_with};
+
+use super::cleanup::purge_derived_data;
+use super::get_claude_dir;
+
+/// Manifest file name at the backup root.
+const MANIFEST_NAME: &str = "snatch-sync.json";
+
+/// Manifest format version.
+const MANIFEST_VERSION: u32 = 1;
+
+/// The backup manifest.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    /// Claude directory the backup was made from.
+    source: PathBuf,
+    /// Time of the last completed sync.
+    updated: Option<DateTime<Utc>>,
+    /// Backed-up files by path relative to the Claude directory, with `/`
+    /// separators.
+    files: BTreeMap<String, FileRecord>,
+}
+
+/// What the manifest knows about one backed-up file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileRecord {
+    size: u64,
+    /// Modification time of the source, in milliseconds since the epoch.
+    modified: i64,
+    /// FNV-1a hash of the content, as 16 hex digits. Detects corruption,
+    /// not tampering.
+    hash: String,
+}
+
+/// What one run did to one file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Action {
+    New,
+    Updated,
+    Unchanged,
+    Restored,
+    Conflict,
+    Missing,
+    Corrupt,
+}
+
+/// Run summary (`-o json`).
+#[derive(Debug, Default, Serialize)]
+struct SyncReport {
+    dest: PathBuf,
+    preview: bool,
+    /// File counts by action.
+    counts: BTreeMap<&'static str, usize>,
+    /// Bytes copied (or that would be copied).
+    bytes: u64,
+    /// Files that were not unchanged, with their action.
+    files: Vec<(String, Action)>,
+}
+
+impl SyncReport {
+    fn record(&mut self, path: &str, action: Action, size: u64) {
+        *self.counts.entry(action_name(action)).or_default() += 1;
+        if matches!(action, Action::New | Action::Updated | Action::Restored) {
+            self.bytes += size;
+        }
+        if action != Action::Unchanged {
+            self.files.push((path.to_string(), action));
+        }
+    }
+
+    fn count(&self, action: Action) -> usize {
+        self.counts.get(action_name(action)).copied().unwrap_or(0)
+    }
+}
+
+/// Run the sync command.
+pub fn run(cli: &Cli, args: &SyncArgs) -> Result<()> {
+    if args.verify {
+        return verify(cli, args);
+    }
+    let claude_dir = get_claude_dir(cli)?;
+    let dest = std::path::absolute(&args.dest)
+        .map_err(|e| SnatchError::io(args.dest.display().to_string(), e))?;
+    if source_roots(&claude_dir)
+        .iter()
+        .any(|root| dest.starts_with(root))
+    {
+        return Err(SnatchError::InvalidArgument {
+            name: "--dest".to_string(),
+            reason: "the backup cannot live inside the directories it backs up".to_string(),
+        });
+    }
+    if args.restore {
+        restore(cli, args, &claude_dir)
+    } else {
+        backup(cli, args, &claude_dir)
+    }
+}
+
+/// Copy new and changed files into the backup.
+fn backup(cli: &Cli, args: &SyncArgs, claude_dir: &ClaudeDirectory) -> Result<()> {
+    let source = claude_dir.root().to_path_buf();
+    let mut manifest = match load_manifest(&args.dest)? {
+        Some(manifest) if manifest.source != source => {
+            return Err(SnatchError::InvalidArgument {
+                name: "--dest".to_string(),
+                reason: format!(
+                    "{} holds a backup of {}; use another destination for {}",
+                    args.dest.display(),
+                    manifest.source.display(),
+                    source.display()
+                ),
+            });
+        }
+        Some(manifest) => manifest,
+        None => Manifest {
+            version: MANIFEST_VERSION,
+            source: source.clone(),
+            updated: None,
+            files: BTreeMap::new(),
+        },
+    };
+
+    let mut report = SyncReport {
+        dest: args.dest.clone(),
+        preview: args.preview,
+        ..SyncReport::default()
+    };
+    let mut seen = BTreeSet::new();
+    for (relative, path) in source_files(claude_dir) {
+        let metadata =
+            fs::metadata(&path).map_err(|e| SnatchError::io(path.display().to_string(), e))?;
+        let size = metadata.len();
+        let modified = metadata.modified().map_or(0, millis_since_epoch);
+        let target = args.dest.join(&relative);
+        let previous = manifest.files.get(&relative);
+        if previous.is_some_and(|r| r.size == size && r.modified == modified) && target.exists() {
+            report.record(&relative, Action::Unchanged, size);
+            seen.insert(relative);
+            continue;
+        }
+
+        let hash = hash_file(&path)?;
+        if previous.is_some_and(|r| r.hash == hash) && target.exists() {
+            // Touched but not changed: remember the new mtime only.
+            report.record(&relative, Action::Unchanged, size);
+            if let Some(record) = manifest.files.get_mut(&relative) {
+                record.modified = modified;
+            }
+            seen.insert(relative);
+            continue;
+        }
+        let action = if previous.is_some() {
+            Action::Updated
+        } else {
+            Action::New
+        };
+        if !args.preview {
+            // Hash what was actually copied, in case the file grew since.
+            let (size, hash) = copy_file(&path, &target)?;
+            set_modified(&target, metadata.modified().ok());
+            manifest.files.insert(
+                relative.clone(),
+                FileRecord {
+                    size,
+                    modified,
+                    hash,
+                },
+            );
+        }
+        report.record(&relative, action, size);
+        seen.insert(relative);
+    }
+    let kept = manifest
+        .files
+        .keys()
+        .filter(|key| !seen.contains(*key))
+        .count();
+
+    if !args.preview {
+        manifest.updated = Some(Utc::now());
+        let json = serde_json::to_vec_pretty(&manifest)?;
+        atomic_write(args.dest.join(MANIFEST_NAME), &json)?;
+    }
+
+    if matches!(cli.effective_output(), OutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if !cli.quiet {
+        print_files(&report);
+        println!(
+            "{} {} new, {} updated ({}); {} unchanged",
+            if args.preview {
+                "Would back up"
+            } else {
+                "Backed up"
+            },
+            report.count(Action::New),
+            report.count(Action::Updated),
+            format_size(report.bytes),
+            report.count(Action::Unchanged),
+        );
+        if kept > 0 {
+            println!("{kept} file(s) deleted locally are kept in the backup");
+        }
+        if args.preview {
+            println!("Preview only; run without --preview to copy.");
+        }
+    }
+    Ok(())
+}
+
+/// Re-hash every backed-up file against the manifest.
+fn verify(cli: &Cli, args: &SyncArgs) -> Result<()> {
+    let manifest = require_manifest(&args.dest)?;
+    let mut report = SyncReport {
+        dest: args.dest.clone(),
+        ..SyncReport::default()
+    };
+    for (relative, record) in &manifest.files {
+        let path = args.dest.join(relative);
+        let action = if !path.exists() {
+            Action::Missing
+        } else if hash_file(&path)? == record.hash {
+            Action::Unchanged
+        } else {
+            Action::Corrupt
+        };
+        report.record(relative, action, record.size);
+    }
+
+    let problems = report.count(Action::Missing) + report.count(Action::Corrupt);
+    if matches!(cli.effective_output(), OutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if !cli.quiet {
+        print_files(&report);
+        println!(
+            "Verified {} file(s): {} intact, {} missing, {} corrupt",
+            manifest.files.len(),
+            report.count(Action::Unchanged),
+            report.count(Action::Missing),
+            report.count(Action::Corrupt),
+        );
+    }
+    if problems > 0 {
+        return Err(SnatchError::validation(format!(
+            "{problems} backed-up file(s) in {} failed verification",
+            args.dest.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Copy backed-up files into the Claude directory.
+fn restore(cli: &Cli, args: &SyncArgs, claude_dir: &ClaudeDirectory) -> Result<()> {
+    let manifest = require_manifest(&args.dest)?;
+    let root = claude_dir.root();
+
+    // (relative path, local path, backup path, record, overwrites)
+    let mut plan = Vec::new();
+    let mut report = SyncReport {
+        dest: args.dest.clone(),
+        preview: args.preview,
+        ..SyncReport::default()
+    };
+    for (relative, record) in &manifest.files {
+        let local = root.join(relative);
+        let backed_up = args.dest.join(relative);
+        if !backed_up.exists() {
+            report.record(relative, Action::Missing, record.size);
+            continue;
+        }
+        if !local.exists() {
+            plan.push((relative, local, backed_up, record, false));
+        } else if hash_file(&local)? == record.hash {
+            report.record(relative, Action::Unchanged, record.size);
+        } else if args.overwrite {
+            plan.push((relative, local, backed_up, record, true));
+        } else {
+            report.record(relative, Action::Conflict, record.size);
+        }
+    }
+
+    let json = matches!(cli.effective_output(), OutputFormat::Json);
+    if !args.preview && !plan.is_empty() && !args.yes {
+        let overwrites = plan.iter().filter(|step| step.4).count();
+        eprint!(
+            "Restore {} file(s) into {}{}? [y/N] ",
+            plan.len(),
+            root.display(),
+            if overwrites > 0 {
+                format!(", replacing {overwrites} local file(s)")
+            } else {
+                String::new()
+            }
+        );
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let answer = input.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            eprintln!("Restore cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut replaced = Vec::new();
+    for (relative, local, backed_up, record, overwrites) in plan {
+        if !args.preview {
+            let (_, hash) = copy_file(&backed_up, &local)?;
+            if hash != record.hash {
+                return Err(SnatchError::validation(format!(
+                    "{} does not match the manifest; run `snatch sync --verify --dest {}`",
+                    backed_up.display(),
+                    args.dest.display()
+                )));
+            }
+            set_modified(
+                &local,
+                u64::try_from(record.modified)
+                    .ok()
+                    .map(|ms| UNIX_EPOCH + Duration::from_millis(ms)),
+            );
+            if overwrites {
+                replaced.push(local);
+            }
+        }
+        report.record(relative, Action::Restored, record.size);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if !cli.quiet {
+        print_files(&report);
+        println!(
+            "{} {} file(s) ({}); {} already present, {} differ locally, {} missing from the backup",
+            if args.preview {
+                "Would restore"
+            } else {
+                "Restored"
+            },
+            report.count(Action::Restored),
+            format_size(report.bytes),
+            report.count(Action::Unchanged),
+            report.count(Action::Conflict),
+            report.count(Action::Missing),
+        );
+        if report.count(Action::Conflict) > 0 {
+            println!("Pass --overwrite to replace local files that differ from the backup.");
+        }
+    }
+    if !replaced.is_empty() {
+        let config = super::index::load_config(cli);
+        purge_derived_data(cli, &config, &replaced)?;
+    }
+    Ok(())
+}
+
+/// Every file under the synced roots, keyed by its path relative to the
+/// Claude directory, in a stable order.
+fn source_files(claude_dir: &ClaudeDirectory) -> Vec<(String, PathBuf)> {
+    let root = claude_dir.root();
+    let mut files = Vec::new();
+    for dir in source_roots(claude_dir) {
+        for entry in walkdir::WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|e| e.file_type().is_file())
+        {
+            let Ok(relative) = entry.path().strip_prefix(root) else {
+                continue;
+            };
+            files.push((manifest_key(relative), entry.into_path()));
+        }
+    }
+    files
+}
+
+/// Directories whose files are backed up.
+fn source_roots(claude_dir: &ClaudeDirectory) -> [&Path; 3] {
+    [
+        claude_dir.projects_dir(),
+        claude_dir.file_history_dir(),
+        claude_dir.imported_dir(),
+    ]
+}
+
+/// A relative path as a manifest key, with `/` separators on every platform.
+fn manifest_key(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn load_manifest(dest: &Path) -> Result<Option<Manifest>> {
+    let path = dest.join(MANIFEST_NAME);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(SnatchError::io(path.display().to_string(), e)),
+    };
+    let manifest: Manifest = serde_json::from_str(&text).map_err(|e| SnatchError::ConfigError {
+        message: format!("Invalid sync manifest {}: {e}", path.display()),
+    })?;
+    if manifest.version > MANIFEST_VERSION {
+        return Err(SnatchError::ConfigError {
+            message: format!(
+                "{} was written by a newer snatch (manifest version {})",
+                path.display(),
+                manifest.version
+            ),
+        });
+    }
+    // Keys are joined onto the backup and Claude directories; never let
+    // one escape them.
+    if let Some(key) = manifest.files.keys().find(|key| !is_plain_relative(key)) {
+        return Err(SnatchError::ConfigError {
+            message: format!(
+                "Invalid sync manifest {}: {key:?} is not a relative path inside the backup",
+                path.display()
+            ),
+        });
+    }
+    Ok(Some(manifest))
+}
+
+/// Whether a manifest key is a relative path of plain names only, with no
+/// root, prefix, `.` or `..`.
+fn is_plain_relative(key: &str) -> bool {
+    !key.is_empty()
+        && Path::new(key)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+fn require_manifest(dest: &Path) -> Result<Manifest> {
+    load_manifest(dest)?.ok_or_else(|| SnatchError::InvalidArgument {
+        name: "--dest".to_string(),
+        reason: format!(
+            "no {MANIFEST_NAME} in {}; run `snatch sync --dest {}` first",
+            dest.display(),
+            dest.display()
+        ),
+    })
+}
+
+fn print_files(report: &SyncReport) {
+    for (path, action) in &report.files {
+        println!("{:<9}  {path}", action_name(*action));
+    }
+}
+
+const fn action_name(action: Action) -> &'static str {
+    match action {
+        Action::New => "new",
+        Action::Updated => "updated",
+        Action::Unchanged => "unchanged",
+        Action::Restored => "restored",
+        Action::Conflict => "conflict",
+        Action::Missing => "missing",
+        Action::Corrupt => "corrupt",
+    }
+}
+
+/// Copy `from` to `to` atomically, returning the bytes copied and their hash.
+fn copy_file(from: &Path, to: &Path) -> Result<(u64, String)> {
+    let mut input = File::open(from)
+        .map_err(|e| SnatchError::io(format!("Failed to open {}", from.display()), e))?;
+    let mut hasher = Fnv1a::default();
+    let mut size = 0;
+    atomic_write_with(to, |out| {
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = input.read(&mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            hasher.update(&buf[..n]);
+            out.write_all(&buf[..n])?;
+            size += n as u64;
+        }
+    })?;
+    Ok((size, hasher.hex()))
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    hash_file_prefix(path, u64::MAX).map(|(_, hash)| hash)
+}
+
+/// Hash the first `limit` bytes of a file (all of it if shorter), returning
+/// the number of bytes hashed and the hash. Shared with `verify`, which
+/// checks that an append-only log still starts with what it recorded.
+pub(super) fn hash_file_prefix(path: &Path, limit: u64) -> Result<(u64, String)> {
+    let mut input = File::open(path)
+        .map_err(|e| SnatchError::io(format!("Failed to open {}", path.display()), e))?
+        .take(limit);
+    let mut hasher = Fnv1a::default();
+    let mut size = 0;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = input
+            .read(&mut buf)
+            .map_err(|e| SnatchError::io(format!("Failed to read {}", path.display()), e))?;
+        if n == 0 {
+            return Ok((size, hasher.hex()));
+        }
+        hasher.update(&buf[..n]);
+        size += n as u64;
+    }
+}
+
+/// Carry the source mtime over to a copy. Best effort: a copy with a fresh
+/// mtime is still a correct copy.
+fn set_modified(path: &Path, modified: Option<SystemTime>) {
+    if let (Some(modified), Ok(file)) = (modified, File::options().write(true).open(path)) {
+        let _ = file.set_modified(modified);
+    }
+}
+
+fn millis_since_epoch(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+}
+
+/// Streaming 64-bit FNV-1a, stable across platforms and releases so
+/// manifests stay comparable between snatch versions.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_file_hashes_what_it_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("a.jsonl");
+        fs::write(&from, b"{\"type\":\"user\"}\n").unwrap();
+        let to = dir.path().join("backup/projects/-p/a.jsonl");
+
+        let (size, hash) = copy_file(&from, &to).unwrap();
+        assert_eq!(size, 16);
+        assert_eq!(fs::read(&to).unwrap(), fs::read(&from).unwrap());
+        assert_eq!(hash, hash_file(&from).unwrap());
+        assert_ne!(hash, {
+            let mut other = Fnv1a::default();
+            other.update(b"{\"type\":\"user\"}");
+            other.hex()
+        });
+        // Known FNV-1a test vector.
+        let mut vector = Fnv1a::default();
+        vector.update(b"a");
+        assert_eq!(vector.hex(), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_manifest_keys_use_forward_slashes() {
+        let relative = Path::new("projects").join("-home-me").join("s.jsonl");
+        assert_eq!(manifest_key(&relative), "projects/-home-me/s.jsonl");
+    }
+
+    #[test]
+    fn test_manifest_rejects_escaping_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let record = r#"{"size":1,"modified":0,"hash":"0000000000000000"}"#;
+        let write_manifest = |key: &str| {
+            let text = format!(
+                r#"{{"version":1,"source":"/c","updated":null,"files":{{{}:{record}}}}}"#,
+                serde_json::to_string(key).unwrap()
+            );
+            fs::write(dir.path().join(MANIFEST_NAME), text).unwrap();
+        };
+
+        write_manifest("projects/-p/s.jsonl");
+        assert!(load_manifest(dir.path()).unwrap().is_some());
+        for key in [
+            "../evil.jsonl",
+            "projects/../../evil.jsonl",
+            "/etc/evil.jsonl",
+            "",
+        ] {
+            write_manifest(key);
+            assert!(load_manifest(dir.path()).is_err(), "{key:?}");
+        }
+    }
+}
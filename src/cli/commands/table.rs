@@ -0,0 +1,414 @@
+//! Delimited (TSV/CSV) table output with stable column sets.
+//!
+//! Tabular views in `list`, `stats`, and `search` declare their columns as a
+//! `&'static` slice. That slice is the documented contract: column names and
+//! their order only change with a changelog entry. Users can narrow or
+//! reorder it with `--columns` and drop the header row with `--no-header`.
+
+use std::io::Write;
+
+use crate::cli::{OutputFormat, TableArgs};
+use crate::error::{Result, SnatchError};
+
+/// Columns for `snatch list projects`.
+pub const LIST_PROJECT_COLUMNS: &[&str] = &["path", "encoded", "session_count"];
+
+/// Columns for `snatch list sessions --no-chain`.
+pub const LIST_SESSION_COLUMNS: &[&str] = &[
+    "session_id",
+    "project",
+    "size",
+    "modified",
+    "subagent",
+    "name",
+    "context",
+];
+
+/// Columns for `snatch list sessions` (resume chains collapsed).
+pub const LIST_CHAIN_COLUMNS: &[&str] = &[
+    "session_id",
+    "project",
+    "size",
+    "modified",
+    "subagent",
+    "name",
+    "member_count",
+    "latest_session_id",
+    "context",
+];
+
+/// Columns for the single-row `snatch stats` summaries (session, project,
+/// multi-project, and global scopes).
+pub const STATS_SUMMARY_COLUMNS: &[&str] = &[
+    "scope",
+    "projects",
+    "sessions",
+    "messages",
+    "input_tokens",
+    "output_tokens",
+    "cache_read_tokens",
+    "cache_creation_tokens",
+    "total_tokens",
+    "total_processed_tokens",
+    "tool_invocations",
+    "cache_hit_rate",
+    "estimated_cost",
+    "provider",
+    "qualified_id",
+    "pricing_policy",
+    "current_streak",
+    "longest_streak",
+    "active_days",
+    "avg_daily_sessions",
+];
+
+/// Columns for the no-scope `snatch stats` overview.
+pub const STATS_OVERVIEW_COLUMNS: &[&str] = &["projects", "sessions", "subagents", "total_size"];
+
+/// Columns for `snatch stats --blocks`.
+pub const STATS_BLOCK_COLUMNS: &[&str] = &[
+    "block_start",
+    "block_end",
+    "status",
+    "tokens",
+    "input",
+    "output",
+    "messages",
+    "tools",
+    "cost",
+];
+
+/// Columns for `snatch stats --history`.
+pub const STATS_HISTORY_COLUMNS: &[&str] = &[
+    "date",
+    "tokens",
+    "input_tokens",
+    "output_tokens",
+    "cache_read",
+    "cost",
+    "sessions",
+    "messages",
+];
+
+/// Columns for `snatch stats --weekly`.
+pub const STATS_WEEKLY_COLUMNS: &[&str] = &["period", "cost"];
+
+/// Columns for `snatch stats --monthly`.
+pub const STATS_MONTHLY_COLUMNS: &[&str] = &["month", "cost"];
+
+/// Columns for `snatch stats --timeline`.
+pub const STATS_TIMELINE_COLUMNS: &[&str] = &["period", "sessions", "tokens", "messages", "cost"];
+
+/// Columns for `snatch stats <session> --timeline` (one row per API turn).
+pub const STATS_TURN_COLUMNS: &[&str] = &[
+    "turn",
+    "timestamp",
+    "model",
+    "input_tokens",
+    "output_tokens",
+    "cache_read_tokens",
+    "cache_creation_tokens",
+    "context_tokens",
+    "cumulative_output_tokens",
+];
+
+/// Columns for `snatch stats --graph`.
+pub const STATS_GRAPH_COLUMNS: &[&str] = &[
+    "period",
+    "input",
+    "output",
+    "cache_read",
+    "cache_write",
+    "total",
+];
+
+/// Columns for `snatch stats --heatmap` (one row per weekday/hour cell).
+pub const STATS_HEATMAP_COLUMNS: &[&str] = &["weekday", "hour", "messages", "tokens"];
+
+/// Columns for `snatch stats --agents` (parent row first, then agent types).
+pub const STATS_AGENT_COLUMNS: &[&str] = &[
+    "agent",
+    "sessions",
+    "tokens",
+    "estimated_cost",
+    "tool_calls",
+    "token_share",
+];
+
+/// Columns for `snatch stats --languages` (`project` is `all` for the
+/// combined rows).
+pub const STATS_LANGUAGE_COLUMNS: &[&str] = &[
+    "project",
+    "language",
+    "modifications",
+    "lines_added",
+    "lines_removed",
+    "net_lines",
+];
+
+/// Columns for `snatch stats --correlation` (one row per length range).
+pub const STATS_CORRELATION_COLUMNS: &[&str] = &[
+    "prompt_length",
+    "prompts",
+    "avg_prompt_chars",
+    "avg_response_tokens",
+    "avg_latency_secs",
+];
+
+/// Columns for `snatch stats --tool-chains` (one row per transition).
+pub const STATS_TOOL_CHAIN_COLUMNS: &[&str] = &["from", "to", "count", "probability"];
+
+/// Columns for `snatch stats --branches` (one row per session with
+/// abandoned branches).
+pub const STATS_BRANCH_COLUMNS: &[&str] = &[
+    "session_id",
+    "project",
+    "branches",
+    "tokens",
+    "estimated_cost",
+    "avg_depth",
+    "max_depth",
+];
+
+/// Columns for `snatch stats --by-branch` (one row per git branch).
+pub const STATS_GIT_BRANCH_COLUMNS: &[&str] = &[
+    "branch",
+    "sessions",
+    "messages",
+    "tokens",
+    "estimated_cost",
+    "active_minutes",
+    "first_seen",
+    "last_seen",
+];
+
+/// Columns for `snatch stats --rolling` (one row per window).
+pub const STATS_ROLLING_COLUMNS: &[&str] = &[
+    "window",
+    "start",
+    "sessions",
+    "messages",
+    "tokens",
+    "input_tokens",
+    "output_tokens",
+    "cache_read_tokens",
+    "estimated_cost",
+];
+
+/// Columns for `snatch stats --errors` (one row per day with activity).
+pub const STATS_ERROR_COLUMNS: &[&str] = &["date", "errors", "turns", "error_rate"];
+
+/// Columns for `snatch stats --churn` (one row per hotspot file).
+pub const STATS_CHURN_COLUMNS: &[&str] = &[
+    "session_id",
+    "project",
+    "file",
+    "edits",
+    "rewrites",
+    "rewrite_rate",
+];
+
+/// Columns for `snatch stats --co-modification` (one row per file pair).
+pub const STATS_CO_MODIFICATION_COLUMNS: &[&str] = &["source", "target", "weight"];
+
+/// Columns for the budget alert table that trails global stats.
+pub const STATS_BUDGET_COLUMNS: &[&str] = &["period", "spent", "limit", "percent_used", "status"];
+
+/// Columns for `snatch search` match results.
+pub const SEARCH_RESULT_COLUMNS: &[&str] =
+    &["session", "project", "uuid", "type", "location", "line"];
+
+/// Columns for `snatch search` with several patterns and `--count`.
+pub const SEARCH_PATTERN_COLUMNS: &[&str] = &["pattern", "count"];
+
+/// Columns for `snatch search --patterns-tsv` counts.
+pub const SEARCH_PATTERN_TSV_COLUMNS: &[&str] = &["category", "subcategory", "label", "count"];
+
+/// Columns for `snatch index search` and indexed `snatch search` results.
+pub const INDEX_SEARCH_COLUMNS: &[&str] = &[
+    "provider", "session", "project", "entry", "type", "location", "score", "line",
+];
+
+/// Columns for `snatch search --semantic` results (best match first).
+pub const SEARCH_SEMANTIC_COLUMNS: &[&str] = &[
+    "source",
+    "session_id",
+    "project",
+    "uuid",
+    "type",
+    "timestamp",
+    "score",
+    "snippet",
+];
+
+/// A delimited table writer bound to one column set.
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: &'static [&'static str],
+    selected: Vec<usize>,
+    separator: char,
+    header: bool,
+}
+
+impl Table {
+    /// Create a table for `columns`.
+    ///
+    /// Without `--columns`, the `defaults` subset is emitted in declaration
+    /// order. An unknown column name is an error listing the valid ones.
+    pub fn new(
+        format: OutputFormat,
+        columns: &'static [&'static str],
+        defaults: &[&str],
+        args: &TableArgs,
+    ) -> Result<Self> {
+        let selected = match &args.columns {
+            Some(requested) => requested
+                .iter()
+                .map(|name| {
+                    let name = name.trim();
+                    columns.iter().position(|c| *c == name).ok_or_else(|| {
+                        SnatchError::InvalidArgument {
+                            name: "--columns".to_string(),
+                            reason: format!(
+                                "unknown column '{name}' (available: {})",
+                                columns.join(", ")
+                            ),
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => (0..columns.len())
+                .filter(|&i| defaults.contains(&columns[i]))
+                .collect(),
+        };
+
+        Ok(Self {
+            columns,
+            selected,
+            separator: if format == OutputFormat::Csv {
+                ','
+            } else {
+                '\t'
+            },
+            header: !args.no_header,
+        })
+    }
+
+    /// Create a table that emits every column by default.
+    pub fn with_all(
+        format: OutputFormat,
+        columns: &'static [&'static str],
+        args: &TableArgs,
+    ) -> Result<Self> {
+        Self::new(format, columns, columns, args)
+    }
+
+    /// Whether a column will be written, so callers can skip costly values.
+    pub fn includes(&self, column: &str) -> bool {
+        self.selected.iter().any(|&i| self.columns[i] == column)
+    }
+
+    /// Write the header row unless `--no-header` was given.
+    pub fn write_header<W: Write>(&self, writer: &mut W) -> Result<()> {
+        if self.header {
+            let names: Vec<&str> = self.selected.iter().map(|&i| self.columns[i]).collect();
+            self.write_fields(writer, &names)?;
+        }
+        Ok(())
+    }
+
+    /// Write one row. `values` are given in full column-set order.
+    pub fn write_row<W: Write>(&self, writer: &mut W, values: &[String]) -> Result<()> {
+        debug_assert_eq!(values.len(), self.columns.len());
+        let fields: Vec<&str> = self
+            .selected
+            .iter()
+            .map(|&i| values.get(i).map_or("", String::as_str))
+            .collect();
+        self.write_fields(writer, &fields)
+    }
+
+    fn write_fields<W: Write>(&self, writer: &mut W, fields: &[&str]) -> Result<()> {
+        let line: Vec<String> = fields.iter().map(|f| self.escape(f)).collect();
+        writeln!(writer, "{}", line.join(&self.separator.to_string()))?;
+        Ok(())
+    }
+
+    /// Make a field safe for the delimiter: TSV flattens tabs and line
+    /// breaks to spaces, CSV quotes per RFC 4180.
+    fn escape(&self, field: &str) -> String {
+        if self.separator == '\t' {
+            field.replace(['\t', '\n', '\r'], " ")
+        } else if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(columns: Option<&[&str]>, no_header: bool) -> TableArgs {
+        TableArgs {
+            columns: columns.map(|c| c.iter().map(ToString::to_string).collect()),
+            no_header,
+        }
+    }
+
+    fn render(table: &Table, rows: &[&[&str]]) -> String {
+        let mut out = Vec::new();
+        table.write_header(&mut out).unwrap();
+        for row in rows {
+            let row: Vec<String> = row.iter().map(ToString::to_string).collect();
+            table.write_row(&mut out, &row).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    const COLUMNS: &[&str] = &["a", "b", "c"];
+
+    #[test]
+    fn test_defaults_keep_declaration_order() {
+        let table =
+            Table::new(OutputFormat::Tsv, COLUMNS, &["c", "a"], &args(None, false)).unwrap();
+        assert_eq!(render(&table, &[&["1", "2", "3"]]), "a\tc\n1\t3\n");
+        assert!(!table.includes("b"));
+    }
+
+    #[test]
+    fn test_columns_select_and_reorder() {
+        let table =
+            Table::with_all(OutputFormat::Tsv, COLUMNS, &args(Some(&["c", "a"]), false)).unwrap();
+        assert_eq!(render(&table, &[&["1", "2", "3"]]), "c\ta\n3\t1\n");
+    }
+
+    #[test]
+    fn test_unknown_column_lists_available() {
+        let err = Table::with_all(OutputFormat::Tsv, COLUMNS, &args(Some(&["z"]), false))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown column 'z'"));
+        assert!(err.contains("a, b, c"));
+    }
+
+    #[test]
+    fn test_no_header() {
+        let table = Table::with_all(OutputFormat::Tsv, COLUMNS, &args(None, true)).unwrap();
+        assert_eq!(render(&table, &[&["1", "2", "3"]]), "1\t2\t3\n");
+    }
+
+    #[test]
+    fn test_escaping() {
+        let tsv = Table::with_all(OutputFormat::Tsv, COLUMNS, &args(None, true)).unwrap();
+        assert_eq!(render(&tsv, &[&["x\ty", "a\nb", "ok"]]), "x y\ta b\tok\n");
+
+        let csv = Table::with_all(OutputFormat::Csv, COLUMNS, &args(None, true)).unwrap();
+        assert_eq!(
+            render(&csv, &[&["x,y", "say \"hi\"", "ok"]]),
+            "\"x,y\",\"say \"\"hi\"\"\",ok\n"
+        );
+    }
+}
@@ -5,7 +5,7 @@
 use std::collections::{BTreeSet, HashSet};
 
 use crate::analytics::SessionAnalytics;
-use crate::cli::{Cli, OutputFormat, TagAction, TagArgs};
+use crate::cli::{AnnotateArgs, Cli, OutputFormat, TagAction, TagArgs};
 use crate::discovery::Session;
 use crate::error::Result;
 use crate::provider::registry::{ProviderRegistry, ProviderSelection};
@@ -259,7 +259,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                         OutputFormat::Json => {
                             println!("{}", serde_json::to_string_pretty(meta)?);
                         }
-                        OutputFormat::Tsv => {
+                        OutputFormat::Tsv | OutputFormat::Csv => {
                             println!("session_id\tname\ttags\tbookmarked");
                             println!(
                                 "{}\t{}\t{}\t{}",
@@ -306,7 +306,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                     OutputFormat::Json => {
                         println!("{}", serde_json::to_string_pretty(&tags)?);
                     }
-                    OutputFormat::Tsv => {
+                    OutputFormat::Tsv | OutputFormat::Csv => {
                         println!("tag\tcount");
                         for tag in &tags {
                             let count = selected_sessions_with_tag(&store, tag, &filter).len();
@@ -334,21 +334,116 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
             }
         }
 
-        TagAction::Bookmark { session } => {
+        TagAction::Bookmark {
+            session,
+            message: Some(uuid),
+        } => {
+            let session_key = resolve_tag_key(cli, provider_flags, &store, session)?;
+            if store.set_message_bookmark_key(&session_key, uuid, true) {
+                store.save()?;
+                println!(
+                    "Bookmarked message {} in session {}",
+                    uuid,
+                    short_key(&session_key)
+                );
+            } else {
+                println!(
+                    "Message {} in session {} is already bookmarked",
+                    uuid,
+                    short_key(&session_key)
+                );
+            }
+        }
+
+        TagAction::Bookmark {
+            session,
+            message: None,
+        } => {
             let session_key = resolve_tag_key(cli, provider_flags, &store, session)?;
             store.set_bookmark_key(&session_key, true);
             store.save()?;
             println!("Bookmarked session {}", short_key(&session_key));
         }
 
-        TagAction::Unbookmark { session } => {
+        TagAction::Unbookmark {
+            session,
+            message: Some(uuid),
+        } => {
+            let session_key = resolve_tag_key(cli, provider_flags, &store, session)?;
+            if store.set_message_bookmark_key(&session_key, uuid, false) {
+                store.save()?;
+                println!(
+                    "Removed bookmark from message {} in session {}",
+                    uuid,
+                    short_key(&session_key)
+                );
+            } else {
+                println!(
+                    "Message {} in session {} is not bookmarked",
+                    uuid,
+                    short_key(&session_key)
+                );
+            }
+        }
+
+        TagAction::Unbookmark {
+            session,
+            message: None,
+        } => {
             let session_key = resolve_tag_key(cli, provider_flags, &store, session)?;
             store.set_bookmark_key(&session_key, false);
             store.save()?;
             println!("Removed bookmark from session {}", short_key(&session_key));
         }
 
-        TagAction::Bookmarks => {
+        TagAction::Bookmarks { messages: true } => {
+            let filter = tag_provider_filter(cli, provider_flags, &store)?;
+            let bookmarked: Vec<_> = store
+                .bookmarked_messages()
+                .into_iter()
+                .filter(|(key, _)| filter.contains(&key.provider))
+                .collect();
+            if bookmarked.is_empty() {
+                println!("No bookmarked messages.");
+                return Ok(());
+            }
+
+            match cli.effective_output() {
+                OutputFormat::Json => {
+                    let rows: Vec<_> = bookmarked
+                        .iter()
+                        .map(|(key, uuid)| {
+                            serde_json::json!({ "session_id": stored_id(key), "uuid": uuid })
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&rows)?);
+                }
+                OutputFormat::Tsv | OutputFormat::Csv => {
+                    println!("session_id\tuuid");
+                    for (key, uuid) in &bookmarked {
+                        println!("{}\t{}", stored_id(key), uuid);
+                    }
+                }
+                OutputFormat::Compact => {
+                    for (key, uuid) in &bookmarked {
+                        println!("{} {}", short_key(key), uuid);
+                    }
+                }
+                OutputFormat::Text => {
+                    println!("Bookmarked messages ({}):", bookmarked.len());
+                    for (key, uuid) in &bookmarked {
+                        let name = store
+                            .get_key(key)
+                            .and_then(|m| m.name.as_deref())
+                            .map(|n| format!(" - {}", n))
+                            .unwrap_or_default();
+                        println!("  {} {}{}", short_key(key), uuid, name);
+                    }
+                }
+            }
+        }
+
+        TagAction::Bookmarks { messages: false } => {
             let filter = tag_provider_filter(cli, provider_flags, &store)?;
             let bookmarked: Vec<_> = store
                 .bookmarked_sessions()
@@ -367,7 +462,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                         serde_json::to_string_pretty(&stored_ids(&bookmarked))?
                     );
                 }
-                OutputFormat::Tsv => {
+                OutputFormat::Tsv | OutputFormat::Csv => {
                     println!("session_id\tname");
                     for id in &bookmarked {
                         let name = store
@@ -408,7 +503,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                 OutputFormat::Json => {
                     println!("{}", serde_json::to_string_pretty(&stored_ids(&sessions))?);
                 }
-                OutputFormat::Tsv => {
+                OutputFormat::Tsv | OutputFormat::Csv => {
                     println!("session_id\tname");
                     for id in &sessions {
                         let name = store
@@ -472,7 +567,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                     OutputFormat::Json => {
                         println!("{}", serde_json::to_string_pretty(&stored_ids(&sessions))?);
                     }
-                    OutputFormat::Tsv => {
+                    OutputFormat::Tsv | OutputFormat::Csv => {
                         println!("session_id\tname\toutcome");
                         for id in &sessions {
                             let name = store
@@ -507,7 +602,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                     OutputFormat::Json => {
                         println!("{}", serde_json::to_string_pretty(&stats)?);
                     }
-                    OutputFormat::Tsv => {
+                    OutputFormat::Tsv | OutputFormat::Csv => {
                         println!("outcome\tcount");
                         println!("success\t{}", stats.success);
                         println!("partial\t{}", stats.partial);
@@ -545,19 +640,31 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
             session,
             text,
             label,
+            message,
         } => {
             let session_key = resolve_tag_key(cli, provider_flags, &store, session)?;
-            store.add_note_key(&session_key, text, label.as_deref());
-            store.save()?;
             let label_str = label
                 .as_ref()
                 .map(|l| format!(" [{}]", l))
                 .unwrap_or_default();
-            println!(
-                "Added note{} to session {}",
-                label_str,
-                short_key(&session_key)
-            );
+            if let Some(uuid) = message {
+                store.add_message_note_key(&session_key, uuid, text, label.as_deref());
+                store.save()?;
+                println!(
+                    "Added note{} to message {} in session {}",
+                    label_str,
+                    uuid,
+                    short_key(&session_key)
+                );
+            } else {
+                store.add_note_key(&session_key, text, label.as_deref());
+                store.save()?;
+                println!(
+                    "Added note{} to session {}",
+                    label_str,
+                    short_key(&session_key)
+                );
+            }
         }
 
         TagAction::Notes { session } => {
@@ -572,13 +679,14 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                     OutputFormat::Json => {
                         println!("{}", serde_json::to_string_pretty(&notes)?);
                     }
-                    OutputFormat::Tsv => {
-                        println!("index\tlabel\tcreated\ttext");
+                    OutputFormat::Tsv | OutputFormat::Csv => {
+                        println!("index\tlabel\tmessage\tcreated\ttext");
                         for (i, note) in notes.iter().enumerate() {
                             println!(
-                                "{}\t{}\t{}\t{}",
+                                "{}\t{}\t{}\t{}\t{}",
                                 i,
                                 note.label.as_deref().unwrap_or(""),
+                                note.message.as_deref().unwrap_or(""),
                                 note.created_at.format("%Y-%m-%d %H:%M"),
                                 note.text.replace('\n', "\\n")
                             );
@@ -602,11 +710,17 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                                 .as_ref()
                                 .map(|l| format!(" [{}]", l))
                                 .unwrap_or_default();
+                            let message_str = note
+                                .message
+                                .as_ref()
+                                .map(|uuid| format!(" on message {}", short_id(uuid)))
+                                .unwrap_or_default();
                             println!(
-                                "[{}]{} - {}",
+                                "[{}]{} - {}{}",
                                 i,
                                 label_str,
-                                note.created_at.format("%Y-%m-%d %H:%M")
+                                note.created_at.format("%Y-%m-%d %H:%M"),
+                                message_str
                             );
                             for line in note.text.lines() {
                                 println!("    {}", line);
@@ -714,7 +828,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                     OutputFormat::Json => {
                         println!("{}", serde_json::to_string_pretty(&stored_ids(&linked))?);
                     }
-                    OutputFormat::Tsv => {
+                    OutputFormat::Tsv | OutputFormat::Csv => {
                         println!("session_id\tname");
                         for id in &linked {
                             let name = store
@@ -774,7 +888,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                             .collect();
                         println!("{}", serde_json::to_string_pretty(&data)?);
                     }
-                    OutputFormat::Tsv => {
+                    OutputFormat::Tsv | OutputFormat::Csv => {
                         println!("session_id\tname\tlinked_count");
                         for id in &sessions {
                             let name = store
@@ -831,7 +945,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                 });
             }
             let session_id = resolve_session_id(cli, session)?;
-            let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+            let claude_dir = get_claude_dir(cli)?;
             let all_sessions = claude_dir.all_sessions()?;
 
             // Find the source session
@@ -915,7 +1029,7 @@ pub fn run(cli: &Cli, args: &TagArgs) -> Result<()> {
                         }))?
                     );
                 }
-                OutputFormat::Tsv => {
+                OutputFormat::Tsv | OutputFormat::Csv => {
                     println!("session_id\tname\ttotal\ttool\tproject\ttime\ttag\ttoken");
                     for (id, score) in &similarities {
                         let name = store.get(id).and_then(|m| m.name.as_deref()).unwrap_or("");
@@ -1338,6 +1452,22 @@ fn resolve_provider_session_key(
     }
 }
 
+/// Run the annotate command, a shortcut for `tag note`.
+pub fn annotate(cli: &Cli, args: &AnnotateArgs) -> Result<()> {
+    run(
+        cli,
+        &TagArgs {
+            provider: args.provider.clone(),
+            action: TagAction::Note {
+                session: args.session.clone(),
+                text: args.text.clone(),
+                label: args.label.clone(),
+                message: args.uuid.clone(),
+            },
+        },
+    )
+}
+
 fn resolve_tag_key(
     cli: &Cli,
     flags: &[String],
@@ -1365,7 +1495,7 @@ fn resolve_session_id(cli: &Cli, prefix: &str) -> Result<String> {
     }
 
     // Then try to find in actual session files
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let sessions = claude_dir.all_sessions()?;
 
     let matches: Vec<_> = sessions
@@ -1434,7 +1564,7 @@ fn get_filtered_sessions(
     until: Option<&str>,
     project: Option<&str>,
 ) -> Result<Vec<String>> {
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let mut sessions = claude_dir.all_sessions()?;
 
     // Apply date filters (content-based timestamps)
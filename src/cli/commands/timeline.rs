@@ -2,7 +2,17 @@
 //!
 //! Shows a turn-by-turn narrative of a session, with tool-only turns
 //! collapsed for readability. Mirrors the MCP `get_session_timeline` tool.
+//!
+//! Without a session it prints a chronological activity log across sessions
+//! instead: session starts and ends, bursts of tool calls, tool errors, and
+//! git commits made in the sessions' repositories.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
 
+use chrono::{DateTime, Local, Utc};
+
+use crate::analysis::extraction::{extract_user_prompt_text, is_human_prompt, truncate_text};
 use crate::analysis::subagents::{match_subagents, SubagentMatches};
 use crate::analysis::timeline::{
     build_semantic_timeline, build_timeline, compaction_events, semantic_turns,
@@ -10,9 +20,17 @@ use crate::analysis::timeline::{
 };
 use crate::cli::{Cli, OutputFormat, TimelineArgs};
 use crate::error::{Result, SnatchError};
+use crate::model::{ContentBlock, LogEntry};
 use crate::reconstruction::Conversation;
+use crate::util::truncate_path;
+
+use super::{get_claude_dir, parse_date_filter};
 
-use super::get_claude_dir;
+/// Longest pause, in seconds, inside one run of tool calls or errors.
+const RUN_GAP_SECS: i64 = 120;
+
+/// Tool calls in one run that make it a notable burst.
+const BURST_MIN_CALLS: usize = 10;
 
 /// JSON output types for serialization.
 #[derive(serde::Serialize)]
@@ -77,20 +95,30 @@ struct TimelineTurnOutput {
 
 /// Run the timeline command.
 pub fn run(cli: &Cli, args: &TimelineArgs) -> Result<()> {
+    let Some(session_id) = args.session_id.as_deref() else {
+        return run_activity_log(cli, args);
+    };
     if !args.provider.is_empty()
-        || (args.session_id.contains(':')
-            && super::helpers::provider_registry(cli).looks_qualified(&args.session_id))
+        || (session_id.contains(':')
+            && super::helpers::provider_registry(cli).looks_qualified(session_id))
     {
-        return run_provider(cli, args);
+        return run_provider(cli, args, session_id);
+    }
+    if (args.since.is_some() || args.until.is_some() || args.project.is_some() || args.no_git)
+        && !cli.quiet
+    {
+        eprintln!(
+            "Note: --since/--until/--project/--no-git only apply to the cross-session activity log; omit the session to use them"
+        );
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     let session =
         claude_dir
-            .find_session(&args.session_id)?
+            .find_session(session_id)?
             .ok_or_else(|| SnatchError::SessionNotFound {
-                session_id: args.session_id.clone(),
+                session_id: session_id.to_string(),
             })?;
 
     let chain_aware = !args.no_chain;
@@ -111,7 +139,7 @@ pub fn run(cli: &Cli, args: &TimelineArgs) -> Result<()> {
     }
     let conversation = Conversation::from_entries(entries)?;
     let ctx = TimelineContext {
-        display_id: args.session_id.clone(),
+        display_id: session_id.to_string(),
         semantic: false,
         chain,
         unparsed,
@@ -124,22 +152,33 @@ pub fn run(cli: &Cli, args: &TimelineArgs) -> Result<()> {
 /// conversation from the COMPLETE ParsedSession bundle (round-21
 /// constraint 5); the normalized entries drive the same turn/timeline
 /// machinery as Claude sessions.
-fn run_provider(cli: &Cli, args: &TimelineArgs) -> Result<()> {
+fn run_provider(cli: &Cli, args: &TimelineArgs, session_id: &str) -> Result<()> {
     // COMPLETE argument classification (destructured without `..`):
-    // --no-chain is Claude resume-chain machinery, refused.
+    // --no-chain is Claude resume-chain machinery, and the activity-log
+    // filters only apply without a session; all are refused.
     let TimelineArgs {
         session_id: _,
         provider: _,
         limit: _,
         no_chain,
+        since,
+        until,
+        project,
+        no_git,
     } = args;
     super::helpers::refuse_unsupported_flags(
         "provider-routed timeline",
-        &[("--no-chain", *no_chain)],
+        &[
+            ("--no-chain", *no_chain),
+            ("--since", since.is_some()),
+            ("--until", until.is_some()),
+            ("--project", project.is_some()),
+            ("--no-git", *no_git),
+        ],
     )?;
 
     let registry = super::helpers::provider_registry(cli);
-    let resolution = registry.resolve_with_default_policy(&args.provider, &args.session_id)?;
+    let resolution = registry.resolve_with_default_policy(&args.provider, session_id)?;
     let parsed = crate::provider::registry::cached_parsed_session(
         crate::cache::global_cache(),
         resolution.provider,
@@ -369,3 +408,430 @@ fn render(
 
     Ok(())
 }
+
+/// Kind of event in the cross-session activity log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ActivityKind {
+    SessionStart,
+    ToolBurst,
+    Error,
+    Commit,
+    SessionEnd,
+}
+
+impl ActivityKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::SessionStart => "start",
+            Self::ToolBurst => "burst",
+            Self::Error => "errors",
+            Self::Commit => "commit",
+            Self::SessionEnd => "end",
+        }
+    }
+}
+
+/// One event in the cross-session activity log.
+#[derive(Debug, serde::Serialize)]
+struct ActivityEvent {
+    timestamp: DateTime<Utc>,
+    kind: ActivityKind,
+    /// Absent for git commits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    project: String,
+    detail: String,
+}
+
+#[derive(serde::Serialize)]
+struct ActivityLogOutput {
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    sessions: usize,
+    total_events: usize,
+    events: Vec<ActivityEvent>,
+}
+
+/// Chronological activity across all Claude sessions in a date range.
+fn run_activity_log(cli: &Cli, args: &TimelineArgs) -> Result<()> {
+    if !args.provider.is_empty() {
+        return Err(SnatchError::InvalidArgument {
+            name: "provider".to_string(),
+            reason: "the cross-session activity log covers Claude Code sessions; pass a session \
+                     id to use --provider"
+                .to_string(),
+        });
+    }
+    if args.no_chain && !cli.quiet {
+        eprintln!("Note: --no-chain only applies to a single-session timeline");
+    }
+    let since: DateTime<Utc> = parse_date_filter(args.since.as_deref().unwrap_or("1week"))?.into();
+    let until: DateTime<Utc> = match args.until.as_deref() {
+        Some(until) => parse_date_filter(until)?.into(),
+        None => Utc::now(),
+    };
+
+    let claude_dir = get_claude_dir(cli)?;
+    let sessions: Vec<_> = claude_dir
+        .all_sessions()?
+        .into_iter()
+        .filter(|s| !s.is_subagent() && s.modified_datetime() >= since)
+        .filter(|s| {
+            args.project
+                .as_ref()
+                .is_none_or(|project| s.project_path().contains(project.as_str()))
+        })
+        .collect();
+
+    let mut events = Vec::new();
+    let mut repo_dirs: Vec<String> = Vec::new();
+    let mut active_sessions = 0;
+    for session in &sessions {
+        let Ok(entries) = session.parse_with_options(cli.max_file_size) else {
+            continue;
+        };
+        let before = events.len();
+        events.extend(
+            session_events(session.session_id(), session.project_path(), &entries)
+                .into_iter()
+                .filter(|event| event.timestamp >= since && event.timestamp <= until),
+        );
+        if events.len() > before {
+            active_sessions += 1;
+            let cwd = entries.iter().find_map(LogEntry::cwd);
+            repo_dirs.push(cwd.unwrap_or(session.project_path()).to_string());
+        }
+    }
+    if !args.no_git {
+        events.extend(commit_events(&repo_dirs, since, until));
+    }
+    events.sort_by(|a, b| (a.timestamp, a.kind).cmp(&(b.timestamp, b.kind)));
+    let total_events = events.len();
+    if total_events > args.limit {
+        events.drain(..total_events - args.limit);
+    }
+
+    if matches!(cli.effective_output(), OutputFormat::Json) {
+        let output = ActivityLogOutput {
+            since,
+            until,
+            sessions: active_sessions,
+            total_events,
+            events,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!(
+        "Activity from {} to {} ({} event{}, {} session{})",
+        since.with_timezone(&Local).format("%Y-%m-%d"),
+        until.with_timezone(&Local).format("%Y-%m-%d"),
+        total_events,
+        if total_events == 1 { "" } else { "s" },
+        active_sessions,
+        if active_sessions == 1 { "" } else { "s" }
+    );
+    if total_events > events.len() {
+        println!(
+            "Showing the latest {}; raise --limit for more.",
+            events.len()
+        );
+    }
+    let mut day = None;
+    for event in &events {
+        let local = event.timestamp.with_timezone(&Local);
+        if day != Some(local.date_naive()) {
+            day = Some(local.date_naive());
+            println!("\n{}", local.format("%Y-%m-%d %a"));
+        }
+        let session = event
+            .session_id
+            .as_deref()
+            .map_or("", |id| id.get(..8).unwrap_or(id));
+        println!(
+            "  {}  {:6}  {:8}  {:30}  {}",
+            local.format("%H:%M"),
+            event.kind.label(),
+            session,
+            truncate_path(&event.project, 30),
+            event.detail
+        );
+    }
+    Ok(())
+}
+
+/// Split time-ordered items into runs separated by pauses over [`RUN_GAP_SECS`].
+fn runs<T>(items: &[(DateTime<Utc>, T)]) -> Vec<&[(DateTime<Utc>, T)]> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=items.len() {
+        if i == items.len() || (items[i].0 - items[i - 1].0).num_seconds() > RUN_GAP_SECS {
+            if i > start {
+                runs.push(&items[start..i]);
+            }
+            start = i;
+        }
+    }
+    runs
+}
+
+/// Names with counts, most frequent first, such as `Bash×8, Edit×3`.
+fn name_counts(names: impl Iterator<Item = String>) -> String {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for name in names {
+        *counts.entry(name).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+        .iter()
+        .take(3)
+        .map(|(name, count)| format!("{name}×{count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Compact span such as `45s`, `12m`, or `2h 5m`.
+fn format_span(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+    let secs = (end - start).num_seconds().max(0);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h {}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+/// Activity events for one session, before date filtering.
+fn session_events(session_id: &str, project: &str, entries: &[LogEntry]) -> Vec<ActivityEvent> {
+    let mut first: Option<DateTime<Utc>> = None;
+    let mut last: Option<DateTime<Utc>> = None;
+    let mut first_prompt = None;
+    let mut prompts = 0;
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    let mut calls: Vec<(DateTime<Utc>, String)> = Vec::new();
+    let mut errors: Vec<(DateTime<Utc>, String)> = Vec::new();
+
+    for entry in entries {
+        let Some(timestamp) = entry.timestamp() else {
+            continue;
+        };
+        first = first.min(Some(timestamp)).or(Some(timestamp));
+        last = last.max(Some(timestamp));
+        match entry {
+            LogEntry::Assistant(assistant) => {
+                for block in &assistant.message.content {
+                    if let ContentBlock::ToolUse(tool_use) = block {
+                        tool_names.insert(&tool_use.id, &tool_use.name);
+                        calls.push((timestamp, tool_use.name.clone()));
+                    }
+                }
+            }
+            LogEntry::User(user) => {
+                if is_human_prompt(entry) {
+                    prompts += 1;
+                    if first_prompt.is_none() {
+                        first_prompt = extract_user_prompt_text(entry);
+                    }
+                }
+                for result in user.message.tool_results() {
+                    if result.is_explicit_error() {
+                        let name = tool_names.get(result.tool_use_id.as_str());
+                        errors.push((timestamp, name.unwrap_or(&"tool").to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let (Some(first), Some(last)) = (first, last) else {
+        return Vec::new();
+    };
+
+    let event = |timestamp, kind, detail| ActivityEvent {
+        timestamp,
+        kind,
+        session_id: Some(session_id.to_string()),
+        project: project.to_string(),
+        detail,
+    };
+    let mut events = vec![event(
+        first,
+        ActivityKind::SessionStart,
+        first_prompt.map_or_else(
+            || "(no prompt)".to_string(),
+            |prompt| truncate_text(&prompt.replace('\n', " "), 80),
+        ),
+    )];
+    for run in runs(&calls) {
+        if run.len() >= BURST_MIN_CALLS {
+            events.push(event(
+                run[0].0,
+                ActivityKind::ToolBurst,
+                format!(
+                    "{} tool calls in {} ({})",
+                    run.len(),
+                    format_span(run[0].0, run[run.len() - 1].0),
+                    name_counts(run.iter().map(|(_, name)| name.clone()))
+                ),
+            ));
+        }
+    }
+    for run in runs(&errors) {
+        events.push(event(
+            run[0].0,
+            ActivityKind::Error,
+            format!(
+                "{} tool error{} ({})",
+                run.len(),
+                if run.len() == 1 { "" } else { "s" },
+                name_counts(run.iter().map(|(_, name)| name.clone()))
+            ),
+        ));
+    }
+    events.push(event(
+        last,
+        ActivityKind::SessionEnd,
+        format!(
+            "{}, {} prompt{}, {} tool call{}",
+            format_span(first, last),
+            prompts,
+            if prompts == 1 { "" } else { "s" },
+            calls.len(),
+            if calls.len() == 1 { "" } else { "s" }
+        ),
+    ));
+    events
+}
+
+/// Commits in the date range, once per repository the sessions worked in.
+fn commit_events(
+    dirs: &[String],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Vec<ActivityEvent> {
+    let mut roots = BTreeSet::new();
+    for dir in dirs {
+        if let Some(repo) = crate::git::get_repo_info(Path::new(dir)) {
+            roots.insert(repo.root);
+        }
+    }
+    roots
+        .into_iter()
+        .flat_map(|root| {
+            crate::git::get_commits_in_range(Path::new(&root), since, until)
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |commit| ActivityEvent {
+                    timestamp: commit.timestamp,
+                    kind: ActivityKind::Commit,
+                    session_id: None,
+                    project: root.clone(),
+                    detail: format!(
+                        "{} {} ({})",
+                        commit.short_hash, commit.message, commit.author
+                    ),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(value: serde_json::Value) -> LogEntry {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn prompt(second: u32, text: &str) -> LogEntry {
+        entry(json!({
+            "type": "user",
+            "uuid": format!("u{second}"),
+            "parentUuid": null,
+            "timestamp": format!("2026-01-01T10:{:02}:{:02}Z", second / 60, second % 60),
+            "sessionId": "s",
+            "version": "2.0.0",
+            "message": { "role": "user", "content": text },
+        }))
+    }
+
+    fn tool_call(second: u32, name: &str) -> LogEntry {
+        entry(json!({
+            "type": "assistant",
+            "uuid": format!("a{second}"),
+            "parentUuid": null,
+            "timestamp": format!("2026-01-01T10:{:02}:{:02}Z", second / 60, second % 60),
+            "sessionId": "s",
+            "version": "2.0.0",
+            "message": {
+                "id": format!("m{second}"),
+                "type": "message",
+                "role": "assistant",
+                "model": "claude-opus-4-5",
+                "content": [{ "type": "tool_use", "id": format!("t{second}"), "name": name, "input": {} }],
+                "stop_reason": null,
+                "stop_sequence": null,
+            },
+        }))
+    }
+
+    fn tool_error(second: u32, call: u32) -> LogEntry {
+        entry(json!({
+            "type": "user",
+            "uuid": format!("r{second}"),
+            "parentUuid": null,
+            "timestamp": format!("2026-01-01T10:{:02}:{:02}Z", second / 60, second % 60),
+            "sessionId": "s",
+            "version": "2.0.0",
+            "message": {
+                "role": "user",
+                "content": [{
+                    "type": "tool_result",
+                    "tool_use_id": format!("t{call}"),
+                    "content": "exit 1",
+                    "is_error": true,
+                }],
+            },
+        }))
+    }
+
+    #[test]
+    fn test_session_events_find_bursts_and_errors() {
+        let mut entries = vec![prompt(0, "Fix the flaky test")];
+        // A burst of ten calls, five seconds apart, then one call ten minutes later.
+        for i in 0..10 {
+            entries.push(tool_call(10 + i * 5, if i < 7 { "Bash" } else { "Edit" }));
+        }
+        entries.push(tool_error(20, 15));
+        entries.push(tool_error(25, 20));
+        entries.push(tool_call(660, "Read"));
+
+        let events = session_events("abcdef12-3456", "/work/app", &entries);
+        let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ActivityKind::SessionStart,
+                ActivityKind::ToolBurst,
+                ActivityKind::Error,
+                ActivityKind::SessionEnd,
+            ]
+        );
+        assert_eq!(events[0].detail, "Fix the flaky test");
+        assert_eq!(events[1].detail, "10 tool calls in 45s (Bash×7, Edit×3)");
+        assert_eq!(events[2].detail, "2 tool errors (Bash×2)");
+        assert_eq!(events[3].detail, "11m, 1 prompt, 11 tool calls");
+    }
+
+    #[test]
+    fn test_runs_split_on_pauses() {
+        let at = |secs: i64| DateTime::<Utc>::UNIX_EPOCH + chrono::Duration::seconds(secs);
+        let items = [(at(0), ()), (at(100), ()), (at(400), ()), (at(450), ())];
+        let lengths: Vec<_> = runs(&items).iter().map(|run| run.len()).collect();
+        assert_eq!(lengths, [2, 2]);
+        assert!(runs::<()>(&[]).is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+//! Top command implementation.
+//!
+//! A `top`-style monitor of recently active sessions: a refreshing table with
+//! one row per session showing tokens used in the last hour, the current
+//! model, the last tool called, and time since the last activity. Like
+//! `watch`, it polls session files; a file is re-parsed only when its size or
+//! modification time changes.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+
+use crate::cli::{Cli, OutputFormat, TopArgs};
+use crate::discovery::{format_number, Session};
+use crate::error::Result;
+use crate::model::{ContentBlock, LogEntry};
+use crate::parser::SessionState;
+use crate::util::{truncate_path, truncate_text};
+
+use super::get_claude_dir;
+
+/// Activity extracted from one session file.
+#[derive(Debug, Default)]
+struct Activity {
+    /// Work tokens per API message, with the time the message started.
+    turns: Vec<(DateTime<Utc>, u64)>,
+    model: Option<String>,
+    last_tool: Option<String>,
+    last_activity: Option<DateTime<Utc>>,
+}
+
+impl Activity {
+    fn from_entries(entries: &[LogEntry]) -> Self {
+        let mut activity = Self::default();
+        // Streaming chunks of one API message share its id and repeat its
+        // usage with a growing output count, so keep the largest per id.
+        let mut turns: IndexMap<&str, (DateTime<Utc>, u64)> = IndexMap::new();
+        for entry in entries {
+            if let Some(timestamp) = entry.timestamp() {
+                activity.last_activity = activity.last_activity.max(Some(timestamp));
+            }
+            let LogEntry::Assistant(assistant) = entry else {
+                continue;
+            };
+            let message = &assistant.message;
+            if !message.model.is_empty() && message.model != "<synthetic>" {
+                activity.model = Some(message.model.clone());
+            }
+            for block in &message.content {
+                if let ContentBlock::ToolUse(tool_use) = block {
+                    activity.last_tool = Some(tool_use.name.clone());
+                }
+            }
+            if let Some(usage) = &message.usage {
+                let turn = turns
+                    .entry(message.id.as_str())
+                    .or_insert((assistant.timestamp, 0));
+                turn.1 = turn.1.max(usage.work_tokens());
+            }
+        }
+        activity.turns = turns.into_values().collect();
+        activity
+    }
+
+    fn tokens_since(&self, cutoff: DateTime<Utc>) -> u64 {
+        self.turns
+            .iter()
+            .filter(|(timestamp, _)| *timestamp >= cutoff)
+            .map(|(_, tokens)| tokens)
+            .sum()
+    }
+}
+
+/// A parsed session file and the file state it was parsed from.
+struct CachedActivity {
+    len: u64,
+    modified: SystemTime,
+    activity: Activity,
+}
+
+/// One row of the monitor.
+#[derive(Debug, serde::Serialize)]
+struct TopRow {
+    session_id: String,
+    project: String,
+    active: bool,
+    tokens_last_hour: u64,
+    model: Option<String>,
+    last_tool: Option<String>,
+    last_activity: Option<DateTime<Utc>>,
+}
+
+/// Run the top command.
+pub fn run(cli: &Cli, args: &TopArgs) -> Result<()> {
+    let claude_dir = get_claude_dir(cli)?;
+    let poll_interval = Duration::from_millis(args.interval);
+    let mut cache: HashMap<PathBuf, CachedActivity> = HashMap::new();
+
+    loop {
+        let now = Utc::now();
+        let cutoff = now - chrono::Duration::hours(1);
+        let sessions: Vec<Session> = claude_dir
+            .all_sessions()?
+            .into_iter()
+            .filter(|s| s.modified_datetime() > cutoff)
+            .filter(|s| {
+                args.project
+                    .as_ref()
+                    .is_none_or(|project| s.project_path().contains(project.as_str()))
+            })
+            .collect();
+        cache.retain(|path, _| sessions.iter().any(|s| s.path() == path.as_path()));
+
+        let mut rows: Vec<TopRow> = sessions
+            .iter()
+            .map(|session| {
+                let activity = refresh(cli, &mut cache, session);
+                TopRow {
+                    session_id: session.session_id().to_string(),
+                    project: session.project_path().to_string(),
+                    active: session
+                        .state()
+                        .is_ok_and(|state| state != SessionState::Inactive),
+                    tokens_last_hour: activity.map_or(0, |a| a.tokens_since(cutoff)),
+                    model: activity.and_then(|a| a.model.clone()),
+                    last_tool: activity.and_then(|a| a.last_tool.clone()),
+                    last_activity: activity.and_then(|a| a.last_activity),
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+
+        match cli.effective_output() {
+            OutputFormat::Json => {
+                let shown = &rows[..rows.len().min(args.limit)];
+                println!("{}", serde_json::to_string(shown)?);
+            }
+            _ => {
+                if !args.once {
+                    // Clear the screen and home the cursor, like `watch --live`.
+                    print!("\x1b[2J\x1b[H");
+                }
+                print!("{}", render(&rows, args.limit, now));
+            }
+        }
+        io::stdout().flush()?;
+
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(poll_interval);
+    }
+}
+
+/// Return the session's activity, re-parsing it only if the file changed.
+fn refresh<'a>(
+    cli: &Cli,
+    cache: &'a mut HashMap<PathBuf, CachedActivity>,
+    session: &Session,
+) -> Option<&'a Activity> {
+    let metadata = std::fs::metadata(session.path()).ok()?;
+    let len = metadata.len();
+    let modified = metadata.modified().ok()?;
+    let stale = cache
+        .get(session.path())
+        .is_none_or(|cached| cached.len != len || cached.modified != modified);
+    if stale {
+        let entries = session.parse_with_options(cli.max_file_size).ok()?;
+        cache.insert(
+            session.path().to_path_buf(),
+            CachedActivity {
+                len,
+                modified,
+                activity: Activity::from_entries(&entries),
+            },
+        );
+    }
+    cache.get(session.path()).map(|cached| &cached.activity)
+}
+
+/// Compact age such as `42s`, `7m`, or `1h`.
+fn format_age(now: DateTime<Utc>, then: DateTime<Utc>) -> String {
+    let secs = (now - then).num_seconds().max(0);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
+    }
+}
+
+/// Render the table, with totals over all rows but at most `limit` shown.
+fn render(rows: &[TopRow], limit: usize, now: DateTime<Utc>) -> String {
+    let total = rows.len();
+    let tokens: u64 = rows.iter().map(|row| row.tokens_last_hour).sum();
+    let active = rows.iter().filter(|row| row.active).count();
+    let mut out = format!(
+        "snatch top - {} | {total} session{} in the last hour, {active} active | {} tokens\n\n",
+        now.with_timezone(&chrono::Local).format("%H:%M:%S"),
+        if total == 1 { "" } else { "s" },
+        format_number(tokens)
+    );
+    out.push_str(&format!(
+        "  {:8}  {:30}  {:>11}  {:24}  {:14}  {:>6}\n",
+        "SESSION", "PROJECT", "TOKENS/1H", "MODEL", "LAST TOOL", "IDLE"
+    ));
+    if rows.is_empty() {
+        out.push_str("  (no sessions active in the last hour)\n");
+    }
+    for row in rows.iter().take(limit) {
+        let idle = row
+            .last_activity
+            .map_or_else(|| "-".to_string(), |at| format_age(now, at));
+        out.push_str(&format!(
+            "{} {:8}  {:30}  {:>11}  {:24}  {:14}  {:>6}\n",
+            if row.active { '●' } else { ' ' },
+            row.session_id.get(..8).unwrap_or(&row.session_id),
+            truncate_path(&row.project, 30),
+            format_number(row.tokens_last_hour),
+            truncate_text(row.model.as_deref().unwrap_or("-"), 24),
+            truncate_text(row.last_tool.as_deref().unwrap_or("-"), 14),
+            idle
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn assistant(id: &str, minute: u32, output: u64, tool: Option<&str>) -> LogEntry {
+        let content = match tool {
+            Some(name) => json!([{ "type": "tool_use", "id": "t", "name": name, "input": {} }]),
+            None => json!([{ "type": "text", "text": "ok" }]),
+        };
+        serde_json::from_value(json!({
+            "type": "assistant",
+            "uuid": format!("{id}-{output}"),
+            "parentUuid": null,
+            "timestamp": format!("2026-01-01T10:{minute:02}:00Z"),
+            "sessionId": "s",
+            "version": "2.0.0",
+            "message": {
+                "id": id,
+                "type": "message",
+                "role": "assistant",
+                "model": "claude-opus-4-5",
+                "content": content,
+                "stop_reason": null,
+                "stop_sequence": null,
+                "usage": { "input_tokens": 10, "output_tokens": output },
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_activity_counts_each_message_once() {
+        let entries = vec![
+            assistant("m1", 0, 5, None),
+            assistant("m1", 0, 20, Some("Bash")),
+            assistant("m2", 30, 40, Some("Read")),
+        ];
+        let activity = Activity::from_entries(&entries);
+
+        assert_eq!(activity.turns.len(), 2);
+        let start = "2026-01-01T10:00:00Z".parse().unwrap();
+        assert_eq!(activity.tokens_since(start), 30 + 50);
+        let later = "2026-01-01T10:10:00Z".parse().unwrap();
+        assert_eq!(activity.tokens_since(later), 50);
+        assert_eq!(activity.model.as_deref(), Some("claude-opus-4-5"));
+        assert_eq!(activity.last_tool.as_deref(), Some("Read"));
+        assert_eq!(
+            activity.last_activity,
+            Some("2026-01-01T10:30:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now: DateTime<Utc> = "2026-01-01T10:00:00Z".parse().unwrap();
+        assert_eq!(format_age(now, now - chrono::Duration::seconds(42)), "42s");
+        assert_eq!(format_age(now, now - chrono::Duration::minutes(7)), "7m");
+        assert_eq!(format_age(now, now - chrono::Duration::hours(2)), "2h");
+        assert_eq!(format_age(now, now + chrono::Duration::seconds(5)), "0s");
+    }
+}
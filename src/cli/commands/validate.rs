@@ -1,17 +1,31 @@
 //! Validate command implementation.
 //!
 //! Validates session files for schema compliance and data integrity.
+//! Lines that fail to parse are reported with their position and kind of
+//! damage, and `--repair` recovers the entries of concatenated and torn
+//! lines. `--strict` fails a session on its first malformed line or schema
+//! deviation instead.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
 
 use crate::cli::{Cli, OutputFormat, ValidateArgs};
+use crate::discovery::SessionState;
 use crate::error::{Result, SnatchError};
-use crate::model::{LogEntry, SchemaVersion};
-use crate::parser::JsonlParser;
+use crate::export::SchemaViolation;
+use crate::model::{LogEntry, SchemaVersion, UnknownFieldCensus, UnknownFieldCount};
+use crate::parser::{is_compressed_path, JsonlParser, ParseDiagnostic, ParseMode, ParseReport};
 use crate::provider::registry::ProviderSelection;
 use crate::provider::{IngestionDiagnostics, ParsedSession, SourceProvider};
 use crate::reconstruction::Conversation;
+use crate::util::atomic_write;
 
 use super::get_claude_dir;
 
+/// Schema violations listed per session in text output.
+const MAX_LISTED_VIOLATIONS: usize = 20;
+
 /// Run the validate command.
 pub fn run(cli: &Cli, args: &ValidateArgs) -> Result<()> {
     if !args.provider.is_empty() {
@@ -29,7 +43,7 @@ pub fn run(cli: &Cli, args: &ValidateArgs) -> Result<()> {
         }
     }
 
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
 
     let sessions = if args.all {
         claude_dir.all_sessions()?
@@ -51,14 +65,19 @@ pub fn run(cli: &Cli, args: &ValidateArgs) -> Result<()> {
     let mut all_results = Vec::new();
     let mut total_errors = 0;
     let mut total_warnings = 0;
+    let mut census = UnknownFieldCensus::new();
 
     for session in &sessions {
-        let result = validate_session(session, args)?;
+        let result = validate_session(session, args, &mut census)?;
         total_errors += result.errors.len();
         total_warnings += result.warnings.len();
         all_results.push(result);
     }
 
+    if args.repair {
+        repair_sessions(cli, args, &sessions, &mut all_results)?;
+    }
+
     // Output results
     match cli.effective_output() {
         OutputFormat::Json => {
@@ -69,10 +88,29 @@ pub fn run(cli: &Cli, args: &ValidateArgs) -> Result<()> {
                     total_errors,
                     total_warnings,
                     results: all_results,
+                    unknown_fields: args.unknown_fields.then(|| census
+                        .fields()
+                        .into_iter()
+                        .cloned()
+                        .collect()),
                 })?
             );
         }
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv if args.unknown_fields => {
+            println!("scope\tkey\tcount\tsessions\texample_session\texample");
+            for field in census.fields() {
+                println!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    field.scope,
+                    field.key,
+                    field.count,
+                    field.sessions,
+                    field.example_session,
+                    field.example.replace(['\t', '\n'], " ")
+                );
+            }
+        }
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!("session\terrors\twarnings\tvalid");
             for result in &all_results {
                 println!(
@@ -127,6 +165,56 @@ pub fn run(cli: &Cli, args: &ValidateArgs) -> Result<()> {
                         println!("    WARN:  {warning}");
                     }
                 }
+
+                if !cli.quiet {
+                    for violation in result.schema_violations.iter().take(MAX_LISTED_VIOLATIONS) {
+                        println!(
+                            "    SCHEMA line {}{}: {}{}",
+                            violation.line,
+                            violation
+                                .uuid
+                                .as_deref()
+                                .map_or_else(String::new, |uuid| format!(" ({uuid})")),
+                            if violation.path.is_empty() {
+                                String::new()
+                            } else {
+                                format!("{}: ", violation.path)
+                            },
+                            violation.message
+                        );
+                    }
+                    if result.schema_violations.len() > MAX_LISTED_VIOLATIONS {
+                        println!(
+                            "    ... and {} more (see -o json)",
+                            result.schema_violations.len() - MAX_LISTED_VIOLATIONS
+                        );
+                    }
+                    for diagnostic in &result.parse_errors {
+                        println!(
+                            "    LINE {} (byte {}): {}: {}",
+                            diagnostic.line,
+                            diagnostic.byte_offset,
+                            diagnostic.kind.as_str(),
+                            diagnostic.message
+                        );
+                        println!("          near: {}", diagnostic.snippet);
+                    }
+                }
+
+                if let Some(repair) = &result.repair {
+                    println!(
+                        "    REPAIRED: {} line{}, {} entr{} recovered (original backed up to {})",
+                        repair.lines,
+                        if repair.lines == 1 { "" } else { "s" },
+                        repair.entries,
+                        if repair.entries == 1 { "y" } else { "ies" },
+                        repair.backup.display()
+                    );
+                }
+            }
+
+            if args.unknown_fields {
+                print_unknown_fields(&census);
             }
 
             println!();
@@ -211,6 +299,9 @@ fn run_provider(
         schema,
         unknown_fields,
         relationships,
+        strict,
+        repair,
+        yes: _,
     } = args;
     super::helpers::refuse_unsupported_flags(
         "validate --provider (source and normalized-provenance validation)",
@@ -224,6 +315,8 @@ fn run_provider(
                 "--relationships (use chain --provider for typed lineage)",
                 *relationships,
             ),
+            ("--strict", *strict),
+            ("--repair", *repair),
         ],
     )?;
 
@@ -380,7 +473,7 @@ fn validate_provider_parse(
 fn render_provider_report(cli: &Cli, report: &ProviderValidationReport) -> Result<()> {
     match cli.effective_output() {
         OutputFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
-        OutputFormat::Tsv => {
+        OutputFormat::Tsv | OutputFormat::Csv => {
             println!(
                 "provider\tqualified_id\trecords\tentries\tmapped\tsuppressed\tunknown\trecovered\tunparseable\tprovenance_valid\tsource_complete\tvalid"
             );
@@ -466,10 +559,35 @@ fn render_provider_report(cli: &Cli, report: &ProviderValidationReport) -> Resul
     Ok(())
 }
 
-/// Validate a single session.
+/// Print the unknown-field census as a table.
+fn print_unknown_fields(census: &UnknownFieldCensus) {
+    println!();
+    println!("Unknown Fields");
+    println!("--------------");
+    if census.is_empty() {
+        println!(
+            "None found in {} entries across {} sessions.",
+            census.entries(),
+            census.sessions()
+        );
+        return;
+    }
+    println!("{:>7} {:>8}  {:<48} EXAMPLE", "COUNT", "SESSIONS", "FIELD");
+    for field in census.fields() {
+        let name = format!("{}.{}", field.scope, field.key);
+        println!(
+            "{:>7} {:>8}  {name:<48} {}",
+            field.count, field.sessions, field.example
+        );
+    }
+}
+
+/// Validate a single session, adding its unknown fields to `census` when
+/// `--unknown-fields` is set.
 fn validate_session(
     session: &crate::discovery::Session,
     args: &ValidateArgs,
+    census: &mut UnknownFieldCensus,
 ) -> Result<ValidationResult> {
     let mut result = ValidationResult {
         session_id: session.session_id().to_string(),
@@ -479,10 +597,19 @@ fn validate_session(
         warnings: Vec::new(),
         schema_version: None,
         unknown_fields: Vec::new(),
+        schema_violations: Vec::new(),
+        parse_errors: Vec::new(),
+        repair: None,
     };
 
     // Parse the session
-    let mut parser = JsonlParser::new().with_lenient(true);
+    let mut parser = JsonlParser::new()
+        .with_mode(if args.strict {
+            ParseMode::Strict
+        } else {
+            ParseMode::Lenient
+        })
+        .with_schema_validation(args.schema);
     let entries = match parser.parse_file(session.path()) {
         Ok(entries) => entries,
         Err(e) => {
@@ -494,7 +621,28 @@ fn validate_session(
 
     result.entry_count = entries.len();
 
-    // Detect schema version
+    // Describe the lines the lenient parser skipped
+    if parser.stats().lines_skipped > 0 {
+        let report = ParseReport::from_file(session.path())?;
+        let repairable = report.repairable().count();
+        result.warnings.push(format!(
+            "{} line{} failed to parse{}",
+            report.diagnostics.len(),
+            if report.diagnostics.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            if repairable > 0 {
+                format!(" ({repairable} repairable with --repair)")
+            } else {
+                String::new()
+            }
+        ));
+        result.parse_errors = report.diagnostics;
+    }
+
+    // Detect schema version and report entry-schema violations
     if args.schema {
         if let Some(first) = entries.first() {
             if let Some(version) = first.version() {
@@ -502,6 +650,22 @@ fn validate_session(
                 result.schema_version = Some(format!("{schema:?}"));
             }
         }
+        result.schema_violations = parser.stats().schema_violations.clone();
+        if !result.schema_violations.is_empty() {
+            let mut lines: Vec<usize> = result.schema_violations.iter().map(|v| v.line).collect();
+            lines.dedup();
+            result.warnings.push(format!(
+                "{} schema violation{} in {} entr{}",
+                result.schema_violations.len(),
+                if result.schema_violations.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+                lines.len(),
+                if lines.len() == 1 { "y" } else { "ies" }
+            ));
+        }
     }
 
     // Validate relationships
@@ -556,11 +720,14 @@ fn validate_session(
     // Check for unknown fields
     if args.unknown_fields {
         for entry in &entries {
-            let unknown = collect_unknown_fields(entry);
-            if !unknown.is_empty() {
-                result.unknown_fields.extend(unknown);
+            for field in entry.unknown_fields() {
+                let name = format!("{}.{}", field.scope, field.key);
+                if !result.unknown_fields.contains(&name) {
+                    result.unknown_fields.push(name);
+                }
             }
         }
+        census.add_session(session.session_id(), &entries);
 
         if !result.unknown_fields.is_empty() {
             result.warnings.push(format!(
@@ -610,24 +777,80 @@ fn validate_session(
     Ok(result)
 }
 
-/// Collect unknown fields from an entry.
-fn collect_unknown_fields(entry: &LogEntry) -> Vec<String> {
-    let mut unknown = Vec::new();
-
-    // Serialize and look for extra fields
-    if let Ok(value) = serde_json::to_value(entry) {
-        if let Some(obj) = value.as_object() {
-            if let Some(extra) = obj.get("extra") {
-                if let Some(extra_obj) = extra.as_object() {
-                    for key in extra_obj.keys() {
-                        unknown.push(key.clone());
-                    }
-                }
-            }
+/// Rewrite sessions with repairable lines, after confirmation, backing up
+/// each original. Active and compressed sessions are left alone.
+fn repair_sessions(
+    cli: &Cli,
+    args: &ValidateArgs,
+    sessions: &[crate::discovery::Session],
+    results: &mut [ValidationResult],
+) -> Result<()> {
+    let mut plans = Vec::new();
+    for (index, (session, result)) in sessions.iter().zip(results.iter_mut()).enumerate() {
+        if !result.parse_errors.iter().any(|d| d.kind.is_repairable()) {
+            continue;
         }
+        if session
+            .state()
+            .is_ok_and(|state| state != SessionState::Inactive)
+        {
+            result
+                .warnings
+                .push("Session looks active; not repaired".to_string());
+            continue;
+        }
+        if is_compressed_path(session.path()) {
+            result
+                .warnings
+                .push("Session is compressed; not repaired".to_string());
+            continue;
+        }
+        let path = session.path();
+        let content = fs::read(path).map_err(|e| SnatchError::io(path.display().to_string(), e))?;
+        let repair = crate::parser::repair(&content);
+        if repair.lines > 0 {
+            plans.push((index, path.to_path_buf(), repair));
+        }
+    }
+    if plans.is_empty() {
+        return Ok(());
     }
 
-    unknown
+    if !args.yes {
+        let lines: usize = plans.iter().map(|(_, _, r)| r.lines).sum();
+        let entries: usize = plans.iter().map(|(_, _, r)| r.entries).sum();
+        eprint!(
+            "Rewrite {lines} line{} in {} file{} to recover {entries} entr{} (originals backed up)? [y/N] ",
+            if lines == 1 { "" } else { "s" },
+            plans.len(),
+            if plans.len() == 1 { "" } else { "s" },
+            if entries == 1 { "y" } else { "ies" }
+        );
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let answer = input.trim().to_lowercase();
+        if answer != "y" && answer != "yes" {
+            eprintln!("Repair cancelled.");
+            return Ok(());
+        }
+    }
+
+    let mut written = Vec::new();
+    for (index, path, repair) in plans {
+        let backup = super::scrub::backup_path(&path);
+        fs::copy(&path, &backup)
+            .map_err(|e| SnatchError::io(format!("Failed to back up {}", path.display()), e))?;
+        atomic_write(&path, &repair.content)?;
+        results[index].repair = Some(RepairOutcome {
+            lines: repair.lines,
+            entries: repair.entries,
+            backup,
+        });
+        written.push(path);
+    }
+    let config = super::index::load_config(cli);
+    super::cleanup::purge_derived_data(cli, &config, &written)
 }
 
 /// Validation result for a session.
@@ -640,6 +863,22 @@ struct ValidationResult {
     warnings: Vec<String>,
     schema_version: Option<String>,
     unknown_fields: Vec<String>,
+    /// Fields violating the entry schema, with `--schema`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    schema_violations: Vec<SchemaViolation>,
+    /// Lines that failed to parse.
+    parse_errors: Vec<ParseDiagnostic>,
+    /// What `--repair` rewrote.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repair: Option<RepairOutcome>,
+}
+
+/// A session rewritten by `--repair`.
+#[derive(Debug, serde::Serialize)]
+struct RepairOutcome {
+    lines: usize,
+    entries: usize,
+    backup: PathBuf,
 }
 
 /// Complete validation report.
@@ -649,6 +888,9 @@ struct ValidationReport {
     total_errors: usize,
     total_warnings: usize,
     results: Vec<ValidationResult>,
+    /// Unknown fields across all sessions, with `--unknown-fields`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unknown_fields: Option<Vec<UnknownFieldCount>>,
 }
 
 #[cfg(test)]
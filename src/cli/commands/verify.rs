@@ -0,0 +1,405 @@
+//! Verify command implementation.
+//!
+//! Records content hashes of session files in a manifest and checks the
+//! files against it later, to catch silent corruption or edits. Session logs
+//! are append-only, so a file that grew is fine as long as it still starts
+//! with the recorded bytes; a shorter file or a changed prefix is reported.
+//!
+//! Every run also checks the conversation tree: each `parentUuid` must name
+//! an entry in the session's project (sessions resumed from another file
+//! point into that file), and every line must be valid JSON.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cli::{Cli, OutputFormat, VerifyArgs};
+use crate::discovery::Session;
+use crate::error::{Result, SnatchError};
+use crate::parser::read_session_to_string;
+use crate::util::{atomic_write, truncate_path};
+
+use super::get_claude_dir;
+use super::helpers::{collect_sessions, SessionCollectParams};
+use super::sync::hash_file_prefix;
+
+/// Default manifest file name, in the claude-snatch config directory.
+const MANIFEST_NAME: &str = "verify-manifest.json";
+
+/// Manifest format version.
+const MANIFEST_VERSION: u32 = 1;
+
+/// Recorded hashes, keyed by session file path.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    files: BTreeMap<PathBuf, Recorded>,
+}
+
+/// What was recorded for one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Recorded {
+    size: u64,
+    /// FNV-1a hash of the file's first `size` bytes.
+    hash: String,
+    recorded: DateTime<Utc>,
+}
+
+/// How a file compares with its recorded hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum HashStatus {
+    /// Same bytes as recorded.
+    Intact,
+    /// Recorded bytes intact, with more appended since.
+    Appended,
+    /// Recorded bytes changed.
+    Modified,
+    /// Shorter than recorded.
+    Truncated,
+    /// Not in the manifest.
+    Unrecorded,
+}
+
+/// A `parentUuid` that names no known entry.
+#[derive(Debug, Serialize)]
+struct DanglingParent {
+    line: usize,
+    uuid: Option<String>,
+    parent_uuid: String,
+}
+
+/// Check result for one file.
+#[derive(Debug, Serialize)]
+struct FileCheck {
+    path: PathBuf,
+    session_id: String,
+    status: HashStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    invalid_lines: Vec<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dangling_parents: Vec<DanglingParent>,
+    #[serde(skip)]
+    size: u64,
+    #[serde(skip)]
+    hash: String,
+}
+
+impl FileCheck {
+    fn has_problems(&self) -> bool {
+        matches!(self.status, HashStatus::Modified | HashStatus::Truncated)
+            || !self.invalid_lines.is_empty()
+            || !self.dangling_parents.is_empty()
+    }
+}
+
+/// The verify report (`-o json`).
+#[derive(Debug, Serialize)]
+struct VerifyReport {
+    manifest: PathBuf,
+    files: Vec<FileCheck>,
+    /// Recorded files that no longer exist (only checked without filters).
+    missing: Vec<PathBuf>,
+    recorded: bool,
+}
+
+/// Run the verify command.
+pub fn run(cli: &Cli, args: &VerifyArgs) -> Result<()> {
+    let manifest_path = match &args.manifest {
+        Some(path) => path.clone(),
+        None => default_manifest_path()?,
+    };
+    let mut manifest = load_manifest(&manifest_path)?;
+
+    let sessions = collect_sessions(
+        cli,
+        &SessionCollectParams {
+            session: args.session.as_deref(),
+            project: args.project.as_deref(),
+            since: None,
+            until: None,
+            recent: None,
+            no_subagents: false,
+        },
+    )?;
+    let full_scope = args.session.is_none() && args.project.is_none();
+
+    // Parents may live in other files of the same project (resumed
+    // sessions), so the known uuids span every session of those projects.
+    let universe = if full_scope {
+        sessions.clone()
+    } else {
+        let projects: HashSet<&str> = sessions.iter().map(Session::project_path).collect();
+        get_claude_dir(cli)?
+            .all_sessions()?
+            .into_iter()
+            .filter(|s| projects.contains(s.project_path()))
+            .collect()
+    };
+    let mut known = HashSet::new();
+    for session in &universe {
+        if let Ok(text) = read_session_to_string(session.path()) {
+            for value in text
+                .lines()
+                .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+            {
+                if let Some(uuid) = value.get("uuid").and_then(Value::as_str) {
+                    known.insert(uuid.to_string());
+                }
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for session in &sessions {
+        files.push(check_file(session, &manifest, &known)?);
+    }
+    let missing: Vec<PathBuf> = if full_scope {
+        let checked: BTreeSet<&Path> = files.iter().map(|f| f.path.as_path()).collect();
+        manifest
+            .files
+            .keys()
+            .filter(|path| !checked.contains(path.as_path()) && !path.exists())
+            .cloned()
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if args.record {
+        let now = Utc::now();
+        for file in &files {
+            manifest.files.insert(
+                file.path.clone(),
+                Recorded {
+                    size: file.size,
+                    hash: file.hash.clone(),
+                    recorded: now,
+                },
+            );
+        }
+        for path in &missing {
+            manifest.files.remove(path);
+        }
+        manifest.version = MANIFEST_VERSION;
+        atomic_write(&manifest_path, &serde_json::to_vec_pretty(&manifest)?)?;
+    }
+
+    let report = VerifyReport {
+        manifest: manifest_path,
+        files,
+        missing,
+        recorded: args.record,
+    };
+    let problems = report.files.iter().filter(|f| f.has_problems()).count()
+        + if args.record { 0 } else { report.missing.len() };
+    if matches!(cli.effective_output(), OutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(cli, &report);
+    }
+    if problems > 0 {
+        return Err(SnatchError::validation(format!(
+            "{problems} session file(s) failed verification"
+        )));
+    }
+    Ok(())
+}
+
+/// Hash-check one file and validate its parent links.
+fn check_file(
+    session: &Session,
+    manifest: &Manifest,
+    known: &HashSet<String>,
+) -> Result<FileCheck> {
+    let path = session.path().to_path_buf();
+    let (size, hash) = hash_file_prefix(&path, u64::MAX)?;
+    let status = match manifest.files.get(&path) {
+        None => HashStatus::Unrecorded,
+        Some(recorded) if size < recorded.size => HashStatus::Truncated,
+        Some(recorded) if size == recorded.size => {
+            if hash == recorded.hash {
+                HashStatus::Intact
+            } else {
+                HashStatus::Modified
+            }
+        }
+        Some(recorded) => {
+            if hash_file_prefix(&path, recorded.size)?.1 == recorded.hash {
+                HashStatus::Appended
+            } else {
+                HashStatus::Modified
+            }
+        }
+    };
+
+    let text = read_session_to_string(&path)
+        .map_err(|e| SnatchError::io(format!("Failed to read {}", path.display()), e))?;
+    let (invalid_lines, dangling_parents) = check_links(&text, known);
+    Ok(FileCheck {
+        path,
+        session_id: session.session_id().to_string(),
+        status,
+        invalid_lines,
+        dangling_parents,
+        size,
+        hash,
+    })
+}
+
+/// Line numbers that are not valid JSON, and `parentUuid`s that name no
+/// entry in `known`.
+fn check_links(text: &str, known: &HashSet<String>) -> (Vec<usize>, Vec<DanglingParent>) {
+    let mut invalid = Vec::new();
+    let mut dangling = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            invalid.push(index + 1);
+            continue;
+        };
+        let Some(parent) = value.get("parentUuid").and_then(Value::as_str) else {
+            continue;
+        };
+        if !known.contains(parent) {
+            dangling.push(DanglingParent {
+                line: index + 1,
+                uuid: value
+                    .get("uuid")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                parent_uuid: parent.to_string(),
+            });
+        }
+    }
+    (invalid, dangling)
+}
+
+fn print_report(cli: &Cli, report: &VerifyReport) {
+    let count = |status| report.files.iter().filter(|f| f.status == status).count();
+    for file in &report.files {
+        let label = match file.status {
+            HashStatus::Modified => "MODIFIED",
+            HashStatus::Truncated => "TRUNCATED",
+            _ if file.has_problems() => "BROKEN",
+            _ => continue,
+        };
+        println!(
+            "{label:<9}  {}",
+            truncate_path(&file.path.display().to_string(), 90)
+        );
+        for line in &file.invalid_lines {
+            println!("           line {line}: not valid JSON");
+        }
+        for parent in &file.dangling_parents {
+            println!(
+                "           line {}: parentUuid {} not found",
+                parent.line, parent.parent_uuid
+            );
+        }
+    }
+    for path in &report.missing {
+        println!(
+            "MISSING    {}",
+            truncate_path(&path.display().to_string(), 90)
+        );
+    }
+
+    if cli.quiet {
+        return;
+    }
+    if !report.files.is_empty() || !report.missing.is_empty() {
+        println!();
+    }
+    println!(
+        "Checked {} file(s): {} intact, {} appended, {} modified, {} truncated, {} unrecorded, {} missing",
+        report.files.len(),
+        count(HashStatus::Intact),
+        count(HashStatus::Appended),
+        count(HashStatus::Modified),
+        count(HashStatus::Truncated),
+        count(HashStatus::Unrecorded),
+        report.missing.len(),
+    );
+    if report.recorded {
+        println!("Recorded current hashes in {}", report.manifest.display());
+    } else if count(HashStatus::Unrecorded) > 0 {
+        println!("Run with --record to add unrecorded files to the manifest.");
+    }
+}
+
+fn default_manifest_path() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().ok_or_else(|| SnatchError::Unsupported {
+        feature: "config directory discovery".to_string(),
+    })?;
+    Ok(config_dir.join("claude-snatch").join(MANIFEST_NAME))
+}
+
+fn load_manifest(path: &Path) -> Result<Manifest> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Manifest {
+                version: MANIFEST_VERSION,
+                files: BTreeMap::new(),
+            })
+        }
+        Err(e) => return Err(SnatchError::io(path.display().to_string(), e)),
+    };
+    let manifest: Manifest = serde_json::from_str(&text).map_err(|e| SnatchError::ConfigError {
+        message: format!("Invalid verify manifest {}: {e}", path.display()),
+    })?;
+    if manifest.version > MANIFEST_VERSION {
+        return Err(SnatchError::ConfigError {
+            message: format!(
+                "{} was written by a newer snatch (manifest version {})",
+                path.display(),
+                manifest.version
+            ),
+        });
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_links_reports_dangling_parents_and_bad_lines() {
+        let text = concat!(
+            "{\"type\":\"user\",\"uuid\":\"a\",\"parentUuid\":null}\n",
+            "{\"type\":\"assistant\",\"uuid\":\"b\",\"parentUuid\":\"a\"}\n",
+            "{\"type\":\"user\",\"uuid\":\"c\",\"parentUuid\":\"gone\"}\n",
+            "{\"type\":\"assistant\",\"uuid\":\n",
+            "\n",
+            "{\"type\":\"summary\",\"summary\":\"s\",\"leafUuid\":\"b\"}\n",
+        );
+        let known: HashSet<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let (invalid, dangling) = check_links(text, &known);
+        assert_eq!(invalid, [4]);
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].line, 3);
+        assert_eq!(dangling[0].parent_uuid, "gone");
+        assert_eq!(dangling[0].uuid.as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_appended_file_keeps_recorded_prefix_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        fs::write(&path, "{\"uuid\":\"a\"}\n").unwrap();
+        let (size, hash) = hash_file_prefix(&path, u64::MAX).unwrap();
+
+        fs::write(&path, "{\"uuid\":\"a\"}\n{\"uuid\":\"b\"}\n").unwrap();
+        assert_eq!(hash_file_prefix(&path, size).unwrap(), (size, hash.clone()));
+        fs::write(&path, "{\"uuid\":\"x\"}\n{\"uuid\":\"b\"}\n").unwrap();
+        assert_ne!(hash_file_prefix(&path, size).unwrap().1, hash);
+    }
+}
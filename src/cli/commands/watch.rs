@@ -27,7 +27,7 @@ pub fn run(cli: &Cli, args: &WatchArgs) -> Result<()> {
             "provider-qualified sessions require a provider-owned active-artifact change cursor; use `snatch messages` or `snatch timeline` for point-in-time views",
         )?;
     }
-    let claude_dir = get_claude_dir(cli.claude_dir.as_ref())?;
+    let claude_dir = get_claude_dir(cli)?;
     let poll_interval = Duration::from_millis(args.interval);
 
     // Find sessions to watch
@@ -390,10 +390,8 @@ fn run_live_dashboard(cli: &Cli, sessions: &[Session], poll_interval: Duration)
         // Get last 5 messages across all sessions
         let mut recent_entries: Vec<(String, LogEntry)> = Vec::new();
         for session in sessions {
-            let entries = session
-                .parse_with_options(cli.max_file_size)
-                .unwrap_or_default();
-            for entry in entries.into_iter().rev().take(5) {
+            let entries = crate::parser::parse_tail(session.path(), 5).unwrap_or_default();
+            for entry in entries.into_iter().rev() {
                 recent_entries.push((session.session_id().to_string(), entry));
             }
         }
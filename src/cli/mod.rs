@@ -23,6 +23,8 @@ use crate::error::Result;
 #[cfg(feature = "mcp")]
 use crate::error::SnatchError;
 use crate::export::ExportFormat;
+use crate::util::age::EncryptTarget;
+use crate::util::json_filter::JsonFilter;
 
 /// Claude Code conversation extractor with maximum data fidelity.
 #[derive(Debug, Parser)]
@@ -43,7 +45,8 @@ pub struct Cli {
     #[arg(long = "help", global = true, action = ArgAction::HelpLong)]
     long_help: (),
 
-    /// Path to Claude directory (default: ~/.claude).
+    /// Path to Claude directory (default: ~/.claude), or
+    /// `ssh://[user@]host[:port]/path` to mirror one from another machine.
     #[arg(
         short = 'd',
         long,
@@ -225,13 +228,17 @@ pub enum Commands {
     Recent(RecentArgs),
 
     /// Display detailed information about a session or project.
-    #[command(alias = "i", alias = "show", display_order = 3)]
+    #[command(alias = "i", display_order = 3)]
     Info(InfoArgs),
 
     /// Interactively pick a session using fuzzy search.
     #[command(alias = "browse", display_order = 4)]
     Pick(PickArgs),
 
+    /// Interactive query shell over a cached session catalog.
+    #[command(alias = "repl", display_order = 4)]
+    Shell(ShellArgs),
+
     /// List classic chains or typed provider lineage.
     #[command(display_order = 5)]
     Chain(commands::chain::ChainArgs),
@@ -240,6 +247,10 @@ pub enum Commands {
     #[command(name = "file-history", display_order = 6)]
     FileHistory(commands::file_history::FileHistoryArgs),
 
+    /// Show which session and message last changed a file or line range.
+    #[command(display_order = 6)]
+    Blame(BlameArgs),
+
     // ═══════════════════════════════════════════════════════════════════════
     // SEARCH - Find content across sessions
     // ═══════════════════════════════════════════════════════════════════════
@@ -266,6 +277,10 @@ pub enum Commands {
     #[command(alias = "daily", display_order = 22)]
     Standup(StandupArgs),
 
+    /// Compose a periodic usage and cost report (Markdown or plain text).
+    #[command(display_order = 22)]
+    Report(ReportArgs),
+
     /// Compare two sessions or conversation versions.
     #[command(alias = "d", display_order = 23)]
     Diff(DiffArgs),
@@ -286,7 +301,7 @@ pub enum Commands {
     #[command(display_order = 24)]
     Priorities(PrioritiesArgs),
 
-    /// Diagnose schema drift: unmodeled entry types, degraded features.
+    /// Diagnose the environment and schema drift, with optional safe repairs.
     #[command(display_order = 24)]
     Doctor(DoctorArgs),
 
@@ -298,7 +313,11 @@ pub enum Commands {
     #[command(display_order = 24)]
     Context(ContextArgs),
 
-    /// Show turn-by-turn narrative timeline of a session.
+    /// Show a message by UUID with its thread context, in any export format.
+    #[command(display_order = 24)]
+    Show(ShowArgs),
+
+    /// Show a session's turn-by-turn timeline, or an activity log across sessions.
     #[command(display_order = 25)]
     Timeline(TimelineArgs),
 
@@ -333,6 +352,18 @@ pub enum Commands {
     #[command(alias = "x", display_order = 30)]
     Export(ExportArgs),
 
+    /// Run export jobs defined in a TOML manifest.
+    #[command(display_order = 30)]
+    Batch(BatchArgs),
+
+    /// Decrypt an export written with `export --encrypt`.
+    #[command(display_order = 30)]
+    Decrypt(DecryptArgs),
+
+    /// Import conversations from other tools (ChatGPT, generic JSONL).
+    #[command(display_order = 30)]
+    Import(ImportArgs),
+
     /// Grab one whole session — parent plus its subagents — as a single file
     /// ready to hand to Claude. Readable markdown with full tool outputs by
     /// default; `--raw` for a byte-faithful JSONL bundle.
@@ -358,6 +389,14 @@ pub enum Commands {
     #[command(display_order = 41)]
     Watch(WatchArgs),
 
+    /// Monitor recently active sessions in a refreshing table.
+    #[command(display_order = 41)]
+    Top(TopArgs),
+
+    /// Serve read-only session data as JSON over HTTP.
+    #[command(display_order = 42)]
+    Serve(ServeArgs),
+
     // ═══════════════════════════════════════════════════════════════════════
     // MANAGEMENT - Session maintenance
     // ═══════════════════════════════════════════════════════════════════════
@@ -365,22 +404,54 @@ pub enum Commands {
     #[command(display_order = 50)]
     Tag(TagArgs),
 
-    /// Clean up old or empty sessions.
-    #[command(alias = "clean", display_order = 51)]
+    /// Clean up old or empty sessions, deleting or archiving them.
+    #[command(alias = "clean", alias = "prune", display_order = 51)]
     Cleanup(CleanupArgs),
 
+    /// Find duplicated sessions and entries, optionally removing them.
+    #[command(display_order = 51)]
+    Dedupe(DedupeArgs),
+
+    /// Redact sensitive values in a session's original JSONL, in place.
+    #[command(display_order = 51)]
+    Scrub(ScrubArgs),
+
+    /// Back up session data incrementally, verify the backup, or restore it.
+    #[command(display_order = 51)]
+    Sync(SyncArgs),
+
     /// Validate session source and normalized integrity.
     #[command(display_order = 52)]
     Validate(ValidateArgs),
 
+    /// Record and check content hashes of session files, and their parent links.
+    #[command(display_order = 52)]
+    Verify(VerifyArgs),
+
     /// Manage the session cache.
     #[command(display_order = 53)]
     Cache(CacheArgs),
 
+    /// Measure parse throughput on your own session files.
+    #[command(display_order = 53)]
+    Bench(BenchArgs),
+
     /// Manage the full-text search index.
     #[command(alias = "idx", display_order = 54)]
     Index(IndexArgs),
 
+    /// Annotate a session or one of its messages with a note.
+    #[command(display_order = 50)]
+    Annotate(AnnotateArgs),
+
+    /// Merge two sessions into one JSONL file, interleaved by timestamp.
+    #[command(display_order = 55)]
+    Merge(MergeArgs),
+
+    /// Split a session into two JSONL files at a message or time.
+    #[command(display_order = 56)]
+    Split(SplitArgs),
+
     // ═══════════════════════════════════════════════════════════════════════
     // CONFIGURATION - Settings and setup
     // ═══════════════════════════════════════════════════════════════════════
@@ -497,10 +568,26 @@ pub enum OutputFormat {
     Json,
     /// Tab-separated values.
     Tsv,
+    /// Comma-separated values. `list`, `stats`, and `search` emit RFC 4180
+    /// CSV; other commands fall back to their TSV layout.
+    Csv,
     /// Compact single-line output.
     Compact,
 }
 
+/// Column selection for TSV/CSV output.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct TableArgs {
+    /// Columns to emit in TSV/CSV output, in the given order
+    /// (comma-separated). An unknown name lists the available columns.
+    #[arg(long, value_delimiter = ',', value_name = "COLUMNS")]
+    pub columns: Option<Vec<String>>,
+
+    /// Omit the header row from TSV/CSV output (e.g. for awk pipelines).
+    #[arg(long)]
+    pub no_header: bool,
+}
+
 /// Arguments for the list command.
 #[derive(Debug, Parser)]
 pub struct ListArgs {
@@ -608,6 +695,15 @@ pub struct ListArgs {
     /// chain into one logical conversation (restores the flat per-file view).
     #[arg(long)]
     pub no_chain: bool,
+
+    /// Filter the JSON output with a jq-style expression, e.g.
+    /// '.[] | select(.file_size > 1000000) | .session_id'.
+    #[arg(long, value_name = "EXPR", value_parser = parse_json_filter)]
+    pub filter: Option<JsonFilter>,
+
+    /// TSV/CSV column selection.
+    #[command(flatten)]
+    pub table: TableArgs,
 }
 
 /// What to list.
@@ -696,7 +792,7 @@ pub struct GrabArgs {
 }
 
 /// Arguments for the export command.
-#[derive(Debug, Parser)]
+#[derive(Debug, Clone, Parser)]
 pub struct ExportArgs {
     /// Session ID to export (supports short prefixes like "780893e4" and
     /// provider-qualified ids like "codex:0198c5c1").
@@ -713,14 +809,10 @@ pub struct ExportArgs {
     #[arg(short = 'O', long = "out")]
     pub output_file: Option<PathBuf>,
 
-    /// Export format.
-    #[arg(
-        short = 'f',
-        long,
-        default_value = "markdown",
-        env = "SNATCH_EXPORT_FORMAT"
-    )]
-    pub format: ExportFormatArg,
+    /// Export format (default markdown). A preset's format applies only when
+    /// neither this flag nor `SNATCH_EXPORT_FORMAT` sets one.
+    #[arg(short = 'f', long, env = "SNATCH_EXPORT_FORMAT")]
+    pub format: Option<ExportFormatArg>,
 
     /// Export all sessions.
     #[arg(short = 'a', long)]
@@ -738,6 +830,14 @@ pub struct ExportArgs {
     #[arg(long)]
     pub until: Option<String>,
 
+    /// Export only sessions with this tag (with --all).
+    #[arg(long)]
+    pub tag: Option<String>,
+
+    /// Export only sessions with any of these tags (comma-separated, with --all).
+    #[arg(long)]
+    pub tags: Option<String>,
+
     /// Include subagent sessions in `--all` batch listing (no effect on a single
     /// session — use --combine-agents to pull subagents into one export).
     #[arg(long)]
@@ -814,6 +914,11 @@ pub struct ExportArgs {
     #[arg(long)]
     pub metadata: bool,
 
+    /// Append an Annotations section with the session's notes (markdown and
+    /// text formats; see `snatch annotate`).
+    #[arg(long)]
+    pub annotations: bool,
+
     /// Only export main thread (exclude branches). By default all entries are exported.
     #[arg(long)]
     pub main_thread: bool,
@@ -894,6 +999,107 @@ pub struct ExportArgs {
     /// List available export templates.
     #[arg(long)]
     pub list_templates: bool,
+
+    /// Filter the JSON document with a jq-style expression, e.g.
+    /// '.entries[] | select(.type == "user")' (json and json-pretty only).
+    #[arg(long, value_name = "EXPR", value_parser = parse_json_filter)]
+    pub filter: Option<JsonFilter>,
+
+    /// Encrypt the output file with age: `age:<recipient>` (repeatable),
+    /// `age-file:<path>`, or `passphrase`. Needs --out and the `age` CLI.
+    #[arg(long, value_name = "TARGET", value_parser = parse_encrypt_target)]
+    pub encrypt: Vec<EncryptTarget>,
+
+    /// Apply the flags saved in the config as `[presets.<NAME>]`; flags
+    /// given on the command line take precedence.
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+}
+
+/// Arguments for the decrypt command.
+#[derive(Debug, Parser)]
+pub struct DecryptArgs {
+    /// Encrypted export file.
+    #[arg(value_name = "FILE")]
+    pub input: PathBuf,
+
+    /// Write the decrypted export here instead of stdout.
+    #[arg(short = 'O', long = "out")]
+    pub output_file: Option<PathBuf>,
+
+    /// age identity file holding the private key (repeatable). Not needed
+    /// for passphrase-encrypted files; age prompts for the passphrase.
+    #[arg(short = 'i', long, value_name = "FILE")]
+    pub identity: Vec<PathBuf>,
+
+    /// Replace an existing output file.
+    #[arg(long)]
+    pub overwrite: bool,
+}
+
+/// Arguments for the import command.
+#[derive(Debug, Parser)]
+pub struct ImportArgs {
+    /// File to import (a ChatGPT `conversations.json`, or JSONL messages).
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Format of the file.
+    #[arg(short = 'f', long, value_enum)]
+    pub format: ImportFormat,
+
+    /// Project to file the conversations under (default: the format name).
+    /// They appear as `/imported/<PROJECT>` in listings and filters.
+    #[arg(short = 'p', long, value_name = "PROJECT")]
+    pub project: Option<String>,
+
+    /// Show what would be imported without writing anything.
+    #[arg(long, alias = "dry-run")]
+    pub preview: bool,
+}
+
+/// Source formats for the import command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// ChatGPT data export (`conversations.json`).
+    Chatgpt,
+    /// One JSON message per line: `role`, `content`, and optional
+    /// `timestamp`, `model`, `conversation_id`.
+    GenericJsonl,
+}
+
+/// Arguments for the batch command.
+#[derive(Debug, Parser)]
+pub struct BatchArgs {
+    /// Batch subcommand to run.
+    #[command(subcommand)]
+    pub command: BatchSubcommand,
+}
+
+/// Batch subcommand actions.
+#[derive(Debug, Subcommand)]
+pub enum BatchSubcommand {
+    /// Run the export jobs in a manifest.
+    Run(BatchRunArgs),
+}
+
+/// Arguments for `batch run`.
+#[derive(Debug, Parser)]
+pub struct BatchRunArgs {
+    /// Manifest file with `[defaults]` and `[[job]]` tables.
+    pub manifest: PathBuf,
+
+    /// Run only this job (repeatable).
+    #[arg(long = "job", value_name = "NAME")]
+    pub jobs: Vec<String>,
+
+    /// Preview the files each job would write without exporting.
+    #[arg(long, alias = "dry-run")]
+    pub preview: bool,
+
+    /// Replace existing output files (overrides the manifest).
+    #[arg(long)]
+    pub overwrite: bool,
 }
 
 /// Export format argument — the full CLI-facing format surface.
@@ -957,6 +1163,14 @@ impl From<RedactionLevel> for crate::util::RedactionConfig {
     }
 }
 
+impl ExportArgs {
+    /// The export format, markdown unless one was given.
+    #[must_use]
+    pub fn format(&self) -> ExportFormatArg {
+        self.format.unwrap_or_default()
+    }
+}
+
 impl From<ExportFormatArg> for ExportFormat {
     fn from(arg: ExportFormatArg) -> Self {
         match arg {
@@ -979,7 +1193,7 @@ impl From<ExportFormatArg> for ExportFormat {
 }
 
 /// Arguments for the search command.
-#[derive(Debug, Parser)]
+#[derive(Debug, Clone, Parser)]
 pub struct SearchArgs {
     /// Search pattern(s) (regex supported). Multiple patterns run in a single pass.
     pub pattern: Vec<String>,
@@ -1133,6 +1347,30 @@ pub struct SearchArgs {
     /// Show message UUIDs in text output (useful for `context` lookups).
     #[arg(long)]
     pub show_uuid: bool,
+
+    /// Rank messages by meaning rather than regex match, fusing embedding
+    /// similarity with keyword relevance (requires the `semantic` feature).
+    #[arg(long)]
+    pub semantic: bool,
+
+    /// Save matches with full metadata to a .json or .csv file instead of
+    /// printing them.
+    #[arg(long = "out", value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+
+    /// Run a search saved in the config as `[searches.<NAME>]`; flags given
+    /// here override its settings.
+    #[arg(long, value_name = "NAME")]
+    pub saved: Option<String>,
+
+    /// Filter the JSON output with a jq-style expression, e.g.
+    /// '.[] | {session_id, matched_text}'.
+    #[arg(long, value_name = "EXPR", value_parser = parse_json_filter)]
+    pub filter: Option<JsonFilter>,
+
+    /// TSV/CSV column selection.
+    #[command(flatten)]
+    pub table: TableArgs,
 }
 
 /// Arguments for the stats command.
@@ -1160,6 +1398,14 @@ pub struct StatsArgs {
     #[arg(short = 'p', long, value_delimiter = ',')]
     pub project: Option<Vec<String>>,
 
+    /// Show stats for sessions with this tag.
+    #[arg(long)]
+    pub tag: Option<String>,
+
+    /// Show stats for sessions with any of these tags (comma-separated).
+    #[arg(long)]
+    pub tags: Option<String>,
+
     /// Show global stats across all sessions.
     #[arg(long)]
     pub global: bool,
@@ -1220,7 +1466,8 @@ pub struct StatsArgs {
     #[arg(long)]
     pub clear_history: bool,
 
-    /// Show activity timeline visualization.
+    /// Show activity timeline visualization. With a session, show
+    /// per-turn input, output, and cache tokens instead.
     #[arg(long)]
     pub timeline: bool,
 
@@ -1235,6 +1482,116 @@ pub struct StatsArgs {
     /// Width of graph visualization (default: 60).
     #[arg(long, value_name = "WIDTH", default_value = "60")]
     pub graph_width: usize,
+
+    /// Metric that ranks the project leaderboard in global stats.
+    #[arg(long, value_enum, default_value = "tokens", value_name = "METRIC")]
+    pub rank_by: LeaderboardMetric,
+
+    /// Show an hour-of-day × day-of-week activity heatmap (local time).
+    /// Scoped by the session or --project when given, otherwise all sessions.
+    #[arg(long)]
+    pub heatmap: bool,
+
+    /// Split tokens, cost, and tool calls between parent sessions and
+    /// subagents, broken down by agent type. A session scope includes its
+    /// subagent transcripts.
+    #[arg(long)]
+    pub agents: bool,
+
+    /// Show lines changed per programming language, with a per-project
+    /// breakdown when the scope spans several projects.
+    #[arg(long)]
+    pub languages: bool,
+
+    /// Correlate prompt length with response output tokens and latency,
+    /// with a least-squares fit and per-length-range averages.
+    #[arg(long)]
+    pub correlation: bool,
+
+    /// Show tool call transitions (e.g. Read → Edit) with their
+    /// probabilities and the most common multi-step workflows.
+    #[arg(long)]
+    pub tool_chains: bool,
+
+    /// Report abandoned conversation branches (edits and retries): how many,
+    /// their average depth, and the tokens spent on them.
+    #[arg(long)]
+    pub branches: bool,
+
+    /// Show rolling usage (tokens, cost, messages) over trailing windows
+    /// ending now, counting each entry by its own timestamp.
+    #[arg(long)]
+    pub rolling: bool,
+
+    /// Report API errors by type and status, daily error rates, retry
+    /// attempts, and tokens wasted on retried turns.
+    #[arg(long)]
+    pub errors: bool,
+
+    /// Report edit churn: Edit calls that rewrite text an earlier edit in
+    /// the same session wrote, and the files reworked most (hotspots).
+    #[arg(long)]
+    pub churn: bool,
+
+    /// Build a graph of files modified together across sessions, with
+    /// edges weighted by the number of sessions touching both. Export it
+    /// with `--format dot` or `-o json`.
+    #[arg(long)]
+    pub co_modification: bool,
+
+    /// Group tokens, cost, messages, and active time by the git branch
+    /// recorded on each entry, ranked by tokens.
+    #[arg(long)]
+    pub by_branch: bool,
+
+    /// Rolling window lengths (comma-separated, e.g. "1h,24h,7d").
+    /// Defaults to 24h, 7d, and 30d.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "PERIODS",
+        requires = "rolling"
+    )]
+    pub windows: Option<Vec<String>>,
+
+    /// Emit in a stats-specific format instead of `--output`.
+    /// `prometheus` writes usage counters and gauges in the Prometheus text
+    /// exposition format, labeled by project and model. `dot` writes the
+    /// `--co-modification` graph for Graphviz.
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub format: Option<StatsFormat>,
+
+    /// Ignore the persisted per-session analytics snapshots and recompute
+    /// global stats from every transcript, rewriting the snapshots.
+    #[arg(long)]
+    pub rebuild_snapshots: bool,
+
+    /// TSV/CSV column selection.
+    #[command(flatten)]
+    pub table: TableArgs,
+}
+
+/// Ranking metric for the global stats project leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LeaderboardMetric {
+    /// Work tokens (input + cache creation + output).
+    #[default]
+    Tokens,
+    /// Estimated API list cost.
+    Cost,
+    /// Session count.
+    Sessions,
+    /// File modifications (Edit/Write).
+    Files,
+}
+
+/// Stats-specific output formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsFormat {
+    /// Prometheus text exposition format (version 0.0.4).
+    Prometheus,
+    /// Graphviz DOT (with `--co-modification`).
+    Dot,
 }
 
 /// Arguments for the info command.
@@ -1296,17 +1653,70 @@ pub struct ValidateArgs {
     #[arg(short = 'a', long)]
     pub all: bool,
 
-    /// Check for schema compatibility.
+    /// Check for schema compatibility, and check every entry against the
+    /// entry JSON Schema, reporting each violating field with the entry's
+    /// line and UUID.
     #[arg(long)]
     pub schema: bool,
 
-    /// Report unknown fields.
+    /// Report fields and entry types the model does not know, counted
+    /// across the validated sessions with an example value each. With
+    /// --tsv/--csv, the census replaces the per-session table.
     #[arg(long)]
     pub unknown_fields: bool,
 
     /// Check parent-child relationships.
     #[arg(long)]
     pub relationships: bool,
+
+    /// Fail a session on its first malformed line or schema deviation
+    /// (unknown field, entry type, or content-block type) instead of
+    /// skipping malformed lines.
+    #[arg(long, conflicts_with = "repair")]
+    pub strict: bool,
+
+    /// Rewrite sessions to recover entries from concatenated and torn
+    /// lines. Originals are backed up first; active sessions are skipped.
+    #[arg(long)]
+    pub repair: bool,
+
+    /// Skip the confirmation prompt for --repair.
+    #[arg(short = 'y', long, requires = "repair")]
+    pub yes: bool,
+}
+
+/// Arguments for the verify command.
+#[derive(Debug, Parser)]
+pub struct VerifyArgs {
+    /// Session ID to verify (supports short prefixes). Default: all sessions.
+    pub session: Option<String>,
+
+    /// Only verify sessions of projects matching this path substring.
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Record the current hashes in the manifest after checking (new files
+    /// are added, files deleted since are dropped).
+    #[arg(long)]
+    pub record: bool,
+
+    /// Manifest file (default: `verify-manifest.json` in the claude-snatch
+    /// config directory).
+    #[arg(long, value_name = "FILE")]
+    pub manifest: Option<PathBuf>,
+}
+
+/// Arguments for the serve command.
+#[derive(Debug, Parser)]
+pub struct ServeArgs {
+    /// Port to listen on.
+    #[arg(long, default_value = "8080")]
+    pub port: u16,
+
+    /// Address to bind. Session logs are private, so only bind a
+    /// non-loopback address on a trusted network.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub bind: std::net::IpAddr,
 }
 
 /// Arguments for the watch command.
@@ -1332,6 +1742,26 @@ pub struct WatchArgs {
     pub interval: u64,
 }
 
+/// Arguments for the top command.
+#[derive(Debug, Parser)]
+pub struct TopArgs {
+    /// Filter by project path (substring match).
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Maximum number of sessions to show.
+    #[arg(short = 'n', long, default_value = "20")]
+    pub limit: usize,
+
+    /// Refresh interval in milliseconds.
+    #[arg(long, default_value = "2000")]
+    pub interval: u64,
+
+    /// Print one snapshot and exit.
+    #[arg(long)]
+    pub once: bool,
+}
+
 /// Arguments for the diff command.
 #[derive(Debug, Parser)]
 pub struct DiffArgs {
@@ -1400,13 +1830,13 @@ pub enum ConfigAction {
 
     /// Get a specific configuration value.
     Get {
-        /// Configuration key (e.g., "theme.color").
+        /// Configuration key (e.g., "display.full_ids").
         key: String,
     },
 
     /// Set a configuration value.
     Set {
-        /// Configuration key (e.g., "theme.color").
+        /// Configuration key (e.g., "display.full_ids").
         key: String,
         /// Value to set.
         value: String,
@@ -1420,6 +1850,55 @@ pub enum ConfigAction {
 
     /// Reset configuration to defaults.
     Reset,
+
+    /// List, add, or remove saved searches.
+    Searches {
+        /// Saved search action.
+        #[command(subcommand)]
+        action: SavedSearchAction,
+    },
+}
+
+/// Saved search actions.
+#[derive(Debug, Subcommand)]
+pub enum SavedSearchAction {
+    /// List saved searches.
+    List,
+
+    /// Save a search under a name, replacing any with that name.
+    Add {
+        /// Name to run it by (`snatch search --saved <NAME>`).
+        name: String,
+        /// Search pattern (regex supported).
+        query: String,
+        /// Filter by message type.
+        #[arg(short = 't', long = "type")]
+        message_type: Option<String>,
+        /// Search in specific project.
+        #[arg(short = 'p', long)]
+        project: Option<String>,
+        /// Filter by model.
+        #[arg(short = 'm', long)]
+        model: Option<String>,
+        /// Filter by tool name.
+        #[arg(long)]
+        tool_name: Option<String>,
+        /// Case-insensitive matching.
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+        /// Include thinking blocks.
+        #[arg(long)]
+        thinking: bool,
+        /// Include tool inputs and results.
+        #[arg(long)]
+        tools: bool,
+    },
+
+    /// Remove a saved search.
+    Remove {
+        /// Name of the saved search.
+        name: String,
+    },
 }
 
 /// Arguments for the cache command.
@@ -1454,6 +1933,35 @@ pub enum CacheAction {
     },
 }
 
+/// Arguments for the bench command.
+#[derive(Debug, Parser)]
+pub struct BenchArgs {
+    /// Benchmark to run.
+    #[command(subcommand)]
+    pub action: BenchAction,
+}
+
+/// Bench subcommands.
+#[derive(Debug, Subcommand)]
+pub enum BenchAction {
+    /// Time the parse strategies (buffered, mmap, simd, parallel) and
+    /// compare their throughput.
+    Parse {
+        /// Session files, or directories searched for `.jsonl` files.
+        /// Default: the sessions of the Claude directory.
+        #[arg(long, value_name = "PATH")]
+        path: Vec<PathBuf>,
+
+        /// Benchmark at most this many files, the largest first (0 for all).
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Runs per strategy; the fastest counts.
+        #[arg(short = 'n', long, default_value = "3")]
+        iterations: usize,
+    },
+}
+
 /// Arguments for the extract command.
 #[derive(Debug, Parser)]
 pub struct ExtractArgs {
@@ -1509,7 +2017,8 @@ pub struct IndexArgs {
 /// Index subcommand actions.
 #[derive(Debug, Subcommand)]
 pub enum IndexSubcommand {
-    /// Build or update the search index.
+    /// Build or update the search index. Incremental: only new and changed
+    /// sessions are re-indexed, and sessions whose logs are gone are dropped.
     Build(IndexBuildArgs),
 
     /// Rebuild the index from scratch.
@@ -1523,6 +2032,9 @@ pub enum IndexSubcommand {
 
     /// Search the committed provider index.
     Search(IndexSearchArgs),
+
+    /// Keep the index up to date, re-indexing whenever session logs change.
+    Watch(IndexWatchArgs),
 }
 
 /// Arguments for index build command.
@@ -1538,6 +2050,23 @@ pub struct IndexBuildArgs {
     pub project: Option<String>,
 }
 
+/// Arguments for index watch command.
+#[derive(Debug, Parser)]
+pub struct IndexWatchArgs {
+    /// Provider(s) to index. Defaults to claude-code; use `all` for every
+    /// available provider.
+    #[arg(long = "provider", value_name = "PROVIDER")]
+    pub provider: Vec<String>,
+
+    /// Only index sessions from specific project.
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Polling interval in milliseconds.
+    #[arg(long, default_value = "2000")]
+    pub interval: u64,
+}
+
 /// Arguments for index rebuild command.
 #[derive(Debug, Parser)]
 pub struct IndexRebuildArgs {
@@ -1551,10 +2080,28 @@ pub struct IndexRebuildArgs {
     pub project: Option<String>,
 }
 
+/// Result order for `index search --sort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IndexSearchSort {
+    /// Session, entry, segment, then line.
+    Source,
+    /// Exact matcher score, best first.
+    Relevance,
+    /// Entry timestamp, newest first.
+    Time,
+}
+
 /// Arguments for index search command.
 #[derive(Debug, Parser)]
 pub struct IndexSearchArgs {
-    /// Search query.
+    /// Search query. A single word or quoted phrase is a regex. Otherwise
+    /// the query uses the index query syntax: adjacent terms must all
+    /// match, `OR` and `NOT` (or `-term`) combine them, parentheses group,
+    /// and `field:value` narrows a term to `content`, `thinking`,
+    /// `tool_input`, or `tool_output`, or matches an exact `session_id`,
+    /// `project`, `uuid`, `message_type`, `model`, `tool_name`, or
+    /// `source`. Terms match literally, and a prefix that names no field
+    /// (`std::fs`, a URL) is plain text.
     pub query: String,
 
     /// Indexed provider partition(s). Defaults to claude-code; use `all` for
@@ -1578,11 +2125,21 @@ pub struct IndexSearchArgs {
     #[arg(long = "tool-name")]
     pub tool_name: Option<String>,
 
+    /// Only sessions read from this `[[index.sources]]` directory (`local`
+    /// for the default one). Repeatable.
+    #[arg(long = "source", value_name = "NAME")]
+    pub source: Vec<String>,
+
     /// Include thinking blocks.
     #[arg(long)]
     pub thinking: bool,
 
-    /// Case-insensitive regex or fuzzy matching.
+    /// Search tool inputs and outputs (command output, file reads) instead
+    /// of message text; with `--thinking`, search everything.
+    #[arg(long)]
+    pub tools: bool,
+
+    /// Case-insensitive matching.
     #[arg(short = 'i', long)]
     pub ignore_case: bool,
 
@@ -1590,6 +2147,10 @@ pub struct IndexSearchArgs {
     #[arg(short = 'f', long)]
     pub fuzzy: bool,
 
+    /// Treat the whole query as one regex instead of query syntax.
+    #[arg(long, conflicts_with = "fuzzy")]
+    pub regex: bool,
+
     /// Minimum fuzzy score (0-100).
     #[arg(long, default_value = "60")]
     pub fuzzy_threshold: u8,
@@ -1598,13 +2159,25 @@ pub struct IndexSearchArgs {
     #[arg(long, default_value = "0")]
     pub offset: usize,
 
+    /// 1-based page of `--limit` results (alternative to `--offset`).
+    #[arg(long, value_name = "N", conflicts_with = "offset")]
+    pub page: Option<usize>,
+
     /// Same-scope exclusion regex.
     #[arg(long)]
     pub exclude: Option<String>,
 
-    /// Sort by exact matcher relevance.
-    #[arg(long)]
-    pub sort: bool,
+    /// Result order: relevance (the default for a bare `--sort`), time
+    /// (newest first), or source. Without `--sort`, results follow source
+    /// order.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "ORDER",
+        num_args = 0..=1,
+        default_missing_value = "relevance"
+    )]
+    pub sort: Option<IndexSearchSort>,
 
     /// Context lines retained inside each projected segment.
     #[arg(short = 'C', long, default_value = "2")]
@@ -1613,6 +2186,15 @@ pub struct IndexSearchArgs {
     /// Maximum number of results.
     #[arg(short = 'n', long)]
     pub limit: Option<usize>,
+
+    /// Save matches with full metadata to a .json or .csv file instead of
+    /// printing them.
+    #[arg(long = "out", value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+
+    /// TSV/CSV column selection.
+    #[command(flatten)]
+    pub table: TableArgs,
 }
 
 /// Arguments for the cleanup command.
@@ -1634,6 +2216,14 @@ pub struct CleanupArgs {
     #[arg(long)]
     pub subagents: bool,
 
+    /// Keep sessions with tags, a name, notes, or bookmarks.
+    #[arg(long)]
+    pub keep_tagged: bool,
+
+    /// Move sessions into this directory instead of deleting them.
+    #[arg(long, value_name = "DIR")]
+    pub archive: Option<PathBuf>,
+
     /// Preview what would be deleted without actually deleting.
     #[arg(long, alias = "dry-run")]
     pub preview: bool,
@@ -1647,6 +2237,159 @@ pub struct CleanupArgs {
     pub verbose: bool,
 }
 
+/// Arguments for the dedupe command.
+#[derive(Debug, Parser)]
+pub struct DedupeArgs {
+    /// Filter by project path (substring match).
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Include subagent sessions.
+    #[arg(long)]
+    pub subagents: bool,
+
+    /// Report session pairs sharing at least this fraction of the smaller
+    /// session's messages (0-1).
+    #[arg(long, default_value_t = 0.5, value_name = "RATIO")]
+    pub min_overlap: f64,
+
+    /// Remove byte-identical copies and copies contained in another file of
+    /// the same session, and drop repeated lines within a file.
+    #[arg(long)]
+    pub remove: bool,
+
+    /// Move removed session files into this directory instead of deleting them.
+    #[arg(long, value_name = "DIR", requires = "remove")]
+    pub archive: Option<PathBuf>,
+
+    /// Skip confirmation prompt.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+/// Arguments for the scrub command.
+#[derive(Debug, Parser)]
+pub struct ScrubArgs {
+    /// Session ID (supports short prefixes).
+    pub session: String,
+
+    /// What to redact: security (keys, passwords, credentials) or all
+    /// (also emails, IP addresses, phone numbers).
+    #[arg(long, value_name = "LEVEL", default_value = "security")]
+    pub redact: RedactionLevel,
+
+    /// Keep a timestamped copy of each original file next to it.
+    #[arg(long)]
+    pub backup: bool,
+
+    /// Also scrub the session's subagent transcripts.
+    #[arg(long)]
+    pub subagents: bool,
+
+    /// Report what would be redacted without rewriting.
+    #[arg(long, alias = "dry-run")]
+    pub preview: bool,
+
+    /// Rewrite even if the session looks active.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Skip confirmation prompt.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+/// Arguments for the sync command.
+#[derive(Debug, Parser)]
+pub struct SyncArgs {
+    /// Backup directory. Created on first sync; holds a `snatch-sync.json`
+    /// manifest alongside the copied files.
+    #[arg(long, value_name = "DIR")]
+    pub dest: PathBuf,
+
+    /// Re-hash every backed-up file against the manifest instead of syncing.
+    /// Catches corruption, not deliberate tampering.
+    #[arg(long, conflicts_with = "restore")]
+    pub verify: bool,
+
+    /// Copy backed-up files back into the Claude directory instead of syncing.
+    /// Missing files are restored; local files that differ are reported.
+    #[arg(long)]
+    pub restore: bool,
+
+    /// With --restore, replace local files that differ from the backup.
+    #[arg(long, requires = "restore")]
+    pub overwrite: bool,
+
+    /// Show what would be copied without writing anything.
+    #[arg(long, alias = "dry-run", conflicts_with = "verify")]
+    pub preview: bool,
+
+    /// Skip the --restore confirmation prompt.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+/// Arguments for the merge command.
+#[derive(Debug, Parser)]
+pub struct MergeArgs {
+    /// First session ID (supports short prefixes); the merged file keeps its ID.
+    pub first: String,
+
+    /// Second session ID (supports short prefixes).
+    pub second: String,
+
+    /// Output JSONL file.
+    #[arg(short = 'O', long = "out", value_name = "FILE")]
+    pub output: PathBuf,
+
+    /// Replace the output file if it exists.
+    #[arg(long)]
+    pub overwrite: bool,
+}
+
+/// Arguments for the split command.
+#[derive(Debug, Parser)]
+pub struct SplitArgs {
+    /// Session ID to split (supports short prefixes).
+    pub session: String,
+
+    /// First message of the second part: a message UUID (or unique prefix)
+    /// or an RFC 3339 timestamp, which selects the first message at or after it.
+    #[arg(long, value_name = "UUID|TIMESTAMP")]
+    pub at: String,
+
+    /// Directory to write both parts to.
+    #[arg(short = 'O', long, default_value = ".")]
+    pub output_dir: PathBuf,
+
+    /// Replace output files if they exist.
+    #[arg(long)]
+    pub overwrite: bool,
+}
+
+/// Arguments for the annotate command.
+#[derive(Debug, Parser)]
+pub struct AnnotateArgs {
+    /// Session ID (supports short prefixes like "780893e4").
+    pub session: String,
+
+    /// Note text.
+    pub text: String,
+
+    /// Attach the note to the message with this UUID instead of the session.
+    #[arg(long, alias = "message", value_name = "UUID")]
+    pub uuid: Option<String>,
+
+    /// Optional label/category for the note (e.g., "todo", "bug", "idea").
+    #[arg(short = 'l', long)]
+    pub label: Option<String>,
+
+    /// Restrict session resolution to one or more session-log providers.
+    #[arg(long = "provider", value_name = "PROVIDER")]
+    pub provider: Vec<String>,
+}
+
 /// Arguments for the tag command.
 #[derive(Debug, Parser)]
 pub struct TagArgs {
@@ -1690,6 +2433,7 @@ pub enum TagAction {
     },
 
     /// Remove a tag from a session (or multiple sessions with filters).
+    #[command(alias = "rm")]
     Remove {
         /// Tag to remove.
         tag: String,
@@ -1729,20 +2473,30 @@ pub enum TagAction {
         session: Option<String>,
     },
 
-    /// Bookmark a session for quick access.
+    /// Bookmark a session (or one of its messages) for quick access.
     Bookmark {
         /// Session ID (supports short prefixes like "780893e4").
         session: String,
+        /// Bookmark the message with this UUID instead of the session.
+        #[arg(long, value_name = "UUID")]
+        message: Option<String>,
     },
 
-    /// Remove bookmark from a session.
+    /// Remove bookmark from a session (or one of its messages).
     Unbookmark {
         /// Session ID (supports short prefixes like "780893e4").
         session: String,
+        /// Remove the bookmark from the message with this UUID.
+        #[arg(long, value_name = "UUID")]
+        message: Option<String>,
     },
 
     /// List all bookmarked sessions.
-    Bookmarks,
+    Bookmarks {
+        /// List bookmarked messages instead of sessions.
+        #[arg(long)]
+        messages: bool,
+    },
 
     /// Show sessions with a specific tag.
     Find {
@@ -1775,6 +2529,9 @@ pub enum TagAction {
         /// Optional label/category for the note (e.g., "todo", "bug", "idea").
         #[arg(short = 'l', long)]
         label: Option<String>,
+        /// Attach the note to the message with this UUID instead of the session.
+        #[arg(long, value_name = "UUID")]
+        message: Option<String>,
     },
 
     /// List notes for a session.
@@ -1852,6 +2609,14 @@ fn parse_outcome(s: &str) -> std::result::Result<crate::tags::SessionOutcome, St
     s.parse()
 }
 
+fn parse_json_filter(s: &str) -> std::result::Result<JsonFilter, String> {
+    s.parse()
+}
+
+fn parse_encrypt_target(s: &str) -> std::result::Result<EncryptTarget, String> {
+    s.parse()
+}
+
 /// Arguments for the code command.
 #[derive(Debug, Parser)]
 pub struct CodeArgs {
@@ -2133,6 +2898,54 @@ pub enum StandupFormat {
     Json,
 }
 
+/// Arguments for the report command.
+#[derive(Debug, Parser)]
+pub struct ReportArgs {
+    /// Report on the last day.
+    #[arg(long, conflicts_with_all = ["weekly", "monthly", "period"])]
+    pub daily: bool,
+
+    /// Report on the last 7 days (the default).
+    #[arg(long, conflicts_with_all = ["monthly", "period"])]
+    pub weekly: bool,
+
+    /// Report on the last 30 days.
+    #[arg(long, conflicts_with = "period")]
+    pub monthly: bool,
+
+    /// Custom period (e.g., "48h", "14d", "2w", or "all").
+    #[arg(long)]
+    pub period: Option<String>,
+
+    /// Filter by project path (substring match).
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Rows to show in each ranked section.
+    #[arg(short = 'n', long, default_value = "10")]
+    pub top: usize,
+
+    /// Report format: markdown, or plain text for mail and cron pipelines.
+    /// The global `-o json` emits the report data as JSON instead.
+    #[arg(long, short = 'f', value_enum, default_value = "markdown")]
+    pub format: ReportFormat,
+
+    /// Output file path (stdout if not specified).
+    #[arg(short = 'O', long = "out")]
+    pub output_file: Option<PathBuf>,
+}
+
+/// Output formats for periodic reports.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum ReportFormat {
+    /// Markdown with tables.
+    #[default]
+    Markdown,
+    /// Plain text without color or markup.
+    #[value(alias = "plain")]
+    Text,
+}
+
 /// Arguments for the lessons command.
 #[derive(Debug, Parser)]
 pub struct LessonsArgs {
@@ -2394,6 +3207,11 @@ pub struct DoctorArgs {
     /// (repeatable; "all" = every installed provider).
     #[arg(long = "provider", value_name = "PROVIDER")]
     pub provider: Vec<String>,
+
+    /// Apply safe repairs: restore owner access to unreadable session files
+    /// and remove an abandoned search-index rebuild lock.
+    #[arg(long)]
+    pub fix: bool,
 }
 
 /// Arguments for the health command.
@@ -2424,6 +3242,26 @@ pub struct HealthArgs {
     pub provider: Vec<String>,
 }
 
+/// Arguments for the blame command.
+#[derive(Debug, Parser)]
+pub struct BlameArgs {
+    /// File to blame (relative paths resolve against the current directory).
+    pub path: PathBuf,
+
+    /// Attribute lines of the file as it is now ("START,END" or one line,
+    /// 1-based).
+    #[arg(short = 'L', long, value_name = "START,END")]
+    pub lines: Option<String>,
+
+    /// Only scan sessions whose project path contains this text.
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Maximum changes to list.
+    #[arg(short = 'l', long, default_value = "20")]
+    pub limit: usize,
+}
+
 /// Arguments for the file-evolution command.
 #[derive(Debug, Parser)]
 pub struct FileEvolutionArgs {
@@ -2492,6 +3330,34 @@ pub struct PrioritiesArgs {
     pub provider: Vec<String>,
 }
 
+/// Arguments for the show command.
+#[derive(Debug, Parser)]
+pub struct ShowArgs {
+    /// Message UUID (full or a unique prefix).
+    pub uuid: String,
+
+    /// Ancestors and levels of replies to include around the message.
+    #[arg(short = 'C', long, default_value = "3")]
+    pub context: usize,
+
+    /// Export format for the excerpt (default markdown, or pretty JSON with
+    /// `-o json`).
+    #[arg(short = 'f', long)]
+    pub format: Option<ExportFormatArg>,
+
+    /// Output file path (stdout if not specified).
+    #[arg(short = 'O', long = "out")]
+    pub output_file: Option<PathBuf>,
+
+    /// Only look in this session (ID or prefix).
+    #[arg(short = 's', long)]
+    pub session: Option<String>,
+
+    /// Only look in projects whose path contains this text.
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+}
+
 /// Arguments for the context command.
 #[derive(Debug, Parser)]
 pub struct ContextArgs {
@@ -2520,8 +3386,9 @@ pub struct ContextArgs {
 /// Arguments for the timeline command.
 #[derive(Debug, Parser)]
 pub struct TimelineArgs {
-    /// Session ID (full UUID or short prefix).
-    pub session_id: String,
+    /// Session ID (full UUID or short prefix). Omit for a chronological
+    /// activity log across all sessions.
+    pub session_id: Option<String>,
 
     /// Route through a session-log provider (repeatable; "all" = every
     /// installed provider). Provider-qualified ids ("codex:...") route
@@ -2529,7 +3396,7 @@ pub struct TimelineArgs {
     #[arg(long = "provider", value_name = "PROVIDER")]
     pub provider: Vec<String>,
 
-    /// Maximum timeline entries.
+    /// Maximum timeline entries (for the activity log, the latest events).
     #[arg(short = 'l', long, default_value = "50")]
     pub limit: usize,
 
@@ -2537,6 +3404,23 @@ pub struct TimelineArgs {
     /// resume chain (chain-aware by default).
     #[arg(long)]
     pub no_chain: bool,
+
+    /// Start of the cross-session activity log (YYYY-MM-DD or relative like
+    /// "1week"). Defaults to one week ago.
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// End of the cross-session activity log (YYYY-MM-DD or relative).
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Restrict the activity log to projects whose path contains this text.
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Leave git commits out of the activity log.
+    #[arg(long)]
+    pub no_git: bool,
 }
 
 /// Detail level for messages output.
@@ -2711,6 +3595,26 @@ pub struct PickArgs {
     pub provider: Vec<String>,
 }
 
+/// Arguments for the shell command.
+#[derive(Debug, Parser)]
+pub struct ShellArgs {
+    /// Filter the catalog by project path (substring match).
+    #[arg(short = 'p', long)]
+    pub project: Option<String>,
+
+    /// Only sessions active since this date (YYYY-MM-DD or relative like "1week", "3days").
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Only sessions active until this date.
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Include subagent sessions.
+    #[arg(long)]
+    pub subagents: bool,
+}
+
 /// Actions available after picking a session.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
 pub enum PickAction {
@@ -2723,6 +3627,9 @@ pub enum PickAction {
     Stats,
     /// Print session file path.
     Open,
+    /// Resume the session in Claude Code (`claude --resume`) from its
+    /// project directory.
+    Resume,
 }
 
 /// Arguments for the quickstart command.
@@ -2917,13 +3824,79 @@ fn init_thread_pool(threads: Option<usize>) {
 
 /// Run the CLI application.
 pub fn run() -> Result<()> {
-    let cli = Cli::parse();
+    run_with(&Cli::parse())
+}
+
+/// Whether the raw arguments, or failing them the environment, ask for JSON
+/// output. Lets the binary report arguments that do not parse in the JSON
+/// error format, without a [`Cli`] to ask.
+pub fn json_output_requested(args: impl IntoIterator<Item = std::ffi::OsString>) -> bool {
+    let args: Vec<String> = args
+        .into_iter()
+        .skip(1)
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .take_while(|arg| arg != "--")
+        .collect();
+    let mut output = None;
+    for (i, arg) in args.iter().enumerate() {
+        match arg.as_str() {
+            "--json" => return true,
+            "-o" | "--output" => output = args.get(i + 1).map(String::as_str),
+            _ => {
+                if let Some(value) = arg
+                    .strip_prefix("--output=")
+                    .or_else(|| arg.strip_prefix("-o="))
+                    .or_else(|| arg.strip_prefix("-o"))
+                {
+                    output = Some(value);
+                }
+            }
+        }
+    }
+    match output {
+        Some(output) => output == "json",
+        None => {
+            std::env::var("SNATCH_OUTPUT").is_ok_and(|v| v == "json")
+                || std::env::var("SNATCH_JSON").is_ok_and(|v| {
+                    !matches!(
+                        v.to_ascii_lowercase().as_str(),
+                        "" | "0" | "n" | "no" | "f" | "false" | "off"
+                    )
+                })
+        }
+    }
+}
+
+/// A command-line parse error as [`SnatchError::InvalidArgument`], naming
+/// the offending argument when clap knows it.
+#[must_use]
+pub fn argument_error(error: &clap::Error) -> SnatchError {
+    use clap::error::ContextKind;
 
+    let name = error
+        .get(ContextKind::InvalidArg)
+        .or_else(|| error.get(ContextKind::InvalidSubcommand))
+        .map_or_else(|| "arguments".to_string(), ToString::to_string);
+    let rendered = error.render().to_string();
+    let reason = rendered
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("error: ")
+        .to_string();
+    SnatchError::InvalidArgument { name, reason }
+}
+
+/// Run the CLI application with already-parsed arguments.
+///
+/// The binary parses arguments itself so it still knows the output format
+/// when reporting an error.
+pub fn run_with(cli: &Cli) -> Result<()> {
     // Initialize thread pool first (before any parallel operations)
     init_thread_pool(cli.threads);
 
     // Initialize logging
-    init_logging(&cli);
+    init_logging(cli);
 
     // Initialize the cache from configuration
     let config = match &cli.config {
@@ -2941,20 +3914,29 @@ pub fn run() -> Result<()> {
 
     match &cli.command {
         // No command provided - show the quick summary
-        None => commands::summary::run_quick_summary(&cli),
-        Some(Commands::List(args)) => commands::list::run(&cli, args),
-        Some(Commands::Export(args)) => commands::export::run(&cli, args),
-        Some(Commands::Grab(args)) => commands::grab::run(&cli, args),
-        Some(Commands::Search(args)) => commands::search::run(&cli, args),
-        Some(Commands::Stats(args)) => commands::stats::run(&cli, args),
-        Some(Commands::Info(args)) => commands::info::run(&cli, args),
-        Some(Commands::Validate(args)) => commands::validate::run(&cli, args),
-        Some(Commands::Watch(args)) => commands::watch::run(&cli, args),
-        Some(Commands::Diff(args)) => commands::diff::run(&cli, args),
-        Some(Commands::Config(args)) => commands::config::run(&cli, args),
-        Some(Commands::Extract(args)) => commands::extract::run(&cli, args),
-        Some(Commands::Cache(args)) => commands::cache::run(&cli, args),
-        Some(Commands::Index(args)) => commands::index::run(&cli, args),
+        None => commands::summary::run_quick_summary(cli),
+        Some(Commands::List(args)) => commands::list::run(cli, args),
+        Some(Commands::Export(args)) => commands::export::run(cli, args),
+        Some(Commands::Batch(args)) => commands::batch::run(cli, args),
+        Some(Commands::Decrypt(args)) => commands::decrypt::run(cli, args),
+        Some(Commands::Import(args)) => commands::import::run(cli, args),
+        Some(Commands::Grab(args)) => commands::grab::run(cli, args),
+        Some(Commands::Search(args)) => commands::search::run(cli, args),
+        Some(Commands::Stats(args)) => commands::stats::run(cli, args),
+        Some(Commands::Info(args)) => commands::info::run(cli, args),
+        Some(Commands::Validate(args)) => commands::validate::run(cli, args),
+        Some(Commands::Verify(args)) => commands::verify::run(cli, args),
+        Some(Commands::Watch(args)) => commands::watch::run(cli, args),
+        Some(Commands::Top(args)) => commands::top::run(cli, args),
+        Some(Commands::Serve(args)) => commands::serve::run(cli, args),
+        Some(Commands::Diff(args)) => commands::diff::run(cli, args),
+        Some(Commands::Config(args)) => commands::config::run(cli, args),
+        Some(Commands::Extract(args)) => commands::extract::run(cli, args),
+        Some(Commands::Cache(args)) => commands::cache::run(cli, args),
+        Some(Commands::Bench(args)) => commands::bench::run(cli, args),
+        Some(Commands::Index(args)) => commands::index::run(cli, args),
+        Some(Commands::Merge(args)) => commands::merge::run(cli, args),
+        Some(Commands::Split(args)) => commands::split::run(cli, args),
         Some(Commands::Completions(args)) => {
             generate_completions(args.shell);
             Ok(())
@@ -2973,35 +3955,43 @@ pub fn run() -> Result<()> {
                 prefix: args.prefix.clone(),
                 limit: Some(args.limit),
             };
-            commands::completions::run(&cli, &internal_args)
+            commands::completions::run(cli, &internal_args)
         }
-        Some(Commands::Cleanup(args)) => commands::cleanup::run(&cli, args),
-        Some(Commands::Tag(args)) => commands::tag::run(&cli, args),
-        Some(Commands::Code(args)) => commands::code::run(&cli, args),
-        Some(Commands::Recover(args)) => commands::recover::run(&cli, args),
-        Some(Commands::Prompts(args)) => commands::prompts::run(&cli, args),
-        Some(Commands::Standup(args)) => commands::standup::run(&cli, args),
-        Some(Commands::Lessons(args)) => commands::lessons::run(&cli, args),
-        Some(Commands::Goals(args)) => commands::goals::run(&cli, args),
-        Some(Commands::Digest(args)) => commands::digest::run(&cli, args),
-        Some(Commands::Notes(args)) => commands::notes::run(&cli, args),
-        Some(Commands::Decisions(args)) => commands::decisions::run(&cli, args),
-        Some(Commands::Thread(args)) => commands::thread::run(&cli, args),
-        Some(Commands::Doctor(args)) => commands::doctor::run(&cli, args),
-        Some(Commands::Providers) => commands::providers::run(&cli),
-        Some(Commands::Health(args)) => commands::health::run(&cli, args),
-        Some(Commands::FileEvolution(args)) => commands::file_evolution::run(&cli, args),
-        Some(Commands::Priorities(args)) => commands::priorities::run(&cli, args),
-        Some(Commands::Context(args)) => commands::context::run(&cli, args),
-        Some(Commands::Timeline(args)) => commands::timeline::run(&cli, args),
-        Some(Commands::Messages(args)) => commands::messages::run(&cli, args),
-        Some(Commands::Chunks(args)) => commands::chunks::run(&cli, args),
-        Some(Commands::Pick(args)) => commands::pick::run(&cli, args),
-        Some(Commands::Chain(args)) => commands::chain::run(&cli, args),
-        Some(Commands::FileHistory(args)) => commands::file_history::run(&cli, args),
-        Some(Commands::Quickstart(args)) => commands::quickstart::run(&cli, args),
-        Some(Commands::Summary(args)) => commands::summary::run(&cli, args),
-        Some(Commands::Recent(args)) => commands::recent::run(&cli, args),
+        Some(Commands::Cleanup(args)) => commands::cleanup::run(cli, args),
+        Some(Commands::Dedupe(args)) => commands::dedupe::run(cli, args),
+        Some(Commands::Scrub(args)) => commands::scrub::run(cli, args),
+        Some(Commands::Sync(args)) => commands::sync::run(cli, args),
+        Some(Commands::Tag(args)) => commands::tag::run(cli, args),
+        Some(Commands::Annotate(args)) => commands::tag::annotate(cli, args),
+        Some(Commands::Code(args)) => commands::code::run(cli, args),
+        Some(Commands::Recover(args)) => commands::recover::run(cli, args),
+        Some(Commands::Prompts(args)) => commands::prompts::run(cli, args),
+        Some(Commands::Standup(args)) => commands::standup::run(cli, args),
+        Some(Commands::Report(args)) => commands::report::run(cli, args),
+        Some(Commands::Lessons(args)) => commands::lessons::run(cli, args),
+        Some(Commands::Goals(args)) => commands::goals::run(cli, args),
+        Some(Commands::Digest(args)) => commands::digest::run(cli, args),
+        Some(Commands::Notes(args)) => commands::notes::run(cli, args),
+        Some(Commands::Decisions(args)) => commands::decisions::run(cli, args),
+        Some(Commands::Thread(args)) => commands::thread::run(cli, args),
+        Some(Commands::Doctor(args)) => commands::doctor::run(cli, args),
+        Some(Commands::Providers) => commands::providers::run(cli),
+        Some(Commands::Health(args)) => commands::health::run(cli, args),
+        Some(Commands::FileEvolution(args)) => commands::file_evolution::run(cli, args),
+        Some(Commands::Priorities(args)) => commands::priorities::run(cli, args),
+        Some(Commands::Context(args)) => commands::context::run(cli, args),
+        Some(Commands::Show(args)) => commands::show::run(cli, args),
+        Some(Commands::Timeline(args)) => commands::timeline::run(cli, args),
+        Some(Commands::Messages(args)) => commands::messages::run(cli, args),
+        Some(Commands::Chunks(args)) => commands::chunks::run(cli, args),
+        Some(Commands::Shell(args)) => commands::shell::run(cli, args),
+        Some(Commands::Pick(args)) => commands::pick::run(cli, args),
+        Some(Commands::Chain(args)) => commands::chain::run(cli, args),
+        Some(Commands::FileHistory(args)) => commands::file_history::run(cli, args),
+        Some(Commands::Blame(args)) => commands::blame::run(cli, args),
+        Some(Commands::Quickstart(args)) => commands::quickstart::run(cli, args),
+        Some(Commands::Summary(args)) => commands::summary::run(cli, args),
+        Some(Commands::Recent(args)) => commands::recent::run(cli, args),
         #[cfg(feature = "mcp")]
         Some(Commands::ServeMcp(_)) => {
             // Run the MCP server
@@ -3010,7 +4000,7 @@ pub fn run() -> Result<()> {
                 source: None,
             })?;
             rt.block_on(crate::mcp_server::run_server_with_index(
-                cli.claude_dir.clone(),
+                commands::local_claude_dir(cli)?,
                 cli.max_file_size,
                 config.index.directory.clone(),
             ))
@@ -3040,6 +4030,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_argument_errors_as_invalid_argument() {
+        let args = |line: &str| line.split(' ').map(std::ffi::OsString::from);
+        assert!(json_output_requested(args("snatch -o json list --bogus")));
+        assert!(json_output_requested(args("snatch list --output=json")));
+        assert!(json_output_requested(args("snatch -ojson list")));
+        assert!(json_output_requested(args("snatch --json list")));
+        assert!(!json_output_requested(args("snatch -o json -o text list")));
+        assert!(!json_output_requested(args(
+            "snatch -o text list -- --json"
+        )));
+
+        let error = Cli::try_parse_from(["snatch", "-o", "json", "list", "--bogus"]).unwrap_err();
+        let error = argument_error(&error);
+        assert_eq!(error.code(), crate::error::ErrorCode::InvalidArgument);
+        assert!(error.to_string().contains("--bogus"), "{error}");
+    }
+
     #[test]
     fn test_log_format_variants() {
         assert_eq!(LogFormat::default(), LogFormat::Text);
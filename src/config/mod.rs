@@ -6,6 +6,7 @@
 //! - Theme settings
 //! - Cache configuration
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
@@ -28,6 +29,18 @@ pub struct Config {
     /// Budget settings for cost alerts.
     #[serde(default)]
     pub budget: BudgetConfig,
+    /// Retention rules applied by `snatch cleanup` (alias `prune`).
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    /// Where to look for Claude directories.
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+    /// Named searches run with `snatch search --saved <NAME>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub searches: BTreeMap<String, SavedSearch>,
+    /// Named export flag bundles used with `snatch export --preset <NAME>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub presets: BTreeMap<String, ExportPreset>,
 }
 
 /// Project-specific configuration filename.
@@ -105,6 +118,18 @@ impl Config {
         if other.index.directory.is_some() {
             self.index.directory = other.index.directory.clone();
         }
+        if other.index.embedding_command.is_some() {
+            self.index.embedding_command = other.index.embedding_command.clone();
+        }
+        if !other.index.sources.is_empty() {
+            self.index.sources = other.index.sources.clone();
+        }
+        if other.index.tokenizer != IndexTokenizer::default() {
+            self.index.tokenizer = other.index.tokenizer;
+        }
+        if other.index.boosts != FieldBoosts::default() {
+            self.index.boosts = other.index.boosts;
+        }
 
         // Merge budget config
         if other.budget.daily_limit.is_some() {
@@ -120,6 +145,26 @@ impl Config {
             self.budget.warning_threshold = other.budget.warning_threshold;
         }
         self.budget.show_in_stats = other.budget.show_in_stats;
+
+        // Merge retention config
+        if other.retention.older_than.is_some() {
+            self.retention.older_than = other.retention.older_than.clone();
+        }
+        self.retention.keep_tagged |= other.retention.keep_tagged;
+        if other.retention.archive_dir.is_some() {
+            self.retention.archive_dir = other.retention.archive_dir.clone();
+        }
+
+        // Merge discovery config
+        self.discovery.wsl |= other.discovery.wsl;
+
+        // Project searches add to the global ones, replacing same-named ones
+        for (name, search) in &other.searches {
+            self.searches.insert(name.clone(), search.clone());
+        }
+        for (name, preset) in &other.presets {
+            self.presets.insert(name.clone(), preset.clone());
+        }
     }
 
     /// Save configuration to the default location.
@@ -196,6 +241,168 @@ pub struct IndexConfig {
     /// Custom index directory.
     #[serde(default)]
     pub directory: Option<PathBuf>,
+    /// Command producing embeddings for `search --semantic` (program then
+    /// arguments). Omitted uses the built-in hashing embedder.
+    #[serde(default)]
+    pub embedding_command: Option<Vec<String>>,
+    /// Additional Claude directories searched alongside the local one, such
+    /// as another machine's `~/.claude` synced to this one.
+    #[serde(default)]
+    pub sources: Vec<ClaudeSource>,
+    /// Tokenizer for the searchable text fields, applied when the index is
+    /// created.
+    #[serde(default)]
+    pub tokenizer: IndexTokenizer,
+    /// Score multipliers for matches in each searchable text field.
+    #[serde(default)]
+    pub boosts: FieldBoosts,
+}
+
+/// Score multipliers applied to search-index matches by field.
+///
+/// The defaults rank matches in visible message text above matches buried
+/// in thinking.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldBoosts {
+    /// Message text.
+    pub content: f32,
+    /// Thinking blocks.
+    pub thinking: f32,
+    /// Tool call inputs.
+    pub tool_input: f32,
+    /// Tool results.
+    pub tool_output: f32,
+}
+
+impl Default for FieldBoosts {
+    fn default() -> Self {
+        Self {
+            content: 2.0,
+            thinking: 0.5,
+            tool_input: 1.0,
+            tool_output: 1.0,
+        }
+    }
+}
+
+/// How the search index splits text into words.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexTokenizer {
+    /// Split on whitespace and punctuation only.
+    Simple,
+    /// Also split identifiers at `snake_case` and `camelCase` boundaries.
+    #[default]
+    Code,
+    /// Like `code`, indexing CJK text as overlapping character bigrams.
+    Cjk,
+}
+
+/// A named Claude directory included in the search index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaudeSource {
+    /// Name stored in each indexed document's `source` field.
+    pub name: String,
+    /// Path to the Claude directory.
+    pub path: PathBuf,
+}
+
+/// A named search stored in the config.
+///
+/// Unset fields leave the corresponding `search` flag at its default, and
+/// flags given on the command line take precedence.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    /// Search pattern, as accepted by `snatch search`.
+    pub query: String,
+    /// Message type filter.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub message_type: Option<String>,
+    /// Project filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Model filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Tool name filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+    /// Case-insensitive matching.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignore_case: bool,
+    /// Include thinking blocks.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub thinking: bool,
+    /// Include tool inputs and results.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tools: bool,
+}
+
+/// A named bundle of export flags, stored as `[presets.<NAME>]`.
+///
+/// Values use the spelling of the matching `export` flag (`format = "html"`,
+/// `redact = "all"`, `only = ["user", "assistant"]`). Unset fields leave the
+/// flag alone, and flags given on the command line take precedence. The
+/// `thinking`, `tool_use`, `tool_results`, `images`, `timestamps`, and
+/// `usage` switches can only turn content off, like their `--no-*` flags.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportPreset {
+    /// Export format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Redaction level (`security` or `all`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redact: Option<String>,
+    /// Content types to keep (`--only`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub only: Vec<String>,
+    /// Export template name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Include thinking blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<bool>,
+    /// Include tool calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_use: Option<bool>,
+    /// Include tool results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_results: Option<bool>,
+    /// Include images.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub images: Option<bool>,
+    /// Include timestamps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<bool>,
+    /// Include token usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<bool>,
+    /// Export only the main thread.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub main_thread: bool,
+    /// Include subagent sessions.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub subagents: bool,
+    /// Include system messages.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub system: bool,
+    /// Include session metadata.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub metadata: bool,
+    /// Warn about PII left after redaction.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub warn_pii: bool,
+    /// Add a table of contents.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub toc: bool,
+    /// Dark HTML theme.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dark: bool,
+    /// Pretty-print JSON.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pretty: bool,
 }
 
 /// Budget configuration for cost alerts.
@@ -232,6 +439,33 @@ impl Default for BudgetConfig {
     }
 }
 
+/// Retention rules for `snatch cleanup`.
+///
+/// Each rule is a default for the matching command-line flag; flags given on
+/// the command line take precedence.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Remove sessions older than this (`YYYY-MM-DD` or relative, like `90d`).
+    #[serde(default)]
+    pub older_than: Option<String>,
+    /// Never remove sessions with tags, a name, notes, or bookmarks.
+    #[serde(default)]
+    pub keep_tagged: bool,
+    /// Move removed sessions here instead of deleting them.
+    #[serde(default)]
+    pub archive_dir: Option<PathBuf>,
+}
+
+/// Discovery of Claude directories.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscoveryConfig {
+    /// Merge in the sessions across a WSL boundary: those of the WSL
+    /// distributions on Windows, the Windows profile's inside WSL. Only
+    /// applies when the Claude directory is discovered, not given.
+    #[serde(default)]
+    pub wsl: bool,
+}
+
 impl BudgetConfig {
     /// Check if any budget limits are configured.
     pub fn has_limits(&self) -> bool {
@@ -424,6 +658,18 @@ pub fn default_cache_dir() -> Result<PathBuf> {
     Ok(cache_dir.join("claude-snatch"))
 }
 
+/// Cache directory for a cache configuration.
+///
+/// `SNATCH_CACHE_DIR` overrides the configured `cache.directory`, which
+/// overrides the OS cache directory.
+pub fn resolve_cache_dir(cache: &CacheConfig) -> Result<PathBuf> {
+    match (std::env::var_os("SNATCH_CACHE_DIR"), &cache.directory) {
+        (Some(dir), _) => Ok(PathBuf::from(dir)),
+        (None, Some(dir)) => Ok(dir.clone()),
+        (None, None) => default_cache_dir(),
+    }
+}
+
 /// Get the default templates directory.
 pub fn default_templates_dir() -> Result<PathBuf> {
     let config_dir = dirs::config_dir().ok_or_else(|| SnatchError::Unsupported {
@@ -703,6 +949,132 @@ truncate_at = 1234
         assert_eq!(parsed.display.truncate_at, 1234);
     }
 
+    #[test]
+    fn test_index_boosts_default_per_field() {
+        let toml = r#"
+[index.boosts]
+thinking = 0.25
+"#;
+        let parsed: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            parsed.index.boosts,
+            FieldBoosts {
+                thinking: 0.25,
+                ..FieldBoosts::default()
+            }
+        );
+
+        let mut config = Config::default();
+        config.merge_from(&parsed);
+        assert!((config.index.boosts.thinking - 0.25).abs() < f32::EPSILON);
+        assert!((config.index.boosts.content - 2.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_index_sources_parse() {
+        let toml = r#"
+[index]
+
+[[index.sources]]
+name = "laptop"
+path = "/sync/laptop/.claude"
+"#;
+        let parsed: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            parsed.index.sources,
+            [ClaudeSource {
+                name: "laptop".to_string(),
+                path: PathBuf::from("/sync/laptop/.claude"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_saved_searches_parse_and_merge() {
+        let toml = r#"
+[searches.bugs]
+query = "panic|unwrap"
+type = "assistant"
+ignore_case = true
+"#;
+        let parsed: Config = toml::from_str(toml).unwrap();
+        let bugs = &parsed.searches["bugs"];
+        assert_eq!(bugs.query, "panic|unwrap");
+        assert_eq!(bugs.message_type.as_deref(), Some("assistant"));
+        assert!(bugs.ignore_case && !bugs.thinking);
+
+        let mut base = Config::default();
+        base.searches.insert(
+            "bugs".to_string(),
+            SavedSearch {
+                query: "old".to_string(),
+                ..SavedSearch::default()
+            },
+        );
+        base.merge_from(&parsed);
+        assert_eq!(base.searches["bugs"], *bugs);
+
+        let round_trip: Config = toml::from_str(&toml::to_string_pretty(&base).unwrap()).unwrap();
+        assert_eq!(round_trip.searches, base.searches);
+    }
+
+    #[test]
+    fn test_export_presets_parse_and_merge() {
+        let toml = r#"
+[presets.share]
+format = "html"
+redact = "all"
+thinking = false
+only = ["user", "assistant"]
+dark = true
+"#;
+        let parsed: Config = toml::from_str(toml).unwrap();
+        let share = &parsed.presets["share"];
+        assert_eq!(share.format.as_deref(), Some("html"));
+        assert_eq!(share.thinking, Some(false));
+        assert_eq!(share.only, ["user", "assistant"]);
+        assert!(share.dark && !share.toc);
+
+        let mut base = Config::default();
+        base.presets
+            .insert("share".to_string(), ExportPreset::default());
+        base.merge_from(&parsed);
+        assert_eq!(base.presets["share"], *share);
+
+        let round_trip: Config = toml::from_str(&toml::to_string_pretty(&base).unwrap()).unwrap();
+        assert_eq!(round_trip.presets, base.presets);
+        assert!(toml::from_str::<Config>("[presets.x]\nminify = true\n").is_err());
+    }
+
+    #[test]
+    fn test_retention_parse_and_merge() {
+        let toml = r#"
+[retention]
+older_than = "90d"
+keep_tagged = true
+"#;
+        let parsed: Config = toml::from_str(toml).unwrap();
+        assert_eq!(parsed.retention.older_than.as_deref(), Some("90d"));
+        assert!(parsed.retention.keep_tagged);
+        assert!(parsed.retention.archive_dir.is_none());
+
+        let mut base = Config::default();
+        base.retention.archive_dir = Some(PathBuf::from("/archive"));
+        base.merge_from(&parsed);
+        assert_eq!(base.retention.older_than.as_deref(), Some("90d"));
+        assert!(base.retention.keep_tagged);
+        assert_eq!(base.retention.archive_dir, Some(PathBuf::from("/archive")));
+    }
+
+    #[test]
+    fn test_discovery_wsl_is_opt_in() {
+        assert!(!Config::default().discovery.wsl);
+        let parsed: Config = toml::from_str("[discovery]\nwsl = true\n").unwrap();
+        let mut base = Config::default();
+        base.merge_from(&parsed);
+        assert!(base.discovery.wsl);
+    }
+
     #[test]
     fn test_config_merge() {
         let mut base = Config::default();
@@ -14,7 +14,7 @@
 //! file and comparing the internal `sessionId` to the filename UUID.
 
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::path::Path;
 
 use chrono::{DateTime, Utc};
@@ -57,8 +57,7 @@ pub(crate) fn extract_session_link(
     path: &Path,
     max_lines: usize,
 ) -> Option<(String, Option<String>, Option<DateTime<Utc>>)> {
-    let file = std::fs::File::open(path).ok()?;
-    let reader = BufReader::new(file);
+    let reader = crate::parser::open_session(path).ok()?;
 
     for line in reader.lines().take(max_lines) {
         let line = line.ok()?;
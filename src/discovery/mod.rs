@@ -4,6 +4,8 @@
 //! projects, and sessions. It handles:
 //! - Auto-discovery of ~/.claude directory
 //! - Platform-specific path handling (Linux, macOS, Windows, WSL)
+//! - Merging in the sessions of the other side of a WSL boundary
+//! - Claude directories on other machines, mirrored over SSH
 //! - Project path encoding/decoding
 //! - Session enumeration and metadata extraction
 //!
@@ -42,6 +44,7 @@ pub mod chain;
 mod hierarchy;
 mod paths;
 mod project;
+mod remote;
 mod session;
 mod session_index;
 pub mod streaming;
@@ -50,6 +53,7 @@ pub use chain::*;
 pub use hierarchy::*;
 pub use paths::*;
 pub use project::*;
+pub use remote::{MirrorStats, RemoteClaudeDir, SSH_SCHEME};
 pub use session::*;
 pub use session_index::*;
 pub use streaming::{detect_session_state, SessionState};
@@ -57,7 +61,7 @@ pub use streaming::{detect_session_state, SessionState};
 use std::path::{Path, PathBuf};
 
 use crate::error::{Result, SnatchError};
-use crate::{FILE_HISTORY_DIR_NAME, PROJECTS_DIR_NAME};
+use crate::{FILE_HISTORY_DIR_NAME, IMPORTED_DIR_NAME, PROJECTS_DIR_NAME};
 
 /// Claude Code data directory manager.
 #[derive(Debug, Clone)]
@@ -68,6 +72,10 @@ pub struct ClaudeDirectory {
     projects_dir: PathBuf,
     /// File history subdirectory.
     file_history_dir: PathBuf,
+    /// Imported conversations subdirectory.
+    imported_dir: PathBuf,
+    /// Other Claude directories whose projects are merged in.
+    peers: Vec<PathBuf>,
 }
 
 impl ClaudeDirectory {
@@ -83,11 +91,14 @@ impl ClaudeDirectory {
 
         let projects_dir = root.join(PROJECTS_DIR_NAME);
         let file_history_dir = root.join(FILE_HISTORY_DIR_NAME);
+        let imported_dir = root.join(IMPORTED_DIR_NAME);
 
         Ok(Self {
             root,
             projects_dir,
             file_history_dir,
+            imported_dir,
+            peers: Vec::new(),
         })
     }
 
@@ -97,6 +108,36 @@ impl ClaudeDirectory {
         Self::from_path(path)
     }
 
+    /// Merge in the projects of other Claude directories, e.g. those found
+    /// by [`wsl_peer_directories`] for users working in both Windows and
+    /// WSL. Peer sessions are listed like this directory's own, so commands
+    /// that rewrite or remove sessions act on them too. File history,
+    /// settings and imports stay this directory's own.
+    #[must_use]
+    pub fn with_peers(mut self, peers: impl IntoIterator<Item = PathBuf>) -> Self {
+        let own = self.root.canonicalize().ok();
+        for peer in peers {
+            let is_own = own.is_some() && peer.canonicalize().ok() == own;
+            if !is_own && !self.peers.contains(&peer) {
+                self.peers.push(peer);
+            }
+        }
+        self
+    }
+
+    /// The Claude directories whose projects are merged in.
+    #[must_use]
+    pub fn peers(&self) -> &[PathBuf] {
+        &self.peers
+    }
+
+    /// The projects directories of this directory and its peers.
+    fn all_projects_dirs(&self) -> Vec<PathBuf> {
+        std::iter::once(self.projects_dir.clone())
+            .chain(self.peers.iter().map(|peer| peer.join(PROJECTS_DIR_NAME)))
+            .collect()
+    }
+
     /// Get the root path.
     #[must_use]
     pub fn root(&self) -> &Path {
@@ -115,6 +156,12 @@ impl ClaudeDirectory {
         &self.file_history_dir
     }
 
+    /// Get the directory holding imported conversations.
+    #[must_use]
+    pub fn imported_dir(&self) -> &Path {
+        &self.imported_dir
+    }
+
     /// Check if the projects directory exists.
     #[must_use]
     pub fn has_projects(&self) -> bool {
@@ -127,30 +174,32 @@ impl ClaudeDirectory {
         self.file_history_dir.exists()
     }
 
-    /// List all projects.
+    /// List all projects, including those holding imported conversations
+    /// and those of the peers.
     pub fn projects(&self) -> Result<Vec<Project>> {
-        if !self.projects_dir.exists() {
-            return Ok(Vec::new());
-        }
-
         let mut projects = Vec::new();
 
-        for entry in std::fs::read_dir(&self.projects_dir).map_err(|e| {
-            SnatchError::io(
-                format!(
-                    "Failed to read projects directory: {}",
-                    self.projects_dir.display()
-                ),
-                e,
-            )
-        })? {
-            let entry = entry.map_err(|e| SnatchError::io("Failed to read directory entry", e))?;
-
-            let path = entry.path();
-            if path.is_dir() {
-                match Project::from_path(&path) {
-                    Ok(project) => projects.push(project),
-                    Err(_) => continue, // Skip invalid project directories
+        let mut dirs = self.all_projects_dirs();
+        dirs.insert(1, self.imported_dir.clone());
+        for dir in &dirs {
+            if !dir.exists() {
+                continue;
+            }
+            for entry in std::fs::read_dir(dir).map_err(|e| {
+                SnatchError::io(
+                    format!("Failed to read projects directory: {}", dir.display()),
+                    e,
+                )
+            })? {
+                let entry =
+                    entry.map_err(|e| SnatchError::io("Failed to read directory entry", e))?;
+
+                let path = entry.path();
+                if path.is_dir() {
+                    match Project::from_path(&path) {
+                        Ok(project) => projects.push(project),
+                        Err(_) => continue, // Skip invalid project directories
+                    }
                 }
             }
         }
@@ -171,23 +220,22 @@ impl ClaudeDirectory {
             claude_encode_project_path(decoded_path),
             encode_project_path(decoded_path),
         ] {
-            let project_dir = self.projects_dir.join(&encoded);
-            if project_dir.exists() {
-                return Ok(Some(Project::from_path(&project_dir)?));
+            if let Some(project) = self.find_project_by_encoded(&encoded)? {
+                return Ok(Some(project));
             }
         }
         Ok(None)
     }
 
-    /// Find a project by encoded directory name.
+    /// Find a project by encoded directory name, here or in a peer.
     pub fn find_project_by_encoded(&self, encoded_name: &str) -> Result<Option<Project>> {
-        let project_dir = self.projects_dir.join(encoded_name);
-
-        if project_dir.exists() {
-            Ok(Some(Project::from_path(&project_dir)?))
-        } else {
-            Ok(None)
+        for dir in self.all_projects_dirs() {
+            let project_dir = dir.join(encoded_name);
+            if project_dir.exists() {
+                return Ok(Some(Project::from_path(&project_dir)?));
+            }
         }
+        Ok(None)
     }
 
     /// Get all sessions across all projects.
@@ -434,6 +482,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_peers_merge_projects() {
+        let (_tmp, dir) = claude_dir_with_project("-home-user-linux-app");
+        let peer = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(
+            peer.path()
+                .join(PROJECTS_DIR_NAME)
+                .join("C--Users-user-windows-app"),
+        )
+        .unwrap();
+        let own_root = dir.root().to_path_buf();
+        let dir = dir.with_peers([
+            peer.path().to_path_buf(),
+            own_root,
+            peer.path().to_path_buf(),
+        ]);
+
+        assert_eq!(dir.peers(), [peer.path().to_path_buf()]);
+        assert_eq!(dir.projects().unwrap().len(), 2);
+        assert!(dir
+            .find_project(r"C:\Users\user\windows-app")
+            .unwrap()
+            .is_some());
+        assert!(dir
+            .find_project_by_encoded("-home-user-linux-app")
+            .unwrap()
+            .is_some());
+    }
+
     #[test]
     fn test_format_size() {
         assert_eq!(format_size(500), "500 B");
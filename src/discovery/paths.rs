@@ -8,7 +8,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::error::{Result, SnatchError};
-use crate::CLAUDE_DIR_NAME;
+use crate::{CLAUDE_DIR_NAME, PROJECTS_DIR_NAME};
 
 /// Discover the Claude Code data directory.
 ///
@@ -103,6 +103,82 @@ pub fn is_wsl() -> bool {
     false
 }
 
+/// Claude directories on the other side of a WSL boundary.
+///
+/// On Windows this probes the home directories of the current user's WSL
+/// distributions (`\\wsl$\<distro>\home\<user>\.claude`); inside WSL
+/// it probes the current Windows user's profile
+/// (`/mnt/c/Users/<user>/.claude`), never other users'. Only directories
+/// holding a projects directory count. Elsewhere the list is empty.
+#[must_use]
+pub fn wsl_peer_directories() -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        // Both shares list the same running distributions; the newer
+        // `wsl.localhost` name is missing on older Windows builds.
+        for share in [r"\\wsl.localhost", r"\\wsl$"] {
+            let Ok(distros) = std::fs::read_dir(share) else {
+                continue;
+            };
+            let dirs: Vec<PathBuf> = distros
+                .filter_map(std::result::Result::ok)
+                .flat_map(|distro| claude_dirs_under(&distro.path().join("home")))
+                .collect();
+            if !dirs.is_empty() {
+                return dirs;
+            }
+        }
+    } else if is_wsl() {
+        return windows_profile()
+            .map(|profile| profile.join(CLAUDE_DIR_NAME))
+            .filter(|dir| dir.join(PROJECTS_DIR_NAME).is_dir())
+            .into_iter()
+            .collect();
+    }
+    Vec::new()
+}
+
+/// The current Windows user's profile, as a WSL path: `USERPROFILE` when
+/// `WSLENV` shares it (translated with `/p` or not), otherwise as reported
+/// by `cmd.exe`.
+fn windows_profile() -> Option<PathBuf> {
+    let profile = match std::env::var("USERPROFILE") {
+        Ok(profile) => profile,
+        Err(_) => {
+            let output = std::process::Command::new("cmd.exe")
+                .args(["/C", "echo", "%USERPROFILE%"])
+                // A Windows working directory avoids cmd's UNC path warning.
+                .current_dir("/mnt/c")
+                .stderr(std::process::Stdio::null())
+                .output()
+                .ok()?;
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+    };
+    if profile.starts_with('/') {
+        return Some(PathBuf::from(profile));
+    }
+    // Unexpanded or relative: cmd.exe could not tell.
+    if profile.len() < 3 || profile.as_bytes()[1] != b':' {
+        return None;
+    }
+    Some(PathBuf::from(windows_to_wsl_path(&profile)))
+}
+
+/// The `.claude` directories, with a projects directory, of the home
+/// directories under `homes`.
+fn claude_dirs_under(homes: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(homes) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path().join(CLAUDE_DIR_NAME))
+        .filter(|dir| dir.join(PROJECTS_DIR_NAME).is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
 /// Get the current platform identifier.
 #[must_use]
 pub fn platform_id() -> &'static str {
@@ -705,9 +781,10 @@ pub fn is_valid_project_path(decoded: &str) -> bool {
 ///
 /// Session files are named `<uuid>.jsonl` or `agent-<variant>-<hash>.jsonl`.
 /// Variants include `agent-<hash>` and `agent-acompact-<hash>` (compacted subagents).
+/// Archived sessions may add `.gz` or `.zst` after `.jsonl`.
 #[must_use]
 pub fn parse_session_filename(filename: &str) -> Option<SessionFileInfo> {
-    let name = filename.strip_suffix(".jsonl")?;
+    let name = crate::parser::strip_session_suffix(filename)?;
 
     if let Some(rest) = name.strip_prefix("agent-") {
         // Accept any agent- prefix: "agent-<hash>", "agent-acompact-<hash>", etc.
@@ -770,12 +847,10 @@ pub fn normalize_path(path: &Path) -> PathBuf {
 /// Check if a path appears to be a valid session file.
 #[must_use]
 pub fn is_session_file(path: &Path) -> bool {
-    path.extension().map(|ext| ext == "jsonl").unwrap_or(false)
-        && path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .map(|n| parse_session_filename(n).is_some())
-            .unwrap_or(false)
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| parse_session_filename(n).is_some())
+        .unwrap_or(false)
 }
 
 /// Convert a Windows path to WSL path format.
@@ -1065,8 +1140,16 @@ mod tests {
         assert!(info.is_subagent);
         assert_eq!(info.agent_hash, Some("3d4e81".to_string()));
 
+        // Archived (compressed)
+        let info =
+            parse_session_filename("40afc8a7-3fcb-4d29-b1ee-100b81b8c6c0.jsonl.zst").unwrap();
+        assert_eq!(info.session_id, "40afc8a7-3fcb-4d29-b1ee-100b81b8c6c0");
+        let info = parse_session_filename("agent-3e533ee.jsonl.gz").unwrap();
+        assert_eq!(info.session_id, "agent-3e533ee");
+
         // Invalid
         assert!(parse_session_filename("not-a-session.txt").is_none());
+        assert!(parse_session_filename("agent-3e533ee.gz").is_none());
         assert!(parse_session_filename("readme.md").is_none());
     }
 
@@ -0,0 +1,584 @@
+//! Claude directories on other machines, reached over SSH.
+//!
+//! `--claude-dir ssh://[user@]host[:port]/path` names a Claude directory on
+//! another machine. A leading `~` in the path is left for the remote shell,
+//! so `ssh://devbox/~/.claude` is the remote user's default directory; a
+//! location without a path means the same.
+//!
+//! [`RemoteClaudeDir::mirror`] lists the session files under the remote
+//! `projects/` directory with the system `ssh` client and copies them into a
+//! local mirror under the cache directory, which then serves as the Claude
+//! directory. Session logs are append-only, so a file that grew only has its
+//! new bytes fetched, once the remote confirms with a `cksum` of the local
+//! length that the mirrored bytes are still its prefix; any other change
+//! fetches the file again, and files gone from the remote are removed from
+//! the mirror. Only files the mirror itself fetched, as recorded in its
+//! manifest, are ever removed; anything else placed under the mirror stays.
+//! Names containing line breaks cannot be framed and are skipped. One `ssh` run lists the files and a second streams every
+//! changed one. Modification times are carried over so recency sorting
+//! matches the remote. Commands that rewrite session files change the
+//! mirror, never the remote.
+//!
+//! Authentication is whatever `ssh` is configured with: keys, the agent,
+//! and host aliases from `~/.ssh/config` all apply.
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+use tracing::debug;
+
+use crate::error::{Result, SnatchError};
+use crate::util::atomic_write;
+use crate::PROJECTS_DIR_NAME;
+
+/// URL scheme of remote Claude directories.
+pub const SSH_SCHEME: &str = "ssh://";
+
+/// Directory of mirrors within the cache directory.
+const MIRROR_DIR: &str = "remote";
+
+/// File within a mirror listing the session files it fetched, one relative
+/// path per line.
+const MANIFEST_FILE: &str = ".snatch-mirror";
+
+/// Exit status of the listing script when the directory does not exist.
+const MISSING_DIR_STATUS: i32 = 3;
+
+/// A Claude directory on another machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteClaudeDir {
+    /// `host` or `user@host`, as given to `ssh`.
+    destination: String,
+    port: Option<u16>,
+    /// Remote path, possibly starting with `~`.
+    path: String,
+}
+
+/// What [`RemoteClaudeDir::mirror`] did.
+#[derive(Debug, Clone, Default)]
+pub struct MirrorStats {
+    /// Local mirror, usable as a Claude directory.
+    pub root: PathBuf,
+    /// Session files on the remote.
+    pub files: usize,
+    /// Files fetched in full or in part.
+    pub fetched: usize,
+    /// Bytes transferred.
+    pub bytes: u64,
+    /// Mirrored files removed because the remote no longer has them.
+    pub removed: usize,
+}
+
+/// Names of session files: plain, gzip or zstd JSONL. Scrub's
+/// `.jsonl.<stamp>.bak` backups are left out.
+const SESSION_PATTERNS: [&str; 3] = ["*.jsonl", "*.jsonl.gz", "*.jsonl.zst"];
+
+/// One remote session file.
+struct RemoteFile {
+    /// Path relative to the remote Claude directory.
+    relative: PathBuf,
+    size: u64,
+    /// Modification time, in seconds since the Unix epoch.
+    mtime: u64,
+}
+
+/// A remote file to copy into the mirror.
+struct Transfer<'a> {
+    file: &'a RemoteFile,
+    local: PathBuf,
+    /// Bytes already mirrored; the remote sends the rest.
+    from: u64,
+    /// POSIX `cksum` of the mirrored bytes, checked against the remote.
+    prefix: u32,
+}
+
+impl RemoteClaudeDir {
+    /// Parse an `ssh://` location; anything else yields `None`.
+    #[must_use]
+    pub fn parse(location: &str) -> Option<Self> {
+        let rest = location.strip_prefix(SSH_SCHEME)?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, ""),
+        };
+        let (destination, port) = match authority.rsplit_once(':') {
+            Some((destination, port)) => (destination, Some(port.parse().ok()?)),
+            None => (authority, None),
+        };
+        if destination.is_empty() {
+            return None;
+        }
+        let path = path.trim_end_matches('/');
+        let path = match path.strip_prefix('/') {
+            None => "~/.claude",
+            Some(home) if home == "~" || home.starts_with("~/") => home,
+            Some(_) => path,
+        };
+        Some(Self {
+            destination: destination.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    /// Parse a `--claude-dir` value, if it names a remote directory.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.to_str().and_then(Self::parse)
+    }
+
+    /// The `ssh` destination (`host` or `user@host`).
+    #[must_use]
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+
+    /// The remote path.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Where this directory is mirrored within `cache_dir`.
+    #[must_use]
+    pub fn mirror_dir(&self, cache_dir: &Path) -> PathBuf {
+        let host = match self.port {
+            Some(port) => format!("{}_{port}", self.destination),
+            None => self.destination.clone(),
+        };
+        cache_dir
+            .join(MIRROR_DIR)
+            .join(sanitize(&host))
+            .join(sanitize(&self.path))
+    }
+
+    /// Bring the mirror in `cache_dir` up to date with the remote.
+    pub fn mirror(&self, cache_dir: &Path) -> Result<MirrorStats> {
+        let root = self.mirror_dir(cache_dir);
+        let projects = root.join(PROJECTS_DIR_NAME);
+        fs::create_dir_all(&projects)
+            .map_err(|e| SnatchError::io(format!("Failed to create {}", projects.display()), e))?;
+
+        let remote = self.list()?;
+        let mut stats = MirrorStats {
+            root: root.clone(),
+            files: remote.len(),
+            ..MirrorStats::default()
+        };
+        let mut transfers = Vec::new();
+        for file in &remote {
+            let local = root.join(&file.relative);
+            let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(file.mtime);
+            let (from, prefix) = match fs::metadata(&local).ok() {
+                Some(m) if m.len() == file.size && m.modified().ok() == Some(mtime) => continue,
+                // Appended since the last mirror: fetch the new bytes only.
+                Some(m) if m.len() < file.size => (m.len(), cksum(&local)?),
+                _ => (0, 0),
+            };
+            transfers.push(Transfer {
+                file,
+                local,
+                from,
+                prefix,
+            });
+        }
+        stats.fetched = transfers.len();
+        // Record the new files before fetching them, so an interrupted run
+        // still knows they belong to the mirror.
+        let previous = read_manifest(&root);
+        let current: HashSet<PathBuf> = remote.iter().map(|f| f.relative.clone()).collect();
+        write_manifest(&root, previous.union(&current))?;
+        stats.bytes = self.transfer(&transfers)?;
+
+        for relative in previous.difference(&current) {
+            if fs::remove_file(root.join(relative)).is_ok() {
+                stats.removed += 1;
+            }
+        }
+        write_manifest(&root, current.iter())?;
+        debug!(
+            remote = %self.destination,
+            files = stats.files,
+            fetched = stats.fetched,
+            bytes = stats.bytes,
+            removed = stats.removed,
+            "Mirrored remote Claude directory"
+        );
+        Ok(stats)
+    }
+
+    /// List the session files under the remote `projects/` directory.
+    fn list(&self) -> Result<Vec<RemoteFile>> {
+        // `stat` differs between GNU and BSD; try both. Names with line
+        // breaks would split the listing, so `find` leaves them out.
+        let script = format!(
+            "cd {} 2>/dev/null || exit {MISSING_DIR_STATUS}; \
+             [ -d {PROJECTS_DIR_NAME} ] || exit 0; \
+             find {PROJECTS_DIR_NAME} -type f \\( {} \\) ! -name '*\n*' ! -name '*\r*' | while IFS= read -r f; do \
+             s=$(wc -c < \"$f\" | tr -d ' '); \
+             m=$(stat -c %Y \"$f\" 2>/dev/null || stat -f %m \"$f\"); \
+             printf '%s\\t%s\\t%s\\n' \"$s\" \"$m\" \"$f\"; done",
+            shell_path(&self.path),
+            SESSION_PATTERNS
+                .iter()
+                .map(|pattern| format!("-name '{pattern}'"))
+                .collect::<Vec<_>>()
+                .join(" -o ")
+        );
+        let output = self.run(&script)?;
+        if output.status.code() == Some(MISSING_DIR_STATUS) {
+            return Err(SnatchError::ClaudeDirectoryNotFound {
+                expected_path: PathBuf::from(format!("{}:{}", self.destination, self.path)),
+            });
+        }
+        let stdout = self.check(output)?;
+        Ok(String::from_utf8_lossy(&stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let size = fields.next()?.parse().ok()?;
+                let mtime = fields.next()?.parse().ok()?;
+                let relative = PathBuf::from(fields.next()?);
+                // Never let a listed name escape the mirror.
+                is_mirrorable(&relative).then_some(RemoteFile {
+                    relative,
+                    size,
+                    mtime,
+                })
+            })
+            .collect())
+    }
+
+    /// Copy the given files into the mirror over one `ssh` run, returning
+    /// the bytes transferred.
+    ///
+    /// Each request line names a file, the bytes already mirrored, how many
+    /// more to send and the `cksum` of the mirrored ones. The remote answers
+    /// each with a `<from> <length>` line and that many bytes, starting over
+    /// from 0 with the whole file when the prefix no longer matches.
+    fn transfer(&self, transfers: &[Transfer<'_>]) -> Result<u64> {
+        if transfers.is_empty() {
+            return Ok(0);
+        }
+        let script = format!(
+            "cd {} || exit 1; t=$(mktemp) || exit 1; trap 'rm -f \"$t\"' EXIT; \
+             while IFS=' ' read -r from want sum f; do \
+             if [ \"$from\" -gt 0 ] && \
+             [ \"$(head -c \"$from\" \"$f\" | cksum | cut -d ' ' -f 1)\" != \"$sum\" ]; then \
+             from=0; cat \"$f\" > \"$t\"; \
+             else tail -c +$((from + 1)) \"$f\" | head -c \"$want\" > \"$t\"; fi; \
+             printf '%s %s\\n' \"$from\" \"$(wc -c < \"$t\" | tr -d ' ')\"; cat \"$t\"; done",
+            shell_path(&self.path)
+        );
+        if let Some(t) = transfers.iter().find(|t| !is_mirrorable(&t.file.relative)) {
+            return Err(SnatchError::InvalidArgument {
+                name: "remote file".to_string(),
+                reason: format!("cannot transfer {:?}", t.file.relative),
+            });
+        }
+        let requests: String = transfers
+            .iter()
+            .map(|t| {
+                format!(
+                    "{} {} {} {}\n",
+                    t.from,
+                    t.file.size - t.from,
+                    t.prefix,
+                    t.file.relative.to_string_lossy()
+                )
+            })
+            .collect();
+
+        let mut child = self
+            .command(&script)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| self.spawn_error(e))?;
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        // Write from another thread so a long request list cannot block on
+        // a full stdout pipe.
+        let writer = std::thread::spawn(move || stdin.write_all(requests.as_bytes()));
+        let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let received = receive(&mut stdout, transfers);
+        drop(stdout);
+        let _ = writer.join();
+        let output = child
+            .wait_with_output()
+            .map_err(|e| SnatchError::io(format!("Failed to run ssh {}", self.destination), e))?;
+        self.check(output)?;
+        received
+    }
+
+    /// Run a shell script on the remote.
+    fn run(&self, script: &str) -> Result<std::process::Output> {
+        self.command(script)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| self.spawn_error(e))
+    }
+
+    /// An `ssh` command running `script` on the remote.
+    fn command(&self, script: &str) -> Command {
+        let mut cmd = Command::new("ssh");
+        if let Some(port) = self.port {
+            cmd.arg("-p").arg(port.to_string());
+        }
+        cmd.arg("--").arg(&self.destination).arg(script);
+        cmd
+    }
+
+    fn spawn_error(&self, e: io::Error) -> SnatchError {
+        let context = if e.kind() == io::ErrorKind::NotFound {
+            "ssh is not installed".to_string()
+        } else {
+            format!("Failed to run ssh {}", self.destination)
+        };
+        SnatchError::io(context, e)
+    }
+
+    /// The stdout of a successful run, or an error carrying its stderr.
+    fn check(&self, output: std::process::Output) -> Result<Vec<u8>> {
+        if output.status.success() {
+            return Ok(output.stdout);
+        }
+        Err(SnatchError::io(
+            format!("ssh {} failed ({})", self.destination, output.status),
+            io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        ))
+    }
+}
+
+/// Whether a remote path stays within the mirror and can be framed on one
+/// line of the transfer protocol and the manifest.
+fn is_mirrorable(relative: &Path) -> bool {
+    relative.starts_with(PROJECTS_DIR_NAME)
+        && relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        && relative
+            .to_str()
+            .is_some_and(|name| !name.contains(['\n', '\r']))
+}
+
+/// The files a mirror fetched on earlier runs.
+fn read_manifest(root: &Path) -> HashSet<PathBuf> {
+    fs::read_to_string(root.join(MANIFEST_FILE))
+        .unwrap_or_default()
+        .lines()
+        .map(PathBuf::from)
+        .filter(|relative| is_mirrorable(relative))
+        .collect()
+}
+
+/// Record the files a mirror holds.
+fn write_manifest<'a>(root: &Path, files: impl Iterator<Item = &'a PathBuf>) -> Result<()> {
+    let mut names: Vec<&str> = files
+        .filter(|f| is_mirrorable(f))
+        .filter_map(|f| f.to_str())
+        .collect();
+    names.sort_unstable();
+    let mut content = names.join("\n");
+    content.push('\n');
+    atomic_write(&root.join(MANIFEST_FILE), content.as_bytes())
+}
+
+/// Read the remote's answers to `transfers` into the mirror, returning the
+/// bytes received.
+fn receive(stdout: &mut impl BufRead, transfers: &[Transfer<'_>]) -> Result<u64> {
+    let mut bytes = 0;
+    for transfer in transfers {
+        let local = &transfer.local;
+        let mut header = String::new();
+        stdout
+            .read_line(&mut header)
+            .map_err(|e| SnatchError::io("Failed to read from ssh", e))?;
+        let (from, len) = header
+            .split_once(' ')
+            .and_then(|(from, len)| Some((from.parse::<u64>().ok()?, len.trim().parse().ok()?)))
+            .ok_or_else(|| {
+                SnatchError::io(
+                    "Failed to read from ssh",
+                    io::Error::new(io::ErrorKind::InvalidData, "truncated transfer"),
+                )
+            })?;
+        let mut content = vec![0; len];
+        stdout
+            .read_exact(&mut content)
+            .map_err(|e| SnatchError::io("Failed to read from ssh", e))?;
+        if from == 0 {
+            atomic_write(local, &content)?;
+        } else {
+            OpenOptions::new()
+                .append(true)
+                .open(local)
+                .and_then(|mut f| f.write_all(&content))
+                .map_err(|e| SnatchError::io(format!("Failed to write {}", local.display()), e))?;
+        }
+        let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(transfer.file.mtime);
+        set_modified(local, mtime)?;
+        bytes += len as u64;
+    }
+    Ok(bytes)
+}
+
+/// The POSIX `cksum` CRC of a file, as the remote's `cksum` prints it.
+fn cksum(path: &Path) -> Result<u32> {
+    fn step(table: &[u32; 256], crc: u32, byte: u8) -> u32 {
+        (crc << 8) ^ table[usize::from((crc >> 24) as u8 ^ byte)]
+    }
+
+    let mut table = [0u32; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut crc = (i as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x04c1_1db7
+            };
+        }
+        *entry = crc;
+    }
+
+    let context = || format!("Failed to read {}", path.display());
+    let mut file = File::open(path).map_err(|e| SnatchError::io(context(), e))?;
+    let mut buf = [0u8; 64 * 1024];
+    let mut crc = 0;
+    let mut len: u64 = 0;
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| SnatchError::io(context(), e))?;
+        if n == 0 {
+            break;
+        }
+        crc = buf[..n]
+            .iter()
+            .fold(crc, |crc, &byte| step(&table, crc, byte));
+        len += n as u64;
+    }
+    // The length follows the content, least significant byte first.
+    while len > 0 {
+        crc = step(&table, crc, len as u8);
+        len >>= 8;
+    }
+    Ok(!crc)
+}
+
+/// Quote a string for a POSIX shell.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Quote a remote path, leaving a leading `~` for the shell to expand.
+fn shell_path(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => format!("~/{}", shell_quote(rest)),
+        None if path == "~" => "~".to_string(),
+        None => shell_quote(path),
+    }
+}
+
+/// A single path component naming `s`.
+fn sanitize(s: &str) -> String {
+    let name: String = s
+        .trim_matches('/')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '@' | '~') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() || name.chars().all(|c| c == '.') {
+        "_".to_string()
+    } else {
+        name
+    }
+}
+
+fn set_modified(path: &Path, mtime: SystemTime) -> Result<()> {
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|f| f.set_modified(mtime))
+        .map_err(|e| SnatchError::io(format!("Failed to update {}", path.display()), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_locations() {
+        let remote = RemoteClaudeDir::parse("ssh://me@devbox:2222/~/.claude").unwrap();
+        assert_eq!(
+            (remote.destination(), remote.port, remote.path()),
+            ("me@devbox", Some(2222), "~/.claude")
+        );
+        for default in ["ssh://devbox", "ssh://devbox/"] {
+            assert_eq!(RemoteClaudeDir::parse(default).unwrap().path(), "~/.claude");
+        }
+        assert_eq!(
+            RemoteClaudeDir::parse("ssh://devbox/~").unwrap().path(),
+            "~"
+        );
+        let absolute = RemoteClaudeDir::parse("ssh://devbox/srv/claude/").unwrap();
+        assert_eq!(absolute.path(), "/srv/claude");
+        assert!(RemoteClaudeDir::parse("/home/me/.claude").is_none());
+        assert!(RemoteClaudeDir::parse("ssh:///tmp").is_none());
+        assert!(RemoteClaudeDir::parse("ssh://devbox:port/x").is_none());
+
+        assert_eq!(
+            remote.mirror_dir(Path::new("/cache")),
+            Path::new("/cache/remote/me@devbox_2222/~_.claude")
+        );
+        assert_eq!(shell_path("~/it's here"), r"~/'it'\''s here'");
+        assert_eq!(shell_path("/srv/claude"), "'/srv/claude'");
+    }
+
+    #[test]
+    fn test_cksum_matches_posix() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        // Values printed by `printf ... | cksum`.
+        for (content, expected) in [
+            ("", 4_294_967_295),
+            ("abc", 1_219_131_554),
+            ("123456789", 930_766_865),
+        ] {
+            fs::write(&path, content).unwrap();
+            assert_eq!(cksum(&path).unwrap(), expected, "{content:?}");
+        }
+    }
+
+    #[test]
+    fn test_manifest_tracks_only_mirrorable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_manifest(dir.path()).is_empty());
+
+        let kept = PathBuf::from("projects/-p/a.jsonl");
+        let files = [
+            kept.clone(),
+            PathBuf::from("projects/-p/b\nc.jsonl"),
+            PathBuf::from("projects/../escape.jsonl"),
+        ];
+        write_manifest(dir.path(), files.iter()).unwrap();
+        // A hand-edited manifest cannot point outside the projects directory.
+        let manifest = dir.path().join(MANIFEST_FILE);
+        let mut content = fs::read_to_string(&manifest).unwrap();
+        content.push_str("settings.json\n/etc/passwd\n");
+        fs::write(&manifest, content).unwrap();
+
+        assert_eq!(read_manifest(dir.path()), HashSet::from([kept]));
+        assert!(!is_mirrorable(Path::new("projects/-p/x\r.jsonl")));
+    }
+}
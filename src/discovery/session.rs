@@ -12,7 +12,7 @@ use tracing::{debug, instrument, trace};
 use crate::cache::global_cache;
 use crate::error::{Result, SnatchError};
 use crate::model::{LogEntry, SchemaVersion, SystemSubtype};
-use crate::parser::{JsonlParser, StreamingParser};
+use crate::parser::{JsonlParser, ParseFilter, StreamingParser};
 
 use super::paths::parse_session_filename;
 use super::streaming::{detect_session_state, SessionState};
@@ -222,7 +222,7 @@ impl Session {
         &self,
         max_file_size: Option<u64>,
     ) -> Result<(Vec<LogEntry>, usize)> {
-        let mut parser = JsonlParser::new().with_lenient(true);
+        let mut parser = JsonlParser::new().with_lenient(true).with_simd(true);
         if let Some(max_size) = max_file_size {
             parser = parser.with_max_file_size(max_size);
         }
@@ -232,6 +232,20 @@ impl Session {
         Ok((entries, unparsed))
     }
 
+    /// Parse only the entries matching `filter`, skipping other lines
+    /// before they are deserialized.
+    pub fn parse_filtered(
+        &self,
+        filter: ParseFilter,
+        max_file_size: Option<u64>,
+    ) -> Result<Vec<LogEntry>> {
+        let mut parser = JsonlParser::new().with_lenient(true).with_filter(filter);
+        if let Some(max_size) = max_file_size {
+            parser = parser.with_max_file_size(max_size);
+        }
+        parser.parse_file(&self.path)
+    }
+
     /// Parse all entries with caching support.
     ///
     /// Uses the global cache to avoid re-parsing unchanged files.
@@ -242,7 +256,7 @@ impl Session {
     }
 
     /// Create a streaming parser for this session.
-    pub fn stream(&self) -> Result<StreamingParser<std::io::BufReader<std::fs::File>>> {
+    pub fn stream(&self) -> Result<StreamingParser<crate::parser::SessionReader>> {
         super::streaming::open_stream(&self.path)
     }
 
@@ -422,6 +436,18 @@ impl Session {
         (count, size)
     }
 
+    /// Agent type of a subagent transcript, read from its sibling
+    /// `agent-*.meta.json` sidecar. `None` for main sessions or when the
+    /// sidecar is missing or lacks `agentType`.
+    #[must_use]
+    pub fn subagent_type(&self) -> Option<String> {
+        if !self.is_subagent {
+            return None;
+        }
+        let stem = self.path.file_stem()?.to_str()?;
+        read_subagent_meta(&self.path.with_file_name(format!("{stem}.meta.json"))).agent_type
+    }
+
     /// Get the subagents directory for this (main) session, if it exists.
     ///
     /// For a main session at `<project>/<uuid>.jsonl`, the subagents live under
@@ -537,8 +563,9 @@ struct SubagentMeta {
     tool_use_id: Option<String>,
 }
 
-/// Append a [`SubagentLink`] for each `agent-*.jsonl` transcript directly in `dir`,
-/// reading the sibling `agent-*.meta.json` sidecar from the same directory.
+/// Append a [`SubagentLink`] for each `agent-*.jsonl` transcript (plain or
+/// compressed) directly in `dir`, reading the sibling `agent-*.meta.json`
+/// sidecar from the same directory.
 fn collect_agent_links(dir: &Path, links: &mut Vec<SubagentLink>) {
     let Ok(entries) = std::fs::read_dir(dir) else {
         return;
@@ -546,10 +573,11 @@ fn collect_agent_links(dir: &Path, links: &mut Vec<SubagentLink>) {
     for entry in entries.flatten() {
         let path = entry.path();
         // Only the transcript files; skip the .meta.json sidecars themselves.
-        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
-            continue;
-        }
-        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        let Some(stem) = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .and_then(crate::parser::strip_session_suffix)
+        else {
             continue;
         };
         if !stem.starts_with("agent-") {
@@ -966,6 +994,27 @@ mod tests {
         assert_eq!(links[3].description, None);
     }
 
+    #[test]
+    fn test_subagent_type_reads_sibling_sidecar() {
+        let tmp = tempfile::tempdir().unwrap();
+        let subagents = tmp.path().join("parent-uuid").join("subagents");
+        std::fs::create_dir_all(&subagents).unwrap();
+        let typed = subagents.join("agent-aaa.jsonl");
+        std::fs::write(&typed, "{}\n").unwrap();
+        std::fs::write(
+            subagents.join("agent-aaa.meta.json"),
+            r#"{"agentType":"Explore"}"#,
+        )
+        .unwrap();
+        let untyped = subagents.join("agent-bbb.jsonl");
+        std::fs::write(&untyped, "{}\n").unwrap();
+
+        let session = Session::from_path(&typed, "/tmp/project").unwrap();
+        assert_eq!(session.subagent_type().as_deref(), Some("Explore"));
+        let session = Session::from_path(&untyped, "/tmp/project").unwrap();
+        assert_eq!(session.subagent_type(), None);
+    }
+
     #[test]
     fn test_subagent_links_empty_for_subagent_session() {
         let tmp = tempfile::tempdir().unwrap();
@@ -15,10 +15,7 @@ use crate::error::Result;
 /// Open a session file for streaming with automatic state detection.
 pub fn open_session_stream(
     path: impl AsRef<Path>,
-) -> Result<(
-    StreamingParser<std::io::BufReader<std::fs::File>>,
-    SessionState,
-)> {
+) -> Result<(StreamingParser<crate::parser::SessionReader>, SessionState)> {
     let path = path.as_ref();
     let state = detect_session_state(path)?;
     let parser = open_stream(path)?;
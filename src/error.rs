@@ -4,8 +4,10 @@
 //! Error types are designed to be informative, actionable, and suitable for both
 //! programmatic handling and user-facing display.
 
+use std::fmt;
 use std::path::PathBuf;
 
+use serde::Serialize;
 use thiserror::Error;
 
 /// Primary error type for claude-snatch operations.
@@ -372,6 +374,196 @@ impl SnatchError {
             _ => None,
         }
     }
+
+    /// Get the stable machine-readable code for this error.
+    #[must_use]
+    pub const fn code(&self) -> ErrorCode {
+        match self {
+            Self::ParseError { .. } => ErrorCode::ParseError,
+            Self::FileNotFound { .. } => ErrorCode::FileNotFound,
+            Self::DirectoryNotFound { .. } => ErrorCode::DirectoryNotFound,
+            Self::PermissionDenied { .. } => ErrorCode::PermissionDenied,
+            Self::SessionNotFound { .. } => ErrorCode::SessionNotFound,
+            Self::Provider(_) => ErrorCode::ProviderError,
+            Self::AmbiguousSessionPrefix { .. } => ErrorCode::AmbiguousSessionPrefix,
+            Self::ProjectNotFound { .. } => ErrorCode::ProjectNotFound,
+            Self::InvalidSessionFile { .. } => ErrorCode::InvalidSessionFile,
+            Self::SchemaVersionMismatch { .. } => ErrorCode::SchemaVersionMismatch,
+            Self::UnknownSchemaVersion { .. } => ErrorCode::UnknownSchemaVersion,
+            Self::ExportError { .. } => ErrorCode::ExportError,
+            Self::SearchError { .. } => ErrorCode::SearchError,
+            Self::ConfigError { .. } => ErrorCode::ConfigError,
+            Self::IoError { .. } => ErrorCode::IoError,
+            Self::SerializationError { .. } => ErrorCode::SerializationError,
+            Self::InvalidPathEncoding { .. } => ErrorCode::InvalidPathEncoding,
+            Self::Interrupted => ErrorCode::Interrupted,
+            Self::ClaudeDirectoryNotFound { .. } => ErrorCode::ClaudeDirectoryNotFound,
+            Self::CorruptedFile { .. } => ErrorCode::CorruptedFile,
+            Self::UnsupportedMessageType { .. } => ErrorCode::UnsupportedMessageType,
+            Self::InvalidUuid { .. } => ErrorCode::InvalidUuid,
+            Self::TreeReconstructionError { .. } => ErrorCode::TreeReconstructionError,
+            Self::AnalyticsError { .. } => ErrorCode::AnalyticsError,
+            Self::Timeout { .. } => ErrorCode::Timeout,
+            Self::DataIntegrityError { .. } => ErrorCode::DataIntegrityError,
+            Self::Unsupported { .. } => ErrorCode::Unsupported,
+            Self::InvalidArgument { .. } => ErrorCode::InvalidArgument,
+            Self::InvalidConfig { .. } => ErrorCode::InvalidConfig,
+            Self::IndexError(_) => ErrorCode::IndexError,
+            Self::ValidationError { .. } => ErrorCode::ValidationError,
+        }
+    }
+
+    /// Build the structured form of this error, printed instead of the
+    /// text message under `--output json`.
+    #[must_use]
+    pub fn report(&self) -> ErrorReport<'_> {
+        ErrorReport {
+            code: self.code(),
+            message: self.to_string(),
+            hint: self.hint(),
+            exit_code: self.exit_code(),
+        }
+    }
+}
+
+/// Stable machine-readable error codes.
+///
+/// Each [`SnatchError`] variant maps to exactly one code, serialized as
+/// [`ErrorCode::as_str`]. Codes are part of the JSON error output and are
+/// never renamed or reused; new variants get new codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// A JSONL line could not be parsed.
+    ParseError,
+    /// A file does not exist.
+    FileNotFound,
+    /// A directory does not exist.
+    DirectoryNotFound,
+    /// Access to a file or directory was denied.
+    PermissionDenied,
+    /// No session matches the given ID or prefix.
+    SessionNotFound,
+    /// A source provider failed.
+    ProviderError,
+    /// A session prefix matches more than one session.
+    AmbiguousSessionPrefix,
+    /// No project matches the given path.
+    ProjectNotFound,
+    /// A session file is not in the expected format.
+    InvalidSessionFile,
+    /// A session uses a schema version that is not supported.
+    SchemaVersionMismatch,
+    /// A session uses an unknown schema version.
+    UnknownSchemaVersion,
+    /// An export failed.
+    ExportError,
+    /// A search failed.
+    SearchError,
+    /// The configuration could not be loaded or saved.
+    ConfigError,
+    /// An I/O operation failed.
+    IoError,
+    /// JSON serialization or deserialization failed.
+    SerializationError,
+    /// A path is not valid UTF-8 or cannot be decoded.
+    InvalidPathEncoding,
+    /// The operation was interrupted.
+    Interrupted,
+    /// The Claude Code data directory was not found.
+    ClaudeDirectoryNotFound,
+    /// A file was only partially written.
+    CorruptedFile,
+    /// A message has an unsupported type.
+    UnsupportedMessageType,
+    /// A value is not a valid UUID.
+    InvalidUuid,
+    /// The conversation tree could not be rebuilt.
+    TreeReconstructionError,
+    /// An analytics calculation failed.
+    AnalyticsError,
+    /// An operation timed out.
+    Timeout,
+    /// Data failed an integrity check.
+    DataIntegrityError,
+    /// An operation or feature is not supported.
+    Unsupported,
+    /// A command-line argument is invalid.
+    InvalidArgument,
+    /// A configuration value is invalid.
+    InvalidConfig,
+    /// The search index failed.
+    IndexError,
+    /// A validation check failed.
+    ValidationError,
+}
+
+impl ErrorCode {
+    /// The code as it appears in JSON output.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ParseError => "parse_error",
+            Self::FileNotFound => "file_not_found",
+            Self::DirectoryNotFound => "directory_not_found",
+            Self::PermissionDenied => "permission_denied",
+            Self::SessionNotFound => "session_not_found",
+            Self::ProviderError => "provider_error",
+            Self::AmbiguousSessionPrefix => "ambiguous_session_prefix",
+            Self::ProjectNotFound => "project_not_found",
+            Self::InvalidSessionFile => "invalid_session_file",
+            Self::SchemaVersionMismatch => "schema_version_mismatch",
+            Self::UnknownSchemaVersion => "unknown_schema_version",
+            Self::ExportError => "export_error",
+            Self::SearchError => "search_error",
+            Self::ConfigError => "config_error",
+            Self::IoError => "io_error",
+            Self::SerializationError => "serialization_error",
+            Self::InvalidPathEncoding => "invalid_path_encoding",
+            Self::Interrupted => "interrupted",
+            Self::ClaudeDirectoryNotFound => "claude_directory_not_found",
+            Self::CorruptedFile => "corrupted_file",
+            Self::UnsupportedMessageType => "unsupported_message_type",
+            Self::InvalidUuid => "invalid_uuid",
+            Self::TreeReconstructionError => "tree_reconstruction_error",
+            Self::AnalyticsError => "analytics_error",
+            Self::Timeout => "timeout",
+            Self::DataIntegrityError => "data_integrity_error",
+            Self::Unsupported => "unsupported",
+            Self::InvalidArgument => "invalid_argument",
+            Self::InvalidConfig => "invalid_config",
+            Self::IndexError => "index_error",
+            Self::ValidationError => "validation_error",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Serialized through `as_str`, the one list of code names.
+impl Serialize for ErrorCode {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A structured error, as printed to stderr under `--output json`.
+#[derive(Debug, Serialize)]
+pub struct ErrorReport<'a> {
+    /// Stable error code.
+    pub code: ErrorCode,
+    /// Human-readable message, the same text as the plain output.
+    pub message: String,
+    /// Actionable hint, or `null` when there is none.
+    pub hint: Option<&'a str>,
+    /// The process exit code.
+    pub exit_code: i32,
 }
 
 /// Result type alias for claude-snatch operations.
@@ -459,4 +651,20 @@ mod tests {
         };
         assert!(!not_found.is_recoverable());
     }
+
+    #[test]
+    fn test_error_report_json() {
+        let err = SnatchError::SessionNotFound {
+            session_id: "abc".to_string(),
+        };
+        let json = serde_json::to_value(err.report()).unwrap();
+        assert_eq!(json["code"], "session_not_found");
+        assert_eq!(json["message"], "Session not found: abc");
+        assert_eq!(json["exit_code"], 3);
+        assert!(json["hint"].is_string());
+
+        let json = serde_json::to_value(SnatchError::Interrupted.report()).unwrap();
+        assert_eq!(json["code"], ErrorCode::Interrupted.as_str());
+        assert!(json["hint"].is_null());
+    }
 }
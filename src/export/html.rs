@@ -873,6 +873,40 @@ document.querySelectorAll('.tool-header, .thinking-header').forEach(header => {{
         Ok(())
     }
 
+    /// Write an entry of a type the model does not know, as its raw JSON.
+    fn write_unknown_entry<W: Write>(
+        &self,
+        writer: &mut W,
+        entry: &LogEntry,
+        raw: &serde_json::Value,
+        options: &ExportOptions,
+    ) -> Result<()> {
+        writeln!(writer, "<article class=\"message message-system\">")?;
+        writeln!(writer, "  <div class=\"message-header\">")?;
+        writeln!(
+            writer,
+            "    <span class=\"message-role\">{}</span>",
+            escape_html(entry.message_type())
+        )?;
+        if options.include_timestamps {
+            if let Some(timestamp) = entry.timestamp() {
+                writeln!(
+                    writer,
+                    "    <span class=\"message-timestamp\">{}</span>",
+                    format_timestamp(&timestamp)
+                )?;
+            }
+        }
+        writeln!(writer, "  </div>")?;
+        let json = serde_json::to_string_pretty(raw).unwrap_or_else(|_| raw.to_string());
+        writeln!(writer, "  <div class=\"message-content\">")?;
+        writeln!(writer, "    <pre><code>{}</code></pre>", escape_html(&json))?;
+        writeln!(writer, "  </div>")?;
+        writeln!(writer, "</article>")?;
+
+        Ok(())
+    }
+
     /// Write a thinking block.
     ///
     /// Recent Claude Code versions persist thinking blocks with empty text
@@ -1193,6 +1227,9 @@ impl Exporter for HtmlExporter {
                 LogEntry::Summary(summary) => {
                     self.write_summary_message(writer, summary, options)?;
                 }
+                LogEntry::Unknown(raw) if options.should_include_system() => {
+                    self.write_unknown_entry(writer, entry, raw, options)?;
+                }
                 _ => {}
             }
         }
@@ -1224,6 +1261,9 @@ impl Exporter for HtmlExporter {
                 LogEntry::System(system) if options.should_include_system() => {
                     self.write_system_message(writer, system, options)?;
                 }
+                LogEntry::Unknown(raw) if options.should_include_system() => {
+                    self.write_unknown_entry(writer, entry, raw, options)?;
+                }
                 _ => {}
             }
         }
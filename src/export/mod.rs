@@ -45,7 +45,7 @@ pub use json::*;
 pub use markdown::*;
 pub use schema::{
     entry_schema, entry_schema_string, export_schema, export_schema_string, validate_entries,
-    validate_export, SchemaValidator, ValidationResult,
+    validate_export, SchemaValidator, SchemaViolation, ValidationResult,
 };
 pub use sqlite::*;
 pub use text::*;
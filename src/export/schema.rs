@@ -1,10 +1,13 @@
 //! JSON Schema definitions for export formats.
 //!
 //! Provides JSON Schema v7 definitions for validating exported data,
-//! ensuring schema-compliant output for interoperability.
+//! ensuring schema-compliant output for interoperability. The entry schema
+//! also checks session files as they are parsed (see
+//! [`JsonlParser::with_schema_validation`](crate::parser::JsonlParser::with_schema_validation)).
 
 use jsonschema::Validator;
 use once_cell::sync::Lazy;
+use serde::Serialize;
 use serde_json::{json, Value};
 
 use crate::error::{Result, SnatchError};
@@ -111,7 +114,12 @@ static EXPORT_SCHEMA: Lazy<Value> = Lazy::new(|| {
                 "properties": {
                     "type": {
                         "type": "string",
-                        "enum": ["user", "assistant", "summary", "system"]
+                        "enum": [
+                            "user", "assistant", "summary", "system",
+                            "file-history-snapshot", "queue-operation", "turn_end",
+                            "progress", "attachment", "last-prompt", "mode",
+                            "permission-mode", "ai-title"
+                        ]
                     },
                     "uuid": { "type": "string" },
                     "parentUuid": { "type": ["string", "null"] },
@@ -273,6 +281,20 @@ impl ValidationResult {
     }
 }
 
+/// One field of a session entry that violates the entry schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaViolation {
+    /// Line of the entry in its file (1-based).
+    pub line: usize,
+    /// UUID of the entry, if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    /// JSON pointer to the offending field (empty for the entry itself).
+    pub path: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
 /// Schema validator for export data.
 #[derive(Debug, Clone, Default)]
 pub struct SchemaValidator {
@@ -316,6 +338,25 @@ impl SchemaValidator {
         self.validate_with_schema(&ENTRY_VALIDATOR, data, 1)
     }
 
+    /// Per-field violations of the log entry on `line`, at most
+    /// `max_errors` of them.
+    pub fn entry_violations(&self, line: usize, data: &Value) -> Vec<SchemaViolation> {
+        if ENTRY_VALIDATOR.is_valid(data) {
+            return Vec::new();
+        }
+        let uuid = data.get("uuid").and_then(Value::as_str);
+        ENTRY_VALIDATOR
+            .iter_errors(data)
+            .take(self.max_errors)
+            .map(|e| SchemaViolation {
+                line,
+                uuid: uuid.map(str::to_string),
+                path: e.instance_path().to_string(),
+                message: e.to_string(),
+            })
+            .collect()
+    }
+
     /// Validate multiple entries (JSONL).
     pub fn validate_entries(&self, entries: &[Value]) -> ValidationResult {
         let mut all_errors = Vec::new();
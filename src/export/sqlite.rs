@@ -576,6 +576,32 @@ impl SqliteExporter {
                 )
                 .map_err(|e| SnatchError::export(format!("Failed to insert summary: {}", e)))?;
             }
+            LogEntry::Unknown(raw) if options.should_include_system() => {
+                // Entry types the model does not know keep their raw JSON
+                // as content, under their own message type.
+                let uuid = entry.uuid();
+                let parent_uuid = entry.parent_uuid();
+                let timestamp = entry.timestamp().map(|ts| format_timestamp(&ts));
+                let content = raw.to_string();
+                let is_sidechain = entry.is_sidechain();
+
+                conn.execute(
+                    "INSERT INTO entries (session_fk, uuid, parent_uuid, message_type, timestamp, content, is_sidechain)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        session_fk,
+                        uuid,
+                        parent_uuid,
+                        entry.message_type(),
+                        timestamp,
+                        content,
+                        is_sidechain
+                    ],
+                )
+                .map_err(|e| {
+                    SnatchError::export(format!("Failed to insert {} entry: {}", entry.message_type(), e))
+                })?;
+            }
             _ => {
                 // Skip other entry types or filtered entries
             }
@@ -16,6 +16,8 @@ use super::provider::{
     project_parsed_session, IndexedSessionManifest, IndexedSkip, ProviderIndexBuildManifest,
     ProviderSearchIndex, PROVIDER_INDEX_SCHEMA_VERSION,
 };
+use super::LOCAL_SOURCE;
+use crate::config::ClaudeSource;
 use crate::error::{Result, SnatchError};
 use crate::provider::project::{history_units, SessionProjectContext};
 use crate::provider::registry::{ProviderRegistry, ProviderSelection};
@@ -31,6 +33,10 @@ pub struct ProviderIndexBuildOptions<'a> {
     pub generation: String,
     /// Timestamp recorded in build/session manifests.
     pub built_at: DateTime<Utc>,
+    /// Named Claude directories merged into the registry's Claude root.
+    /// Sessions read from one of them record its name as their source;
+    /// every other session records [`LOCAL_SOURCE`].
+    pub sources: &'a [ClaudeSource],
 }
 
 impl<'a> ProviderIndexBuildOptions<'a> {
@@ -42,8 +48,15 @@ impl<'a> ProviderIndexBuildOptions<'a> {
             project_filter,
             generation: uuid::Uuid::new_v4().to_string(),
             built_at: Utc::now(),
+            sources: &[],
         }
     }
+
+    /// Tag sessions read from `sources` with their names.
+    pub fn with_sources(mut self, sources: &'a [ClaudeSource]) -> Self {
+        self.sources = sources;
+        self
+    }
 }
 
 /// Machine-readable result of one committed provider-index generation.
@@ -265,12 +278,25 @@ fn selected_provider_ids(
     Ok(ids)
 }
 
+/// Name of the source whose directory holds the session's preferred
+/// artifact, or [`LOCAL_SOURCE`].
+fn session_source<'a>(descriptor: &SessionDescriptor, sources: &'a [ClaudeSource]) -> &'a str {
+    descriptor
+        .preferred_artifact()
+        .and_then(|artifact| {
+            let path = Path::new(&artifact.snapshot.id.locator);
+            sources.iter().find(|source| path.starts_with(&source.path))
+        })
+        .map_or(LOCAL_SOURCE, |source| source.name.as_str())
+}
+
 fn index_metadata_fingerprint(
     descriptor: &SessionDescriptor,
     context: &SessionProjectContext,
     logical_root: &LogicalSessionKey,
     project_key: &str,
     project_path: &str,
+    source: &str,
     spawned: bool,
 ) -> Result<String> {
     // The full canonical serialization is retained instead of a short hash:
@@ -282,6 +308,7 @@ fn index_metadata_fingerprint(
         logical_root.to_string(),
         project_key,
         project_path,
+        source,
         spawned,
         context.cwd.as_deref(),
         context.git_root.as_deref(),
@@ -418,12 +445,14 @@ pub fn update_provider_index(
             }
             let is_spawned = spawned.contains(key);
             let logical_root = roots.get(key).unwrap_or(key);
+            let source = session_source(&session.descriptor, options.sources);
             let metadata_fingerprint = index_metadata_fingerprint(
                 &session.descriptor,
                 &session.context,
                 logical_root,
                 &project_key,
                 &project_path,
+                source,
                 is_spawned,
             )?;
             let provider = match registry.get(&key.provider) {
@@ -482,6 +511,7 @@ pub fn update_provider_index(
                 logical_root,
                 &project_key,
                 &project_path,
+                source,
                 is_spawned,
                 revision_token,
                 metadata_fingerprint,
@@ -823,6 +853,7 @@ mod tests {
             project_filter: None,
             generation: generation.to_string(),
             built_at: "2026-07-22T00:00:00Z".parse().unwrap(),
+            sources: &[],
         }
     }
 
@@ -0,0 +1,497 @@
+//! Query language for the full-text indexes.
+//!
+//! [`SearchIndex::search`](super::SearchIndex::search) parses queries with
+//! this module instead of handing raw strings to tantivy, so mistakes are
+//! reported with a position and a hint rather than a parser internals dump.
+//! `snatch index search` runs the same syntax against the provider index
+//! through [`IndexedQueryExpr`](super::query::IndexedQueryExpr), where terms
+//! match literal text rather than words.
+//!
+//! # Syntax
+//!
+//! | Form | Meaning |
+//! |------|---------|
+//! | `falcon dive` | both words (adjacent terms are joined with `AND`) |
+//! | `falcon AND dive` | both words |
+//! | `falcon OR hawk` | either word |
+//! | `NOT falcon`, `-falcon` | documents without the word |
+//! | `"peregrine falcon"` | the words as an adjacent phrase |
+//! | `(falcon OR hawk) dive` | grouping |
+//! | `tool_name:Bash` | a value of one field |
+//! | `project:"/home/user/my app"` | a quoted field value |
+//! | `std::fs`, `https://example.com` | plain text: the prefix names no field |
+//!
+//! `NOT` binds tighter than `AND`, which binds tighter than `OR`. Operators
+//! are recognized only in upper case; `and` is an ordinary word. Unprefixed
+//! terms search `content`, `thinking`, and `tool_input`. Text fields are
+//! matched word by word, case-insensitively; `session_id`, `project`, `uuid`,
+//! `message_type`, `model`, `tool_name`, and `source` match their exact value.
+
+use std::fmt;
+
+use crate::error::{Result, SnatchError};
+
+use super::fields;
+
+/// A searchable index field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryField {
+    /// Session ID (exact).
+    SessionId,
+    /// Project path (exact).
+    Project,
+    /// Message UUID (exact).
+    Uuid,
+    /// Message type such as `user` or `assistant` (exact).
+    MessageType,
+    /// Assistant model (exact).
+    Model,
+    /// Message text.
+    Content,
+    /// Thinking text.
+    Thinking,
+    /// Tool name (exact).
+    ToolName,
+    /// Tool input JSON text.
+    ToolInput,
+    /// Tool result text. Searched only when named or opted into, since
+    /// command output is noisy.
+    ToolOutput,
+    /// Name of the Claude directory the session came from (exact).
+    Source,
+}
+
+impl QueryField {
+    /// Every field accepted as a `field:` prefix.
+    pub const ALL: &'static [Self] = &[
+        Self::SessionId,
+        Self::Project,
+        Self::Uuid,
+        Self::MessageType,
+        Self::Model,
+        Self::Content,
+        Self::Thinking,
+        Self::ToolName,
+        Self::ToolInput,
+        Self::ToolOutput,
+        Self::Source,
+    ];
+
+    /// Fields searched by a term without a prefix.
+    pub const DEFAULT: &'static [Self] = &[Self::Content, Self::Thinking, Self::ToolInput];
+
+    /// Schema field name, which is also the query prefix.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::SessionId => fields::SESSION_ID,
+            Self::Project => fields::PROJECT,
+            Self::Uuid => fields::UUID,
+            Self::MessageType => fields::MESSAGE_TYPE,
+            Self::Model => fields::MODEL,
+            Self::Content => fields::CONTENT,
+            Self::Thinking => fields::THINKING,
+            Self::ToolName => fields::TOOL_NAME,
+            Self::ToolInput => fields::TOOL_INPUT,
+            Self::ToolOutput => fields::TOOL_OUTPUT,
+            Self::Source => fields::SOURCE,
+        }
+    }
+
+    /// Look up a field by its prefix.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|field| field.name() == name)
+    }
+
+    /// Whether the field matches whole values rather than words.
+    #[must_use]
+    pub const fn is_exact(self) -> bool {
+        !matches!(
+            self,
+            Self::Content | Self::Thinking | Self::ToolInput | Self::ToolOutput
+        )
+    }
+}
+
+/// A parsed query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryExpr {
+    /// A word or quoted phrase, in one field or the default fields.
+    Match {
+        /// Field prefix, or `None` for the default fields.
+        field: Option<QueryField>,
+        /// Word, phrase, or exact value.
+        text: String,
+    },
+    /// Every operand matches.
+    And(Vec<QueryExpr>),
+    /// At least one operand matches.
+    Or(Vec<QueryExpr>),
+    /// The operand does not match.
+    Not(Box<QueryExpr>),
+}
+
+impl QueryExpr {
+    /// A match on one field.
+    pub fn field(field: QueryField, text: impl Into<String>) -> Self {
+        Self::Match {
+            field: Some(field),
+            text: text.into(),
+        }
+    }
+
+    /// Search unprefixed terms in `fields` instead of [`QueryField::DEFAULT`].
+    #[must_use]
+    pub fn with_default_fields(self, fields: &[QueryField]) -> Self {
+        match self {
+            Self::Match { field: None, text } => Self::Or(
+                fields
+                    .iter()
+                    .map(|field| Self::field(*field, text.clone()))
+                    .collect(),
+            ),
+            Self::Match { .. } => self,
+            Self::And(all) => Self::And(
+                all.into_iter()
+                    .map(|expr| expr.with_default_fields(fields))
+                    .collect(),
+            ),
+            Self::Or(any) => Self::Or(
+                any.into_iter()
+                    .map(|expr| expr.with_default_fields(fields))
+                    .collect(),
+            ),
+            Self::Not(inner) => Self::Not(Box::new(inner.with_default_fields(fields))),
+        }
+    }
+}
+
+/// Parse a query string.
+pub fn parse(input: &str) -> Result<QueryExpr> {
+    if input.trim().is_empty() {
+        return Err(query_error("query is empty"));
+    }
+
+    let mut parser = Parser {
+        tokens: lex(input)?,
+        next: 0,
+    };
+    let expr = parser.parse_or()?;
+    match parser.tokens.get(parser.next) {
+        None => Ok(expr),
+        Some(token) => Err(syntax_error(
+            token.pos,
+            format!("unexpected {} with no matching '('", token.kind),
+        )),
+    }
+}
+
+fn query_error(reason: impl Into<String>) -> SnatchError {
+    SnatchError::InvalidArgument {
+        name: "query".to_string(),
+        reason: reason.into(),
+    }
+}
+
+fn syntax_error(pos: usize, message: impl fmt::Display) -> SnatchError {
+    query_error(format!("{message} at position {pos}"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term {
+        field: Option<QueryField>,
+        text: String,
+    },
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LParen => f.write_str("'('"),
+            Self::RParen => f.write_str("')'"),
+            Self::And => f.write_str("'AND'"),
+            Self::Or => f.write_str("'OR'"),
+            Self::Not => f.write_str("'NOT'"),
+            Self::Term { text, .. } => write!(f, "'{text}'"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    /// 1-based character position in the query.
+    pos: usize,
+}
+
+/// Read a quoted phrase starting at the opening quote `chars[start]`.
+/// Returns the unescaped text and the index after the closing quote.
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize)> {
+    let mut text = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => {
+                if text.trim().is_empty() {
+                    return Err(syntax_error(start + 1, "empty phrase"));
+                }
+                return Ok((text, i + 1));
+            }
+            '\\' if matches!(chars.get(i + 1), Some('"' | '\\')) => {
+                text.push(chars[i + 1]);
+                i += 2;
+            }
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    Err(syntax_error(start + 1, "unterminated quote"))
+}
+
+fn lex(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let pos = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let kind = match c {
+            '(' => {
+                i += 1;
+                TokenKind::LParen
+            }
+            ')' => {
+                i += 1;
+                TokenKind::RParen
+            }
+            '-' if chars
+                .get(i + 1)
+                .is_some_and(|next| !next.is_whitespace() && *next != ')') =>
+            {
+                i += 1;
+                TokenKind::Not
+            }
+            '"' => {
+                let (text, next) = read_quoted(&chars, i)?;
+                i = next;
+                TokenKind::Term { field: None, text }
+            }
+            _ => {
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !matches!(chars[i], '(' | ')' | '"')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+
+                // A prefix that names no field, as in `std::fs` or a URL,
+                // leaves the word as plain text.
+                let prefixed = word
+                    .split_once(':')
+                    .and_then(|(name, value)| Some((QueryField::from_name(name)?, name, value)));
+                if let Some((field, name, value)) = prefixed {
+                    let text = if !value.is_empty() {
+                        value.to_string()
+                    } else if chars.get(i) == Some(&'"') {
+                        let (text, next) = read_quoted(&chars, i)?;
+                        i = next;
+                        text
+                    } else {
+                        return Err(syntax_error(pos, format!("'{name}:' needs a value")));
+                    };
+                    TokenKind::Term {
+                        field: Some(field),
+                        text,
+                    }
+                } else {
+                    match word.as_str() {
+                        "AND" => TokenKind::And,
+                        "OR" => TokenKind::Or,
+                        "NOT" => TokenKind::Not,
+                        _ => TokenKind::Term {
+                            field: None,
+                            text: word,
+                        },
+                    }
+                }
+            }
+        };
+        tokens.push(Token { kind, pos });
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over lexed tokens.
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.next).map(|token| &token.kind)
+    }
+
+    fn parse_or(&mut self) -> Result<QueryExpr> {
+        let mut any = vec![self.parse_and()?];
+        while self.peek() == Some(&TokenKind::Or) {
+            self.next += 1;
+            any.push(self.parse_and()?);
+        }
+        Ok(if any.len() == 1 {
+            any.remove(0)
+        } else {
+            QueryExpr::Or(any)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<QueryExpr> {
+        let mut all = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some(TokenKind::And) => {
+                    self.next += 1;
+                    all.push(self.parse_unary()?);
+                }
+                Some(TokenKind::Not | TokenKind::LParen | TokenKind::Term { .. }) => {
+                    all.push(self.parse_unary()?);
+                }
+                Some(TokenKind::Or | TokenKind::RParen) | None => break,
+            }
+        }
+        Ok(if all.len() == 1 {
+            all.remove(0)
+        } else {
+            QueryExpr::And(all)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<QueryExpr> {
+        let Some(token) = self.tokens.get(self.next) else {
+            let after = self.next.checked_sub(1).map(|i| &self.tokens[i]);
+            return Err(query_error(match after {
+                Some(token) => format!("expected a term after {} at end of query", token.kind),
+                None => "expected a term".to_string(),
+            }));
+        };
+        let pos = token.pos;
+        let kind = token.kind.clone();
+        self.next += 1;
+
+        match kind {
+            TokenKind::Term { field, text } => Ok(QueryExpr::Match { field, text }),
+            TokenKind::Not => Ok(QueryExpr::Not(Box::new(self.parse_unary()?))),
+            TokenKind::LParen => {
+                if self.peek() == Some(&TokenKind::RParen) {
+                    return Err(syntax_error(pos, "empty parentheses"));
+                }
+                let expr = self.parse_or()?;
+                if self.peek() != Some(&TokenKind::RParen) {
+                    return Err(syntax_error(pos, "unclosed '('"));
+                }
+                self.next += 1;
+                Ok(expr)
+            }
+            TokenKind::RParen => Err(syntax_error(pos, "expected a term before ')'")),
+            op @ (TokenKind::And | TokenKind::Or) => {
+                Err(syntax_error(pos, format!("expected a term before {op}")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str) -> QueryExpr {
+        QueryExpr::Match {
+            field: None,
+            text: text.to_string(),
+        }
+    }
+
+    fn error(input: &str) -> String {
+        parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse_precedence_and_grouping() {
+        assert_eq!(
+            parse("a b OR NOT c").unwrap(),
+            QueryExpr::Or(vec![
+                QueryExpr::And(vec![word("a"), word("b")]),
+                QueryExpr::Not(Box::new(word("c"))),
+            ])
+        );
+        assert_eq!(
+            parse("(a OR b) AND -c").unwrap(),
+            QueryExpr::And(vec![
+                QueryExpr::Or(vec![word("a"), word("b")]),
+                QueryExpr::Not(Box::new(word("c"))),
+            ])
+        );
+        // Lower-case operators are plain words.
+        assert_eq!(
+            parse("cats and dogs").unwrap(),
+            QueryExpr::And(vec![word("cats"), word("and"), word("dogs")])
+        );
+    }
+
+    #[test]
+    fn test_parse_phrases_and_fields() {
+        assert_eq!(
+            parse(r#"tool_name:Bash project:"/home/user/my app" "say \"hi\"""#).unwrap(),
+            QueryExpr::And(vec![
+                QueryExpr::field(QueryField::ToolName, "Bash"),
+                QueryExpr::field(QueryField::Project, "/home/user/my app"),
+                word("say \"hi\""),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_are_positioned() {
+        assert!(error("").contains("query is empty"));
+        assert!(error("a AND").contains("expected a term after 'AND' at end of query"));
+        assert!(error("OR a").contains("expected a term before 'OR' at position 1"));
+        assert!(error("(a OR b").contains("unclosed '(' at position 1"));
+        assert!(error("a)").contains("unexpected ')' with no matching '(' at position 2"));
+        assert!(error("a \"b c").contains("unterminated quote at position 3"));
+        assert!(error("()").contains("empty parentheses"));
+        assert!(error("model:").contains("'model:' needs a value"));
+    }
+
+    #[test]
+    fn test_unknown_prefixes_are_plain_text() {
+        assert_eq!(
+            parse("http://example.com").unwrap(),
+            word("http://example.com")
+        );
+        assert_eq!(
+            parse("std::fs model:opus").unwrap(),
+            QueryExpr::And(vec![
+                word("std::fs"),
+                QueryExpr::field(QueryField::Model, "opus")
+            ])
+        );
+    }
+}
@@ -8,31 +8,52 @@
 //! - Fast full-text search across all sessions
 //! - Incremental index updates
 //! - Field-specific search (message type, model, tool)
+//! - Boolean/phrase [query language](dsl) with positioned errors
 //! - Index persistence and management
 
 /// Versioned provider-neutral search index.
 pub mod build;
+/// Boolean/phrase query language for the legacy index.
+pub mod dsl;
 /// Versioned provider-neutral storage and projection contracts.
 pub mod provider;
 /// Exact provider-index regex/fuzzy query execution.
 pub mod query;
+/// Embedding-based semantic search with hybrid ranking.
+#[cfg(feature = "semantic")]
+pub mod semantic;
+/// Code- and CJK-aware tokenizers for the search indexes.
+pub mod tokenizer;
 
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 
-use parking_lot::RwLock;
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
 use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
-use tantivy::schema::{Schema, Value, FAST, STORED, STRING, TEXT};
+use tantivy::query::{AllQuery, BooleanQuery, BoostQuery, Occur, PhraseQuery, Query, TermQuery};
+use tantivy::schema::{
+    IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, FAST, STORED, STRING,
+};
+use tantivy::tokenizer::TokenStream;
 use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument};
 
+use crate::config::{FieldBoosts, IndexTokenizer};
 use crate::discovery::Session;
 use crate::error::{Result, SnatchError};
 use crate::model::{ContentBlock, LogEntry};
 
+use dsl::{QueryExpr, QueryField};
+
 /// Default number of search results.
 pub const DEFAULT_RESULT_LIMIT: usize = 100;
 
+/// Source name of sessions indexed without one, normally the local Claude
+/// directory.
+pub const LOCAL_SOURCE: &str = "local";
+
 /// Schema field names.
 mod fields {
     pub const SESSION_ID: &str = "session_id";
@@ -45,6 +66,8 @@ mod fields {
     pub const THINKING: &str = "thinking";
     pub const TOOL_NAME: &str = "tool_name";
     pub const TOOL_INPUT: &str = "tool_input";
+    pub const SOURCE: &str = "source";
+    pub const TOOL_OUTPUT: &str = "tool_output";
 }
 
 /// A search index for Claude Code conversation logs.
@@ -54,11 +77,18 @@ pub struct SearchIndex {
     reader: IndexReader,
     writer: Arc<RwLock<IndexWriter>>,
     index_path: PathBuf,
+    /// Sessions written since the last commit, recorded in the manifest once
+    /// committed.
+    pending: Mutex<Vec<IndexedSession>>,
+    /// Score multipliers for matches in each text field.
+    boosts: FieldBoosts,
 }
 
 /// A search result from the index.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct SearchHit {
+    /// Name of the Claude directory the session came from.
+    pub source: String,
     /// Session ID containing the match.
     pub session_id: String,
     /// Project path.
@@ -90,9 +120,29 @@ pub struct IndexStats {
     pub last_updated: Option<String>,
 }
 
+/// How completely the index covers a set of discovered sessions.
+///
+/// Sessions are named `source/session_id`. This is the library
+/// [`SearchIndex`]'s view; `snatch index status` reports the provider index,
+/// whose per-session manifests carry its session and entry counts.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct IndexCoverage {
+    /// Sessions indexed from their current file state.
+    pub indexed: usize,
+    /// Documents of the up-to-date sessions.
+    pub documents: usize,
+    /// Sessions whose file changed since they were indexed.
+    pub stale: Vec<String>,
+    /// Sessions never indexed.
+    pub missing: Vec<String>,
+    /// Indexed sessions no longer discovered.
+    pub orphaned: Vec<String>,
+}
+
 impl SearchIndex {
-    /// Create the tantivy schema for conversation logs.
-    fn build_schema() -> Schema {
+    /// Create the tantivy schema for conversation logs, analyzing the
+    /// searchable text fields with `tokenizer`.
+    fn build_schema(tokenizer: IndexTokenizer) -> Schema {
         let mut schema_builder = Schema::builder();
 
         // Stored fields for result display
@@ -102,18 +152,34 @@ impl SearchIndex {
         schema_builder.add_text_field(fields::TIMESTAMP, STRING | STORED | FAST);
         schema_builder.add_text_field(fields::MESSAGE_TYPE, STRING | STORED);
         schema_builder.add_text_field(fields::MODEL, STRING | STORED);
+        schema_builder.add_text_field(fields::SOURCE, STRING | STORED);
 
         // Searchable text fields
-        schema_builder.add_text_field(fields::CONTENT, TEXT | STORED);
-        schema_builder.add_text_field(fields::THINKING, TEXT);
+        let text = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer(tokenizer::tokenizer_name(tokenizer))
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        );
+        schema_builder.add_text_field(fields::CONTENT, text.clone() | STORED);
+        schema_builder.add_text_field(fields::THINKING, text.clone());
         schema_builder.add_text_field(fields::TOOL_NAME, STRING | STORED);
-        schema_builder.add_text_field(fields::TOOL_INPUT, TEXT);
+        schema_builder.add_text_field(fields::TOOL_INPUT, text.clone());
+        schema_builder.add_text_field(fields::TOOL_OUTPUT, text);
 
         schema_builder.build()
     }
 
     /// Open or create a search index at the specified path.
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with_tokenizer(path, IndexTokenizer::default())
+    }
+
+    /// Open or create a search index at the specified path, analyzing text
+    /// with `tokenizer` if the index is created.
+    ///
+    /// An existing index keeps the tokenizer it was created with; delete it
+    /// to rebuild with another.
+    pub fn open_with_tokenizer(path: impl AsRef<Path>, tokenizer: IndexTokenizer) -> Result<Self> {
         let path = path.as_ref();
         provider::refuse_rebuild_in_progress(path)?;
 
@@ -127,16 +193,25 @@ impl SearchIndex {
             })?;
         }
 
-        let schema = Self::build_schema();
-
         // Try to open existing index, or create new one
         let index = if path.join("meta.json").exists() {
-            Index::open_in_dir(path)
-                .map_err(|e| SnatchError::IndexError(format!("Failed to open index: {}", e)))?
+            let index = Index::open_in_dir(path)
+                .map_err(|e| SnatchError::IndexError(format!("Failed to open index: {}", e)))?;
+            for field in [fields::SOURCE, fields::TOOL_OUTPUT] {
+                if index.schema().get_field(field).is_err() {
+                    return Err(SnatchError::IndexError(format!(
+                        "Index at {} predates the `{field}` field; delete it to rebuild",
+                        path.display()
+                    )));
+                }
+            }
+            index
         } else {
-            Index::create_in_dir(path, schema.clone())
+            Index::create_in_dir(path, Self::build_schema(tokenizer))
                 .map_err(|e| SnatchError::IndexError(format!("Failed to create index: {}", e)))?
         };
+        tokenizer::register(&index);
+        let schema = index.schema();
 
         // Create reader with reload on commit
         let reader = index
@@ -156,6 +231,8 @@ impl SearchIndex {
             reader,
             writer: Arc::new(RwLock::new(writer)),
             index_path: path.to_path_buf(),
+            pending: Mutex::new(Vec::new()),
+            boosts: FieldBoosts::default(),
         })
     }
 
@@ -176,7 +253,27 @@ impl SearchIndex {
                     .join("search-index")
             });
 
-        Self::open(index_dir)
+        let tokenizer = config.map(|c| c.index.tokenizer).unwrap_or_default();
+        let boosts = config.map(|c| c.index.boosts).unwrap_or_default();
+        Ok(Self::open_with_tokenizer(index_dir, tokenizer)?.with_field_boosts(boosts))
+    }
+
+    /// Weight matches in each text field by `boosts` when ranking.
+    #[must_use]
+    pub fn with_field_boosts(mut self, boosts: FieldBoosts) -> Self {
+        self.boosts = boosts;
+        self
+    }
+
+    /// Score multiplier for matches in `field`.
+    const fn field_boost(&self, field: QueryField) -> f32 {
+        match field {
+            QueryField::Content => self.boosts.content,
+            QueryField::Thinking => self.boosts.thinking,
+            QueryField::ToolInput => self.boosts.tool_input,
+            QueryField::ToolOutput => self.boosts.tool_output,
+            _ => 1.0,
+        }
     }
 
     /// Get the default index directory path.
@@ -191,6 +288,7 @@ impl SearchIndex {
     fn index_entry(
         &self,
         writer: &mut IndexWriter,
+        source: &str,
         session_id: &str,
         project: &str,
         entry: &LogEntry,
@@ -214,6 +312,7 @@ impl SearchIndex {
             .get_field(fields::MESSAGE_TYPE)
             .expect("schema field");
         let model_field = self.schema.get_field(fields::MODEL).expect("schema field");
+        let source_field = self.schema.get_field(fields::SOURCE).expect("schema field");
         let content_field = self
             .schema
             .get_field(fields::CONTENT)
@@ -230,6 +329,10 @@ impl SearchIndex {
             .schema
             .get_field(fields::TOOL_INPUT)
             .expect("schema field");
+        let tool_output_field = self
+            .schema
+            .get_field(fields::TOOL_OUTPUT)
+            .expect("schema field");
 
         let uuid = entry.uuid().unwrap_or("").to_string();
         let timestamp = entry
@@ -239,6 +342,7 @@ impl SearchIndex {
 
         match entry {
             LogEntry::User(user) => {
+                let mut tool_outputs = Vec::new();
                 let content = match &user.message {
                     crate::model::UserContent::Simple(s) => s.content.clone(),
                     crate::model::UserContent::Blocks(b) => b
@@ -246,24 +350,37 @@ impl SearchIndex {
                         .iter()
                         .filter_map(|c| match c {
                             ContentBlock::Text(t) => Some(t.text.as_str()),
+                            ContentBlock::ToolResult(r) => {
+                                if let Some(output) = &r.content {
+                                    tool_outputs.push(output.to_display_string(false));
+                                }
+                                None
+                            }
                             _ => None,
                         })
                         .collect::<Vec<_>>()
                         .join("\n"),
                 };
+                if let Some(result) = &user.tool_use_result {
+                    flatten_json_text(result, &mut tool_outputs);
+                }
 
-                writer
-                    .add_document(doc!(
-                        session_id_field => session_id,
-                        project_field => project,
-                        uuid_field => uuid,
-                        timestamp_field => timestamp,
-                        message_type_field => message_type,
-                        content_field => content
-                    ))
-                    .map_err(|e| {
-                        SnatchError::IndexError(format!("Failed to add document: {}", e))
-                    })?;
+                let mut doc = doc!(
+                    session_id_field => session_id,
+                    source_field => source,
+                    project_field => project,
+                    uuid_field => uuid,
+                    timestamp_field => timestamp,
+                    message_type_field => message_type,
+                    content_field => content
+                );
+                for output in &tool_outputs {
+                    doc.add_text(tool_output_field, output);
+                }
+
+                writer.add_document(doc).map_err(|e| {
+                    SnatchError::IndexError(format!("Failed to add document: {}", e))
+                })?;
             }
             LogEntry::Assistant(assistant) => {
                 let model = assistant.message.model.clone();
@@ -272,6 +389,7 @@ impl SearchIndex {
                 let mut thinking_parts = Vec::new();
                 let mut tool_names = Vec::new();
                 let mut tool_inputs = Vec::new();
+                let mut tool_outputs = Vec::new();
 
                 for block in &assistant.message.content {
                     match block {
@@ -284,10 +402,8 @@ impl SearchIndex {
                             }
                         }
                         ContentBlock::ToolResult(r) => {
-                            if let Some(crate::model::content::ToolResultContent::String(s)) =
-                                &r.content
-                            {
-                                content_parts.push(s.clone());
+                            if let Some(output) = &r.content {
+                                tool_outputs.push(output.to_display_string(false));
                             }
                         }
                         _ => {}
@@ -299,6 +415,7 @@ impl SearchIndex {
 
                 let mut doc = doc!(
                     session_id_field => session_id,
+                    source_field => source,
                     project_field => project,
                     uuid_field => uuid,
                     timestamp_field => timestamp,
@@ -314,6 +431,9 @@ impl SearchIndex {
                 for input in &tool_inputs {
                     doc.add_text(tool_input_field, input);
                 }
+                for output in &tool_outputs {
+                    doc.add_text(tool_output_field, output);
+                }
 
                 writer.add_document(doc).map_err(|e| {
                     SnatchError::IndexError(format!("Failed to add document: {}", e))
@@ -324,6 +444,7 @@ impl SearchIndex {
                     writer
                         .add_document(doc!(
                             session_id_field => session_id,
+                            source_field => source,
                             project_field => project,
                             uuid_field => uuid,
                             timestamp_field => timestamp,
@@ -339,6 +460,7 @@ impl SearchIndex {
                 writer
                     .add_document(doc!(
                         session_id_field => session_id,
+                        source_field => source,
                         project_field => project,
                         uuid_field => uuid,
                         timestamp_field => timestamp,
@@ -355,24 +477,29 @@ impl SearchIndex {
         Ok(())
     }
 
-    /// Index a session's entries.
+    /// Index a session's entries, replacing any documents it already has
+    /// so re-indexing is idempotent.
     pub fn index_session(&self, session: &Session) -> Result<usize> {
         let entries = session.parse()?;
         let session_id = session.session_id();
         let project = session.project_path();
 
         let mut writer = self.writer.write();
+        self.delete_source_session(&writer, LOCAL_SOURCE, session_id)?;
         let mut indexed = 0;
 
         for entry in &entries {
-            self.index_entry(&mut writer, session_id, project, entry)?;
+            self.index_entry(&mut writer, LOCAL_SOURCE, session_id, project, entry)?;
             indexed += 1;
         }
+        self.pending
+            .lock()
+            .push(IndexedSession::of(LOCAL_SOURCE, session, indexed));
 
         Ok(indexed)
     }
 
-    /// Index multiple sessions.
+    /// Index multiple sessions, replacing documents of any already indexed.
     pub fn index_sessions(&self, sessions: &[Session]) -> Result<IndexingResult> {
         let mut total_indexed = 0;
         let mut session_count = 0;
@@ -387,16 +514,28 @@ impl SearchIndex {
                         let session_id = session.session_id();
                         let project = session.project_path();
 
+                        self.delete_source_session(&writer, LOCAL_SOURCE, session_id)?;
+                        let mut documents = 0;
                         for entry in &entries {
-                            if let Err(e) =
-                                self.index_entry(&mut writer, session_id, project, entry)
-                            {
+                            if let Err(e) = self.index_entry(
+                                &mut writer,
+                                LOCAL_SOURCE,
+                                session_id,
+                                project,
+                                entry,
+                            ) {
                                 errors.push((session.session_id().to_string(), e.to_string()));
                             } else {
-                                total_indexed += 1;
+                                documents += 1;
                             }
                         }
+                        total_indexed += documents;
                         session_count += 1;
+                        self.pending.lock().push(IndexedSession::of(
+                            LOCAL_SOURCE,
+                            session,
+                            documents,
+                        ));
                     }
                     Err(e) => {
                         errors.push((session.session_id().to_string(), e.to_string()));
@@ -412,13 +551,175 @@ impl SearchIndex {
         })
     }
 
+    /// Tantivy term matching every document of a session.
+    fn session_term(&self, session_id: &str) -> tantivy::Term {
+        let session_id_field = self
+            .schema
+            .get_field(fields::SESSION_ID)
+            .expect("schema field");
+        tantivy::Term::from_field_text(session_id_field, session_id)
+    }
+
+    /// Query matching every document of a session from one source.
+    fn source_session_query(&self, source: &str, session_id: &str) -> Box<dyn Query> {
+        let source_field = self.schema.get_field(fields::SOURCE).expect("schema field");
+        Box::new(BooleanQuery::new(vec![
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    tantivy::Term::from_field_text(source_field, source),
+                    IndexRecordOption::Basic,
+                )) as Box<dyn Query>,
+            ),
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    self.session_term(session_id),
+                    IndexRecordOption::Basic,
+                )),
+            ),
+        ]))
+    }
+
+    /// Queue deletion of every document of a session from one source.
+    fn delete_source_session(
+        &self,
+        writer: &IndexWriter,
+        source: &str,
+        session_id: &str,
+    ) -> Result<()> {
+        writer
+            .delete_query(self.source_session_query(source, session_id))
+            .map_err(|e| SnatchError::IndexError(format!("Failed to delete documents: {}", e)))?;
+        Ok(())
+    }
+
+    /// Read the session manifest. A missing, unreadable, or outdated manifest
+    /// is treated as empty, so every session is re-indexed once.
+    fn load_manifest(&self) -> SessionManifest {
+        std::fs::read(self.index_path.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<SessionManifest>(&bytes).ok())
+            .filter(|manifest| manifest.version == SessionManifest::CURRENT_VERSION)
+            .unwrap_or_default()
+    }
+
+    fn save_manifest(&self, manifest: &SessionManifest) -> Result<()> {
+        let json = serde_json::to_vec(manifest)?;
+        crate::util::atomic_write(self.index_path.join(MANIFEST_FILE), &json)
+    }
+
+    /// Bring the index up to date with `sessions`, indexing only what changed.
+    ///
+    /// Equivalent to [`Self::update_sources`] with every session in
+    /// [`LOCAL_SOURCE`].
+    pub fn update_sessions(&self, sessions: &[Session]) -> Result<IndexUpdateResult> {
+        self.update_sources(&[(LOCAL_SOURCE, sessions)])
+    }
+
+    /// Bring the index up to date with sessions from several Claude
+    /// directories, indexing only what changed.
+    ///
+    /// Each document is tagged with its source name, searchable as
+    /// `source:<name>`. A manifest in the index directory records each
+    /// session's file modification time and size as of its last indexing.
+    /// Sessions whose file is unchanged are skipped; new and changed sessions
+    /// have their old documents replaced; sessions in the manifest but not in
+    /// `sources` are treated as removed and their documents deleted, so pass
+    /// the complete set. Changes are committed before returning.
+    pub fn update_sources(&self, sources: &[(&str, &[Session])]) -> Result<IndexUpdateResult> {
+        let mut result = IndexUpdateResult::default();
+        let mut live = HashSet::new();
+
+        let manifest = {
+            let mut writer = self.writer.write();
+            // Sessions indexed since the last commit are committed below too.
+            let mut manifest = self.load_manifest();
+            manifest.record(self.pending.lock().drain(..));
+
+            for (source, sessions) in sources {
+                for session in *sessions {
+                    let session_id = session.session_id();
+                    let key = format!("{source}/{session_id}");
+                    live.insert(key.clone());
+                    if manifest
+                        .sessions
+                        .get(&key)
+                        .is_some_and(|indexed| indexed.is_current(session))
+                    {
+                        result.sessions_unchanged += 1;
+                        continue;
+                    }
+
+                    self.delete_source_session(&writer, source, session_id)?;
+                    match session.parse() {
+                        Ok(entries) => {
+                            let mut documents = 0;
+                            for entry in &entries {
+                                if let Err(e) = self.index_entry(
+                                    &mut writer,
+                                    source,
+                                    session_id,
+                                    session.project_path(),
+                                    entry,
+                                ) {
+                                    result.errors.push((session_id.to_string(), e.to_string()));
+                                } else {
+                                    documents += 1;
+                                }
+                            }
+                            result.documents_indexed += documents;
+                            result.sessions_indexed += 1;
+                            manifest
+                                .sessions
+                                .insert(key, IndexedSession::of(source, session, documents));
+                        }
+                        Err(e) => {
+                            // Retry on the next update rather than trusting a
+                            // stale manifest entry.
+                            manifest.sessions.remove(&key);
+                            result.errors.push((session_id.to_string(), e.to_string()));
+                        }
+                    }
+                }
+            }
+
+            let removed: Vec<String> = manifest
+                .sessions
+                .keys()
+                .filter(|key| !live.contains(*key))
+                .cloned()
+                .collect();
+            for key in removed {
+                if let Some(state) = manifest.sessions.remove(&key) {
+                    self.delete_source_session(&writer, &state.source, &state.session_id)?;
+                    result.sessions_removed += 1;
+                }
+            }
+
+            writer
+                .commit()
+                .map_err(|e| SnatchError::IndexError(format!("Failed to commit: {}", e)))?;
+            manifest
+        };
+
+        self.save_manifest(&manifest)?;
+        Ok(result)
+    }
+
     /// Commit pending changes to the index.
     pub fn commit(&self) -> Result<()> {
         let mut writer = self.writer.write();
         writer
             .commit()
             .map_err(|e| SnatchError::IndexError(format!("Failed to commit: {}", e)))?;
-        Ok(())
+        let mut pending = self.pending.lock();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let mut manifest = self.load_manifest();
+        manifest.record(pending.drain(..));
+        self.save_manifest(&manifest)
     }
 
     /// Clear the entire index.
@@ -430,65 +731,163 @@ impl SearchIndex {
         writer
             .commit()
             .map_err(|e| SnatchError::IndexError(format!("Failed to commit: {}", e)))?;
-        Ok(())
+        self.pending.lock().clear();
+        self.save_manifest(&SessionManifest::default())
     }
 
     /// Delete documents for a specific session.
     pub fn delete_session(&self, session_id: &str) -> Result<()> {
-        let session_id_field = self
-            .schema
-            .get_field(fields::SESSION_ID)
-            .expect("schema field");
-        let term = tantivy::Term::from_field_text(session_id_field, session_id);
+        let term = self.session_term(session_id);
 
         let writer = self.writer.write();
         writer.delete_term(term);
         Ok(())
     }
 
-    /// Search the index.
-    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
-        let searcher = self.reader.searcher();
+    /// Build a tantivy query from a parsed DSL expression.
+    fn build_query(&self, expr: &QueryExpr) -> Result<Box<dyn Query>> {
+        match expr {
+            QueryExpr::Match {
+                field: Some(field),
+                text,
+            } => self.match_query(*field, text),
+            QueryExpr::Match { field: None, text } => {
+                let clauses = QueryField::DEFAULT
+                    .iter()
+                    .map(|field| Ok((Occur::Should, self.match_query(*field, text)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Box::new(BooleanQuery::new(clauses)))
+            }
+            QueryExpr::And(all) => {
+                let mut clauses = Vec::with_capacity(all.len() + 1);
+                for expr in all {
+                    clauses.push(match expr {
+                        QueryExpr::Not(inner) => (Occur::MustNot, self.build_query(inner)?),
+                        _ => (Occur::Must, self.build_query(expr)?),
+                    });
+                }
+                // Tantivy matches nothing for purely negative clauses.
+                if clauses.iter().all(|(occur, _)| *occur == Occur::MustNot) {
+                    clauses.push((Occur::Must, Box::new(AllQuery)));
+                }
+                Ok(Box::new(BooleanQuery::new(clauses)))
+            }
+            QueryExpr::Or(any) => {
+                let clauses = any
+                    .iter()
+                    .map(|expr| Ok((Occur::Should, self.build_query(expr)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Box::new(BooleanQuery::new(clauses)))
+            }
+            QueryExpr::Not(inner) => Ok(Box::new(BooleanQuery::new(vec![
+                (Occur::Must, Box::new(AllQuery) as Box<dyn Query>),
+                (Occur::MustNot, self.build_query(inner)?),
+            ]))),
+        }
+    }
 
-        // Parse query - search content by default
-        let content_field = self
-            .schema
-            .get_field(fields::CONTENT)
-            .expect("schema field");
-        let thinking_field = self
-            .schema
-            .get_field(fields::THINKING)
-            .expect("schema field");
-        let tool_input_field = self
-            .schema
-            .get_field(fields::TOOL_INPUT)
-            .expect("schema field");
+    /// Query for one word, phrase, or exact value in `field`.
+    fn match_query(&self, field: QueryField, text: &str) -> Result<Box<dyn Query>> {
+        let schema_field = self.schema.get_field(field.name()).expect("schema field");
+        if field.is_exact() {
+            return Ok(Box::new(TermQuery::new(
+                tantivy::Term::from_field_text(schema_field, text),
+                IndexRecordOption::Basic,
+            )));
+        }
 
-        let query_parser = QueryParser::for_index(
-            &self.index,
-            vec![content_field, thinking_field, tool_input_field],
-        );
+        let mut analyzer = self
+            .index
+            .tokenizer_for_field(schema_field)
+            .map_err(|e| SnatchError::IndexError(format!("Failed to get tokenizer: {}", e)))?;
+        // Tokenizers may emit several tokens at one position, such as a whole
+        // identifier and its first part; the last one is the finest-grained.
+        let mut terms: Vec<(usize, tantivy::Term)> = Vec::new();
+        analyzer.token_stream(text).process(&mut |token| {
+            let term = tantivy::Term::from_field_text(schema_field, &token.text);
+            match terms.last_mut() {
+                Some(last) if last.0 == token.position => last.1 = term,
+                _ => terms.push((token.position, term)),
+            }
+        });
 
-        let parsed_query =
-            query_parser
-                .parse_query(query)
-                .map_err(|e| SnatchError::InvalidArgument {
+        let query: Box<dyn Query> = match terms.len() {
+            0 => {
+                return Err(SnatchError::InvalidArgument {
                     name: "query".to_string(),
-                    reason: e.to_string(),
-                })?;
+                    reason: format!("'{text}' contains no searchable words"),
+                })
+            }
+            1 => Box::new(TermQuery::new(
+                terms.remove(0).1,
+                IndexRecordOption::WithFreqs,
+            )),
+            _ => Box::new(PhraseQuery::new_with_offset(terms)),
+        };
+        let boost = self.field_boost(field);
+        if (boost - 1.0).abs() < f32::EPSILON {
+            Ok(query)
+        } else {
+            Ok(Box::new(BoostQuery::new(query, boost)))
+        }
+    }
 
+    /// Search the index.
+    ///
+    /// `query` uses the [query language](dsl) of this module: `AND`, `OR`,
+    /// `NOT`, quoted phrases, parentheses, and `field:` prefixes. Adjacent
+    /// terms must all match; before the query language, any one of them
+    /// was enough, so write `a OR b` for the old behavior.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        self.search_expr(&dsl::parse(query)?, limit)
+    }
+
+    /// Search the index, skipping the first `offset` ranked hits.
+    pub fn search_page(&self, query: &str, offset: usize, limit: usize) -> Result<SearchPage> {
+        self.search_expr_page(&dsl::parse(query)?, offset, limit)
+    }
+
+    /// Search the index with a parsed query.
+    pub fn search_expr(&self, expr: &QueryExpr, limit: usize) -> Result<Vec<SearchHit>> {
+        Ok(self.search_expr_page(expr, 0, limit)?.hits)
+    }
+
+    /// Search the index with a parsed query, skipping the first `offset`
+    /// ranked hits.
+    pub fn search_expr_page(
+        &self,
+        expr: &QueryExpr,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SearchPage> {
+        let searcher = self.reader.searcher();
+        let parsed_query = self.build_query(expr)?;
+
+        // One hit past the page tells whether another page exists.
+        let window = offset
+            .checked_add(limit)
+            .and_then(|end| end.checked_add(1))
+            .ok_or_else(|| SnatchError::InvalidArgument {
+                name: "offset".to_string(),
+                reason: "offset + limit overflows".to_string(),
+            })?;
         let top_docs = searcher
-            .search(&parsed_query, &TopDocs::with_limit(limit).order_by_score())
+            .search(
+                &*parsed_query,
+                &TopDocs::with_limit(window).order_by_score(),
+            )
             .map_err(|e| SnatchError::IndexError(format!("Search failed: {}", e)))?;
+        let next_offset = (top_docs.len() == window).then_some(offset + limit);
 
         let mut results = Vec::new();
 
-        for (score, doc_address) in top_docs {
+        for (score, doc_address) in top_docs.into_iter().skip(offset).take(limit) {
             let doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| {
                 SnatchError::IndexError(format!("Failed to retrieve document: {}", e))
             })?;
 
             let session_id = self.get_text_field(&doc, fields::SESSION_ID);
+            let source = self.get_text_field(&doc, fields::SOURCE);
             let project = self.get_text_field(&doc, fields::PROJECT);
             let uuid = self.get_text_field(&doc, fields::UUID);
             let timestamp = self.get_text_field(&doc, fields::TIMESTAMP);
@@ -511,6 +910,7 @@ impl SearchIndex {
             };
 
             results.push(SearchHit {
+                source,
                 session_id,
                 project,
                 uuid,
@@ -522,37 +922,53 @@ impl SearchIndex {
             });
         }
 
-        Ok(results)
+        Ok(SearchPage {
+            hits: results,
+            offset,
+            next_offset,
+        })
     }
 
     /// Search with advanced options.
     pub fn search_advanced(&self, options: &SearchOptions) -> Result<Vec<SearchHit>> {
-        // Build query string with field filters
-        let mut query_parts = Vec::new();
+        // Filters are added as field matches rather than interpolated into
+        // the query string, so their values need no escaping.
+        let mut all = Vec::new();
 
         if !options.query.is_empty() {
-            query_parts.push(options.query.clone());
+            let mut fields = vec![QueryField::Content, QueryField::ToolInput];
+            if options.include_thinking {
+                fields.push(QueryField::Thinking);
+            }
+            if options.include_tool_output {
+                fields.push(QueryField::ToolOutput);
+            }
+            all.push(dsl::parse(&options.query)?.with_default_fields(&fields));
         }
 
         if let Some(ref message_type) = options.message_type {
-            query_parts.push(format!("{}:{}", fields::MESSAGE_TYPE, message_type));
+            all.push(QueryExpr::field(QueryField::MessageType, message_type));
         }
 
         if let Some(ref model) = options.model {
-            query_parts.push(format!("{}:{}", fields::MODEL, model));
+            all.push(QueryExpr::field(QueryField::Model, model));
         }
 
         if let Some(ref session_id) = options.session_id {
-            query_parts.push(format!("{}:{}", fields::SESSION_ID, session_id));
+            all.push(QueryExpr::field(QueryField::SessionId, session_id));
         }
 
         if let Some(ref tool_name) = options.tool_name {
-            query_parts.push(format!("{}:{}", fields::TOOL_NAME, tool_name));
+            all.push(QueryExpr::field(QueryField::ToolName, tool_name));
         }
 
-        let full_query = query_parts.join(" AND ");
-
-        self.search(&full_query, options.limit.unwrap_or(DEFAULT_RESULT_LIMIT))
+        Ok(self
+            .search_expr_page(
+                &QueryExpr::And(all),
+                options.offset,
+                options.limit.unwrap_or(DEFAULT_RESULT_LIMIT),
+            )?
+            .hits)
     }
 
     /// Get index statistics.
@@ -568,17 +984,50 @@ impl SearchIndex {
             .map(|e| e.metadata().map(|m| m.len()).unwrap_or(0))
             .sum();
 
-        // Count unique sessions (expensive operation)
-        let session_count = 0; // Would require iterating all docs
+        let manifest = self.load_manifest();
+        let last_updated = manifest
+            .sessions
+            .values()
+            .map(|indexed| indexed.indexed_at)
+            .max()
+            .map(|at| at.to_rfc3339());
 
         Ok(IndexStats {
             document_count,
-            session_count,
+            session_count: manifest.sessions.len(),
             size_bytes,
-            last_updated: None,
+            last_updated,
         })
     }
 
+    /// Sessions recorded in the index manifest, ordered by source and ID.
+    pub fn indexed_sessions(&self) -> Vec<IndexedSession> {
+        self.load_manifest().sessions.into_values().collect()
+    }
+
+    /// Compare discovered sessions, grouped by source, with the manifest.
+    pub fn coverage(&self, sources: &[(&str, &[Session])]) -> IndexCoverage {
+        let mut manifest = self.load_manifest().sessions;
+        let mut coverage = IndexCoverage::default();
+        for (source, sessions) in sources {
+            for session in *sessions {
+                let key = format!("{source}/{}", session.session_id());
+                match manifest.remove(&key) {
+                    Some(indexed) if indexed.is_current(session) => {
+                        coverage.indexed += 1;
+                        coverage.documents += indexed.documents;
+                    }
+                    Some(_) => coverage.stale.push(key),
+                    None => coverage.missing.push(key),
+                }
+            }
+        }
+        coverage.orphaned = manifest.into_keys().collect();
+        coverage.stale.sort();
+        coverage.missing.sort();
+        coverage
+    }
+
     /// Get text from a field in a document.
     fn get_text_field(&self, doc: &TantivyDocument, field_name: &str) -> String {
         let field = self.schema.get_field(field_name).expect("schema field");
@@ -622,7 +1071,6 @@ impl SearchIndex {
     fn aggregate_field(&self, field_name: &str) -> Result<FieldAggregation> {
         use std::collections::HashMap;
         use tantivy::collector::DocSetCollector;
-        use tantivy::query::AllQuery;
 
         let searcher = self.reader.searcher();
         let field = self
@@ -673,8 +1121,12 @@ impl SearchIndex {
         value: &str,
         limit: usize,
     ) -> Result<Vec<SearchHit>> {
-        let query_str = format!("{}:{}", field_name, value);
-        self.search(&query_str, limit)
+        let field =
+            QueryField::from_name(field_name).ok_or_else(|| SnatchError::InvalidArgument {
+                name: "field".to_string(),
+                reason: format!("unknown index field '{field_name}'"),
+            })?;
+        self.search_expr(&QueryExpr::field(field, value), limit)
     }
 
     /// Search for messages using a specific tool.
@@ -712,6 +1164,36 @@ impl SearchIndex {
     }
 }
 
+/// Collect the string and number leaves of a JSON value as text.
+fn flatten_json_text(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) if !s.is_empty() => out.push(s.clone()),
+        serde_json::Value::Number(n) => out.push(n.to_string()),
+        serde_json::Value::Array(values) => {
+            for value in values {
+                flatten_json_text(value, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for value in map.values() {
+                flatten_json_text(value, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// One page of ranked search results.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchPage {
+    /// Hits on this page, best first.
+    pub hits: Vec<SearchHit>,
+    /// Number of ranked hits skipped before this page.
+    pub offset: usize,
+    /// Offset of the next page, or `None` when this page reaches the end.
+    pub next_offset: Option<usize>,
+}
+
 /// Options for advanced search.
 #[derive(Debug, Clone, Default)]
 pub struct SearchOptions {
@@ -725,10 +1207,14 @@ pub struct SearchOptions {
     pub session_id: Option<String>,
     /// Filter by tool name.
     pub tool_name: Option<String>,
-    /// Include thinking blocks in search.
+    /// Also match unprefixed terms against thinking blocks.
     pub include_thinking: bool,
+    /// Also match unprefixed terms against tool output.
+    pub include_tool_output: bool,
     /// Maximum number of results.
     pub limit: Option<usize>,
+    /// Number of ranked results to skip.
+    pub offset: usize,
 }
 
 /// Aggregation of field values with counts.
@@ -753,6 +1239,89 @@ pub struct IndexingResult {
     pub errors: Vec<(String, String)>,
 }
 
+/// Result of an incremental index update.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct IndexUpdateResult {
+    /// Number of documents indexed.
+    pub documents_indexed: usize,
+    /// New or changed sessions that were (re-)indexed.
+    pub sessions_indexed: usize,
+    /// Sessions skipped because their file was unchanged.
+    pub sessions_unchanged: usize,
+    /// Sessions whose documents were deleted because they are gone.
+    pub sessions_removed: usize,
+    /// Errors encountered.
+    pub errors: Vec<(String, String)>,
+}
+
+/// File in the index directory recording the file state each session was
+/// indexed from.
+const MANIFEST_FILE: &str = "snatch-sessions.json";
+
+/// Manifest record of one indexed session.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IndexedSession {
+    /// Name of the Claude directory the session came from.
+    pub source: String,
+    /// Session ID.
+    pub session_id: String,
+    /// Modification time of the file when it was indexed.
+    pub modified: SystemTime,
+    /// Size of the file in bytes when it was indexed.
+    pub size: u64,
+    /// Documents indexed from the session.
+    pub documents: usize,
+    /// When the session was indexed.
+    pub indexed_at: DateTime<Utc>,
+}
+
+impl IndexedSession {
+    fn of(source: &str, session: &Session, documents: usize) -> Self {
+        Self {
+            source: source.to_string(),
+            session_id: session.session_id().to_string(),
+            modified: session.modified_time(),
+            size: session.file_size(),
+            documents,
+            indexed_at: Utc::now(),
+        }
+    }
+
+    /// Whether the session file is unchanged since it was indexed.
+    #[must_use]
+    pub fn is_current(&self, session: &Session) -> bool {
+        self.modified == session.modified_time() && self.size == session.file_size()
+    }
+}
+
+/// Per-session index manifest used by [`SearchIndex::update_sources`],
+/// keyed by `source/session_id`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct SessionManifest {
+    version: u32,
+    sessions: BTreeMap<String, IndexedSession>,
+}
+
+impl SessionManifest {
+    const CURRENT_VERSION: u32 = 3;
+
+    fn record(&mut self, sessions: impl IntoIterator<Item = IndexedSession>) {
+        for indexed in sessions {
+            let key = format!("{}/{}", indexed.source, indexed.session_id);
+            self.sessions.insert(key, indexed);
+        }
+    }
+}
+
+impl Default for SessionManifest {
+    fn default() -> Self {
+        Self {
+            version: Self::CURRENT_VERSION,
+            sessions: BTreeMap::new(),
+        }
+    }
+}
+
 /// Progress update for background indexing.
 #[derive(Debug, Clone)]
 pub struct IndexingProgress {
@@ -869,21 +1438,37 @@ impl SearchIndex {
                 let mut writer = index.writer.write();
 
                 for (i, session) in sessions.iter().enumerate() {
-                    match session.parse() {
+                    // Replace any documents the session already has.
+                    let parsed = session.parse().and_then(|entries| {
+                        index.delete_source_session(&writer, LOCAL_SOURCE, session.session_id())?;
+                        Ok(entries)
+                    });
+                    match parsed {
                         Ok(entries) => {
                             let session_id = session.session_id();
                             let project = session.project_path();
 
+                            let mut documents = 0;
                             for entry in &entries {
-                                if let Err(e) =
-                                    index.index_entry(&mut writer, session_id, project, entry)
-                                {
+                                if let Err(e) = index.index_entry(
+                                    &mut writer,
+                                    LOCAL_SOURCE,
+                                    session_id,
+                                    project,
+                                    entry,
+                                ) {
                                     errors.push((session.session_id().to_string(), e.to_string()));
                                 } else {
-                                    documents_indexed += 1;
+                                    documents += 1;
                                 }
                             }
+                            documents_indexed += documents;
                             session_count += 1;
+                            index.pending.lock().push(IndexedSession::of(
+                                LOCAL_SOURCE,
+                                session,
+                                documents,
+                            ));
                         }
                         Err(e) => {
                             errors.push((session.session_id().to_string(), e.to_string()));
@@ -1001,6 +1586,301 @@ mod tests {
         assert!(index.search("marmot", 10).unwrap().is_empty());
     }
 
+    #[test]
+    fn test_search_query_language() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let lines: Vec<String> = [("u1", "the peregrine falcon dives fast"), ("u2", "a falcon perched")]
+            .iter()
+            .map(|(uuid, text)| {
+                format!(
+                    r#"{{"uuid":"{uuid}","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"{text}"}}}}"#
+                )
+            })
+            .collect();
+        std::fs::write(&jsonl_path, lines.join("\n") + "\n").unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let index_path = dir.path().join("test-index");
+        let index = SearchIndex::open(&index_path).unwrap();
+        index.index_session(&session).unwrap();
+        index.commit().unwrap();
+        // Reopen so the reader sees the commit deterministically.
+        drop(index);
+        let index = SearchIndex::open(&index_path).unwrap();
+
+        let uuids = |query: &str| -> Vec<String> {
+            let mut uuids: Vec<String> = index
+                .search(query, 10)
+                .unwrap()
+                .into_iter()
+                .map(|hit| hit.uuid)
+                .collect();
+            uuids.sort();
+            uuids
+        };
+        assert_eq!(uuids("falcon"), ["u1", "u2"]);
+        assert_eq!(uuids("\"peregrine falcon\""), ["u1"]);
+        assert!(uuids("\"falcon peregrine\"").is_empty());
+        assert_eq!(uuids("falcon -peregrine"), ["u2"]);
+        assert_eq!(uuids("NOT perched"), ["u1"]);
+        assert_eq!(uuids("marmot OR perched"), ["u2"]);
+        assert_eq!(uuids("message_type:user FALCON"), ["u1", "u2"]);
+        assert!(uuids("message_type:assistant").is_empty());
+
+        let err = index.search("tool:Bash", 10).unwrap_err().to_string();
+        assert!(err.contains("unknown field 'tool'"));
+    }
+
+    #[test]
+    fn test_search_page_walks_ranked_results() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let lines: String = (1..=3)
+            .map(|n| {
+                format!(
+                    r#"{{"uuid":"u{n}","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:0{n}Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"peregrine sighting {n}"}}}}"#
+                ) + "\n"
+            })
+            .collect();
+        std::fs::write(&jsonl_path, lines).unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let index_path = dir.path().join("test-index");
+        let index = SearchIndex::open(&index_path).unwrap();
+        index.index_session(&session).unwrap();
+        index.commit().unwrap();
+        drop(index);
+        let index = SearchIndex::open(&index_path).unwrap();
+
+        let first = index.search_page("peregrine", 0, 2).unwrap();
+        assert_eq!(first.hits.len(), 2);
+        assert_eq!(first.next_offset, Some(2));
+        let second = index
+            .search_page("peregrine", first.next_offset.unwrap(), 2)
+            .unwrap();
+        assert_eq!(second.hits.len(), 1);
+        assert_eq!(second.next_offset, None);
+
+        let mut seen: Vec<_> = first
+            .hits
+            .iter()
+            .chain(&second.hits)
+            .map(|hit| hit.uuid.clone())
+            .collect();
+        seen.sort();
+        assert_eq!(seen, ["u1", "u2", "u3"]);
+    }
+
+    #[test]
+    fn test_tool_output_is_searchable_on_request() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let line = format!(
+            r#"{{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"toolu_1","content":"segfault in libfalcon"}}]}},"toolUseResult":{{"stdout":"segfault in libfalcon","stderr":"core dumped","exitCode":139}}}}"#
+        );
+        std::fs::write(&jsonl_path, format!("{line}\n")).unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let index_path = dir.path().join("test-index");
+        let index = SearchIndex::open(&index_path).unwrap();
+        index.index_session(&session).unwrap();
+        index.commit().unwrap();
+        drop(index);
+        let index = SearchIndex::open(&index_path).unwrap();
+
+        assert!(index.search("libfalcon", 10).unwrap().is_empty());
+        assert_eq!(index.search("tool_output:libfalcon", 10).unwrap().len(), 1);
+        // Structured `toolUseResult` fields are indexed as text too.
+        assert_eq!(index.search("tool_output:dumped", 10).unwrap().len(), 1);
+        let options = SearchOptions {
+            query: "segfault AND NOT cats".to_string(),
+            include_tool_output: true,
+            ..SearchOptions::default()
+        };
+        assert_eq!(index.search_advanced(&options).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_thinking_is_opt_in_and_boosted_below_content() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let lines = [
+            format!(
+                r#"{{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"a falcon perched"}}}}"#
+            ),
+            format!(
+                r#"{{"uuid":"a1","parentUuid":"u1","type":"assistant","timestamp":"2025-12-23T00:00:01Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"id":"m1","type":"message","role":"assistant","model":"claude-opus-4-5","content":[{{"type":"thinking","thinking":"falcon falcon falcon","signature":"sig"}}]}}}}"#
+            ),
+        ];
+        std::fs::write(&jsonl_path, lines.join("\n") + "\n").unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let index_path = dir.path().join("test-index");
+        let index = SearchIndex::open(&index_path).unwrap();
+        index.index_session(&session).unwrap();
+        index.commit().unwrap();
+        drop(index);
+        let index = SearchIndex::open(&index_path).unwrap();
+
+        let uuids = |index: &SearchIndex, include_thinking: bool| -> Vec<String> {
+            let options = SearchOptions {
+                query: "falcon".to_string(),
+                include_thinking,
+                ..SearchOptions::default()
+            };
+            index
+                .search_advanced(&options)
+                .unwrap()
+                .into_iter()
+                .map(|hit| hit.uuid)
+                .collect()
+        };
+        assert_eq!(uuids(&index, false), ["u1"]);
+        assert_eq!(uuids(&index, true), ["u1", "a1"]);
+
+        let index = index.with_field_boosts(FieldBoosts {
+            content: 0.5,
+            thinking: 2.0,
+            ..FieldBoosts::default()
+        });
+        assert_eq!(uuids(&index, true), ["a1", "u1"]);
+    }
+
+    #[test]
+    fn test_tokenizers_split_identifiers_and_cjk_text() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let line = format!(
+            r#"{{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"rename parseSessionId in 東京都"}}}}"#
+        );
+        std::fs::write(&jsonl_path, format!("{line}\n")).unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        // Reopen so the reader sees the commit deterministically.
+        let build = |name: &str, tokenizer: IndexTokenizer| {
+            let index_path = dir.path().join(name);
+            let index = SearchIndex::open_with_tokenizer(&index_path, tokenizer).unwrap();
+            index.index_session(&session).unwrap();
+            index.commit().unwrap();
+            drop(index);
+            SearchIndex::open(&index_path).unwrap()
+        };
+
+        let code = build("code-index", IndexTokenizer::Code);
+        for query in ["parseSessionId", "session", "\"session id\"", "東京都"] {
+            assert_eq!(code.search(query, 10).unwrap().len(), 1, "{query}");
+        }
+        assert!(code.search("京都", 10).unwrap().is_empty());
+
+        // The tokenizer an index was created with survives reopening.
+        let cjk = build("cjk-index", IndexTokenizer::Cjk);
+        assert_eq!(cjk.search("京都", 10).unwrap().len(), 1);
+        assert_eq!(cjk.search("session", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_reindexing_a_session_replaces_its_documents() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let line = format!(
+            r#"{{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"the peregrine falcon dives fast"}}}}"#
+        );
+        std::fs::write(&jsonl_path, format!("{line}\n")).unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let index_path = dir.path().join("test-index");
+        let index = SearchIndex::open(&index_path).unwrap();
+        index.index_session(&session).unwrap();
+        index.commit().unwrap();
+        index
+            .index_sessions(std::slice::from_ref(&session))
+            .unwrap();
+        index.commit().unwrap();
+        // Reopen so the reader sees the commits deterministically.
+        drop(index);
+        let index = SearchIndex::open(&index_path).unwrap();
+
+        assert_eq!(index.stats().unwrap().document_count, 1);
+        assert_eq!(index.search("peregrine", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_stats_and_coverage_follow_the_manifest() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let line = format!(
+            r#"{{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"the peregrine falcon dives fast"}}}}"#
+        );
+        std::fs::write(&jsonl_path, format!("{line}\n")).unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let index = SearchIndex::open(dir.path().join("test-index")).unwrap();
+        let before = index.coverage(&[(LOCAL_SOURCE, std::slice::from_ref(&session))]);
+        assert_eq!(before.missing, [format!("local/{session_id}")]);
+
+        index
+            .index_sessions(std::slice::from_ref(&session))
+            .unwrap();
+        index.commit().unwrap();
+        let stats = index.stats().unwrap();
+        assert_eq!(stats.session_count, 1);
+        assert!(stats.last_updated.is_some());
+        let indexed = index.indexed_sessions();
+        assert_eq!(indexed.len(), 1);
+        assert_eq!(indexed[0].documents, 1);
+
+        let current = index.coverage(&[(LOCAL_SOURCE, std::slice::from_ref(&session))]);
+        assert_eq!((current.indexed, current.documents), (1, 1));
+        assert!(current.missing.is_empty() && current.stale.is_empty());
+
+        std::fs::write(&jsonl_path, format!("{line}\n{line}\n")).unwrap();
+        let changed = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+        let stale = index.coverage(&[(LOCAL_SOURCE, std::slice::from_ref(&changed))]);
+        assert_eq!(stale.stale, [format!("local/{session_id}")]);
+        assert_eq!(index.coverage(&[]).orphaned.len(), 1);
+    }
+
+    #[test]
+    fn test_update_sessions_skips_unchanged_and_drops_removed() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let line = format!(
+            r#"{{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"the peregrine falcon dives fast"}}}}"#
+        );
+        std::fs::write(&jsonl_path, format!("{line}\n")).unwrap();
+        let session = crate::discovery::Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let index_path = dir.path().join("test-index");
+        let index = SearchIndex::open(&index_path).unwrap();
+        let first = index
+            .update_sessions(std::slice::from_ref(&session))
+            .unwrap();
+        assert_eq!(first.sessions_indexed, 1);
+        assert_eq!(first.documents_indexed, 1);
+
+        let second = index
+            .update_sessions(std::slice::from_ref(&session))
+            .unwrap();
+        assert_eq!(second.sessions_indexed, 0);
+        assert_eq!(second.sessions_unchanged, 1);
+
+        let third = index.update_sessions(&[]).unwrap();
+        assert_eq!(third.sessions_removed, 1);
+        // Reopen so the reader sees the committed deletion.
+        drop(index);
+        let index = SearchIndex::open(&index_path).unwrap();
+        assert!(index.search("peregrine", 10).unwrap().is_empty());
+    }
+
     #[test]
     fn test_list_tool_names_empty() {
         let dir = tempdir().unwrap();
@@ -18,7 +18,8 @@ use serde::{Deserialize, Serialize};
 use tantivy::collector::DocSetCollector;
 use tantivy::query::{BooleanQuery, EnableScoring, Query, RangeQuery, RegexQuery, TermQuery};
 use tantivy::schema::{
-    Field, IndexRecordOption, Schema, Value, FAST, INDEXED, STORED, STRING, TEXT,
+    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, FAST, INDEXED, STORED,
+    STRING,
 };
 use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
 
@@ -33,7 +34,10 @@ use crate::provider::{
 };
 
 /// Current provider-index schema. A change requires an explicit rebuild.
-pub const PROVIDER_INDEX_SCHEMA_VERSION: u64 = 4;
+///
+/// Version 5 analyzes the text fields with the code/CJK tokenizer; version 6
+/// records each session's source.
+pub const PROVIDER_INDEX_SCHEMA_VERSION: u64 = 6;
 
 const WRITER_MEMORY_BYTES: usize = 50_000_000;
 
@@ -45,6 +49,7 @@ mod fields {
     pub const LOGICAL_ROOT: &str = "logical_root";
     pub const PROJECT_KEY: &str = "project_key";
     pub const PROJECT_PATH: &str = "project_path";
+    pub const SOURCE: &str = "source";
     pub const ENTRY_ID: &str = "entry_id";
     pub const ENTRY_ORDER: &str = "entry_order";
     pub const TIMESTAMP_MILLIS: &str = "timestamp_millis";
@@ -93,6 +98,10 @@ pub struct IndexedSessionManifest {
     pub project_key: String,
     /// Human-readable project path captured at build time.
     pub project_path: String,
+    /// Name of the Claude directory the session was read from
+    /// ([`LOCAL_SOURCE`](super::LOCAL_SOURCE) unless it is a configured
+    /// `[[index.sources]]` entry).
+    pub source: String,
     /// Whether typed lineage classifies this source session as spawned.
     pub spawned: bool,
     /// Provider parse-cache token covering artifacts and parse policy.
@@ -313,6 +322,8 @@ pub struct IndexedSearchEntry {
     pub project_key: String,
     /// Human-readable project path.
     pub project_path: String,
+    /// Name of the Claude directory the session was read from.
+    pub source: String,
     /// Deterministic normalized entry identity.
     pub entry_id: String,
     /// Zero-based normalized entry order inside the source-session
@@ -459,6 +470,7 @@ impl IndexedSessionBatch {
             if entry.logical_root != self.manifest.logical_root
                 || entry.project_key != self.manifest.project_key
                 || entry.project_path != self.manifest.project_path
+                || entry.source != self.manifest.source
                 || entry.spawned != self.manifest.spawned
             {
                 violations.push(format!(
@@ -540,6 +552,7 @@ pub fn project_parsed_session(
     logical_root: &LogicalSessionKey,
     project_key: &str,
     project_path: &str,
+    source: &str,
     spawned: bool,
     revision_token: String,
     metadata_fingerprint: String,
@@ -591,6 +604,7 @@ pub fn project_parsed_session(
                 logical_root: logical_root.clone(),
                 project_key: project_key.to_string(),
                 project_path: project_path.to_string(),
+                source: source.to_string(),
                 entry_id: identified.id.to_string(),
                 entry_order,
                 timestamp: identified.entry.timestamp(),
@@ -616,6 +630,7 @@ pub fn project_parsed_session(
         logical_root,
         project_key: project_key.to_string(),
         project_path: project_path.to_string(),
+        source: source.to_string(),
         spawned,
         revision_token,
         metadata_fingerprint,
@@ -640,6 +655,7 @@ struct ProviderIndexFields {
     logical_root: Field,
     project_key: Field,
     project_path: Field,
+    source: Field,
     entry_id: Field,
     entry_order: Field,
     timestamp_millis: Field,
@@ -675,6 +691,7 @@ impl ProviderIndexFields {
             logical_root: field(fields::LOGICAL_ROOT)?,
             project_key: field(fields::PROJECT_KEY)?,
             project_path: field(fields::PROJECT_PATH)?,
+            source: field(fields::SOURCE)?,
             entry_id: field(fields::ENTRY_ID)?,
             entry_order: field(fields::ENTRY_ORDER)?,
             timestamp_millis: field(fields::TIMESTAMP_MILLIS)?,
@@ -704,6 +721,7 @@ fn build_schema() -> Schema {
     builder.add_text_field(fields::LOGICAL_ROOT, STRING | STORED);
     builder.add_text_field(fields::PROJECT_KEY, STRING | STORED);
     builder.add_text_field(fields::PROJECT_PATH, STRING | STORED);
+    builder.add_text_field(fields::SOURCE, STRING | STORED);
     builder.add_text_field(fields::ENTRY_ID, STRING | STORED);
     builder.add_u64_field(fields::ENTRY_ORDER, INDEXED | FAST | STORED);
     builder.add_i64_field(fields::TIMESTAMP_MILLIS, INDEXED | FAST | STORED);
@@ -713,9 +731,16 @@ fn build_schema() -> Schema {
     builder.add_text_field(fields::ACTIVITY, STRING | STORED);
     builder.add_u64_field(fields::SPAWNED, INDEXED | FAST | STORED);
     builder.add_text_field(fields::TOOL_NAME, STRING | STORED);
-    builder.add_text_field(fields::TEXT, TEXT);
-    builder.add_text_field(fields::REASONING, TEXT);
-    builder.add_text_field(fields::TOOL_TEXT, TEXT);
+    // Identifiers are indexed whole and split at `snake_case`/`camelCase`
+    // boundaries; CJK runs become bigrams.
+    let text = TextOptions::default().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer(super::tokenizer::CODE_CJK_TOKENIZER)
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+    );
+    builder.add_text_field(fields::TEXT, text.clone());
+    builder.add_text_field(fields::REASONING, text.clone());
+    builder.add_text_field(fields::TOOL_TEXT, text);
     builder.add_u64_field(fields::TEXT_HAS_LITERAL_FALLBACK, INDEXED | FAST);
     builder.add_u64_field(fields::REASONING_HAS_LITERAL_FALLBACK, INDEXED | FAST);
     builder.add_u64_field(fields::TOOL_HAS_LITERAL_FALLBACK, INDEXED | FAST);
@@ -726,13 +751,16 @@ fn build_schema() -> Schema {
 /// Whether an entry needs the completeness fallback for plain-literal token
 /// acceleration. Tantivy drops tokens at 40 UTF-8 bytes, and Unicode-aware
 /// case-insensitive regex can match non-ASCII case-fold equivalents that an
-/// ASCII lowercase token query cannot enumerate.
+/// ASCII lowercase token query cannot enumerate. The code tokenizer keeps a
+/// whole `snake_case` identifier as one token, so a literal spanning a
+/// `camelCase` boundary inside it is only indexed while the identifier,
+/// underscores included, stays under the limit.
 fn has_literal_fallback_token(text: &str) -> bool {
-    text.split(|character: char| !character.is_alphanumeric())
+    text.split(|character: char| !(character.is_alphanumeric() || character == '_'))
         .any(|token| token.len() >= 40 || !token.is_ascii())
 }
 
-pub(super) fn rebuild_lock_path(path: &Path) -> Result<PathBuf> {
+pub(crate) fn rebuild_lock_path(path: &Path) -> Result<PathBuf> {
     let parent = path.parent().ok_or_else(|| {
         SnatchError::IndexError(format!(
             "provider index target has no parent: {}",
@@ -906,6 +934,7 @@ pub(crate) struct IndexedEntryCandidateFilter {
     pub session_keys_match_none: bool,
     pub logical_roots: Vec<String>,
     pub project_keys: Vec<String>,
+    pub sources: Vec<String>,
     pub message_types: Vec<String>,
     pub activities: Vec<String>,
     pub spawned: Option<bool>,
@@ -1172,6 +1201,7 @@ impl ProviderSearchIndex {
         if writable {
             secure_index_storage(path)?;
         }
+        super::tokenizer::register(&index);
         let schema = index.schema();
         let fields = ProviderIndexFields::from_schema(&schema)?;
         let reader = index
@@ -1288,6 +1318,7 @@ impl ProviderSearchIndex {
         document.add_text(self.fields.logical_root, &entry.logical_root);
         document.add_text(self.fields.project_key, &entry.project_key);
         document.add_text(self.fields.project_path, &entry.project_path);
+        document.add_text(self.fields.source, &entry.source);
         document.add_text(self.fields.entry_id, &entry.entry_id);
         document.add_u64(
             self.fields.entry_order,
@@ -1348,6 +1379,7 @@ impl ProviderSearchIndex {
         document.add_text(self.fields.logical_root, &manifest.logical_root);
         document.add_text(self.fields.project_key, &manifest.project_key);
         document.add_text(self.fields.project_path, &manifest.project_path);
+        document.add_text(self.fields.source, &manifest.source);
         document.add_u64(self.fields.spawned, u64::from(manifest.spawned));
         document.add_text(self.fields.payload, serde_json::to_string(manifest)?);
         Ok(document)
@@ -1594,6 +1626,7 @@ impl ProviderSearchIndex {
             (self.fields.session_key, &filter.session_keys),
             (self.fields.logical_root, &filter.logical_roots),
             (self.fields.project_key, &filter.project_keys),
+            (self.fields.source, &filter.sources),
             (self.fields.message_type, &filter.message_types),
             (self.fields.activity, &filter.activities),
         ] {
@@ -1821,6 +1854,7 @@ mod tests {
             &parsed.descriptor.key,
             "cwd:/work/fake",
             "/work/fake",
+            crate::index::LOCAL_SOURCE,
             false,
             "revision-1".to_string(),
             "metadata-1".to_string(),
@@ -2065,6 +2099,7 @@ mod tests {
             &parsed.descriptor.key,
             "cwd:/work/fake",
             "/work/fake",
+            crate::index::LOCAL_SOURCE,
             false,
             "revision-1".to_string(),
             "metadata-1".to_string(),
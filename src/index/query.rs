@@ -14,9 +14,11 @@ use crate::analysis::search::{
     count_projection_matches, projection_matches, search_projection, ExactSearchMatcher,
     ProjectedSearchMatch, SearchScope, SearchSegmentKind,
 };
+use crate::config::FieldBoosts;
 use crate::error::{Result, SnatchError};
 use crate::provider::LogicalSessionKey;
 
+use super::dsl::{QueryExpr, QueryField};
 use super::provider::{
     IndexedEntryCandidateFilter, IndexedSearchEntry, IndexedSkip, ProviderSearchIndex,
     PROVIDER_INDEX_SCHEMA_VERSION,
@@ -73,6 +75,8 @@ pub struct IndexedSearchFilters {
     pub project_keys: Vec<String>,
     /// Case-insensitive substring of the project display path.
     pub project_contains: Option<String>,
+    /// Exact names of the Claude directories sessions were read from.
+    pub sources: Vec<String>,
     /// Exact normalized entry discriminator(s).
     pub message_types: Vec<String>,
     /// Case-insensitive assistant-model substring.
@@ -97,6 +101,8 @@ pub struct IndexedSearchFilters {
     pub timestamp_until: Option<DateTime<Utc>>,
     /// Require a tool result with this explicit native error state.
     pub tool_error: Option<bool>,
+    /// Boolean query-language expression the entry must satisfy.
+    pub expression: Option<IndexedQueryExpr>,
     /// Include fork-inherited entries in cross-session search. A selected
     /// source session is content-complete regardless of this value.
     pub include_inherited: bool,
@@ -105,6 +111,205 @@ pub struct IndexedSearchFilters {
     pub include_spawned: bool,
 }
 
+/// A [query language](super::dsl) expression checked against each stored
+/// entry.
+///
+/// Words and phrases match as literal text, in the segments their field
+/// names or, unprefixed, in the search scope. `session_id`, `project`,
+/// `uuid` (the indexed entry id), `message_type`, `model`, and `tool_name`
+/// match their exact value.
+#[derive(Debug, Clone)]
+pub enum IndexedQueryExpr {
+    /// Literal text in the segments `field` names, or in `scope` when unset.
+    Text {
+        /// The text as written.
+        text: String,
+        /// Scope of an unprefixed term.
+        scope: SearchScope,
+        /// Text field of a prefixed term.
+        field: Option<QueryField>,
+        /// Literal matcher for `text`.
+        matcher: ExactSearchMatcher,
+    },
+    /// An exact entry value.
+    Value {
+        /// Entry field.
+        field: QueryField,
+        /// Value to compare with.
+        value: String,
+    },
+    /// Every operand matches.
+    And(Vec<IndexedQueryExpr>),
+    /// At least one operand matches.
+    Or(Vec<IndexedQueryExpr>),
+    /// The operand does not match.
+    Not(Box<IndexedQueryExpr>),
+}
+
+impl IndexedQueryExpr {
+    /// Compile a parsed query; unprefixed terms search `scope`.
+    pub fn compile(expr: &QueryExpr, scope: SearchScope, ignore_case: bool) -> Result<Self> {
+        let compile_all = |all: &[QueryExpr]| {
+            all.iter()
+                .map(|expr| Self::compile(expr, scope, ignore_case))
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(match expr {
+            QueryExpr::Match { field, text } => match field {
+                Some(field) if field.is_exact() => Self::Value {
+                    field: *field,
+                    value: text.clone(),
+                },
+                _ => Self::Text {
+                    text: text.clone(),
+                    scope,
+                    field: *field,
+                    matcher: ExactSearchMatcher::regex(&regex::escape(text), ignore_case).map_err(
+                        |error| SnatchError::InvalidArgument {
+                            name: "query".to_string(),
+                            reason: error.to_string(),
+                        },
+                    )?,
+                },
+            },
+            QueryExpr::And(all) => Self::And(compile_all(all)?),
+            QueryExpr::Or(any) => Self::Or(compile_all(any)?),
+            QueryExpr::Not(inner) => Self::Not(Box::new(Self::compile(inner, scope, ignore_case)?)),
+        })
+    }
+
+    /// Whether `entry` satisfies the expression.
+    #[must_use]
+    pub fn matches(&self, entry: &IndexedSearchEntry) -> bool {
+        match self {
+            Self::Text {
+                scope,
+                field,
+                matcher,
+                ..
+            } => entry.projection.segments.iter().any(|segment| {
+                text_kind_includes(*scope, *field, segment.kind) && matcher.is_match(&segment.text)
+            }),
+            Self::Value { field, value } => match field {
+                QueryField::SessionId => {
+                    entry.session_key == *value
+                        || entry
+                            .session_key
+                            .rsplit_once(':')
+                            .is_some_and(|(_, native)| native == value)
+                }
+                QueryField::Project => entry.project_path == *value,
+                QueryField::Source => entry.source == *value,
+                QueryField::Uuid => entry.entry_id == *value,
+                QueryField::MessageType => entry.message_type == *value,
+                QueryField::Model => entry.model.as_deref() == Some(value.as_str()),
+                QueryField::ToolName => entry
+                    .projection
+                    .segments
+                    .iter()
+                    .any(|segment| segment.tool_name.as_deref() == Some(value.as_str())),
+                _ => false,
+            },
+            Self::And(all) => all.iter().all(|expr| expr.matches(entry)),
+            Self::Or(any) => any.iter().any(|expr| expr.matches(entry)),
+            Self::Not(inner) => !inner.matches(entry),
+        }
+    }
+
+    /// Texts that count towards a match, that is, not under a `NOT`.
+    fn positive_texts(&self) -> Vec<(&str, SearchScope, Option<QueryField>)> {
+        match self {
+            Self::Text {
+                text, scope, field, ..
+            } => vec![(text.as_str(), *scope, *field)],
+            Self::Value { .. } | Self::Not(_) => Vec::new(),
+            Self::And(all) | Self::Or(all) => all.iter().flat_map(Self::positive_texts).collect(),
+        }
+    }
+
+    /// A matcher for the lines to show from matching entries, and the scope
+    /// covering every segment the expression's terms search. `None` when no
+    /// term counts towards a match, as in `NOT a` or `tool_name:Bash`.
+    pub fn highlighter(
+        &self,
+        ignore_case: bool,
+    ) -> Result<Option<(ExactSearchMatcher, SearchScope)>> {
+        let texts = self.positive_texts();
+        let Some(&(_, mut scope, _)) = texts.first() else {
+            return Ok(None);
+        };
+        let pattern = texts
+            .iter()
+            .map(|(text, _, _)| regex::escape(text))
+            .collect::<Vec<_>>()
+            .join("|");
+        for &(_, term_scope, field) in &texts {
+            scope = widen_scope(scope, term_scope, field);
+        }
+        let matcher = ExactSearchMatcher::regex(&pattern, ignore_case).map_err(|error| {
+            SnatchError::InvalidArgument {
+                name: "query".to_string(),
+                reason: error.to_string(),
+            }
+        })?;
+        Ok(Some((matcher, scope)))
+    }
+}
+
+/// The segment kinds a text field names.
+const fn field_includes(field: QueryField, kind: SearchSegmentKind) -> bool {
+    match field {
+        QueryField::Content => kind.is_text(),
+        QueryField::Thinking => matches!(kind, SearchSegmentKind::Reasoning),
+        QueryField::ToolInput => matches!(kind, SearchSegmentKind::ToolInput),
+        QueryField::ToolOutput => matches!(kind, SearchSegmentKind::ToolResult),
+        _ => false,
+    }
+}
+
+const fn text_kind_includes(
+    scope: SearchScope,
+    field: Option<QueryField>,
+    kind: SearchSegmentKind,
+) -> bool {
+    match field {
+        Some(field) => field_includes(field, kind),
+        None => scope.includes(kind),
+    }
+}
+
+/// The narrowest scope covering `scope` and a term's segments.
+fn widen_scope(
+    scope: SearchScope,
+    term_scope: SearchScope,
+    field: Option<QueryField>,
+) -> SearchScope {
+    let kinds = [
+        SearchSegmentKind::UserText,
+        SearchSegmentKind::AssistantText,
+        SearchSegmentKind::SystemText,
+        SearchSegmentKind::SummaryText,
+        SearchSegmentKind::Reasoning,
+        SearchSegmentKind::ToolInput,
+        SearchSegmentKind::ToolResult,
+    ];
+    [
+        scope,
+        SearchScope::Default,
+        SearchScope::Thinking,
+        SearchScope::Tools,
+        SearchScope::All,
+    ]
+    .into_iter()
+    .find(|candidate| {
+        kinds.iter().all(|&kind| {
+            !(scope.includes(kind) || text_kind_includes(term_scope, field, kind))
+                || candidate.includes(kind)
+        })
+    })
+    .unwrap_or(SearchScope::All)
+}
+
 /// Stable result order independent of Tantivy segment layout.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub enum IndexedSearchOrder {
@@ -113,6 +318,9 @@ pub enum IndexedSearchOrder {
     Source,
     /// Score descending with source order as a deterministic tie-breaker.
     Relevance,
+    /// Newest entry first, undated entries last, with source order as a
+    /// deterministic tie-breaker.
+    Time,
 }
 
 /// One exact indexed-search request.
@@ -132,6 +340,9 @@ pub struct IndexedSearchRequest {
     pub context_lines: usize,
     /// Deterministic result ordering.
     pub order: IndexedSearchOrder,
+    /// Relevance weight of matches by the kind of segment they are in
+    /// (`[index.boosts]`).
+    pub boosts: FieldBoosts,
     /// Zero-based result offset after ordering.
     pub offset: usize,
     /// Maximum returned results.
@@ -151,6 +362,8 @@ pub struct IndexedSearchMatch {
     pub project_key: String,
     /// Project display path captured at build time.
     pub project_path: String,
+    /// Name of the Claude directory the session was read from.
+    pub source: String,
     /// Deterministic normalized entry id.
     pub entry_id: String,
     /// Normalized entry order within the source session.
@@ -181,6 +394,10 @@ pub struct IndexedSearchMatch {
     pub context_after: String,
     /// Matcher relevance score (0-100).
     pub score: u8,
+    /// `score` weighted by the segment's field boost, in hundredths; orders
+    /// relevance results.
+    #[serde(skip)]
+    pub relevance: u32,
 }
 
 /// Machine-readable snapshot and completeness statement attached to every
@@ -254,6 +471,15 @@ pub struct IndexedSearchResponse {
     pub coverage: IndexedQueryCoverage,
 }
 
+impl IndexedSearchResponse {
+    /// Offset of the next page, or `None` when this page reaches the end.
+    #[must_use]
+    pub fn next_offset(&self) -> Option<usize> {
+        let end = self.offset.saturating_add(self.returned);
+        (self.returned > 0 && end < self.total_matches).then_some(end)
+    }
+}
+
 fn normalize_values(values: &[String], label: &str) -> Result<Vec<String>> {
     let normalized: BTreeSet<_> = values.iter().cloned().collect();
     if normalized.len() != values.len() || normalized.contains("") {
@@ -333,6 +559,13 @@ fn entry_matches_filters(entry: &IndexedSearchEntry, filters: &IndexedSearchFilt
             return false;
         }
     }
+    if filters
+        .expression
+        .as_ref()
+        .is_some_and(|expression| !expression.matches(entry))
+    {
+        return false;
+    }
     if let Some(is_error) = filters.tool_error {
         if !entry.projection.segments.iter().any(|segment| {
             segment.kind == SearchSegmentKind::ToolResult && segment.tool_is_error == Some(is_error)
@@ -360,8 +593,14 @@ fn result_cmp(
     match order {
         IndexedSearchOrder::Source => source_cmp(left, right),
         IndexedSearchOrder::Relevance => right
-            .score
-            .cmp(&left.score)
+            .relevance
+            .cmp(&left.relevance)
+            .then_with(|| right.score.cmp(&left.score))
+            .then_with(|| source_cmp(left, right)),
+        // `None` sorts before `Some`, so descending order puts undated last.
+        IndexedSearchOrder::Time => right
+            .timestamp
+            .cmp(&left.timestamp)
             .then_with(|| source_cmp(left, right)),
     }
 }
@@ -416,17 +655,39 @@ fn retain_match(
     }
 }
 
+/// The `[index.boosts]` multiplier of a segment kind: visible text counts as
+/// content.
+const fn field_boost(boosts: &FieldBoosts, kind: SearchSegmentKind) -> f32 {
+    match kind {
+        SearchSegmentKind::UserText
+        | SearchSegmentKind::AssistantText
+        | SearchSegmentKind::SystemText
+        | SearchSegmentKind::SummaryText => boosts.content,
+        SearchSegmentKind::Reasoning => boosts.thinking,
+        SearchSegmentKind::ToolInput => boosts.tool_input,
+        SearchSegmentKind::ToolResult => boosts.tool_output,
+    }
+}
+
 fn make_result(
     entry: &IndexedSearchEntry,
     provider: &str,
     matched: ProjectedSearchMatch,
+    boosts: &FieldBoosts,
 ) -> IndexedSearchMatch {
+    let boost = entry
+        .projection
+        .segments
+        .get(matched.segment_index)
+        .map_or(1.0, |segment| field_boost(boosts, segment.kind));
     IndexedSearchMatch {
+        relevance: (f32::from(matched.score) * boost.max(0.0) * 100.0).round() as u32,
         provider: provider.to_string(),
         session_key: entry.session_key.clone(),
         logical_root: entry.logical_root.clone(),
         project_key: entry.project_key.clone(),
         project_path: entry.project_path.clone(),
+        source: entry.source.clone(),
         entry_id: entry.entry_id.clone(),
         entry_order: entry.entry_order,
         timestamp: entry.timestamp,
@@ -706,6 +967,7 @@ impl ProviderSearchIndex {
             && !request.filters.session_keys_are_scope_filter)
             || !logical_roots.is_empty();
         let project_keys = normalize_values(&request.filters.project_keys, "project_key")?;
+        let sources = normalize_values(&request.filters.sources, "source")?;
         let message_types = normalize_values(&request.filters.message_types, "message_type")?;
         let activities = if selected_session || request.filters.include_inherited {
             Vec::new()
@@ -725,6 +987,7 @@ impl ProviderSearchIndex {
                 && request.filters.session_keys.is_empty(),
             logical_roots,
             project_keys,
+            sources,
             message_types,
             activities,
             spawned,
@@ -783,7 +1046,7 @@ impl ProviderSearchIndex {
                 total_matches = total_matches.saturating_add(1);
                 retain_match(
                     &mut retained,
-                    make_result(&entry, &provider, matched),
+                    make_result(&entry, &provider, matched, &request.boosts),
                     request.order,
                     window,
                 );
@@ -897,6 +1160,7 @@ mod tests {
     use crate::index::provider::{
         IndexedSessionBatch, IndexedSessionManifest, ProviderIndexBuildManifest,
     };
+    use crate::index::LOCAL_SOURCE;
     use crate::provider::{ProviderId, SessionNamespace};
 
     fn key(provider: &str, native: &str) -> LogicalSessionKey {
@@ -928,6 +1192,7 @@ mod tests {
             logical_root: session.to_string(),
             project_key: format!("project:{}", session.provider),
             project_path: format!("/work/{}", session.provider),
+            source: LOCAL_SOURCE.to_string(),
             entry_id: format!(
                 "{}:global:{}:{order}:0",
                 session.provider, session.native_id
@@ -979,6 +1244,9 @@ mod tests {
                 logical_root: session.to_string(),
                 project_key: format!("project:{}", session.provider),
                 project_path: format!("/work/{}", session.provider),
+                source: entries
+                    .first()
+                    .map_or_else(|| LOCAL_SOURCE.to_string(), |entry| entry.source.clone()),
                 spawned: entries.first().is_some_and(|entry| entry.spawned),
                 revision_token: format!("revision-{generation}"),
                 metadata_fingerprint: format!("metadata-{generation}"),
@@ -1022,6 +1290,7 @@ mod tests {
             filters: IndexedSearchFilters::default(),
             context_lines: 1,
             order: IndexedSearchOrder::Source,
+            boosts: FieldBoosts::default(),
             offset: 0,
             limit: 50,
         }
@@ -1063,10 +1332,12 @@ mod tests {
         assert_eq!(first_page.matches[0].segment_index, 0);
         assert_eq!(first_page.matches[0].context_before, "😀 before");
         assert_eq!(first_page.matches[0].context_after, "世界 after");
+        assert_eq!(first_page.next_offset(), Some(1));
 
         query.offset = 1;
         let second_page = index.query(&query).unwrap();
         assert_eq!(second_page.matches[0].segment_index, 1);
+        assert_eq!(second_page.next_offset(), None);
         query.scope = SearchScope::Thinking;
         query.offset = 0;
         query.limit = 50;
@@ -1179,6 +1450,62 @@ mod tests {
         assert_eq!(unicode_fold.matches[0].entry_order, 5);
     }
 
+    #[test]
+    fn literal_accelerator_finds_literals_across_identifier_boundaries() {
+        let dir = tempdir().unwrap();
+        let index = ProviderSearchIndex::open(dir.path().join("index")).unwrap();
+        let session = key("alpha", "identifier-candidates");
+        let long_identifier = format!("{}_parseSessionId", "x".repeat(40));
+        let entries = vec![
+            entry(
+                &session,
+                0,
+                SearchSegmentKind::AssistantText,
+                "call parseSessionId()",
+            ),
+            entry(&session, 1, SearchSegmentKind::AssistantText, "unrelated"),
+            entry(
+                &session,
+                2,
+                SearchSegmentKind::AssistantText,
+                &long_identifier,
+            ),
+            entry(&session, 3, SearchSegmentKind::AssistantText, "session id"),
+        ];
+        index
+            .apply_generation(
+                &[batch(&session, "g1", entries)],
+                &[],
+                &build("g1", &["alpha"], &["alpha"], Vec::new()),
+            )
+            .unwrap();
+
+        let mut candidates = BTreeSet::new();
+        index
+            .visit_candidate_entries(
+                &IndexedEntryCandidateFilter {
+                    providers: vec!["alpha".to_string()],
+                    literal_token_contains: Some("sessionid".to_string()),
+                    literal_scope: Some(SearchScope::Default),
+                    ..IndexedEntryCandidateFilter::default()
+                },
+                |candidate| {
+                    candidates.insert(candidate.entry_order);
+                    Ok(())
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            candidates,
+            BTreeSet::from([0, 2]),
+            "the whole identifier is indexed, and one past the token limit falls back"
+        );
+
+        let mut insensitive = request("alpha", "sessionid");
+        insensitive.matcher = ExactSearchMatcher::regex("sessionid", true).unwrap();
+        assert_eq!(index.query(&insensitive).unwrap().total_matches, 2);
+    }
+
     #[test]
     fn cross_session_defaults_exclude_inherited_and_spawned_but_selected_sessions_are_complete() {
         let dir = tempdir().unwrap();
@@ -1476,6 +1803,33 @@ mod tests {
             .contains("invalid escape sequence"));
     }
 
+    #[test]
+    fn time_order_puts_newest_entries_first() {
+        let dir = tempdir().unwrap();
+        let index = ProviderSearchIndex::open(dir.path().join("index")).unwrap();
+        let session = key("alpha", "a");
+        index
+            .apply_generation(
+                &[batch(
+                    &session,
+                    "g1",
+                    vec![
+                        entry(&session, 0, SearchSegmentKind::AssistantText, "old needle"),
+                        entry(&session, 1, SearchSegmentKind::AssistantText, "new needle"),
+                    ],
+                )],
+                &[],
+                &build("g1", &["alpha"], &["alpha"], Vec::new()),
+            )
+            .unwrap();
+        let mut query = request("alpha", "needle");
+        query.order = IndexedSearchOrder::Time;
+        let result = index.query(&query).unwrap();
+        assert_eq!(result.matches[0].line, "new needle");
+        assert_eq!(result.matches[1].line, "old needle");
+        assert!(result.matches[0].timestamp > result.matches[1].timestamp);
+    }
+
     #[test]
     fn relevance_order_is_deterministic_and_exclusion_uses_the_same_line_scope() {
         let dir = tempdir().unwrap();
@@ -1522,6 +1876,126 @@ mod tests {
         assert_eq!(excluded.matches[0].session_key, second.to_string());
     }
 
+    #[test]
+    fn relevance_order_applies_field_boosts() {
+        let dir = tempdir().unwrap();
+        let index = ProviderSearchIndex::open(dir.path().join("index")).unwrap();
+        let session = key("alpha", "boosted");
+        index
+            .apply_generation(
+                &[batch(
+                    &session,
+                    "g1",
+                    vec![
+                        entry(&session, 0, SearchSegmentKind::Reasoning, "needle"),
+                        entry(&session, 1, SearchSegmentKind::ToolResult, "needle"),
+                        entry(&session, 2, SearchSegmentKind::AssistantText, "needle"),
+                    ],
+                )],
+                &[],
+                &build("g1", &["alpha"], &["alpha"], Vec::new()),
+            )
+            .unwrap();
+        let mut query = request("alpha", "needle");
+        query.scope = SearchScope::All;
+        query.order = IndexedSearchOrder::Relevance;
+        let orders = |query: &IndexedSearchRequest| {
+            index
+                .query(query)
+                .unwrap()
+                .matches
+                .iter()
+                .map(|result| result.entry_order)
+                .collect::<Vec<_>>()
+        };
+        // Content 2.0, tool output 1.0, thinking 0.5 by default.
+        assert_eq!(orders(&query), [2, 1, 0]);
+
+        query.boosts = FieldBoosts {
+            thinking: 3.0,
+            ..FieldBoosts::default()
+        };
+        assert_eq!(orders(&query), [0, 2, 1]);
+    }
+
+    #[test]
+    fn query_expressions_combine_terms_fields_and_literal_text() {
+        let dir = tempdir().unwrap();
+        let index = ProviderSearchIndex::open(dir.path().join("index")).unwrap();
+        let session = key("alpha", "dsl");
+        let mut tool = entry(&session, 3, SearchSegmentKind::ToolInput, "use std::fs;");
+        tool.projection.segments[0].tool_name = Some("Edit".to_string());
+        index
+            .apply_generation(
+                &[batch(
+                    &session,
+                    "g1",
+                    vec![
+                        entry(&session, 0, SearchSegmentKind::UserText, "flaky test again"),
+                        entry(&session, 1, SearchSegmentKind::UserText, "flaky build"),
+                        entry(
+                            &session,
+                            2,
+                            SearchSegmentKind::Reasoning,
+                            "the test is flaky",
+                        ),
+                        tool,
+                        entry(
+                            &session,
+                            4,
+                            SearchSegmentKind::AssistantText,
+                            "see https://example.com/a",
+                        ),
+                    ],
+                )],
+                &[],
+                &build("g1", &["alpha"], &["alpha"], Vec::new()),
+            )
+            .unwrap();
+        let orders = |query: &str| {
+            let expr = IndexedQueryExpr::compile(
+                &super::super::dsl::parse(query).unwrap(),
+                SearchScope::Default,
+                false,
+            )
+            .unwrap();
+            let (matcher, scope) = expr.highlighter(false).unwrap().unwrap();
+            let mut request = request("alpha", "unused");
+            request.matcher = matcher;
+            request.scope = scope;
+            request.filters.expression = Some(expr);
+            index
+                .query(&request)
+                .unwrap()
+                .matches
+                .iter()
+                .map(|result| result.entry_order)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(orders("flaky test"), [0]);
+        assert_eq!(orders("test OR build"), [0, 1]);
+        assert_eq!(orders("flaky -test"), [1]);
+        assert_eq!(orders("thinking:test"), [2]);
+        assert_eq!(orders("thinking:flaky OR build"), [1, 2]);
+        assert_eq!(orders("https://example.com"), [4]);
+        assert_eq!(orders("tool_input:std::fs"), [3]);
+        assert_eq!(orders("tool_name:Edit tool_input:std::fs"), [3]);
+        assert_eq!(
+            orders("tool_name:Bash tool_input:std::fs"),
+            Vec::<usize>::new()
+        );
+        assert_eq!(orders("session_id:dsl build"), [1]);
+
+        let tool_only = IndexedQueryExpr::compile(
+            &super::super::dsl::parse("tool_name:Edit").unwrap(),
+            SearchScope::Default,
+            false,
+        )
+        .unwrap();
+        assert!(tool_only.highlighter(false).unwrap().is_none());
+    }
+
     #[test]
     fn empty_complete_provider_and_request_bounds_are_unambiguous() {
         let dir = tempdir().unwrap();
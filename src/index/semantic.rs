@@ -0,0 +1,775 @@
+//! Semantic search over message embeddings.
+//!
+//! Compiled with the `semantic` feature. Each user and assistant message's
+//! text is embedded into a vector stored in SQLite next to a tantivy
+//! [`SearchIndex`] of the same sessions. A query is answered by ranking
+//! messages by cosine similarity and by lexical score, then merging the two
+//! rankings with reciprocal rank fusion, so an exact identifier still
+//! surfaces while paraphrased questions find related discussion.
+//!
+//! Vectors come from an [`Embedder`]. The built-in [`HashingEmbedder`] runs
+//! locally with no model download: it hashes stemmed words, word pairs, and
+//! character trigrams into a fixed-size vector, which captures shared
+//! vocabulary and morphology rather than deep meaning. For model-quality
+//! embeddings, configure `index.embedding_command` to use a
+//! [`CommandEmbedder`] that delegates to any local or hosted model.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use super::dsl::{QueryExpr, QueryField};
+use super::{SearchHit, SearchIndex};
+use crate::config::IndexConfig;
+use crate::discovery::Session;
+use crate::error::{Result, SnatchError};
+use crate::model::{ContentBlock, LogEntry, UserContent};
+
+/// Texts sent to an embedder per call.
+const EMBED_BATCH_SIZE: usize = 64;
+/// Characters of a message passed to the embedder.
+const MAX_EMBED_CHARS: usize = 4000;
+/// Characters of a message kept for display.
+const SNIPPET_CHARS: usize = 200;
+/// Version of the embeddings database layout (SQLite `user_version`).
+const SCHEMA_VERSION: i64 = 1;
+/// Reciprocal rank fusion constant; larger values flatten rank differences.
+const RRF_K: f32 = 60.0;
+
+/// Produces embedding vectors for text.
+pub trait Embedder: Send + Sync {
+    /// Stable identifier stored with the vectors. Vectors from a different
+    /// embedder are discarded and recomputed.
+    fn id(&self) -> String;
+
+    /// Embed each text. Returns one vector per input, all the same length.
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Dependency-free local embedder based on feature hashing.
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DIMENSIONS)
+    }
+}
+
+impl HashingEmbedder {
+    /// Default vector length.
+    pub const DEFAULT_DIMENSIONS: usize = 512;
+
+    /// Create an embedder producing `dimensions`-long vectors.
+    #[must_use]
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    fn add_feature(&self, vector: &mut [f32], feature: &str, weight: f32) {
+        let hash = fnv1a(feature.as_bytes());
+        let slot = (hash % self.dimensions as u64) as usize;
+        // The top bit decides the sign so unrelated collisions cancel out
+        // on average instead of accumulating.
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[slot] += sign * weight;
+    }
+
+    fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0; self.dimensions];
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| stem(&word.to_lowercase()))
+            .collect();
+
+        for word in &words {
+            self.add_feature(&mut vector, &format!("w:{word}"), 1.0);
+            let padded: Vec<char> = format!("#{word}#").chars().collect();
+            for trigram in padded.windows(3) {
+                let trigram: String = trigram.iter().collect();
+                self.add_feature(&mut vector, &format!("c:{trigram}"), 0.25);
+            }
+        }
+        for pair in words.windows(2) {
+            self.add_feature(&mut vector, &format!("b:{} {}", pair[0], pair[1]), 0.5);
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+}
+
+impl Embedder for HashingEmbedder {
+    fn id(&self) -> String {
+        format!("hashing-v1:{}", self.dimensions)
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_one(text)).collect())
+    }
+}
+
+/// 64-bit FNV-1a, stable across platforms and releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Strip a common English inflection so "fixed", "fixes", and "fixing"
+/// share a feature.
+fn stem(word: &str) -> String {
+    for suffix in ["ing", "ed", "es", "s"] {
+        if let Some(base) = word.strip_suffix(suffix) {
+            if base.chars().count() >= 3 {
+                return base.to_string();
+            }
+        }
+    }
+    word.to_string()
+}
+
+/// Scale `vector` to unit length so dot products are cosine similarities.
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in vector {
+            *x /= norm;
+        }
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Embedder that runs an external command.
+///
+/// The command receives `{"texts": ["..."]}` as JSON on stdin and must print
+/// `{"embeddings": [[0.1, ...], ...]}` with one vector per text. A small
+/// script around Ollama, llama.cpp, or a hosted embeddings API fits this.
+#[derive(Debug, Clone)]
+pub struct CommandEmbedder {
+    argv: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    texts: &'a [&'a str],
+}
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+impl CommandEmbedder {
+    /// Create an embedder running `argv` (program followed by arguments).
+    pub fn new(argv: Vec<String>) -> Result<Self> {
+        if argv.first().is_none_or(|program| program.trim().is_empty()) {
+            return Err(SnatchError::ConfigError {
+                message: "index.embedding_command must name a program".to_string(),
+            });
+        }
+        Ok(Self { argv })
+    }
+}
+
+impl Embedder for CommandEmbedder {
+    fn id(&self) -> String {
+        format!("command:{}", self.argv.join(" "))
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let program = &self.argv[0];
+        let mut child = Command::new(program)
+            .args(&self.argv[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| SnatchError::io(format!("Failed to run embedder '{program}'"), e))?;
+
+        let request = serde_json::to_vec(&EmbedRequest { texts })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(&request).map_err(|e| {
+                SnatchError::io(format!("Failed to write to embedder '{program}'"), e)
+            })?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| SnatchError::io(format!("Failed to read embedder '{program}'"), e))?;
+        if !output.status.success() {
+            return Err(SnatchError::IndexError(format!(
+                "Embedder '{program}' exited with {}",
+                output.status
+            )));
+        }
+
+        let response: EmbedResponse = serde_json::from_slice(&output.stdout)?;
+        if response.embeddings.len() != texts.len() {
+            return Err(SnatchError::IndexError(format!(
+                "Embedder '{program}' returned {} vectors for {} texts",
+                response.embeddings.len(),
+                texts.len()
+            )));
+        }
+        let mut embeddings = response.embeddings;
+        for vector in &mut embeddings {
+            normalize(vector);
+        }
+        Ok(embeddings)
+    }
+}
+
+/// The embedder selected by configuration: `index.embedding_command` if
+/// set, otherwise the built-in [`HashingEmbedder`].
+pub fn embedder_from_config(config: &IndexConfig) -> Result<Box<dyn Embedder>> {
+    Ok(match &config.embedding_command {
+        Some(argv) => Box::new(CommandEmbedder::new(argv.clone())?),
+        None => Box::new(HashingEmbedder::default()),
+    })
+}
+
+/// One semantic or hybrid search result.
+#[derive(Debug, Clone, Serialize)]
+pub struct SemanticHit {
+    /// Name of the Claude directory the session came from.
+    pub source: String,
+    /// Session ID containing the message.
+    pub session_id: String,
+    /// Project path.
+    pub project: String,
+    /// Message UUID.
+    pub uuid: String,
+    /// Timestamp of the message.
+    pub timestamp: String,
+    /// Message type (user or assistant).
+    pub message_type: String,
+    /// Start of the message text.
+    pub snippet: String,
+    /// Cosine similarity for semantic results, fused score for hybrid ones.
+    pub score: f32,
+}
+
+impl From<SearchHit> for SemanticHit {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            source: hit.source,
+            session_id: hit.session_id,
+            project: hit.project,
+            uuid: hit.uuid,
+            timestamp: hit.timestamp,
+            message_type: hit.message_type,
+            snippet: hit.content_snippet,
+            score: hit.score,
+        }
+    }
+}
+
+/// Result of [`SemanticIndex::update`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SemanticUpdateResult {
+    /// New or changed sessions that were embedded.
+    pub sessions_embedded: usize,
+    /// Sessions skipped because their file was unchanged.
+    pub sessions_unchanged: usize,
+    /// Sessions dropped because they are gone.
+    pub sessions_removed: usize,
+    /// Messages embedded.
+    pub messages_embedded: usize,
+    /// Errors encountered, as (session ID, message).
+    pub errors: Vec<(String, String)>,
+}
+
+/// Merge rankings by reciprocal rank fusion.
+///
+/// Each key scores `1 / (k + rank)` per ranking it appears in (rank from 1),
+/// which rewards agreement between rankings without comparing their raw
+/// scores. Returns keys by descending fused score.
+pub fn reciprocal_rank_fusion(rankings: &[Vec<String>], k: f32) -> Vec<(String, f32)> {
+    let mut scores: HashMap<&str, f32> = HashMap::new();
+    for ranking in rankings {
+        for (rank, key) in ranking.iter().enumerate() {
+            *scores.entry(key).or_default() += 1.0 / (k + rank as f32 + 1.0);
+        }
+    }
+    let mut fused: Vec<(String, f32)> = scores
+        .into_iter()
+        .map(|(key, score)| (key.to_string(), score))
+        .collect();
+    fused.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    fused
+}
+
+/// Text worth embedding from a user or assistant message.
+fn message_text(entry: &LogEntry) -> Option<String> {
+    let text = match entry {
+        LogEntry::User(user) => match &user.message {
+            UserContent::Simple(simple) => simple.content.clone(),
+            UserContent::Blocks(blocks) => blocks
+                .content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text(text) => Some(text.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        },
+        LogEntry::Assistant(assistant) => assistant.message.combined_text(),
+        _ => return None,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn prefix_chars(text: &str, limit: usize) -> &str {
+    text.char_indices()
+        .nth(limit)
+        .map_or(text, |(end, _)| &text[..end])
+}
+
+fn db_error(context: &'static str) -> impl Fn(rusqlite::Error) -> SnatchError {
+    move |e| SnatchError::IndexError(format!("{context}: {e}"))
+}
+
+fn vector_to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn blob_to_vector(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+/// File state of a session as stored in the `sessions` table.
+fn session_state(session: &Session) -> (i64, i64) {
+    let modified = session
+        .modified_time()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_nanos()).unwrap_or(i64::MAX));
+    (modified, session.file_size() as i64)
+}
+
+/// Message embeddings plus a lexical index of the same sessions.
+pub struct SemanticIndex {
+    conn: Connection,
+    lexical: SearchIndex,
+}
+
+impl SemanticIndex {
+    /// Directory for the semantic index that accompanies the provider index
+    /// at `index_dir`.
+    #[must_use]
+    pub fn dir_for(index_dir: &Path) -> PathBuf {
+        let name = index_dir
+            .file_name()
+            .map_or_else(|| "search-index".into(), |name| name.to_string_lossy());
+        index_dir.with_file_name(format!("{name}-semantic"))
+    }
+
+    /// Open or create a semantic index in `dir`.
+    pub fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            SnatchError::io(
+                format!("Failed to create index directory: {}", dir.display()),
+                e,
+            )
+        })?;
+        let conn = Connection::open(dir.join("embeddings.db"))
+            .map_err(db_error("Failed to open embeddings database"))?;
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(db_error("Failed to read embeddings schema version"))?;
+        if version != SCHEMA_VERSION {
+            // Embeddings are derived data; rebuild rather than migrate.
+            conn.execute_batch(
+                "DROP TABLE IF EXISTS meta;
+                 DROP TABLE IF EXISTS sessions;
+                 DROP TABLE IF EXISTS embeddings;",
+            )
+            .map_err(db_error("Failed to reset embeddings schema"))?;
+        }
+        conn.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+             CREATE TABLE IF NOT EXISTS sessions (
+                 source TEXT NOT NULL,
+                 session_id TEXT NOT NULL,
+                 modified_nanos INTEGER NOT NULL,
+                 size INTEGER NOT NULL,
+                 PRIMARY KEY (source, session_id)
+             );
+             CREATE TABLE IF NOT EXISTS embeddings (
+                 source TEXT NOT NULL,
+                 session_id TEXT NOT NULL,
+                 uuid TEXT NOT NULL,
+                 project TEXT NOT NULL,
+                 timestamp TEXT NOT NULL,
+                 message_type TEXT NOT NULL,
+                 snippet TEXT NOT NULL,
+                 vector BLOB NOT NULL,
+                 PRIMARY KEY (source, session_id, uuid)
+             );
+             PRAGMA user_version = {SCHEMA_VERSION};"
+        ))
+        .map_err(db_error("Failed to create embeddings schema"))?;
+
+        Ok(Self {
+            conn,
+            lexical: SearchIndex::open(dir.join("lexical"))?,
+        })
+    }
+
+    /// Bring both indexes up to date with sessions grouped by source name,
+    /// the complete set to search. Only new and changed sessions are
+    /// embedded; sessions not in `sources` are removed.
+    pub fn update(
+        &mut self,
+        sources: &[(&str, &[Session])],
+        embedder: &dyn Embedder,
+    ) -> Result<SemanticUpdateResult> {
+        let lexical = self.lexical.update_sources(sources)?;
+        let mut result = SemanticUpdateResult {
+            errors: lexical.errors,
+            ..SemanticUpdateResult::default()
+        };
+
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(db_error("Failed to begin transaction"))?;
+
+        // Vectors from different embedders are not comparable.
+        let embedder_id = embedder.id();
+        let stored_id: Option<String> = tx
+            .query_row("SELECT value FROM meta WHERE key = 'embedder'", [], |row| {
+                row.get(0)
+            })
+            .optional()
+            .map_err(db_error("Failed to read embeddings metadata"))?;
+        if stored_id.as_deref() != Some(embedder_id.as_str()) {
+            tx.execute_batch("DELETE FROM embeddings; DELETE FROM sessions;")
+                .map_err(db_error("Failed to reset embeddings"))?;
+            tx.execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES ('embedder', ?1)",
+                params![embedder_id],
+            )
+            .map_err(db_error("Failed to write embeddings metadata"))?;
+        }
+
+        let indexed: HashMap<(String, String), (i64, i64)> = {
+            let mut stmt = tx
+                .prepare("SELECT source, session_id, modified_nanos, size FROM sessions")
+                .map_err(db_error("Failed to read sessions"))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(((row.get(0)?, row.get(1)?), (row.get(2)?, row.get(3)?)))
+                })
+                .map_err(db_error("Failed to read sessions"))?;
+            rows.collect::<std::result::Result<_, _>>()
+                .map_err(db_error("Failed to read sessions"))?
+        };
+
+        let mut live: HashSet<(String, String)> = HashSet::new();
+        for (source, sessions) in sources {
+            for session in *sessions {
+                let session_id = session.session_id();
+                let key = (source.to_string(), session_id.to_string());
+                let state = session_state(session);
+                let unchanged = indexed.get(&key) == Some(&state);
+                live.insert(key);
+                if unchanged {
+                    result.sessions_unchanged += 1;
+                    continue;
+                }
+
+                let entries = match session.parse() {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        result.errors.push((session_id.to_string(), e.to_string()));
+                        continue;
+                    }
+                };
+                let messages: Vec<(&LogEntry, String)> = entries
+                    .iter()
+                    .filter_map(|entry| message_text(entry).map(|text| (entry, text)))
+                    .collect();
+
+                tx.execute(
+                    "DELETE FROM embeddings WHERE source = ?1 AND session_id = ?2",
+                    params![source, session_id],
+                )
+                .map_err(db_error("Failed to delete embeddings"))?;
+                for batch in messages.chunks(EMBED_BATCH_SIZE) {
+                    let texts: Vec<&str> = batch
+                        .iter()
+                        .map(|(_, text)| prefix_chars(text, MAX_EMBED_CHARS))
+                        .collect();
+                    let vectors = embedder.embed(&texts)?;
+                    for ((entry, text), vector) in batch.iter().zip(vectors) {
+                        tx.execute(
+                            "INSERT OR REPLACE INTO embeddings
+                             (source, session_id, uuid, project, timestamp, message_type,
+                              snippet, vector)
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                            params![
+                                source,
+                                session_id,
+                                entry.uuid().unwrap_or(""),
+                                session.project_path(),
+                                entry
+                                    .timestamp()
+                                    .map_or_else(String::new, |t| t.to_rfc3339()),
+                                entry.message_type(),
+                                prefix_chars(text, SNIPPET_CHARS),
+                                vector_to_blob(&vector),
+                            ],
+                        )
+                        .map_err(db_error("Failed to insert embedding"))?;
+                        result.messages_embedded += 1;
+                    }
+                }
+                tx.execute(
+                    "INSERT OR REPLACE INTO sessions (source, session_id, modified_nanos, size)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![source, session_id, state.0, state.1],
+                )
+                .map_err(db_error("Failed to record session"))?;
+                result.sessions_embedded += 1;
+            }
+        }
+
+        for (source, session_id) in indexed.keys().filter(|key| !live.contains(*key)) {
+            tx.execute(
+                "DELETE FROM embeddings WHERE source = ?1 AND session_id = ?2",
+                params![source, session_id],
+            )
+            .map_err(db_error("Failed to delete embeddings"))?;
+            tx.execute(
+                "DELETE FROM sessions WHERE source = ?1 AND session_id = ?2",
+                params![source, session_id],
+            )
+            .map_err(db_error("Failed to delete session"))?;
+            result.sessions_removed += 1;
+        }
+
+        tx.commit()
+            .map_err(db_error("Failed to commit embeddings"))?;
+        Ok(result)
+    }
+
+    /// Messages ranked by cosine similarity to `query`.
+    pub fn semantic_search(
+        &self,
+        query: &str,
+        embedder: &dyn Embedder,
+        limit: usize,
+    ) -> Result<Vec<SemanticHit>> {
+        let query_vector = embedder
+            .embed(&[query])?
+            .pop()
+            .ok_or_else(|| SnatchError::IndexError("Embedder returned no vector".to_string()))?;
+
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT source, session_id, uuid, project, timestamp, message_type, snippet,
+                        vector
+                 FROM embeddings",
+            )
+            .map_err(db_error("Failed to query embeddings"))?;
+        let rows = stmt
+            .query_map([], |row| {
+                let vector: Vec<u8> = row.get(7)?;
+                Ok(SemanticHit {
+                    source: row.get(0)?,
+                    session_id: row.get(1)?,
+                    uuid: row.get(2)?,
+                    project: row.get(3)?,
+                    timestamp: row.get(4)?,
+                    message_type: row.get(5)?,
+                    snippet: row.get(6)?,
+                    score: dot(&query_vector, &blob_to_vector(&vector)),
+                })
+            })
+            .map_err(db_error("Failed to query embeddings"))?;
+
+        let mut hits = rows
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(db_error("Failed to query embeddings"))?;
+        hits.retain(|hit| hit.score > 0.0);
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Messages ranked by fusing semantic similarity with lexical relevance.
+    ///
+    /// The lexical side matches any word of `query`, so a natural-language
+    /// question still contributes keyword hits.
+    pub fn search(
+        &self,
+        query: &str,
+        embedder: &dyn Embedder,
+        limit: usize,
+    ) -> Result<Vec<SemanticHit>> {
+        // Fuse from deeper candidate lists than the final page so a result
+        // ranked moderately by both sides is not cut off early.
+        let depth = limit.saturating_mul(4).max(limit);
+        let semantic = self.semantic_search(query, embedder, depth)?;
+
+        let words: Vec<QueryExpr> = query
+            .split_whitespace()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .map(|word| QueryExpr::Match {
+                field: Some(QueryField::Content),
+                text: word.to_string(),
+            })
+            .collect();
+        let lexical = if words.is_empty() {
+            Vec::new()
+        } else {
+            self.lexical.search_expr(&QueryExpr::Or(words), depth)?
+        };
+
+        let key = |hit_source: &str, session_id: &str, uuid: &str| {
+            format!("{hit_source}/{session_id}/{uuid}")
+        };
+        let rankings = [
+            semantic
+                .iter()
+                .map(|hit| key(&hit.source, &hit.session_id, &hit.uuid))
+                .collect(),
+            lexical
+                .iter()
+                .map(|hit| key(&hit.source, &hit.session_id, &hit.uuid))
+                .collect(),
+        ];
+        let mut hits: HashMap<String, SemanticHit> = lexical
+            .into_iter()
+            .map(|hit| {
+                (
+                    key(&hit.source, &hit.session_id, &hit.uuid),
+                    SemanticHit::from(hit),
+                )
+            })
+            .collect();
+        // Prefer the semantic row: its snippet is char-bounded.
+        for hit in semantic {
+            hits.insert(key(&hit.source, &hit.session_id, &hit.uuid), hit);
+        }
+
+        Ok(reciprocal_rank_fusion(&rankings, RRF_K)
+            .into_iter()
+            .filter_map(|(key, score)| {
+                hits.remove(&key).map(|mut hit| {
+                    hit.score = score;
+                    hit
+                })
+            })
+            .take(limit)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_hashing_embedder_relates_shared_vocabulary() {
+        let embedder = HashingEmbedder::default();
+        let vectors = embedder
+            .embed(&[
+                "how did I fix the race condition",
+                "fixed a race in the file watcher",
+                "bake the bread at 220 degrees",
+            ])
+            .unwrap();
+        let related = dot(&vectors[0], &vectors[1]);
+        let unrelated = dot(&vectors[0], &vectors[2]);
+        assert!(related > unrelated, "{related} <= {unrelated}");
+        assert!((dot(&vectors[0], &vectors[0]) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion_rewards_agreement() {
+        let fused = reciprocal_rank_fusion(
+            &[
+                vec!["a".to_string(), "b".to_string()],
+                vec!["b".to_string(), "c".to_string()],
+            ],
+            RRF_K,
+        );
+        let keys: Vec<&str> = fused.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_update_and_hybrid_search() {
+        let dir = tempdir().unwrap();
+        let session_id = "abcdef01-2345-6789-abcd-ef0123456789";
+        let jsonl_path = dir.path().join(format!("{session_id}.jsonl"));
+        let lines: Vec<String> = [
+            ("u1", "the file watcher had a race condition on startup"),
+            ("u2", "please bake bread"),
+        ]
+        .iter()
+        .map(|(uuid, text)| {
+            format!(
+                r#"{{"uuid":"{uuid}","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"{session_id}","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"{text}"}}}}"#
+            )
+        })
+        .collect();
+        std::fs::write(&jsonl_path, lines.join("\n") + "\n").unwrap();
+        let session = Session::from_path(&jsonl_path, "/home/user/proj").unwrap();
+
+        let embedder = HashingEmbedder::default();
+        let index_dir = dir.path().join("semantic");
+        let mut index = SemanticIndex::open(&index_dir).unwrap();
+        let first = index
+            .update(&[("local", std::slice::from_ref(&session))], &embedder)
+            .unwrap();
+        assert_eq!(first.messages_embedded, 2);
+        let second = index
+            .update(&[("local", std::slice::from_ref(&session))], &embedder)
+            .unwrap();
+        assert_eq!(second.sessions_unchanged, 1);
+
+        // The same session synced from another machine is kept apart.
+        let sessions = std::slice::from_ref(&session);
+        let both = index
+            .update(&[("local", sessions), ("laptop", sessions)], &embedder)
+            .unwrap();
+        assert_eq!((both.sessions_embedded, both.sessions_unchanged), (1, 1));
+
+        let hits = index
+            .semantic_search("how did I fix the race", &embedder, 10)
+            .unwrap();
+        assert_eq!(hits[0].uuid, "u1");
+        assert_eq!(hits[1].uuid, "u1");
+        assert_ne!(hits[0].source, hits[1].source);
+
+        let hybrid = index.search("race condition", &embedder, 10).unwrap();
+        assert_eq!(hybrid[0].uuid, "u1");
+
+        let removed = index.update(&[], &embedder).unwrap();
+        assert_eq!(removed.sessions_removed, 2);
+        assert!(index
+            .semantic_search("race condition", &embedder, 10)
+            .unwrap()
+            .is_empty());
+    }
+}
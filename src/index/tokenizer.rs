@@ -0,0 +1,270 @@
+//! Code- and CJK-aware tokenizers for the search indexes.
+//!
+//! The code tokenizer splits identifiers at `snake_case` underscores and
+//! `camelCase` boundaries while keeping the whole identifier as a token, so
+//! `parseSessionId` matches queries for `parse`, `session id`, and
+//! `parseSessionId` alike. The whole identifier shares the position of its
+//! first part, which keeps phrase queries across identifiers working.
+//!
+//! With CJK bigrams enabled, runs of Han, kana, and Hangul characters, which
+//! carry no spaces between words, are indexed as overlapping two-character
+//! grams, so any substring of two or more characters is searchable.
+
+use tantivy::tokenizer::{
+    LowerCaser, RemoveLongFilter, TextAnalyzer, Token, TokenStream, Tokenizer,
+};
+
+use crate::config::IndexTokenizer;
+
+/// Registered name of the code tokenizer.
+pub const CODE_TOKENIZER: &str = "snatch_code";
+
+/// Registered name of the code tokenizer with CJK bigrams.
+pub const CODE_CJK_TOKENIZER: &str = "snatch_code_cjk";
+
+/// Tantivy's built-in word tokenizer.
+const SIMPLE_TOKENIZER: &str = "default";
+
+/// Tokens longer than this many bytes are dropped, as by tantivy's default.
+const MAX_TOKEN_BYTES: usize = 40;
+
+/// Name of the registered analyzer implementing `kind`.
+#[must_use]
+pub const fn tokenizer_name(kind: IndexTokenizer) -> &'static str {
+    match kind {
+        IndexTokenizer::Simple => SIMPLE_TOKENIZER,
+        IndexTokenizer::Code => CODE_TOKENIZER,
+        IndexTokenizer::Cjk => CODE_CJK_TOKENIZER,
+    }
+}
+
+/// Register the custom analyzers with an index.
+///
+/// Both are registered whatever the configuration, since an existing index
+/// records the analyzer it was created with. The provider index always
+/// analyzes its text with [`CODE_CJK_TOKENIZER`].
+pub fn register(index: &tantivy::Index) {
+    for (name, cjk_bigrams) in [(CODE_TOKENIZER, false), (CODE_CJK_TOKENIZER, true)] {
+        index.tokenizers().register(
+            name,
+            TextAnalyzer::builder(CodeTokenizer { cjk_bigrams })
+                .filter(RemoveLongFilter::limit(MAX_TOKEN_BYTES))
+                .filter(LowerCaser)
+                .build(),
+        );
+    }
+}
+
+/// Identifier-splitting tokenizer; see the [module docs](self).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CodeTokenizer {
+    /// Index CJK runs as overlapping bigrams instead of whole runs.
+    pub cjk_bigrams: bool,
+}
+
+impl Tokenizer for CodeTokenizer {
+    type TokenStream<'a> = CodeTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        CodeTokenStream {
+            tokens: tokenize(text, self.cjk_bigrams),
+            next: 0,
+        }
+    }
+}
+
+/// Tokens of one text, produced up front.
+#[derive(Debug)]
+pub struct CodeTokenStream {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl TokenStream for CodeTokenStream {
+    fn advance(&mut self) -> bool {
+        if self.next < self.tokens.len() {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.next - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.next - 1]
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{20000}'..='\u{2FA1F}')
+}
+
+fn tokenize(text: &str, cjk_bigrams: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if cjk_bigrams && is_cjk(c) {
+            let mut run = Vec::new();
+            while let Some(&(offset, c)) = chars.peek() {
+                if !is_cjk(c) {
+                    break;
+                }
+                run.push((offset, c));
+                chars.next();
+            }
+            let end_of = |i: usize| run[i].0 + run[i].1.len_utf8();
+            if run.len() == 1 {
+                push(&mut tokens, text, start, end_of(0), position);
+                position += 1;
+            }
+            for i in 1..run.len() {
+                push(&mut tokens, text, run[i - 1].0, end_of(i), position);
+                position += 1;
+            }
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut end = start;
+            while let Some(&(offset, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') || (cjk_bigrams && is_cjk(c)) {
+                    break;
+                }
+                end = offset + c.len_utf8();
+                chars.next();
+            }
+            let parts = identifier_parts(text, start, end);
+            if parts.len() > 1 {
+                // The whole identifier without surrounding underscores.
+                let whole_start = parts[0].0;
+                let whole_end = parts[parts.len() - 1].1;
+                push(&mut tokens, text, whole_start, whole_end, position);
+            }
+            for (part_start, part_end) in parts {
+                push(&mut tokens, text, part_start, part_end, position);
+                position += 1;
+            }
+        } else {
+            chars.next();
+        }
+    }
+
+    tokens
+}
+
+fn push(tokens: &mut Vec<Token>, text: &str, from: usize, to: usize, position: usize) {
+    tokens.push(Token {
+        offset_from: from,
+        offset_to: to,
+        position,
+        text: text[from..to].to_string(),
+        position_length: 1,
+    });
+}
+
+/// Byte ranges of the words in the identifier `text[start..end]`, split at
+/// underscores and case changes.
+fn identifier_parts(text: &str, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let chars: Vec<(usize, char)> = text[start..end]
+        .char_indices()
+        .map(|(offset, c)| (start + offset, c))
+        .collect();
+    let mut parts = Vec::new();
+    let mut part_start = None;
+
+    for (i, &(offset, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if let Some(from) = part_start.take() {
+                parts.push((from, offset));
+            }
+            continue;
+        }
+        if let Some(from) = part_start {
+            let prev = chars[i - 1].1;
+            let next_is_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+            // `parseSession`, `base64Encode`, and the `S` in `HTTPServer`.
+            let boundary = c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_numeric()
+                    || (prev.is_uppercase() && next_is_lower));
+            if boundary {
+                parts.push((from, offset));
+                part_start = Some(offset);
+            }
+        } else {
+            part_start = Some(offset);
+        }
+    }
+    if let Some(from) = part_start {
+        parts.push((from, end));
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str, cjk_bigrams: bool) -> Vec<(String, usize)> {
+        tokenize(text, cjk_bigrams)
+            .into_iter()
+            .map(|token| (token.text, token.position))
+            .collect()
+    }
+
+    #[test]
+    fn test_identifiers_keep_the_whole_and_split_parts() {
+        assert_eq!(
+            texts("call parseSessionId(__init__)", false),
+            [
+                ("call".to_string(), 0),
+                ("parseSessionId".to_string(), 1),
+                ("parse".to_string(), 1),
+                ("Session".to_string(), 2),
+                ("Id".to_string(), 3),
+                ("init".to_string(), 4),
+            ]
+        );
+        let parts: Vec<_> = texts("HTTPServer snake_case base64Encode", false)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect();
+        assert_eq!(
+            parts,
+            [
+                "HTTPServer",
+                "HTTP",
+                "Server",
+                "snake_case",
+                "snake",
+                "case",
+                "base64Encode",
+                "base64",
+                "Encode"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cjk_runs_become_bigrams_only_when_enabled() {
+        assert_eq!(texts("東京都", false), [("東京都".to_string(), 0)]);
+        assert_eq!(
+            texts("東京都 x 字", true),
+            [
+                ("東京".to_string(), 0),
+                ("京都".to_string(), 1),
+                ("x".to_string(), 2),
+                ("字".to_string(), 3),
+            ]
+        );
+    }
+}
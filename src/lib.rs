@@ -126,6 +126,10 @@ pub const PROJECTS_DIR_NAME: &str = "projects";
 /// File history subdirectory name.
 pub const FILE_HISTORY_DIR_NAME: &str = "filehistory";
 
+/// Subdirectory holding conversations brought in by `snatch import`, laid
+/// out like the projects directory.
+pub const IMPORTED_DIR_NAME: &str = "snatch-imported";
+
 /// Prelude module for convenient imports.
 pub mod prelude {
 
@@ -6,12 +6,35 @@
 
 use std::process::ExitCode;
 
-use claude_snatch::cli;
+use clap::error::ErrorKind;
+use clap::Parser;
+use claude_snatch::cli::{self, Cli, OutputFormat};
+use claude_snatch::SnatchError;
 
 fn main() -> ExitCode {
-    // Run the CLI (logging is initialized by cli::run based on --log-level and --log-format)
-    match cli::run() {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            // Help and version print as usual, and so do usage errors unless
+            // JSON output was asked for.
+            let informational = matches!(
+                e.kind(),
+                ErrorKind::DisplayHelp
+                    | ErrorKind::DisplayVersion
+                    | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+            );
+            if informational || !cli::json_output_requested(std::env::args_os()) {
+                e.exit();
+            }
+            return report_json(&cli::argument_error(&e));
+        }
+    };
+    let json_errors = cli.effective_output() == OutputFormat::Json;
+
+    // Run the CLI (logging is initialized by cli::run_with based on --log-level and --log-format)
+    match cli::run_with(&cli) {
         Ok(()) => ExitCode::SUCCESS,
+        Err(e) if json_errors => report_json(&e),
         Err(e) => {
             // Print error message
             eprintln!("Error: {e}");
@@ -31,3 +54,12 @@ fn main() -> ExitCode {
         }
     }
 }
+
+/// Print an error as one JSON object on stderr, so stdout stays parseable.
+fn report_json(e: &SnatchError) -> ExitCode {
+    match serde_json::to_string(&e.report()) {
+        Ok(json) => eprintln!("{json}"),
+        Err(_) => eprintln!("Error: {e}"),
+    }
+    ExitCode::from(e.exit_code() as u8)
+}
@@ -578,6 +578,7 @@ impl SnatchServer {
         crate::provider::registry::ProviderRegistry::with_config(
             &crate::provider::registry::RegistryConfig {
                 claude_root: self.claude_dir.clone(),
+                claude_peers: Vec::new(),
                 codex_root: self.codex_dir.clone(),
                 max_file_size: self.max_file_size,
             },
@@ -654,9 +655,10 @@ impl SnatchServer {
         let order = match request.sort.as_deref().unwrap_or("source") {
             "source" => IndexedSearchOrder::Source,
             "relevance" => IndexedSearchOrder::Relevance,
+            "time" => IndexedSearchOrder::Time,
             other => {
                 return ToolOutput::error(format!(
-                    "Invalid sort '{other}'; expected source or relevance"
+                    "Invalid sort '{other}'; expected source, relevance, or time"
                 ))
             }
         };
@@ -726,6 +728,7 @@ impl SnatchServer {
             filters,
             context_lines,
             order,
+            boosts: crate::config::FieldBoosts::default(),
             offset: request.offset.unwrap_or(0),
             limit,
         }) {
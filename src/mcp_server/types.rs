@@ -680,7 +680,8 @@ pub struct SearchSessionsRequest {
     /// Provider-index route only.
     pub exclude: Option<String>,
 
-    /// Stable result order: "source" (default) or "relevance".
+    /// Stable result order: "source" (default), "relevance", or "time"
+    /// (newest first).
     /// Provider-index route only.
     pub sort: Option<String>,
 
@@ -0,0 +1,257 @@
+//! Normalization of older Claude Code log formats.
+//!
+//! The model follows the current format. Entries written by older Claude
+//! Code versions can use shapes it no longer expects: envelope fields in
+//! snake_case, `model` and `usage` beside `message` instead of inside it,
+//! and the background-task tools that v2.0.64 folded into `TaskOutput`.
+//! Such entries fail to parse or parse with those details lost.
+//!
+//! [`migrate`] detects the [`SchemaVersion`] of a single entry from its own
+//! `version` stamp and runs each adapter registered for that version over
+//! the raw JSON, before it is deserialized. Adapters only rewrite shapes
+//! they recognize and never overwrite a field that is already present, so
+//! migrating a current entry is a no-op. Unlike
+//! [`SchemaMigration`](super::schema::SchemaMigration), which stamps export
+//! output with the target version, the entry keeps its own `version`.
+//!
+//! ```rust
+//! use claude_snatch::model::migration;
+//!
+//! let line = r#"{"type":"summary","summary":"s","leaf_uuid":"u1"}"#;
+//! let (entry, migration) = migration::parse_migrated(line)?;
+//! assert_eq!(entry.message_type(), "summary");
+//! assert!(migration.applied.is_empty());
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use serde_json::{Map, Value};
+
+use super::{LogEntry, SchemaVersion};
+
+/// Envelope fields early logs wrote in snake_case, with their current names.
+const SNAKE_CASE_ENVELOPE: &[(&str, &str)] = &[
+    ("parent_uuid", "parentUuid"),
+    ("session_id", "sessionId"),
+    ("is_sidechain", "isSidechain"),
+    ("user_type", "userType"),
+    ("git_branch", "gitBranch"),
+];
+
+/// Assistant fields early logs wrote beside `message` rather than inside it.
+const TOP_LEVEL_MESSAGE_FIELDS: &[&str] = &["model", "usage", "stop_reason"];
+
+/// Tools renamed before v2.0.64, with their current name and the renamed
+/// input parameter.
+const RENAMED_TOOLS: &[(&str, &str, (&str, &str))] = &[
+    ("BashOutput", "TaskOutput", ("bash_id", "task_id")),
+    ("AgentOutputTool", "TaskOutput", ("agentId", "task_id")),
+    ("KillBash", "KillShell", ("bash_id", "shell_id")),
+];
+
+/// What [`migrate`] did to one entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Migration {
+    /// Schema version detected from the entry's `version` stamp, if any.
+    pub version: Option<SchemaVersion>,
+    /// Names of the adapters that changed the entry, in order.
+    pub applied: Vec<&'static str>,
+}
+
+impl Migration {
+    /// Whether the entry was changed.
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        !self.applied.is_empty()
+    }
+}
+
+/// A per-version rewrite of a raw entry.
+struct Adapter {
+    /// Short name, as reported in [`Migration::applied`].
+    name: &'static str,
+    /// Whether the adapter runs for entries of this version (`None` when
+    /// the entry has no `version` stamp).
+    applies: fn(Option<&SchemaVersion>) -> bool,
+    /// Rewrite the entry; returns whether anything changed.
+    apply: fn(&mut Map<String, Value>) -> bool,
+}
+
+/// Adapters in the order they run.
+const ADAPTERS: &[Adapter] = &[
+    Adapter {
+        name: "snake_case_envelope",
+        applies: is_legacy,
+        apply: rename_snake_case_envelope,
+    },
+    Adapter {
+        name: "nested_message_fields",
+        applies: is_legacy,
+        apply: nest_message_fields,
+    },
+    Adapter {
+        name: "task_output_tools",
+        applies: predates_unified_tasks,
+        apply: rename_task_tools,
+    },
+];
+
+/// The schema version of one entry, from its own `version` stamp. Entry
+/// types that carry no stamp (summaries, snapshots) yield `None`.
+#[must_use]
+pub fn entry_version(value: &Value) -> Option<SchemaVersion> {
+    value
+        .get("version")
+        .and_then(Value::as_str)
+        .map(SchemaVersion::from_version_string)
+}
+
+/// Normalize a raw entry to the current format in place.
+pub fn migrate(value: &mut Value) -> Migration {
+    let version = entry_version(value);
+    let mut migration = Migration {
+        version,
+        applied: Vec::new(),
+    };
+    let Some(object) = value.as_object_mut() else {
+        return migration;
+    };
+    for adapter in ADAPTERS {
+        if (adapter.applies)(migration.version.as_ref()) && (adapter.apply)(object) {
+            migration.applied.push(adapter.name);
+        }
+    }
+    migration
+}
+
+/// Parse one JSONL line, migrating it first.
+pub fn parse_migrated(line: &str) -> serde_json::Result<(LogEntry, Migration)> {
+    let mut value: Value = serde_json::from_str(line)?;
+    let migration = migrate(&mut value);
+    Ok((serde_json::from_value(value)?, migration))
+}
+
+fn is_legacy(version: Option<&SchemaVersion>) -> bool {
+    matches!(version, None | Some(SchemaVersion::V1Legacy))
+}
+
+fn predates_unified_tasks(version: Option<&SchemaVersion>) -> bool {
+    version.is_some_and(|version| !version.supports_feature("task_output"))
+}
+
+/// Move `from` to `to` unless `to` is already set.
+fn rename_key(object: &mut Map<String, Value>, from: &str, to: &str) -> bool {
+    if object.contains_key(to) {
+        return false;
+    }
+    match object.remove(from) {
+        Some(value) => {
+            object.insert(to.to_string(), value);
+            true
+        }
+        None => false,
+    }
+}
+
+fn rename_snake_case_envelope(entry: &mut Map<String, Value>) -> bool {
+    let mut changed = false;
+    for (from, to) in SNAKE_CASE_ENVELOPE {
+        changed |= rename_key(entry, from, to);
+    }
+    changed
+}
+
+fn nest_message_fields(entry: &mut Map<String, Value>) -> bool {
+    if entry.get("type").and_then(Value::as_str) != Some("assistant") {
+        return false;
+    }
+    let mut moved = Vec::new();
+    for key in TOP_LEVEL_MESSAGE_FIELDS {
+        let movable = entry
+            .get("message")
+            .and_then(Value::as_object)
+            .is_some_and(|message| !message.contains_key(*key));
+        if movable {
+            if let Some(value) = entry.remove(*key) {
+                moved.push((*key, value));
+            }
+        }
+    }
+    let Some(message) = entry.get_mut("message").and_then(Value::as_object_mut) else {
+        return false;
+    };
+    let changed = !moved.is_empty();
+    for (key, value) in moved {
+        message.insert(key.to_string(), value);
+    }
+    changed
+}
+
+fn rename_task_tools(entry: &mut Map<String, Value>) -> bool {
+    let Some(blocks) = entry
+        .get_mut("message")
+        .and_then(|message| message.get_mut("content"))
+        .and_then(Value::as_array_mut)
+    else {
+        return false;
+    };
+    let mut changed = false;
+    for block in blocks.iter_mut().filter_map(Value::as_object_mut) {
+        if block.get("type").and_then(Value::as_str) != Some("tool_use") {
+            continue;
+        }
+        let Some(&(_, current, (old_param, new_param))) = RENAMED_TOOLS
+            .iter()
+            .find(|(old, ..)| block.get("name").and_then(Value::as_str) == Some(*old))
+        else {
+            continue;
+        };
+        block.insert("name".to_string(), Value::from(current));
+        if let Some(input) = block.get_mut("input").and_then(Value::as_object_mut) {
+            rename_key(input, old_param, new_param);
+        }
+        changed = true;
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ContentBlock;
+
+    #[test]
+    fn test_adapters_follow_entry_version() {
+        let legacy = r#"{"type":"assistant","uuid":"a1","parent_uuid":null,"timestamp":"2025-03-01T00:00:00Z","session_id":"s","version":"1.0.17","is_sidechain":false,"model":"claude-3-7-sonnet","usage":{"input_tokens":3,"output_tokens":2},"message":{"id":"m","type":"message","role":"assistant","content":[{"type":"tool_use","id":"t","name":"BashOutput","input":{"bash_id":"b1"}}]}}"#;
+        let (entry, migration) = parse_migrated(legacy).unwrap();
+        assert_eq!(migration.version, Some(SchemaVersion::V1Legacy));
+        assert_eq!(
+            migration.applied,
+            [
+                "snake_case_envelope",
+                "nested_message_fields",
+                "task_output_tools"
+            ]
+        );
+        let LogEntry::Assistant(assistant) = entry else {
+            panic!("expected an assistant entry");
+        };
+        assert_eq!(assistant.session_id, "s");
+        assert_eq!(assistant.message.model, "claude-3-7-sonnet");
+        assert_eq!(assistant.message.usage.map(|u| u.output_tokens), Some(2));
+        let ContentBlock::ToolUse(tool) = &assistant.message.content[0] else {
+            panic!("expected a tool_use block");
+        };
+        assert_eq!(tool.name, "TaskOutput");
+        assert_eq!(tool.input["task_id"], "b1");
+
+        // The same tool name is left alone once TaskOutput exists, and a
+        // current entry is untouched.
+        let current = legacy
+            .replace("1.0.17", "2.0.74")
+            .replace("session_id", "sessionId");
+        let mut value: Value = serde_json::from_str(&current).unwrap();
+        let before = value.clone();
+        assert!(!migrate(&mut value).is_changed());
+        assert_eq!(value, before);
+    }
+}
@@ -42,13 +42,18 @@
 //! assert!(version.supports_feature("lsp"));
 //! assert!(version.supports_feature("thinking_metadata"));
 //! ```
+//!
+//! Entries in older formats are normalized by [`migration`]. Fields the
+//! model does not know yet are counted by [`UnknownFieldCensus`].
 
 pub mod content;
 pub mod message;
 pub mod metadata;
+pub mod migration;
 pub mod schema;
 mod serde_str;
 pub mod tools;
+pub mod unknown;
 pub mod usage;
 
 pub use content::*;
@@ -56,6 +61,7 @@ pub use message::*;
 pub use metadata::*;
 pub use schema::*;
 pub use tools::*;
+pub use unknown::{UnknownField, UnknownFieldCensus, UnknownFieldCount};
 pub use usage::*;
 
 use indexmap::IndexMap;
@@ -0,0 +1,310 @@
+//! Census of unknown fields across parsed entries.
+//!
+//! Model structs keep the keys they do not recognize in `extra` maps so
+//! they round-trip. [`UnknownFieldCensus`] walks those maps across any
+//! number of sessions, together with entry and content-block types the
+//! model has no variant for, and counts each key with an example value.
+//! New fields in a Claude Code release show up here before anything
+//! depends on them.
+//!
+//! ```rust
+//! use claude_snatch::model::{LogEntry, UnknownFieldCensus};
+//!
+//! let entry: LogEntry = serde_json::from_str(
+//!     r#"{"type":"summary","summary":"s","leafUuid":"u1","newField":42}"#,
+//! )?;
+//! let mut census = UnknownFieldCensus::new();
+//! census.add_session("s1", std::slice::from_ref(&entry));
+//! let fields = census.fields();
+//! assert_eq!((fields[0].scope.as_str(), fields[0].key.as_str()), ("summary", "newField"));
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{ContentBlock, LogEntry, UserContent};
+
+/// Longest example value kept, in characters.
+const EXAMPLE_CHARS: usize = 80;
+
+/// Scope under which entry types the model does not know are counted.
+pub const UNKNOWN_ENTRY_TYPE: &str = "entry_type";
+
+/// Scope under which content-block types the model does not know are counted.
+pub const UNKNOWN_BLOCK_TYPE: &str = "content_block_type";
+
+/// One unknown key found in an entry.
+#[derive(Debug, Clone, Copy)]
+pub struct UnknownField<'a> {
+    /// Where the key was found, e.g. `assistant.message.usage`.
+    pub scope: &'static str,
+    /// The key (or the type name, for unknown entry and block types).
+    pub key: &'a str,
+    /// Its value (the whole entry or block, for unknown types).
+    pub value: &'a Value,
+}
+
+impl LogEntry {
+    /// Every unknown key captured in this entry's `extra` maps, plus the
+    /// entry's own type or its content blocks' types when the model has no
+    /// variant for them.
+    #[must_use]
+    pub fn unknown_fields(&self) -> Vec<UnknownField<'_>> {
+        let mut fields = Vec::new();
+        let mut add = |scope, extra| push_extra(&mut fields, scope, extra);
+        match self {
+            Self::Assistant(m) => {
+                add("assistant", &m.extra);
+                add("assistant.message", &m.message.extra);
+                if let Some(usage) = &m.message.usage {
+                    add("assistant.message.usage", &usage.extra);
+                }
+            }
+            Self::User(m) => {
+                add("user", &m.extra);
+                match &m.message {
+                    UserContent::Simple(content) => add("user.message", &content.extra),
+                    UserContent::Blocks(content) => add("user.message", &content.extra),
+                }
+                if let Some(thinking) = &m.thinking_metadata {
+                    add("user.thinkingMetadata", &thinking.extra);
+                }
+                for todo in &m.todos {
+                    add("user.todos", &todo.extra);
+                }
+            }
+            Self::System(m) => {
+                add("system", &m.extra);
+                if let Some(compact) = &m.compact_metadata {
+                    add("system.compactMetadata", &compact.extra);
+                }
+                for hook in &m.hook_infos {
+                    add("system.hookInfos", &hook.extra);
+                }
+            }
+            Self::Summary(m) => add("summary", &m.extra),
+            Self::FileHistorySnapshot(m) => {
+                add("file-history-snapshot", &m.extra);
+                add("file-history-snapshot.snapshot", &m.snapshot.extra);
+            }
+            Self::QueueOperation(m) => add("queue-operation", &m.extra),
+            Self::TurnEnd(m) => add("turn_end", &m.extra),
+            Self::Progress(m) => add("progress", &m.extra),
+            Self::Attachment(m) => add("attachment", &m.extra),
+            Self::LastPrompt(m) => add("last-prompt", &m.extra),
+            Self::Mode(m) => add("mode", &m.extra),
+            Self::PermissionMode(m) => add("permission-mode", &m.extra),
+            Self::AiTitle(m) => add("ai-title", &m.extra),
+            Self::Unknown(raw) => fields.push(UnknownField {
+                scope: UNKNOWN_ENTRY_TYPE,
+                key: self.message_type(),
+                value: raw,
+            }),
+        }
+
+        let blocks: &[ContentBlock] = match self {
+            Self::Assistant(m) => &m.message.content,
+            Self::User(m) => match &m.message {
+                UserContent::Blocks(content) => &content.content,
+                UserContent::Simple(_) => &[],
+            },
+            _ => &[],
+        };
+        for block in blocks {
+            let (scope, extra) = match block {
+                ContentBlock::Text(b) => ("content.text", &b.extra),
+                ContentBlock::ToolUse(b) => ("content.tool_use", &b.extra),
+                ContentBlock::ToolResult(b) => ("content.tool_result", &b.extra),
+                ContentBlock::Thinking(b) => ("content.thinking", &b.extra),
+                ContentBlock::Image(b) => ("content.image", &b.extra),
+                ContentBlock::Unknown { kind, raw } => {
+                    fields.push(UnknownField {
+                        scope: UNKNOWN_BLOCK_TYPE,
+                        key: kind,
+                        value: raw,
+                    });
+                    continue;
+                }
+            };
+            push_extra(&mut fields, scope, extra);
+        }
+        fields
+    }
+}
+
+fn push_extra<'a>(
+    fields: &mut Vec<UnknownField<'a>>,
+    scope: &'static str,
+    extra: &'a IndexMap<String, Value>,
+) {
+    fields.extend(
+        extra
+            .iter()
+            .map(|(key, value)| UnknownField { scope, key, value }),
+    );
+}
+
+/// How often one unknown key was seen.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnknownFieldCount {
+    /// Where the key was found.
+    pub scope: String,
+    /// The key.
+    pub key: String,
+    /// Entries carrying it.
+    pub count: usize,
+    /// Sessions carrying it.
+    pub sessions: usize,
+    /// The first value seen, as compact JSON, shortened.
+    pub example: String,
+    /// The session the example came from.
+    pub example_session: String,
+    /// The Claude Code version of the first entry seen with the key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example_version: Option<String>,
+}
+
+/// Unknown keys aggregated across sessions.
+#[derive(Debug, Clone, Default)]
+pub struct UnknownFieldCensus {
+    sessions: usize,
+    entries: usize,
+    fields: BTreeMap<(&'static str, String), UnknownFieldCount>,
+}
+
+impl UnknownFieldCensus {
+    /// Create an empty census.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count the unknown keys of one session's entries.
+    pub fn add_session(&mut self, session_id: &str, entries: &[LogEntry]) {
+        self.sessions += 1;
+        self.entries += entries.len();
+        let mut seen = HashSet::new();
+        for entry in entries {
+            for field in entry.unknown_fields() {
+                let id = (field.scope, field.key.to_string());
+                let count = self
+                    .fields
+                    .entry(id.clone())
+                    .or_insert_with(|| UnknownFieldCount {
+                        scope: field.scope.to_string(),
+                        key: field.key.to_string(),
+                        count: 0,
+                        sessions: 0,
+                        example: example(field.value),
+                        example_session: session_id.to_string(),
+                        example_version: entry.version().map(str::to_string),
+                    });
+                count.count += 1;
+                if seen.insert(id) {
+                    count.sessions += 1;
+                }
+            }
+        }
+    }
+
+    /// Sessions added.
+    #[must_use]
+    pub const fn sessions(&self) -> usize {
+        self.sessions
+    }
+
+    /// Entries scanned.
+    #[must_use]
+    pub const fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Whether no unknown key was found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Every unknown key, most frequent first.
+    #[must_use]
+    pub fn fields(&self) -> Vec<&UnknownFieldCount> {
+        let mut fields: Vec<_> = self.fields.values().collect();
+        fields.sort_by(|a, b| b.count.cmp(&a.count));
+        fields
+    }
+}
+
+/// Compact JSON for a value, cut to [`EXAMPLE_CHARS`].
+fn example(value: &Value) -> String {
+    let text = value.to_string();
+    match text.char_indices().nth(EXAMPLE_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_census_counts_keys_across_sessions() {
+        let parse = |line: &str| serde_json::from_str::<LogEntry>(line).unwrap();
+        let assistant = parse(
+            r#"{"type":"assistant","uuid":"a1","timestamp":"2026-01-01T00:00:00Z","sessionId":"s","version":"2.1.5","newFlag":true,"message":{"id":"m","type":"message","role":"assistant","model":"x","content":[{"type":"text","text":"hi","citations":[]},{"type":"future_block","data":1}],"usage":{"input_tokens":1,"output_tokens":1,"inference_geo":"us"}}}"#,
+        );
+        let user = parse(
+            r#"{"type":"user","uuid":"u1","timestamp":"2026-01-01T00:00:00Z","sessionId":"s","version":"2.1.5","newFlag":false,"message":{"role":"user","content":"hi"}}"#,
+        );
+        let future = parse(r#"{"type":"pr-link","url":"https://example.com"}"#);
+
+        let keys: Vec<_> = assistant
+            .unknown_fields()
+            .iter()
+            .map(|f| (f.scope, f.key.to_string()))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("assistant", "newFlag".to_string()),
+                ("assistant.message.usage", "inference_geo".to_string()),
+                ("content.text", "citations".to_string()),
+                (UNKNOWN_BLOCK_TYPE, "future_block".to_string()),
+            ]
+        );
+
+        let mut census = UnknownFieldCensus::new();
+        census.add_session("s1", &[assistant.clone(), user.clone(), user]);
+        census.add_session("s2", &[assistant, future]);
+        assert_eq!((census.sessions(), census.entries()), (2, 5));
+
+        let fields = census.fields();
+        let flag = fields[0];
+        assert_eq!(
+            (flag.scope.as_str(), flag.key.as_str()),
+            ("assistant", "newFlag")
+        );
+        // Counted separately from the user's newFlag, which sorts after it.
+        assert_eq!((flag.count, flag.sessions), (2, 2));
+        assert_eq!(flag.example, "true");
+        assert_eq!(flag.example_version.as_deref(), Some("2.1.5"));
+        let user_flag = fields
+            .iter()
+            .find(|f| f.scope == "user" && f.key == "newFlag")
+            .unwrap();
+        assert_eq!((user_flag.count, user_flag.sessions), (2, 1));
+        let pr_link = fields
+            .iter()
+            .find(|f| f.scope == UNKNOWN_ENTRY_TYPE)
+            .unwrap();
+        assert_eq!(pr_link.key, "pr-link");
+        assert_eq!(pr_link.example_session, "s2");
+
+        let long = Value::from("x".repeat(200));
+        assert_eq!(example(&long).chars().count(), EXAMPLE_CHARS + 1);
+    }
+}
@@ -76,6 +76,25 @@ impl Usage {
         self.input_tokens + self.cache_creation_input_tokens.unwrap_or(0) + self.output_tokens
     }
 
+    /// Extended-thinking tokens, when the payload reports them.
+    ///
+    /// Thinking is billed as output, so this is a subset of `output_tokens`.
+    /// Claude Code logs do not always carry the split; it is read from a
+    /// top-level `thinking_tokens`/`reasoning_tokens` field or from an
+    /// `output_tokens_details` object when present.
+    #[must_use]
+    pub fn thinking_tokens(&self) -> Option<u64> {
+        const KEYS: [&str; 2] = ["thinking_tokens", "reasoning_tokens"];
+        let top_level = KEYS
+            .iter()
+            .find_map(|key| self.extra.get(*key).and_then(Value::as_u64));
+        top_level.or_else(|| {
+            let details = self.extra.get("output_tokens_details")?;
+            KEYS.iter()
+                .find_map(|key| details.get(*key).and_then(Value::as_u64))
+        })
+    }
+
     /// Calculate cache hit rate as a percentage.
     #[must_use]
     pub fn cache_hit_rate(&self) -> f64 {
@@ -307,6 +326,12 @@ pub struct CostEstimate {
     pub cache_read_cost: f64,
     /// Additional usage-based charges for server-side tools.
     pub server_tool_cost: f64,
+    /// Money saved by cache reads: cache-read tokens at the full input rate
+    /// minus what they cost at the cache-read rate.
+    pub cache_read_savings: f64,
+    /// Extra paid to write the cache: cache-write cost minus the same tokens
+    /// at the full input rate.
+    pub cache_write_premium: f64,
     /// Total cost.
     pub total_cost: f64,
     /// Currency (typically "USD").
@@ -327,6 +352,8 @@ impl CostEstimate {
         self.cache_write_breakdown_mismatch |= other.cache_write_breakdown_mismatch;
         self.cache_read_cost += other.cache_read_cost;
         self.server_tool_cost += other.server_tool_cost;
+        self.cache_read_savings += other.cache_read_savings;
+        self.cache_write_premium += other.cache_write_premium;
         self.total_cost += other.total_cost;
         if self.currency.is_empty() {
             self.currency.clone_from(&other.currency);
@@ -363,7 +390,7 @@ pub struct ModelPricing {
 
 /// One pricing bucket used to preserve effective-date and modifier context
 /// while token totals continue to aggregate by model for compatibility.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CostBucketKey {
     /// Native model identifier.
     pub model: String,
@@ -578,8 +605,12 @@ impl ModelPricing {
             (unclassified_cache_write_tokens as f64 / 1_000_000.0) * self.cache_write_per_million;
         let cache_write_cost =
             cache_write_5m_cost + cache_write_1h_cost + cache_write_unclassified_cost;
-        let cache_read_cost = (usage.cache_read_input_tokens.unwrap_or(0) as f64 / 1_000_000.0)
-            * self.cache_read_per_million;
+        let cache_read_tokens = usage.cache_read_input_tokens.unwrap_or(0) as f64 / 1_000_000.0;
+        let cache_read_cost = cache_read_tokens * self.cache_read_per_million;
+        let cache_read_savings =
+            cache_read_tokens * (self.input_per_million - self.cache_read_per_million);
+        let cache_write_premium = cache_write_cost
+            - (aggregate_cache_write as f64 / 1_000_000.0) * self.input_per_million;
         // Web search is $10 per 1,000 searches; web fetch has no additional
         // charge beyond the tokens it adds to context.
         let server_tool_cost = f64::from(usage.web_search_requests()) * 0.01;
@@ -598,6 +629,8 @@ impl ModelPricing {
             cache_write_breakdown_mismatch,
             cache_read_cost,
             server_tool_cost,
+            cache_read_savings,
+            cache_write_premium,
             total_cost,
             currency: "USD".to_string(),
         }
@@ -668,7 +701,10 @@ impl ModelPricing {
 }
 
 /// Aggregated usage statistics for a session or project.
-#[derive(Debug, Clone, Default)]
+///
+/// Only raw counts are serialized; the derived cost fields are skipped and
+/// must be recomputed with [`Self::calculate_cost`] after deserializing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AggregatedUsage {
     /// Total messages processed.
     pub message_count: usize,
@@ -678,8 +714,10 @@ pub struct AggregatedUsage {
     pub by_model: IndexMap<String, Usage>,
     /// Billing totals split by effective rate card and unmodeled modifier set.
     /// This preserves the context that `by_model` intentionally collapses.
+    #[serde(with = "crate::util::serde_pairs")]
     pub cost_buckets: IndexMap<CostBucketKey, Usage>,
     /// Calculated cost breakdown by model using `cost_buckets`.
+    #[serde(skip)]
     pub cost_by_model: IndexMap<String, CostEstimate>,
     /// Total tool invocations.
     pub tool_invocations: usize,
@@ -689,13 +727,17 @@ pub struct AggregatedUsage {
     pub error_count: usize,
     /// Estimated total cost. When `by_model` contains models with no known
     /// rate (see `unpriced_models`), this sum covers only the priced models.
+    #[serde(skip)]
     pub estimated_cost: Option<f64>,
     /// Models present in `by_model` that have no known rate, so their cost is
     /// excluded from `estimated_cost`. Non-empty means the estimate is partial.
+    #[serde(skip)]
     pub unpriced_models: Vec<String>,
     /// Stable rate-card identifiers used by the estimate.
+    #[serde(skip)]
     pub pricing_rate_cards: Vec<String>,
     /// Assumptions or unsupported modifiers which qualify the estimate.
+    #[serde(skip)]
     pub pricing_qualifications: Vec<String>,
 }
 
@@ -759,6 +801,29 @@ impl AggregatedUsage {
         self.cost_buckets.entry(bucket).or_default().merge(usage);
     }
 
+    /// Merge another aggregate's raw counts into this one.
+    ///
+    /// Derived cost fields are not merged; call [`Self::calculate_cost`]
+    /// afterwards.
+    pub fn merge(&mut self, other: &Self) {
+        self.message_count += other.message_count;
+        self.usage.merge(&other.usage);
+        for (model, usage) in &other.by_model {
+            self.by_model.entry(model.clone()).or_default().merge(usage);
+        }
+        for (bucket, usage) in &other.cost_buckets {
+            self.cost_buckets
+                .entry(bucket.clone())
+                .or_default()
+                .merge(usage);
+        }
+        self.tool_invocations += other.tool_invocations;
+        for (tool, count) in &other.tools_by_name {
+            *self.tools_by_name.entry(tool.clone()).or_insert(0) += count;
+        }
+        self.error_count += other.error_count;
+    }
+
     /// Record a tool invocation.
     pub fn record_tool(&mut self, tool_name: &str) {
         self.tool_invocations += 1;
@@ -825,6 +890,25 @@ impl AggregatedUsage {
         self.estimated_cost = if priced { Some(total) } else { None };
     }
 
+    /// Money saved by prompt caching across priced models, as
+    /// `(cache-read savings, net savings after the cache-write premium)`.
+    ///
+    /// `None` when no model is priced. Call after [`Self::calculate_cost`].
+    #[must_use]
+    pub fn cache_savings(&self) -> Option<(f64, f64)> {
+        self.estimated_cost?;
+        let (savings, premium) =
+            self.cost_by_model
+                .values()
+                .fold((0.0, 0.0), |(savings, premium), cost| {
+                    (
+                        savings + cost.cache_read_savings,
+                        premium + cost.cache_write_premium,
+                    )
+                });
+        Some((savings, savings - premium))
+    }
+
     /// Get the most used tool.
     #[must_use]
     pub fn most_used_tool(&self) -> Option<(&str, usize)> {
@@ -889,6 +973,22 @@ mod tests {
         assert!(usage.work_tokens() > usage.input_tokens + usage.output_tokens);
     }
 
+    #[test]
+    fn test_thinking_tokens_from_payload() {
+        let plain: Usage = serde_json::from_str(r#"{"output_tokens": 50}"#).unwrap();
+        assert_eq!(plain.thinking_tokens(), None);
+
+        let top_level: Usage =
+            serde_json::from_str(r#"{"output_tokens": 50, "thinking_tokens": 30}"#).unwrap();
+        assert_eq!(top_level.thinking_tokens(), Some(30));
+
+        let nested: Usage = serde_json::from_str(
+            r#"{"output_tokens": 50, "output_tokens_details": {"reasoning_tokens": 12}}"#,
+        )
+        .unwrap();
+        assert_eq!(nested.thinking_tokens(), Some(12));
+    }
+
     #[test]
     fn test_cache_hit_rate() {
         let usage = Usage {
@@ -925,6 +1025,31 @@ mod tests {
         assert!((cost.output_cost - 1.5).abs() < 0.001);
     }
 
+    #[test]
+    fn test_cache_savings() {
+        let usage = Usage {
+            input_tokens: 1_000_000,
+            cache_creation_input_tokens: Some(500_000),
+            cache_read_input_tokens: Some(2_000_000),
+            ..Default::default()
+        };
+
+        // Sonnet 4: reads save 2M * ($3 - $0.30)/M = $5.40; 5m writes cost
+        // 0.5M * ($3.75 - $3)/M = $0.375 over the input rate.
+        let cost = ModelPricing::claude_sonnet_4().calculate_cost(&usage);
+        assert!((cost.cache_read_savings - 5.4).abs() < 0.001);
+        assert!((cost.cache_write_premium - 0.375).abs() < 0.001);
+
+        let mut aggregated = AggregatedUsage::default();
+        assert_eq!(aggregated.cache_savings(), None);
+        aggregated.add_usage("claude-sonnet-4-20250514", &usage);
+        aggregated.add_usage("unknown-model", &usage);
+        aggregated.calculate_cost();
+        let (gross, net) = aggregated.cache_savings().unwrap();
+        assert!((gross - 5.4).abs() < 0.001);
+        assert!((net - 5.025).abs() < 0.001);
+    }
+
     #[test]
     fn test_usage_merge() {
         let mut usage1 = Usage {
@@ -0,0 +1,220 @@
+//! Transparent reading of compressed session files.
+//!
+//! Archived sessions can be stored as `<id>.jsonl.gz` or `<id>.jsonl.zst`
+//! next to plain `<id>.jsonl` files. Discovery lists them like any other
+//! session, and every reader that goes through this module sees the
+//! decompressed JSONL, so export, search, and stats work on them unchanged.
+//!
+//! The format is detected from the leading magic bytes, so a misnamed file
+//! still decodes; the extension is only the fallback for files too short
+//! to carry a header. Decoding needs the `compression` feature (on by
+//! default). Commands that rewrite session files in place refuse
+//! compressed ones.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::error::{Result, SnatchError};
+
+/// Largest zstd window accepted (128 MiB), bounding decoder memory.
+#[cfg(feature = "compression")]
+const ZSTD_WINDOW_LOG_MAX: u32 = 27;
+
+/// A decoded line reader over a session file.
+pub type SessionReader = Box<dyn BufRead + Send>;
+
+/// Compression applied to a session file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Plain JSONL.
+    None,
+    /// gzip (`.jsonl.gz`).
+    Gzip,
+    /// Zstandard (`.jsonl.zst`).
+    Zstd,
+}
+
+impl Compression {
+    /// Guess the compression from a file name.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("gz") => Self::Gzip,
+            Some("zst") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    /// Recognize the compression from the first bytes of a file.
+    #[must_use]
+    pub fn from_magic(header: &[u8]) -> Option<Self> {
+        if header.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::Gzip)
+        } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::Zstd)
+        } else {
+            None
+        }
+    }
+
+    /// Detect the compression of an opened file from its magic bytes,
+    /// falling back to the extension, and rewind it.
+    pub fn detect(file: &mut File, path: &Path) -> io::Result<Self> {
+        let mut header = [0u8; 4];
+        let mut len = 0;
+        while len < header.len() {
+            match file.read(&mut header[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        file.seek(SeekFrom::Start(0))?;
+        Ok(if len == header.len() {
+            Self::from_magic(&header).unwrap_or(Self::None)
+        } else {
+            Self::from_path(path)
+        })
+    }
+
+    /// Whether the file is compressed.
+    #[must_use]
+    pub const fn is_compressed(self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// Short name, for messages.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "plain",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Strip a session file suffix (`.jsonl`, `.jsonl.gz` or `.jsonl.zst`)
+/// from a file name.
+#[must_use]
+pub fn strip_session_suffix(filename: &str) -> Option<&str> {
+    let name = filename
+        .strip_suffix(".gz")
+        .or_else(|| filename.strip_suffix(".zst"))
+        .unwrap_or(filename);
+    name.strip_suffix(".jsonl")
+}
+
+/// Whether a session file is stored compressed, judged by its name.
+#[must_use]
+pub fn is_compressed_path(path: &Path) -> bool {
+    Compression::from_path(path).is_compressed()
+}
+
+/// Wrap an opened file in a reader that decodes `compression`.
+pub(super) fn decode(file: File, compression: Compression) -> io::Result<SessionReader> {
+    match compression {
+        Compression::None => Ok(Box::new(BufReader::new(file))),
+        #[cfg(feature = "compression")]
+        Compression::Gzip => Ok(Box::new(BufReader::new(
+            flate2::bufread::MultiGzDecoder::new(BufReader::new(file)),
+        ))),
+        #[cfg(feature = "compression")]
+        Compression::Zstd => {
+            let mut decoder = zstd::stream::read::Decoder::new(file)?;
+            decoder.window_log_max(ZSTD_WINDOW_LOG_MAX)?;
+            Ok(Box::new(BufReader::new(decoder)))
+        }
+        #[cfg(not(feature = "compression"))]
+        other => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "{} session files need the `compression` feature",
+                other.as_str()
+            ),
+        )),
+    }
+}
+
+/// Open a session file for line reading, decompressing it if needed.
+pub fn open_session(path: impl AsRef<Path>) -> Result<SessionReader> {
+    let path = path.as_ref();
+    let mut file = File::open(path).map_err(|e| open_error(path, e))?;
+    let compression = Compression::detect(&mut file, path).map_err(|e| open_error(path, e))?;
+    decode(file, compression).map_err(|e| open_error(path, e))
+}
+
+/// Read a whole session file, decompressed. A drop-in for [`std::fs::read`].
+pub fn read_session(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let path = path.as_ref();
+    let mut file = File::open(path)?;
+    let compression = Compression::detect(&mut file, path)?;
+    let mut content = Vec::new();
+    decode(file, compression)?.read_to_end(&mut content)?;
+    Ok(content)
+}
+
+/// Read a whole session file as text, decompressed. A drop-in for
+/// [`std::fs::read_to_string`].
+pub fn read_session_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    String::from_utf8(read_session(path)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.utf8_error()))
+}
+
+/// Map an error opening `path` onto the matching [`SnatchError`].
+pub(super) fn open_error(path: &Path, e: io::Error) -> SnatchError {
+    match e.kind() {
+        io::ErrorKind::NotFound => SnatchError::FileNotFound {
+            path: path.to_path_buf(),
+        },
+        io::ErrorKind::PermissionDenied => SnatchError::PermissionDenied {
+            path: path.to_path_buf(),
+        },
+        io::ErrorKind::Unsupported => SnatchError::Unsupported {
+            feature: format!("{}: {e}", path.display()),
+        },
+        _ => SnatchError::io(format!("Failed to open {}", path.display()), e),
+    }
+}
+
+#[cfg(all(test, feature = "compression"))]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_compressed_sessions_read_like_plain_ones() {
+        let content =
+            b"{\"type\":\"summary\",\"summary\":\"a\"}\n{\"type\":\"summary\",\"summary\":\"b\"}\n";
+        let dir = tempfile::tempdir().unwrap();
+
+        let gz = dir.path().join("s.jsonl.gz");
+        let mut encoder =
+            flate2::write::GzEncoder::new(File::create(&gz).unwrap(), flate2::Compression::fast());
+        encoder.write_all(content).unwrap();
+        encoder.finish().unwrap();
+        // Detection goes by magic bytes, not the name.
+        let zst = dir.path().join("s.jsonl");
+        std::fs::write(&zst, zstd::encode_all(&content[..], 3).unwrap()).unwrap();
+        let plain = dir.path().join("p.jsonl");
+        std::fs::write(&plain, content).unwrap();
+
+        for path in [&gz, &zst, &plain] {
+            assert_eq!(read_session(path).unwrap(), content);
+            let lines = open_session(path).unwrap().lines().count();
+            assert_eq!(lines, 2);
+        }
+        let mut file = File::open(&zst).unwrap();
+        assert_eq!(
+            Compression::detect(&mut file, &zst).unwrap(),
+            Compression::Zstd
+        );
+
+        assert_eq!(strip_session_suffix("abc.jsonl.gz"), Some("abc"));
+        assert_eq!(strip_session_suffix("abc.jsonl.zst"), Some("abc"));
+        assert_eq!(strip_session_suffix("abc.jsonl"), Some("abc"));
+        assert_eq!(strip_session_suffix("abc.gz"), None);
+    }
+}
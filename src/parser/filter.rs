@@ -0,0 +1,227 @@
+//! Filtered parsing: skip lines before deserializing them.
+//!
+//! Most of the cost of parsing a session goes into deserializing entries
+//! that a query then throws away. A [`ParseFilter`] set with
+//! [`JsonlParser::with_filter`](super::JsonlParser::with_filter) is checked
+//! twice: a string prescan of the raw line, which only rules a line out when
+//! it cannot match, and then an exact check of the parsed entry. Lines the
+//! prescan rules out are never deserialized, so a malformed line among them
+//! is not reported either.
+//!
+//! ```rust,no_run
+//! use claude_snatch::parser::{JsonlParser, ParseFilter};
+//!
+//! let filter = ParseFilter::new().with_types(["user", "assistant"]);
+//! let mut parser = JsonlParser::new().with_filter(filter);
+//! let entries = parser.parse_file("session.jsonl")?;
+//! println!(
+//!     "{} entries kept, {} lines filtered",
+//!     entries.len(),
+//!     parser.stats().lines_filtered
+//! );
+//! # Ok::<(), claude_snatch::SnatchError>(())
+//! ```
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+
+use crate::model::LogEntry;
+
+/// Which entries a parser keeps. Every condition that is set must hold.
+#[derive(Debug, Clone, Default)]
+pub struct ParseFilter {
+    /// Entry types, each with its JSON string literal (`"user"`) for the
+    /// prescan.
+    types: Option<Vec<(String, String)>>,
+    /// Earliest timestamp kept (inclusive).
+    since: Option<DateTime<Utc>>,
+    /// Latest timestamp kept (inclusive).
+    until: Option<DateTime<Utc>>,
+    /// UUIDs kept.
+    uuids: Option<HashSet<String>>,
+}
+
+impl ParseFilter {
+    /// Create a filter that keeps everything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only entries of these types (as in [`LogEntry::message_type`],
+    /// e.g. `user`, `assistant`, `system`).
+    #[must_use]
+    pub fn with_types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.types = Some(
+            types
+                .into_iter()
+                .map(|t| {
+                    let name = t.as_ref().to_string();
+                    let literal = serde_json::Value::from(name.as_str()).to_string();
+                    (name, literal)
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Keep only entries timestamped at or after `since`. Entries without a
+    /// timestamp are dropped once a time bound is set.
+    #[must_use]
+    pub fn with_since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Keep only entries timestamped at or before `until`.
+    #[must_use]
+    pub fn with_until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Keep only entries with one of these UUIDs.
+    #[must_use]
+    pub fn with_uuids<I, S>(mut self, uuids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.uuids = Some(uuids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Check a parsed entry.
+    #[must_use]
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(types) = &self.types {
+            let kind = entry.message_type();
+            if !types.iter().any(|(name, _)| name == kind) {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Some(timestamp) = entry.timestamp() else {
+                return false;
+            };
+            if !self.in_range(timestamp) {
+                return false;
+            }
+        }
+        if let Some(uuids) = &self.uuids {
+            if !entry.uuid().is_some_and(|uuid| uuids.contains(uuid)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Check a raw line without deserializing it. Returns `false` only when
+    /// no entry on the line can match; `true` may still be a false positive
+    /// (a matching value nested below the top level), which [`matches`]
+    /// settles after parsing.
+    ///
+    /// [`matches`]: Self::matches
+    #[must_use]
+    pub fn prescan(&self, line: &str) -> bool {
+        if let Some(types) = &self.types {
+            if !types
+                .iter()
+                .any(|(_, literal)| line.contains(literal.as_str()))
+            {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let mut values = string_values(line, "timestamp").peekable();
+            if values.peek().is_none() {
+                return false;
+            }
+            let possible = values.any(|value| {
+                value.is_none_or(|value| {
+                    DateTime::parse_from_rfc3339(value)
+                        .map_or(true, |ts| self.in_range(ts.with_timezone(&Utc)))
+                })
+            });
+            if !possible {
+                return false;
+            }
+        }
+        if let Some(uuids) = &self.uuids {
+            if !string_values(line, "uuid")
+                .any(|value| value.is_none_or(|value| uuids.contains(value)))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn in_range(&self, timestamp: DateTime<Utc>) -> bool {
+        self.since.is_none_or(|since| timestamp >= since)
+            && self.until.is_none_or(|until| timestamp <= until)
+    }
+}
+
+/// The string values of every `"key": "..."` pair on a line, at any depth.
+/// A value that cannot be read without unescaping yields `None`, so callers
+/// treat it as a possible match.
+fn string_values<'a>(line: &'a str, key: &str) -> impl Iterator<Item = Option<&'a str>> {
+    let needle = format!("\"{key}\"");
+    let mut from = 0;
+    std::iter::from_fn(move || loop {
+        let pos = line[from..].find(&needle)? + from;
+        from = pos + needle.len();
+        let rest = line[from..].trim_start();
+        let Some(rest) = rest.strip_prefix(':') else {
+            continue;
+        };
+        let Some(value) = rest.trim_start().strip_prefix('"') else {
+            continue;
+        };
+        let end = value.find(['"', '\\']);
+        return Some(match end {
+            Some(end) if value.as_bytes()[end] == b'"' => Some(&value[..end]),
+            _ => None,
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prescan_and_matches() {
+        let line = r#"{"type":"user","uuid":"u1","timestamp":"2025-12-23T10:00:00Z","message":{"role":"user","content":[{"type":"text","text":"hi"}]}}"#;
+        let entry: LogEntry = serde_json::from_str(
+            r#"{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T10:00:00Z","sessionId":"s","version":"2.0.74","isSidechain":false,"message":{"role":"user","content":"hi"}}"#,
+        )
+        .unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+        let users = ParseFilter::new().with_types(["user"]);
+        assert!(users.prescan(line) && users.matches(&entry));
+        let assistants = ParseFilter::new().with_types(["assistant"]);
+        assert!(!assistants.prescan(line) && !assistants.matches(&entry));
+
+        let later = ParseFilter::new().with_since(at("2025-12-24T00:00:00Z"));
+        assert!(!later.prescan(line) && !later.matches(&entry));
+        let day = ParseFilter::new()
+            .with_since(at("2025-12-23T00:00:00Z"))
+            .with_until(at("2025-12-23T23:59:59Z"));
+        assert!(day.prescan(line) && day.matches(&entry));
+        assert!(!day.prescan(r#"{"type":"summary","summary":"x"}"#));
+
+        let u2 = ParseFilter::new().with_uuids(["u2"]);
+        assert!(ParseFilter::new().with_uuids(["u1"]).prescan(line));
+        assert!(!u2.prescan(line) && !u2.prescan(r#"{"uuid" : "u1"}"#));
+        // Escaped values cannot be ruled out without unescaping.
+        assert!(u2.prescan(r#"{"uuid":"u\u0032"}"#));
+    }
+}
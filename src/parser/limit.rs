@@ -0,0 +1,177 @@
+//! Size limits on tool results during parse.
+//!
+//! A single tool result can hold megabytes of command output, and the user
+//! entry carrying it usually repeats the output in `toolUseResult`. A
+//! [`ToolResultLimit`] set with
+//! [`JsonlParser::with_tool_result_limit`](super::JsonlParser::with_tool_result_limit)
+//! cuts every string in a tool result that exceeds a byte threshold as
+//! entries are parsed: truncation keeps its start, skipping drops it
+//! entirely, and either way a marker records how much was removed. Lines are
+//! still read whole, but the parsed entries no longer hold the payloads.
+//!
+//! ```rust
+//! use claude_snatch::parser::{JsonlParser, ToolResultLimit};
+//!
+//! let mut parser = JsonlParser::new().with_tool_result_limit(ToolResultLimit::truncate(64 * 1024));
+//! let entries = parser.parse_str("")?;
+//! assert!(entries.is_empty());
+//! assert_eq!(parser.stats().tool_results_truncated, 0);
+//! # Ok::<(), claude_snatch::SnatchError>(())
+//! ```
+
+use serde_json::Value;
+
+use super::format_bytes;
+use crate::model::{ContentBlock, LogEntry, ToolResultContent, UserContent};
+
+/// A byte threshold for tool result strings, and what to do above it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolResultLimit {
+    max_bytes: usize,
+    skip: bool,
+}
+
+impl ToolResultLimit {
+    /// Keep the first `max_bytes` of each oversized string.
+    #[must_use]
+    pub const fn truncate(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            skip: false,
+        }
+    }
+
+    /// Replace each oversized string with a marker.
+    #[must_use]
+    pub const fn skip(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            skip: true,
+        }
+    }
+
+    /// The threshold, in bytes.
+    #[must_use]
+    pub const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Whether oversized strings are dropped rather than truncated.
+    #[must_use]
+    pub const fn skips(&self) -> bool {
+        self.skip
+    }
+
+    /// Cut the tool results of one entry, returning how many tool result
+    /// blocks and `toolUseResult` payloads were changed.
+    pub fn apply(&self, entry: &mut LogEntry) -> usize {
+        let LogEntry::User(user) = entry else {
+            return 0;
+        };
+        let mut cut = 0;
+        if let UserContent::Blocks(content) = &mut user.message {
+            for block in &mut content.content {
+                let ContentBlock::ToolResult(result) = block else {
+                    continue;
+                };
+                let changed = match &mut result.content {
+                    Some(ToolResultContent::String(text)) => self.cut_str(text),
+                    Some(ToolResultContent::Array(items)) => items
+                        .iter_mut()
+                        .fold(false, |changed, item| self.cut_value(item) | changed),
+                    None => false,
+                };
+                cut += usize::from(changed);
+            }
+        }
+        if let Some(result) = &mut user.tool_use_result {
+            cut += usize::from(self.cut_value(result));
+        }
+        cut
+    }
+
+    fn cut_value(&self, value: &mut Value) -> bool {
+        match value {
+            Value::String(text) => self.cut_str(text),
+            Value::Array(items) => items
+                .iter_mut()
+                .fold(false, |changed, item| self.cut_value(item) | changed),
+            Value::Object(map) => map
+                .values_mut()
+                .fold(false, |changed, item| self.cut_value(item) | changed),
+            _ => false,
+        }
+    }
+
+    fn cut_str(&self, text: &mut String) -> bool {
+        if text.len() <= self.max_bytes {
+            return false;
+        }
+        if self.skip {
+            *text = format!("[{} tool output skipped]", format_bytes(text.len() as u64));
+            return true;
+        }
+        let end = (0..=self.max_bytes)
+            .rev()
+            .find(|&i| text.is_char_boundary(i))
+            .unwrap_or(0);
+        let removed = text.len() - end;
+        text.truncate(end);
+        text.push_str(&format!(
+            "\n[… {} of tool output truncated]",
+            format_bytes(removed as u64)
+        ));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::JsonlParser;
+
+    #[test]
+    fn test_limit_cuts_tool_output() {
+        let output = "é".repeat(3000);
+        let line = format!(
+            r#"{{"uuid":"u1","parentUuid":"a1","type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"t1","content":"{output}"}},{{"type":"tool_result","tool_use_id":"t2","content":"short"}}]}},"toolUseResult":{{"stdout":"{output}","stderr":"","interrupted":false}}}}"#
+        );
+        let tool_output = |entry: &LogEntry| match entry {
+            LogEntry::User(user) => match &user.message {
+                UserContent::Blocks(content) => match &content.content[0] {
+                    ContentBlock::ToolResult(result) => match &result.content {
+                        Some(ToolResultContent::String(text)) => text.clone(),
+                        _ => String::new(),
+                    },
+                    _ => String::new(),
+                },
+                UserContent::Simple(_) => String::new(),
+            },
+            _ => String::new(),
+        };
+
+        let mut truncating =
+            JsonlParser::new().with_tool_result_limit(ToolResultLimit::truncate(1001));
+        let entries = truncating.parse_str(&line).unwrap();
+        assert_eq!(truncating.stats().tool_results_truncated, 2);
+        let text = tool_output(&entries[0]);
+        // Cut at a char boundary below the limit.
+        assert!(text.starts_with(&"é".repeat(500)));
+        assert!(text.ends_with("of tool output truncated]"));
+        let LogEntry::User(user) = &entries[0] else {
+            unreachable!();
+        };
+        let stdout = user.tool_use_result.as_ref().unwrap()["stdout"]
+            .as_str()
+            .unwrap();
+        assert!(stdout.len() < 1100);
+
+        let mut skipping = JsonlParser::new().with_tool_result_limit(ToolResultLimit::skip(1000));
+        let entries = skipping.parse_str(&line).unwrap();
+        assert_eq!(tool_output(&entries[0]), "[5.9 KB tool output skipped]");
+
+        let mut unlimited = JsonlParser::new();
+        let entries = unlimited.parse_str(&line).unwrap();
+        assert_eq!(tool_output(&entries[0]), output);
+    }
+}
@@ -5,6 +5,10 @@
 //! - Graceful error recovery for malformed lines
 //! - Schema version detection
 //! - Partial line handling for active sessions
+//! - Tail reads of the last entries ([`parse_tail`])
+//! - Random access by UUID through a cached [`OffsetIndex`]
+//! - Pluggable handlers for unknown entry types ([`UnknownEntryHandler`])
+//! - Size limits on giant tool results ([`ToolResultLimit`])
 //!
 //! # Example
 //!
@@ -27,30 +31,53 @@
 //! # Parsing Modes
 //!
 //! - **Lenient mode** (default): Skips malformed lines, logs errors
-//! - **Strict mode**: Fails on first parse error
+//! - **Strict mode**: Fails on the first malformed line or schema deviation
+//! - **Salvage mode**: Like lenient, but keeps lines that are valid JSON yet
+//!   do not fit the model as raw entries
 //!
 //! ```rust
-//! use claude_snatch::parser::JsonlParser;
+//! use claude_snatch::parser::{JsonlParser, ParseMode};
 //!
 //! // Strict mode for validation
-//! let mut strict_parser = JsonlParser::new().with_lenient(false);
+//! let mut strict_parser = JsonlParser::new().with_mode(ParseMode::Strict);
 //!
 //! // Lenient mode for robust parsing
 //! let mut lenient_parser = JsonlParser::new().with_lenient(true);
 //! ```
 
+mod compression;
+mod filter;
+mod limit;
+mod offsets;
+mod report;
+#[cfg(feature = "simd")]
+mod simd;
 mod streaming;
+mod tail;
+mod unknown;
 
 use tracing::{debug, instrument, trace, warn};
 
+pub use compression::{
+    is_compressed_path, open_session, read_session, read_session_to_string, strip_session_suffix,
+    Compression, SessionReader,
+};
+pub use filter::ParseFilter;
+pub use limit::ToolResultLimit;
+pub use offsets::{IndexedEntry, OffsetIndex};
+pub use report::{repair, ParseDiagnostic, ParseErrorKind, ParseReport, Repair};
 pub use streaming::*;
+pub use tail::parse_tail;
+pub use unknown::{UnknownEntryHandler, UnknownEntryHandlers};
 
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
 use crate::error::{Result, SnatchError};
-use crate::model::{LogEntry, SchemaVersion};
+use crate::export::{SchemaValidator, SchemaViolation};
+use crate::model::unknown::{UNKNOWN_BLOCK_TYPE, UNKNOWN_ENTRY_TYPE};
+use crate::model::{migration, LogEntry, SchemaVersion};
 
 /// Default maximum file size (unlimited).
 ///
@@ -58,15 +85,45 @@ use crate::model::{LogEntry, SchemaVersion};
 /// all their data to be included. Use `--max-file-size N` to set a limit in bytes.
 pub const DEFAULT_MAX_FILE_SIZE: u64 = 0;
 
+/// How a [`JsonlParser`] treats lines that do not fit the model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Fail on the first malformed line, and on any schema deviation: an
+    /// unknown field, entry type, or content-block type. For validation.
+    Strict,
+    /// Skip malformed lines, recording each in [`ParseStats::errors`], and
+    /// salvage complete entries from torn lines. Unknown fields and types
+    /// are preserved.
+    #[default]
+    Lenient,
+    /// As lenient, but a line that is valid JSON without fitting the model
+    /// (a known entry type missing a required field, say) is kept as
+    /// [`LogEntry::Unknown`] with its raw JSON, and still recorded in
+    /// [`ParseStats::errors`].
+    Salvage,
+}
+
 /// JSONL parser for Claude Code session logs.
 #[derive(Debug)]
 pub struct JsonlParser {
     /// Detected schema version.
     schema_version: Option<SchemaVersion>,
-    /// Whether to skip malformed lines instead of failing.
-    lenient: bool,
+    /// How lines that do not fit the model are handled.
+    mode: ParseMode,
     /// Maximum file size in bytes (0 = unlimited).
     max_file_size: u64,
+    /// Whether `parse_file` may use the SIMD fast path.
+    simd: bool,
+    /// Which entries to keep.
+    filter: Option<ParseFilter>,
+    /// Whether to normalize older formats before deserializing.
+    migrate: bool,
+    /// Whether to check each line against the entry schema.
+    validate_schema: bool,
+    /// Handlers for entry types the model does not know.
+    unknown_handlers: UnknownEntryHandlers,
+    /// Size limit on tool result strings.
+    tool_result_limit: Option<ToolResultLimit>,
     /// Statistics about parsing.
     stats: ParseStats,
 }
@@ -78,7 +135,7 @@ pub struct ParseStats {
     pub lines_processed: usize,
     /// Entries parsed from cleanly-parsed lines. Does NOT include salvaged
     /// entries, so `lines_processed == entries_parsed + lines_skipped +
-    /// empty_lines` holds; total emitted entries are
+    /// empty_lines + lines_filtered` holds; total emitted entries are
     /// `entries_parsed + entries_salvaged`.
     pub entries_parsed: usize,
     /// Malformed/skipped lines.
@@ -88,8 +145,27 @@ pub struct ParseStats {
     /// separately from `entries_parsed`; the torn line itself still counts in
     /// `lines_skipped`.
     pub entries_salvaged: usize,
+    /// Lines kept as raw [`LogEntry::Unknown`] entries in
+    /// [`ParseMode::Salvage`]. Like salvaged entries, they are counted apart
+    /// from `entries_parsed` and their lines still count in `lines_skipped`.
+    pub entries_partial: usize,
     /// Empty lines.
     pub empty_lines: usize,
+    /// Lines dropped by the parser's [`ParseFilter`], whether by the prescan
+    /// or after parsing.
+    pub lines_filtered: usize,
+    /// Parsed entries rewritten from an older format (counted within
+    /// `entries_parsed`, or dropped by the filter).
+    pub entries_migrated: usize,
+    /// Parsed entries of a type the model does not know, kept as
+    /// [`LogEntry::Unknown`] (counted within `entries_parsed`). Entries an
+    /// [`UnknownEntryHandler`] drops count as filtered instead.
+    pub entries_unknown: usize,
+    /// Tool results cut by the parser's [`ToolResultLimit`].
+    pub tool_results_truncated: usize,
+    /// Entry-schema violations, with
+    /// [`JsonlParser::with_schema_validation`].
+    pub schema_violations: Vec<SchemaViolation>,
     /// Detected schema version.
     pub schema_version: Option<SchemaVersion>,
     /// Parsing errors encountered.
@@ -103,7 +179,8 @@ impl ParseStats {
         if self.lines_processed == 0 {
             return 100.0;
         }
-        let valid = self.lines_processed - self.lines_skipped - self.empty_lines;
+        let valid =
+            self.lines_processed - self.lines_skipped - self.empty_lines - self.lines_filtered;
         if valid == 0 {
             return 0.0;
         }
@@ -131,19 +208,43 @@ impl JsonlParser {
     pub fn new() -> Self {
         Self {
             schema_version: None,
-            lenient: true,
+            mode: ParseMode::Lenient,
             max_file_size: DEFAULT_MAX_FILE_SIZE,
+            simd: false,
+            filter: None,
+            migrate: false,
+            validate_schema: false,
+            unknown_handlers: UnknownEntryHandlers::new(),
+            tool_result_limit: None,
             stats: ParseStats::default(),
         }
     }
 
     /// Set lenient mode (skip malformed lines instead of failing).
+    /// `false` selects [`ParseMode::Strict`].
     #[must_use]
     pub fn with_lenient(mut self, lenient: bool) -> Self {
-        self.lenient = lenient;
+        self.mode = if lenient {
+            ParseMode::Lenient
+        } else {
+            ParseMode::Strict
+        };
+        self
+    }
+
+    /// Set how lines that do not fit the model are handled.
+    #[must_use]
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
         self
     }
 
+    /// The parse mode.
+    #[must_use]
+    pub fn mode(&self) -> ParseMode {
+        self.mode
+    }
+
     /// Set maximum file size in bytes (0 = unlimited).
     ///
     /// Prevents memory exhaustion from malicious or corrupted large files.
@@ -154,6 +255,69 @@ impl JsonlParser {
         self
     }
 
+    /// Let `parse_file` use the SIMD fast path for bulk scans.
+    ///
+    /// With the `simd` feature, lenient parses of files memory-map the file
+    /// and tokenize each line with simd-json; lines simd-json rejects are
+    /// retried with serde_json, so the same entries come out. Strict parses
+    /// always use serde_json so error positions stay exact. Without the
+    /// feature this setting has no effect.
+    #[must_use]
+    pub fn with_simd(mut self, simd: bool) -> Self {
+        self.simd = simd;
+        self
+    }
+
+    /// Keep only entries matching `filter`. Lines the filter's prescan rules
+    /// out are skipped without being deserialized.
+    #[must_use]
+    pub fn with_filter(mut self, filter: ParseFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Normalize entries written in older Claude Code formats into the
+    /// current model (see [`migration`](crate::model::migration)). Off by
+    /// default, since migrated entries no longer round-trip byte for byte.
+    /// Migrating parses go through serde_json even with
+    /// [`with_simd`](Self::with_simd).
+    #[must_use]
+    pub fn with_migration(mut self, migrate: bool) -> Self {
+        self.migrate = migrate;
+        self
+    }
+
+    /// Check every line against the entry schema
+    /// ([`entry_schema`](crate::export::entry_schema)) before deserializing
+    /// it, collecting per-field violations in
+    /// [`ParseStats::schema_violations`]. Violations do not stop an entry
+    /// from being parsed; lines are checked as written, before any
+    /// migration. Validating parses go through serde_json even with
+    /// [`with_simd`](Self::with_simd).
+    #[must_use]
+    pub fn with_schema_validation(mut self, validate: bool) -> Self {
+        self.validate_schema = validate;
+        self
+    }
+
+    /// Pass parsed entries of the handler's type to `handler`, which may
+    /// keep, replace or drop them. A strict parser accepts entry types that
+    /// have a handler.
+    #[must_use]
+    pub fn with_unknown_handler(mut self, handler: impl UnknownEntryHandler + 'static) -> Self {
+        self.unknown_handlers.register(handler);
+        self
+    }
+
+    /// Cut tool result strings over the limit's threshold as entries are
+    /// parsed, keeping sessions with megabytes of command output small in
+    /// memory.
+    #[must_use]
+    pub fn with_tool_result_limit(mut self, limit: ToolResultLimit) -> Self {
+        self.tool_result_limit = Some(limit);
+        self
+    }
+
     /// Get parse statistics.
     #[must_use]
     pub fn stats(&self) -> &ParseStats {
@@ -170,21 +334,67 @@ impl JsonlParser {
     #[instrument(skip(self), fields(path = %path.as_ref().display()))]
     pub fn parse_file(&mut self, path: impl AsRef<Path>) -> Result<Vec<LogEntry>> {
         let path = path.as_ref();
+        let mut file = self.open_file(path)?;
+        let compression =
+            Compression::detect(&mut file, path).map_err(|e| compression::open_error(path, e))?;
+        if compression.is_compressed() {
+            let reader = compression::decode(file, compression)
+                .map_err(|e| compression::open_error(path, e))?;
+            return self.parse_reader(reader);
+        }
+        #[cfg(feature = "simd")]
+        if self.simd && self.mode != ParseMode::Strict && !self.migrate && !self.validate_schema {
+            return self.parse_mapped(&file, path);
+        }
+        let reader = BufReader::new(file);
+        self.parse_reader(reader)
+    }
+
+    /// Iterate over the entries of a JSONL file, reading and deserializing
+    /// one line at a time instead of collecting a `Vec`.
+    ///
+    /// Memory use stays constant in the file size, so analytics and export
+    /// pipelines can fold over large sessions. The size limit applies. A
+    /// strict parser fails on malformed lines but not on schema deviations;
+    /// otherwise skipped lines are available from
+    /// [`LogEntryIterator::errors`] and salvage mode keeps nothing extra.
+    /// Parse statistics are not updated.
+    ///
+    /// ```rust,no_run
+    /// use claude_snatch::parser::JsonlParser;
+    ///
+    /// let parser = JsonlParser::new();
+    /// let mut tokens = 0;
+    /// for entry in parser.iter_entries("session.jsonl")? {
+    ///     if let Some(usage) = entry?.usage() {
+    ///         tokens += usage.total_tokens();
+    ///     }
+    /// }
+    /// # Ok::<(), claude_snatch::SnatchError>(())
+    /// ```
+    pub fn iter_entries(&self, path: impl AsRef<Path>) -> Result<LogEntryIterator<SessionReader>> {
+        let path = path.as_ref();
+        let mut file = self.open_file(path)?;
+        let reader = Compression::detect(&mut file, path)
+            .and_then(|compression| compression::decode(file, compression))
+            .map_err(|e| compression::open_error(path, e))?;
+        let mut iter = LogEntryIterator::new(reader, self.mode != ParseMode::Strict)
+            .with_unknown_handlers(self.unknown_handlers.clone());
+        if let Some(filter) = &self.filter {
+            iter = iter.with_filter(filter.clone());
+        }
+        if let Some(limit) = self.tool_result_limit {
+            iter = iter.with_tool_result_limit(limit);
+        }
+        Ok(iter)
+    }
+
+    /// Open a file for parsing, enforcing the size limit. For compressed
+    /// files the limit applies to the size on disk.
+    fn open_file(&self, path: &Path) -> Result<File> {
         debug!("Opening file for parsing");
 
-        let file = File::open(path).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                SnatchError::FileNotFound {
-                    path: path.to_path_buf(),
-                }
-            } else if e.kind() == std::io::ErrorKind::PermissionDenied {
-                SnatchError::PermissionDenied {
-                    path: path.to_path_buf(),
-                }
-            } else {
-                SnatchError::io(format!("Failed to open {}", path.display()), e)
-            }
-        })?;
+        let file = File::open(path).map_err(|e| compression::open_error(path, e))?;
 
         // Check file size limit to prevent memory exhaustion
         if self.max_file_size > 0 {
@@ -213,8 +423,7 @@ impl JsonlParser {
             }
         }
 
-        let reader = BufReader::new(file);
-        self.parse_reader(reader)
+        Ok(file)
     }
 
     /// Parse JSONL from a reader.
@@ -230,7 +439,7 @@ impl JsonlParser {
             let line = match line_result {
                 Ok(l) => l,
                 Err(e) => {
-                    if self.lenient {
+                    if self.mode != ParseMode::Strict {
                         self.stats.lines_skipped += 1;
                         self.stats.errors.push(ParseError {
                             line: line_num,
@@ -253,55 +462,14 @@ impl JsonlParser {
                 self.stats.empty_lines += 1;
                 continue;
             }
+            if !self.prescan(trimmed) {
+                continue;
+            }
 
             // Parse the JSON line
             match self.parse_line(trimmed, line_num) {
-                Ok(entry) => {
-                    // Detect schema version from first entry with version field
-                    if self.schema_version.is_none() {
-                        if let Some(version) = entry.version() {
-                            self.schema_version = Some(SchemaVersion::from_version_string(version));
-                            self.stats.schema_version = self.schema_version.clone();
-                            debug!(version, "Detected schema version");
-                        }
-                    }
-
-                    self.stats.entries_parsed += 1;
-                    entries.push(entry);
-                }
-                Err(e) => {
-                    if self.lenient {
-                        // Interrupted writes can fuse a truncated entry with
-                        // complete trailing entries on one line; salvage the
-                        // complete tail instead of dropping it with the line.
-                        let salvaged = salvage_torn_line(trimmed);
-                        let salvaged_count = salvaged.len();
-                        for entry in salvaged {
-                            // Counted in entries_salvaged only — the line still
-                            // counts as skipped, keeping the line-accounting
-                            // invariant (processed == parsed + skipped + empty).
-                            self.stats.entries_salvaged += 1;
-                            entries.push(entry);
-                        }
-                        self.stats.lines_skipped += 1;
-                        let message = if salvaged_count > 0 {
-                            format!(
-                                "{e} (salvaged {salvaged_count} complete trailing {} from torn line)",
-                                if salvaged_count == 1 { "entry" } else { "entries" }
-                            )
-                        } else {
-                            e.to_string()
-                        };
-                        self.stats.errors.push(ParseError {
-                            line: line_num,
-                            message,
-                            raw_line: trimmed.to_string(),
-                        });
-                        trace!(line = line_num, error = %e, salvaged = salvaged_count, "Parse error, skipping line");
-                        continue;
-                    }
-                    return Err(e);
-                }
+                Ok(entry) => self.accept(entry, &mut entries),
+                Err(e) => self.reject(trimmed, line_num, e, &mut entries)?,
             }
         }
 
@@ -314,10 +482,150 @@ impl JsonlParser {
         Ok(entries)
     }
 
-    /// Parse a single JSON line.
-    fn parse_line(&self, line: &str, line_num: usize) -> Result<LogEntry> {
-        serde_json::from_str(line)
-            .map_err(|e| SnatchError::parse_with_source(line_num, e.to_string(), e))
+    /// Record a parsed entry, detecting the schema version from the first
+    /// entry that carries one.
+    fn accept(&mut self, mut entry: LogEntry, entries: &mut Vec<LogEntry>) {
+        if self.schema_version.is_none() {
+            if let Some(version) = entry.version() {
+                self.schema_version = Some(SchemaVersion::from_version_string(version));
+                self.stats.schema_version = self.schema_version.clone();
+                debug!(version, "Detected schema version");
+            }
+        }
+
+        let Some(mut entry) = self.unknown_handlers.handle(entry) else {
+            self.stats.lines_filtered += 1;
+            return;
+        };
+        if self.filter.as_ref().is_some_and(|f| !f.matches(&entry)) {
+            self.stats.lines_filtered += 1;
+            return;
+        }
+        if let Some(limit) = &self.tool_result_limit {
+            self.stats.tool_results_truncated += limit.apply(&mut entry);
+        }
+        if matches!(entry, LogEntry::Unknown(_)) {
+            self.stats.entries_unknown += 1;
+        }
+        self.stats.entries_parsed += 1;
+        entries.push(entry);
+    }
+
+    /// Run the filter's prescan on a line, counting it as filtered when it
+    /// is ruled out.
+    fn prescan(&mut self, line: &str) -> bool {
+        if self.filter.as_ref().is_some_and(|f| !f.prescan(line)) {
+            self.stats.lines_filtered += 1;
+            return false;
+        }
+        true
+    }
+
+    /// Handle a line that failed to parse: in lenient mode skip it, keeping
+    /// any entries salvaged from it; in salvage mode keep a JSON object that
+    /// does not fit the model as a raw entry; in strict mode return the
+    /// error.
+    fn reject(
+        &mut self,
+        trimmed: &str,
+        line_num: usize,
+        e: SnatchError,
+        entries: &mut Vec<LogEntry>,
+    ) -> Result<()> {
+        if self.mode == ParseMode::Strict {
+            return Err(e);
+        }
+        if self.mode == ParseMode::Salvage {
+            if let Ok(raw @ serde_json::Value::Object(_)) = serde_json::from_str(trimmed) {
+                let entry = LogEntry::Unknown(raw);
+                if self.filter.as_ref().is_none_or(|f| f.matches(&entry)) {
+                    self.stats.entries_partial += 1;
+                    entries.push(entry);
+                }
+                self.stats.lines_skipped += 1;
+                self.stats.errors.push(ParseError {
+                    line: line_num,
+                    message: format!("{e} (kept as a raw entry)"),
+                    raw_line: trimmed.to_string(),
+                });
+                return Ok(());
+            }
+        }
+        // Interrupted writes can fuse a truncated entry with complete trailing
+        // entries on one line; salvage the complete tail instead of dropping
+        // it with the line.
+        let salvaged = salvage_torn_line(trimmed);
+        let salvaged_count = salvaged.len();
+        for entry in salvaged {
+            if self.filter.as_ref().is_some_and(|f| !f.matches(&entry)) {
+                continue;
+            }
+            // Counted in entries_salvaged only — the line still counts as
+            // skipped, keeping the line-accounting invariant
+            // (processed == parsed + skipped + empty + filtered).
+            self.stats.entries_salvaged += 1;
+            entries.push(entry);
+        }
+        self.stats.lines_skipped += 1;
+        let message = if salvaged_count > 0 {
+            format!(
+                "{e} (salvaged {salvaged_count} complete trailing {} from torn line)",
+                if salvaged_count == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                }
+            )
+        } else {
+            e.to_string()
+        };
+        self.stats.errors.push(ParseError {
+            line: line_num,
+            message,
+            raw_line: trimmed.to_string(),
+        });
+        trace!(line = line_num, error = %e, salvaged = salvaged_count, "Parse error, skipping line");
+        Ok(())
+    }
+
+    /// Parse a single JSON line, validating and migrating it first if
+    /// enabled.
+    fn parse_line(&mut self, line: &str, line_num: usize) -> Result<LogEntry> {
+        let parsed = if self.migrate || self.validate_schema {
+            serde_json::from_str::<serde_json::Value>(line).and_then(|mut value| {
+                if self.validate_schema {
+                    self.stats
+                        .schema_violations
+                        .extend(SchemaValidator::new().entry_violations(line_num, &value));
+                }
+                if self.migrate && migration::migrate(&mut value).is_changed() {
+                    self.stats.entries_migrated += 1;
+                }
+                serde_json::from_value(value)
+            })
+        } else {
+            serde_json::from_str(line)
+        };
+        let entry =
+            parsed.map_err(|e| SnatchError::parse_with_source(line_num, e.to_string(), e))?;
+        if self.mode == ParseMode::Strict {
+            let unhandled = entry.unknown_fields().into_iter().find(|field| {
+                field.scope != UNKNOWN_ENTRY_TYPE || !self.unknown_handlers.handles(field.key)
+            });
+            if let Some(field) = unhandled {
+                return Err(SnatchError::parse(
+                    line_num,
+                    match field.scope {
+                        UNKNOWN_ENTRY_TYPE => format!("unknown entry type `{}`", field.key),
+                        UNKNOWN_BLOCK_TYPE => {
+                            format!("unknown content block type `{}`", field.key)
+                        }
+                        scope => format!("unknown field `{}` in {scope}", field.key),
+                    },
+                ));
+            }
+        }
+        Ok(entry)
     }
 
     /// Parse JSONL from a string.
@@ -339,14 +647,37 @@ impl JsonlParser {
 /// rest of the line parses as a clean sequence of entries, so a partial or
 /// garbage parse is never admitted.
 pub(crate) fn salvage_torn_line(line: &str) -> Vec<LogEntry> {
+    let Some(salvage) = salvage_line(line) else {
+        return Vec::new();
+    };
+    salvage
+        .entries
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+/// Complete entries recovered from a line that does not parse as one entry.
+pub(crate) struct Salvage {
+    /// Byte offset of the first complete entry. Zero means the line is
+    /// nothing but entries concatenated without newlines; anything later
+    /// means a truncated entry precedes them.
+    pub offset: usize,
+    /// Each recovered entry with its byte range in the line.
+    pub entries: Vec<(std::ops::Range<usize>, LogEntry)>,
+}
+
+/// Find the complete entries in a broken line; see [`salvage_torn_line`].
+pub(crate) fn salvage_line(line: &str) -> Option<Salvage> {
     // Entry objects start with one of these keys. Inside a JSON string these
     // bytes can't occur unescaped (quotes would be `\"`), so a raw match is
     // either the fused entry we want or part of the already-lost prefix.
     const ENTRY_STARTS: [&str; 3] = [r#"{"parentUuid""#, r#"{"type""#, r#"{"uuid""#];
 
-    let mut candidates: Vec<usize> = Vec::new();
+    // Offset 0 failed to parse as a single entry, but may still be several
+    // complete entries written without newlines between them.
+    let mut candidates: Vec<usize> = vec![0];
     for pattern in ENTRY_STARTS {
-        // Offset 0 already failed to parse as a whole; start past it.
         let mut from = 1;
         while let Some(pos) = line.get(from..).and_then(|s| s.find(pattern)) {
             candidates.push(from + pos);
@@ -359,23 +690,25 @@ pub(crate) fn salvage_torn_line(line: &str) -> Vec<LogEntry> {
     for offset in candidates {
         let tail = &line[offset..];
         let mut stream = serde_json::Deserializer::from_str(tail).into_iter::<LogEntry>();
-        let mut parsed = Vec::new();
+        let mut entries = Vec::new();
         let mut clean = true;
-        for item in &mut stream {
-            match item {
-                Ok(entry) => parsed.push(entry),
-                Err(_) => {
-                    clean = false;
-                    break;
-                }
-            }
+        let mut start = 0;
+        while let Some(item) = stream.next() {
+            let Ok(entry) = item else {
+                clean = false;
+                break;
+            };
+            let end = stream.byte_offset();
+            let object = tail[start..end].trim_start();
+            entries.push((offset + end - object.len()..offset + end, entry));
+            start = end;
         }
         // Require full consumption: the tail must be nothing but valid entries.
-        if clean && !parsed.is_empty() && tail[stream.byte_offset()..].trim().is_empty() {
-            return parsed;
+        if clean && !entries.is_empty() && tail[stream.byte_offset()..].trim().is_empty() {
+            return Some(Salvage { offset, entries });
         }
     }
-    Vec::new()
+    None
 }
 
 impl Default for JsonlParser {
@@ -425,11 +758,22 @@ impl RawLogEntry {
 }
 
 /// Iterator over log entries from a reader.
+///
+/// Yields the same entries as [`JsonlParser::parse_reader`], including
+/// entries salvaged from torn lines in lenient mode.
 pub struct LogEntryIterator<R: BufRead> {
     reader: std::io::Lines<R>,
     line_num: usize,
     lenient: bool,
     errors: Vec<ParseError>,
+    /// Entries salvaged from a torn line, not yet yielded.
+    salvaged: std::collections::VecDeque<LogEntry>,
+    /// Which entries to yield.
+    filter: Option<ParseFilter>,
+    /// Handlers for entry types the model does not know.
+    unknown_handlers: UnknownEntryHandlers,
+    /// Size limit on tool result strings.
+    tool_result_limit: Option<ToolResultLimit>,
 }
 
 impl<R: BufRead> LogEntryIterator<R> {
@@ -440,9 +784,34 @@ impl<R: BufRead> LogEntryIterator<R> {
             line_num: 0,
             lenient,
             errors: Vec::new(),
+            salvaged: std::collections::VecDeque::new(),
+            filter: None,
+            unknown_handlers: UnknownEntryHandlers::new(),
+            tool_result_limit: None,
         }
     }
 
+    /// Yield only entries matching `filter`.
+    #[must_use]
+    pub fn with_filter(mut self, filter: ParseFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Pass entries of unknown types to `handlers` before filtering.
+    #[must_use]
+    pub fn with_unknown_handlers(mut self, handlers: UnknownEntryHandlers) -> Self {
+        self.unknown_handlers = handlers;
+        self
+    }
+
+    /// Cut tool result strings over the limit's threshold.
+    #[must_use]
+    pub fn with_tool_result_limit(mut self, limit: ToolResultLimit) -> Self {
+        self.tool_result_limit = Some(limit);
+        self
+    }
+
     /// Get parsing errors encountered so far.
     #[must_use]
     pub fn errors(&self) -> &[ParseError] {
@@ -461,6 +830,9 @@ impl<R: BufRead> Iterator for LogEntryIterator<R> {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
+            if let Some(entry) = self.salvaged.pop_front() {
+                return Some(Ok(entry));
+            }
             let line_result = self.reader.next()?;
             self.line_num += 1;
 
@@ -486,14 +858,40 @@ impl<R: BufRead> Iterator for LogEntryIterator<R> {
             if trimmed.is_empty() {
                 continue;
             }
+            if self.filter.as_ref().is_some_and(|f| !f.prescan(trimmed)) {
+                continue;
+            }
 
             match serde_json::from_str::<LogEntry>(trimmed) {
-                Ok(entry) => return Some(Ok(entry)),
+                Ok(entry) => {
+                    let Some(mut entry) = self.unknown_handlers.handle(entry) else {
+                        continue;
+                    };
+                    if self.filter.as_ref().is_none_or(|f| f.matches(&entry)) {
+                        if let Some(limit) = &self.tool_result_limit {
+                            limit.apply(&mut entry);
+                        }
+                        return Some(Ok(entry));
+                    }
+                }
                 Err(e) => {
                     if self.lenient {
+                        let filter = self.filter.as_ref();
+                        self.salvaged.extend(
+                            salvage_torn_line(trimmed)
+                                .into_iter()
+                                .filter(|entry| filter.is_none_or(|f| f.matches(entry))),
+                        );
+                        let message = match self.salvaged.len() {
+                            0 => e.to_string(),
+                            1 => format!("{e} (salvaged 1 complete trailing entry from torn line)"),
+                            n => format!(
+                                "{e} (salvaged {n} complete trailing entries from torn line)"
+                            ),
+                        };
                         self.errors.push(ParseError {
                             line: self.line_num,
-                            message: e.to_string(),
+                            message,
                             raw_line: trimmed.to_string(),
                         });
                         continue;
@@ -525,6 +923,37 @@ impl LogEntryIteratorExt for File {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_iter_entries_matches_parse_file() {
+        let user = r#"{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s","version":"2.0.74","isSidechain":false,"message":{"role":"user","content":"hello"}}"#;
+        let fused = user.replace("u1", "u2");
+        let content = format!("{user}\n\nnot json\n{{\"type\":\"user\",\"uuid\":\"torn{fused}\n");
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, content.as_bytes()).unwrap();
+
+        let mut parser = JsonlParser::new();
+        let parsed = parser.parse_file(file.path()).unwrap();
+        let mut iter = parser.iter_entries(file.path()).unwrap();
+        let streamed: Vec<LogEntry> = iter.by_ref().collect::<Result<_>>().unwrap();
+        let uuids = |entries: &[LogEntry]| -> Vec<String> {
+            entries
+                .iter()
+                .filter_map(|e| e.uuid().map(String::from))
+                .collect()
+        };
+        assert_eq!(uuids(&streamed), ["u1", "u2"]);
+        assert_eq!(uuids(&streamed), uuids(&parsed));
+        assert_eq!(iter.errors().len(), 2);
+
+        let strict = JsonlParser::new().with_lenient(false);
+        let results: Vec<_> = strict.iter_entries(file.path()).unwrap().collect();
+        assert!(results[0].is_ok() && results[1].is_err());
+        assert!(JsonlParser::new()
+            .with_max_file_size(10)
+            .iter_entries(file.path())
+            .is_err());
+    }
+
     #[test]
     fn test_parse_empty() {
         let mut parser = JsonlParser::new();
@@ -642,4 +1071,54 @@ bad
         assert_eq!(parser.stats().lines_skipped, 1);
         assert_eq!(parser.stats().entries_parsed, 2);
     }
+
+    #[test]
+    fn test_parse_modes() {
+        let user = r#"{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s1","version":"2.0.74","isSidechain":false,"message":{"role":"user","content":"a"}}"#;
+        let extra = user.replace("\"isSidechain\"", "\"newField\":1,\"isSidechain\"");
+        // A user entry missing its required message.
+        let partial = r#"{"uuid":"u2","type":"user","timestamp":"2025-12-23T00:00:01Z","sessionId":"s1","version":"2.0.74"}"#;
+        let content = format!("{user}\n{partial}\nbad\n");
+
+        let mut lenient = JsonlParser::new();
+        assert_eq!(lenient.parse_str(&content).unwrap().len(), 1);
+        assert_eq!(lenient.stats().lines_skipped, 2);
+
+        let mut salvage = JsonlParser::new().with_mode(ParseMode::Salvage);
+        let entries = salvage.parse_str(&content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(&entries[1], LogEntry::Unknown(raw) if raw["uuid"] == "u2"));
+        let stats = salvage.stats();
+        assert_eq!((stats.entries_parsed, stats.entries_partial), (1, 1));
+        assert_eq!(stats.lines_skipped, 2);
+        assert!(stats.errors[0].message.contains("kept as a raw entry"));
+
+        let mut strict = JsonlParser::new().with_mode(ParseMode::Strict);
+        assert_eq!(strict.parse_str(user).unwrap().len(), 1);
+        let err = strict.parse_str(&extra).unwrap_err().to_string();
+        assert!(err.contains("unknown field `newField` in user"), "{err}");
+        assert!(strict.parse_str(&content).is_err());
+        assert!(JsonlParser::new().parse_str(&extra).is_ok());
+    }
+
+    #[test]
+    fn test_schema_validation_reports_fields() {
+        let user = r#"{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s1","version":"2.0.74","isSidechain":false,"message":{"role":"user","content":"a"}}"#;
+        let bad = user
+            .replace("\"u1\"", "\"u2\"")
+            .replace(r#""role":"user""#, r#""role":"narrator""#);
+        let content = format!("{user}\n{bad}\n");
+
+        let mut parser = JsonlParser::new().with_schema_validation(true);
+        assert_eq!(parser.parse_str(&content).unwrap().len(), 2);
+        let violations = &parser.stats().schema_violations;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, 2);
+        assert_eq!(violations[0].uuid.as_deref(), Some("u2"));
+        assert_eq!(violations[0].path, "/message/role");
+
+        let mut plain = JsonlParser::new();
+        plain.parse_str(&content).unwrap();
+        assert!(plain.stats().schema_violations.is_empty());
+    }
 }
@@ -0,0 +1,440 @@
+//! Byte-offset index for random access to session entries.
+//!
+//! Looking up one message in a multi-hundred-megabyte session should not
+//! mean parsing all of it. An [`OffsetIndex`] maps each entry UUID to the
+//! byte offset of its line, along with its parent UUID so a thread can be
+//! walked without reading the entries in between. Entries are then read by
+//! seeking straight to their lines.
+//!
+//! [`OffsetIndex::open`] keeps the index as a sidecar file under
+//! `offsets/` in the cache directory. The sidecar records the session's
+//! size and modification time: an unchanged session reuses it, a session
+//! that only grew (the usual case for an active one) has just the new lines
+//! indexed, and any other change rebuilds it. A read that lands on a line
+//! with a different UUID returns nothing rather than the wrong entry.
+//! Compressed sessions cannot be seeked and are not indexed.
+//!
+//! ```rust,no_run
+//! use claude_snatch::parser::OffsetIndex;
+//!
+//! let index = OffsetIndex::open("session.jsonl", None)?;
+//! if let Some(entry) = index.read_entry("6b9d0f0e-51c3-4c5b-9d0e-2f1c4b0a7e11")? {
+//!     println!("{}", entry.message_type());
+//! }
+//! # Ok::<(), claude_snatch::SnatchError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::compression::open_error;
+use super::Compression;
+use crate::error::{Result, SnatchError};
+use crate::model::LogEntry;
+use crate::util::atomic_write;
+
+/// Format version of sidecar files.
+const CURRENT_VERSION: u32 = 1;
+
+/// Directory of sidecar files within the cache directory.
+const SIDECAR_DIR: &str = "offsets";
+
+/// One indexed entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedEntry {
+    /// Entry UUID.
+    pub uuid: String,
+    /// Parent UUID, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Byte offset of the entry's line.
+    pub offset: u64,
+}
+
+/// The fields of a line the index needs.
+#[derive(Deserialize)]
+struct Head {
+    uuid: Option<String>,
+    #[serde(rename = "parentUuid")]
+    parent_uuid: Option<String>,
+}
+
+/// On-disk layout of a sidecar.
+#[derive(Serialize, Deserialize)]
+struct Persisted {
+    version: u32,
+    path: PathBuf,
+    size: u64,
+    mtime_nanos: u64,
+    indexed_len: u64,
+    entries: Vec<IndexedEntry>,
+}
+
+/// UUID → byte offset index of one session file.
+#[derive(Debug, Clone)]
+pub struct OffsetIndex {
+    path: PathBuf,
+    size: u64,
+    mtime_nanos: u64,
+    /// End of the last complete line indexed.
+    indexed_len: u64,
+    /// Entries in file order.
+    entries: Vec<IndexedEntry>,
+    by_uuid: HashMap<String, usize>,
+    children: HashMap<String, Vec<usize>>,
+}
+
+impl OffsetIndex {
+    /// Index a session file from scratch.
+    pub fn build(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut index = Self {
+            path: path.to_path_buf(),
+            size: 0,
+            mtime_nanos: 0,
+            indexed_len: 0,
+            entries: Vec::new(),
+            by_uuid: HashMap::new(),
+            children: HashMap::new(),
+        };
+        index.extend()?;
+        Ok(index)
+    }
+
+    /// Load the index of a session from its sidecar in `cache_dir`,
+    /// bringing it up to date and saving it back. Without a cache
+    /// directory the index is built in memory.
+    pub fn open(path: impl AsRef<Path>, cache_dir: Option<&Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let Some(cache_dir) = cache_dir else {
+            return Self::build(path);
+        };
+        let sidecar = Self::sidecar_path(cache_dir, path);
+        let (size, mtime_nanos) = file_state(path)?;
+        let mut index = match Self::load(&sidecar, path) {
+            Some(index) if index.size == size && index.mtime_nanos == mtime_nanos => {
+                return Ok(index);
+            }
+            Some(mut index) if index.size < size => {
+                index.extend()?;
+                index
+            }
+            _ => Self::build(path)?,
+        };
+        index.save(&sidecar)?;
+        Ok(index)
+    }
+
+    /// Where the sidecar of a session lives in `cache_dir`.
+    #[must_use]
+    pub fn sidecar_path(cache_dir: &Path, path: &Path) -> PathBuf {
+        let project = path.parent().and_then(Path::file_name).unwrap_or_default();
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".json");
+        cache_dir.join(SIDECAR_DIR).join(project).join(name)
+    }
+
+    /// Delete the sidecar of a session, returning whether there was one.
+    pub fn remove_sidecar(cache_dir: &Path, path: &Path) -> io::Result<bool> {
+        match fs::remove_file(Self::sidecar_path(cache_dir, path)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The indexed session file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of indexed entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no entry is indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Indexed entries, in file order.
+    #[must_use]
+    pub fn entries(&self) -> &[IndexedEntry] {
+        &self.entries
+    }
+
+    /// Byte offset of an entry's line.
+    #[must_use]
+    pub fn offset(&self, uuid: &str) -> Option<u64> {
+        self.get(uuid).map(|entry| entry.offset)
+    }
+
+    /// Parent UUID of an entry.
+    #[must_use]
+    pub fn parent(&self, uuid: &str) -> Option<&str> {
+        self.get(uuid)?.parent.as_deref()
+    }
+
+    /// UUIDs of an entry's children, in file order.
+    #[must_use]
+    pub fn children(&self, uuid: &str) -> Vec<&str> {
+        self.children.get(uuid).map_or_else(Vec::new, |children| {
+            children
+                .iter()
+                .map(|&i| self.entries[i].uuid.as_str())
+                .collect()
+        })
+    }
+
+    /// UUIDs starting with `prefix`, in file order.
+    pub fn matching_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.uuid.as_str())
+            .filter(move |uuid| uuid.starts_with(prefix))
+    }
+
+    /// Read one entry by seeking to its line. Returns `None` when the UUID
+    /// is not indexed or the line no longer holds it.
+    pub fn read_entry(&self, uuid: &str) -> Result<Option<LogEntry>> {
+        let Some(offset) = self.offset(uuid) else {
+            return Ok(None);
+        };
+        let mut reader = self.reader()?;
+        self.read_at(&mut reader, offset, uuid)
+    }
+
+    /// Read the given entries, in file order. UUIDs that are not indexed
+    /// or whose line no longer holds them are left out.
+    pub fn read_entries<'a>(
+        &self,
+        uuids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<LogEntry>> {
+        let mut wanted: Vec<(u64, &str)> = uuids
+            .into_iter()
+            .filter_map(|uuid| Some((self.offset(uuid)?, uuid)))
+            .collect();
+        wanted.sort_unstable();
+        wanted.dedup();
+        let mut reader = self.reader()?;
+        let mut entries = Vec::with_capacity(wanted.len());
+        for (offset, uuid) in wanted {
+            entries.extend(self.read_at(&mut reader, offset, uuid)?);
+        }
+        Ok(entries)
+    }
+
+    fn get(&self, uuid: &str) -> Option<&IndexedEntry> {
+        self.by_uuid.get(uuid).map(|&i| &self.entries[i])
+    }
+
+    fn reader(&self) -> Result<BufReader<File>> {
+        File::open(&self.path)
+            .map(BufReader::new)
+            .map_err(|e| open_error(&self.path, e))
+    }
+
+    fn read_at(
+        &self,
+        reader: &mut BufReader<File>,
+        offset: u64,
+        uuid: &str,
+    ) -> Result<Option<LogEntry>> {
+        let mut line = Vec::new();
+        reader
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| reader.read_until(b'\n', &mut line))
+            .map_err(|e| self.read_error(e))?;
+        Ok(serde_json::from_slice::<LogEntry>(&line)
+            .ok()
+            .filter(|entry| entry.uuid() == Some(uuid)))
+    }
+
+    /// Index the lines added since `indexed_len`.
+    fn extend(&mut self) -> Result<()> {
+        let mut file = File::open(&self.path).map_err(|e| open_error(&self.path, e))?;
+        let compression =
+            Compression::detect(&mut file, &self.path).map_err(|e| open_error(&self.path, e))?;
+        if compression.is_compressed() {
+            return Err(SnatchError::Unsupported {
+                feature: format!(
+                    "offset index of a {} compressed session ({})",
+                    compression.as_str(),
+                    self.path.display()
+                ),
+            });
+        }
+        let (size, mtime_nanos) = file_state(&self.path)?;
+        let mut reader = BufReader::new(file);
+        reader
+            .seek(SeekFrom::Start(self.indexed_len))
+            .map_err(|e| self.read_error(e))?;
+
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let read = reader
+                .read_until(b'\n', &mut line)
+                .map_err(|e| self.read_error(e))?;
+            // A line without its newline may still be being written.
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            let offset = self.indexed_len;
+            self.indexed_len += read as u64;
+            let Ok(Head {
+                uuid: Some(uuid),
+                parent_uuid,
+            }) = serde_json::from_slice::<Head>(&line)
+            else {
+                continue;
+            };
+            self.insert(IndexedEntry {
+                uuid,
+                parent: parent_uuid,
+                offset,
+            });
+        }
+        self.size = size;
+        self.mtime_nanos = mtime_nanos;
+        Ok(())
+    }
+
+    /// Add an entry; a repeated UUID keeps its first occurrence.
+    fn insert(&mut self, entry: IndexedEntry) {
+        if self.by_uuid.contains_key(&entry.uuid) {
+            return;
+        }
+        let i = self.entries.len();
+        self.by_uuid.insert(entry.uuid.clone(), i);
+        if let Some(parent) = &entry.parent {
+            self.children.entry(parent.clone()).or_default().push(i);
+        }
+        self.entries.push(entry);
+    }
+
+    /// Load a sidecar, if it exists, is current, and describes `path`.
+    fn load(sidecar: &Path, path: &Path) -> Option<Self> {
+        let content = fs::read(sidecar).ok()?;
+        let persisted: Persisted = serde_json::from_slice(&content).ok()?;
+        if persisted.version != CURRENT_VERSION || persisted.path != path {
+            return None;
+        }
+        let mut index = Self {
+            path: persisted.path,
+            size: persisted.size,
+            mtime_nanos: persisted.mtime_nanos,
+            indexed_len: persisted.indexed_len,
+            entries: Vec::with_capacity(persisted.entries.len()),
+            by_uuid: HashMap::with_capacity(persisted.entries.len()),
+            children: HashMap::new(),
+        };
+        for entry in persisted.entries {
+            index.insert(entry);
+        }
+        Some(index)
+    }
+
+    fn save(&self, sidecar: &Path) -> Result<()> {
+        let persisted = Persisted {
+            version: CURRENT_VERSION,
+            path: self.path.clone(),
+            size: self.size,
+            mtime_nanos: self.mtime_nanos,
+            indexed_len: self.indexed_len,
+            entries: self.entries.clone(),
+        };
+        atomic_write(sidecar, &serde_json::to_vec(&persisted)?)
+    }
+
+    fn read_error(&self, e: io::Error) -> SnatchError {
+        SnatchError::io(format!("Failed to read {}", self.path.display()), e)
+    }
+}
+
+/// Size and modification time (nanoseconds since the Unix epoch) of a file.
+fn file_state(path: &Path) -> Result<(u64, u64)> {
+    let metadata = fs::metadata(path).map_err(|e| open_error(path, e))?;
+    let mtime_nanos = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(0, |d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX));
+    Ok((metadata.len(), mtime_nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn line(uuid: &str, parent: Option<&str>) -> String {
+        let parent = parent.map_or("null".to_string(), |p| format!("\"{p}\""));
+        format!(
+            r#"{{"uuid":"{uuid}","parentUuid":{parent},"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"{uuid}"}}}}"#
+        ) + "\n"
+    }
+
+    #[test]
+    fn test_index_seeks_and_follows_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        let path = dir.path().join("project").join("s.jsonl");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut file = File::create(&path).unwrap();
+        write!(
+            file,
+            "{}not json\n{}",
+            line("a", None),
+            line("b", Some("a"))
+        )
+        .unwrap();
+        // A partial line is left for a later pass.
+        write!(file, r#"{{"uuid":"c","#).unwrap();
+
+        let index = OffsetIndex::open(&path, Some(&cache)).unwrap();
+        assert!(OffsetIndex::sidecar_path(&cache, &path).exists());
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.children("a"), ["b"]);
+        assert_eq!(index.parent("b"), Some("a"));
+        let entry = index.read_entry("b").unwrap().unwrap();
+        assert_eq!(entry.uuid(), Some("b"));
+
+        // Finish the partial line and append another; only the new lines
+        // are indexed, from the saved sidecar.
+        let rest = line("c", Some("b"));
+        write!(
+            file,
+            "{}{}",
+            &rest[r#"{"uuid":"c","#.len()..],
+            line("d", Some("b"))
+        )
+        .unwrap();
+        drop(file);
+        let index = OffsetIndex::open(&path, Some(&cache)).unwrap();
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.children("b"), ["c", "d"]);
+        assert_eq!(index.matching_prefix("c").collect::<Vec<_>>(), ["c"]);
+        let uuids: Vec<_> = index
+            .read_entries(["d", "a", "missing"])
+            .unwrap()
+            .iter()
+            .filter_map(|e| e.uuid().map(str::to_string))
+            .collect();
+        assert_eq!(uuids, ["a", "d"]);
+
+        // A rewrite that moves lines is detected on read.
+        fs::write(&path, line("b", Some("a")) + &line("a", None)).unwrap();
+        assert!(index.read_entry("b").unwrap().is_none());
+        assert!(OffsetIndex::remove_sidecar(&cache, &path).unwrap());
+    }
+}
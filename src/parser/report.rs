@@ -0,0 +1,321 @@
+//! Structured parse diagnostics and line repair.
+//!
+//! The lenient parser skips lines it cannot read and keeps a message for
+//! each. [`ParseReport`] describes those lines for whoever has to fix the
+//! file: where the failure is, what kind of damage it is, the text around
+//! it, and how many entries can be recovered from the line.
+//!
+//! [`repair`] rewrites the recoverable lines. Entries concatenated on one
+//! line are split onto lines of their own, and the truncated prefix of a
+//! torn line is dropped so the complete entries after it remain. Every other
+//! line, including damaged lines with nothing to recover, keeps its bytes.
+
+use std::ops::Range;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::error::Category;
+
+use super::salvage_line;
+use crate::error::{Result, SnatchError};
+use crate::model::LogEntry;
+
+/// Bytes of context kept on each side of a failure in a snippet.
+const SNIPPET_CONTEXT: usize = 40;
+
+/// What is wrong with a line that failed to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseErrorKind {
+    /// The line is not valid UTF-8.
+    Encoding,
+    /// The line ends partway through an entry, as after an interrupted write.
+    Truncated,
+    /// Complete entries written without newlines between them.
+    Concatenated,
+    /// A truncated entry with complete entries fused after it.
+    Torn,
+    /// Malformed JSON.
+    Syntax,
+    /// Valid JSON that is not a valid log entry.
+    Schema,
+}
+
+impl ParseErrorKind {
+    /// Short name, as in JSON output.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Encoding => "encoding",
+            Self::Truncated => "truncated",
+            Self::Concatenated => "concatenated",
+            Self::Torn => "torn",
+            Self::Syntax => "syntax",
+            Self::Schema => "schema",
+        }
+    }
+
+    /// Whether [`repair`] recovers entries from lines of this kind.
+    #[must_use]
+    pub const fn is_repairable(self) -> bool {
+        matches!(self, Self::Concatenated | Self::Torn)
+    }
+}
+
+/// One line that failed to parse.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParseDiagnostic {
+    /// Line number (1-indexed).
+    pub line: usize,
+    /// Byte offset of the failure in the file.
+    pub byte_offset: u64,
+    /// Kind of damage.
+    pub kind: ParseErrorKind,
+    /// The parser's error message.
+    pub message: String,
+    /// Text around the failure.
+    pub snippet: String,
+    /// Complete entries recoverable from the line.
+    pub recoverable: usize,
+}
+
+/// Diagnostics for the lines of a JSONL file that failed to parse.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParseReport {
+    /// Lines read.
+    pub lines: usize,
+    /// Entries parsed from intact lines.
+    pub entries: usize,
+    /// Lines that failed to parse, in file order.
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl ParseReport {
+    /// Build a report for JSONL content.
+    #[must_use]
+    pub fn from_bytes(content: &[u8]) -> Self {
+        let mut report = Self::default();
+        let mut line_start = 0u64;
+        for raw in content.split_inclusive(|&b| b == b'\n') {
+            report.lines += 1;
+            match classify(raw) {
+                Line::Blank => {}
+                Line::Entry => report.entries += 1,
+                Line::Broken(mut diagnostic, _) => {
+                    diagnostic.line = report.lines;
+                    diagnostic.byte_offset += line_start;
+                    report.diagnostics.push(diagnostic);
+                }
+            }
+            line_start += raw.len() as u64;
+        }
+        report
+    }
+
+    /// Build a report for a JSONL file, decompressing it if needed.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = super::read_session(path)
+            .map_err(|e| SnatchError::io(format!("Failed to read {}", path.display()), e))?;
+        Ok(Self::from_bytes(&content))
+    }
+
+    /// Whether every non-blank line parsed.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Lines [`repair`] would rewrite.
+    pub fn repairable(&self) -> impl Iterator<Item = &ParseDiagnostic> {
+        self.diagnostics.iter().filter(|d| d.kind.is_repairable())
+    }
+}
+
+/// The result of [`repair`].
+#[derive(Debug, Clone, Default)]
+pub struct Repair {
+    /// The repaired content.
+    pub content: Vec<u8>,
+    /// Lines rewritten.
+    pub lines: usize,
+    /// Entries recovered from the rewritten lines.
+    pub entries: usize,
+}
+
+/// Rewrite concatenated and torn lines so each recovered entry is on a line
+/// of its own. All other lines keep their exact bytes.
+#[must_use]
+pub fn repair(content: &[u8]) -> Repair {
+    let mut result = Repair {
+        content: Vec::with_capacity(content.len()),
+        ..Repair::default()
+    };
+    for raw in content.split_inclusive(|&b| b == b'\n') {
+        let recovered = match classify(raw) {
+            Line::Broken(_, ranges) if !ranges.is_empty() => ranges,
+            _ => {
+                result.content.extend_from_slice(raw);
+                continue;
+            }
+        };
+        // Recovered ranges only exist for UTF-8 lines.
+        let text = String::from_utf8_lossy(raw);
+        let body = text.trim();
+        let separator = if raw.ends_with(b"\r\n") { "\r\n" } else { "\n" };
+        let lines: Vec<&str> = recovered.iter().map(|range| &body[range.clone()]).collect();
+        result
+            .content
+            .extend_from_slice(lines.join(separator).as_bytes());
+        result
+            .content
+            .extend_from_slice(&raw[text.trim_end().len()..]);
+        result.lines += 1;
+        result.entries += recovered.len();
+    }
+    result
+}
+
+/// One classified line.
+enum Line {
+    Blank,
+    Entry,
+    /// A line that failed to parse, with the byte ranges of the entries
+    /// recoverable from its trimmed text.
+    Broken(ParseDiagnostic, Vec<Range<usize>>),
+}
+
+/// Classify a raw line (including its line ending). Diagnostic offsets are
+/// relative to the line; the line number is left for the caller.
+fn classify(raw: &[u8]) -> Line {
+    let text = match std::str::from_utf8(raw) {
+        Ok(text) => text,
+        Err(e) => {
+            let at = e.valid_up_to();
+            let text = String::from_utf8_lossy(raw);
+            return Line::Broken(
+                ParseDiagnostic {
+                    line: 0,
+                    byte_offset: at as u64,
+                    kind: ParseErrorKind::Encoding,
+                    message: e.to_string(),
+                    snippet: snippet(&text, at.min(text.len())),
+                    recoverable: 0,
+                },
+                Vec::new(),
+            );
+        }
+    };
+    let body = text.trim();
+    if body.is_empty() {
+        return Line::Blank;
+    }
+    let lead = text.len() - text.trim_start().len();
+
+    let Err(e) = serde_json::from_str::<LogEntry>(body) else {
+        return Line::Entry;
+    };
+    // Errors raised while mapping a parsed object onto an entry type carry
+    // no position.
+    let at = floor_char_boundary(body, e.column().saturating_sub(1).min(body.len()));
+    let salvage = salvage_line(body);
+    let kind = match &salvage {
+        Some(salvage) if salvage.offset == 0 => ParseErrorKind::Concatenated,
+        Some(_) => ParseErrorKind::Torn,
+        None => match e.classify() {
+            Category::Eof => ParseErrorKind::Truncated,
+            Category::Data => ParseErrorKind::Schema,
+            Category::Syntax | Category::Io => ParseErrorKind::Syntax,
+        },
+    };
+    let ranges: Vec<Range<usize>> = salvage
+        .map(|salvage| {
+            salvage
+                .entries
+                .into_iter()
+                .map(|(range, _)| range)
+                .collect()
+        })
+        .unwrap_or_default();
+    Line::Broken(
+        ParseDiagnostic {
+            line: 0,
+            byte_offset: (lead + at) as u64,
+            kind,
+            message: e.to_string(),
+            snippet: snippet(body, at),
+            recoverable: ranges.len(),
+        },
+        ranges,
+    )
+}
+
+/// The text around byte `at`, marking cut ends with `…`.
+fn snippet(text: &str, at: usize) -> String {
+    let start = floor_char_boundary(text, at.saturating_sub(SNIPPET_CONTEXT));
+    let mut end = (at + SNIPPET_CONTEXT).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        &text[start..end],
+        if end < text.len() { "…" } else { "" }
+    )
+}
+
+fn floor_char_boundary(text: &str, mut at: usize) -> usize {
+    while !text.is_char_boundary(at) {
+        at -= 1;
+    }
+    at
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(uuid: &str) -> String {
+        format!(
+            r#"{{"uuid":"{uuid}","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"hi"}}}}"#
+        )
+    }
+
+    #[test]
+    fn test_report_and_repair() {
+        let (a, b, c, d) = (entry("a"), entry("b"), entry("c"), entry("d"));
+        let content = format!(
+            "{a}\n{b}{c}\r\n\n{{\"type\":\"user\",\"uuid\":\"cut{d}\n[1]\n{{\"type\":\"user\",\"uuid\":\"x"
+        );
+
+        let report = ParseReport::from_bytes(content.as_bytes());
+        assert_eq!((report.lines, report.entries), (6, 1));
+        let kinds: Vec<_> = report.diagnostics.iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ParseErrorKind::Concatenated,
+                ParseErrorKind::Torn,
+                ParseErrorKind::Schema,
+                ParseErrorKind::Truncated
+            ]
+        );
+        let concatenated = &report.diagnostics[0];
+        assert_eq!(concatenated.line, 2);
+        assert_eq!(concatenated.byte_offset, (a.len() + 1 + b.len()) as u64);
+        assert!(concatenated.snippet.starts_with('…'));
+        assert_eq!(report.repairable().map(|d| d.recoverable).sum::<usize>(), 3);
+
+        let repaired = repair(content.as_bytes());
+        assert_eq!((repaired.lines, repaired.entries), (2, 3));
+        let text = String::from_utf8(repaired.content).unwrap();
+        assert_eq!(
+            text,
+            format!("{a}\n{b}\r\n{c}\r\n\n{d}\n[1]\n{{\"type\":\"user\",\"uuid\":\"x")
+        );
+        let after = ParseReport::from_bytes(text.as_bytes());
+        assert_eq!(after.entries, 4);
+        assert_eq!(after.diagnostics.len(), 2);
+    }
+}
@@ -0,0 +1,121 @@
+//! SIMD-accelerated parsing for bulk scans (`simd` feature).
+//!
+//! Scans over multi-GB corpora spend most of their time tokenizing JSON.
+//! This path memory-maps the file and hands each line to simd-json instead
+//! of reading it through a `BufReader` into serde_json. simd-json parses in
+//! place, so every line is copied into a reused scratch buffer first.
+//!
+//! Lines simd-json rejects are retried with serde_json before they count as
+//! malformed, so the fast path yields the same entries, statistics, and
+//! error messages as [`JsonlParser::parse_reader`]. It is only used for
+//! lenient parses; strict parsing (validation) stays on serde_json.
+//!
+//! Appending to a mapped file is harmless (the new bytes are not seen), but
+//! truncating one while it is mapped is undefined, as for [`MmapParser`].
+
+use std::fs::File;
+use std::path::Path;
+
+use tracing::debug;
+
+use super::{JsonlParser, MmapParser, ParseError, ParseStats};
+use crate::error::{Result, SnatchError};
+use crate::model::LogEntry;
+
+impl JsonlParser {
+    /// Parse an opened file through a memory map, tokenizing with simd-json.
+    pub(super) fn parse_mapped(&mut self, file: &File, path: &Path) -> Result<Vec<LogEntry>> {
+        let mapped = MmapParser::new(file)
+            .map_err(|e| SnatchError::io(format!("Failed to memory-map {}", path.display()), e))?;
+        let mut entries = Vec::new();
+        self.stats = ParseStats::default();
+
+        let bytes = mapped.as_bytes();
+        if bytes.is_empty() {
+            return Ok(entries);
+        }
+        // A trailing newline ends the last line rather than starting a new one,
+        // matching `BufRead::lines`.
+        let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+
+        let mut scratch = Vec::new();
+        let mut buffers = simd_json::Buffers::default();
+        for (index, line) in bytes.split(|&b| b == b'\n').enumerate() {
+            let line_num = index + 1;
+            self.stats.lines_processed += 1;
+
+            let Ok(text) = std::str::from_utf8(line) else {
+                self.stats.lines_skipped += 1;
+                self.stats.errors.push(ParseError {
+                    line: line_num,
+                    message: "I/O error: stream did not contain valid UTF-8".to_string(),
+                    raw_line: String::new(),
+                });
+                continue;
+            };
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                self.stats.empty_lines += 1;
+                continue;
+            }
+            if !self.prescan(trimmed) {
+                continue;
+            }
+
+            scratch.clear();
+            scratch.extend_from_slice(trimmed.as_bytes());
+            let parsed =
+                simd_json::serde::from_slice_with_buffers::<LogEntry>(&mut scratch, &mut buffers)
+                    .or_else(|_| self.parse_line(trimmed, line_num));
+            match parsed {
+                Ok(entry) => self.accept(entry, &mut entries),
+                Err(e) => self.reject(trimmed, line_num, e, &mut entries)?,
+            }
+        }
+
+        debug!(
+            entries = entries.len(),
+            lines = self.stats.lines_processed,
+            skipped = self.stats.lines_skipped,
+            "SIMD parsing complete"
+        );
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simd_matches_serde_json() {
+        let user = r#"{"uuid":"u1","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s","version":"2.0.74","isSidechain":false,"message":{"role":"user","content":"café \"quoted\""}}"#;
+        let assistant = r#"{"uuid":"a1","parentUuid":"u1","type":"assistant","timestamp":"2025-12-23T00:00:01Z","sessionId":"s","message":{"role":"assistant","model":"m","content":[{"type":"text","text":"ok"}],"usage":{"input_tokens":10,"output_tokens":5}},"costUSD":0.1234567890123}"#;
+        let fused = user.replace("u1", "u2");
+        let content = format!(
+            "{user}\r\n\n{assistant}\nnot json\n{{\"type\":\"user\",\"uuid\":\"torn{fused}\n"
+        );
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, content.as_bytes()).unwrap();
+
+        let mut plain = JsonlParser::new();
+        let expected = plain.parse_file(file.path()).unwrap();
+        let mut simd = JsonlParser::new().with_simd(true);
+        let entries = simd.parse_file(file.path()).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&entries).unwrap(),
+            serde_json::to_string(&expected).unwrap()
+        );
+        let (a, b) = (plain.stats(), simd.stats());
+        assert_eq!(
+            (a.lines_processed, a.entries_parsed, a.lines_skipped),
+            (b.lines_processed, b.entries_parsed, b.lines_skipped)
+        );
+        assert_eq!(
+            (a.empty_lines, a.entries_salvaged),
+            (b.empty_lines, b.entries_salvaged)
+        );
+        assert_eq!(simd.schema_version(), plain.schema_version());
+    }
+}
@@ -8,10 +8,11 @@
 #![cfg_attr(feature = "mmap", allow(unsafe_code))]
 
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::io::{BufRead, Read, Seek, SeekFrom};
 use std::path::Path;
 use std::time::{Duration, SystemTime};
 
+use super::{is_compressed_path, open_session, SessionReader};
 use crate::error::{Result, SnatchError};
 use crate::model::LogEntry;
 
@@ -128,27 +129,9 @@ impl<R: BufRead> Iterator for StreamingIterator<R> {
     }
 }
 
-/// Open a file for streaming parsing.
-pub fn open_stream(path: impl AsRef<Path>) -> Result<StreamingParser<BufReader<File>>> {
-    let path = path.as_ref();
-    let file = File::open(path).map_err(|e| {
-        if e.kind() == std::io::ErrorKind::NotFound {
-            SnatchError::FileNotFound {
-                path: path.to_path_buf(),
-            }
-        } else if e.kind() == std::io::ErrorKind::PermissionDenied {
-            SnatchError::PermissionDenied {
-                path: path.to_path_buf(),
-            }
-        } else {
-            SnatchError::io(format!("Failed to open {}", path.display()), e)
-        }
-    })?;
-
-    Ok(StreamingParser::new(BufReader::with_capacity(
-        64 * 1024,
-        file,
-    )))
+/// Open a file for streaming parsing, decompressing it if needed.
+pub fn open_stream(path: impl AsRef<Path>) -> Result<StreamingParser<SessionReader>> {
+    Ok(StreamingParser::new(open_session(path)?))
 }
 
 /// Session file state detection.
@@ -213,7 +196,8 @@ pub fn has_incomplete_line(path: impl AsRef<Path>) -> Result<bool> {
         SnatchError::io(format!("Failed to read metadata for {}", path.display()), e)
     })?;
 
-    if metadata.len() == 0 {
+    // Compressed files are finished archives, never written to by Claude Code.
+    if metadata.len() == 0 || is_compressed_path(path) {
         return Ok(false);
     }
 
@@ -677,7 +661,7 @@ impl<'a> Iterator for ZeroCopyLines<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Cursor;
+    use std::io::{BufReader, Cursor};
 
     #[test]
     fn test_streaming_parser() {
@@ -0,0 +1,177 @@
+//! Reading the last entries of a session without parsing the rest.
+//!
+//! Previews and live dashboards only show the most recent entries, but a
+//! long session can run to hundreds of megabytes. [`parse_tail`] reads the
+//! file backwards in fixed-size blocks, finds line boundaries from the end,
+//! and parses lines until it has the entries asked for, so the cost tracks
+//! the size of the tail rather than the file.
+//!
+//! Lines are parsed leniently: malformed lines are skipped and torn lines
+//! yield their salvaged entries, as in [`JsonlParser`](super::JsonlParser).
+//! A partial line still being written at the end of an active session is
+//! skipped the same way. Compressed files cannot be read backwards and are
+//! decoded from the start instead.
+//!
+//! ```rust,no_run
+//! use claude_snatch::parser::parse_tail;
+//!
+//! for entry in parse_tail("session.jsonl", 5)? {
+//!     println!("{}", entry.message_type());
+//! }
+//! # Ok::<(), claude_snatch::SnatchError>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::compression::{decode, open_error};
+use super::{salvage_torn_line, Compression, LogEntryIterator};
+use crate::error::{Result, SnatchError};
+use crate::model::LogEntry;
+
+/// Bytes read per step when scanning backwards.
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Parse the last `n` entries of a session file, in file order.
+pub fn parse_tail(path: impl AsRef<Path>, n: usize) -> Result<Vec<LogEntry>> {
+    let path = path.as_ref();
+    let mut file = File::open(path).map_err(|e| open_error(path, e))?;
+    let compression = Compression::detect(&mut file, path).map_err(|e| open_error(path, e))?;
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if compression.is_compressed() {
+        let reader = decode(file, compression).map_err(|e| open_error(path, e))?;
+        let mut tail = VecDeque::with_capacity(n);
+        for entry in LogEntryIterator::new(reader, true) {
+            if tail.len() == n {
+                tail.pop_front();
+            }
+            tail.push_back(entry?);
+        }
+        return Ok(tail.into());
+    }
+
+    let read_error = |e| SnatchError::io(format!("Failed to read {}", path.display()), e);
+    let mut tail = TailReader {
+        file,
+        wanted: n,
+        entries: Vec::new(),
+    };
+    let mut pos = tail.file.metadata().map_err(read_error)?.len();
+    // End of the line whose start has not been found yet.
+    let mut line_end = pos;
+    let mut block = vec![0u8; BLOCK_SIZE];
+    while pos > 0 && !tail.is_full() {
+        let len = BLOCK_SIZE.min(usize::try_from(pos).unwrap_or(BLOCK_SIZE));
+        pos -= len as u64;
+        tail.file
+            .seek(SeekFrom::Start(pos))
+            .and_then(|_| tail.file.read_exact(&mut block[..len]))
+            .map_err(read_error)?;
+        for (i, _) in block[..len]
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|&(_, &b)| b == b'\n')
+        {
+            let newline = pos + i as u64;
+            tail.take_line(newline + 1, line_end).map_err(read_error)?;
+            line_end = newline;
+            if tail.is_full() {
+                break;
+            }
+        }
+    }
+    if pos == 0 && !tail.is_full() {
+        tail.take_line(0, line_end).map_err(read_error)?;
+    }
+
+    let mut entries = tail.entries;
+    entries.truncate(n);
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Entries collected from the end of a file, newest first.
+struct TailReader {
+    file: File,
+    wanted: usize,
+    entries: Vec<LogEntry>,
+}
+
+impl TailReader {
+    fn is_full(&self) -> bool {
+        self.entries.len() >= self.wanted
+    }
+
+    /// Parse the line in `start..end` and collect its entries.
+    fn take_line(&mut self, start: u64, end: u64) -> std::io::Result<()> {
+        let Ok(len) = usize::try_from(end - start) else {
+            return Ok(());
+        };
+        if len == 0 {
+            return Ok(());
+        }
+        let mut line = vec![0u8; len];
+        self.file.seek(SeekFrom::Start(start))?;
+        self.file.read_exact(&mut line)?;
+        let Ok(text) = std::str::from_utf8(&line) else {
+            return Ok(());
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(());
+        }
+        match serde_json::from_str::<LogEntry>(text) {
+            Ok(entry) => self.entries.push(entry),
+            Err(_) => self
+                .entries
+                .extend(salvage_torn_line(text).into_iter().rev()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::JsonlParser;
+
+    #[test]
+    fn test_tail_matches_full_parse() {
+        let entry = |i: usize| {
+            format!(
+                r#"{{"uuid":"u{i}","parentUuid":null,"type":"user","timestamp":"2025-12-23T00:00:00Z","sessionId":"s","version":"2.0.74","isSidechain":false,"message":{{"role":"user","content":"{}"}}}}"#,
+                "x".repeat(i * 97 % 5000)
+            )
+        };
+        // Enough lines to span several blocks, a malformed line, CRLF
+        // endings, and a partial line being written at the end.
+        let mut content = String::new();
+        for i in 0..200 {
+            content.push_str(&entry(i));
+            content.push_str(if i % 7 == 0 { "\r\n" } else { "\n" });
+            if i == 190 {
+                content.push_str("not json\n\n");
+            }
+        }
+        content.push_str(r#"{"uuid":"partial","type":"us"#);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, content.as_bytes()).unwrap();
+
+        let all = JsonlParser::new().parse_file(file.path()).unwrap();
+        let uuids = |entries: &[LogEntry]| {
+            entries
+                .iter()
+                .map(|e| e.uuid().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        for n in [0, 1, 9, 10, 150, 200, 500] {
+            let tail = parse_tail(file.path(), n).unwrap();
+            assert_eq!(uuids(&tail), uuids(&all[all.len().saturating_sub(n)..]));
+        }
+    }
+}
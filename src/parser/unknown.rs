@@ -0,0 +1,183 @@
+//! Pluggable handling of entry types the model does not know.
+//!
+//! Claude Code adds entry types faster than the model follows them. An
+//! entry whose `type` has no [`LogEntry`] variant parses as
+//! [`LogEntry::Unknown`] with its whole raw object, and every exporter
+//! passes it through rather than dropping it. An [`UnknownEntryHandler`]
+//! registered with
+//! [`JsonlParser::with_unknown_handler`](super::JsonlParser::with_unknown_handler)
+//! takes the entries of one such type as they are parsed and can keep them,
+//! rewrite them, map them onto a modeled entry, or drop them. A strict
+//! parser accepts entry types that have a handler.
+//!
+//! ```rust
+//! use claude_snatch::model::LogEntry;
+//! use claude_snatch::parser::{JsonlParser, UnknownEntryHandler};
+//! use serde_json::Value;
+//!
+//! /// Drops `pr-link` entries.
+//! struct DropPrLinks;
+//!
+//! impl UnknownEntryHandler for DropPrLinks {
+//!     fn entry_type(&self) -> &str {
+//!         "pr-link"
+//!     }
+//!
+//!     fn handle(&self, _raw: Value) -> Option<LogEntry> {
+//!         None
+//!     }
+//! }
+//!
+//! let mut parser = JsonlParser::new().with_unknown_handler(DropPrLinks);
+//! let entries = parser.parse_str(r#"{"type":"pr-link","prNumber":1}"#)?;
+//! assert!(entries.is_empty());
+//! # Ok::<(), claude_snatch::SnatchError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::model::LogEntry;
+
+/// Handles parsed entries of one entry type the model does not know.
+pub trait UnknownEntryHandler: Send + Sync {
+    /// The `type` value this handler takes.
+    fn entry_type(&self) -> &str;
+
+    /// Handle one raw entry. Return `Some(LogEntry::Unknown(raw))` to keep
+    /// it as is, another entry to replace it, or `None` to drop it.
+    fn handle(&self, raw: Value) -> Option<LogEntry>;
+}
+
+/// Registered [`UnknownEntryHandler`]s, by entry type.
+#[derive(Clone, Default)]
+pub struct UnknownEntryHandlers {
+    handlers: HashMap<String, Arc<dyn UnknownEntryHandler>>,
+}
+
+impl UnknownEntryHandlers {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler, replacing any earlier one for the same type.
+    pub fn register(&mut self, handler: impl UnknownEntryHandler + 'static) {
+        self.handlers
+            .insert(handler.entry_type().to_string(), Arc::new(handler));
+    }
+
+    /// Whether a handler is registered for `entry_type`.
+    #[must_use]
+    pub fn handles(&self, entry_type: &str) -> bool {
+        self.handlers.contains_key(entry_type)
+    }
+
+    /// Whether no handler is registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Pass an unknown entry to the handler for its type. Other entries,
+    /// and unknown ones without a handler, are returned unchanged.
+    #[must_use]
+    pub fn handle(&self, entry: LogEntry) -> Option<LogEntry> {
+        let LogEntry::Unknown(raw) = entry else {
+            return Some(entry);
+        };
+        let handler = raw
+            .get("type")
+            .and_then(Value::as_str)
+            .and_then(|kind| self.handlers.get(kind));
+        match handler {
+            Some(handler) => handler.handle(raw),
+            None => Some(LogEntry::Unknown(raw)),
+        }
+    }
+}
+
+impl fmt::Debug for UnknownEntryHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut types: Vec<_> = self.handlers.keys().collect();
+        types.sort();
+        f.debug_struct("UnknownEntryHandlers")
+            .field("types", &types)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SummaryMessage;
+    use crate::parser::{JsonlParser, ParseMode};
+
+    /// Maps `custom-title` entries onto summaries.
+    struct CustomTitles;
+
+    impl UnknownEntryHandler for CustomTitles {
+        fn entry_type(&self) -> &str {
+            "custom-title"
+        }
+
+        fn handle(&self, raw: Value) -> Option<LogEntry> {
+            let title = raw.get("customTitle")?.as_str()?;
+            serde_json::from_value::<SummaryMessage>(serde_json::json!({ "summary": title }))
+                .ok()
+                .map(LogEntry::Summary)
+        }
+    }
+
+    #[test]
+    fn test_handlers_take_their_type_only() {
+        let content = concat!(
+            r#"{"type":"custom-title","customTitle":"streaming","sessionId":"s"}"#,
+            "\n",
+            r#"{"type":"custom-title","sessionId":"s"}"#,
+            "\n",
+            r#"{"type":"agent-name","name":"explorer","sessionId":"s"}"#,
+            "\n",
+        );
+
+        let mut plain = JsonlParser::new();
+        let entries = plain.parse_str(content).unwrap();
+        assert!(entries.iter().all(|e| matches!(e, LogEntry::Unknown(_))));
+        assert_eq!(plain.stats().entries_unknown, 3);
+        assert!(JsonlParser::new()
+            .with_mode(ParseMode::Strict)
+            .parse_str(content)
+            .is_err());
+
+        let mut parser = JsonlParser::new().with_unknown_handler(CustomTitles);
+        let entries = parser.parse_str(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(&entries[0], LogEntry::Summary(s) if s.summary == "streaming"));
+        assert_eq!(entries[1].message_type(), "agent-name");
+        let stats = parser.stats();
+        assert_eq!(
+            (
+                stats.entries_parsed,
+                stats.entries_unknown,
+                stats.lines_filtered
+            ),
+            (2, 1, 1)
+        );
+
+        let mut strict = JsonlParser::new()
+            .with_mode(ParseMode::Strict)
+            .with_unknown_handler(CustomTitles);
+        assert_eq!(
+            strict
+                .parse_str(content.lines().next().unwrap())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(strict.parse_str(content).is_err());
+    }
+}
@@ -22,6 +22,9 @@ pub struct RegistryConfig {
     /// Explicit Claude root (the CLI's global `--claude-dir`); `None`
     /// discovers.
     pub claude_root: Option<PathBuf>,
+    /// Other Claude directories whose projects are merged into the Claude
+    /// root's (see [`ClaudeDirectory::with_peers`]).
+    pub claude_peers: Vec<PathBuf>,
     /// Explicit Codex root for embedded/library callers; `None` discovers from
     /// Codex's normal environment/default location.
     pub codex_root: Option<PathBuf>,
@@ -99,7 +102,8 @@ impl ProviderRegistry {
         let claude_dir = match &config.claude_root {
             Some(root) => ClaudeDirectory::from_path(root),
             None => ClaudeDirectory::discover(),
-        };
+        }
+        .map(|dir| dir.with_peers(config.claude_peers.iter().cloned()));
         let (root, provider) = match claude_dir {
             Ok(dir) => (
                 Some(dir.root().to_path_buf()),
@@ -72,6 +72,9 @@ pub struct SessionNote {
     /// Optional category/label for the note.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// UUID of the message the note annotates, if not the whole session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 impl SessionNote {
@@ -81,6 +84,7 @@ impl SessionNote {
             text: text.into(),
             created_at: chrono::Utc::now(),
             label: None,
+            message: None,
         }
     }
 
@@ -90,8 +94,16 @@ impl SessionNote {
             text: text.into(),
             created_at: chrono::Utc::now(),
             label: Some(label.into()),
+            message: None,
         }
     }
+
+    /// Attach the note to one message of the session.
+    #[must_use]
+    pub fn for_message(mut self, uuid: impl Into<String>) -> Self {
+        self.message = Some(uuid.into());
+        self
+    }
 }
 
 /// Statistics for session outcomes.
@@ -139,6 +151,9 @@ pub struct SessionMeta {
     /// Whether this session is bookmarked/favorited.
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub bookmarked: bool,
+    /// UUIDs of bookmarked messages within the session.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarked_messages: Vec<String>,
     /// Session outcome classification.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub outcome: Option<SessionOutcome>,
@@ -151,11 +166,12 @@ pub struct SessionMeta {
 }
 
 impl SessionMeta {
-    /// Check if this metadata is empty (no name, tags, bookmark, outcome, notes, or links).
+    /// Check if this metadata is empty (no name, tags, bookmarks, outcome, notes, or links).
     pub fn is_empty(&self) -> bool {
         self.name.is_none()
             && self.tags.is_empty()
             && !self.bookmarked
+            && self.bookmarked_messages.is_empty()
             && self.outcome.is_none()
             && self.notes.is_empty()
             && self.linked_sessions.is_empty()
@@ -273,6 +289,7 @@ impl LegacySessionMeta {
             name: self.name,
             tags: self.tags,
             bookmarked: self.bookmarked,
+            bookmarked_messages: Vec::new(),
             outcome: self.outcome,
             notes: self.notes,
             linked_sessions: self
@@ -658,6 +675,44 @@ impl TagStore {
             .collect()
     }
 
+    /// Bookmark or unbookmark a message in a qualified session.
+    ///
+    /// Returns whether the bookmark state changed.
+    pub fn set_message_bookmark_key(
+        &mut self,
+        key: &LogicalSessionKey,
+        uuid: &str,
+        bookmarked: bool,
+    ) -> bool {
+        let meta = self.get_or_create_key(key);
+        let position = meta.bookmarked_messages.iter().position(|m| m == uuid);
+        let changed = match (position, bookmarked) {
+            (None, true) => {
+                meta.bookmarked_messages.push(uuid.to_string());
+                true
+            }
+            (Some(pos), false) => {
+                meta.bookmarked_messages.remove(pos);
+                true
+            }
+            _ => false,
+        };
+        self.cleanup_empty_key(key);
+        changed
+    }
+
+    /// Get all bookmarked messages as (session key, message UUID) pairs.
+    pub fn bookmarked_messages(&self) -> Vec<(&LogicalSessionKey, &str)> {
+        self.sessions
+            .iter()
+            .flat_map(|(key, m)| {
+                m.bookmarked_messages
+                    .iter()
+                    .map(move |uuid| (key, uuid.as_str()))
+            })
+            .collect()
+    }
+
     /// Set outcome classification for a session.
     pub fn set_outcome(&mut self, session_id: &str, outcome: Option<SessionOutcome>) {
         self.set_outcome_key(&legacy_key(session_id), outcome);
@@ -710,6 +765,23 @@ impl TagStore {
         meta.notes.push(note);
     }
 
+    /// Add a note to one message of a qualified session.
+    pub fn add_message_note_key(
+        &mut self,
+        key: &LogicalSessionKey,
+        uuid: &str,
+        text: &str,
+        label: Option<&str>,
+    ) {
+        let note = match label {
+            Some(label) => SessionNote::with_label(text, label),
+            None => SessionNote::new(text),
+        };
+        self.get_or_create_key(key)
+            .notes
+            .push(note.for_message(uuid));
+    }
+
     /// Remove a note from a session by index.
     pub fn remove_note(&mut self, session_id: &str, index: usize) -> bool {
         let Some(key) = self.resolve_id(session_id).cloned() else {
@@ -1030,6 +1102,27 @@ mod tests {
         assert!(store.get(session_id).is_none());
     }
 
+    #[test]
+    fn test_tag_store_message_bookmarks() {
+        let mut store = TagStore::default();
+        let key = legacy_key("test-session");
+
+        assert!(store.set_message_bookmark_key(&key, "msg-1", true));
+        assert!(!store.set_message_bookmark_key(&key, "msg-1", true));
+        assert!(store.set_message_bookmark_key(&key, "msg-2", true));
+        assert_eq!(
+            store.bookmarked_messages(),
+            [(&key, "msg-1"), (&key, "msg-2")]
+        );
+        // A message bookmark does not bookmark the session itself.
+        assert!(store.bookmarked_sessions().is_empty());
+
+        assert!(store.set_message_bookmark_key(&key, "msg-1", false));
+        assert!(store.set_message_bookmark_key(&key, "msg-2", false));
+        assert!(!store.set_message_bookmark_key(&key, "msg-2", false));
+        assert!(store.get_key(&key).is_none());
+    }
+
     #[test]
     fn test_all_tags() {
         let mut store = TagStore::default();
@@ -1241,6 +1334,22 @@ mod tests {
         assert_eq!(notes[1].label, Some("important".to_string()));
     }
 
+    #[test]
+    fn test_add_message_note_round_trips() {
+        let mut store = TagStore::default();
+        let key = legacy_key("test-session");
+
+        store.add_message_note_key(&key, "msg-1", "Wrong fix here", Some("bug"));
+        store.add_note_key(&key, "Session note", None);
+
+        let json = serde_json::to_string(&store).unwrap();
+        let loaded: TagStore = serde_json::from_str(&json).unwrap();
+        let notes = loaded.get_notes_key(&key).unwrap();
+        assert_eq!(notes[0].message.as_deref(), Some("msg-1"));
+        assert_eq!(notes[0].label.as_deref(), Some("bug"));
+        assert!(notes[1].message.is_none());
+    }
+
     #[test]
     fn test_remove_note() {
         let mut store = TagStore::default();
@@ -0,0 +1,157 @@
+//! Encryption of exports with [age](https://age-encryption.org).
+//!
+//! snatch drives the `age` command-line tool rather than linking a crypto
+//! library, the same way gist upload drives `gh`. Files are passed to `age`
+//! by path and its stdio is inherited, so `age` itself prompts for
+//! passphrases on the terminal.
+//!
+//! ```
+//! use claude_snatch::util::age::EncryptTarget;
+//!
+//! let target: EncryptTarget = "age:age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p"
+//!     .parse()
+//!     .unwrap();
+//! assert!(matches!(target, EncryptTarget::Recipient(_)));
+//! assert_eq!("passphrase".parse::<EncryptTarget>(), Ok(EncryptTarget::Passphrase));
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use crate::error::{Result, SnatchError};
+
+/// Who can decrypt an encrypted export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncryptTarget {
+    /// An age recipient: an `age1…` public key or an SSH public key.
+    Recipient(String),
+    /// A file listing recipients, one per line.
+    RecipientsFile(PathBuf),
+    /// A passphrase, prompted for by `age`.
+    Passphrase,
+}
+
+impl FromStr for EncryptTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s == "passphrase" {
+            return Ok(Self::Passphrase);
+        }
+        if let Some(recipient) = s.strip_prefix("age:").filter(|r| !r.trim().is_empty()) {
+            return Ok(Self::Recipient(recipient.trim().to_string()));
+        }
+        if let Some(path) = s.strip_prefix("age-file:").filter(|p| !p.is_empty()) {
+            return Ok(Self::RecipientsFile(PathBuf::from(path)));
+        }
+        Err(format!(
+            "invalid encryption target '{s}': expected age:<recipient>, age-file:<path>, or passphrase"
+        ))
+    }
+}
+
+impl fmt::Display for EncryptTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Recipient(recipient) => write!(f, "age:{recipient}"),
+            Self::RecipientsFile(path) => write!(f, "age-file:{}", path.display()),
+            Self::Passphrase => f.write_str("passphrase"),
+        }
+    }
+}
+
+/// Check that a set of targets can be used together: at least one, and a
+/// passphrase only on its own (age cannot mix it with recipients).
+pub fn check_targets(targets: &[EncryptTarget]) -> Result<()> {
+    let passphrase = targets.contains(&EncryptTarget::Passphrase);
+    if targets.is_empty() || (passphrase && targets.len() > 1) {
+        return Err(SnatchError::InvalidArgument {
+            name: "--encrypt".to_string(),
+            reason: "give one or more age recipients, or passphrase on its own".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check whether the `age` CLI is installed.
+pub fn is_available() -> bool {
+    Command::new("age")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Encrypt `input` to `output` for the given targets.
+pub fn encrypt_file(input: &Path, output: &Path, targets: &[EncryptTarget]) -> Result<()> {
+    check_targets(targets)?;
+    let mut cmd = Command::new("age");
+    cmd.arg("--encrypt");
+    for target in targets {
+        match target {
+            EncryptTarget::Recipient(recipient) => cmd.arg("--recipient").arg(recipient),
+            EncryptTarget::RecipientsFile(path) => cmd.arg("--recipients-file").arg(path),
+            EncryptTarget::Passphrase => cmd.arg("--passphrase"),
+        };
+    }
+    cmd.arg("--output").arg(output).arg(input);
+    run_age(cmd, "encrypt")
+}
+
+/// Decrypt `input` to `output`, or to stdout when `output` is `None`.
+/// Passphrase-encrypted files need no identities; `age` prompts instead.
+pub fn decrypt_file(input: &Path, output: Option<&Path>, identities: &[PathBuf]) -> Result<()> {
+    let mut cmd = Command::new("age");
+    cmd.arg("--decrypt");
+    for identity in identities {
+        cmd.arg("--identity").arg(identity);
+    }
+    if let Some(output) = output {
+        cmd.arg("--output").arg(output);
+    }
+    cmd.arg(input);
+    run_age(cmd, "decrypt")
+}
+
+fn run_age(mut cmd: Command, action: &str) -> Result<()> {
+    let status = cmd.status().map_err(|e| SnatchError::ExportError {
+        message: format!(
+            "Failed to run age to {action} (install it from https://age-encryption.org): {e}"
+        ),
+        source: Some(Box::new(e)),
+    })?;
+    if !status.success() {
+        return Err(SnatchError::export(format!(
+            "age failed to {action} ({status})"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        assert_eq!(
+            "age:age1abc".parse::<EncryptTarget>(),
+            Ok(EncryptTarget::Recipient("age1abc".to_string()))
+        );
+        assert_eq!(
+            "age-file:keys.txt".parse::<EncryptTarget>(),
+            Ok(EncryptTarget::RecipientsFile(PathBuf::from("keys.txt")))
+        );
+        assert!("age:".parse::<EncryptTarget>().is_err());
+        assert!("gpg:me".parse::<EncryptTarget>().is_err());
+
+        let recipient = EncryptTarget::Recipient("age1abc".to_string());
+        assert!(check_targets(&[recipient.clone(), recipient.clone()]).is_ok());
+        assert!(check_targets(&[EncryptTarget::Passphrase]).is_ok());
+        assert!(check_targets(&[recipient, EncryptTarget::Passphrase]).is_err());
+        assert!(check_targets(&[]).is_err());
+    }
+}
@@ -0,0 +1,865 @@
+//! jq-style filter expressions for JSON output.
+//!
+//! `--filter` on `export`, `list`, and `search` runs the command's JSON
+//! output through a small subset of jq, so fields can be projected and
+//! records selected without piping to an external tool:
+//!
+//! - paths: `.`, `.field`, `."odd key"`, `.[0]`, `.[-1]`, `.["key"]`, `.[]`
+//! - pipes and streams: `a | b`, `a, b`
+//! - construction: `[expr]`, `{name, alias: expr, "key": expr}`
+//! - literals: strings, numbers, `true`, `false`, `null`
+//! - comparisons and logic: `==`, `!=`, `<`, `<=`, `>`, `>=`, `and`, `or`
+//! - functions: `select(f)`, `map(f)`, `has(key)`, `contains(x)`,
+//!   `test(regex)`, `length`, `keys`, `not`, `first`, `last`
+//!
+//! As in jq, a filter maps its input to a stream of outputs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use claude_snatch::util::json_filter::JsonFilter;
+//! use serde_json::json;
+//!
+//! let filter: JsonFilter = ".[] | select(.size > 10) | .id".parse().unwrap();
+//! let input = json!([{ "id": "a", "size": 5 }, { "id": "b", "size": 50 }]);
+//! assert_eq!(filter.apply(&input).unwrap(), vec![json!("b")]);
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde_json::{Map, Number, Value};
+
+use crate::error::{Result, SnatchError};
+
+/// A parsed filter expression.
+#[derive(Debug, Clone)]
+pub struct JsonFilter {
+    source: String,
+    expr: Expr,
+}
+
+impl JsonFilter {
+    /// The expression as written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Run the filter over `input`, returning its output stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgument` when the expression does not fit the data,
+    /// such as iterating over a string or indexing an array with a key.
+    pub fn apply(&self, input: &Value) -> Result<Vec<Value>> {
+        eval(&self.expr, input).map_err(|reason| SnatchError::InvalidArgument {
+            name: "filter".to_string(),
+            reason: format!("'{}': {reason}", self.source),
+        })
+    }
+}
+
+impl FromStr for JsonFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.pipe()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {token}"));
+        }
+        Ok(Self {
+            source: s.to_string(),
+            expr,
+        })
+    }
+}
+
+impl fmt::Display for JsonFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    fn test(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering == Ordering::Equal,
+            Self::Ne => ordering != Ordering::Equal,
+            Self::Lt => ordering == Ordering::Less,
+            Self::Le => ordering != Ordering::Greater,
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+    Select,
+    Map,
+    Has,
+    Contains,
+    Test,
+    Length,
+    Keys,
+    Not,
+    First,
+    Last,
+}
+
+impl Func {
+    /// Look up a function and whether it takes an argument.
+    fn lookup(name: &str) -> Option<(Self, bool)> {
+        Some(match name {
+            "select" => (Self::Select, true),
+            "map" => (Self::Map, true),
+            "has" => (Self::Has, true),
+            "contains" => (Self::Contains, true),
+            "test" => (Self::Test, true),
+            "length" => (Self::Length, false),
+            "keys" => (Self::Keys, false),
+            "not" => (Self::Not, false),
+            "first" => (Self::First, false),
+            "last" => (Self::Last, false),
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Identity,
+    Literal(Value),
+    Index(Box<Expr>, Box<Expr>),
+    Iterate(Box<Expr>),
+    Pipe(Box<Expr>, Box<Expr>),
+    Comma(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, CmpOp, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Array(Option<Box<Expr>>),
+    Object(Vec<(String, Expr)>),
+    Call(Func, Option<Box<Expr>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    Field(String),
+    Ident(String),
+    Str(String),
+    Num(Number),
+    Op(CmpOp),
+    Pipe,
+    Comma,
+    Colon,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dot => f.write_str("'.'"),
+            Self::Field(name) => write!(f, "'.{name}'"),
+            Self::Ident(name) => write!(f, "'{name}'"),
+            Self::Str(s) => write!(f, "string {s:?}"),
+            Self::Num(n) => write!(f, "number {n}"),
+            Self::Op(op) => write!(f, "operator {op:?}"),
+            Self::Pipe => f.write_str("'|'"),
+            Self::Comma => f.write_str("','"),
+            Self::Colon => f.write_str("':'"),
+            Self::LParen => f.write_str("'('"),
+            Self::RParen => f.write_str("')'"),
+            Self::LBracket => f.write_str("'['"),
+            Self::RBracket => f.write_str("']'"),
+            Self::LBrace => f.write_str("'{'"),
+            Self::RBrace => f.write_str("'}'"),
+        }
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn tokenize(source: &str) -> std::result::Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '.' if next.is_some_and(is_ident_start) => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                tokens.push(Token::Field(chars[start..i].iter().collect()));
+            }
+            '.' if next == Some('"') => {
+                let (s, end) = read_string(&chars, i + 1)?;
+                tokens.push(Token::Field(s));
+                i = end;
+            }
+            '.' => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            '"' => {
+                let (s, end) = read_string(&chars, i)?;
+                tokens.push(Token::Str(s));
+                i = end;
+            }
+            '0'..='9' | '-' if c != '-' || next.is_some_and(|n| n.is_ascii_digit()) => {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_ascii_digit()
+                        || matches!(chars[i], '.' | 'e' | 'E')
+                        || (matches!(chars[i], '+' | '-') && matches!(chars[i - 1], 'e' | 'E')))
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse::<Number>()
+                    .map_err(|_| format!("invalid number '{text}'"))?;
+                tokens.push(Token::Num(number));
+            }
+            _ if is_ident_start(c) => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            '=' | '!' | '<' | '>' => {
+                let (op, len) = match (c, next) {
+                    ('=', Some('=')) => (CmpOp::Eq, 2),
+                    ('!', Some('=')) => (CmpOp::Ne, 2),
+                    ('<', Some('=')) => (CmpOp::Le, 2),
+                    ('>', Some('=')) => (CmpOp::Ge, 2),
+                    ('<', _) => (CmpOp::Lt, 1),
+                    ('>', _) => (CmpOp::Gt, 1),
+                    _ => return Err(format!("unexpected '{c}' (comparisons use '==')")),
+                };
+                tokens.push(Token::Op(op));
+                i += len;
+            }
+            _ => {
+                tokens.push(match c {
+                    '|' => Token::Pipe,
+                    ',' => Token::Comma,
+                    ':' => Token::Colon,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    '{' => Token::LBrace,
+                    '}' => Token::RBrace,
+                    _ => return Err(format!("unexpected character '{c}'")),
+                });
+                i += 1;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Read a JSON string literal starting at the opening quote, returning it
+/// and the index just past the closing quote.
+fn read_string(chars: &[char], start: usize) -> std::result::Result<(String, usize), String> {
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '"' => {
+                let literal: String = chars[start..=i].iter().collect();
+                let value = serde_json::from_str(&literal)
+                    .map_err(|e| format!("invalid string {literal}: {e}"))?;
+                return Ok((value, i + 1));
+            }
+            _ => i += 1,
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> std::result::Result<(), String> {
+        match self.advance() {
+            Some(found) if found == *token => Ok(()),
+            Some(found) => Err(format!("expected {token}, found {found}")),
+            None => Err(format!("expected {token} at end of filter")),
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(name)) if name == keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn pipe(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.comma()?;
+        while self.eat(&Token::Pipe) {
+            expr = Expr::Pipe(Box::new(expr), Box::new(self.comma()?));
+        }
+        Ok(expr)
+    }
+
+    fn comma(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.or()?;
+        while self.eat(&Token::Comma) {
+            expr = Expr::Comma(Box::new(expr), Box::new(self.or()?));
+        }
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.comparison()?;
+        while self.eat_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.comparison()?));
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> std::result::Result<Expr, String> {
+        let left = self.postfix()?;
+        if let Some(Token::Op(op)) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            return Ok(Expr::Compare(Box::new(left), op, Box::new(self.postfix()?)));
+        }
+        Ok(left)
+    }
+
+    fn postfix(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.primary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Field(name)) => {
+                    let key = Expr::Literal(Value::String(name.clone()));
+                    self.pos += 1;
+                    expr = Expr::Index(Box::new(expr), Box::new(key));
+                }
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    if self.eat(&Token::RBracket) {
+                        expr = Expr::Iterate(Box::new(expr));
+                    } else {
+                        let index = self.pipe()?;
+                        self.expect(&Token::RBracket)?;
+                        expr = Expr::Index(Box::new(expr), Box::new(index));
+                    }
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    fn primary(&mut self) -> std::result::Result<Expr, String> {
+        match self.advance() {
+            Some(Token::Dot) => Ok(Expr::Identity),
+            Some(Token::Field(name)) => Ok(Expr::Index(
+                Box::new(Expr::Identity),
+                Box::new(Expr::Literal(Value::String(name))),
+            )),
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Num(n)) => Ok(Expr::Literal(Value::Number(n))),
+            Some(Token::LParen) => {
+                let expr = self.pipe()?;
+                self.expect(&Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::LBracket) => {
+                if self.eat(&Token::RBracket) {
+                    return Ok(Expr::Array(None));
+                }
+                let expr = self.pipe()?;
+                self.expect(&Token::RBracket)?;
+                Ok(Expr::Array(Some(Box::new(expr))))
+            }
+            Some(Token::LBrace) => self.object(),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ => {
+                    let (func, takes_arg) =
+                        Func::lookup(&name).ok_or_else(|| format!("unknown function '{name}'"))?;
+                    if !takes_arg {
+                        return Ok(Expr::Call(func, None));
+                    }
+                    self.expect(&Token::LParen)
+                        .map_err(|_| format!("'{name}' takes an argument: {name}(...)"))?;
+                    let arg = self.pipe()?;
+                    self.expect(&Token::RParen)?;
+                    Ok(Expr::Call(func, Some(Box::new(arg))))
+                }
+            },
+            Some(token) => Err(format!("unexpected {token}")),
+            None => Err("unexpected end of filter".to_string()),
+        }
+    }
+
+    fn object(&mut self) -> std::result::Result<Expr, String> {
+        let mut fields = Vec::new();
+        if self.eat(&Token::RBrace) {
+            return Ok(Expr::Object(fields));
+        }
+        loop {
+            let key = match self.advance() {
+                Some(Token::Ident(name) | Token::Str(name)) => name,
+                Some(token) => return Err(format!("expected an object key, found {token}")),
+                None => return Err("unterminated object".to_string()),
+            };
+            let value = if self.eat(&Token::Colon) {
+                self.or()?
+            } else {
+                Expr::Index(
+                    Box::new(Expr::Identity),
+                    Box::new(Expr::Literal(Value::String(key.clone()))),
+                )
+            };
+            fields.push((key, value));
+            if self.eat(&Token::RBrace) {
+                return Ok(Expr::Object(fields));
+            }
+            self.expect(&Token::Comma)?;
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+/// jq's total order: null < false < true < numbers < strings < arrays < objects.
+fn compare(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(false) => 1,
+            Value::Bool(true) => 2,
+            Value::Number(_) => 3,
+            Value::String(_) => 4,
+            Value::Array(_) => 5,
+            Value::Object(_) => 6,
+        }
+    }
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Array(x), Value::Array(y)) => x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| compare(x, y))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| x.len().cmp(&y.len())),
+        (Value::Object(x), Value::Object(y)) => {
+            let mut x_keys: Vec<&String> = x.keys().collect();
+            let mut y_keys: Vec<&String> = y.keys().collect();
+            x_keys.sort();
+            y_keys.sort();
+            x_keys.cmp(&y_keys).then_with(|| {
+                x_keys
+                    .iter()
+                    .map(|key| compare(&x[*key], &y[*key]))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+        }
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// jq `contains`: substrings for strings, recursive containment otherwise.
+fn contains(a: &Value, b: &Value) -> std::result::Result<bool, String> {
+    Ok(match (a, b) {
+        (Value::String(x), Value::String(y)) => x.contains(y.as_str()),
+        (Value::Array(x), Value::Array(y)) => {
+            for wanted in y {
+                let mut found = false;
+                for have in x {
+                    if contains(have, wanted).unwrap_or(false) {
+                        found = true;
+                        break;
+                    }
+                }
+                if !found {
+                    return Ok(false);
+                }
+            }
+            true
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            for (key, wanted) in y {
+                match x.get(key) {
+                    Some(have) if contains(have, wanted)? => {}
+                    _ => return Ok(false),
+                }
+            }
+            true
+        }
+        _ if type_name(a) == type_name(b) => a == b,
+        _ => {
+            return Err(format!(
+                "{} and {} cannot have their containment checked",
+                type_name(a),
+                type_name(b)
+            ))
+        }
+    })
+}
+
+fn index(target: &Value, key: &Value) -> std::result::Result<Value, String> {
+    match (target, key) {
+        (Value::Object(map), Value::String(key)) => {
+            Ok(map.get(key).cloned().unwrap_or(Value::Null))
+        }
+        (Value::Array(items), Value::Number(n)) => {
+            let Some(i) = n.as_i64() else {
+                return Err(format!("array index {n} is not an integer"));
+            };
+            let i = if i < 0 {
+                i64::try_from(items.len()).unwrap_or(i64::MAX) + i
+            } else {
+                i
+            };
+            Ok(usize::try_from(i)
+                .ok()
+                .and_then(|i| items.get(i))
+                .cloned()
+                .unwrap_or(Value::Null))
+        }
+        (Value::Null, Value::String(_) | Value::Number(_)) => Ok(Value::Null),
+        _ => Err(format!(
+            "cannot index {} with {}",
+            type_name(target),
+            type_name(key)
+        )),
+    }
+}
+
+fn iterate(value: &Value) -> std::result::Result<Vec<Value>, String> {
+    match value {
+        Value::Array(items) => Ok(items.clone()),
+        Value::Object(map) => Ok(map.values().cloned().collect()),
+        _ => Err(format!("cannot iterate over {}", type_name(value))),
+    }
+}
+
+fn eval(expr: &Expr, input: &Value) -> std::result::Result<Vec<Value>, String> {
+    match expr {
+        Expr::Identity => Ok(vec![input.clone()]),
+        Expr::Literal(value) => Ok(vec![value.clone()]),
+        Expr::Index(target, key) => {
+            let mut out = Vec::new();
+            for target in eval(target, input)? {
+                for key in eval(key, input)? {
+                    out.push(index(&target, &key)?);
+                }
+            }
+            Ok(out)
+        }
+        Expr::Iterate(target) => {
+            let mut out = Vec::new();
+            for target in eval(target, input)? {
+                out.extend(iterate(&target)?);
+            }
+            Ok(out)
+        }
+        Expr::Pipe(left, right) => {
+            let mut out = Vec::new();
+            for value in eval(left, input)? {
+                out.extend(eval(right, &value)?);
+            }
+            Ok(out)
+        }
+        Expr::Comma(left, right) => {
+            let mut out = eval(left, input)?;
+            out.extend(eval(right, input)?);
+            Ok(out)
+        }
+        Expr::Compare(left, op, right) => {
+            let mut out = Vec::new();
+            for r in eval(right, input)? {
+                for l in eval(left, input)? {
+                    out.push(Value::Bool(op.test(compare(&l, &r))));
+                }
+            }
+            Ok(out)
+        }
+        Expr::And(left, right) | Expr::Or(left, right) => {
+            let is_and = matches!(expr, Expr::And(..));
+            let mut out = Vec::new();
+            for l in eval(left, input)? {
+                if truthy(&l) != is_and {
+                    out.push(Value::Bool(!is_and));
+                    continue;
+                }
+                for r in eval(right, input)? {
+                    out.push(Value::Bool(truthy(&r)));
+                }
+            }
+            Ok(out)
+        }
+        Expr::Array(None) => Ok(vec![Value::Array(Vec::new())]),
+        Expr::Array(Some(inner)) => Ok(vec![Value::Array(eval(inner, input)?)]),
+        Expr::Object(fields) => {
+            // Each field may yield several values; build every combination.
+            let mut objects = vec![Map::new()];
+            for (key, value) in fields {
+                let values = eval(value, input)?;
+                let mut next = Vec::with_capacity(objects.len() * values.len());
+                for object in &objects {
+                    for value in &values {
+                        let mut object = object.clone();
+                        object.insert(key.clone(), value.clone());
+                        next.push(object);
+                    }
+                }
+                objects = next;
+            }
+            Ok(objects.into_iter().map(Value::Object).collect())
+        }
+        Expr::Call(func, arg) => call(*func, arg.as_deref(), input),
+    }
+}
+
+fn call(func: Func, arg: Option<&Expr>, input: &Value) -> std::result::Result<Vec<Value>, String> {
+    let arg_values = || eval(arg.unwrap_or(&Expr::Identity), input);
+    match func {
+        Func::Select => Ok(arg_values()?
+            .iter()
+            .filter(|value| truthy(value))
+            .map(|_| input.clone())
+            .collect()),
+        Func::Map => {
+            let mut mapped = Vec::new();
+            for item in iterate(input)? {
+                mapped.extend(eval(arg.unwrap_or(&Expr::Identity), &item)?);
+            }
+            Ok(vec![Value::Array(mapped)])
+        }
+        Func::Has => arg_values()?
+            .iter()
+            .map(|key| match (input, key) {
+                (Value::Object(map), Value::String(key)) => Ok(Value::Bool(map.contains_key(key))),
+                (Value::Array(items), Value::Number(n)) => Ok(Value::Bool(
+                    n.as_u64()
+                        .and_then(|i| usize::try_from(i).ok())
+                        .is_some_and(|i| i < items.len()),
+                )),
+                _ => Err(format!(
+                    "cannot check whether {} has a {} key",
+                    type_name(input),
+                    type_name(key)
+                )),
+            })
+            .collect(),
+        Func::Contains => arg_values()?
+            .iter()
+            .map(|wanted| contains(input, wanted).map(Value::Bool))
+            .collect(),
+        Func::Test => {
+            let Value::String(text) = input else {
+                return Err(format!(
+                    "{} cannot be matched, as it is not a string",
+                    type_name(input)
+                ));
+            };
+            arg_values()?
+                .iter()
+                .map(|pattern| {
+                    let Value::String(pattern) = pattern else {
+                        return Err(format!("{} is not a regex string", type_name(pattern)));
+                    };
+                    let regex = Regex::new(pattern).map_err(|e| format!("invalid regex: {e}"))?;
+                    Ok(Value::Bool(regex.is_match(text)))
+                })
+                .collect()
+        }
+        Func::Length => Ok(vec![match input {
+            Value::Null => Value::from(0),
+            Value::Bool(_) => return Err("boolean has no length".to_string()),
+            Value::Number(n) => n
+                .as_f64()
+                .and_then(|f| Number::from_f64(f.abs()))
+                .map_or(Value::Null, Value::Number),
+            Value::String(s) => Value::from(s.chars().count()),
+            Value::Array(items) => Value::from(items.len()),
+            Value::Object(map) => Value::from(map.len()),
+        }]),
+        Func::Keys => Ok(vec![match input {
+            Value::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                Value::Array(keys.into_iter().cloned().map(Value::String).collect())
+            }
+            Value::Array(items) => Value::Array((0..items.len()).map(Value::from).collect()),
+            _ => return Err(format!("{} has no keys", type_name(input))),
+        }]),
+        Func::Not => Ok(vec![Value::Bool(!truthy(input))]),
+        Func::First | Func::Last => match input {
+            Value::Array(items) => Ok(vec![if func == Func::First {
+                items.first()
+            } else {
+                items.last()
+            }
+            .cloned()
+            .unwrap_or(Value::Null)]),
+            Value::Null => Ok(vec![Value::Null]),
+            _ => Err(format!("cannot take an element of {}", type_name(input))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(filter: &str, input: &Value) -> Vec<Value> {
+        filter.parse::<JsonFilter>().unwrap().apply(input).unwrap()
+    }
+
+    #[test]
+    fn test_paths_and_iteration() {
+        let input = json!({
+            "sessions": [
+                { "id": "a", "meta": { "tokens": 10 } },
+                { "id": "b", "meta": { "tokens": 30 } },
+            ]
+        });
+        assert_eq!(run(".", &input), vec![input.clone()]);
+        assert_eq!(run(".sessions[].id", &input), vec![json!("a"), json!("b")]);
+        assert_eq!(run(".sessions[-1].meta.tokens", &input), vec![json!(30)]);
+        assert_eq!(run(".sessions[5]", &input), vec![Value::Null]);
+        assert_eq!(run(".\"sessions\" | length", &input), vec![json!(2)]);
+        assert_eq!(run(".missing.deeper", &input), vec![Value::Null]);
+    }
+
+    #[test]
+    fn test_select_and_construction() {
+        let input = json!([
+            { "id": "a", "project": "/work/app", "size": 5 },
+            { "id": "b", "project": "/work/lib", "size": 50 },
+            { "id": "c", "project": "/home/x", "size": 500 },
+        ]);
+        assert_eq!(
+            run(
+                "[.[] | select(.size >= 50 and (.project | test(\"^/work\"))) | {id, kb: .size}]",
+                &input
+            ),
+            vec![json!([{ "id": "b", "kb": 50 }])]
+        );
+        assert_eq!(
+            run("map(.id) | contains([\"c\"]), length", &input),
+            vec![json!(true), json!(3)]
+        );
+        assert_eq!(
+            run("first | keys", &input),
+            vec![json!(["id", "project", "size"])]
+        );
+        assert_eq!(
+            run(".[] | select(.id == \"a\" or .size > 100) | .id", &input),
+            vec![json!("a"), json!("c")]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        for bad in [
+            ".[",
+            "select",
+            ".a ==",
+            "frobnicate",
+            "{1: .a}",
+            ".a = 1",
+            "\"open",
+        ] {
+            assert!(bad.parse::<JsonFilter>().is_err(), "{bad} should not parse");
+        }
+        let filter: JsonFilter = ".[]".parse().unwrap();
+        let err = filter.apply(&json!("text")).unwrap_err().to_string();
+        assert!(err.contains("cannot iterate over string"), "{err}");
+    }
+}
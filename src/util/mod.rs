@@ -4,6 +4,10 @@
 //! - Atomic file operations for data safety
 //! - Path utilities
 //! - Sensitive data redaction
+//! - jq-style filtering of JSON output
+
+pub mod age;
+pub mod json_filter;
 
 use std::borrow::Cow;
 use std::io::{self, Write};
@@ -1034,10 +1038,114 @@ pub mod pager {
     }
 }
 
+/// System clipboard access with an OSC 52 terminal fallback.
+///
+/// Over SSH, or where no system clipboard is reachable, text is sent to the
+/// terminal as an OSC 52 escape sequence, which most terminal emulators (and
+/// tmux with `set-clipboard on`) copy into the local clipboard.
+pub mod clipboard {
+    use std::io::{self, IsTerminal, Write};
+
+    use base64::Engine as _;
+
+    /// How copied text reached the clipboard.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ClipboardMethod {
+        /// The system clipboard.
+        System,
+        /// An OSC 52 escape sequence written to the terminal.
+        Osc52,
+    }
+
+    impl ClipboardMethod {
+        /// Name of the clipboard for status messages.
+        #[must_use]
+        pub const fn describe(self) -> &'static str {
+            match self {
+                Self::System => "clipboard",
+                Self::Osc52 => "terminal clipboard (OSC 52)",
+            }
+        }
+    }
+
+    /// Copy `text` to the clipboard.
+    ///
+    /// Uses OSC 52 directly over SSH, where the system clipboard would be the
+    /// remote host's, and as a fallback when the system clipboard fails.
+    pub fn copy(text: &str) -> Result<ClipboardMethod, String> {
+        if std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some() {
+            return copy_osc52(text).map(|()| ClipboardMethod::Osc52);
+        }
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+            Ok(()) => Ok(ClipboardMethod::System),
+            Err(system) => copy_osc52(text)
+                .map(|()| ClipboardMethod::Osc52)
+                .map_err(|osc52| format!("{system} (OSC 52 fallback: {osc52})")),
+        }
+    }
+
+    fn copy_osc52(text: &str) -> Result<(), String> {
+        let mut stderr = io::stderr();
+        if !stderr.is_terminal() {
+            return Err("stderr is not a terminal".to_string());
+        }
+        let sequence = osc52_sequence(text, std::env::var_os("TMUX").is_some());
+        stderr
+            .write_all(sequence.as_bytes())
+            .and_then(|()| stderr.flush())
+            .map_err(|e| e.to_string())
+    }
+
+    /// The OSC 52 sequence setting the clipboard to `text`, wrapped in a
+    /// tmux passthrough when `tmux` is set.
+    #[must_use]
+    pub fn osc52_sequence(text: &str, tmux: bool) -> String {
+        let payload = base64::engine::general_purpose::STANDARD.encode(text);
+        if tmux {
+            format!("\x1bPtmux;\x1b\x1b]52;c;{payload}\x07\x1b\\")
+        } else {
+            format!("\x1b]52;c;{payload}\x07")
+        }
+    }
+}
+
 // ============================================================================
 // Display Utilities
 // ============================================================================
 
+/// Serde helpers for maps whose keys are not strings.
+///
+/// JSON object keys must be strings, so an `IndexMap` keyed by a struct or a
+/// tuple is written as a list of `[key, value]` pairs instead. Use with
+/// `#[serde(with = "crate::util::serde_pairs")]`.
+pub mod serde_pairs {
+    use std::hash::Hash;
+
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize the map as a sequence of `(key, value)` pairs.
+    pub fn serialize<K, V, S>(map: &IndexMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    /// Deserialize a sequence of `(key, value)` pairs, keeping their order.
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<IndexMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
+
 /// Truncate a path for display using smart middle truncation.
 ///
 /// This function preserves both the beginning context (username, root) and
@@ -1281,6 +1389,15 @@ mod tests {
     use std::io::Read;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(clipboard::osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            clipboard::osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
     #[test]
     fn test_atomic_write() {
         let dir = tempdir().unwrap();
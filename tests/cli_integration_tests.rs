@@ -243,6 +243,87 @@ fn test_list_sessions_json_contains_session_id() {
         .stdout(predicate::str::contains("aaaaaaaa"));
 }
 
+#[test]
+fn test_list_and_export_filter_json_output() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["list", "sessions", "-o", "json", "--full-ids"])
+        .args(["--filter", "[.[] | {session_id, chain_member_count}]"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let rows: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        rows,
+        serde_json::json!([{ "session_id": SESSION_ID, "chain_member_count": 1 }])
+    );
+
+    // Each filter output is its own document.
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["export", SESSION_ID, "-f", "json"])
+        .args(["--filter", ".entries[] | select(.type == \"user\") | .uuid"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"11111111-1111-1111-1111-111111111111\"",
+        ))
+        .stdout(predicate::str::contains("22222222").not());
+
+    // Filters apply to JSON only and are refused elsewhere.
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["list", "sessions", "--filter", "."])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--json"));
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["export", SESSION_ID, "--filter", "."])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("json-pretty"));
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["list", "sessions", "--json", "--filter", ".[] |"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected end of filter"));
+}
+
+#[test]
+fn test_export_encrypt_refuses_unusable_targets() {
+    let tmp = setup_fixture_dir();
+    // Checked before the age CLI is looked for, so these hold without it.
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["export", SESSION_ID, "--encrypt", "passphrase"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--out"));
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["export", SESSION_ID, "-O", "out.age"])
+        .args(["--encrypt", "passphrase", "--encrypt", "age:age1abc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("passphrase on its own"));
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["export", "--all", "-O", "out", "--encrypt", "age:age1abc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--all"));
+    snatch_cmd()
+        .args(["export", SESSION_ID, "--encrypt", "gpg:me"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("age:<recipient>"));
+}
+
 #[test]
 fn flagless_file_history_keeps_the_classic_json_shape() {
     let tmp = setup_file_snapshot_dir();
@@ -354,7 +435,7 @@ fn provider_index_cli_build_search_status_and_clear_are_snapshot_backed() {
         .stdout
         .clone();
     let status: serde_json::Value = serde_json::from_slice(&status).unwrap();
-    assert_eq!(status["schema_version"], 4);
+    assert_eq!(status["schema_version"], 6);
     assert_eq!(status["session_count"], 1);
     assert_eq!(status["entry_count"], 6);
     assert_eq!(status["build"]["complete_providers"][0], "claude-code");
@@ -389,6 +470,32 @@ fn provider_index_cli_build_search_status_and_clear_are_snapshot_backed() {
     );
     assert_eq!(indexed["coverage"]["incomplete"], false);
 
+    let query_total = |query: &str| {
+        let output = snatch_cmd()
+            .args([
+                "--config",
+                config.to_str().unwrap(),
+                "-o",
+                "json",
+                "index",
+                "search",
+                query,
+            ])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        serde_json::from_slice::<serde_json::Value>(&output).unwrap()["total_matches"]
+            .as_u64()
+            .unwrap()
+    };
+    let directory = query_total("directory");
+    assert_eq!(query_total("directory OR xyzzy_absent"), directory);
+    assert_eq!(query_total("directory xyzzy_absent"), 0);
+    assert_eq!(query_total("directory -directory"), 0);
+    assert_eq!(query_total("std::fs https://example.com"), 0);
+
     let count = snatch_cmd()
         .args([
             "--config",
@@ -463,6 +570,81 @@ fn provider_index_cli_build_search_status_and_clear_are_snapshot_backed() {
     assert!(cleared["build"].is_null());
 }
 
+#[test]
+fn provider_index_tags_sessions_with_their_configured_source() {
+    const LAPTOP_SESSION_ID: &str = "eeeeeeee-ffff-0000-1111-222222222222";
+    let claude = setup_fixture_dir();
+    let laptop = TempDir::new().unwrap();
+    let project_dir = laptop
+        .path()
+        .join("projects")
+        .join(encode_project_path(PROJECT_PATH));
+    std::fs::create_dir_all(&project_dir).unwrap();
+    let line = serde_json::json!({
+        "type": "user", "uuid": "laptop-user", "parentUuid": null,
+        "timestamp": "2025-01-16T10:00:00Z", "sessionId": LAPTOP_SESSION_ID,
+        "version": "2.0.74", "message": {"role": "user",
+            "content": "Which directory holds the laptop backups?"}
+    });
+    std::fs::write(
+        project_dir.join(format!("{LAPTOP_SESSION_ID}.jsonl")),
+        format!("{line}\n"),
+    )
+    .unwrap();
+    let config_home = TempDir::new().unwrap();
+    let (config, _) = write_index_config(&config_home);
+    let mut toml = std::fs::read_to_string(&config).unwrap();
+    toml.push_str(&format!(
+        "\n[[index.sources]]\nname = \"laptop\"\npath = {}\n",
+        serde_json::to_string(&laptop.path().to_string_lossy()).unwrap()
+    ));
+    std::fs::write(&config, toml).unwrap();
+
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", claude.path())
+        .env("CODEX_HOME", config_home.path().join("missing-codex"))
+        .args(["--config", config.to_str().unwrap(), "index", "build"])
+        .assert()
+        .success();
+
+    let sources = |args: &[&str]| {
+        let output = snatch_cmd()
+            .args([
+                "--config",
+                config.to_str().unwrap(),
+                "-o",
+                "json",
+                "index",
+                "search",
+            ])
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let response: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        response["matches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| result["source"].as_str().unwrap().to_string())
+            .collect::<std::collections::BTreeSet<_>>()
+    };
+    let names = |names: &[&str]| {
+        names
+            .iter()
+            .map(ToString::to_string)
+            .collect::<std::collections::BTreeSet<_>>()
+    };
+    assert_eq!(sources(&["directory"]), names(&["laptop", "local"]));
+    assert_eq!(
+        sources(&["directory", "--source", "laptop"]),
+        names(&["laptop"])
+    );
+    assert_eq!(sources(&["directory source:local"]), names(&["local"]));
+}
+
 #[test]
 fn provider_index_requires_explicit_rebuild_of_a_legacy_schema() {
     let claude = setup_fixture_dir();
@@ -1092,6 +1274,129 @@ fn test_info_shows_session_metadata() {
         .stdout(predicate::str::contains("aaaaaaaa"));
 }
 
+// =============================================================================
+// delimited (TSV/CSV) output
+// =============================================================================
+
+/// Run snatch against the fixture and return stdout lines.
+fn fixture_stdout_lines(args: &[&str]) -> Vec<String> {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn test_list_tsv_headers_are_stable() {
+    let lines = fixture_stdout_lines(&["-o", "tsv", "list", "sessions"]);
+    assert_eq!(
+        lines[0],
+        "session_id\tproject\tsize\tmodified\tsubagent\tname\tmember_count\tlatest_session_id"
+    );
+
+    let lines = fixture_stdout_lines(&["-o", "tsv", "list", "sessions", "--no-chain"]);
+    assert_eq!(
+        lines[0],
+        "session_id\tproject\tsize\tmodified\tsubagent\tname"
+    );
+
+    let lines = fixture_stdout_lines(&["-o", "tsv", "list", "projects"]);
+    assert_eq!(lines[0], "path\tencoded\tsession_count");
+}
+
+#[test]
+fn test_stats_tsv_header_is_stable() {
+    let lines = fixture_stdout_lines(&["-o", "tsv", "stats", SESSION_ID]);
+    assert_eq!(
+        lines[0],
+        "scope\tprojects\tsessions\tmessages\tinput_tokens\toutput_tokens\t\
+         cache_read_tokens\tcache_creation_tokens\ttotal_tokens\ttotal_processed_tokens\t\
+         tool_invocations\tcache_hit_rate\testimated_cost\tprovider\tqualified_id\tpricing_policy\t\
+         current_streak\tlongest_streak\tactive_days\tavg_daily_sessions"
+    );
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].starts_with("session\t1\t1\t6\t75\t65\t"));
+}
+
+#[test]
+fn test_search_tsv_header_is_stable() {
+    let lines = fixture_stdout_lines(&["-o", "tsv", "search", "Hello"]);
+    assert_eq!(lines[0], "session\tproject\tuuid\ttype\tlocation\tline");
+    assert!(lines.len() > 1);
+}
+
+#[test]
+fn test_search_csv_header_and_quoting() {
+    let lines = fixture_stdout_lines(&["-o", "csv", "search", "Hello, Claude"]);
+    assert_eq!(lines[0], "session,project,uuid,type,location,line");
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].ends_with(",\"Hello, Claude!\""), "{}", lines[1]);
+
+    // Several patterns count in one pass; the counts are CSV too.
+    let lines = fixture_stdout_lines(&["-o", "csv", "search", "Hello, Claude", "files"]);
+    assert_eq!(lines[0], "pattern,count");
+    assert_eq!(lines[1], "\"Hello, Claude\",1");
+    assert!(lines[2].starts_with("files,"), "{}", lines[2]);
+
+    let lines = fixture_stdout_lines(&[
+        "-o",
+        "csv",
+        "search",
+        "Hello, Claude",
+        "files",
+        "--columns",
+        "count",
+        "--no-header",
+    ]);
+    assert_eq!(lines[0], "1");
+}
+
+#[test]
+fn test_csv_columns_and_no_header() {
+    let lines = fixture_stdout_lines(&[
+        "-o",
+        "csv",
+        "stats",
+        SESSION_ID,
+        "--columns",
+        "output_tokens,input_tokens",
+        "--no-header",
+    ]);
+    assert_eq!(lines, vec!["65,75".to_string()]);
+
+    let lines = fixture_stdout_lines(&[
+        "-o",
+        "csv",
+        "list",
+        "sessions",
+        "--columns",
+        "session_id,subagent",
+    ]);
+    assert_eq!(lines, vec!["session_id,subagent", "aaaaaaaa,false"]);
+}
+
+#[test]
+fn test_unknown_column_is_rejected() {
+    let tmp = setup_fixture_dir();
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "tsv", "list", "sessions", "--columns", "bogus"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown column 'bogus'"))
+        .stderr(predicate::str::contains("session_id"));
+}
+
 // =============================================================================
 // stats
 // =============================================================================
@@ -1125,6 +1430,422 @@ fn test_stats_json_contains_token_counts() {
         .stdout(predicate::str::contains("65"));
 }
 
+#[test]
+fn test_stats_global_json_includes_streaks() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", "--global"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    // One fixture session in January 2025: a single active day, long past.
+    assert_eq!(value["streaks"]["active_days"], 1);
+    assert_eq!(value["streaks"]["longest_streak"], 1);
+    assert_eq!(value["streaks"]["current_streak"], 0);
+}
+
+#[test]
+fn test_stats_global_reuses_persisted_snapshots() {
+    let tmp = setup_fixture_dir();
+    let cache = TempDir::new().unwrap();
+    let global_tokens = |extra: &[&str]| {
+        let output = snatch_cmd()
+            .env("SNATCH_CLAUDE_DIR", tmp.path())
+            .env("SNATCH_CACHE_DIR", cache.path())
+            .args(["-o", "json", "stats", "--global"])
+            .args(extra)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        value["projects"][0]["total_tokens"].clone()
+    };
+
+    assert_eq!(global_tokens(&[]), 140);
+    let snapshots = cache.path().join("analytics_snapshots.json");
+    assert!(snapshots.exists());
+
+    // A second run reuses every snapshot, so the store is left untouched.
+    let written = std::fs::metadata(&snapshots).unwrap().modified().unwrap();
+    assert_eq!(global_tokens(&[]), 140);
+    assert_eq!(
+        std::fs::metadata(&snapshots).unwrap().modified().unwrap(),
+        written
+    );
+    assert_eq!(global_tokens(&["--rebuild-snapshots"]), 140);
+}
+
+#[test]
+fn test_stats_global_json_includes_project_leaderboard() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", "--global", "--rank-by", "sessions"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let projects = value["projects"].as_array().unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0]["rank"], 1);
+    assert_eq!(projects[0]["project"], "/home/user/test-project");
+    assert_eq!(projects[0]["sessions"], 1);
+    assert_eq!(projects[0]["total_tokens"], 140);
+}
+
+#[test]
+fn test_stats_agents_json_attributes_parent_usage() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", SESSION_ID, "--agents"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(value["parent"]["sessions"], 1);
+    assert_eq!(value["parent"]["tokens"], 140);
+    assert_eq!(value["parent"]["tool_calls"], 1);
+    assert_eq!(value["subagents"]["tokens"], 0);
+    assert_eq!(value["by_agent_type"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_stats_languages_json_counts_lines_per_language() {
+    let tmp = setup_fixture_dir();
+    let session_id = "11111111-2222-3333-4444-555555555555";
+    let entry = serde_json::json!({
+        "type": "assistant", "uuid": "lang-assistant", "parentUuid": null,
+        "timestamp": "2025-01-16T10:00:00Z", "sessionId": session_id, "version": "2.0.74",
+        "message": {"id": "lang-message", "type": "message", "role": "assistant",
+            "model": "claude-sonnet-4-20250514", "usage": {"input_tokens": 1, "output_tokens": 1},
+            "content": [
+                {"type": "tool_use", "id": "toolu_w", "name": "Write",
+                    "input": {"file_path": "/work/src/lib.rs", "content": "a\nb\nc"}},
+                {"type": "tool_use", "id": "toolu_e", "name": "Edit",
+                    "input": {"file_path": "/work/app.py", "old_string": "x\ny", "new_string": "x"}}
+            ]}
+    });
+    let project_dir = tmp
+        .path()
+        .join("projects")
+        .join(encode_project_path(PROJECT_PATH));
+    std::fs::write(
+        project_dir.join(format!("{session_id}.jsonl")),
+        format!("{entry}\n"),
+    )
+    .unwrap();
+
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", "--languages"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let languages = value["languages"].as_array().unwrap();
+    assert_eq!(languages.len(), 2);
+    assert_eq!(languages[0]["language"], "Rust");
+    assert_eq!(languages[0]["lines_added"], 3);
+    assert_eq!(languages[1]["language"], "Python");
+    assert_eq!(languages[1]["lines_removed"], 1);
+    assert_eq!(value["projects"][0]["project"], PROJECT_PATH);
+}
+
+#[test]
+fn test_stats_correlation_json_fits_prompt_length() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", SESSION_ID, "--correlation"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    // Two human prompts (14 and 49 chars) answered with 15 and 30 + 20 output
+    // tokens; the tool result continues the second turn.
+    assert_eq!(value["prompts"], 2);
+    let slope = value["response_tokens_fit"]["slope"].as_f64().unwrap();
+    assert!((slope - 1.0).abs() < 1e-9);
+    assert_eq!(value["buckets"][0]["prompt_length"], "<100");
+    assert_eq!(value["buckets"][0]["prompts"], 2);
+    assert_eq!(value["buckets"][0]["avg_latency_secs"], 2.0);
+}
+
+#[test]
+fn test_stats_tool_chains_json_single_call_has_no_transitions() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", SESSION_ID, "--tool-chains"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    // The fixture makes a single Bash call, so there is nothing to chain.
+    assert_eq!(value["sessions"], 1);
+    assert_eq!(value["transitions"].as_array().unwrap().len(), 0);
+    assert_eq!(value["chains"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_stats_branches_json_linear_session_has_none_abandoned() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", SESSION_ID, "--branches"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(value["sessions"], 1);
+    assert_eq!(value["abandoned_branches"], 0);
+    assert_eq!(value["tokens"], 0);
+    assert_eq!(value["by_session"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_stats_prometheus_format_exposes_project_counters() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["stats", "--global", "--format", "prometheus"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(output).unwrap();
+    assert!(text.contains("# TYPE snatch_tokens_total counter\n"));
+    assert!(text.contains(&format!(
+        "snatch_tokens_total{{project=\"{PROJECT_PATH}\",type=\"output\"}} 65\n"
+    )));
+    assert!(text.contains(&format!(
+        "snatch_sessions{{project=\"{PROJECT_PATH}\"}} 1\n"
+    )));
+}
+
+#[test]
+fn test_stats_rolling_json_windows_by_entry_time() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args([
+            "-o",
+            "json",
+            "stats",
+            "--global",
+            "--rolling",
+            "--windows",
+            "24h,100000d",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    // The fixture conversation took place in January 2025.
+    assert_eq!(value["windows"][0]["window"], "24h");
+    assert_eq!(value["windows"][0]["messages"], 0);
+    assert_eq!(value["windows"][1]["sessions"], 1);
+    assert_eq!(value["windows"][1]["messages"], 6);
+    assert_eq!(value["windows"][1]["tokens"], 140);
+}
+
+#[test]
+fn test_stats_rolling_rejects_unbounded_window() {
+    let tmp = setup_fixture_dir();
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["stats", "--rolling", "--windows", "all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("positive period"));
+}
+
+#[test]
+fn test_stats_errors_json_counts_turns_without_errors() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", SESSION_ID, "--errors"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(value["api_errors"], 0);
+    assert_eq!(value["retried_turns"], 0);
+    assert_eq!(value["wasted_tokens"], 0);
+    assert_eq!(value["by_day"][0]["turns"], 3);
+    assert_eq!(value["by_day"][0]["errors"], 0);
+}
+
+#[test]
+fn test_stats_json_reports_model_switches() {
+    let tmp = TempDir::new().unwrap();
+    let project_dir = tmp
+        .path()
+        .join("projects")
+        .join(encode_project_path(PROJECT_PATH));
+    std::fs::create_dir_all(&project_dir).unwrap();
+    let sonnet = "claude-sonnet-4-20250514";
+    let opus = "claude-opus-4-20250514";
+    let lines: Vec<String> = [
+        ("a1", None, "2026-07-22T00:00:00Z", sonnet, 10),
+        ("a2", Some("a1"), "2026-07-22T00:01:00Z", opus, 20),
+    ]
+    .into_iter()
+    .map(|(uuid, parent, timestamp, model, output)| {
+        serde_json::json!({
+            "type": "assistant", "uuid": uuid, "parentUuid": parent,
+            "timestamp": timestamp, "sessionId": SESSION_ID, "version": "2.0.74",
+            "message": {"id": format!("msg_{uuid}"), "type": "message",
+                "role": "assistant", "model": model,
+                "content": [{"type": "text", "text": "ok"}],
+                "usage": {"input_tokens": 0, "output_tokens": output}}
+        })
+        .to_string()
+    })
+    .collect();
+    std::fs::write(
+        project_dir.join(format!("{SESSION_ID}.jsonl")),
+        lines.join("\n") + "\n",
+    )
+    .unwrap();
+
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", SESSION_ID])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let switches = &value["model_switches"];
+    assert_eq!(switches["switches"], 1);
+    assert_eq!(switches["transitions"][0]["from"], sonnet);
+    assert_eq!(switches["transitions"][0]["to"], opus);
+    assert_eq!(switches["tokens_before_switch"], 10);
+    assert_eq!(switches["tokens_after_switch"], 20);
+}
+
+#[test]
+fn test_stats_churn_json_without_edits() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", "--churn"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    // The fixture session runs a single Bash call and never edits a file.
+    assert_eq!(value["sessions"], 1);
+    assert_eq!(value["sessions_with_edits"], 0);
+    assert_eq!(value["rewrites"], 0);
+    assert!(value["hotspots"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_stats_co_modification_dot_and_format_guard() {
+    let tmp = setup_fixture_dir();
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["stats", "--co-modification", "--format", "dot"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("graph co_modification {\n"));
+
+    snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["stats", "--format", "dot"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires --co-modification"));
+}
+
+#[test]
+fn test_stats_session_timeline_json_per_turn() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", SESSION_ID, "--timeline"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(value["session_id"], SESSION_ID);
+    let turns = value["turns"].as_array().unwrap();
+    assert_eq!(turns.len(), 3);
+    assert_eq!(turns[0]["turn"], 1);
+    assert_eq!(turns[0]["output_tokens"], 15);
+    assert_eq!(turns[2]["cumulative_output_tokens"], 65);
+}
+
+#[test]
+fn test_stats_heatmap_json_totals() {
+    let tmp = setup_fixture_dir();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", "--heatmap"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    // Three user + three assistant entries; 75 input + 65 output tokens.
+    assert_eq!(value["total_messages"], 6);
+    assert_eq!(value["total_tokens"], 140);
+    assert_eq!(value["messages"].as_array().unwrap().len(), 7);
+    assert_eq!(value["messages"][0].as_array().unwrap().len(), 24);
+    assert_eq!(value["peak_tokens"]["value"], 140);
+}
+
+#[test]
+fn test_stats_heatmap_json_without_activity_is_an_empty_heatmap() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::create_dir_all(tmp.path().join("projects")).unwrap();
+    let output = snatch_cmd()
+        .env("SNATCH_CLAUDE_DIR", tmp.path())
+        .args(["-o", "json", "stats", "--heatmap"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(value["total_messages"], 0);
+    assert_eq!(value["messages"].as_array().unwrap().len(), 7);
+    assert!(value["peak_messages"].is_null());
+}
+
 #[test]
 fn test_summary_counts_every_project_not_only_the_top_five() {
     let tmp = TempDir::new().unwrap();
@@ -4042,7 +4763,7 @@ fn provider_normalized_export_refuses_nonportable_flags_individually() {
 #[test]
 fn provider_doctor_refuses_unsupported_filters_individually() {
     let tmp = setup_fixture_dir();
-    let cases: &[&[&str]] = &[&["proj"], &["--since", "1d"], &["--subagents"]];
+    let cases: &[&[&str]] = &[&["proj"], &["--since", "1d"], &["--subagents"], &["--fix"]];
     for extra in cases {
         let mut args = vec!["doctor", "--provider", "claude-code"];
         args.extend_from_slice(extra);
@@ -317,6 +317,55 @@ fn malformed_lines_skipped_with_diagnostics() {
     );
 }
 
+#[test]
+fn legacy_formats_migrate_to_current_model() {
+    let content =
+        std::fs::read_to_string(fixture_path("legacy_session.jsonl")).expect("read legacy fixture");
+
+    // Without migration the v1.x entries (snake_case envelope) fail to parse.
+    let mut plain = JsonlParser::new();
+    assert_eq!(plain.parse_str(&content).expect("lenient parse").len(), 3);
+    assert_eq!(plain.stats().lines_skipped, 2);
+
+    let mut parser = JsonlParser::new().with_migration(true);
+    let entries = parser.parse_str(&content).expect("migrating parse");
+    assert_eq!(entries.len(), 5, "every legacy entry should parse");
+    assert_eq!(parser.stats().entries_migrated, 3);
+    assert_eq!(entries[0].session_id(), Some("legacy-session-001"));
+    assert_eq!(entries[1].parent_uuid(), entries[0].uuid());
+
+    let LogEntry::Assistant(v1) = &entries[1] else {
+        panic!("expected the v1 assistant entry");
+    };
+    assert_eq!(v1.message.model, "claude-3-7-sonnet-20250219");
+    assert_eq!(v1.message.stop_reason, Some(StopReason::ToolUse));
+    assert_eq!(entries[1].usage().map(|u| u.output_tokens), Some(20));
+
+    // Pre-v2.0.64 background-task tools come out under their current names.
+    let tools: Vec<String> = all_content_blocks(&entries)
+        .into_iter()
+        .filter_map(|block| match block {
+            ContentBlock::ToolUse(ToolUse { name, input, .. }) => {
+                let params: Vec<&str> = input
+                    .as_object()
+                    .map(|input| input.keys().map(String::as_str).collect())
+                    .unwrap_or_default();
+                Some(format!("{name}({})", params.join(",")))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        tools,
+        [
+            "Bash(command,run_in_background)",
+            "TaskOutput(task_id)",
+            "KillShell(shell_id)",
+            "TaskOutput(task_id)"
+        ]
+    );
+}
+
 /// Regression guard for issue 0018 (fixed): a `thinking` block missing the
 /// `signature` field must still parse (preserving the reasoning text and its
 /// entry) rather than failing deserialization and silently dropping the whole